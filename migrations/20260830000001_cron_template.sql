@@ -0,0 +1,3 @@
+-- Add optional outbound template reference to cron_jobs.
+
+ALTER TABLE cron_jobs ADD COLUMN template TEXT;
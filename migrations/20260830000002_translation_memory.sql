@@ -0,0 +1,13 @@
+-- Translation memory: cached translations and canned responses keyed by
+-- content hash and language pair so repeated phrases skip the LLM.
+CREATE TABLE IF NOT EXISTS translation_memory (
+    content_hash TEXT NOT NULL,
+    source_lang TEXT NOT NULL,
+    target_lang TEXT NOT NULL,
+    source_text TEXT NOT NULL,
+    translated_text TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    last_used_at INTEGER NOT NULL,
+    hits INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (content_hash, source_lang, target_lang)
+);
@@ -0,0 +1,8 @@
+-- Tracks which senders have been onboarded (or opted out) per binding.
+CREATE TABLE IF NOT EXISTS onboarded_users (
+    binding_key TEXT NOT NULL,
+    sender_id TEXT NOT NULL,
+    completed_at INTEGER NOT NULL,
+    opted_out INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (binding_key, sender_id)
+);
@@ -0,0 +1,8 @@
+-- Per-sender privacy consent preferences; absent rows mean full consent.
+CREATE TABLE IF NOT EXISTS user_consent (
+    sender_id TEXT PRIMARY KEY,
+    history_retention INTEGER NOT NULL DEFAULT 1,
+    llm_processing INTEGER NOT NULL DEFAULT 1,
+    analytics INTEGER NOT NULL DEFAULT 1,
+    updated_at INTEGER NOT NULL
+);
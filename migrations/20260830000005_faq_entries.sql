@@ -0,0 +1,13 @@
+-- Curated FAQ entries with question embeddings: inbound questions that
+-- closely match an entry are answered from the store before invoking the
+-- model.
+CREATE TABLE IF NOT EXISTS faq_entries (
+    id TEXT PRIMARY KEY,
+    question TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    source_conversation_id TEXT,
+    embedding BLOB NOT NULL,
+    created_at INTEGER NOT NULL,
+    last_used_at INTEGER NOT NULL,
+    hits INTEGER NOT NULL DEFAULT 0
+);
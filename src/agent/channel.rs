@@ -35,6 +35,12 @@ const RETRIGGER_DEBOUNCE_MS: u64 = 500;
 /// infinite retrigger cascades where each retrigger spawns more work.
 const MAX_RETRIGGERS_PER_TURN: usize = 3;
 
+/// Upcoming cron runs listed in channel time context.
+const TIME_CONTEXT_UPCOMING_RUNS: usize = 3;
+
+/// Recent cron execution summaries listed in channel time context.
+const TIME_CONTEXT_RECENT_OUTPUTS: i64 = 3;
+
 #[derive(Debug, Clone)]
 enum TemporalTimezone {
     Named { timezone_name: String, timezone: Tz },
@@ -144,6 +150,20 @@ impl TemporalContext {
     }
 }
 
+/// First line of `text`, clipped so cron prompts and outputs each stay a
+/// single bulletin line.
+fn truncate_context_line(text: &str) -> String {
+    const MAX_CHARS: usize = 140;
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= MAX_CHARS {
+        line.to_string()
+    } else {
+        let mut clipped: String = line.chars().take(MAX_CHARS - 1).collect();
+        clipped.push('\u{2026}');
+        clipped
+    }
+}
+
 fn build_worker_task_with_temporal_context(
     task: &str,
     temporal_context: &TemporalContext,
@@ -171,6 +191,40 @@ struct PendingResult {
     success: bool,
 }
 
+/// A temporary routing override forcing this channel onto a specific model.
+///
+/// Set via the `/model` chat command or the channels API, and expires on its
+/// own so an escalated thread drifts back to the configured routing.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ModelOverride {
+    pub model: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Where the override came from: "chat" or "api".
+    pub set_by: String,
+}
+
+/// An operator- or agent-pinned piece of context injected into every system
+/// prompt for this channel until unpinned.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PinnedContextItem {
+    pub id: String,
+    pub content: String,
+    /// Who pinned it: "operator" (API) or "agent" (tool).
+    pub source: String,
+    pub pinned_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PinnedContextItem {
+    pub fn new(content: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            content: content.into(),
+            source: source.into(),
+            pinned_at: chrono::Utc::now(),
+        }
+    }
+}
+
 /// Shared state that channel tools need to act on the channel.
 ///
 /// Wrapped in Arc and passed to tools (branch, spawn_worker, route, cancel)
@@ -195,6 +249,19 @@ pub struct ChannelState {
     pub channel_store: ChannelStore,
     pub screenshot_dir: std::path::PathBuf,
     pub logs_dir: std::path::PathBuf,
+    /// Context items pinned into every system prompt for this channel.
+    pub pinned_context: Arc<RwLock<Vec<PinnedContextItem>>>,
+    /// Pinned knowledge for the binding that routed this conversation. Reads
+    /// through to the instance-level store, so chat and API updates apply to
+    /// live channels. None when no binding matched.
+    pub binding_pins: Arc<RwLock<Option<crate::binding_pins::ChannelBindingPins>>>,
+    /// The most recently assembled system prompt, kept for API inspection.
+    pub last_system_prompt: Arc<RwLock<String>>,
+    /// Active per-conversation model override, if any.
+    pub model_override: Arc<RwLock<Option<ModelOverride>>>,
+    /// Provider regions allowed to receive this channel's data, from the
+    /// binding that routed the conversation. Empty means no restriction.
+    pub allowed_regions: Arc<RwLock<Vec<String>>>,
 }
 
 impl ChannelState {
@@ -345,6 +412,11 @@ impl Channel {
             channel_store: channel_store.clone(),
             screenshot_dir,
             logs_dir,
+            pinned_context: Arc::new(RwLock::new(Vec::new())),
+            binding_pins: Arc::new(RwLock::new(None)),
+            last_system_prompt: Arc::new(RwLock::new(String::new())),
+            model_override: Arc::new(RwLock::new(None)),
+            allowed_regions: Arc::new(RwLock::new(Vec::new())),
         };
 
         // Each channel gets its own isolated tool server to avoid races between
@@ -760,9 +832,15 @@ impl Channel {
         );
 
         // Build system prompt with coalesce hint
+        let prompt_build_started = std::time::Instant::now();
         let system_prompt = self
             .build_system_prompt_with_coalesce(message_count, elapsed_secs, unique_sender_count)
             .await?;
+        crate::perf::PerfRecorder::global().record(
+            crate::perf::PerfStage::PromptBuild,
+            prompt_build_started.elapsed(),
+        );
+        *self.state.last_system_prompt.write().await = system_prompt.clone();
 
         {
             let mut reply_target = self.state.reply_target_message_id.write().await;
@@ -780,8 +858,13 @@ impl Channel {
             )
             .await?;
 
+        let format_started = std::time::Instant::now();
         self.handle_agent_result(result, &skip_flag, &replied_flag, false)
             .await;
+        crate::perf::PerfRecorder::global().record(
+            crate::perf::PerfStage::Format,
+            format_started.elapsed(),
+        );
         // Check compaction
         if let Err(error) = self.compactor.check_and_compact().await {
             tracing::warn!(channel_id = %self.id, %error, "compaction check failed");
@@ -819,10 +902,10 @@ impl Channel {
         )?;
 
         let temporal_context = TemporalContext::from_runtime(rc.as_ref());
-        let current_time_line = temporal_context.current_time_line();
+        let time_context = self.render_time_context(&temporal_context).await;
         let status_text = {
             let status = self.state.status_block.read().await;
-            status.render_with_time_context(Some(&current_time_line))
+            status.render_with_time_context(time_context.as_deref())
         };
 
         // Render coalesce hint
@@ -839,6 +922,8 @@ impl Channel {
             .current_adapter()
             .and_then(|adapter| prompt_engine.render_channel_adapter_prompt(adapter));
 
+        let pinned_context = self.render_pinned_context().await;
+
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
 
         prompt_engine.render_channel_prompt_with_links(
@@ -852,6 +937,7 @@ impl Channel {
             available_channels,
             org_context,
             adapter_prompt,
+            pinned_context,
         )
     }
 
@@ -877,6 +963,52 @@ impl Channel {
             self.source_adapter = Some(message.source.clone());
         }
 
+        // Handle the /model override command without spending an LLM turn
+        if message.source != "system"
+            && let crate::MessageContent::Text(text) = &message.content
+            && let Some(rest) = text.trim().strip_prefix("/model")
+            && (rest.is_empty() || rest.starts_with(' '))
+        {
+            let reply = self.handle_model_command(rest.trim()).await;
+            let _ = self.response_tx.send(OutboundResponse::Text(reply)).await;
+            return Ok(());
+        }
+
+        // Handle the /consent privacy command without spending an LLM turn
+        if message.source != "system"
+            && let crate::MessageContent::Text(text) = &message.content
+            && let Some(rest) = text.trim().strip_prefix("/consent")
+            && (rest.is_empty() || rest.starts_with(' '))
+        {
+            let reply = self
+                .handle_consent_command(&message.sender_id, rest.trim())
+                .await;
+            let _ = self.response_tx.send(OutboundResponse::Text(reply)).await;
+            return Ok(());
+        }
+
+        // Load the sender's privacy preferences once per message
+        let consent = if message.source == "system" {
+            crate::consent::ConsentRecord::default()
+        } else {
+            crate::consent::ConsentStore::new(self.deps.sqlite_pool.clone())
+                .get(&message.sender_id)
+                .await
+                .unwrap_or_default()
+        };
+
+        if !consent.llm_processing {
+            let _ = self
+                .response_tx
+                .send(OutboundResponse::Text(
+                    "You've opted out of LLM processing, so this message wasn't handled. \
+                     Send `/consent llm on` to opt back in."
+                        .to_string(),
+                ))
+                .await;
+            return Ok(());
+        }
+
         let (raw_text, attachments) = match &message.content {
             crate::MessageContent::Text(text) => (text.clone(), Vec::new()),
             crate::MessageContent::Media { text, attachments } => {
@@ -896,8 +1028,8 @@ impl Channel {
             Vec::new()
         };
 
-        // Persist user messages (skip system re-triggers)
-        if message.source != "system" {
+        // Persist user messages (skip system re-triggers and retention opt-outs)
+        if message.source != "system" && consent.history_retention {
             let sender_name = message
                 .metadata
                 .get("sender_display_name")
@@ -945,7 +1077,13 @@ impl Channel {
             )?);
         }
 
+        let prompt_build_started = std::time::Instant::now();
         let system_prompt = self.build_system_prompt().await?;
+        crate::perf::PerfRecorder::global().record(
+            crate::perf::PerfStage::PromptBuild,
+            prompt_build_started.elapsed(),
+        );
+        *self.state.last_system_prompt.write().await = system_prompt.clone();
 
         {
             let mut reply_target = self.state.reply_target_message_id.write().await;
@@ -964,8 +1102,13 @@ impl Channel {
             )
             .await?;
 
+        let format_started = std::time::Instant::now();
         self.handle_agent_result(result, &skip_flag, &replied_flag, is_retrigger)
             .await;
+        crate::perf::PerfRecorder::global().record(
+            crate::perf::PerfStage::Format,
+            format_started.elapsed(),
+        );
 
         // After a successful retrigger relay, inject a compact record into
         // history so the conversation has context about what was relayed.
@@ -1121,10 +1264,10 @@ impl Channel {
         )?;
 
         let temporal_context = TemporalContext::from_runtime(rc.as_ref());
-        let current_time_line = temporal_context.current_time_line();
+        let time_context = self.render_time_context(&temporal_context).await;
         let status_text = {
             let status = self.state.status_block.read().await;
-            status.render_with_time_context(Some(&current_time_line))
+            status.render_with_time_context(time_context.as_deref())
         };
 
         let available_channels = self.build_available_channels().await;
@@ -1135,6 +1278,8 @@ impl Channel {
             .current_adapter()
             .and_then(|adapter| prompt_engine.render_channel_adapter_prompt(adapter));
 
+        let pinned_context = self.render_pinned_context().await;
+
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
 
         prompt_engine.render_channel_prompt_with_links(
@@ -1148,9 +1293,218 @@ impl Channel {
             available_channels,
             org_context,
             adapter_prompt,
+            pinned_context,
         )
     }
 
+    /// The overriding model for this channel, clearing it once expired.
+    async fn active_model_override(&self) -> Option<String> {
+        let expired = {
+            let guard = self.state.model_override.read().await;
+            match guard.as_ref() {
+                Some(o) if o.expires_at > chrono::Utc::now() => return Some(o.model.clone()),
+                Some(_) => true,
+                None => false,
+            }
+        };
+        if expired {
+            let mut guard = self.state.model_override.write().await;
+            if let Some(o) = guard.take() {
+                tracing::info!(channel_id = %self.id, model = %o.model, "model override expired");
+            }
+        }
+        None
+    }
+
+    /// Handle the `/model` chat command: set, clear, or show the override.
+    async fn handle_model_command(&self, args: &str) -> String {
+        match args {
+            "" => match self.active_model_override().await {
+                Some(model) => {
+                    let guard = self.state.model_override.read().await;
+                    let remaining = guard
+                        .as_ref()
+                        .map(|o| (o.expires_at - chrono::Utc::now()).num_minutes().max(0))
+                        .unwrap_or(0);
+                    format!("Model override: {model} ({remaining}m remaining). Use `/model clear` to remove it.")
+                }
+                None => {
+                    let routing = self.deps.runtime_config.routing.load();
+                    let model = routing.resolve(ProcessType::Channel, None).to_string();
+                    format!("No override active — routing resolves to {model}. Use `/model <name> [ttl]` to set one.")
+                }
+            },
+            "clear" | "off" | "reset" => {
+                *self.state.model_override.write().await = None;
+                "Model override cleared.".to_string()
+            }
+            _ => {
+                let mut parts = args.split_whitespace();
+                let model = parts.next().unwrap_or_default().to_string();
+                let ttl = match parts.next() {
+                    Some(raw) => match parse_override_ttl(raw) {
+                        Some(ttl) => ttl,
+                        None => {
+                            return format!(
+                                "Couldn't parse TTL '{raw}' — use forms like 30m, 2h, or 900s."
+                            );
+                        }
+                    },
+                    None => chrono::Duration::hours(1),
+                };
+
+                if let Err(error) = self.deps.llm_manager.resolve_model(&model) {
+                    return format!("Unknown model '{model}': {error}");
+                }
+
+                let expires_at = chrono::Utc::now() + ttl;
+                *self.state.model_override.write().await = Some(ModelOverride {
+                    model: model.clone(),
+                    expires_at,
+                    set_by: "chat".to_string(),
+                });
+                tracing::info!(
+                    channel_id = %self.id,
+                    model = %model,
+                    ttl_minutes = ttl.num_minutes(),
+                    "model override set via chat command"
+                );
+                format!(
+                    "Model override set: {model} for {}m. Use `/model clear` to remove it early.",
+                    ttl.num_minutes()
+                )
+            }
+        }
+    }
+
+    /// Handle the `/consent` chat command: show or change the sender's
+    /// privacy preferences.
+    async fn handle_consent_command(&self, sender_id: &str, args: &str) -> String {
+        let store = crate::consent::ConsentStore::new(self.deps.sqlite_pool.clone());
+        let on_off = |allowed: bool| if allowed { "on" } else { "off" };
+
+        if args.is_empty() {
+            let record = store.get(sender_id).await.unwrap_or_default();
+            return format!(
+                "Your privacy preferences:\n\
+                 - history retention: {}\n\
+                 - LLM processing: {}\n\
+                 - analytics: {}\n\
+                 Change one with `/consent <history|llm|analytics> <on|off>`, or `/consent reset`.",
+                on_off(record.history_retention),
+                on_off(record.llm_processing),
+                on_off(record.analytics),
+            );
+        }
+
+        if matches!(args, "reset" | "clear") {
+            return match store.reset(sender_id).await {
+                Ok(()) => "Privacy preferences reset to defaults (everything on).".to_string(),
+                Err(error) => format!("Couldn't reset preferences: {error}"),
+            };
+        }
+
+        let mut parts = args.split_whitespace();
+        let preference = parts.next().unwrap_or_default();
+        let Some(value) = parts.next().and_then(crate::consent::parse_toggle) else {
+            return "Usage: `/consent <history|llm|analytics> <on|off>` or `/consent reset`."
+                .to_string();
+        };
+
+        let mut record = store.get(sender_id).await.unwrap_or_default();
+        match preference {
+            "history" => record.history_retention = value,
+            "llm" => record.llm_processing = value,
+            "analytics" => record.analytics = value,
+            other => return format!("Unknown preference '{other}' — use history, llm, or analytics."),
+        }
+
+        match store.set(sender_id, record).await {
+            Ok(()) => {
+                tracing::info!(
+                    channel_id = %self.id,
+                    sender_id,
+                    preference,
+                    value,
+                    "consent preference changed via chat command"
+                );
+                format!("Set {preference} to {}.", on_off(value))
+            }
+            Err(error) => format!("Couldn't save preference: {error}"),
+        }
+    }
+
+    /// Render pinned context items as a bulleted list, or `None` when nothing
+    /// is pinned.
+    async fn render_pinned_context(&self) -> Option<String> {
+        let mut lines: Vec<String> = self
+            .state
+            .pinned_context
+            .read()
+            .await
+            .iter()
+            .map(|item| format!("- [{}] {}", item.id, item.content))
+            .collect();
+        if let Some(binding_pins) = self.state.binding_pins.read().await.as_ref() {
+            lines.extend(binding_pins.lines().await);
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        Some(lines.join("\n"))
+    }
+
+    /// Time context for the system prompt: current date/time in the
+    /// conversation's timezone plus upcoming cron runs and recent cron
+    /// output, so the agent answers "what day is it" and schedule questions
+    /// without tool calls. Disabled per agent via `defaults.time_context`.
+    async fn render_time_context(&self, temporal_context: &TemporalContext) -> Option<String> {
+        if !**self.deps.runtime_config.time_context.load() {
+            return None;
+        }
+
+        let mut lines = vec![temporal_context.current_time_line()];
+        if let Some(cron_tool) = &self.deps.cron_tool {
+            let (upcoming, recent) = cron_tool
+                .time_context(TIME_CONTEXT_UPCOMING_RUNS, TIME_CONTEXT_RECENT_OUTPUTS)
+                .await;
+
+            if !upcoming.is_empty() {
+                lines.push("Upcoming scheduled jobs:".to_string());
+                for run in upcoming {
+                    let when = match run.next_fire_utc {
+                        Some(at) => format!("next: {}", temporal_context.format_timestamp(at)),
+                        None => format!("every {}s", run.interval_secs),
+                    };
+                    lines.push(format!(
+                        "- [{}] {} ({when})",
+                        run.id,
+                        truncate_context_line(&run.prompt)
+                    ));
+                }
+            }
+
+            let recent_lines: Vec<String> = recent
+                .into_iter()
+                .filter_map(|entry| {
+                    let summary = entry.result_summary?;
+                    let marker = if entry.success { "" } else { " (failed)" };
+                    Some(format!(
+                        "- {}{marker}: {}",
+                        entry.executed_at,
+                        truncate_context_line(&summary)
+                    ))
+                })
+                .collect();
+            if !recent_lines.is_empty() {
+                lines.push("Recent scheduled job output:".to_string());
+                lines.extend(recent_lines);
+            }
+        }
+
+        Some(lines.join("\n"))
+    }
+
     /// Register per-turn tools, run the LLM agentic loop, and clean up.
     ///
     /// Returns the prompt result and skip flag for the caller to dispatch.
@@ -1192,8 +1546,31 @@ impl Channel {
         let rc = &self.deps.runtime_config;
         let routing = rc.routing.load();
         let max_turns = **rc.max_turns.load();
-        let model_name = routing.resolve(ProcessType::Channel, None);
-        let model = SpacebotModel::make(&self.deps.llm_manager, model_name)
+        let model_name = match self.active_model_override().await {
+            Some(model) => model,
+            None => routing.resolve(ProcessType::Channel, None).to_string(),
+        };
+
+        // Data-residency enforcement: refuse the turn rather than route the
+        // conversation through a non-compliant provider
+        {
+            let allowed_regions = self.state.allowed_regions.read().await;
+            if let Err(error) = self
+                .deps
+                .llm_manager
+                .check_region(&model_name, &allowed_regions)
+            {
+                let _ = self
+                    .response_tx
+                    .send(OutboundResponse::Text(format!(
+                        "This conversation can't be processed: {error}"
+                    )))
+                    .await;
+                return Err(error);
+            }
+        }
+
+        let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "channel")
             .with_routing((**routing).clone());
 
@@ -1227,6 +1604,7 @@ impl Channel {
         };
         let history_len_before = history.len();
 
+        let llm_started = std::time::Instant::now();
         let mut result = agent
             .prompt(user_text)
             .with_history(&mut history)
@@ -1261,6 +1639,9 @@ impl Channel {
                 .await;
         }
 
+        crate::perf::PerfRecorder::global()
+            .record(crate::perf::PerfStage::Llm, llm_started.elapsed());
+
         {
             let mut guard = self.state.history.write().await;
             apply_history_after_turn(
@@ -1991,6 +2372,7 @@ async fn spawn_branch(
         state.conversation_logger.clone(),
         state.channel_store.clone(),
         crate::conversation::ProcessRunLogger::new(state.deps.sqlite_pool.clone()),
+        state.deps.sqlite_pool.clone(),
     );
     let branch_max_turns = **state.deps.runtime_config.branch_max_turns.load();
 
@@ -2397,6 +2779,27 @@ fn extract_reply_from_tool_syntax(text: &str) -> Option<String> {
     None
 }
 
+/// Parse a model-override TTL like `30m`, `2h`, or `900s` (bare numbers are
+/// seconds). Returns `None` for anything unparseable or non-positive.
+fn parse_override_ttl(raw: &str) -> Option<chrono::Duration> {
+    let raw = raw.trim();
+    let (value, unit) = match raw.char_indices().last() {
+        Some((idx, c)) if c.is_ascii_alphabetic() => (&raw[..idx], c.to_ascii_lowercase()),
+        _ => (raw, 's'),
+    };
+    let value: i64 = value.parse().ok()?;
+    if value <= 0 {
+        return None;
+    }
+    match unit {
+        's' => Some(chrono::Duration::seconds(value)),
+        'm' => Some(chrono::Duration::minutes(value)),
+        'h' => Some(chrono::Duration::hours(value)),
+        'd' => Some(chrono::Duration::days(value)),
+        _ => None,
+    }
+}
+
 /// Format a user message with sender attribution from message metadata.
 ///
 /// In multi-user channels, this lets the LLM distinguish who said what.
@@ -2573,6 +2976,41 @@ async fn download_attachments(
     parts
 }
 
+/// Ceiling on inbound attachment downloads. Bodies are streamed chunk by
+/// chunk and the transfer is aborted as soon as the running total crosses
+/// this, so an oversized (or lying) server can't balloon memory.
+const MAX_ATTACHMENT_DOWNLOAD_BYTES: usize = 50 * 1024 * 1024;
+
+/// Stream a response body into memory, enforcing
+/// [`MAX_ATTACHMENT_DOWNLOAD_BYTES`] both up front (via `Content-Length`)
+/// and as chunks arrive.
+async fn collect_body_limited(
+    response: reqwest::Response,
+) -> std::result::Result<bytes::Bytes, String> {
+    use futures::StreamExt as _;
+
+    if let Some(length) = response.content_length()
+        && length > MAX_ATTACHMENT_DOWNLOAD_BYTES as u64
+    {
+        return Err(format!(
+            "attachment is too large ({length} bytes, max {MAX_ATTACHMENT_DOWNLOAD_BYTES})"
+        ));
+    }
+
+    let mut body = bytes::BytesMut::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        if body.len() + chunk.len() > MAX_ATTACHMENT_DOWNLOAD_BYTES {
+            return Err(format!(
+                "attachment is too large (exceeds {MAX_ATTACHMENT_DOWNLOAD_BYTES} bytes)"
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
 /// Download raw bytes from an attachment URL, including auth if present.
 ///
 /// When `auth_header` is set (Slack), uses a no-redirect client and manually
@@ -2581,7 +3019,7 @@ async fn download_attachments(
 async fn download_attachment_bytes(
     http: &reqwest::Client,
     attachment: &crate::Attachment,
-) -> std::result::Result<Vec<u8>, String> {
+) -> std::result::Result<bytes::Bytes, String> {
     if attachment.auth_header.is_some() {
         download_attachment_bytes_with_auth(attachment).await
     } else {
@@ -2593,11 +3031,7 @@ async fn download_attachment_bytes(
         if !response.status().is_success() {
             return Err(format!("HTTP {}", response.status()));
         }
-        response
-            .bytes()
-            .await
-            .map(|b| b.to_vec())
-            .map_err(|e| e.to_string())
+        collect_body_limited(response).await
     }
 }
 
@@ -2606,8 +3040,8 @@ async fn download_attachment_bytes(
 /// original URL. This prevents credential leakage on cross-origin redirects.
 async fn download_attachment_bytes_with_auth(
     attachment: &crate::Attachment,
-) -> std::result::Result<Vec<u8>, String> {
-    let client = reqwest::Client::builder()
+) -> std::result::Result<bytes::Bytes, String> {
+    let client = crate::http::builder()
         .redirect(reqwest::redirect::Policy::none())
         .timeout(std::time::Duration::from_secs(60))
         .build()
@@ -2650,11 +3084,7 @@ async fn download_attachment_bytes_with_auth(
             return Err(format!("HTTP {}", status));
         }
 
-        return response
-            .bytes()
-            .await
-            .map(|b| b.to_vec())
-            .map_err(|e| e.to_string());
+        return collect_body_limited(response).await;
     }
 
     Err("too many redirects".into())
@@ -3563,6 +3993,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn time_context_lines_clip_to_the_first_line() {
+        use super::truncate_context_line;
+
+        assert_eq!(truncate_context_line("  short prompt  "), "short prompt");
+        assert_eq!(
+            truncate_context_line("first line\nsecond line"),
+            "first line"
+        );
+
+        let long = "x".repeat(200);
+        let clipped = truncate_context_line(&long);
+        assert_eq!(clipped.chars().count(), 140);
+        assert!(clipped.ends_with('\u{2026}'));
+    }
+
     #[test]
     fn temporal_context_uses_cron_timezone_when_user_timezone_is_invalid() {
         let resolved = super::TemporalContext::resolve_timezone_from_names(
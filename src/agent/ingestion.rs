@@ -480,6 +480,7 @@ async fn process_chunk(
         conversation_logger,
         channel_store,
         crate::conversation::ProcessRunLogger::new(deps.sqlite_pool.clone()),
+        deps.sqlite_pool.clone(),
     );
 
     let agent = AgentBuilder::new(model)
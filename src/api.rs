@@ -8,21 +8,31 @@ mod agents;
 mod bindings;
 mod channels;
 mod config;
+mod consent;
 mod cortex;
 mod cron;
+mod faq;
+mod flags;
+mod forks;
+mod hooks;
 mod ingest;
 mod links;
+mod maintenance;
 mod mcp;
 mod memories;
 mod messaging;
 mod models;
+mod perf;
+mod privacy;
 mod providers;
+mod quarantine;
 mod server;
 mod settings;
 mod skills;
 mod state;
 mod system;
 mod tasks;
+mod templates;
 mod webchat;
 mod workers;
 
@@ -715,6 +715,13 @@ pub(super) async fn create_agent(
     let event_rx = event_tx.subscribe();
     state.register_agent_events(agent_id.clone(), event_rx);
 
+    let template_store = std::sync::Arc::new(
+        crate::templates::TemplateStore::new(instance_dir.join("templates")).map_err(|error| {
+            tracing::error!(%error, "failed to open template store");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?,
+    );
+
     let cron_store = std::sync::Arc::new(crate::cron::CronStore::new(db.sqlite.clone()));
     let cron_context = crate::cron::CronContext {
         deps: deps.clone(),
@@ -728,6 +735,7 @@ pub(super) async fn create_agent(
                 .unwrap_or_else(|| std::sync::Arc::new(crate::messaging::MessagingManager::new()))
         },
         store: cron_store.clone(),
+        templates: template_store,
     };
     let scheduler = std::sync::Arc::new(crate::cron::Scheduler::new(cron_context));
     runtime_config.set_cron(cron_store.clone(), scheduler.clone());
@@ -494,6 +494,11 @@ pub(super) async fn create_binding(
 
         let manager_guard = state.messaging_manager.read().await;
         if let Some(manager) = manager_guard.as_ref() {
+            #[cfg(not(feature = "adapter-discord"))]
+            if new_discord_token.is_some() {
+                tracing::warn!("discord credentials saved but this binary was built without the adapter-discord feature");
+            }
+            #[cfg(feature = "adapter-discord")]
             if let Some(token) = new_discord_token {
                 let discord_perms = {
                     let perms_guard = state.discord_permissions.read().await;
@@ -522,12 +527,22 @@ pub(super) async fn create_binding(
                     "discord",
                     &token,
                     discord_perms,
+                    new_config
+                        .messaging
+                        .discord
+                        .as_ref()
+                        .and_then(|discord| discord.proxy.clone()),
                 );
                 if let Err(error) = manager.register_and_start(adapter).await {
                     tracing::error!(%error, "failed to hot-start discord adapter");
                 }
             }
 
+            #[cfg(not(feature = "adapter-slack"))]
+            if new_slack_tokens.is_some() {
+                tracing::warn!("slack credentials saved but this binary was built without the adapter-slack feature");
+            }
+            #[cfg(feature = "adapter-slack")]
             if let Some((bot_token, app_token)) = new_slack_tokens {
                 let slack_perms = {
                     let perms_guard = state.slack_permissions.read().await;
@@ -576,6 +591,11 @@ pub(super) async fn create_binding(
                 }
             }
 
+            #[cfg(not(feature = "adapter-telegram"))]
+            if new_telegram_token.is_some() {
+                tracing::warn!("telegram credentials saved but this binary was built without the adapter-telegram feature");
+            }
+            #[cfg(feature = "adapter-telegram")]
             if let Some(token) = new_telegram_token {
                 let telegram_perms = {
                     let Some(telegram_config) = new_config.messaging.telegram.as_ref() else {
@@ -592,12 +612,23 @@ pub(super) async fn create_binding(
                     "telegram",
                     &token,
                     telegram_perms,
+                    Some(config_path.clone()),
+                    new_config
+                        .messaging
+                        .telegram
+                        .as_ref()
+                        .and_then(|telegram| telegram.proxy.clone()),
                 );
                 if let Err(error) = manager.register_and_start(adapter).await {
                     tracing::error!(%error, "failed to hot-start telegram adapter");
                 }
             }
 
+            #[cfg(not(feature = "adapter-email"))]
+            if new_email_configured {
+                tracing::warn!("email credentials saved but this binary was built without the adapter-email feature");
+            }
+            #[cfg(feature = "adapter-email")]
             if new_email_configured {
                 let Some(email_config) = new_config.messaging.email.as_ref() else {
                     tracing::error!("email config missing despite credentials being provided");
@@ -981,3 +1012,324 @@ pub(super) async fn delete_binding(
         message: "Binding deleted.".to_string(),
     }))
 }
+
+#[derive(Deserialize)]
+pub(super) struct RetargetBindingsRequest {
+    channel: String,
+    /// Adapter instance the bindings currently point at; omit to match
+    /// bindings on the platform default.
+    #[serde(default)]
+    from_adapter: Option<String>,
+    /// New adapter instance; omit to retarget onto the platform default.
+    #[serde(default)]
+    to_adapter: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct BulkDeleteBindingsRequest {
+    agent_id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct BulkBindingsResponse {
+    success: bool,
+    affected: usize,
+    message: String,
+}
+
+/// Reload binding-derived state after a config edit: the shared bindings
+/// swap plus the per-platform permission snapshots computed from bindings.
+async fn refresh_binding_state(state: &ApiState, config_path: &std::path::Path) {
+    let Ok(new_config) = crate::config::Config::load_from_path(config_path) else {
+        return;
+    };
+
+    let bindings_guard = state.bindings.read().await;
+    if let Some(bindings_swap) = bindings_guard.as_ref() {
+        bindings_swap.store(std::sync::Arc::new(new_config.bindings.clone()));
+    }
+    drop(bindings_guard);
+
+    if let Some(discord_config) = &new_config.messaging.discord {
+        let new_perms =
+            crate::config::DiscordPermissions::from_config(discord_config, &new_config.bindings);
+        let perms = state.discord_permissions.read().await;
+        if let Some(arc_swap) = perms.as_ref() {
+            arc_swap.store(std::sync::Arc::new(new_perms));
+        }
+    }
+
+    if let Some(slack_config) = &new_config.messaging.slack {
+        let new_perms =
+            crate::config::SlackPermissions::from_config(slack_config, &new_config.bindings);
+        let perms = state.slack_permissions.read().await;
+        if let Some(arc_swap) = perms.as_ref() {
+            arc_swap.store(std::sync::Arc::new(new_perms));
+        }
+    }
+}
+
+/// Move every binding on a channel from one adapter instance to another in
+/// one edit. All matches are rewritten in memory and written back in a single
+/// save, so a failure part-way never lands on disk.
+pub(super) async fn retarget_bindings(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<RetargetBindingsRequest>,
+) -> Result<Json<BulkBindingsResponse>, StatusCode> {
+    let config_path = state.config_path.read().await.clone();
+    if !config_path.exists() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let content = tokio::fs::read_to_string(&config_path)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut doc: toml_edit::DocumentMut = content.parse().map_err(|error| {
+        tracing::warn!(%error, "failed to parse config.toml");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let bindings_array = doc
+        .get_mut("bindings")
+        .and_then(|b| b.as_array_of_tables_mut())
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut affected = 0;
+    for table in bindings_array.iter_mut() {
+        let matches_channel = table
+            .get("channel")
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| v == request.channel);
+        let matches_adapter = match &request.from_adapter {
+            Some(adapter) => table
+                .get("adapter")
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| v == adapter),
+            None => table.get("adapter").is_none(),
+        };
+        if !matches_channel || !matches_adapter {
+            continue;
+        }
+
+        match &request.to_adapter {
+            Some(adapter) => {
+                table["adapter"] = toml_edit::value(adapter.clone());
+            }
+            None => {
+                table.remove("adapter");
+            }
+        }
+        affected += 1;
+    }
+
+    if affected == 0 {
+        return Ok(Json(BulkBindingsResponse {
+            success: false,
+            affected: 0,
+            message: "No matching bindings found.".to_string(),
+        }));
+    }
+
+    tokio::fs::write(&config_path, doc.to_string())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to write config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        channel = %request.channel,
+        from_adapter = ?request.from_adapter,
+        to_adapter = ?request.to_adapter,
+        affected,
+        "bindings retargeted via API"
+    );
+
+    refresh_binding_state(&state, &config_path).await;
+
+    Ok(Json(BulkBindingsResponse {
+        success: true,
+        affected,
+        message: format!("{affected} bindings retargeted."),
+    }))
+}
+
+/// Delete every binding for one agent in a single config edit.
+pub(super) async fn bulk_delete_bindings(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<BulkDeleteBindingsRequest>,
+) -> Result<Json<BulkBindingsResponse>, StatusCode> {
+    let config_path = state.config_path.read().await.clone();
+    if !config_path.exists() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let content = tokio::fs::read_to_string(&config_path)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to read config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut doc: toml_edit::DocumentMut = content.parse().map_err(|error| {
+        tracing::warn!(%error, "failed to parse config.toml");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let bindings_array = doc
+        .get_mut("bindings")
+        .and_then(|b| b.as_array_of_tables_mut())
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut affected = 0;
+    let mut index = 0;
+    while index < bindings_array.len() {
+        let matches = bindings_array
+            .get(index)
+            .and_then(|t| t.get("agent_id"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| v == request.agent_id);
+        if matches {
+            bindings_array.remove(index);
+            affected += 1;
+        } else {
+            index += 1;
+        }
+    }
+
+    if affected == 0 {
+        return Ok(Json(BulkBindingsResponse {
+            success: false,
+            affected: 0,
+            message: "No matching bindings found.".to_string(),
+        }));
+    }
+
+    tokio::fs::write(&config_path, doc.to_string())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to write config.toml");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        affected,
+        "agent bindings bulk-deleted via API"
+    );
+
+    refresh_binding_state(&state, &config_path).await;
+
+    Ok(Json(BulkBindingsResponse {
+        success: true,
+        affected,
+        message: format!("{affected} bindings deleted."),
+    }))
+}
+
+#[derive(Deserialize)]
+pub(super) struct BindingPinsQuery {
+    /// Binding key (`{adapter_key}/{agent_id}`); omit to list all bindings.
+    #[serde(default)]
+    binding: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct BindingPinsListResponse {
+    /// Pins grouped by binding key.
+    pins: std::collections::HashMap<String, Vec<crate::binding_pins::BindingPin>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PinBindingRequest {
+    /// Binding key (`{adapter_key}/{agent_id}`).
+    binding: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct UnpinBindingRequest {
+    binding: String,
+    id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct PinBindingResponse {
+    success: bool,
+    /// The pinned item's ID (present after a pin).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+/// List knowledge pinned to bindings, optionally for a single binding key.
+pub(super) async fn list_binding_pins(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<BindingPinsQuery>,
+) -> Result<Json<BindingPinsListResponse>, StatusCode> {
+    let store_guard = state.binding_pins.read().await;
+    let store = store_guard
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let pins = match query.binding {
+        Some(binding) => {
+            let entries = store.pins_for(&binding).await;
+            std::iter::once((binding, entries)).collect()
+        }
+        None => store.all().await,
+    };
+    drop(store_guard);
+
+    Ok(Json(BindingPinsListResponse { pins }))
+}
+
+/// Pin a piece of knowledge to a binding.
+pub(super) async fn pin_binding_knowledge(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<PinBindingRequest>,
+) -> Result<Json<PinBindingResponse>, StatusCode> {
+    let content = request.content.trim();
+    if content.is_empty() || request.binding.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let store_guard = state.binding_pins.read().await;
+    let store = store_guard
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let pin = store.add(&request.binding, content, "api").await;
+    drop(store_guard);
+
+    tracing::info!(binding = %request.binding, pin_id = %pin.id, "binding knowledge pinned via API");
+    Ok(Json(PinBindingResponse {
+        success: true,
+        id: Some(pin.id),
+    }))
+}
+
+/// Remove a pinned piece of knowledge from a binding.
+pub(super) async fn unpin_binding_knowledge(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<UnpinBindingRequest>,
+) -> Result<Json<PinBindingResponse>, StatusCode> {
+    let store_guard = state.binding_pins.read().await;
+    let store = store_guard
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let removed = store.remove(&request.binding, &request.id).await;
+    drop(store_guard);
+
+    if !removed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(binding = %request.binding, pin_id = %request.id, "binding knowledge unpinned via API");
+    Ok(Json(PinBindingResponse {
+        success: true,
+        id: None,
+    }))
+}
@@ -181,6 +181,279 @@ pub(super) async fn delete_channel(
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+#[derive(Deserialize)]
+pub(super) struct ChannelContextQuery {
+    channel_id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct ContextHistoryEntry {
+    role: String,
+    text: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct ChannelContextResponse {
+    channel_id: String,
+    /// The system prompt assembled for the most recent turn. Empty until the
+    /// channel has handled at least one message.
+    system_prompt: String,
+    /// The memory bulletin currently injected into prompts.
+    memory_bulletin: String,
+    /// Items pinned into every prompt for this channel.
+    pinned: Vec<crate::agent::channel::PinnedContextItem>,
+    /// The in-memory history slice the model sees, as readable text.
+    history: Vec<ContextHistoryEntry>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PinContextRequest {
+    channel_id: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct UnpinContextRequest {
+    channel_id: String,
+    id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct PinContextResponse {
+    success: bool,
+    /// The pinned item's ID (present after a pin).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+/// Render a rig message as role + concatenated text for inspection.
+fn render_history_entry(message: &rig::message::Message) -> ContextHistoryEntry {
+    use rig::message::{AssistantContent, Message, UserContent};
+
+    match message {
+        Message::User { content } => {
+            let mut text = String::new();
+            for item in content.iter() {
+                match item {
+                    UserContent::Text(t) => text.push_str(&t.text),
+                    UserContent::ToolResult(tr) => {
+                        for part in tr.content.iter() {
+                            if let rig::message::ToolResultContent::Text(t) = part {
+                                text.push_str("[tool result] ");
+                                text.push_str(&t.text);
+                            }
+                        }
+                    }
+                    _ => text.push_str("[attachment]"),
+                }
+            }
+            ContextHistoryEntry {
+                role: "user".to_string(),
+                text,
+            }
+        }
+        Message::Assistant { content, .. } => {
+            let mut text = String::new();
+            for item in content.iter() {
+                match item {
+                    AssistantContent::Text(t) => text.push_str(&t.text),
+                    AssistantContent::ToolCall(tc) => {
+                        text.push_str(&format!(
+                            "[tool call: {}({})]",
+                            tc.function.name, tc.function.arguments
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            ContextHistoryEntry {
+                role: "assistant".to_string(),
+                text,
+            }
+        }
+    }
+}
+
+/// Inspect the prompt assembly for a live channel: last system prompt,
+/// memory bulletin, pinned items, and the in-memory history slice.
+pub(super) async fn channel_context(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ChannelContextQuery>,
+) -> Result<Json<ChannelContextResponse>, StatusCode> {
+    let states = state.channel_states.read().await;
+    let channel_state = states.get(&query.channel_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let system_prompt = channel_state.last_system_prompt.read().await.clone();
+    let memory_bulletin = channel_state
+        .deps
+        .runtime_config
+        .memory_bulletin
+        .load()
+        .to_string();
+    let pinned = channel_state.pinned_context.read().await.clone();
+    let history = channel_state
+        .history
+        .read()
+        .await
+        .iter()
+        .map(render_history_entry)
+        .collect();
+
+    Ok(Json(ChannelContextResponse {
+        channel_id: query.channel_id,
+        system_prompt,
+        memory_bulletin,
+        pinned,
+        history,
+    }))
+}
+
+/// Pin a context item into a live channel's system prompt.
+pub(super) async fn pin_channel_context(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<PinContextRequest>,
+) -> Result<Json<PinContextResponse>, StatusCode> {
+    let content = request.content.trim();
+    if content.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let states = state.channel_states.read().await;
+    let channel_state = states
+        .get(&request.channel_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let item = crate::agent::channel::PinnedContextItem::new(content, "operator");
+    let id = item.id.clone();
+    channel_state.pinned_context.write().await.push(item);
+
+    tracing::info!(channel_id = %request.channel_id, pin_id = %id, "context pinned via API");
+    Ok(Json(PinContextResponse {
+        success: true,
+        id: Some(id),
+    }))
+}
+
+/// Unpin a context item from a live channel.
+pub(super) async fn unpin_channel_context(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<UnpinContextRequest>,
+) -> Result<Json<PinContextResponse>, StatusCode> {
+    let states = state.channel_states.read().await;
+    let channel_state = states
+        .get(&request.channel_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut pinned = channel_state.pinned_context.write().await;
+    let before = pinned.len();
+    pinned.retain(|item| item.id != request.id);
+    if pinned.len() == before {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(channel_id = %request.channel_id, pin_id = %request.id, "context unpinned via API");
+    Ok(Json(PinContextResponse {
+        success: true,
+        id: None,
+    }))
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetModelOverrideRequest {
+    channel_id: String,
+    model: String,
+    /// How long the override lasts, in seconds. Defaults to one hour.
+    ttl_secs: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ModelOverrideResponse {
+    channel_id: String,
+    /// The active override, or `None` when the channel follows routing.
+    active: Option<crate::agent::channel::ModelOverride>,
+}
+
+/// Get the active model override for a channel.
+pub(super) async fn channel_model_override(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ChannelContextQuery>,
+) -> Result<Json<ModelOverrideResponse>, StatusCode> {
+    let states = state.channel_states.read().await;
+    let channel_state = states.get(&query.channel_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let active = channel_state
+        .model_override
+        .read()
+        .await
+        .clone()
+        .filter(|o| o.expires_at > chrono::Utc::now());
+
+    Ok(Json(ModelOverrideResponse {
+        channel_id: query.channel_id,
+        active,
+    }))
+}
+
+/// Force a channel onto a specific model for a limited time.
+pub(super) async fn set_channel_model_override(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<SetModelOverrideRequest>,
+) -> Result<Json<ModelOverrideResponse>, StatusCode> {
+    let ttl_secs = request.ttl_secs.unwrap_or(3600);
+    if ttl_secs <= 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let states = state.channel_states.read().await;
+    let channel_state = states
+        .get(&request.channel_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if channel_state
+        .deps
+        .llm_manager
+        .resolve_model(&request.model)
+        .is_err()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let item = crate::agent::channel::ModelOverride {
+        model: request.model,
+        expires_at: chrono::Utc::now() + chrono::Duration::seconds(ttl_secs),
+        set_by: "api".to_string(),
+    };
+    *channel_state.model_override.write().await = Some(item.clone());
+
+    tracing::info!(
+        channel_id = %request.channel_id,
+        model = %item.model,
+        ttl_secs,
+        "model override set via API"
+    );
+    Ok(Json(ModelOverrideResponse {
+        channel_id: request.channel_id,
+        active: Some(item),
+    }))
+}
+
+/// Clear a channel's model override so it follows routing again.
+pub(super) async fn clear_channel_model_override(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ChannelContextQuery>,
+) -> Result<Json<ModelOverrideResponse>, StatusCode> {
+    let states = state.channel_states.read().await;
+    let channel_state = states.get(&query.channel_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    *channel_state.model_override.write().await = None;
+
+    tracing::info!(channel_id = %query.channel_id, "model override cleared via API");
+    Ok(Json(ModelOverrideResponse {
+        channel_id: query.channel_id,
+        active: None,
+    }))
+}
+
 /// Cancel a running worker or branch via the API.
 pub(super) async fn cancel_process(
     State(state): State<Arc<ApiState>>,
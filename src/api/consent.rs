@@ -0,0 +1,118 @@
+use super::state::ApiState;
+
+use crate::consent::{ConsentRecord, ConsentStore};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct ConsentQuery {
+    agent_id: String,
+    sender_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetConsentRequest {
+    agent_id: String,
+    sender_id: String,
+    history_retention: Option<bool>,
+    llm_processing: Option<bool>,
+    analytics: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ConsentResponse {
+    sender_id: String,
+    consent: ConsentRecord,
+}
+
+/// Read a sender's privacy preferences for one agent.
+pub(super) async fn get_consent(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ConsentQuery>,
+) -> Result<Json<ConsentResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let consent = ConsentStore::new(pool.clone())
+        .get(&query.sender_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to load consent record");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ConsentResponse {
+        sender_id: query.sender_id,
+        consent,
+    }))
+}
+
+/// Update a sender's privacy preferences; omitted fields are left unchanged.
+pub(super) async fn set_consent(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<SetConsentRequest>,
+) -> Result<Json<ConsentResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&request.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let store = ConsentStore::new(pool.clone());
+
+    let mut consent = store.get(&request.sender_id).await.map_err(|error| {
+        tracing::error!(%error, "failed to load consent record");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Some(value) = request.history_retention {
+        consent.history_retention = value;
+    }
+    if let Some(value) = request.llm_processing {
+        consent.llm_processing = value;
+    }
+    if let Some(value) = request.analytics {
+        consent.analytics = value;
+    }
+
+    store
+        .set(&request.sender_id, consent)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to store consent record");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        sender_id = %request.sender_id,
+        "consent preferences updated via API"
+    );
+
+    Ok(Json(ConsentResponse {
+        sender_id: request.sender_id,
+        consent,
+    }))
+}
+
+/// Reset a sender's preferences to the full-consent defaults.
+pub(super) async fn reset_consent(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ConsentQuery>,
+) -> Result<Json<ConsentResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    ConsentStore::new(pool.clone())
+        .reset(&query.sender_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to reset consent record");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ConsentResponse {
+        sender_id: query.sender_id,
+        consent: ConsentRecord::default(),
+    }))
+}
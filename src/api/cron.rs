@@ -36,6 +36,8 @@ pub(super) struct CreateCronRequest {
     interval_secs: u64,
     delivery_target: String,
     #[serde(default)]
+    template: Option<String>,
+    #[serde(default)]
     active_start_hour: Option<u8>,
     #[serde(default)]
     active_end_hour: Option<u8>,
@@ -326,6 +328,7 @@ pub(super) async fn create_or_update_cron(
             .map(ToString::to_string),
         interval_secs: request.interval_secs,
         delivery_target: request.delivery_target,
+        template: request.template,
         active_hours,
         enabled: request.enabled,
         run_once: request.run_once,
@@ -428,3 +431,84 @@ pub(super) async fn toggle_cron(
         message: format!("Cron job '{}' {}", request.cron_id, status),
     }))
 }
+
+#[derive(Deserialize)]
+pub(super) struct BulkToggleCronRequest {
+    agent_id: String,
+    cron_ids: Vec<String>,
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct BulkCronActionResponse {
+    success: bool,
+    updated: usize,
+    message: String,
+}
+
+/// Enable or disable a batch of cron jobs in one call. The whole batch is
+/// validated against the scheduler first, so one unknown ID fails the request
+/// before anything is toggled.
+pub(super) async fn bulk_toggle_cron(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<BulkToggleCronRequest>,
+) -> Result<Json<BulkCronActionResponse>, (StatusCode, String)> {
+    if request.cron_ids.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "cron_ids is empty".to_string()));
+    }
+
+    let stores = state.cron_stores.load();
+    let store = stores.get(&request.agent_id).ok_or((
+        StatusCode::NOT_FOUND,
+        format!("no cron store for agent '{}'", request.agent_id),
+    ))?;
+
+    let schedulers = state.cron_schedulers.load();
+    let scheduler = schedulers.get(&request.agent_id).ok_or((
+        StatusCode::NOT_FOUND,
+        format!("no cron scheduler for agent '{}'", request.agent_id),
+    ))?;
+
+    let mut unknown = Vec::new();
+    for cron_id in &request.cron_ids {
+        if !scheduler.is_registered(cron_id).await {
+            unknown.push(cron_id.as_str());
+        }
+    }
+    if !unknown.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("unknown cron jobs: {}", unknown.join(", ")),
+        ));
+    }
+
+    let mut updated = 0;
+    for cron_id in &request.cron_ids {
+        store.update_enabled(cron_id, request.enabled).await.map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, cron_id = %cron_id, "failed to update cron job enabled state");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed after updating {updated} of {} jobs", request.cron_ids.len()),
+            )
+        })?;
+        scheduler.set_enabled(cron_id, request.enabled).await.map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, cron_id = %cron_id, "failed to update scheduler enabled state");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed after updating {updated} of {} jobs", request.cron_ids.len()),
+            )
+        })?;
+        updated += 1;
+    }
+
+    let status = if request.enabled {
+        "enabled"
+    } else {
+        "disabled"
+    };
+    Ok(Json(BulkCronActionResponse {
+        success: true,
+        updated,
+        message: format!("{updated} cron jobs {status}"),
+    }))
+}
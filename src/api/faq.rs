@@ -0,0 +1,123 @@
+use super::state::ApiState;
+
+use crate::memory::embedding::embed_text;
+use crate::memory::faq::{FaqEntry, FaqStore, format_faq_answer};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub(super) struct FaqListResponse {
+    entries: Vec<FaqEntry>,
+    total: usize,
+}
+
+#[derive(Deserialize)]
+pub(super) struct FaqListQuery {
+    agent_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PromoteFaqRequest {
+    agent_id: String,
+    /// Canonical phrasing of the question; this is what gets embedded.
+    question: String,
+    answer: String,
+    /// Conversation the answer was given in, linked in future FAQ replies.
+    #[serde(default)]
+    source_conversation_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct PromoteFaqResponse {
+    entry: FaqEntry,
+    /// The reply users will see when this entry answers a question.
+    preview: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct DeleteFaqQuery {
+    agent_id: String,
+}
+
+/// List the curated FAQ entries for an agent.
+pub(super) async fn list_faq(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<FaqListQuery>,
+) -> Result<Json<FaqListResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let entries = FaqStore::new(pool.clone())
+        .list()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let total = entries.len();
+
+    Ok(Json(FaqListResponse { entries, total }))
+}
+
+/// Promote an answer into the curated FAQ.
+pub(super) async fn promote_faq(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<PromoteFaqRequest>,
+) -> Result<Json<PromoteFaqResponse>, (StatusCode, String)> {
+    if request.question.trim().is_empty() || request.answer.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "question and answer must be non-empty".into(),
+        ));
+    }
+
+    let pools = state.agent_pools.load();
+    let pool = pools
+        .get(&request.agent_id)
+        .ok_or((StatusCode::NOT_FOUND, "unknown agent".into()))?;
+
+    let embedding_model = state
+        .embedding_model
+        .read()
+        .await
+        .clone()
+        .ok_or((StatusCode::SERVICE_UNAVAILABLE, "embeddings unavailable".into()))?;
+    let embedding = embed_text(&embedding_model, &request.question)
+        .await
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    let entry = FaqStore::new(pool.clone())
+        .add(
+            &request.question,
+            &request.answer,
+            request.source_conversation_id.as_deref(),
+            &embedding,
+        )
+        .await
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    let preview = format_faq_answer(&entry);
+    Ok(Json(PromoteFaqResponse { entry, preview }))
+}
+
+/// Remove an FAQ entry.
+pub(super) async fn delete_faq(
+    State(state): State<Arc<ApiState>>,
+    Path(id): Path<String>,
+    Query(query): Query<DeleteFaqQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let removed = FaqStore::new(pool.clone())
+        .remove(&id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
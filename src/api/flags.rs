@@ -0,0 +1,158 @@
+//! Feature flag management endpoints.
+
+use super::state::ApiState;
+
+use crate::flags::{FeatureFlag, FeatureFlags};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub(super) struct FlagsListResponse {
+    flags: Vec<FeatureFlag>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PutFlagRequest {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    agent_overrides: HashMap<String, bool>,
+    #[serde(default)]
+    rollout_percent: Option<u8>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct EvaluateFlagQuery {
+    name: String,
+    #[serde(default)]
+    agent_id: Option<String>,
+    #[serde(default)]
+    conversation_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct EvaluateFlagResponse {
+    name: String,
+    enabled: bool,
+}
+
+/// List all feature flags.
+pub(super) async fn list_flags(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<FlagsListResponse>, StatusCode> {
+    let flags = state
+        .feature_flags
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    Ok(Json(FlagsListResponse {
+        flags: flags.load().flags.clone(),
+    }))
+}
+
+/// Create or update a feature flag and hot-swap the shared set.
+pub(super) async fn put_flag(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<PutFlagRequest>,
+) -> Result<Json<FeatureFlag>, (StatusCode, String)> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "flag name must be non-empty".into()));
+    }
+    if let Some(percent) = request.rollout_percent
+        && percent > 100
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "rollout_percent must be between 0 and 100".into(),
+        ));
+    }
+
+    let shared = state
+        .feature_flags
+        .read()
+        .await
+        .clone()
+        .ok_or((StatusCode::SERVICE_UNAVAILABLE, "flags not initialized".into()))?;
+
+    let flag = FeatureFlag {
+        name,
+        enabled: request.enabled,
+        agent_overrides: request.agent_overrides,
+        rollout_percent: request.rollout_percent,
+        description: request.description,
+    };
+
+    let mut updated: FeatureFlags = (**shared.load()).clone();
+    updated.upsert(flag.clone());
+
+    let instance_dir = state.instance_dir.load();
+    updated
+        .save(&instance_dir)
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    shared.store(Arc::new(updated));
+
+    Ok(Json(flag))
+}
+
+/// Delete a feature flag.
+pub(super) async fn delete_flag(
+    State(state): State<Arc<ApiState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let shared = state
+        .feature_flags
+        .read()
+        .await
+        .clone()
+        .ok_or((StatusCode::SERVICE_UNAVAILABLE, "flags not initialized".into()))?;
+
+    let mut updated: FeatureFlags = (**shared.load()).clone();
+    if !updated.remove(&name) {
+        return Err((StatusCode::NOT_FOUND, "unknown flag".into()));
+    }
+
+    let instance_dir = state.instance_dir.load();
+    updated
+        .save(&instance_dir)
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    shared.store(Arc::new(updated));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Evaluate a flag as a given agent/conversation would see it.
+pub(super) async fn evaluate_flag(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<EvaluateFlagQuery>,
+) -> Result<Json<EvaluateFlagResponse>, StatusCode> {
+    let flags = state
+        .feature_flags
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let enabled = flags.load().is_enabled(
+        &query.name,
+        query.agent_id.as_deref(),
+        query.conversation_id.as_deref(),
+    );
+
+    Ok(Json(EvaluateFlagResponse {
+        name: query.name,
+        enabled,
+    }))
+}
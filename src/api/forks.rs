@@ -0,0 +1,248 @@
+//! Conversation forking for what-if replay.
+//!
+//! Forks clone a live channel's in-memory history at a chosen turn into a
+//! sandbox conversation that is never delivered to any adapter. The dashboard
+//! chat console can then replay alternative prompts or models against the
+//! same history and compare the answers. Forks live only in API state and
+//! vanish on restart.
+
+use super::state::ApiState;
+
+use crate::ProcessType;
+use crate::llm::SpacebotModel;
+
+use axum::Json;
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel as _, Prompt as _};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Cap on concurrently held forks across all channels.
+const MAX_FORKS: usize = 32;
+
+/// A sandbox conversation forked from a live channel.
+pub(crate) struct ConversationFork {
+    pub id: String,
+    pub channel_id: String,
+    pub agent_id: String,
+    pub model: String,
+    pub system_prompt: String,
+    pub history: Vec<rig::message::Message>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct CreateForkRequest {
+    channel_id: String,
+    /// Keep only the first N history messages; defaults to the full history.
+    at_index: Option<usize>,
+    /// Model to replay with; defaults to the channel's routing resolution.
+    model: Option<String>,
+    /// Alternative system prompt; defaults to the channel's last prompt.
+    system_prompt: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ForkSummary {
+    fork_id: String,
+    channel_id: String,
+    agent_id: String,
+    model: String,
+    history_len: usize,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ForkSendRequest {
+    fork_id: String,
+    text: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct ForkSendResponse {
+    fork_id: String,
+    response: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ForkQuery {
+    fork_id: String,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct ListForksQuery {
+    channel_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ForksResponse {
+    forks: Vec<ForkSummary>,
+}
+
+fn summarize(fork: &ConversationFork) -> ForkSummary {
+    ForkSummary {
+        fork_id: fork.id.clone(),
+        channel_id: fork.channel_id.clone(),
+        agent_id: fork.agent_id.clone(),
+        model: fork.model.clone(),
+        history_len: fork.history.len(),
+        created_at: fork.created_at.to_rfc3339(),
+    }
+}
+
+/// Fork a live channel at a past turn into a sandbox conversation.
+pub(super) async fn create_fork(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<CreateForkRequest>,
+) -> Result<Json<ForkSummary>, StatusCode> {
+    let states = state.channel_states.read().await;
+    let channel_state = states
+        .get(&request.channel_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let model = match request.model {
+        Some(model) => {
+            if channel_state.deps.llm_manager.resolve_model(&model).is_err() {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            model
+        }
+        None => {
+            let routing = channel_state.deps.runtime_config.routing.load();
+            routing.resolve(ProcessType::Channel, None).to_string()
+        }
+    };
+
+    let system_prompt = match request.system_prompt {
+        Some(prompt) if !prompt.trim().is_empty() => prompt,
+        _ => channel_state.last_system_prompt.read().await.clone(),
+    };
+
+    let mut history = channel_state.history.read().await.clone();
+    if let Some(at_index) = request.at_index {
+        history.truncate(at_index);
+    }
+    let agent_id = channel_state.deps.agent_id.to_string();
+    drop(states);
+
+    let fork = ConversationFork {
+        id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+        channel_id: request.channel_id,
+        agent_id,
+        model,
+        system_prompt,
+        history,
+        created_at: chrono::Utc::now(),
+    };
+    let summary = summarize(&fork);
+
+    let mut forks = state.conversation_forks.write().await;
+    if forks.len() >= MAX_FORKS {
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+    forks.insert(fork.id.clone(), fork);
+
+    tracing::info!(
+        channel_id = %summary.channel_id,
+        fork_id = %summary.fork_id,
+        model = %summary.model,
+        "conversation forked for replay"
+    );
+    Ok(Json(summary))
+}
+
+/// Send a message into a fork and get the sandboxed reply.
+///
+/// Runs a plain completion — no tools, no delivery — so what-if replays
+/// cannot touch adapters, memory, or workers.
+pub(super) async fn fork_send(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<ForkSendRequest>,
+) -> Result<Json<ForkSendResponse>, StatusCode> {
+    let text = request.text.trim().to_string();
+    if text.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let (model_name, system_prompt, mut history, agent_id) = {
+        let forks = state.conversation_forks.read().await;
+        let fork = forks.get(&request.fork_id).ok_or(StatusCode::NOT_FOUND)?;
+        (
+            fork.model.clone(),
+            fork.system_prompt.clone(),
+            fork.history.clone(),
+            fork.agent_id.clone(),
+        )
+    };
+
+    let llm_manager = {
+        let guard = state.llm_manager.read().await;
+        guard
+            .as_ref()
+            .ok_or(StatusCode::SERVICE_UNAVAILABLE)?
+            .clone()
+    };
+
+    let model = SpacebotModel::make(&llm_manager, &model_name).with_context(&agent_id, "fork");
+    let agent = AgentBuilder::new(model)
+        .preamble(&system_prompt)
+        .build();
+
+    let response = agent
+        .prompt(&text)
+        .with_history(&mut history)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, fork_id = %request.fork_id, "fork replay failed");
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    // `with_history` appended the user turn and the reply; persist both
+    let mut forks = state.conversation_forks.write().await;
+    if let Some(fork) = forks.get_mut(&request.fork_id) {
+        fork.history = history;
+    }
+
+    Ok(Json(ForkSendResponse {
+        fork_id: request.fork_id,
+        response,
+    }))
+}
+
+/// List forks, optionally filtered to one channel.
+pub(super) async fn list_forks(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ListForksQuery>,
+) -> Json<ForksResponse> {
+    let forks = state.conversation_forks.read().await;
+    let mut summaries: Vec<ForkSummary> = forks
+        .values()
+        .filter(|fork| {
+            query
+                .channel_id
+                .as_deref()
+                .is_none_or(|channel_id| fork.channel_id == channel_id)
+        })
+        .map(summarize)
+        .collect();
+    summaries.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Json(ForksResponse { forks: summaries })
+}
+
+/// Discard a fork.
+pub(super) async fn delete_fork(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ForkQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let removed = state
+        .conversation_forks
+        .write()
+        .await
+        .remove(&query.fork_id);
+    if removed.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(serde_json::json!({ "success": true })))
+}
@@ -0,0 +1,137 @@
+use super::state::ApiState;
+use crate::{InboundMessage, MessageContent};
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Seconds to wait for a reply when the caller does not say.
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 30;
+
+/// Longest a hook call is allowed to hold its HTTP connection open.
+const MAX_HOOK_TIMEOUT_SECS: u64 = 120;
+
+#[derive(Deserialize, Default)]
+pub(super) struct HookSendRequest {
+    message: String,
+    /// Stable key for a continuing conversation; omitted means a fresh one.
+    session_id: Option<String>,
+    sender_name: Option<String>,
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub(super) struct HookSendResponse {
+    reply: Option<String>,
+    conversation_id: String,
+    /// True when the agent was still working at the timeout; the reply lands
+    /// in the conversation log and the SSE event stream instead.
+    pending: bool,
+}
+
+/// POST /hooks/agents/{id} — send a message straight to one agent and wait
+/// for its reply. Plain-text bodies are taken as the message verbatim; JSON
+/// bodies allow session, sender, and timeout control.
+pub(super) async fn hook_agent_send(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<(StatusCode, Json<HookSendResponse>), StatusCode> {
+    let manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let adapter = state
+        .hooks_adapter
+        .load()
+        .as_ref()
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    if !state.agent_pools.load().contains_key(&agent_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("json"));
+    let request: HookSendRequest = if is_json {
+        serde_json::from_str(&body).map_err(|_| StatusCode::BAD_REQUEST)?
+    } else {
+        HookSendRequest {
+            message: body,
+            ..Default::default()
+        }
+    };
+
+    if request.message.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let session = request
+        .session_id
+        .filter(|session| !session.trim().is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let conversation_id = format!("hooks:{agent_id}:{session}");
+    let sender_name = request.sender_name.unwrap_or_else(|| "script".to_string());
+    let timeout_secs = request
+        .timeout_secs
+        .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS)
+        .clamp(1, MAX_HOOK_TIMEOUT_SECS);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "display_name".into(),
+        serde_json::Value::String(sender_name.clone()),
+    );
+
+    let inbound = InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: "hooks".into(),
+        adapter: Some("hooks".into()),
+        conversation_id: conversation_id.clone(),
+        sender_id: sender_name.clone(),
+        agent_id: Some(agent_id.into()),
+        content: MessageContent::Text(request.message),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(sender_name),
+    };
+
+    // Park the waiter before injecting so a fast reply cannot slip past it.
+    let receiver = adapter.wait_for_reply(&conversation_id).await;
+
+    manager.inject_message(inbound).await.map_err(|error| {
+        tracing::warn!(%error, "failed to inject hook message");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), receiver).await {
+        Ok(Ok(reply)) => Ok((
+            StatusCode::OK,
+            Json(HookSendResponse {
+                reply: Some(reply),
+                conversation_id,
+                pending: false,
+            }),
+        )),
+        _ => {
+            adapter.abandon(&conversation_id).await;
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(HookSendResponse {
+                    reply: None,
+                    conversation_id,
+                    pending: true,
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::state::ApiState;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub(super) struct MaintenanceStatusResponse {
+    active: bool,
+    message: Option<String>,
+    until: Option<i64>,
+    enabled_at: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct EnableMaintenanceRequest {
+    #[serde(default)]
+    message: Option<String>,
+    /// Unix timestamp after which maintenance ends automatically.
+    #[serde(default)]
+    until: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub(super) struct MaintenanceToggleResponse {
+    success: bool,
+    active: bool,
+}
+
+/// GET /maintenance — current maintenance window, if any.
+pub(super) async fn maintenance_status(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<MaintenanceStatusResponse>, StatusCode> {
+    let store = state.maintenance.read().await;
+    let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let status = store.status().await;
+    Ok(Json(MaintenanceStatusResponse {
+        active: status.is_some(),
+        message: status.as_ref().and_then(|window| window.message.clone()),
+        until: status.as_ref().and_then(|window| window.until),
+        enabled_at: status.as_ref().map(|window| window.enabled_at),
+    }))
+}
+
+/// POST /maintenance — pause inbound processing.
+pub(super) async fn enable_maintenance(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<EnableMaintenanceRequest>,
+) -> Result<Json<MaintenanceToggleResponse>, StatusCode> {
+    let store = state.maintenance.read().await;
+    let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    store.enable(request.message, request.until).await;
+    Ok(Json(MaintenanceToggleResponse {
+        success: true,
+        active: true,
+    }))
+}
+
+/// POST /maintenance/disable — resume inbound processing.
+pub(super) async fn disable_maintenance(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<MaintenanceToggleResponse>, StatusCode> {
+    let store = state.maintenance.read().await;
+    let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let was_active = store.disable().await;
+    Ok(Json(MaintenanceToggleResponse {
+        success: was_active,
+        active: false,
+    }))
+}
@@ -60,6 +60,10 @@ pub(super) struct InstanceCredentials {
     #[serde(default)]
     telegram_token: Option<String>,
     #[serde(default)]
+    line_channel_secret: Option<String>,
+    #[serde(default)]
+    line_channel_access_token: Option<String>,
+    #[serde(default)]
     twitch_username: Option<String>,
     #[serde(default)]
     twitch_oauth_token: Option<String>,
@@ -344,6 +348,243 @@ pub(super) async fn messaging_status(
                 enabled: false,
             });
 
+        // Nostr has no dedicated column in the response; it surfaces through
+        // the generic instances list like other newer platforms.
+        if let Some(nostr) = doc.get("messaging").and_then(|m| m.get("nostr")) {
+            let has_key = nostr
+                .get("secret_key")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = nostr
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_key {
+                push_instance_status(&mut instances, bindings, "nostr", None, true, enabled);
+            }
+        }
+
+        if let Some(xmpp) = doc.get("messaging").and_then(|m| m.get("xmpp")) {
+            let has_jid = xmpp
+                .get("jid")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = xmpp
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_jid {
+                push_instance_status(&mut instances, bindings, "xmpp", None, true, enabled);
+            }
+        }
+
+        if let Some(line) = doc.get("messaging").and_then(|m| m.get("line")) {
+            let has_credentials = line
+                .get("channel_access_token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = line
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_credentials {
+                push_instance_status(&mut instances, bindings, "line", None, true, enabled);
+            }
+        }
+
+        if let Some(reddit) = doc.get("messaging").and_then(|m| m.get("reddit")) {
+            let has_credentials = reddit
+                .get("client_id")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = reddit
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_credentials {
+                push_instance_status(&mut instances, bindings, "reddit", None, true, enabled);
+            }
+        }
+
+        if let Some(github) = doc.get("messaging").and_then(|m| m.get("github")) {
+            let has_token = github
+                .get("token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = github
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_token {
+                push_instance_status(&mut instances, bindings, "github", None, true, enabled);
+            }
+        }
+
+        if let Some(gitlab) = doc.get("messaging").and_then(|m| m.get("gitlab")) {
+            let has_token = gitlab
+                .get("token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = gitlab
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_token {
+                push_instance_status(&mut instances, bindings, "gitlab", None, true, enabled);
+            }
+        }
+
+        if let Some(jira) = doc.get("messaging").and_then(|m| m.get("jira")) {
+            let has_credentials = jira
+                .get("api_token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = jira
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_credentials {
+                push_instance_status(&mut instances, bindings, "jira", None, true, enabled);
+            }
+        }
+
+        if let Some(linear) = doc.get("messaging").and_then(|m| m.get("linear")) {
+            let has_key = linear
+                .get("api_key")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = linear
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_key {
+                push_instance_status(&mut instances, bindings, "linear", None, true, enabled);
+            }
+
+            if let Some(named_instances) = linear
+                .get("instances")
+                .and_then(|value| value.as_array_of_tables())
+            {
+                for instance in named_instances {
+                    let instance_name = normalize_adapter_selector(
+                        instance.get("name").and_then(|value| value.as_str()),
+                    );
+                    let instance_enabled = instance
+                        .get("enabled")
+                        .and_then(|value| value.as_bool())
+                        .unwrap_or(true)
+                        && enabled;
+                    let instance_configured = instance
+                        .get("api_key")
+                        .and_then(|value| value.as_str())
+                        .is_some_and(|key| !key.is_empty());
+
+                    if let Some(instance_name) = instance_name
+                        && instance_configured
+                    {
+                        push_instance_status(
+                            &mut instances,
+                            bindings,
+                            "linear",
+                            Some(instance_name),
+                            true,
+                            instance_enabled,
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(nextcloud) = doc.get("messaging").and_then(|m| m.get("nextcloud")) {
+            let has_credentials = nextcloud
+                .get("app_password")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = nextcloud
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_credentials {
+                push_instance_status(&mut instances, bindings, "nextcloud", None, true, enabled);
+            }
+        }
+
+        if let Some(rss) = doc.get("messaging").and_then(|m| m.get("rss")) {
+            let has_feeds = rss
+                .get("feeds")
+                .and_then(|v| v.as_array())
+                .is_some_and(|feeds| !feeds.is_empty());
+            let enabled = rss
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_feeds {
+                push_instance_status(&mut instances, bindings, "rss", None, true, enabled);
+            }
+        }
+
+        if let Some(mqtt) = doc.get("messaging").and_then(|m| m.get("mqtt")) {
+            let has_broker = mqtt
+                .get("host")
+                .and_then(|v| v.as_str())
+                .is_some_and(|s| !s.is_empty());
+            let enabled = mqtt
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if has_broker {
+                push_instance_status(&mut instances, bindings, "mqtt", None, true, enabled);
+            }
+        }
+
+        if let Some(websocket) = doc.get("messaging").and_then(|m| m.get("websocket")) {
+            let enabled = websocket
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            push_instance_status(&mut instances, bindings, "websocket", None, true, enabled);
+        }
+
+        if let Some(console) = doc.get("messaging").and_then(|m| m.get("console")) {
+            let enabled = console
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            push_instance_status(&mut instances, bindings, "console", None, true, enabled);
+        }
+
+        if let Some(voice) = doc.get("messaging").and_then(|m| m.get("voice")) {
+            let enabled = voice
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            push_instance_status(&mut instances, bindings, "voice", None, true, enabled);
+        }
+
+        if let Some(steam) = doc.get("messaging").and_then(|m| m.get("steam")) {
+            let has_token = steam
+                .get("access_token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| !v.is_empty());
+            let enabled = steam
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            push_instance_status(&mut instances, bindings, "steam", None, has_token, enabled);
+        }
+
+        if let Some(viber) = doc.get("messaging").and_then(|m| m.get("viber")) {
+            let has_token = viber
+                .get("auth_token")
+                .and_then(|v| v.as_str())
+                .is_some_and(|v| !v.is_empty());
+            let enabled = viber
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            push_instance_status(&mut instances, bindings, "viber", None, has_token, enabled);
+        }
+
         let email_status = doc
             .get("messaging")
             .and_then(|m| m.get("email"))
@@ -734,6 +975,237 @@ pub(super) async fn disconnect_platform(
 
 /// Toggle a messaging platform's enabled state. When disabling, shuts down the
 /// adapter. When enabling, reads credentials from config and hot-starts it.
+/// Receive one Bot API update pushed by Telegram to a registered webhook and
+/// feed it into the matching adapter's update loop. Auth is the secret token
+/// Telegram echoes back, not the API bearer token.
+#[cfg(feature = "adapter-telegram")]
+pub(super) async fn telegram_webhook(
+    State(state): State<Arc<ApiState>>,
+    axum::extract::Path(adapter): axum::extract::Path<String>,
+    headers: axum::http::HeaderMap,
+    Json(update): Json<teloxide::types::Update>,
+) -> StatusCode {
+    let target = state
+        .telegram_webhook_adapters
+        .read()
+        .await
+        .get(&adapter)
+        .cloned();
+    let Some(target) = target else {
+        return StatusCode::NOT_FOUND;
+    };
+
+    if let Some(secret) = target.webhook_secret() {
+        let presented = headers
+            .get("x-telegram-bot-api-secret-token")
+            .and_then(|value| value.to_str().ok());
+        if presented != Some(secret) {
+            tracing::warn!(%adapter, "rejected telegram webhook delivery with bad secret token");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    match target.inject_update(update).await {
+        Ok(()) => StatusCode::OK,
+        Err(error) => {
+            tracing::warn!(%error, %adapter, "failed to queue telegram webhook update");
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
+/// Stub used when the telegram adapter is compiled out.
+#[cfg(not(feature = "adapter-telegram"))]
+pub(super) async fn telegram_webhook(
+    State(_state): State<Arc<ApiState>>,
+    axum::extract::Path(_adapter): axum::extract::Path<String>,
+) -> StatusCode {
+    StatusCode::NOT_IMPLEMENTED
+}
+
+#[cfg(feature = "adapter-email")]
+#[derive(Deserialize)]
+pub(super) struct EmailActionRequest {
+    folder: String,
+    uid: u32,
+    action: String,
+    #[serde(default)]
+    target_folder: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct EmailActionResponse {
+    summary: String,
+}
+
+/// Apply a mailbox action (archive, move, label, mark read/unread, delete)
+/// to one message in the configured email account.
+#[cfg(feature = "adapter-email")]
+pub(super) async fn email_mailbox_action(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<EmailActionRequest>,
+) -> Result<Json<EmailActionResponse>, (StatusCode, String)> {
+    let action = crate::messaging::email::EmailMailboxAction::from_parts(
+        &request.action,
+        request.target_folder,
+        request.label,
+    )
+    .map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    let config_path = state.config_path.read().await.clone();
+    let config = crate::config::Config::load_from_path(&config_path).map_err(|error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to load config: {error}"),
+        )
+    })?;
+    let Some(email_config) = config.messaging.email else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "email adapter is not configured".to_string(),
+        ));
+    };
+
+    let folder = request.folder;
+    let uid = request.uid;
+    let summary = tokio::task::spawn_blocking(move || {
+        crate::messaging::email::apply_mailbox_action(&email_config, &folder, uid, &action)
+    })
+    .await
+    .map_err(|error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("email action task failed: {error}"),
+        )
+    })?
+    .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    Ok(Json(EmailActionResponse { summary }))
+}
+
+/// Stub used when the email adapter is compiled out.
+#[cfg(not(feature = "adapter-email"))]
+pub(super) async fn email_mailbox_action(
+    State(_state): State<Arc<ApiState>>,
+    Json(_request): Json<serde_json::Value>,
+) -> Result<Json<EmailActionResponse>, (StatusCode, String)> {
+    Err((
+        StatusCode::NOT_IMPLEMENTED,
+        "this build was compiled without the adapter-email feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "adapter-email")]
+#[derive(Deserialize)]
+pub(super) struct EmailSearchRequest {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    subject: Option<String>,
+    #[serde(default)]
+    folders: Vec<String>,
+    #[serde(default)]
+    unread_only: bool,
+    #[serde(default)]
+    since_days: Option<u32>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub(super) struct EmailSearchHitResponse {
+    folder: String,
+    uid: u32,
+    from: String,
+    subject: String,
+    date: Option<String>,
+    message_id: Option<String>,
+    body: String,
+    attachment_names: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct EmailSearchResponse {
+    hits: Vec<EmailSearchHitResponse>,
+}
+
+/// Search the configured email account over IMAP — the same
+/// `search_mailbox` the `email_search` tool uses, exposed for scripts
+/// and the portal.
+#[cfg(feature = "adapter-email")]
+pub(super) async fn email_mailbox_search(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<EmailSearchRequest>,
+) -> Result<Json<EmailSearchResponse>, (StatusCode, String)> {
+    let config_path = state.config_path.read().await.clone();
+    let config = crate::config::Config::load_from_path(&config_path).map_err(|error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to load config: {error}"),
+        )
+    })?;
+    let Some(email_config) = config.messaging.email else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "email adapter is not configured".to_string(),
+        ));
+    };
+
+    let query = crate::messaging::email::EmailSearchQuery {
+        text: request.query.filter(|value| !value.trim().is_empty()),
+        from: request.from.filter(|value| !value.trim().is_empty()),
+        subject: request.subject.filter(|value| !value.trim().is_empty()),
+        unread_only: request.unread_only,
+        since_days: request.since_days.filter(|days| *days > 0),
+        folders: request.folders,
+        limit: request.limit.unwrap_or(10).clamp(1, 50),
+    };
+
+    let hits = tokio::task::spawn_blocking(move || {
+        crate::messaging::email::search_mailbox(&email_config, query)
+    })
+    .await
+    .map_err(|error| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("email search task failed: {error}"),
+        )
+    })?
+    .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+
+    let hits = hits
+        .into_iter()
+        .map(|hit| EmailSearchHitResponse {
+            folder: hit.folder,
+            uid: hit.uid,
+            from: hit.from,
+            subject: hit.subject,
+            date: hit.date,
+            message_id: hit.message_id,
+            body: hit.body,
+            attachment_names: hit.attachment_names,
+        })
+        .collect();
+
+    Ok(Json(EmailSearchResponse { hits }))
+}
+
+/// Stub used when the email adapter is compiled out.
+#[cfg(not(feature = "adapter-email"))]
+pub(super) async fn email_mailbox_search(
+    State(_state): State<Arc<ApiState>>,
+    Json(_request): Json<serde_json::Value>,
+) -> Result<Json<EmailSearchResponse>, (StatusCode, String)> {
+    Err((
+        StatusCode::NOT_IMPLEMENTED,
+        "this build was compiled without the adapter-email feature".to_string(),
+    ))
+}
+
 pub(super) async fn toggle_platform(
     State(state): State<Arc<ApiState>>,
     Json(request): Json<TogglePlatformRequest>,
@@ -811,6 +1283,11 @@ pub(super) async fn toggle_platform(
             && let Some(manager) = manager
         {
             match platform.as_str() {
+                #[cfg(not(feature = "adapter-discord"))]
+                "discord" => {
+                    tracing::warn!("discord adapter not compiled into this build");
+                }
+                #[cfg(feature = "adapter-discord")]
                 "discord" => {
                     if let Some(discord_config) = &new_config.messaging.discord {
                         if !discord_config.token.is_empty() {
@@ -836,6 +1313,7 @@ pub(super) async fn toggle_platform(
                                 "discord",
                                 &discord_config.token,
                                 perms,
+                                discord_config.proxy.clone(),
                             );
                             if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, "failed to start discord adapter on toggle");
@@ -861,6 +1339,7 @@ pub(super) async fn toggle_platform(
                                 runtime_key,
                                 &instance.token,
                                 perms,
+                                discord_config.proxy.clone(),
                             );
                             if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, adapter = %instance.name, "failed to start named discord adapter on toggle");
@@ -868,6 +1347,11 @@ pub(super) async fn toggle_platform(
                         }
                     }
                 }
+                #[cfg(not(feature = "adapter-slack"))]
+                "slack" => {
+                    tracing::warn!("slack adapter not compiled into this build");
+                }
+                #[cfg(feature = "adapter-slack")]
                 "slack" => {
                     if let Some(slack_config) = &new_config.messaging.slack {
                         if !slack_config.bot_token.is_empty() && !slack_config.app_token.is_empty()
@@ -942,6 +1426,11 @@ pub(super) async fn toggle_platform(
                         }
                     }
                 }
+                #[cfg(not(feature = "adapter-telegram"))]
+                "telegram" => {
+                    tracing::warn!("telegram adapter not compiled into this build");
+                }
+                #[cfg(feature = "adapter-telegram")]
                 "telegram" => {
                     if let Some(telegram_config) = &new_config.messaging.telegram {
                         if !telegram_config.token.is_empty() {
@@ -955,8 +1444,31 @@ pub(super) async fn toggle_platform(
                                 "telegram",
                                 &telegram_config.token,
                                 arc_swap,
+                                Some(config_path.clone()),
+                                telegram_config.proxy.clone(),
                             );
-                            if let Err(error) = manager.register_and_start(adapter).await {
+                            let adapter = match &telegram_config.stt {
+                                Some(stt) if stt.enabled => adapter.with_stt(stt.clone()),
+                                _ => adapter,
+                            };
+                            let adapter =
+                                adapter.with_commands(telegram_config.commands.clone());
+                            if telegram_config.mode == crate::config::TelegramMode::Webhook
+                                && !telegram_config.webhook_url.is_empty()
+                            {
+                                let adapter = std::sync::Arc::new(adapter.with_webhook(
+                                    telegram_config.webhook_url.clone(),
+                                    telegram_config.webhook_secret.clone(),
+                                ));
+                                state
+                                    .insert_telegram_webhook_adapter("telegram", adapter.clone())
+                                    .await;
+                                if let Err(error) =
+                                    manager.register_and_start_shared(adapter).await
+                                {
+                                    tracing::error!(%error, "failed to start telegram adapter on toggle");
+                                }
+                            } else if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, "failed to start telegram adapter on toggle");
                             }
                         }
@@ -977,26 +1489,69 @@ pub(super) async fn toggle_platform(
                                 ),
                             ));
                             let adapter = crate::messaging::telegram::TelegramAdapter::new(
-                                runtime_key,
+                                runtime_key.clone(),
                                 &instance.token,
                                 perms,
+                                Some(config_path.clone()),
+                                telegram_config.proxy.clone(),
                             );
-                            if let Err(error) = manager.register_and_start(adapter).await {
+                            let adapter = match &telegram_config.stt {
+                                Some(stt) if stt.enabled => adapter.with_stt(stt.clone()),
+                                _ => adapter,
+                            };
+                            let adapter =
+                                adapter.with_commands(telegram_config.commands.clone());
+                            if telegram_config.mode == crate::config::TelegramMode::Webhook
+                                && !telegram_config.webhook_url.is_empty()
+                            {
+                                let adapter = std::sync::Arc::new(adapter.with_webhook(
+                                    telegram_config.webhook_url.clone(),
+                                    telegram_config.webhook_secret.clone(),
+                                ));
+                                state
+                                    .insert_telegram_webhook_adapter(&runtime_key, adapter.clone())
+                                    .await;
+                                if let Err(error) =
+                                    manager.register_and_start_shared(adapter).await
+                                {
+                                    tracing::error!(%error, adapter = %instance.name, "failed to start named telegram adapter on toggle");
+                                }
+                            } else if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, adapter = %instance.name, "failed to start named telegram adapter on toggle");
                             }
                         }
                     }
                 }
+                #[cfg(not(feature = "adapter-email"))]
+                "email" => {
+                    tracing::warn!("email adapter not compiled into this build");
+                }
+                #[cfg(feature = "adapter-email")]
                 "email" => {
                     if let Some(email_config) = &new_config.messaging.email {
-                        match crate::messaging::email::EmailAdapter::from_config(email_config) {
-                            Ok(adapter) => {
-                                if let Err(error) = manager.register_and_start(adapter).await {
-                                    tracing::error!(%error, "failed to start email adapter on toggle");
+                        if email_config.backend == crate::config::EmailBackend::Graph {
+                            match crate::messaging::graphmail::GraphEmailAdapter::from_config(
+                                email_config,
+                            ) {
+                                Ok(adapter) => {
+                                    if let Err(error) = manager.register_and_start(adapter).await {
+                                        tracing::error!(%error, "failed to start graph email adapter on toggle");
+                                    }
+                                }
+                                Err(error) => {
+                                    tracing::error!(%error, "failed to build graph email adapter on toggle");
                                 }
                             }
-                            Err(error) => {
-                                tracing::error!(%error, "failed to build email adapter on toggle");
+                        } else {
+                            match crate::messaging::email::EmailAdapter::from_config(email_config) {
+                                Ok(adapter) => {
+                                    if let Err(error) = manager.register_and_start(adapter).await {
+                                        tracing::error!(%error, "failed to start email adapter on toggle");
+                                    }
+                                }
+                                Err(error) => {
+                                    tracing::error!(%error, "failed to build email adapter on toggle");
+                                }
                             }
                         }
                     }
@@ -1128,7 +1683,7 @@ pub(super) async fn create_messaging_instance(
 
     if !matches!(
         platform.as_str(),
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook"
+        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "line"
     ) {
         return Ok(Json(MessagingInstanceActionResponse {
             success: false,
@@ -1136,6 +1691,13 @@ pub(super) async fn create_messaging_instance(
         }));
     }
 
+    if request.name.is_some() && platform == "line" {
+        return Ok(Json(MessagingInstanceActionResponse {
+            success: false,
+            message: "line supports only the default instance".to_string(),
+        }));
+    }
+
     // Validate instance name
     if let Some(name) = &request.name {
         let trimmed = name.trim();
@@ -1212,6 +1774,14 @@ pub(super) async fn create_messaging_instance(
                         platform_table["token"] = toml_edit::value(token.as_str());
                     }
                 }
+                "line" => {
+                    if let Some(secret) = &credentials.line_channel_secret {
+                        platform_table["channel_secret"] = toml_edit::value(secret.as_str());
+                    }
+                    if let Some(token) = &credentials.line_channel_access_token {
+                        platform_table["channel_access_token"] = toml_edit::value(token.as_str());
+                    }
+                }
                 "twitch" => {
                     if let Some(username) = &credentials.twitch_username {
                         platform_table["username"] = toml_edit::value(username.as_str());
@@ -1443,7 +2013,7 @@ pub(super) async fn delete_messaging_instance(
 
     if !matches!(
         platform.as_str(),
-        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook"
+        "discord" | "slack" | "telegram" | "twitch" | "email" | "webhook" | "line"
     ) {
         return Ok(Json(MessagingInstanceActionResponse {
             success: false,
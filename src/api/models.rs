@@ -172,7 +172,7 @@ fn extra_models() -> Vec<ModelInfo> {
 
 /// Fetch the full model catalog from models.dev and transform into ModelInfo entries.
 async fn fetch_models_dev() -> anyhow::Result<Vec<ModelInfo>> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .get("https://models.dev/api.json")
         .timeout(std::time::Duration::from_secs(15))
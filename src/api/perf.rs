@@ -0,0 +1,17 @@
+//! Hot-path performance report endpoints.
+
+use crate::perf::{PerfRecorder, PerfReport};
+
+use axum::Json;
+use axum::http::StatusCode;
+
+/// Per-stage latency histograms for the message pipeline.
+pub(super) async fn perf_report() -> Json<PerfReport> {
+    Json(PerfRecorder::global().report())
+}
+
+/// Clear recorded samples so a fresh measurement window can start.
+pub(super) async fn reset_perf_report() -> StatusCode {
+    PerfRecorder::global().reset();
+    StatusCode::NO_CONTENT
+}
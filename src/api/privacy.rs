@@ -0,0 +1,257 @@
+use super::state::ApiState;
+
+use crate::consent::ConsentStore;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use sqlx::Row as _;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct DataSubjectQuery {
+    agent_id: String,
+    sender_id: String,
+}
+
+/// Everything stored about one sender, for data-subject access requests.
+#[derive(Serialize)]
+pub(super) struct DataSubjectExport {
+    sender_id: String,
+    exported_at: String,
+    messages: Vec<ExportedMessage>,
+    /// Memories whose content mentions the sender ID. Memory rows carry no
+    /// sender column, so this is a best-effort content scan.
+    memories: Vec<ExportedMemory>,
+    consent: crate::consent::ConsentRecord,
+    onboarding_bindings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ExportedMessage {
+    id: String,
+    channel_id: String,
+    content: String,
+    created_at: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct ExportedMemory {
+    id: String,
+    content: String,
+    memory_type: String,
+}
+
+/// What was removed by a deletion request, for compliance records.
+#[derive(Serialize)]
+pub(super) struct DeletionReport {
+    sender_id: String,
+    completed_at: String,
+    messages_deleted: u64,
+    memories_deleted: u64,
+    onboarding_rows_deleted: u64,
+    consent_record_deleted: bool,
+}
+
+/// Export all stored data tied to a platform sender ID.
+pub(super) async fn export_data_subject(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<DataSubjectQuery>,
+) -> Result<Json<DataSubjectExport>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let message_rows = sqlx::query(
+        "SELECT id, channel_id, content, created_at FROM conversation_messages \
+         WHERE sender_id = ? ORDER BY created_at",
+    )
+    .bind(&query.sender_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(%error, "failed to export messages");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let messages = message_rows
+        .into_iter()
+        .map(|row| ExportedMessage {
+            id: row.try_get("id").unwrap_or_default(),
+            channel_id: row.try_get("channel_id").unwrap_or_default(),
+            content: row.try_get("content").unwrap_or_default(),
+            created_at: row
+                .try_get::<chrono::DateTime<chrono::Utc>, _>("created_at")
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let memories = memories_mentioning(pool, &query.sender_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to export memories");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let consent = ConsentStore::new(pool.clone())
+        .get(&query.sender_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to export consent record");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let onboarding_bindings = sqlx::query(
+        "SELECT binding_key FROM onboarded_users WHERE sender_id = ? ORDER BY binding_key",
+    )
+    .bind(&query.sender_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|error| {
+        tracing::error!(%error, "failed to export onboarding records");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .into_iter()
+    .map(|row| row.try_get("binding_key").unwrap_or_default())
+    .collect();
+
+    Ok(Json(DataSubjectExport {
+        sender_id: query.sender_id,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        messages,
+        memories,
+        consent,
+        onboarding_bindings,
+    }))
+}
+
+/// Irreversibly delete all stored data tied to a platform sender ID and
+/// return a deletion report.
+pub(super) async fn delete_data_subject(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<DataSubjectQuery>,
+) -> Result<Json<DeletionReport>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let messages_deleted = sqlx::query("DELETE FROM conversation_messages WHERE sender_id = ?")
+        .bind(&query.sender_id)
+        .execute(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to delete messages");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .rows_affected();
+
+    // Delete matching memories through the search layer so the embedding
+    // index stays in sync with the SQLite rows
+    let memory_ids: Vec<String> = memories_mentioning(pool, &query.sender_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to scan memories for deletion");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .into_iter()
+        .map(|memory| memory.id)
+        .collect();
+
+    // An erasure endpoint must not claim completion it can't back up: if
+    // matching memories exist but can't all be deleted, fail the request
+    // instead of returning a report.
+    let mut memories_deleted = 0u64;
+    let searches = state.memory_searches.load();
+    match searches.get(&query.agent_id) {
+        Some(search) => {
+            for memory_id in &memory_ids {
+                search.store().delete(memory_id).await.map_err(|error| {
+                    tracing::error!(%error, memory_id, "failed to delete memory row");
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+                search
+                    .embedding_table()
+                    .delete(memory_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(%error, memory_id, "failed to delete memory embedding");
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+                memories_deleted += 1;
+            }
+        }
+        None if memory_ids.is_empty() => {}
+        None => {
+            tracing::error!(
+                agent_id = %query.agent_id,
+                matched = memory_ids.len(),
+                "memory search layer unavailable, cannot erase matched memories"
+            );
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    let onboarding_rows_deleted = sqlx::query("DELETE FROM onboarded_users WHERE sender_id = ?")
+        .bind(&query.sender_id)
+        .execute(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to delete onboarding records");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .rows_affected();
+
+    let consent_record_deleted = sqlx::query("DELETE FROM user_consent WHERE sender_id = ?")
+        .bind(&query.sender_id)
+        .execute(pool)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "failed to delete consent record");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .rows_affected()
+        > 0;
+
+    let report = DeletionReport {
+        sender_id: query.sender_id,
+        completed_at: chrono::Utc::now().to_rfc3339(),
+        messages_deleted,
+        memories_deleted,
+        onboarding_rows_deleted,
+        consent_record_deleted,
+    };
+
+    tracing::info!(
+        agent_id = %query.agent_id,
+        sender_id = %report.sender_id,
+        messages_deleted,
+        memories_deleted,
+        "data subject deletion completed"
+    );
+
+    Ok(Json(report))
+}
+
+/// Best-effort scan for memories that mention a sender ID in their content.
+async fn memories_mentioning(
+    pool: &sqlx::SqlitePool,
+    sender_id: &str,
+) -> crate::error::Result<Vec<ExportedMemory>> {
+    let rows = sqlx::query(
+        "SELECT id, content, memory_type FROM memories \
+         WHERE content LIKE '%' || ? || '%' ORDER BY created_at",
+    )
+    .bind(sender_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ExportedMemory {
+            id: row.try_get("id").unwrap_or_default(),
+            content: row.try_get("content").unwrap_or_default(),
+            memory_type: row.try_get("memory_type").unwrap_or_default(),
+        })
+        .collect())
+}
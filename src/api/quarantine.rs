@@ -0,0 +1,106 @@
+use super::state::ApiState;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub(super) struct QuarantineEntryResponse {
+    id: String,
+    reason: String,
+    quarantined_at: i64,
+    sender: String,
+    subject: Option<String>,
+    adapter: Option<String>,
+    preview: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct QuarantineListResponse {
+    entries: Vec<QuarantineEntryResponse>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct QuarantineActionRequest {
+    id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct QuarantineActionResponse {
+    success: bool,
+}
+
+/// GET /email/quarantine — quarantined messages awaiting review.
+pub(super) async fn list_quarantine(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<QuarantineListResponse>, StatusCode> {
+    let store = state.quarantine.read().await;
+    let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let entries = store
+        .list()
+        .into_iter()
+        .map(|entry| {
+            let preview: String = entry.message.content.to_string().chars().take(200).collect();
+            QuarantineEntryResponse {
+                id: entry.id,
+                reason: entry.reason,
+                quarantined_at: entry.quarantined_at,
+                sender: entry.message.sender_id.clone(),
+                subject: entry
+                    .message
+                    .metadata
+                    .get("email_subject")
+                    .and_then(|value| value.as_str().map(str::to_string)),
+                adapter: entry.message.adapter.clone(),
+                preview,
+            }
+        })
+        .collect();
+
+    Ok(Json(QuarantineListResponse { entries }))
+}
+
+/// POST /email/quarantine/release — re-inject a quarantined message into
+/// inbound processing.
+pub(super) async fn release_quarantined(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<QuarantineActionRequest>,
+) -> Result<Json<QuarantineActionResponse>, StatusCode> {
+    let manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let message = {
+        let store = state.quarantine.read().await;
+        let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+        store.release(&request.id).ok_or(StatusCode::NOT_FOUND)?
+    };
+
+    manager.inject_message(message).await.map_err(|error| {
+        tracing::warn!(%error, "failed to re-inject released quarantine entry");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(QuarantineActionResponse { success: true }))
+}
+
+/// POST /email/quarantine/discard — drop a quarantined message for good.
+pub(super) async fn discard_quarantined(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<QuarantineActionRequest>,
+) -> Result<Json<QuarantineActionResponse>, StatusCode> {
+    let store = state.quarantine.read().await;
+    let store = store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    if !store.discard(&request.id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(QuarantineActionResponse { success: true }))
+}
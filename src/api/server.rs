@@ -2,8 +2,12 @@
 
 use super::state::ApiState;
 use super::{
-    agents, bindings, channels, config, cortex, cron, ingest, links, mcp, memories, messaging,
-    models, providers, settings, skills, system, tasks, webchat, workers,
+    agents, bindings, channels, config, consent, cortex, cron, faq, flags, forks, hooks, ingest,
+    links,
+    maintenance, mcp,
+    memories,
+    messaging, models, perf, privacy, providers, quarantine, settings, skills, system, tasks,
+    templates, webchat, workers,
 };
 
 use axum::Json;
@@ -18,8 +22,10 @@ use rust_embed::Embed;
 use serde_json::json;
 use tower_http::cors::CorsLayer;
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 /// Embedded frontend assets from the Vite build output.
 #[derive(Embed)]
@@ -51,6 +57,7 @@ pub async fn start_http_server(
         .route("/health", get(system::health))
         .route("/idle", get(system::idle))
         .route("/status", get(system::status))
+        .route("/selftest", get(system::self_test))
         .route("/system/storage", get(system::storage_status))
         .route("/system/backup/export", get(system::backup_export))
         .route("/system/backup/restore", post(system::backup_restore))
@@ -88,8 +95,36 @@ pub async fn start_http_server(
         )
         .route("/channels/messages", get(channels::channel_messages))
         .route("/channels/status", get(channels::channel_status))
+        .route("/channels/context", get(channels::channel_context))
+        .route("/channels/context/pin", post(channels::pin_channel_context))
+        .route(
+            "/channels/context/unpin",
+            post(channels::unpin_channel_context),
+        )
+        .route(
+            "/channels/model",
+            get(channels::channel_model_override)
+                .post(channels::set_channel_model_override)
+                .delete(channels::clear_channel_model_override),
+        )
+        .route(
+            "/consent",
+            get(consent::get_consent)
+                .post(consent::set_consent)
+                .delete(consent::reset_consent),
+        )
+        .route("/privacy/export", get(privacy::export_data_subject))
+        .route("/privacy/data", delete(privacy::delete_data_subject))
+        .route(
+            "/channels/fork",
+            post(forks::create_fork).delete(forks::delete_fork),
+        )
+        .route("/channels/fork/send", post(forks::fork_send))
+        .route("/channels/forks", get(forks::list_forks))
         .route("/agents/workers", get(workers::list_workers))
         .route("/agents/workers/detail", get(workers::worker_detail))
+        .route("/agents/faq", get(faq::list_faq).post(faq::promote_faq))
+        .route("/agents/faq/{id}", delete(faq::delete_faq))
         .route("/agents/memories", get(memories::list_memories))
         .route("/agents/memories/search", get(memories::search_memories))
         .route("/agents/memories/graph", get(memories::memory_graph))
@@ -116,8 +151,16 @@ pub async fn start_http_server(
                 .delete(cron::delete_cron),
         )
         .route("/agents/cron/executions", get(cron::cron_executions))
+        .route(
+            "/templates",
+            get(templates::list_templates)
+                .put(templates::put_template)
+                .delete(templates::delete_template),
+        )
+        .route("/templates/content", get(templates::get_template))
         .route("/agents/cron/trigger", post(cron::trigger_cron))
         .route("/agents/cron/toggle", put(cron::toggle_cron))
+        .route("/agents/cron/bulk-toggle", post(cron::bulk_toggle_cron))
         .route(
             "/agents/tasks",
             get(tasks::list_tasks).post(tasks::create_task),
@@ -163,6 +206,10 @@ pub async fn start_http_server(
             post(messaging::disconnect_platform),
         )
         .route("/messaging/toggle", post(messaging::toggle_platform))
+        .route(
+            "/messaging/email/action",
+            post(messaging::email_mailbox_action),
+        )
         .route(
             "/messaging/instances",
             post(messaging::create_messaging_instance).delete(messaging::delete_messaging_instance),
@@ -174,6 +221,38 @@ pub async fn start_http_server(
                 .put(bindings::update_binding)
                 .delete(bindings::delete_binding),
         )
+        .route("/bindings/retarget", post(bindings::retarget_bindings))
+        .route("/bindings/bulk-delete", post(bindings::bulk_delete_bindings))
+        .route(
+            "/bindings/pins",
+            get(bindings::list_binding_pins).post(bindings::pin_binding_knowledge),
+        )
+        .route(
+            "/bindings/pins/unpin",
+            post(bindings::unpin_binding_knowledge),
+        )
+        .route(
+            "/maintenance",
+            get(maintenance::maintenance_status).post(maintenance::enable_maintenance),
+        )
+        .route(
+            "/maintenance/disable",
+            post(maintenance::disable_maintenance),
+        )
+        .route("/email/search", post(messaging::email_mailbox_search))
+        .route("/email/quarantine", get(quarantine::list_quarantine))
+        .route(
+            "/email/quarantine/release",
+            post(quarantine::release_quarantined),
+        )
+        .route(
+            "/email/quarantine/discard",
+            post(quarantine::discard_quarantined),
+        )
+        .route("/flags", get(flags::list_flags).put(flags::put_flag))
+        .route("/flags/{name}", delete(flags::delete_flag))
+        .route("/flags/evaluate", get(flags::evaluate_flag))
+        .route("/perf", get(perf::perf_report).delete(perf::reset_perf_report))
         .route(
             "/settings",
             get(settings::get_global_settings).put(settings::update_global_settings),
@@ -187,8 +266,13 @@ pub async fn start_http_server(
             get(settings::update_check).post(settings::update_check_now),
         )
         .route("/update/apply", post(settings::update_apply))
+        .route(
+            "/telegram/webhook/{adapter}",
+            post(messaging::telegram_webhook),
+        )
         .route("/webchat/send", post(webchat::webchat_send))
         .route("/webchat/history", get(webchat::webchat_history))
+        .route("/hooks/agents/{id}", post(hooks::hook_agent_send))
         .route("/links", get(links::list_links).post(links::create_link))
         .route(
             "/links/{from}/{to}",
@@ -207,6 +291,7 @@ pub async fn start_http_server(
             put(links::update_human).delete(links::delete_human),
         )
         .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+        .layer(middleware::from_fn(idempotency_middleware))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             api_auth_middleware,
@@ -251,6 +336,12 @@ async fn api_auth_middleware(
         return next.run(request).await;
     }
 
+    // Telegram can't attach a bearer token to webhook deliveries; that route
+    // authenticates them with the configured secret token instead.
+    if path.starts_with("/api/telegram/webhook/") {
+        return next.run(request).await;
+    }
+
     let is_authorized = request
         .headers()
         .get(header::AUTHORIZATION)
@@ -269,6 +360,137 @@ async fn api_auth_middleware(
     }
 }
 
+/// How long a cached idempotent response can be replayed.
+const IDEMPOTENCY_WINDOW: Duration = Duration::from_secs(600);
+
+/// Cached responses kept before the oldest are dropped.
+const IDEMPOTENCY_CACHE_CAP: usize = 256;
+
+/// A successful response retained for replay under its idempotency key.
+struct CachedResponse {
+    status: StatusCode,
+    content_type: Option<header::HeaderValue>,
+    body: axum::body::Bytes,
+    /// SHA-256 of the request body the response was produced for, so a key
+    /// reused with a different payload is detected instead of replayed.
+    request_hash: [u8; 32],
+    stored_at: Instant,
+}
+
+static IDEMPOTENCY_CACHE: LazyLock<Mutex<HashMap<String, CachedResponse>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Replay mutating requests that repeat an `Idempotency-Key` header within
+/// the cache window, so dashboard retries and flaky networks don't create
+/// duplicate instances, links, or outbound messages. Only successful
+/// responses are cached — errors stay retryable. Replays carry an
+/// `Idempotency-Replayed: true` header. Reusing a key with a different
+/// request body is a client bug, not a retry, and gets 409 instead of a
+/// silent replay that would swallow the second mutation.
+async fn idempotency_middleware(request: Request, next: Next) -> Response {
+    use axum::http::Method;
+    use sha2::Digest as _;
+
+    let mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    let key = request
+        .headers()
+        .get("idempotency-key")
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| !key.is_empty() && mutating)
+        .map(str::to_string);
+    let Some(key) = key else {
+        return next.run(request).await;
+    };
+
+    // Scope the key to method + path so one key reused across endpoints
+    // doesn't replay an unrelated response.
+    let cache_key = format!("{} {} {key}", request.method(), request.uri().path());
+
+    // Buffer the request body so it can be hashed and then handed on.
+    let (parts, body) = request.into_parts();
+    let request_bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(%error, "failed to buffer request for idempotency check");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+    let request_hash: [u8; 32] = sha2::Sha256::digest(&request_bytes).into();
+    let request = Request::from_parts(parts, axum::body::Body::from(request_bytes));
+
+    {
+        let cache = IDEMPOTENCY_CACHE.lock().expect("idempotency lock poisoned");
+        if let Some(cached) = cache.get(&cache_key)
+            && cached.stored_at.elapsed() < IDEMPOTENCY_WINDOW
+        {
+            if cached.request_hash != request_hash {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "Idempotency-Key reused with a different request body"
+                    })),
+                )
+                    .into_response();
+            }
+            let mut response = Response::new(axum::body::Body::from(cached.body.clone()));
+            *response.status_mut() = cached.status;
+            if let Some(content_type) = &cached.content_type {
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_TYPE, content_type.clone());
+            }
+            response.headers_mut().insert(
+                "idempotency-replayed",
+                header::HeaderValue::from_static("true"),
+            );
+            return response;
+        }
+    }
+
+    let response = next.run(request).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    // Buffer the body so it can be both cached and returned.
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(%error, "failed to buffer response for idempotency cache");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    {
+        let mut cache = IDEMPOTENCY_CACHE.lock().expect("idempotency lock poisoned");
+        cache.retain(|_, cached| cached.stored_at.elapsed() < IDEMPOTENCY_WINDOW);
+        if cache.len() >= IDEMPOTENCY_CACHE_CAP
+            && let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.stored_at)
+                .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest);
+        }
+        cache.insert(
+            cache_key,
+            CachedResponse {
+                status: parts.status,
+                content_type: parts.headers.get(header::CONTENT_TYPE).cloned(),
+                body: bytes.clone(),
+                request_hash,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    Response::from_parts(parts, axum::body::Body::from(bytes))
+}
+
 async fn static_handler(uri: Uri) -> Response {
     let path = uri.path().trim_start_matches('/');
 
@@ -288,3 +510,88 @@ async fn static_handler(uri: Uri) -> Response {
 
     (StatusCode::NOT_FOUND, "not found").into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt as _;
+
+    /// A router whose handler counts invocations, wrapped in the middleware.
+    fn test_router(hits: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/mutate",
+                post(move || {
+                    let hits = hits.clone();
+                    async move {
+                        hits.fetch_add(1, Ordering::SeqCst);
+                        "done"
+                    }
+                }),
+            )
+            .layer(middleware::from_fn(idempotency_middleware))
+    }
+
+    fn post_with_key(key: &str, body: &str) -> Request {
+        axum::http::Request::builder()
+            .method("POST")
+            .uri("/mutate")
+            .header("idempotency-key", key)
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    // The cache is a process-wide static, so each test uses its own key.
+
+    #[tokio::test]
+    async fn repeated_key_with_same_body_replays() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let router = test_router(hits.clone());
+
+        let first = router
+            .clone()
+            .oneshot(post_with_key("replay-test", r#"{"a":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+
+        let second = router
+            .oneshot(post_with_key("replay-test", r#"{"a":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(
+            second
+                .headers()
+                .get("idempotency-replayed")
+                .and_then(|value| value.to_str().ok()),
+            Some("true")
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 1, "handler ran twice");
+    }
+
+    #[tokio::test]
+    async fn repeated_key_with_different_body_conflicts() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let router = test_router(hits.clone());
+
+        let first = router
+            .clone()
+            .oneshot(post_with_key("conflict-test", r#"{"a":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+
+        // Same key, different payload: the second mutation must not be
+        // silently swallowed by a replay of the first response.
+        let second = router
+            .oneshot(post_with_key("conflict-test", r#"{"a":2}"#))
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::CONFLICT);
+        assert!(second.headers().get("idempotency-replayed").is_none());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -233,7 +233,7 @@ pub(super) async fn registry_browse(
 
     let url = format!("https://skills.sh/api/skills/{}/{}", view, query.page);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .get(&url)
         .timeout(Duration::from_secs(10))
@@ -283,7 +283,7 @@ pub(super) async fn registry_search(
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .get("https://skills.sh/api/search")
         .query(&[
@@ -9,6 +9,7 @@ use crate::llm::LlmManager;
 use crate::mcp::McpManager;
 use crate::memory::{EmbeddingModel, MemorySearch};
 use crate::messaging::MessagingManager;
+use crate::messaging::hooks::HooksAdapter;
 use crate::messaging::webchat::WebChatAdapter;
 use crate::prompts::PromptEngine;
 use crate::tasks::TaskStore;
@@ -56,6 +57,8 @@ pub struct ApiState {
     /// Live channel states for active channels, keyed by channel_id.
     /// Used by the cancel API to abort workers and branches.
     pub channel_states: RwLock<HashMap<String, ChannelState>>,
+    /// Sandbox conversations forked for what-if replay, keyed by fork ID.
+    pub(crate) conversation_forks: RwLock<HashMap<String, super::forks::ConversationFork>>,
     /// Per-agent cortex chat sessions.
     pub cortex_chat_sessions: arc_swap::ArcSwap<HashMap<String, Arc<CortexChatSession>>>,
     /// Per-agent workspace paths for identity file access.
@@ -76,10 +79,21 @@ pub struct ApiState {
     pub sandboxes: ArcSwap<HashMap<String, Arc<crate::sandbox::Sandbox>>>,
     /// Shared reference to the Discord permissions ArcSwap (same instance used by the adapter and file watcher).
     pub discord_permissions: RwLock<Option<Arc<ArcSwap<DiscordPermissions>>>>,
+    /// Telegram adapters running in webhook mode, keyed by runtime key, so
+    /// the webhook route can feed deliveries into the right update loop.
+    #[cfg(feature = "adapter-telegram")]
+    pub telegram_webhook_adapters:
+        RwLock<HashMap<String, Arc<crate::messaging::telegram::TelegramAdapter>>>,
     /// Shared reference to the Slack permissions ArcSwap (same instance used by the adapter and file watcher).
     pub slack_permissions: RwLock<Option<Arc<ArcSwap<SlackPermissions>>>>,
     /// Shared reference to the bindings ArcSwap (same instance used by the main loop and file watcher).
     pub bindings: RwLock<Option<Arc<ArcSwap<Vec<Binding>>>>>,
+    /// Shared binding pin store (same instance used by the main loop and live channels).
+    pub binding_pins: RwLock<Option<crate::binding_pins::BindingPinStore>>,
+    pub maintenance: RwLock<Option<crate::maintenance::MaintenanceStore>>,
+    pub quarantine: RwLock<Option<crate::quarantine::QuarantineStore>>,
+    /// Shared reference to the feature flags ArcSwap (same instance consumers evaluate against).
+    pub feature_flags: RwLock<Option<Arc<ArcSwap<crate::flags::FeatureFlags>>>>,
     /// Shared messaging manager for runtime adapter addition.
     pub messaging_manager: RwLock<Option<Arc<MessagingManager>>>,
     /// Sender to signal the main event loop that provider keys have been configured.
@@ -92,6 +106,11 @@ pub struct ApiState {
     pub llm_manager: RwLock<Option<Arc<LlmManager>>>,
     /// Shared embedding model for agent creation.
     pub embedding_model: RwLock<Option<Arc<EmbeddingModel>>>,
+    /// Startup self-test results, for the diagnostics endpoint.
+    pub self_test: RwLock<Option<crate::selftest::SelfTestReport>>,
+    /// Whether the instance is running in safe mode (critical self-test
+    /// failure; only the management API is up).
+    pub safe_mode: std::sync::atomic::AtomicBool,
     /// Prompt engine snapshot for agent creation.
     pub prompt_engine: RwLock<Option<PromptEngine>>,
     /// Instance-level defaults for resolving new agent configs.
@@ -102,6 +121,8 @@ pub struct ApiState {
     pub agent_remove_tx: mpsc::Sender<String>,
     /// Shared webchat adapter for session management from API handlers.
     pub webchat_adapter: ArcSwap<Option<Arc<WebChatAdapter>>>,
+    /// Shared hooks adapter so persona endpoints can wait for replies.
+    pub hooks_adapter: ArcSwap<Option<Arc<HooksAdapter>>>,
     /// Instance-level agent links for the communication graph.
     pub agent_links: ArcSwap<Vec<crate::links::AgentLink>>,
     /// Visual agent groups for the topology UI.
@@ -171,6 +192,16 @@ pub enum ApiEvent {
         branch_id: String,
         conclusion: String,
     },
+    /// Progress heartbeat from a long-running worker or cron generation.
+    GenerationProgress {
+        agent_id: String,
+        channel_id: Option<String>,
+        process_type: String,
+        process_id: String,
+        turns: u64,
+        output_tokens: u64,
+        total_tokens: u64,
+    },
     /// A tool call started on a process.
     ToolStarted {
         agent_id: String,
@@ -231,6 +262,7 @@ impl ApiState {
             memory_searches: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             channel_status_blocks: RwLock::new(HashMap::new()),
             channel_states: RwLock::new(HashMap::new()),
+            conversation_forks: RwLock::new(HashMap::new()),
             cortex_chat_sessions: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             agent_workspaces: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             config_path: RwLock::new(PathBuf::new()),
@@ -241,19 +273,28 @@ impl ApiState {
             mcp_managers: ArcSwap::from_pointee(HashMap::new()),
             sandboxes: ArcSwap::from_pointee(HashMap::new()),
             discord_permissions: RwLock::new(None),
+            #[cfg(feature = "adapter-telegram")]
+            telegram_webhook_adapters: RwLock::new(HashMap::new()),
             slack_permissions: RwLock::new(None),
             bindings: RwLock::new(None),
+            binding_pins: RwLock::new(None),
+            maintenance: RwLock::new(None),
+            quarantine: RwLock::new(None),
+            feature_flags: RwLock::new(None),
             messaging_manager: RwLock::new(None),
             provider_setup_tx,
             update_status: crate::update::new_shared_status(),
             instance_dir: ArcSwap::from_pointee(PathBuf::new()),
             llm_manager: RwLock::new(None),
             embedding_model: RwLock::new(None),
+            self_test: RwLock::new(None),
+            safe_mode: std::sync::atomic::AtomicBool::new(false),
             prompt_engine: RwLock::new(None),
             defaults_config: RwLock::new(None),
             agent_tx,
             agent_remove_tx,
             webchat_adapter: ArcSwap::from_pointee(None),
+            hooks_adapter: ArcSwap::from_pointee(None),
             agent_links: ArcSwap::from_pointee(Vec::new()),
             agent_groups: ArcSwap::from_pointee(Vec::new()),
             agent_humans: ArcSwap::from_pointee(Vec::new()),
@@ -380,6 +421,27 @@ impl ApiState {
                                     })
                                     .ok();
                             }
+                            ProcessEvent::GenerationProgress {
+                                process_id,
+                                channel_id,
+                                turns,
+                                output_tokens,
+                                total_tokens,
+                                ..
+                            } => {
+                                let (process_type, id_str) = process_id_info(process_id);
+                                api_tx
+                                    .send(ApiEvent::GenerationProgress {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.as_deref().map(|s| s.to_string()),
+                                        process_type,
+                                        process_id: id_str,
+                                        turns: *turns,
+                                        output_tokens: *output_tokens,
+                                        total_tokens: *total_tokens,
+                                    })
+                                    .ok();
+                            }
                             ProcessEvent::ToolStarted {
                                 process_id,
                                 channel_id,
@@ -538,6 +600,19 @@ impl ApiState {
         self.sandboxes.store(Arc::new(sandboxes));
     }
 
+    /// Route webhook deliveries for this runtime key to the given Telegram adapter.
+    #[cfg(feature = "adapter-telegram")]
+    pub async fn insert_telegram_webhook_adapter(
+        &self,
+        runtime_key: &str,
+        adapter: Arc<crate::messaging::telegram::TelegramAdapter>,
+    ) {
+        self.telegram_webhook_adapters
+            .write()
+            .await
+            .insert(runtime_key.to_string(), adapter);
+    }
+
     /// Share the Discord permissions ArcSwap with the API so reads get hot-reloaded values.
     pub async fn set_discord_permissions(&self, permissions: Arc<ArcSwap<DiscordPermissions>>) {
         *self.discord_permissions.write().await = Some(permissions);
@@ -553,6 +628,24 @@ impl ApiState {
         *self.bindings.write().await = Some(bindings);
     }
 
+    /// Share the binding pin store so the API can manage per-binding knowledge.
+    pub async fn set_binding_pins(&self, store: crate::binding_pins::BindingPinStore) {
+        *self.binding_pins.write().await = Some(store);
+    }
+
+    pub async fn set_maintenance(&self, store: crate::maintenance::MaintenanceStore) {
+        *self.maintenance.write().await = Some(store);
+    }
+
+    pub async fn set_quarantine(&self, store: crate::quarantine::QuarantineStore) {
+        *self.quarantine.write().await = Some(store);
+    }
+
+    /// Share the feature flags ArcSwap so the API can evaluate and hot-swap flags.
+    pub async fn set_feature_flags(&self, flags: Arc<ArcSwap<crate::flags::FeatureFlags>>) {
+        *self.feature_flags.write().await = Some(flags);
+    }
+
     /// Share the messaging manager for runtime adapter addition from API handlers.
     pub async fn set_messaging_manager(&self, manager: Arc<MessagingManager>) {
         *self.messaging_manager.write().await = Some(manager);
@@ -573,6 +666,11 @@ impl ApiState {
         *self.embedding_model.write().await = Some(model);
     }
 
+    /// Record the startup self-test report for the diagnostics endpoint.
+    pub async fn set_self_test(&self, report: crate::selftest::SelfTestReport) {
+        *self.self_test.write().await = Some(report);
+    }
+
     /// Set the prompt engine snapshot for runtime agent creation.
     pub async fn set_prompt_engine(&self, engine: PromptEngine) {
         *self.prompt_engine.write().await = Some(engine);
@@ -588,6 +686,11 @@ impl ApiState {
         self.webchat_adapter.store(Arc::new(Some(adapter)));
     }
 
+    /// Set the shared hooks adapter for the persona endpoints.
+    pub fn set_hooks_adapter(&self, adapter: Arc<HooksAdapter>) {
+        self.hooks_adapter.store(Arc::new(Some(adapter)));
+    }
+
     /// Set the agent links for the communication graph.
     pub fn set_agent_links(&self, links: Vec<crate::links::AgentLink>) {
         self.agent_links.store(Arc::new(links));
@@ -40,6 +40,21 @@ pub(super) async fn health() -> Json<HealthResponse> {
     Json(HealthResponse { status: "ok" })
 }
 
+#[derive(Serialize)]
+pub(super) struct SelfTestResponse {
+    /// True when a critical check failed and only the management API is up.
+    safe_mode: bool,
+    report: Option<crate::selftest::SelfTestReport>,
+}
+
+/// Startup self-test diagnostics, including the safe-mode flag.
+pub(super) async fn self_test(State(state): State<Arc<ApiState>>) -> Json<SelfTestResponse> {
+    Json(SelfTestResponse {
+        safe_mode: state.safe_mode.load(std::sync::atomic::Ordering::Relaxed),
+        report: state.self_test.read().await.clone(),
+    })
+}
+
 /// Reports whether the instance is idle (no active workers or branches).
 /// Used by the platform to gate rolling updates.
 pub(super) async fn idle(State(state): State<Arc<ApiState>>) -> Json<IdleResponse> {
@@ -90,6 +105,7 @@ pub(super) async fn events_sse(
                             ApiEvent::WorkerCompleted { .. } => "worker_completed",
                             ApiEvent::BranchStarted { .. } => "branch_started",
                             ApiEvent::BranchCompleted { .. } => "branch_completed",
+                            ApiEvent::GenerationProgress { .. } => "generation_progress",
                             ApiEvent::ToolStarted { .. } => "tool_started",
                             ApiEvent::ToolCompleted { .. } => "tool_completed",
                             ApiEvent::ConfigReloaded => "config_reloaded",
@@ -0,0 +1,130 @@
+use super::state::ApiState;
+
+use crate::templates::{TemplateInfo, TemplateStore};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct TemplateQuery {
+    name: String,
+    #[serde(default)]
+    platform: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PutTemplateRequest {
+    name: String,
+    #[serde(default)]
+    platform: Option<String>,
+    source: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct TemplatesListResponse {
+    templates: Vec<TemplateInfo>,
+}
+
+#[derive(Serialize)]
+pub(super) struct TemplateContentResponse {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    platform: Option<String>,
+    source: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct TemplateMutationResponse {
+    success: bool,
+}
+
+fn template_err(status: StatusCode, message: impl Into<String>) -> (StatusCode, String) {
+    (status, message.into())
+}
+
+fn open_store(state: &ApiState) -> Result<TemplateStore, (StatusCode, String)> {
+    let instance_dir = state.instance_dir.load();
+    TemplateStore::new(instance_dir.join("templates")).map_err(|error| {
+        template_err(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to open template store: {error}"),
+        )
+    })
+}
+
+/// GET /api/templates — list stored templates and their platform variants.
+pub(super) async fn list_templates(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<TemplatesListResponse>, (StatusCode, String)> {
+    let store = open_store(&state)?;
+    let templates = store.list().map_err(|error| {
+        template_err(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to list templates: {error}"),
+        )
+    })?;
+    Ok(Json(TemplatesListResponse { templates }))
+}
+
+/// GET /api/templates/content — fetch one template variant's source.
+pub(super) async fn get_template(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<TemplateQuery>,
+) -> Result<Json<TemplateContentResponse>, (StatusCode, String)> {
+    let store = open_store(&state)?;
+    let source = store
+        .get(&query.name, query.platform.as_deref())
+        .map_err(|error| template_err(StatusCode::BAD_REQUEST, error.to_string()))?
+        .ok_or_else(|| {
+            template_err(
+                StatusCode::NOT_FOUND,
+                format!("no template named '{}'", query.name),
+            )
+        })?;
+
+    Ok(Json(TemplateContentResponse {
+        name: query.name,
+        platform: query.platform,
+        source,
+    }))
+}
+
+/// PUT /api/templates — create or update a template variant.
+pub(super) async fn put_template(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<PutTemplateRequest>,
+) -> Result<Json<TemplateMutationResponse>, (StatusCode, String)> {
+    let store = open_store(&state)?;
+    store
+        .put(&request.name, request.platform.as_deref(), &request.source)
+        .map_err(|error| template_err(StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    tracing::info!(
+        name = %request.name,
+        platform = ?request.platform,
+        "template saved via API"
+    );
+    Ok(Json(TemplateMutationResponse { success: true }))
+}
+
+/// DELETE /api/templates — delete a template variant.
+pub(super) async fn delete_template(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<TemplateQuery>,
+) -> Result<Json<TemplateMutationResponse>, (StatusCode, String)> {
+    let store = open_store(&state)?;
+    let removed = store
+        .delete(&query.name, query.platform.as_deref())
+        .map_err(|error| template_err(StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    if !removed {
+        return Err(template_err(
+            StatusCode::NOT_FOUND,
+            format!("no template named '{}'", query.name),
+        ));
+    }
+    Ok(Json(TemplateMutationResponse { success: true }))
+}
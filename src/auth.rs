@@ -32,7 +32,7 @@ impl OAuthCredentials {
 
     /// Refresh the access token. Returns new credentials with updated tokens.
     pub async fn refresh(&self) -> Result<Self> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         let body = serde_json::json!({
             "grant_type": "refresh_token",
             "refresh_token": self.refresh_token,
@@ -135,7 +135,7 @@ pub async fn exchange_code(code_with_state: &str, verifier: &str) -> Result<OAut
         .split_once('#')
         .unwrap_or((code_with_state, ""));
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let body = serde_json::json!({
         "code": code,
         "state": state,
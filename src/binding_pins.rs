@@ -0,0 +1,285 @@
+//! Pinned knowledge scoped to a channel binding.
+//!
+//! Operators pin facts or standing instructions to a binding — "in #support,
+//! always link the status page" — with `!pin add <text>`, `!pin remove <id>`,
+//! and `!pin list`, or through the `/bindings/pins` API. Pins live outside the
+//! agent prompt in `binding_pins.json` in the instance directory and are
+//! injected into the system prompt only for conversations routed by that
+//! binding, so the same agent can carry different standing context per venue.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// File in the instance directory holding pins for all bindings.
+const BINDING_PINS_FILE: &str = "binding_pins.json";
+
+/// One piece of knowledge pinned to a binding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingPin {
+    pub id: String,
+    pub content: String,
+    /// Who pinned it: "chat" (command) or "api".
+    pub source: String,
+    pub pinned_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl BindingPin {
+    fn new(content: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            content: content.into(),
+            source: source.into(),
+            pinned_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Pinned knowledge shared between the inbound loop, live channels, and the
+/// API, keyed by binding key (`{adapter_key}/{agent_id}`).
+#[derive(Clone)]
+pub struct BindingPinStore {
+    path: PathBuf,
+    pins: Arc<RwLock<HashMap<String, Vec<BindingPin>>>>,
+}
+
+impl BindingPinStore {
+    /// Load pins from the instance directory, starting empty when the file
+    /// doesn't exist yet.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(BINDING_PINS_FILE);
+        let pins = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            pins: Arc::new(RwLock::new(pins)),
+        }
+    }
+
+    /// Pin a piece of knowledge to a binding, returning the stored pin.
+    pub async fn add(
+        &self,
+        binding_key: &str,
+        content: &str,
+        source: &str,
+    ) -> BindingPin {
+        let pin = BindingPin::new(content, source);
+        let mut pins = self.pins.write().await;
+        pins.entry(binding_key.to_string())
+            .or_default()
+            .push(pin.clone());
+        self.save(&pins);
+        pin
+    }
+
+    /// Remove a pin by id. Returns false when no pin with that id exists for
+    /// the binding.
+    pub async fn remove(&self, binding_key: &str, id: &str) -> bool {
+        let mut pins = self.pins.write().await;
+        let Some(entries) = pins.get_mut(binding_key) else {
+            return false;
+        };
+        let before = entries.len();
+        entries.retain(|pin| pin.id != id);
+        let removed = entries.len() != before;
+        if entries.is_empty() {
+            pins.remove(binding_key);
+        }
+        if removed {
+            self.save(&pins);
+        }
+        removed
+    }
+
+    /// Pins currently attached to a binding.
+    pub async fn pins_for(&self, binding_key: &str) -> Vec<BindingPin> {
+        self.pins
+            .read()
+            .await
+            .get(binding_key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// All pins grouped by binding key, for the API listing.
+    pub async fn all(&self) -> HashMap<String, Vec<BindingPin>> {
+        self.pins.read().await.clone()
+    }
+
+    /// Handle a `!pin` command, returning the reply text when the message was
+    /// a command (the caller responds and skips agent routing). `binding_key`
+    /// is the binding that routed the message, if any.
+    pub async fn handle_command(
+        &self,
+        message: &crate::InboundMessage,
+        binding_key: Option<&str>,
+    ) -> Option<String> {
+        let text = message.content.to_string();
+        let rest = text.trim().strip_prefix("!pin")?.trim().to_string();
+
+        let Some(binding_key) = binding_key else {
+            return Some(
+                "This conversation isn't routed by a binding, so there's nothing to pin to."
+                    .to_string(),
+            );
+        };
+
+        if rest == "list" {
+            let pins = self.pins_for(binding_key).await;
+            if pins.is_empty() {
+                return Some("No knowledge is pinned to this binding.".to_string());
+            }
+            let lines: Vec<String> = pins
+                .iter()
+                .map(|pin| format!("- [{}] {}", pin.id, pin.content))
+                .collect();
+            return Some(format!("Pinned to this binding:\n{}", lines.join("\n")));
+        }
+
+        if let Some(content) = rest.strip_prefix("add ") {
+            let content = content.trim();
+            if content.is_empty() {
+                return Some("Usage: !pin add <text>".to_string());
+            }
+            let pin = self.add(binding_key, content, "chat").await;
+            return Some(format!(
+                "Pinned [{}]; it will be included in every conversation on this binding.",
+                pin.id
+            ));
+        }
+
+        if let Some(id) = rest.strip_prefix("remove ") {
+            let id = id.trim();
+            if self.remove(binding_key, id).await {
+                return Some(format!("Removed pin [{id}]."));
+            }
+            return Some(format!("No pin [{id}] on this binding."));
+        }
+
+        Some("Usage: !pin add <text> | !pin remove <id> | !pin list".to_string())
+    }
+
+    fn save(&self, pins: &HashMap<String, Vec<BindingPin>>) {
+        match serde_json::to_string_pretty(pins) {
+            Ok(contents) => {
+                if let Err(error) = std::fs::write(&self.path, contents) {
+                    tracing::warn!(%error, path = %self.path.display(), "failed to save binding pins");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(%error, "failed to serialize binding pins");
+            }
+        }
+    }
+}
+
+/// Handle a live channel holds to render the pins for the binding that routed
+/// it. Reads through to the shared store, so pins added or removed after the
+/// channel was created still apply on the next turn.
+#[derive(Clone)]
+pub struct ChannelBindingPins {
+    store: BindingPinStore,
+    binding_key: String,
+}
+
+impl ChannelBindingPins {
+    pub fn new(store: BindingPinStore, binding_key: String) -> Self {
+        Self { store, binding_key }
+    }
+
+    /// Formatted pin lines for the system prompt's pinned-context block.
+    pub async fn lines(&self) -> Vec<String> {
+        self.store
+            .pins_for(&self.binding_key)
+            .await
+            .iter()
+            .map(|pin| format!("- [{}] {}", pin.id, pin.content))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BindingPinStore;
+    use crate::{InboundMessage, MessageContent};
+
+    fn message(text: &str) -> InboundMessage {
+        InboundMessage {
+            id: "mid".into(),
+            source: "discord".into(),
+            adapter: None,
+            conversation_id: "c1".into(),
+            sender_id: "alice".into(),
+            agent_id: None,
+            content: MessageContent::Text(text.into()),
+            timestamp: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            formatted_author: None,
+        }
+    }
+
+    fn temp_store() -> BindingPinStore {
+        let dir = std::env::temp_dir().join(format!("spacebot-pins-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        BindingPinStore::load(&dir)
+    }
+
+    #[tokio::test]
+    async fn add_list_remove_round_trip() {
+        let store = temp_store();
+        let reply = store
+            .handle_command(&message("!pin add always link the status page"), Some("discord/support"))
+            .await
+            .expect("command handled");
+        assert!(reply.contains("Pinned ["));
+
+        let pins = store.pins_for("discord/support").await;
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].content, "always link the status page");
+        assert_eq!(pins[0].source, "chat");
+
+        // Pins are scoped to the binding they were created on.
+        assert!(store.pins_for("discord/general").await.is_empty());
+
+        let listing = store
+            .handle_command(&message("!pin list"), Some("discord/support"))
+            .await
+            .unwrap();
+        assert!(listing.contains("status page"));
+
+        let id = pins[0].id.clone();
+        let removal = store
+            .handle_command(&message(&format!("!pin remove {id}")), Some("discord/support"))
+            .await
+            .unwrap();
+        assert!(removal.contains("Removed"));
+        assert!(store.pins_for("discord/support").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn command_without_binding_explains_scope() {
+        let store = temp_store();
+        let reply = store
+            .handle_command(&message("!pin add something"), None)
+            .await
+            .unwrap();
+        assert!(reply.contains("isn't routed by a binding"));
+    }
+
+    #[tokio::test]
+    async fn pins_persist_across_reload() {
+        let dir = std::env::temp_dir().join(format!("spacebot-pins-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = BindingPinStore::load(&dir);
+        store.add("telegram/bot", "office hours are 9-5 UTC", "api").await;
+
+        let reloaded = BindingPinStore::load(&dir);
+        let pins = reloaded.pins_for("telegram/bot").await;
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].source, "api");
+    }
+}
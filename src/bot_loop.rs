@@ -0,0 +1,139 @@
+//! Bot-to-bot loop detection and suppression.
+//!
+//! Two bots sharing a channel can feed each other replies forever. The guard
+//! inspects inbound messages for bot authorship (the platform bot flag
+//! adapters surface as `sender_is_bot` metadata, plus `reply_to_is_bot` for
+//! reply chains) and rate-limits how often the agent answers other bots in a
+//! conversation: a bot replying to a bot message is dropped outright as a
+//! feedback chain, and other bot-authored messages get a small reply budget
+//! per window so legitimate bridge and integration bots still work. A human
+//! message resets the conversation's streak. Enforced per binding through
+//! `bot_loop_protection` (on by default).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Rolling window over which bot-authored replies are budgeted.
+const BOT_LOOP_WINDOW_SECS: u64 = 120;
+
+/// Bot-authored messages answered per conversation per window.
+const BOT_LOOP_MAX_REPLIES: usize = 3;
+
+/// Whether the sending platform flagged the author as a bot.
+pub fn is_bot_message(message: &crate::InboundMessage) -> bool {
+    message
+        .metadata
+        .get("sender_is_bot")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether the message replies to a bot-authored message.
+fn replies_to_bot(message: &crate::InboundMessage) -> bool {
+    message
+        .metadata
+        .get("reply_to_is_bot")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Per-conversation state for bot loop suppression, shared by the inbound loop.
+#[derive(Clone, Default)]
+pub struct BotLoopGuard {
+    /// Timestamps of recently answered bot messages, keyed by conversation.
+    replies: Arc<Mutex<HashMap<String, Vec<Instant>>>>,
+}
+
+impl BotLoopGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decide whether to suppress the response to this message, recording it
+    /// against the conversation's reply budget when allowed. Returns the
+    /// suppression reason, or None when the agent should respond normally.
+    pub fn check(&self, message: &crate::InboundMessage) -> Option<&'static str> {
+        let mut replies = self.replies.lock().expect("bot loop guard lock poisoned");
+
+        if !is_bot_message(message) {
+            // A human message breaks any ping-pong pattern.
+            replies.remove(&message.conversation_id);
+            return None;
+        }
+
+        // A bot replying to a bot message is the feedback shape itself.
+        if replies_to_bot(message) {
+            return Some("bot reply to a bot message");
+        }
+
+        let recent = replies.entry(message.conversation_id.clone()).or_default();
+        let window = std::time::Duration::from_secs(BOT_LOOP_WINDOW_SECS);
+        let now = Instant::now();
+        recent.retain(|instant| now.duration_since(*instant) < window);
+
+        if recent.len() >= BOT_LOOP_MAX_REPLIES {
+            return Some("bot reply budget for this conversation is exhausted");
+        }
+
+        recent.push(now);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BOT_LOOP_MAX_REPLIES, BotLoopGuard};
+    use crate::{InboundMessage, MessageContent};
+
+    fn message(conversation: &str, bot: bool, reply_to_bot: bool) -> InboundMessage {
+        let mut metadata = std::collections::HashMap::new();
+        if bot {
+            metadata.insert("sender_is_bot".into(), true.into());
+        }
+        if reply_to_bot {
+            metadata.insert("reply_to_is_bot".into(), true.into());
+        }
+        InboundMessage {
+            id: "mid".into(),
+            source: "discord".into(),
+            adapter: None,
+            conversation_id: conversation.into(),
+            sender_id: "other-bot".into(),
+            agent_id: None,
+            content: MessageContent::Text("ping".into()),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: None,
+        }
+    }
+
+    #[test]
+    fn humans_are_never_suppressed() {
+        let guard = BotLoopGuard::new();
+        assert!(guard.check(&message("c1", false, false)).is_none());
+        assert!(guard.check(&message("c1", false, true)).is_none());
+    }
+
+    #[test]
+    fn bot_replies_to_bots_are_suppressed_immediately() {
+        let guard = BotLoopGuard::new();
+        assert!(guard.check(&message("c1", true, true)).is_some());
+    }
+
+    #[test]
+    fn bot_budget_exhausts_then_human_resets() {
+        let guard = BotLoopGuard::new();
+        for _ in 0..BOT_LOOP_MAX_REPLIES {
+            assert!(guard.check(&message("c1", true, false)).is_none());
+        }
+        assert!(guard.check(&message("c1", true, false)).is_some());
+
+        // A separate conversation has its own budget.
+        assert!(guard.check(&message("c2", true, false)).is_none());
+
+        // Human activity clears the streak.
+        guard.check(&message("c1", false, false));
+        assert!(guard.check(&message("c1", true, false)).is_none());
+    }
+}
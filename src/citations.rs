@@ -0,0 +1,139 @@
+//! Source tracking for response citations.
+//!
+//! Retrieval tools (memory recall, web search) record what they surfaced,
+//! keyed by the channel doing the work; when `cite_sources` is enabled the
+//! reply tool drains the channel's sources and appends platform-appropriate
+//! footnotes. The registry is process-global because recall happens in
+//! branch and worker tool servers that only share a channel ID with the
+//! replying channel.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Sources kept per channel before the oldest are dropped.
+const MAX_SOURCES_PER_CHANNEL: usize = 10;
+
+static SOURCES: LazyLock<Mutex<HashMap<String, Vec<Source>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// One retrieved source behind a pending reply.
+#[derive(Debug, Clone)]
+pub struct Source {
+    /// Where it came from: "memory", "web", ...
+    pub kind: &'static str,
+    /// Short human-readable label (page title, memory snippet).
+    pub title: String,
+    /// URL or stable identifier, when one exists.
+    pub reference: Option<String>,
+}
+
+/// Record a source for a channel. Repeats of the same title or reference
+/// are collapsed so a re-run search doesn't double up footnotes.
+pub fn record(
+    channel_id: &str,
+    kind: &'static str,
+    title: impl Into<String>,
+    reference: Option<String>,
+) {
+    let title = title.into();
+    let mut sources = SOURCES.lock().expect("citation lock poisoned");
+    let entry = sources.entry(channel_id.to_string()).or_default();
+    if entry.iter().any(|source| {
+        source.title == title || (source.reference.is_some() && source.reference == reference)
+    }) {
+        return;
+    }
+    entry.push(Source {
+        kind,
+        title,
+        reference,
+    });
+    if entry.len() > MAX_SOURCES_PER_CHANNEL {
+        let excess = entry.len() - MAX_SOURCES_PER_CHANNEL;
+        entry.drain(..excess);
+    }
+}
+
+/// Take and clear the channel's recorded sources.
+pub fn drain(channel_id: &str) -> Vec<Source> {
+    SOURCES
+        .lock()
+        .expect("citation lock poisoned")
+        .remove(channel_id)
+        .unwrap_or_default()
+}
+
+/// Render numbered footnotes for the platform the reply goes to. Empty
+/// input renders to an empty string so callers can append unconditionally.
+pub fn format_citations(sources: &[Source], platform: &str) -> String {
+    use std::fmt::Write as _;
+
+    if sources.is_empty() {
+        return String::new();
+    }
+
+    let mut rendered = String::from("\n\nSources:");
+    for (index, source) in sources.iter().enumerate() {
+        let number = index + 1;
+        let title = &source.title;
+        rendered.push('\n');
+        let _ = match (&source.reference, platform) {
+            (Some(reference), "slack") => {
+                write!(rendered, "[{number}] <{reference}|{title}>")
+            }
+            // Angle brackets suppress Discord's link previews so a footnote
+            // list stays compact.
+            (Some(reference), "discord") => {
+                write!(rendered, "[{number}] [{title}](<{reference}>)")
+            }
+            (Some(reference), _) => write!(rendered, "[{number}] {title} — {reference}"),
+            (None, _) => write!(rendered, "[{number}] {title} ({kind})", kind = source.kind),
+        };
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Source, drain, format_citations, record};
+
+    #[test]
+    fn record_dedupes_and_drain_clears() {
+        let channel = "test:citations:record";
+        record(channel, "web", "Example", Some("https://example.com".into()));
+        record(channel, "web", "Example", Some("https://example.com".into()));
+        record(channel, "memory", "user prefers metric units", None);
+
+        let sources = drain(channel);
+        assert_eq!(sources.len(), 2);
+        assert!(drain(channel).is_empty());
+    }
+
+    #[test]
+    fn formats_footnotes_per_platform() {
+        let sources = vec![
+            Source {
+                kind: "web",
+                title: "Example".into(),
+                reference: Some("https://example.com".into()),
+            },
+            Source {
+                kind: "memory",
+                title: "user prefers metric units".into(),
+                reference: None,
+            },
+        ];
+
+        let slack = format_citations(&sources, "slack");
+        assert!(slack.contains("[1] <https://example.com|Example>"));
+        assert!(slack.contains("[2] user prefers metric units (memory)"));
+
+        let discord = format_citations(&sources, "discord");
+        assert!(discord.contains("[1] [Example](<https://example.com>)"));
+
+        let plain = format_citations(&sources, "email");
+        assert!(plain.contains("[1] Example — https://example.com"));
+
+        assert!(format_citations(&[], "slack").is_empty());
+    }
+}
@@ -59,6 +59,50 @@ pub struct Config {
     pub metrics: MetricsConfig,
     /// OpenTelemetry export configuration.
     pub telemetry: TelemetryConfig,
+    /// Outbound network options (egress proxy).
+    pub network: NetworkConfig,
+}
+
+/// Outbound network configuration shared by all adapters and providers.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    /// Default egress proxy (`socks5://` or `http://`) for all outbound
+    /// HTTP. Per-adapter `proxy` settings override it for that adapter.
+    pub proxy: Option<String>,
+    /// Address-family preference when a host resolves to both A and AAAA
+    /// records. IPv6-only deployments should set `prefer = "ipv6"`.
+    pub ip_preference: IpPreference,
+    /// Custom DNS server IPs (UDP port 53) used instead of the system
+    /// resolver. Ignored when `doh_provider` is set.
+    pub dns_servers: Vec<String>,
+    /// DNS-over-HTTPS provider: `cloudflare`, `google`, or `quad9`.
+    pub doh_provider: Option<String>,
+}
+
+/// Address-family preference for outbound connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IpPreference {
+    /// Use resolver order as returned.
+    #[default]
+    Auto,
+    /// Sort IPv4 addresses first.
+    Ipv4,
+    /// Sort IPv6 addresses first.
+    Ipv6,
+}
+
+impl IpPreference {
+    fn from_toml_value(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("auto") => Self::Auto,
+            Some("ipv4") | Some("4") => Self::Ipv4,
+            Some("ipv6") | Some("6") => Self::Ipv6,
+            Some(other) => {
+                tracing::warn!(value = other, "unknown [network] prefer value, using auto");
+                Self::Auto
+            }
+        }
+    }
 }
 
 /// A link definition from config, connecting two nodes (agents or humans).
@@ -119,6 +163,8 @@ impl Default for ApiConfig {
 pub struct MetricsConfig {
     /// Whether the metrics endpoint is enabled.
     pub enabled: bool,
+    /// Whether hot-path timing breadcrumbs are recorded for `/api/perf`.
+    pub perf_instrumentation: bool,
     /// Port to bind the metrics HTTP server on.
     pub port: u16,
     /// Address to bind the metrics HTTP server on.
@@ -129,6 +175,7 @@ impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
             enabled: false,
+            perf_instrumentation: false,
             port: 9090,
             bind: "0.0.0.0".into(),
         }
@@ -222,6 +269,9 @@ pub struct LlmConfig {
     pub moonshot_key: Option<String>,
     pub zai_coding_plan_key: Option<String>,
     pub providers: HashMap<String, ProviderConfig>,
+    /// Data-residency region per provider ID (lowercase, e.g. "eu"),
+    /// from the `region` key on custom provider sections.
+    pub provider_regions: HashMap<String, String>,
 }
 
 impl std::fmt::Debug for LlmConfig {
@@ -293,6 +343,7 @@ impl std::fmt::Debug for LlmConfig {
                 &self.zai_coding_plan_key.as_ref().map(|_| "[REDACTED]"),
             )
             .field("providers", &self.providers)
+            .field("provider_regions", &self.provider_regions)
             .finish()
     }
 }
@@ -546,8 +597,25 @@ pub struct DefaultsConfig {
     /// Default timezone for channel/worker temporal context.
     pub user_timezone: Option<String>,
     pub history_backfill_count: usize,
+    /// Append source footnotes (retrieved memories, web results) to replies.
+    pub cite_sources: bool,
+    /// Inject current date/time, upcoming cron runs, and recent cron output
+    /// into channel context. On by default.
+    pub time_context: bool,
     pub cron: Vec<CronDef>,
     pub opencode: OpenCodeConfig,
+    /// Host operations tool (Docker / systemd) configuration.
+    pub ops: OpsConfig,
+    /// Read-only Kubernetes observability tool configuration.
+    pub kube: KubeConfig,
+    /// Read-only SQL query tool configuration.
+    pub sql: SqlConfig,
+    /// SSH remote-command tool configuration.
+    pub ssh: SshConfig,
+    /// Translation memory (cached translations for repeated phrases).
+    pub translation_memory: TranslationMemoryConfig,
+    /// Nightly memory maintenance job (consolidation, pruning, reindexing).
+    pub memory_maintenance: MemoryMaintenanceConfig,
     /// Worker log mode: "errors_only", "all_separate", or "all_combined".
     pub worker_log_mode: crate::settings::WorkerLogMode,
 }
@@ -576,8 +644,16 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("cron_timezone", &self.cron_timezone)
             .field("user_timezone", &self.user_timezone)
             .field("history_backfill_count", &self.history_backfill_count)
+            .field("cite_sources", &self.cite_sources)
+            .field("time_context", &self.time_context)
             .field("cron", &self.cron)
             .field("opencode", &self.opencode)
+            .field("ops", &self.ops)
+            .field("kube", &self.kube)
+            .field("sql", &self.sql)
+            .field("ssh", &self.ssh)
+            .field("translation_memory", &self.translation_memory)
+            .field("memory_maintenance", &self.memory_maintenance)
             .field("worker_log_mode", &self.worker_log_mode)
             .finish()
     }
@@ -738,6 +814,194 @@ impl Default for BrowserConfig {
     }
 }
 
+/// Host operations (Docker / systemd) tool configuration.
+///
+/// Opt-in chatops control plane for task workers. Every target must appear on
+/// the relevant allowlist; restarts are additionally gated by `allow_restart`.
+#[derive(Debug, Clone)]
+pub struct OpsConfig {
+    /// Whether the ops tool is available to workers at all.
+    pub enabled: bool,
+    /// Allow Docker container operations via the local socket.
+    pub docker_enabled: bool,
+    /// Allow systemd unit operations via systemctl/journalctl.
+    pub systemd_enabled: bool,
+    /// Container names the tool may list, restart, or tail logs for.
+    pub allowed_containers: Vec<String>,
+    /// systemd unit names the tool may list, restart, or tail logs for.
+    pub allowed_units: Vec<String>,
+    /// Permit restart actions (listing and log tailing are always read-only).
+    pub allow_restart: bool,
+    /// Maximum number of log lines a single tail request may return.
+    pub log_tail_limit: usize,
+}
+
+impl Default for OpsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            docker_enabled: false,
+            systemd_enabled: false,
+            allowed_containers: Vec::new(),
+            allowed_units: Vec::new(),
+            allow_restart: false,
+            log_tail_limit: 500,
+        }
+    }
+}
+
+/// Read-only Kubernetes observability tool configuration. Disabled by
+/// default; queries are restricted to the allowlisted contexts and namespaces.
+#[derive(Debug, Clone)]
+pub struct KubeConfig {
+    /// Whether the kube tool is available to workers at all.
+    pub enabled: bool,
+    /// kubectl contexts the tool may query. The first entry is the default.
+    pub contexts: Vec<String>,
+    /// Namespaces the tool may query. The first entry is the default.
+    pub namespaces: Vec<String>,
+    /// Maximum number of log lines a single tail request may return.
+    pub log_tail_limit: usize,
+}
+
+impl Default for KubeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            contexts: Vec::new(),
+            namespaces: Vec::new(),
+            log_tail_limit: 500,
+        }
+    }
+}
+
+/// Read-only SQL query tool configuration. The statement filter guards
+/// against accidental writes; point DSNs at read-only roles for real
+/// isolation.
+#[derive(Clone)]
+pub struct SqlConfig {
+    /// Whether the SQL tool is available to workers at all.
+    pub enabled: bool,
+    /// Named databases the tool may query. The first entry is the default.
+    pub databases: Vec<SqlDatabaseConfig>,
+    /// Maximum number of rows a single query may return.
+    pub max_rows: usize,
+}
+
+/// A named database connection for the SQL tool.
+#[derive(Clone)]
+pub struct SqlDatabaseConfig {
+    pub name: String,
+    /// Postgres/MySQL/SQLite DSN, e.g. `postgres://ro@db/app`.
+    pub dsn: String,
+}
+
+impl Default for SqlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            databases: Vec::new(),
+            max_rows: 200,
+        }
+    }
+}
+
+impl std::fmt::Debug for SqlConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.databases.iter().map(|db| db.name.as_str()).collect();
+        f.debug_struct("SqlConfig")
+            .field("enabled", &self.enabled)
+            .field("databases", &names)
+            .field("max_rows", &self.max_rows)
+            .finish()
+    }
+}
+
+/// SSH remote-command tool configuration. Disabled by default; commands
+/// run only on configured hosts and only when they match an allowed prefix.
+#[derive(Debug, Clone)]
+pub struct SshConfig {
+    /// Whether the SSH tool is available to workers at all.
+    pub enabled: bool,
+    /// Whether prefixes flagged destructive on a host may be run.
+    pub allow_destructive: bool,
+    /// Connection timeout in seconds passed to the ssh client.
+    pub connect_timeout_secs: u64,
+    /// Hosts the tool may reach.
+    pub hosts: Vec<SshHostConfig>,
+}
+
+/// A named SSH host with its own command allowlist.
+#[derive(Debug, Clone)]
+pub struct SshHostConfig {
+    /// Name agents use to refer to this host.
+    pub name: String,
+    /// `user@hostname` or plain hostname passed to ssh.
+    pub address: String,
+    pub port: u16,
+    /// Optional identity file; falls back to the ssh client's defaults.
+    pub identity_file: Option<String>,
+    /// Command prefixes that may be executed on this host.
+    pub allowed_commands: Vec<String>,
+    /// Command prefixes additionally gated by `allow_destructive`.
+    pub destructive_commands: Vec<String>,
+}
+
+impl Default for SshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allow_destructive: false,
+            connect_timeout_secs: 10,
+            hosts: Vec::new(),
+        }
+    }
+}
+
+/// Translation memory configuration. When enabled, branches get
+/// `translation_lookup`/`translation_save` tools backed by a per-agent
+/// cache keyed by content hash and language pair.
+#[derive(Debug, Clone)]
+pub struct TranslationMemoryConfig {
+    /// Whether the translation memory tools are available.
+    pub enabled: bool,
+    /// How long cached translations stay valid, in seconds.
+    pub ttl_secs: u64,
+}
+
+impl Default for TranslationMemoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // 30 days: canned support answers are stable on that horizon
+            ttl_secs: 30 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Nightly memory maintenance configuration. When enabled, each agent's
+/// memory store is consolidated, pruned, and reindexed once a day during the
+/// off-hours window (evaluated in `cron_timezone`).
+#[derive(Debug, Clone)]
+pub struct MemoryMaintenanceConfig {
+    pub enabled: bool,
+    /// Off-hours window (start_hour, end_hour) in 24h format.
+    pub active_hours: (u8, u8),
+    /// Delivery target in "adapter:target" format for the change report,
+    /// e.g. an admin channel. `None` only logs.
+    pub report_target: Option<String>,
+}
+
+impl Default for MemoryMaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            active_hours: (3, 5),
+            report_target: None,
+        }
+    }
+}
+
 /// OpenCode subprocess worker configuration.
 #[derive(Debug, Clone)]
 pub struct OpenCodeConfig {
@@ -985,6 +1249,8 @@ pub struct CronDef {
     pub interval_secs: u64,
     /// Delivery target in "adapter:target" format (e.g. "discord:123456789").
     pub delivery_target: String,
+    /// Optional outbound template name the result renders through before delivery.
+    pub template: Option<String>,
     /// Optional active hours window (start_hour, end_hour) in 24h format.
     pub active_hours: Option<(u8, u8)>,
     pub enabled: bool,
@@ -1024,6 +1290,11 @@ pub struct ResolvedAgentConfig {
     pub sandbox: crate::sandbox::SandboxConfig,
     /// Number of messages to fetch from the platform when a new channel is created.
     pub history_backfill_count: usize,
+    /// Append source footnotes (retrieved memories, web results) to replies.
+    pub cite_sources: bool,
+    /// Inject current date/time, upcoming cron runs, and recent cron output
+    /// into channel context. On by default.
+    pub time_context: bool,
     pub cron: Vec<CronDef>,
 }
 
@@ -1048,8 +1319,16 @@ impl Default for DefaultsConfig {
             cron_timezone: None,
             user_timezone: None,
             history_backfill_count: 50,
+            cite_sources: false,
+            time_context: true,
             cron: Vec::new(),
             opencode: OpenCodeConfig::default(),
+            ops: OpsConfig::default(),
+            kube: KubeConfig::default(),
+            sql: SqlConfig::default(),
+            ssh: SshConfig::default(),
+            translation_memory: TranslationMemoryConfig::default(),
+            memory_maintenance: MemoryMaintenanceConfig::default(),
             worker_log_mode: crate::settings::WorkerLogMode::default(),
         }
     }
@@ -1115,6 +1394,8 @@ impl AgentConfig {
             user_timezone: resolved_user_timezone,
             sandbox: self.sandbox.clone().unwrap_or_default(),
             history_backfill_count: defaults.history_backfill_count,
+            cite_sources: defaults.cite_sources,
+            time_context: defaults.time_context,
             cron: self.cron.clone(),
         }
     }
@@ -1182,6 +1463,30 @@ pub struct Binding {
     pub require_mention: bool,
     /// User IDs allowed to DM the bot through this binding.
     pub dm_allowed_users: Vec<String>,
+    /// Onboarding messages sent in order the first time a new sender
+    /// interacts through this binding. Empty disables onboarding.
+    pub onboarding: Vec<String>,
+    /// Provider regions allowed to receive this binding's data (e.g. ["eu"]).
+    /// Empty means no restriction.
+    pub allowed_regions: Vec<String>,
+    /// Trigger prefix required in group chats before the agent responds
+    /// (e.g. `!ask`). DMs bypass the trigger.
+    pub trigger_prefix: Option<String>,
+    /// Wake words that satisfy the trigger anywhere in the message,
+    /// matched case-insensitively.
+    pub wake_words: Vec<String>,
+    /// Suppress feedback loops with other bots: bot replies to bot messages
+    /// are dropped and other bot-authored messages get a small reply budget.
+    pub bot_loop_protection: bool,
+    /// Check URLs in inbound messages against URLhaus/Safe Browsing, warn the
+    /// conversation about flagged links, and refuse to browse them.
+    pub link_safety: bool,
+    /// Announce in the bound channel when the bot comes back online after
+    /// downtime exceeding the threshold below. Needs `chat_id` (or a first
+    /// `channel_ids` entry) to know where to post.
+    pub reconnect_greeting: bool,
+    /// Minimum downtime in seconds before a reconnect greeting is sent.
+    pub reconnect_greeting_threshold_secs: u64,
 }
 
 impl Binding {
@@ -1295,6 +1600,36 @@ impl Binding {
             }
         }
 
+        // Per-binding trigger: group messages must carry the prefix or a wake
+        // word (or mention the bot — adapters flag that in `mentions_bot`,
+        // plus Discord's dedicated key); DMs bypass the trigger entirely.
+        if (self.trigger_prefix.is_some() || !self.wake_words.is_empty())
+            && !message_is_direct(message)
+        {
+            let text = message.content.to_string();
+            let prefix_match = self
+                .trigger_prefix
+                .as_deref()
+                .is_some_and(|prefix| text.trim_start().starts_with(prefix));
+            let lowered = text.to_lowercase();
+            let wake_match = self
+                .wake_words
+                .iter()
+                .any(|word| contains_word(&lowered, &word.to_lowercase()));
+            let mention_match = ["discord_mentions_or_replies_to_bot", "mentions_bot"]
+                .iter()
+                .any(|key| {
+                    message
+                        .metadata
+                        .get(*key)
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false)
+                });
+            if !prefix_match && !wake_match && !mention_match {
+                return false;
+            }
+        }
+
         if let Some(chat_id) = &self.chat_id {
             let message_chat = message.metadata.get("telegram_chat_id").and_then(|value| {
                 value
@@ -1321,6 +1656,55 @@ pub fn binding_runtime_adapter_key(platform: &str, adapter: Option<&str>) -> Str
     platform.to_string()
 }
 
+/// Whether `haystack` contains `needle` on word boundaries, so the wake
+/// word "bot" doesn't fire on "robotics". Both are expected lowercased.
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    let mut start = 0;
+    while let Some(pos) = haystack[start..].find(needle) {
+        let at = start + pos;
+        let end = at + needle.len();
+        let before_ok = haystack[..at]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = haystack[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return true;
+        }
+        start = end;
+    }
+    false
+}
+
+/// Whether a message arrived over a private/direct conversation.
+fn message_is_direct(message: &crate::InboundMessage) -> bool {
+    match message.source.as_str() {
+        "discord" => !message.metadata.contains_key("discord_guild_id"),
+        "telegram" => {
+            message
+                .metadata
+                .get("telegram_chat_type")
+                .and_then(|v| v.as_str())
+                == Some("private")
+        }
+        "slack" => message
+            .metadata
+            .get("slack_channel_id")
+            .and_then(|v| v.as_str())
+            .is_some_and(|id| id.starts_with('D')),
+        "xmpp" => {
+            message.metadata.get("xmpp_type").and_then(|v| v.as_str()) == Some("chat")
+        }
+        _ => false,
+    }
+}
+
 /// Match a binding's adapter selector against an inbound message adapter.
 fn binding_adapter_matches(binding: &Binding, message: &crate::InboundMessage) -> bool {
     match (&binding.adapter, message.adapter_selector()) {
@@ -1583,6 +1967,14 @@ fn validate_runtime_keys(
 ///
 /// Checks bindings in order. First match wins. Falls back to the default
 /// agent if no binding matches.
+/// The first binding that matches an inbound message, if any.
+pub fn matching_binding<'a>(
+    bindings: &'a [Binding],
+    message: &crate::InboundMessage,
+) -> Option<&'a Binding> {
+    bindings.iter().find(|binding| binding.matches(message))
+}
+
 pub fn resolve_agent_for_message(
     bindings: &[Binding],
     message: &crate::InboundMessage,
@@ -1596,6 +1988,19 @@ pub fn resolve_agent_for_message(
     std::sync::Arc::from(default_agent_id)
 }
 
+/// Parse an explicit thread handoff command (`!handoff <agent-id>`).
+///
+/// Returns the target agent ID when the message is exactly a handoff
+/// command, so sticky thread assignments can be re-pointed on demand.
+pub fn parse_handoff_command(text: &str) -> Option<&str> {
+    let rest = text.trim().strip_prefix("!handoff")?;
+    let target = rest.trim();
+    if target.is_empty() || target.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(target)
+}
+
 /// Messaging platform credentials (instance-level).
 #[derive(Debug, Clone, Default)]
 pub struct MessagingConfig {
@@ -1605,6 +2010,35 @@ pub struct MessagingConfig {
     pub email: Option<EmailConfig>,
     pub webhook: Option<WebhookConfig>,
     pub twitch: Option<TwitchConfig>,
+    pub mattermost: Option<MattermostConfig>,
+    pub teams: Option<TeamsConfig>,
+    pub signal: Option<SignalConfig>,
+    pub whatsapp: Option<WhatsAppConfig>,
+    pub sms: Option<SmsConfig>,
+    pub zulip: Option<ZulipConfig>,
+    pub ntfy: Option<NtfyConfig>,
+    pub pushover: Option<PushoverConfig>,
+    pub gotify: Option<GotifyConfig>,
+    pub googlechat: Option<GoogleChatConfig>,
+    pub rocketchat: Option<RocketChatConfig>,
+    pub mastodon: Option<MastodonConfig>,
+    pub bluesky: Option<BlueskyConfig>,
+    pub nostr: Option<NostrConfig>,
+    pub xmpp: Option<XmppConfig>,
+    pub line: Option<LineConfig>,
+    pub reddit: Option<RedditConfig>,
+    pub github: Option<GithubConfig>,
+    pub gitlab: Option<GitlabConfig>,
+    pub jira: Option<JiraConfig>,
+    pub linear: Option<LinearConfig>,
+    pub nextcloud: Option<NextcloudConfig>,
+    pub rss: Option<RssConfig>,
+    pub mqtt: Option<MqttConfig>,
+    pub websocket: Option<WebsocketConfig>,
+    pub console: Option<ConsoleConfig>,
+    pub voice: Option<VoiceConfig>,
+    pub steam: Option<SteamConfig>,
+    pub viber: Option<ViberConfig>,
 }
 
 #[derive(Clone)]
@@ -1617,6 +2051,50 @@ pub struct DiscordConfig {
     pub dm_allowed_users: Vec<String>,
     /// Whether to process messages from other bots (self-messages are always ignored).
     pub allow_bot_messages: bool,
+    /// How often to sync Discord channel visibility into the permission
+    /// filters, in seconds. `None` disables syncing.
+    pub permission_sync_interval_secs: Option<u64>,
+    /// Voice-channel presence (only honored by `discord-voice` builds).
+    pub voice: Option<DiscordVoiceConfig>,
+    /// Egress proxy for REST traffic (`socks5://` or `http://`); the gateway
+    /// websocket still connects directly. Named instances inherit it.
+    pub proxy: Option<String>,
+}
+
+/// Voice-channel presence: channel to join plus the OpenAI-compatible speech
+/// endpoints used for transcription and synthesis.
+#[derive(Clone)]
+pub struct DiscordVoiceConfig {
+    pub enabled: bool,
+    /// Guild hosting the voice channel.
+    pub guild_id: String,
+    /// Voice channel to join on startup.
+    pub channel_id: String,
+    /// Base URL of the speech API, e.g. `https://api.openai.com/v1`.
+    pub api_base: String,
+    /// API key for the speech endpoints.
+    pub api_key: String,
+    /// Transcription model, e.g. `whisper-1`.
+    pub stt_model: String,
+    /// Synthesis model, e.g. `tts-1`.
+    pub tts_model: String,
+    /// Synthesis voice name.
+    pub tts_voice: String,
+}
+
+impl std::fmt::Debug for DiscordVoiceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiscordVoiceConfig")
+            .field("enabled", &self.enabled)
+            .field("guild_id", &self.guild_id)
+            .field("channel_id", &self.channel_id)
+            .field("api_base", &self.api_base)
+            .field("api_key", &"[REDACTED]")
+            .field("stt_model", &self.stt_model)
+            .field("tts_model", &self.tts_model)
+            .field("tts_voice", &self.tts_voice)
+            .finish()
+    }
 }
 
 #[derive(Clone)]
@@ -1628,6 +2106,8 @@ pub struct DiscordInstanceConfig {
     pub dm_allowed_users: Vec<String>,
     /// Whether this bot instance processes messages from other bots.
     pub allow_bot_messages: bool,
+    /// How often this instance syncs channel visibility, in seconds.
+    pub permission_sync_interval_secs: Option<u64>,
 }
 
 impl std::fmt::Debug for DiscordInstanceConfig {
@@ -1638,6 +2118,10 @@ impl std::fmt::Debug for DiscordInstanceConfig {
             .field("token", &"[REDACTED]")
             .field("dm_allowed_users", &self.dm_allowed_users)
             .field("allow_bot_messages", &self.allow_bot_messages)
+            .field(
+                "permission_sync_interval_secs",
+                &self.permission_sync_interval_secs,
+            )
             .finish()
     }
 }
@@ -1650,6 +2134,11 @@ impl std::fmt::Debug for DiscordConfig {
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
             .field("allow_bot_messages", &self.allow_bot_messages)
+            .field(
+                "permission_sync_interval_secs",
+                &self.permission_sync_interval_secs,
+            )
+            .field("voice", &self.voice)
             .finish()
     }
 }
@@ -1909,6 +2398,21 @@ impl DiscordPermissions {
     }
 }
 
+/// A single slash command definition for the Telegram adapter.
+///
+/// Maps a Telegram bot command (e.g. `/ask`) to a target agent. Configured
+/// commands are registered with Telegram via `setMyCommands` so they show up
+/// in the chat command menu.
+#[derive(Debug, Clone)]
+pub struct TelegramCommandConfig {
+    /// The command string exactly as Telegram sends it, e.g. `"/ask"`.
+    pub command: String,
+    /// ID of the agent that should handle this command.
+    pub agent_id: String,
+    /// Short description shown in Telegram's command menu (optional).
+    pub description: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct TelegramConfig {
     pub enabled: bool,
@@ -1917,6 +2421,88 @@ pub struct TelegramConfig {
     pub instances: Vec<TelegramInstanceConfig>,
     /// User IDs allowed to DM the bot. If empty, DMs are ignored entirely.
     pub dm_allowed_users: Vec<String>,
+    /// Only respond in group chats to messages starting with this prefix.
+    pub trigger_prefix: Option<String>,
+    /// Egress proxy for Bot API traffic (`socks5://` or `http://`).
+    /// Named instances inherit it.
+    pub proxy: Option<String>,
+    /// How updates arrive: long polling (the default) or a registered
+    /// webhook served on the API listener. Named instances inherit it.
+    pub mode: TelegramMode,
+    /// Public base URL webhook updates are delivered under; the adapter
+    /// registers `{webhook_url}/api/telegram/webhook/{adapter}` with Telegram.
+    pub webhook_url: String,
+    /// Secret Telegram echoes back in `X-Telegram-Bot-Api-Secret-Token`
+    /// on webhook deliveries. Empty disables verification.
+    pub webhook_secret: String,
+    /// Transcribe inbound voice notes so agents can respond to voice.
+    pub stt: Option<TelegramSttConfig>,
+    /// Slash command definitions registered as the bot's command menu.
+    /// Named instances inherit them.
+    pub commands: Vec<TelegramCommandConfig>,
+    /// Group moderation actions agents may perform where the bot is admin.
+    pub moderation: TelegramModerationConfig,
+}
+
+/// Agent-callable group moderation actions, all off by default. Telegram
+/// additionally requires the bot to be an admin in the target group.
+#[derive(Debug, Clone, Default)]
+pub struct TelegramModerationConfig {
+    /// Allow deleting other users' messages.
+    pub allow_delete: bool,
+    /// Allow muting users (restricting them to read-only).
+    pub allow_mute: bool,
+    /// Allow banning users from the group.
+    pub allow_ban: bool,
+    /// Allow pinning messages.
+    pub allow_pin: bool,
+}
+
+/// Speech-to-text for inbound Telegram voice notes.
+#[derive(Clone)]
+pub struct TelegramSttConfig {
+    pub enabled: bool,
+    /// Base URL of an OpenAI-compatible speech API: `https://api.openai.com/v1`
+    /// or a local whisper server.
+    pub api_base: String,
+    /// API key for the transcription endpoint; empty for local servers.
+    pub api_key: String,
+    /// Transcription model, e.g. `whisper-1`.
+    pub model: String,
+}
+
+impl std::fmt::Debug for TelegramSttConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramSttConfig")
+            .field("enabled", &self.enabled)
+            .field("api_base", &self.api_base)
+            .field("api_key", &"[REDACTED]")
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+/// How the Telegram adapter receives updates from the Bot API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TelegramMode {
+    /// getUpdates long polling (the default).
+    #[default]
+    Polling,
+    /// Updates pushed by Telegram to a webhook route on the API listener.
+    Webhook,
+}
+
+impl TelegramMode {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(|value| value.trim().to_ascii_lowercase()).as_deref() {
+            Some("webhook") => Self::Webhook,
+            Some("polling") | Some("") | None => Self::Polling,
+            Some(other) => {
+                tracing::warn!(mode = other, "unknown telegram mode, falling back to polling");
+                Self::Polling
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -1926,6 +2512,8 @@ pub struct TelegramInstanceConfig {
     pub token: String,
     /// User IDs allowed to DM this bot instance.
     pub dm_allowed_users: Vec<String>,
+    /// Only respond in group chats to messages starting with this prefix.
+    pub trigger_prefix: Option<String>,
 }
 
 impl std::fmt::Debug for TelegramInstanceConfig {
@@ -1935,6 +2523,7 @@ impl std::fmt::Debug for TelegramInstanceConfig {
             .field("enabled", &self.enabled)
             .field("token", &"[REDACTED]")
             .field("dm_allowed_users", &self.dm_allowed_users)
+            .field("trigger_prefix", &self.trigger_prefix)
             .finish()
     }
 }
@@ -1946,10 +2535,42 @@ impl std::fmt::Debug for TelegramConfig {
             .field("token", &"[REDACTED]")
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
+            .field("trigger_prefix", &self.trigger_prefix)
+            .field("mode", &self.mode)
+            .field("webhook_url", &self.webhook_url)
+            .field("stt", &self.stt)
+            .field("commands", &self.commands)
+            .field("moderation", &self.moderation)
             .finish()
     }
 }
 
+/// Which protocol stack the email adapter speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmailBackend {
+    /// IMAP polling + SMTP delivery (the default).
+    #[default]
+    Imap,
+    /// Microsoft Graph API (Exchange Online) using OAuth2 client credentials.
+    Graph,
+    /// JMAP (RFC 8620/8621) for providers like Fastmail, using a bearer token.
+    Jmap,
+}
+
+impl EmailBackend {
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(|value| value.trim().to_ascii_lowercase()).as_deref() {
+            Some("graph") => Self::Graph,
+            Some("jmap") => Self::Jmap,
+            Some("imap") | Some("") | None => Self::Imap,
+            Some(other) => {
+                tracing::warn!(backend = other, "unknown email backend, falling back to imap");
+                Self::Imap
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct EmailConfig {
     pub enabled: bool,
@@ -1970,6 +2591,50 @@ pub struct EmailConfig {
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
     pub max_attachment_bytes: usize,
+    /// Reply to the sender when their mail was truncated or attachments were
+    /// dropped for exceeding the size limits, explaining what to resend.
+    pub size_policy_reply: bool,
+    /// Replies sent to one sender per hour before further responses are
+    /// suppressed. 0 disables the throttle.
+    pub max_replies_per_hour: usize,
+    /// Case-insensitive regexes quarantining mail whose subject or body
+    /// matches. Empty disables pattern filtering.
+    pub spam_patterns: Vec<String>,
+    /// External scorer command run with the raw message on stdin; a nonzero
+    /// exit quarantines the mail. Empty disables the scorer.
+    pub spam_scorer_command: String,
+    /// Quarantine inbound mail whose DKIM or SPF verdict is a failure.
+    /// `allowed_senders` is trivially spoofable without this.
+    pub drop_failed_auth: bool,
+    /// Clearsign outbound replies with PGP by shelling out to `gpg`. Keys
+    /// are read from `{instance_dir}/gnupg` when that directory exists.
+    /// Signed mail is sent as a single text/plain part, since an unsigned
+    /// HTML alternative is what most clients would render instead.
+    pub pgp_sign: bool,
+    /// Key ID or address passed to `gpg --local-user`; empty signs with the
+    /// keyring's default key.
+    pub pgp_key_id: String,
+    /// DKIM selector for signing outbound mail (the `s=` tag). Signing is
+    /// enabled when both this and `dkim_key_path` are set.
+    pub dkim_selector: String,
+    /// Path to the PEM-encoded RSA private key used for DKIM signing;
+    /// signatures are produced by shelling out to `openssl`.
+    pub dkim_key_path: String,
+    /// Extra headers added verbatim to outbound mail, e.g. `List-Unsubscribe`
+    /// or `X-Mailer`.
+    pub custom_headers: HashMap<String, String>,
+    /// Reply to all original To/Cc recipients instead of only the sender.
+    pub reply_all: bool,
+    pub backend: EmailBackend,
+    pub graph_tenant_id: String,
+    pub graph_client_id: String,
+    pub graph_client_secret: String,
+    /// Mailbox (user principal name) the Graph backend operates as.
+    pub graph_user: String,
+    /// JMAP session resource URL (e.g. https://api.fastmail.com/jmap/session).
+    pub jmap_session_url: String,
+    /// Bearer token for the JMAP backend.
+    pub jmap_token: String,
     pub instances: Vec<EmailInstanceConfig>,
 }
 
@@ -1995,6 +2660,44 @@ pub struct EmailInstanceConfig {
     pub allowed_senders: Vec<String>,
     pub max_body_bytes: usize,
     pub max_attachment_bytes: usize,
+    /// Reply to the sender when their mail was truncated or attachments were
+    /// dropped for exceeding the size limits, explaining what to resend.
+    pub size_policy_reply: bool,
+    pub max_replies_per_hour: usize,
+    /// Case-insensitive regexes quarantining mail whose subject or body
+    /// matches. Empty disables pattern filtering.
+    pub spam_patterns: Vec<String>,
+    /// External scorer command run with the raw message on stdin; a nonzero
+    /// exit quarantines the mail. Empty disables the scorer.
+    pub spam_scorer_command: String,
+    /// Quarantine inbound mail whose DKIM or SPF verdict is a failure.
+    /// `allowed_senders` is trivially spoofable without this.
+    pub drop_failed_auth: bool,
+    /// Clearsign outbound replies with PGP by shelling out to `gpg`. Keys
+    /// are read from `{instance_dir}/gnupg` when that directory exists.
+    /// Signed mail is sent as a single text/plain part, since an unsigned
+    /// HTML alternative is what most clients would render instead.
+    pub pgp_sign: bool,
+    /// Key ID or address passed to `gpg --local-user`; empty signs with the
+    /// keyring's default key.
+    pub pgp_key_id: String,
+    /// DKIM selector for signing outbound mail (the `s=` tag). Signing is
+    /// enabled when both this and `dkim_key_path` are set.
+    pub dkim_selector: String,
+    /// Path to the PEM-encoded RSA private key used for DKIM signing;
+    /// signatures are produced by shelling out to `openssl`.
+    pub dkim_key_path: String,
+    /// Extra headers added verbatim to outbound mail, e.g. `List-Unsubscribe`
+    /// or `X-Mailer`.
+    pub custom_headers: HashMap<String, String>,
+    pub reply_all: bool,
+    pub backend: EmailBackend,
+    pub graph_tenant_id: String,
+    pub graph_client_id: String,
+    pub graph_client_secret: String,
+    pub graph_user: String,
+    pub jmap_session_url: String,
+    pub jmap_token: String,
 }
 
 impl std::fmt::Debug for EmailInstanceConfig {
@@ -2019,6 +2722,23 @@ impl std::fmt::Debug for EmailInstanceConfig {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field("size_policy_reply", &self.size_policy_reply)
+            .field("max_replies_per_hour", &self.max_replies_per_hour)
+            .field("spam_patterns", &self.spam_patterns)
+            .field("spam_scorer_command", &self.spam_scorer_command)
+            .field("drop_failed_auth", &self.drop_failed_auth)
+            .field("pgp_sign", &self.pgp_sign)
+            .field("pgp_key_id", &self.pgp_key_id)
+            .field("dkim_selector", &self.dkim_selector)
+            .field("dkim_key_path", &self.dkim_key_path)
+            .field("custom_headers", &self.custom_headers)
+            .field("reply_all", &self.reply_all)
+            .field("backend", &self.backend)
+            .field("graph_tenant_id", &self.graph_tenant_id)
+            .field("graph_client_id", &"[REDACTED]")
+            .field("graph_client_secret", &"[REDACTED]")
+            .field("graph_user", &"[REDACTED]")
+            .field("jmap_session_url", &self.jmap_session_url)
             .finish()
     }
 }
@@ -2044,6 +2764,23 @@ impl std::fmt::Debug for EmailConfig {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field("size_policy_reply", &self.size_policy_reply)
+            .field("max_replies_per_hour", &self.max_replies_per_hour)
+            .field("spam_patterns", &self.spam_patterns)
+            .field("spam_scorer_command", &self.spam_scorer_command)
+            .field("drop_failed_auth", &self.drop_failed_auth)
+            .field("pgp_sign", &self.pgp_sign)
+            .field("pgp_key_id", &self.pgp_key_id)
+            .field("dkim_selector", &self.dkim_selector)
+            .field("dkim_key_path", &self.dkim_key_path)
+            .field("custom_headers", &self.custom_headers)
+            .field("reply_all", &self.reply_all)
+            .field("backend", &self.backend)
+            .field("graph_tenant_id", &self.graph_tenant_id)
+            .field("graph_client_id", &"[REDACTED]")
+            .field("graph_client_secret", &"[REDACTED]")
+            .field("graph_user", &"[REDACTED]")
+            .field("jmap_session_url", &self.jmap_session_url)
             .finish()
     }
 }
@@ -2057,18 +2794,31 @@ pub struct TelegramPermissions {
     pub chat_filter: Option<Vec<i64>>,
     /// User IDs allowed in private chats.
     pub dm_allowed_users: Vec<i64>,
+    /// Only respond in group chats to messages starting with this prefix.
+    pub trigger_prefix: Option<String>,
+    /// Group moderation actions agents may perform. Only populated for the
+    /// default adapter; named instances get everything disabled.
+    pub moderation: TelegramModerationConfig,
 }
 
 impl TelegramPermissions {
     /// Build from the current config's telegram settings and bindings.
     pub fn from_config(telegram: &TelegramConfig, bindings: &[Binding]) -> Self {
-        Self::from_bindings_for_adapter(telegram.dm_allowed_users.clone(), bindings, None)
+        let mut permissions = Self::from_bindings_for_adapter(
+            telegram.dm_allowed_users.clone(),
+            telegram.trigger_prefix.clone(),
+            bindings,
+            None,
+        );
+        permissions.moderation = telegram.moderation.clone();
+        permissions
     }
 
     /// Build permissions for a named Telegram adapter instance.
     pub fn from_instance_config(instance: &TelegramInstanceConfig, bindings: &[Binding]) -> Self {
         Self::from_bindings_for_adapter(
             instance.dm_allowed_users.clone(),
+            instance.trigger_prefix.clone(),
             bindings,
             Some(instance.name.as_str()),
         )
@@ -2076,6 +2826,7 @@ impl TelegramPermissions {
 
     fn from_bindings_for_adapter(
         seed_dm_allowed_users: Vec<String>,
+        trigger_prefix: Option<String>,
         bindings: &[Binding],
         adapter_selector: Option<&str>,
     ) -> Self {
@@ -2117,6 +2868,8 @@ impl TelegramPermissions {
         Self {
             chat_filter,
             dm_allowed_users,
+            trigger_prefix,
+            moderation: TelegramModerationConfig::default(),
         }
     }
 }
@@ -2264,702 +3017,2175 @@ pub struct WebhookConfig {
     pub auth_token: Option<String>,
 }
 
-// -- TOML deserialization types --
+#[derive(Clone)]
+pub struct MattermostConfig {
+    pub enabled: bool,
+    /// Base server URL, e.g. `https://mattermost.example.com`.
+    pub url: String,
+    /// Bot account personal access token.
+    pub token: String,
+    /// Channel IDs to accept messages from. Empty means all channels the bot is in.
+    pub channels: Vec<String>,
+}
 
-#[derive(Deserialize)]
-struct TomlConfig {
-    #[serde(default)]
-    llm: TomlLlmConfig,
-    #[serde(default)]
-    defaults: TomlDefaultsConfig,
-    #[serde(default)]
-    agents: Vec<TomlAgentConfig>,
-    #[serde(default)]
-    links: Vec<TomlLinkDef>,
-    #[serde(default)]
-    groups: Vec<TomlGroupDef>,
-    #[serde(default)]
-    humans: Vec<TomlHumanDef>,
-    #[serde(default)]
-    messaging: TomlMessagingConfig,
-    #[serde(default)]
-    bindings: Vec<TomlBinding>,
-    #[serde(default)]
-    api: TomlApiConfig,
-    #[serde(default)]
-    metrics: TomlMetricsConfig,
-    #[serde(default)]
-    telemetry: TomlTelemetryConfig,
+#[derive(Clone)]
+pub struct WhatsAppConfig {
+    pub enabled: bool,
+    /// Graph API access token for the business account.
+    pub access_token: String,
+    /// The business phone number ID messages are sent from.
+    pub phone_number_id: String,
+    /// Token Meta echoes back during webhook verification.
+    pub verify_token: String,
+    /// Meta app secret used to verify `X-Hub-Signature-256` on deliveries.
+    pub app_secret: String,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-#[derive(Deserialize)]
-struct TomlLinkDef {
-    from: String,
-    to: String,
-    #[serde(default = "default_link_direction")]
-    direction: String,
-    #[serde(default = "default_link_kind")]
-    kind: String,
-    /// Backward compat: old configs use `relationship` instead of `kind`
-    #[serde(default)]
-    relationship: Option<String>,
+impl std::fmt::Debug for WhatsAppConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WhatsAppConfig")
+            .field("enabled", &self.enabled)
+            .field("access_token", &"[REDACTED]")
+            .field("phone_number_id", &self.phone_number_id)
+            .field("verify_token", &"[REDACTED]")
+            .field("app_secret", &"[REDACTED]")
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-fn default_link_direction() -> String {
-    "two_way".into()
+#[derive(Clone)]
+pub struct SmsConfig {
+    pub enabled: bool,
+    /// Twilio account SID (also used as the API username).
+    pub account_sid: String,
+    /// Twilio auth token.
+    pub auth_token: String,
+    /// Our numbers in E.164. The first is the default for broadcasts.
+    pub numbers: Vec<String>,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-fn default_link_kind() -> String {
-    "peer".into()
+impl std::fmt::Debug for SmsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmsConfig")
+            .field("enabled", &self.enabled)
+            .field("account_sid", &self.account_sid)
+            .field("auth_token", &"[REDACTED]")
+            .field("numbers", &self.numbers)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlGroupDef {
-    name: String,
-    #[serde(default)]
-    agent_ids: Vec<String>,
-    color: Option<String>,
+#[derive(Clone)]
+pub struct ZulipConfig {
+    pub enabled: bool,
+    /// Zulip server URL, e.g. `https://chat.example.com`.
+    pub url: String,
+    /// Bot email address.
+    pub email: String,
+    /// Bot API key.
+    pub api_key: String,
+    /// Stream names to accept messages from. Empty means all subscribed streams.
+    pub streams: Vec<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlHumanDef {
-    id: String,
-    display_name: Option<String>,
-    role: Option<String>,
-    bio: Option<String>,
+impl std::fmt::Debug for ZulipConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZulipConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("email", &self.email)
+            .field("api_key", &"[REDACTED]")
+            .field("streams", &self.streams)
+            .finish()
+    }
 }
 
-#[derive(Deserialize, Default)]
-struct TomlTelemetryConfig {
-    otlp_endpoint: Option<String>,
-    otlp_headers: Option<String>,
-    service_name: Option<String>,
-    sample_rate: Option<f64>,
+/// Google Chat app backed by a service account. The credentials path points
+/// at the downloaded service-account key JSON; secrets stay on disk.
+#[derive(Debug, Clone)]
+pub struct GoogleChatConfig {
+    pub enabled: bool,
+    /// Path to the service-account key JSON file.
+    pub credentials_path: String,
+    /// Audience (the Cloud project number) expected in inbound event JWTs.
+    pub audience: String,
+    /// Port for the inbound event endpoint (webhook or Pub/Sub push).
+    pub port: u16,
+    /// Bind address for the event endpoint.
+    pub bind: String,
+    /// Space names (e.g. `spaces/AAAA1234`) to accept events from. Empty means all.
+    pub spaces: Vec<String>,
+    /// Sender user names (e.g. `users/1234`) allowed to talk to the bot. Empty means all.
+    pub users: Vec<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlApiConfig {
-    #[serde(default = "default_api_enabled")]
-    enabled: bool,
-    #[serde(default = "default_api_port")]
-    port: u16,
-    #[serde(default = "default_api_bind")]
-    bind: String,
-    #[serde(default)]
-    auth_token: Option<String>,
+/// Rocket.Chat bot account credentials.
+#[derive(Clone)]
+pub struct RocketChatConfig {
+    pub enabled: bool,
+    /// Server base URL, e.g. `https://chat.example.com`.
+    pub url: String,
+    /// Bot account username.
+    pub username: String,
+    /// Bot account password.
+    pub password: String,
+    /// Room names to accept messages from. Empty means all rooms the bot is in.
+    pub channels: Vec<String>,
 }
 
-impl Default for TomlApiConfig {
-    fn default() -> Self {
-        Self {
-            enabled: default_api_enabled(),
-            port: default_api_port(),
-            bind: default_api_bind(),
-            auth_token: None,
-        }
+impl std::fmt::Debug for RocketChatConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RocketChatConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .field("channels", &self.channels)
+            .finish()
     }
 }
 
-fn default_api_enabled() -> bool {
-    true
+/// Nostr bot identity and relay list.
+#[derive(Clone)]
+pub struct NostrConfig {
+    pub enabled: bool,
+    /// Hex-encoded secret key of the bot identity.
+    pub secret_key: String,
+    /// Relay WebSocket URLs, e.g. `wss://relay.damus.io`.
+    pub relays: Vec<String>,
 }
-fn default_api_port() -> u16 {
-    19898
+
+impl std::fmt::Debug for NostrConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NostrConfig")
+            .field("enabled", &self.enabled)
+            .field("secret_key", &"[REDACTED]")
+            .field("relays", &self.relays)
+            .finish()
+    }
 }
-fn default_api_bind() -> String {
-    "127.0.0.1".into()
+
+/// XMPP/Jabber account, room list, and connection overrides.
+#[derive(Clone)]
+pub struct XmppConfig {
+    pub enabled: bool,
+    /// Bare JID to log in as, e.g. `bot@example.org`.
+    pub jid: String,
+    pub password: String,
+    /// Host to connect to when it differs from the JID domain.
+    pub server: Option<String>,
+    pub port: u16,
+    /// Nickname for MUC rooms; defaults to the JID localpart.
+    pub nick: Option<String>,
+    /// MUC room JIDs to join, e.g. `dev@conference.example.org`.
+    pub rooms: Vec<String>,
 }
 
-fn hosted_api_bind(bind: String) -> String {
-    match std::env::var("SPACEBOT_DEPLOYMENT") {
-        Ok(deployment) if deployment.eq_ignore_ascii_case("hosted") => "[::]".into(),
-        _ => bind,
+impl std::fmt::Debug for XmppConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XmppConfig")
+            .field("enabled", &self.enabled)
+            .field("jid", &self.jid)
+            .field("password", &"[REDACTED]")
+            .field("server", &self.server)
+            .field("port", &self.port)
+            .field("nick", &self.nick)
+            .field("rooms", &self.rooms)
+            .finish()
     }
 }
 
-#[derive(Deserialize)]
-struct TomlMetricsConfig {
-    #[serde(default)]
-    enabled: bool,
-    #[serde(default = "default_metrics_port")]
-    port: u16,
-    #[serde(default = "default_metrics_bind")]
-    bind: String,
+/// LINE Messaging API channel credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct LineConfig {
+    pub enabled: bool,
+    /// Channel secret used to validate webhook signatures.
+    pub channel_secret: String,
+    /// Long-lived channel access token for the Messaging API.
+    pub channel_access_token: String,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-impl Default for TomlMetricsConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            port: default_metrics_port(),
-            bind: default_metrics_bind(),
-        }
+impl std::fmt::Debug for LineConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineConfig")
+            .field("enabled", &self.enabled)
+            .field("channel_secret", &"[REDACTED]")
+            .field("channel_access_token", &"[REDACTED]")
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
     }
 }
 
-fn default_metrics_port() -> u16 {
-    9090
+/// Reddit script-app credentials and inbox polling settings.
+#[derive(Clone)]
+pub struct RedditConfig {
+    pub enabled: bool,
+    /// Script-app client ID from the Reddit app console.
+    pub client_id: String,
+    /// Script-app client secret.
+    pub client_secret: String,
+    /// Bot account username (without the `u/` prefix).
+    pub username: String,
+    /// Bot account password for the OAuth password grant.
+    pub password: String,
+    /// Subreddits (without `r/`) whose new posts are ingested.
+    pub subreddits: Vec<String>,
+    /// Seconds between inbox polls; clamped to at least 15.
+    pub poll_interval_secs: u64,
 }
-fn default_metrics_bind() -> String {
-    "0.0.0.0".into()
+
+impl std::fmt::Debug for RedditConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedditConfig")
+            .field("enabled", &self.enabled)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"[REDACTED]")
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .field("subreddits", &self.subreddits)
+            .field("poll_interval_secs", &self.poll_interval_secs)
+            .finish()
+    }
 }
 
-#[derive(Deserialize, Debug)]
-struct TomlProviderConfig {
-    api_type: ApiType,
-    base_url: String,
-    api_key: String,
-    name: Option<String>,
+/// GitHub issue/PR conversation credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct GithubConfig {
+    pub enabled: bool,
+    /// Personal access token (or GitHub App installation token).
+    pub token: String,
+    /// Webhook secret; deliveries are accepted unsigned when absent.
+    pub webhook_secret: Option<String>,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-#[derive(Deserialize, Default)]
-struct TomlLlmConfigFields {
-    anthropic_key: Option<String>,
-    openai_key: Option<String>,
-    openrouter_key: Option<String>,
-    kilo_key: Option<String>,
-    zhipu_key: Option<String>,
-    groq_key: Option<String>,
-    together_key: Option<String>,
-    fireworks_key: Option<String>,
-    deepseek_key: Option<String>,
-    xai_key: Option<String>,
-    mistral_key: Option<String>,
-    gemini_key: Option<String>,
-    ollama_key: Option<String>,
-    ollama_base_url: Option<String>,
-    opencode_zen_key: Option<String>,
-    opencode_go_key: Option<String>,
-    nvidia_key: Option<String>,
-    minimax_key: Option<String>,
-    minimax_cn_key: Option<String>,
-    moonshot_key: Option<String>,
-    zai_coding_plan_key: Option<String>,
-    #[serde(default)]
-    providers: HashMap<String, TomlProviderConfig>,
-    #[serde(default)]
-    #[serde(flatten)]
-    extra: HashMap<String, toml::Value>,
+impl std::fmt::Debug for GithubConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GithubConfig")
+            .field("enabled", &self.enabled)
+            .field("token", &"[REDACTED]")
+            .field("webhook_secret", &self.webhook_secret.as_ref().map(|_| "[REDACTED]"))
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-#[derive(Default)]
-struct TomlLlmConfig {
-    anthropic_key: Option<String>,
-    openai_key: Option<String>,
-    openrouter_key: Option<String>,
-    kilo_key: Option<String>,
-    zhipu_key: Option<String>,
-    groq_key: Option<String>,
-    together_key: Option<String>,
-    fireworks_key: Option<String>,
-    deepseek_key: Option<String>,
-    xai_key: Option<String>,
-    mistral_key: Option<String>,
-    gemini_key: Option<String>,
-    ollama_key: Option<String>,
-    ollama_base_url: Option<String>,
-    opencode_zen_key: Option<String>,
-    opencode_go_key: Option<String>,
-    nvidia_key: Option<String>,
-    minimax_key: Option<String>,
-    minimax_cn_key: Option<String>,
-    moonshot_key: Option<String>,
-    zai_coding_plan_key: Option<String>,
-    providers: HashMap<String, TomlProviderConfig>,
+/// GitLab issue/MR conversation credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct GitlabConfig {
+    pub enabled: bool,
+    /// Instance base URL; defaults to gitlab.com.
+    pub base_url: String,
+    /// Personal or project access token with `api` scope.
+    pub token: String,
+    /// Webhook secret token; deliveries are accepted unsigned when absent.
+    pub webhook_token: Option<String>,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-impl<'de> Deserialize<'de> for TomlLlmConfig {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let mut fields = TomlLlmConfigFields::deserialize(deserializer)?;
-        let mut providers = fields.providers;
+impl std::fmt::Debug for GitlabConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitlabConfig")
+            .field("enabled", &self.enabled)
+            .field("base_url", &self.base_url)
+            .field("token", &"[REDACTED]")
+            .field("webhook_token", &self.webhook_token.as_ref().map(|_| "[REDACTED]"))
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
+}
 
-        for (key, value) in fields.extra {
-            if key == "provider" {
-                let table = value
-                    .as_table()
-                    .ok_or_else(|| serde::de::Error::custom("`llm.provider` must be a table"))?;
-                for (provider_id, provider_value) in table {
-                    let provider_config = provider_value
-                        .clone()
-                        .try_into()
-                        .map_err(serde::de::Error::custom)?;
-                    providers.insert(provider_id.to_string(), provider_config);
-                }
-            }
+/// Jira issue-comment conversation credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct JiraConfig {
+    pub enabled: bool,
+    /// Site base URL, e.g. `https://acme.atlassian.net`.
+    pub base_url: String,
+    /// Account email paired with the API token for basic auth.
+    pub email: String,
+    pub api_token: String,
+    /// Shared secret expected in the webhook's `token` query parameter.
+    pub webhook_token: Option<String>,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
+}
 
-            if let Some(provider_id) = key.strip_prefix("provider.") {
-                let provider_config = value.try_into().map_err(serde::de::Error::custom)?;
-                providers.insert(provider_id.to_string(), provider_config);
-            }
-        }
+impl std::fmt::Debug for JiraConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JiraConfig")
+            .field("enabled", &self.enabled)
+            .field("base_url", &self.base_url)
+            .field("email", &self.email)
+            .field("api_token", &"[REDACTED]")
+            .field("webhook_token", &self.webhook_token.as_ref().map(|_| "[REDACTED]"))
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
+}
 
-        fields.providers = providers;
+/// Linear workspace credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct LinearConfig {
+    pub enabled: bool,
+    /// Personal or workspace API key for the GraphQL endpoint.
+    pub api_key: String,
+    /// Webhook signing secret; deliveries are accepted unsigned when absent.
+    pub webhook_secret: Option<String>,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
+    /// Additional named Linear workspaces, each with its own API key.
+    pub instances: Vec<LinearInstanceConfig>,
+}
 
-        Ok(Self {
-            anthropic_key: fields.anthropic_key,
-            openai_key: fields.openai_key,
-            openrouter_key: fields.openrouter_key,
-            kilo_key: fields.kilo_key,
-            zhipu_key: fields.zhipu_key,
-            groq_key: fields.groq_key,
-            together_key: fields.together_key,
-            fireworks_key: fields.fireworks_key,
-            deepseek_key: fields.deepseek_key,
-            xai_key: fields.xai_key,
-            mistral_key: fields.mistral_key,
-            gemini_key: fields.gemini_key,
-            ollama_key: fields.ollama_key,
-            ollama_base_url: fields.ollama_base_url,
-            opencode_zen_key: fields.opencode_zen_key,
-            opencode_go_key: fields.opencode_go_key,
-            nvidia_key: fields.nvidia_key,
-            minimax_key: fields.minimax_key,
-            minimax_cn_key: fields.minimax_cn_key,
-            moonshot_key: fields.moonshot_key,
-            zai_coding_plan_key: fields.zai_coding_plan_key,
-            providers: fields.providers,
-        })
+impl std::fmt::Debug for LinearConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinearConfig")
+            .field("enabled", &self.enabled)
+            .field("api_key", &"[REDACTED]")
+            .field("webhook_secret", &self.webhook_secret.as_ref().map(|_| "[REDACTED]"))
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .field("instances", &self.instances)
+            .finish()
     }
 }
 
-#[derive(Deserialize, Default)]
-struct TomlDefaultsConfig {
-    routing: Option<TomlRoutingConfig>,
-    max_concurrent_branches: Option<usize>,
-    max_concurrent_workers: Option<usize>,
-    max_turns: Option<usize>,
-    branch_max_turns: Option<usize>,
-    context_window: Option<usize>,
-    compaction: Option<TomlCompactionConfig>,
-    memory_persistence: Option<TomlMemoryPersistenceConfig>,
-    coalesce: Option<TomlCoalesceConfig>,
-    ingestion: Option<TomlIngestionConfig>,
-    cortex: Option<TomlCortexConfig>,
-    warmup: Option<TomlWarmupConfig>,
-    browser: Option<TomlBrowserConfig>,
-    #[serde(default)]
-    mcp: Vec<TomlMcpServerConfig>,
-    brave_search_key: Option<String>,
-    cron_timezone: Option<String>,
-    user_timezone: Option<String>,
-    opencode: Option<TomlOpenCodeConfig>,
-    worker_log_mode: Option<String>,
+#[derive(Clone)]
+pub struct LinearInstanceConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub api_key: String,
+    pub webhook_secret: Option<String>,
+    /// Port for this instance's webhook endpoint; must not collide with
+    /// the default adapter or other instances.
+    pub port: u16,
+    pub bind: String,
 }
 
-#[derive(Deserialize, Default)]
-struct TomlRoutingConfig {
-    channel: Option<String>,
-    branch: Option<String>,
-    worker: Option<String>,
-    compactor: Option<String>,
-    cortex: Option<String>,
-    voice: Option<String>,
-    rate_limit_cooldown_secs: Option<u64>,
-    channel_thinking_effort: Option<String>,
-    branch_thinking_effort: Option<String>,
-    worker_thinking_effort: Option<String>,
-    compactor_thinking_effort: Option<String>,
-    cortex_thinking_effort: Option<String>,
-    #[serde(default)]
-    task_overrides: HashMap<String, String>,
-    fallbacks: Option<HashMap<String, Vec<String>>>,
+impl std::fmt::Debug for LinearInstanceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinearInstanceConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("api_key", &"[REDACTED]")
+            .field("webhook_secret", &self.webhook_secret.as_ref().map(|_| "[REDACTED]"))
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlMemoryPersistenceConfig {
-    enabled: Option<bool>,
-    message_interval: Option<usize>,
+/// Nextcloud Talk credentials and room selection.
+#[derive(Clone)]
+pub struct NextcloudConfig {
+    pub enabled: bool,
+    /// Server base URL, e.g. `https://cloud.example.org`.
+    pub base_url: String,
+    pub username: String,
+    /// App password paired with the username for basic auth.
+    pub app_password: String,
+    /// Room tokens to poll. Empty polls every joined room.
+    pub rooms: Vec<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlCoalesceConfig {
-    enabled: Option<bool>,
-    debounce_ms: Option<u64>,
-    max_wait_ms: Option<u64>,
-    min_messages: Option<usize>,
-    multi_user_only: Option<bool>,
+impl std::fmt::Debug for NextcloudConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NextcloudConfig")
+            .field("enabled", &self.enabled)
+            .field("base_url", &self.base_url)
+            .field("username", &self.username)
+            .field("app_password", &"[REDACTED]")
+            .field("rooms", &self.rooms)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlIngestionConfig {
-    enabled: Option<bool>,
-    poll_interval_secs: Option<u64>,
-    chunk_size: Option<usize>,
+/// MQTT broker connection and topic subscriptions.
+#[derive(Clone)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Client identifier presented to the broker.
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Topic filters to subscribe to, e.g. `home/+/motion`.
+    pub topics: Vec<String>,
+    /// Topic agent responses publish to; defaults to `<topic>/response`.
+    pub response_topic: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlCompactionConfig {
-    background_threshold: Option<f32>,
-    aggressive_threshold: Option<f32>,
-    emergency_threshold: Option<f32>,
+impl std::fmt::Debug for MqttConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttConfig")
+            .field("enabled", &self.enabled)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("client_id", &self.client_id)
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .field("topics", &self.topics)
+            .field("response_topic", &self.response_topic)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlCortexConfig {
-    tick_interval_secs: Option<u64>,
-    worker_timeout_secs: Option<u64>,
-    branch_timeout_secs: Option<u64>,
-    circuit_breaker_threshold: Option<u8>,
-    bulletin_interval_secs: Option<u64>,
-    bulletin_max_words: Option<usize>,
-    bulletin_max_turns: Option<usize>,
-    association_interval_secs: Option<u64>,
-    association_similarity_threshold: Option<f32>,
-    association_updates_threshold: Option<f32>,
-    association_max_per_pass: Option<usize>,
+/// RSS/Atom feeds polled as a read-only message source.
+#[derive(Debug, Clone)]
+pub struct RssConfig {
+    pub enabled: bool,
+    /// Feeds to poll, each becoming its own conversation.
+    pub feeds: Vec<RssFeedConfig>,
+    /// Seconds between fetches of each feed.
+    pub poll_interval_secs: u64,
 }
 
-#[derive(Deserialize)]
-struct TomlWarmupConfig {
-    enabled: Option<bool>,
-    eager_embedding_load: Option<bool>,
-    refresh_secs: Option<u64>,
-    startup_delay_secs: Option<u64>,
+#[derive(Debug, Clone)]
+pub struct RssFeedConfig {
+    /// Short name used in conversation IDs, e.g. `hn`.
+    pub name: String,
+    pub url: String,
 }
 
-#[derive(Deserialize)]
-struct TomlBrowserConfig {
-    enabled: Option<bool>,
-    headless: Option<bool>,
-    evaluate_enabled: Option<bool>,
-    executable_path: Option<String>,
-    screenshot_dir: Option<String>,
+/// WebSocket server for custom frontends exchanging JSON frames.
+#[derive(Clone)]
+pub struct WebsocketConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub bind: String,
+    pub auth_token: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlOpenCodeConfig {
-    enabled: Option<bool>,
-    path: Option<String>,
-    max_servers: Option<usize>,
-    server_startup_timeout_secs: Option<u64>,
-    max_restart_retries: Option<u32>,
-    permissions: Option<TomlOpenCodePermissions>,
+impl std::fmt::Debug for WebsocketConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebsocketConfig")
+            .field("enabled", &self.enabled)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .field("auth_token", &"[REDACTED]")
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlOpenCodePermissions {
-    edit: Option<String>,
-    bash: Option<String>,
-    webfetch: Option<String>,
+/// Twilio Voice phone call adapter (TwiML webhooks with speech gathering).
+#[derive(Clone)]
+pub struct VoiceConfig {
+    pub enabled: bool,
+    /// Twilio auth token, used to verify webhook signatures.
+    pub auth_token: String,
+    /// Port for the TwiML webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
+    /// Spoken when a call is answered.
+    pub greeting: String,
+    /// TTS voice name passed to `<Say>`.
+    pub voice: String,
+    /// Speech recognition and TTS language, e.g. `en-US`.
+    pub language: String,
 }
 
-#[derive(Deserialize, Clone)]
-struct TomlMcpServerConfig {
-    name: String,
-    transport: String,
-    #[serde(default = "default_mcp_enabled")]
-    enabled: bool,
-    command: Option<String>,
-    #[serde(default)]
-    args: Vec<String>,
-    #[serde(default)]
-    env: HashMap<String, String>,
-    url: Option<String>,
-    #[serde(default)]
-    headers: HashMap<String, String>,
+impl std::fmt::Debug for VoiceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoiceConfig")
+            .field("enabled", &self.enabled)
+            .field("auth_token", &"[REDACTED]")
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .field("greeting", &self.greeting)
+            .field("voice", &self.voice)
+            .field("language", &self.language)
+            .finish()
+    }
 }
 
-fn default_mcp_enabled() -> bool {
-    true
+/// Steam friend chat via the Web User Presence OAuth API.
+#[derive(Clone)]
+pub struct SteamConfig {
+    pub enabled: bool,
+    /// OAuth access token for the bot's Steam account.
+    pub access_token: String,
+    /// SteamID64s allowed to chat. Empty accepts every friend.
+    pub allowed_friends: Vec<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlAgentConfig {
-    id: String,
-    #[serde(default)]
-    default: bool,
-    display_name: Option<String>,
-    role: Option<String>,
-    workspace: Option<String>,
-    routing: Option<TomlRoutingConfig>,
-    max_concurrent_branches: Option<usize>,
-    max_concurrent_workers: Option<usize>,
-    max_turns: Option<usize>,
-    branch_max_turns: Option<usize>,
-    context_window: Option<usize>,
-    compaction: Option<TomlCompactionConfig>,
-    memory_persistence: Option<TomlMemoryPersistenceConfig>,
-    coalesce: Option<TomlCoalesceConfig>,
-    ingestion: Option<TomlIngestionConfig>,
-    cortex: Option<TomlCortexConfig>,
-    warmup: Option<TomlWarmupConfig>,
-    browser: Option<TomlBrowserConfig>,
-    mcp: Option<Vec<TomlMcpServerConfig>>,
-    brave_search_key: Option<String>,
-    cron_timezone: Option<String>,
-    user_timezone: Option<String>,
-    sandbox: Option<crate::sandbox::SandboxConfig>,
-    #[serde(default)]
-    cron: Vec<TomlCronDef>,
+impl std::fmt::Debug for SteamConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SteamConfig")
+            .field("enabled", &self.enabled)
+            .field("access_token", &"[REDACTED]")
+            .field("allowed_friends", &self.allowed_friends)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlCronDef {
-    id: String,
-    prompt: String,
-    cron_expr: Option<String>,
-    interval_secs: Option<u64>,
-    delivery_target: String,
-    active_start_hour: Option<u8>,
-    active_end_hour: Option<u8>,
-    #[serde(default = "default_enabled")]
-    enabled: bool,
-    #[serde(default)]
-    run_once: bool,
-    timeout_secs: Option<u64>,
+/// Viber Bot API credentials and webhook endpoint.
+#[derive(Clone)]
+pub struct ViberConfig {
+    pub enabled: bool,
+    /// Bot auth token; also the HMAC key for webhook signature validation.
+    pub auth_token: String,
+    /// Sender display name attached to every outbound message.
+    pub sender_name: String,
+    /// Port for the inbound webhook endpoint.
+    pub port: u16,
+    /// Bind address for the webhook endpoint.
+    pub bind: String,
 }
 
-fn default_enabled() -> bool {
-    true
+impl std::fmt::Debug for ViberConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViberConfig")
+            .field("enabled", &self.enabled)
+            .field("auth_token", &"[REDACTED]")
+            .field("sender_name", &self.sender_name)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-#[derive(Deserialize, Default)]
-struct TomlMessagingConfig {
-    discord: Option<TomlDiscordConfig>,
-    slack: Option<TomlSlackConfig>,
-    telegram: Option<TomlTelegramConfig>,
-    email: Option<TomlEmailConfig>,
-    webhook: Option<TomlWebhookConfig>,
-    twitch: Option<TomlTwitchConfig>,
+/// Local console adapter reading stdin and printing to stdout.
+#[derive(Debug, Clone)]
+pub struct ConsoleConfig {
+    pub enabled: bool,
 }
 
-#[derive(Deserialize)]
-struct TomlDiscordConfig {
-    #[serde(default)]
-    enabled: bool,
-    token: Option<String>,
-    #[serde(default)]
-    instances: Vec<TomlDiscordInstanceConfig>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
-    #[serde(default)]
-    allow_bot_messages: bool,
+/// Bluesky (AT Protocol) accounts.
+#[derive(Debug, Clone)]
+pub struct BlueskyConfig {
+    pub enabled: bool,
+    /// Accounts to run, each as its own adapter instance.
+    pub instances: Vec<BlueskyInstanceConfig>,
 }
 
-#[derive(Deserialize)]
-struct TomlDiscordInstanceConfig {
-    name: String,
-    #[serde(default)]
-    enabled: bool,
-    token: Option<String>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
-    #[serde(default)]
-    allow_bot_messages: bool,
+#[derive(Clone)]
+pub struct BlueskyInstanceConfig {
+    pub name: String,
+    pub enabled: bool,
+    /// PDS base URL, e.g. `https://bsky.social`.
+    pub service: String,
+    /// Account handle, e.g. `bot.bsky.social`.
+    pub handle: String,
+    /// App password generated in account settings (not the main password).
+    pub app_password: String,
 }
 
-#[derive(Deserialize)]
-struct TomlSlackConfig {
-    #[serde(default)]
-    enabled: bool,
-    bot_token: Option<String>,
-    app_token: Option<String>,
-    #[serde(default)]
-    instances: Vec<TomlSlackInstanceConfig>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
-    #[serde(default)]
-    commands: Vec<TomlSlackCommandConfig>,
+impl std::fmt::Debug for BlueskyInstanceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlueskyInstanceConfig")
+            .field("name", &self.name)
+            .field("enabled", &self.enabled)
+            .field("service", &self.service)
+            .field("handle", &self.handle)
+            .field("app_password", &"[REDACTED]")
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlSlackInstanceConfig {
-    name: String,
-    #[serde(default)]
-    enabled: bool,
-    bot_token: Option<String>,
-    app_token: Option<String>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
-    #[serde(default)]
-    commands: Vec<TomlSlackCommandConfig>,
+/// Mastodon bot account credentials.
+#[derive(Clone)]
+pub struct MastodonConfig {
+    pub enabled: bool,
+    /// Instance base URL, e.g. `https://mastodon.social`.
+    pub url: String,
+    /// Access token for the bot account (read, write, push scopes).
+    pub access_token: String,
+    /// Visibility for replies: "inherit" mirrors the trigger (demoting
+    /// public to unlisted), or a fixed value like "unlisted" or "direct".
+    pub reply_visibility: String,
 }
 
-#[derive(Deserialize)]
-struct TomlSlackCommandConfig {
-    command: String,
-    agent_id: String,
-    description: Option<String>,
+impl std::fmt::Debug for MastodonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MastodonConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("access_token", &"[REDACTED]")
+            .field("reply_visibility", &self.reply_visibility)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlTelegramConfig {
-    #[serde(default)]
-    enabled: bool,
-    token: Option<String>,
-    #[serde(default)]
-    instances: Vec<TomlTelegramInstanceConfig>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
+/// ntfy push target (outbound-only).
+#[derive(Clone)]
+pub struct NtfyConfig {
+    pub enabled: bool,
+    /// Server URL; defaults to the public ntfy.sh instance.
+    pub server: String,
+    /// Default topic when a broadcast target doesn't name one.
+    pub topic: String,
+    /// Optional access token for protected topics.
+    pub token: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct TomlTelegramInstanceConfig {
-    name: String,
-    #[serde(default)]
-    enabled: bool,
-    token: Option<String>,
-    #[serde(default)]
-    dm_allowed_users: Vec<String>,
+impl std::fmt::Debug for NtfyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NtfyConfig")
+            .field("enabled", &self.enabled)
+            .field("server", &self.server)
+            .field("topic", &self.topic)
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlEmailConfig {
-    #[serde(default)]
-    enabled: bool,
-    imap_host: Option<String>,
-    #[serde(default = "default_email_imap_port")]
-    imap_port: u16,
-    imap_username: Option<String>,
-    imap_password: Option<String>,
-    #[serde(default = "default_email_imap_use_tls")]
-    imap_use_tls: bool,
-    smtp_host: Option<String>,
-    #[serde(default = "default_email_smtp_port")]
-    smtp_port: u16,
-    smtp_username: Option<String>,
-    smtp_password: Option<String>,
-    #[serde(default = "default_email_smtp_use_starttls")]
-    smtp_use_starttls: bool,
-    from_address: Option<String>,
-    from_name: Option<String>,
-    #[serde(default = "default_email_poll_interval_secs")]
-    poll_interval_secs: u64,
-    #[serde(default = "default_email_folders")]
-    folders: Vec<String>,
-    #[serde(default)]
-    allowed_senders: Vec<String>,
-    #[serde(default = "default_email_max_body_bytes")]
-    max_body_bytes: usize,
-    #[serde(default = "default_email_max_attachment_bytes")]
-    max_attachment_bytes: usize,
-    #[serde(default)]
-    instances: Vec<TomlEmailInstanceConfig>,
+/// Pushover push target (outbound-only).
+#[derive(Clone)]
+pub struct PushoverConfig {
+    pub enabled: bool,
+    /// Application API token.
+    pub token: String,
+    /// Default user or group key when a broadcast target doesn't name one.
+    pub user: String,
 }
 
-#[derive(Deserialize)]
-struct TomlEmailInstanceConfig {
-    name: String,
-    #[serde(default)]
-    enabled: bool,
-    imap_host: Option<String>,
-    #[serde(default = "default_email_imap_port")]
-    imap_port: u16,
-    imap_username: Option<String>,
-    imap_password: Option<String>,
-    #[serde(default = "default_email_imap_use_tls")]
-    imap_use_tls: bool,
-    smtp_host: Option<String>,
-    #[serde(default = "default_email_smtp_port")]
-    smtp_port: u16,
-    smtp_username: Option<String>,
-    smtp_password: Option<String>,
-    #[serde(default = "default_email_smtp_use_starttls")]
-    smtp_use_starttls: bool,
-    from_address: Option<String>,
-    from_name: Option<String>,
-    #[serde(default = "default_email_poll_interval_secs")]
-    poll_interval_secs: u64,
-    #[serde(default = "default_email_folders")]
-    folders: Vec<String>,
-    #[serde(default)]
-    allowed_senders: Vec<String>,
-    #[serde(default = "default_email_max_body_bytes")]
-    max_body_bytes: usize,
-    #[serde(default = "default_email_max_attachment_bytes")]
-    max_attachment_bytes: usize,
+impl std::fmt::Debug for PushoverConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PushoverConfig")
+            .field("enabled", &self.enabled)
+            .field("token", &"[REDACTED]")
+            .field("user", &self.user)
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlWebhookConfig {
-    #[serde(default)]
-    enabled: bool,
-    #[serde(default = "default_webhook_port")]
-    port: u16,
-    #[serde(default = "default_webhook_bind")]
-    bind: String,
-    auth_token: Option<String>,
+/// Gotify push target (outbound-only).
+#[derive(Clone)]
+pub struct GotifyConfig {
+    pub enabled: bool,
+    /// Server URL of the Gotify instance.
+    pub server: String,
+    /// Application token; addresses a single app.
+    pub token: String,
 }
 
-#[derive(Deserialize)]
-struct TomlTwitchConfig {
-    #[serde(default)]
-    enabled: bool,
-    username: Option<String>,
-    oauth_token: Option<String>,
-    client_id: Option<String>,
-    client_secret: Option<String>,
-    refresh_token: Option<String>,
-    #[serde(default)]
-    instances: Vec<TomlTwitchInstanceConfig>,
-    #[serde(default)]
-    channels: Vec<String>,
-    trigger_prefix: Option<String>,
+impl std::fmt::Debug for GotifyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GotifyConfig")
+            .field("enabled", &self.enabled)
+            .field("server", &self.server)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
 }
 
-#[derive(Deserialize)]
-struct TomlTwitchInstanceConfig {
-    name: String,
-    #[serde(default)]
-    enabled: bool,
-    username: Option<String>,
-    oauth_token: Option<String>,
-    client_id: Option<String>,
-    client_secret: Option<String>,
-    refresh_token: Option<String>,
-    #[serde(default)]
-    channels: Vec<String>,
-    trigger_prefix: Option<String>,
+/// Signal is linked as a secondary device through an external signal-cli
+/// daemon; no secrets live in this config beyond the account number.
+#[derive(Debug, Clone)]
+pub struct SignalConfig {
+    pub enabled: bool,
+    /// The linked account's E.164 number, e.g. `+15551234567`.
+    pub account: String,
+    /// TCP address of the signal-cli daemon's JSON-RPC socket.
+    pub daemon_addr: String,
 }
 
-fn default_webhook_port() -> u16 {
-    18789
-}
-fn default_webhook_bind() -> String {
-    "127.0.0.1".into()
+#[derive(Clone)]
+pub struct TeamsConfig {
+    pub enabled: bool,
+    /// Azure bot registration application (client) ID.
+    pub app_id: String,
+    /// Client secret for the app registration.
+    pub app_password: String,
+    /// Entra tenant ID used for the client-credential token request.
+    pub tenant_id: String,
+    /// Port for the Bot Framework messaging endpoint.
+    pub port: u16,
+    /// Bind address for the messaging endpoint.
+    pub bind: String,
 }
 
-fn default_email_imap_port() -> u16 {
-    993
+impl std::fmt::Debug for TeamsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TeamsConfig")
+            .field("enabled", &self.enabled)
+            .field("app_id", &self.app_id)
+            .field("app_password", &"[REDACTED]")
+            .field("tenant_id", &self.tenant_id)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .finish()
+    }
 }
 
-fn default_email_imap_use_tls() -> bool {
-    true
+impl std::fmt::Debug for MattermostConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MattermostConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("token", &"[REDACTED]")
+            .field("channels", &self.channels)
+            .finish()
+    }
 }
 
-fn default_email_smtp_port() -> u16 {
-    587
-}
+// -- TOML deserialization types --
 
-fn default_email_smtp_use_starttls() -> bool {
-    true
+#[derive(Deserialize)]
+struct TomlConfig {
+    #[serde(default)]
+    llm: TomlLlmConfig,
+    #[serde(default)]
+    defaults: TomlDefaultsConfig,
+    #[serde(default)]
+    agents: Vec<TomlAgentConfig>,
+    #[serde(default)]
+    links: Vec<TomlLinkDef>,
+    #[serde(default)]
+    groups: Vec<TomlGroupDef>,
+    #[serde(default)]
+    humans: Vec<TomlHumanDef>,
+    #[serde(default)]
+    messaging: TomlMessagingConfig,
+    #[serde(default)]
+    bindings: Vec<TomlBinding>,
+    #[serde(default)]
+    api: TomlApiConfig,
+    #[serde(default)]
+    metrics: TomlMetricsConfig,
+    #[serde(default)]
+    telemetry: TomlTelemetryConfig,
+    #[serde(default)]
+    network: TomlNetworkConfig,
 }
 
-fn default_email_poll_interval_secs() -> u64 {
-    30
+#[derive(Deserialize, Default)]
+struct TomlNetworkConfig {
+    proxy: Option<String>,
+    prefer: Option<String>,
+    #[serde(default)]
+    dns_servers: Vec<String>,
+    doh_provider: Option<String>,
 }
 
-fn default_email_folders() -> Vec<String> {
-    vec!["INBOX".to_string()]
+#[derive(Deserialize)]
+struct TomlLinkDef {
+    from: String,
+    to: String,
+    #[serde(default = "default_link_direction")]
+    direction: String,
+    #[serde(default = "default_link_kind")]
+    kind: String,
+    /// Backward compat: old configs use `relationship` instead of `kind`
+    #[serde(default)]
+    relationship: Option<String>,
 }
 
-fn default_email_max_body_bytes() -> usize {
-    256 * 1024
+fn default_link_direction() -> String {
+    "two_way".into()
 }
 
-fn default_email_max_attachment_bytes() -> usize {
-    10 * 1024 * 1024
+fn default_link_kind() -> String {
+    "peer".into()
 }
 
 #[derive(Deserialize)]
-struct TomlBinding {
-    agent_id: String,
-    channel: String,
+struct TomlGroupDef {
+    name: String,
     #[serde(default)]
-    adapter: Option<String>,
+    agent_ids: Vec<String>,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlHumanDef {
+    id: String,
+    display_name: Option<String>,
+    role: Option<String>,
+    bio: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct TomlTelemetryConfig {
+    otlp_endpoint: Option<String>,
+    otlp_headers: Option<String>,
+    service_name: Option<String>,
+    sample_rate: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct TomlApiConfig {
+    #[serde(default = "default_api_enabled")]
+    enabled: bool,
+    #[serde(default = "default_api_port")]
+    port: u16,
+    #[serde(default = "default_api_bind")]
+    bind: String,
+    #[serde(default)]
+    auth_token: Option<String>,
+}
+
+impl Default for TomlApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_api_enabled(),
+            port: default_api_port(),
+            bind: default_api_bind(),
+            auth_token: None,
+        }
+    }
+}
+
+fn default_api_enabled() -> bool {
+    true
+}
+fn default_api_port() -> u16 {
+    19898
+}
+fn default_api_bind() -> String {
+    "127.0.0.1".into()
+}
+
+fn hosted_api_bind(bind: String) -> String {
+    match std::env::var("SPACEBOT_DEPLOYMENT") {
+        Ok(deployment) if deployment.eq_ignore_ascii_case("hosted") => "[::]".into(),
+        _ => bind,
+    }
+}
+
+#[derive(Deserialize)]
+struct TomlMetricsConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    perf_instrumentation: bool,
+    #[serde(default = "default_metrics_port")]
+    port: u16,
+    #[serde(default = "default_metrics_bind")]
+    bind: String,
+}
+
+impl Default for TomlMetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            perf_instrumentation: false,
+            port: default_metrics_port(),
+            bind: default_metrics_bind(),
+        }
+    }
+}
+
+fn default_metrics_port() -> u16 {
+    9090
+}
+fn default_metrics_bind() -> String {
+    "0.0.0.0".into()
+}
+
+#[derive(Deserialize, Debug)]
+struct TomlProviderConfig {
+    api_type: ApiType,
+    base_url: String,
+    api_key: String,
+    name: Option<String>,
+    /// Data-residency region this provider's endpoint lives in (e.g. "eu").
+    region: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct TomlLlmConfigFields {
+    anthropic_key: Option<String>,
+    openai_key: Option<String>,
+    openrouter_key: Option<String>,
+    kilo_key: Option<String>,
+    zhipu_key: Option<String>,
+    groq_key: Option<String>,
+    together_key: Option<String>,
+    fireworks_key: Option<String>,
+    deepseek_key: Option<String>,
+    xai_key: Option<String>,
+    mistral_key: Option<String>,
+    gemini_key: Option<String>,
+    ollama_key: Option<String>,
+    ollama_base_url: Option<String>,
+    opencode_zen_key: Option<String>,
+    opencode_go_key: Option<String>,
+    nvidia_key: Option<String>,
+    minimax_key: Option<String>,
+    minimax_cn_key: Option<String>,
+    moonshot_key: Option<String>,
+    zai_coding_plan_key: Option<String>,
+    #[serde(default)]
+    providers: HashMap<String, TomlProviderConfig>,
+    #[serde(default)]
+    #[serde(flatten)]
+    extra: HashMap<String, toml::Value>,
+}
+
+#[derive(Default)]
+struct TomlLlmConfig {
+    anthropic_key: Option<String>,
+    openai_key: Option<String>,
+    openrouter_key: Option<String>,
+    kilo_key: Option<String>,
+    zhipu_key: Option<String>,
+    groq_key: Option<String>,
+    together_key: Option<String>,
+    fireworks_key: Option<String>,
+    deepseek_key: Option<String>,
+    xai_key: Option<String>,
+    mistral_key: Option<String>,
+    gemini_key: Option<String>,
+    ollama_key: Option<String>,
+    ollama_base_url: Option<String>,
+    opencode_zen_key: Option<String>,
+    opencode_go_key: Option<String>,
+    nvidia_key: Option<String>,
+    minimax_key: Option<String>,
+    minimax_cn_key: Option<String>,
+    moonshot_key: Option<String>,
+    zai_coding_plan_key: Option<String>,
+    providers: HashMap<String, TomlProviderConfig>,
+}
+
+impl<'de> Deserialize<'de> for TomlLlmConfig {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = TomlLlmConfigFields::deserialize(deserializer)?;
+        let mut providers = fields.providers;
+
+        for (key, value) in fields.extra {
+            if key == "provider" {
+                let table = value
+                    .as_table()
+                    .ok_or_else(|| serde::de::Error::custom("`llm.provider` must be a table"))?;
+                for (provider_id, provider_value) in table {
+                    let provider_config = provider_value
+                        .clone()
+                        .try_into()
+                        .map_err(serde::de::Error::custom)?;
+                    providers.insert(provider_id.to_string(), provider_config);
+                }
+            }
+
+            if let Some(provider_id) = key.strip_prefix("provider.") {
+                let provider_config = value.try_into().map_err(serde::de::Error::custom)?;
+                providers.insert(provider_id.to_string(), provider_config);
+            }
+        }
+
+        fields.providers = providers;
+
+        Ok(Self {
+            anthropic_key: fields.anthropic_key,
+            openai_key: fields.openai_key,
+            openrouter_key: fields.openrouter_key,
+            kilo_key: fields.kilo_key,
+            zhipu_key: fields.zhipu_key,
+            groq_key: fields.groq_key,
+            together_key: fields.together_key,
+            fireworks_key: fields.fireworks_key,
+            deepseek_key: fields.deepseek_key,
+            xai_key: fields.xai_key,
+            mistral_key: fields.mistral_key,
+            gemini_key: fields.gemini_key,
+            ollama_key: fields.ollama_key,
+            ollama_base_url: fields.ollama_base_url,
+            opencode_zen_key: fields.opencode_zen_key,
+            opencode_go_key: fields.opencode_go_key,
+            nvidia_key: fields.nvidia_key,
+            minimax_key: fields.minimax_key,
+            minimax_cn_key: fields.minimax_cn_key,
+            moonshot_key: fields.moonshot_key,
+            zai_coding_plan_key: fields.zai_coding_plan_key,
+            providers: fields.providers,
+        })
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct TomlDefaultsConfig {
+    routing: Option<TomlRoutingConfig>,
+    max_concurrent_branches: Option<usize>,
+    max_concurrent_workers: Option<usize>,
+    max_turns: Option<usize>,
+    branch_max_turns: Option<usize>,
+    context_window: Option<usize>,
+    compaction: Option<TomlCompactionConfig>,
+    memory_persistence: Option<TomlMemoryPersistenceConfig>,
+    coalesce: Option<TomlCoalesceConfig>,
+    ingestion: Option<TomlIngestionConfig>,
+    cortex: Option<TomlCortexConfig>,
+    warmup: Option<TomlWarmupConfig>,
+    browser: Option<TomlBrowserConfig>,
+    #[serde(default)]
+    mcp: Vec<TomlMcpServerConfig>,
+    brave_search_key: Option<String>,
+    cron_timezone: Option<String>,
+    user_timezone: Option<String>,
+    cite_sources: Option<bool>,
+    time_context: Option<bool>,
+    opencode: Option<TomlOpenCodeConfig>,
+    ops: Option<TomlOpsConfig>,
+    kube: Option<TomlKubeConfig>,
+    sql: Option<TomlSqlConfig>,
+    ssh: Option<TomlSshConfig>,
+    translation_memory: Option<TomlTranslationMemoryConfig>,
+    memory_maintenance: Option<TomlMemoryMaintenanceConfig>,
+    worker_log_mode: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlOpsConfig {
+    enabled: Option<bool>,
+    docker_enabled: Option<bool>,
+    systemd_enabled: Option<bool>,
+    #[serde(default)]
+    allowed_containers: Vec<String>,
+    #[serde(default)]
+    allowed_units: Vec<String>,
+    allow_restart: Option<bool>,
+    log_tail_limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlKubeConfig {
+    enabled: Option<bool>,
+    #[serde(default)]
+    contexts: Vec<String>,
+    #[serde(default)]
+    namespaces: Vec<String>,
+    log_tail_limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlSqlConfig {
+    enabled: Option<bool>,
+    #[serde(default)]
+    databases: Vec<TomlSqlDatabaseConfig>,
+    max_rows: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlSqlDatabaseConfig {
+    name: String,
+    dsn: String,
+}
+
+#[derive(Deserialize)]
+struct TomlTranslationMemoryConfig {
+    enabled: Option<bool>,
+    ttl_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TomlMemoryMaintenanceConfig {
+    enabled: Option<bool>,
+    start_hour: Option<u8>,
+    end_hour: Option<u8>,
+    report_target: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlSshConfig {
+    enabled: Option<bool>,
+    allow_destructive: Option<bool>,
+    connect_timeout_secs: Option<u64>,
+    #[serde(default)]
+    hosts: Vec<TomlSshHostConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlSshHostConfig {
+    name: String,
+    address: String,
+    port: Option<u16>,
+    identity_file: Option<String>,
+    #[serde(default)]
+    allowed_commands: Vec<String>,
+    #[serde(default)]
+    destructive_commands: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct TomlRoutingConfig {
+    channel: Option<String>,
+    branch: Option<String>,
+    worker: Option<String>,
+    compactor: Option<String>,
+    cortex: Option<String>,
+    voice: Option<String>,
+    rate_limit_cooldown_secs: Option<u64>,
+    channel_thinking_effort: Option<String>,
+    branch_thinking_effort: Option<String>,
+    worker_thinking_effort: Option<String>,
+    compactor_thinking_effort: Option<String>,
+    cortex_thinking_effort: Option<String>,
+    #[serde(default)]
+    task_overrides: HashMap<String, String>,
+    fallbacks: Option<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Deserialize)]
+struct TomlMemoryPersistenceConfig {
+    enabled: Option<bool>,
+    message_interval: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlCoalesceConfig {
+    enabled: Option<bool>,
+    debounce_ms: Option<u64>,
+    max_wait_ms: Option<u64>,
+    min_messages: Option<usize>,
+    multi_user_only: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct TomlIngestionConfig {
+    enabled: Option<bool>,
+    poll_interval_secs: Option<u64>,
+    chunk_size: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlCompactionConfig {
+    background_threshold: Option<f32>,
+    aggressive_threshold: Option<f32>,
+    emergency_threshold: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct TomlCortexConfig {
+    tick_interval_secs: Option<u64>,
+    worker_timeout_secs: Option<u64>,
+    branch_timeout_secs: Option<u64>,
+    circuit_breaker_threshold: Option<u8>,
+    bulletin_interval_secs: Option<u64>,
+    bulletin_max_words: Option<usize>,
+    bulletin_max_turns: Option<usize>,
+    association_interval_secs: Option<u64>,
+    association_similarity_threshold: Option<f32>,
+    association_updates_threshold: Option<f32>,
+    association_max_per_pass: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TomlWarmupConfig {
+    enabled: Option<bool>,
+    eager_embedding_load: Option<bool>,
+    refresh_secs: Option<u64>,
+    startup_delay_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TomlBrowserConfig {
+    enabled: Option<bool>,
+    headless: Option<bool>,
+    evaluate_enabled: Option<bool>,
+    executable_path: Option<String>,
+    screenshot_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlOpenCodeConfig {
+    enabled: Option<bool>,
+    path: Option<String>,
+    max_servers: Option<usize>,
+    server_startup_timeout_secs: Option<u64>,
+    max_restart_retries: Option<u32>,
+    permissions: Option<TomlOpenCodePermissions>,
+}
+
+#[derive(Deserialize)]
+struct TomlOpenCodePermissions {
+    edit: Option<String>,
+    bash: Option<String>,
+    webfetch: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+struct TomlMcpServerConfig {
+    name: String,
+    transport: String,
+    #[serde(default = "default_mcp_enabled")]
+    enabled: bool,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+fn default_mcp_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct TomlAgentConfig {
+    id: String,
+    #[serde(default)]
+    default: bool,
+    display_name: Option<String>,
+    role: Option<String>,
+    workspace: Option<String>,
+    routing: Option<TomlRoutingConfig>,
+    max_concurrent_branches: Option<usize>,
+    max_concurrent_workers: Option<usize>,
+    max_turns: Option<usize>,
+    branch_max_turns: Option<usize>,
+    context_window: Option<usize>,
+    compaction: Option<TomlCompactionConfig>,
+    memory_persistence: Option<TomlMemoryPersistenceConfig>,
+    coalesce: Option<TomlCoalesceConfig>,
+    ingestion: Option<TomlIngestionConfig>,
+    cortex: Option<TomlCortexConfig>,
+    warmup: Option<TomlWarmupConfig>,
+    browser: Option<TomlBrowserConfig>,
+    mcp: Option<Vec<TomlMcpServerConfig>>,
+    brave_search_key: Option<String>,
+    cron_timezone: Option<String>,
+    user_timezone: Option<String>,
+    sandbox: Option<crate::sandbox::SandboxConfig>,
+    #[serde(default)]
+    cron: Vec<TomlCronDef>,
+}
+
+#[derive(Deserialize)]
+struct TomlCronDef {
+    id: String,
+    prompt: String,
+    cron_expr: Option<String>,
+    interval_secs: Option<u64>,
+    delivery_target: String,
+    template: Option<String>,
+    active_start_hour: Option<u8>,
+    active_end_hour: Option<u8>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    #[serde(default)]
+    run_once: bool,
+    timeout_secs: Option<u64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize, Default)]
+struct TomlMessagingConfig {
+    discord: Option<TomlDiscordConfig>,
+    slack: Option<TomlSlackConfig>,
+    telegram: Option<TomlTelegramConfig>,
+    email: Option<TomlEmailConfig>,
+    webhook: Option<TomlWebhookConfig>,
+    twitch: Option<TomlTwitchConfig>,
+    mattermost: Option<TomlMattermostConfig>,
+    teams: Option<TomlTeamsConfig>,
+    signal: Option<TomlSignalConfig>,
+    whatsapp: Option<TomlWhatsAppConfig>,
+    sms: Option<TomlSmsConfig>,
+    zulip: Option<TomlZulipConfig>,
+    ntfy: Option<TomlNtfyConfig>,
+    googlechat: Option<TomlGoogleChatConfig>,
+    rocketchat: Option<TomlRocketChatConfig>,
+    mastodon: Option<TomlMastodonConfig>,
+    bluesky: Option<TomlBlueskyConfig>,
+    nostr: Option<TomlNostrConfig>,
+    xmpp: Option<TomlXmppConfig>,
+    line: Option<TomlLineConfig>,
+    reddit: Option<TomlRedditConfig>,
+    github: Option<TomlGithubConfig>,
+    gitlab: Option<TomlGitlabConfig>,
+    jira: Option<TomlJiraConfig>,
+    linear: Option<TomlLinearConfig>,
+    nextcloud: Option<TomlNextcloudConfig>,
+    rss: Option<TomlRssConfig>,
+    mqtt: Option<TomlMqttConfig>,
+    websocket: Option<TomlWebsocketConfig>,
+    console: Option<TomlConsoleConfig>,
+    voice: Option<TomlVoiceConfig>,
+    steam: Option<TomlSteamConfig>,
+    viber: Option<TomlViberConfig>,
+    pushover: Option<TomlPushoverConfig>,
+    gotify: Option<TomlGotifyConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlDiscordConfig {
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    #[serde(default)]
+    instances: Vec<TomlDiscordInstanceConfig>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    allow_bot_messages: bool,
+    permission_sync_interval_secs: Option<u64>,
+    voice: Option<TomlDiscordVoiceConfig>,
+    proxy: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlDiscordVoiceConfig {
+    #[serde(default)]
+    enabled: bool,
+    guild_id: Option<String>,
+    channel_id: Option<String>,
+    #[serde(default = "default_speech_api_base")]
+    api_base: String,
+    api_key: Option<String>,
+    #[serde(default = "default_stt_model")]
+    stt_model: String,
+    #[serde(default = "default_tts_model")]
+    tts_model: String,
+    #[serde(default = "default_tts_voice")]
+    tts_voice: String,
+}
+
+fn default_speech_api_base() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_stt_model() -> String {
+    "whisper-1".to_string()
+}
+
+fn default_tts_model() -> String {
+    "tts-1".to_string()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlDiscordInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    allow_bot_messages: bool,
+    permission_sync_interval_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TomlSlackConfig {
+    #[serde(default)]
+    enabled: bool,
+    bot_token: Option<String>,
+    app_token: Option<String>,
+    #[serde(default)]
+    instances: Vec<TomlSlackInstanceConfig>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    commands: Vec<TomlSlackCommandConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlSlackInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    bot_token: Option<String>,
+    app_token: Option<String>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    commands: Vec<TomlSlackCommandConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlSlackCommandConfig {
+    command: String,
+    agent_id: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlTelegramConfig {
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    #[serde(default)]
+    instances: Vec<TomlTelegramInstanceConfig>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    trigger_prefix: Option<String>,
+    proxy: Option<String>,
+    mode: Option<String>,
+    webhook_url: Option<String>,
+    webhook_secret: Option<String>,
+    stt: Option<TomlTelegramSttConfig>,
+    #[serde(default)]
+    commands: Vec<TomlTelegramCommandConfig>,
+    #[serde(default)]
+    moderation: TomlTelegramModerationConfig,
+}
+
+#[derive(Deserialize, Default)]
+struct TomlTelegramModerationConfig {
+    #[serde(default)]
+    allow_delete: bool,
+    #[serde(default)]
+    allow_mute: bool,
+    #[serde(default)]
+    allow_ban: bool,
+    #[serde(default)]
+    allow_pin: bool,
+}
+
+#[derive(Deserialize)]
+struct TomlTelegramCommandConfig {
+    command: String,
+    agent_id: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlTelegramSttConfig {
+    #[serde(default)]
+    enabled: bool,
+    api_base: Option<String>,
+    api_key: Option<String>,
+    model: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlTelegramInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    #[serde(default)]
+    dm_allowed_users: Vec<String>,
+    trigger_prefix: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlEmailConfig {
+    #[serde(default)]
+    enabled: bool,
+    imap_host: Option<String>,
+    #[serde(default = "default_email_imap_port")]
+    imap_port: u16,
+    imap_username: Option<String>,
+    imap_password: Option<String>,
+    #[serde(default = "default_email_imap_use_tls")]
+    imap_use_tls: bool,
+    smtp_host: Option<String>,
+    #[serde(default = "default_email_smtp_port")]
+    smtp_port: u16,
+    smtp_username: Option<String>,
+    smtp_password: Option<String>,
+    #[serde(default = "default_email_smtp_use_starttls")]
+    smtp_use_starttls: bool,
+    from_address: Option<String>,
+    from_name: Option<String>,
+    #[serde(default = "default_email_poll_interval_secs")]
+    poll_interval_secs: u64,
+    #[serde(default = "default_email_folders")]
+    folders: Vec<String>,
+    #[serde(default)]
+    allowed_senders: Vec<String>,
+    #[serde(default = "default_email_max_body_bytes")]
+    max_body_bytes: usize,
+    #[serde(default = "default_email_max_attachment_bytes")]
+    max_attachment_bytes: usize,
+    #[serde(default = "default_email_size_policy_reply")]
+    size_policy_reply: bool,
+    #[serde(default = "default_email_max_replies_per_hour")]
+    max_replies_per_hour: usize,
+    #[serde(default)]
+    spam_patterns: Vec<String>,
+    #[serde(default)]
+    spam_scorer_command: Option<String>,
+    #[serde(default)]
+    drop_failed_auth: bool,
+    #[serde(default)]
+    pgp_sign: bool,
+    #[serde(default)]
+    pgp_key_id: Option<String>,
+    #[serde(default)]
+    dkim_selector: Option<String>,
+    #[serde(default)]
+    dkim_key_path: Option<String>,
+    #[serde(default)]
+    custom_headers: HashMap<String, String>,
+    #[serde(default)]
+    reply_all: bool,
+    backend: Option<String>,
+    graph_tenant_id: Option<String>,
+    graph_client_id: Option<String>,
+    graph_client_secret: Option<String>,
+    graph_user: Option<String>,
+    jmap_session_url: Option<String>,
+    jmap_token: Option<String>,
+    #[serde(default)]
+    instances: Vec<TomlEmailInstanceConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlEmailInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    imap_host: Option<String>,
+    #[serde(default = "default_email_imap_port")]
+    imap_port: u16,
+    imap_username: Option<String>,
+    imap_password: Option<String>,
+    #[serde(default = "default_email_imap_use_tls")]
+    imap_use_tls: bool,
+    smtp_host: Option<String>,
+    #[serde(default = "default_email_smtp_port")]
+    smtp_port: u16,
+    smtp_username: Option<String>,
+    smtp_password: Option<String>,
+    #[serde(default = "default_email_smtp_use_starttls")]
+    smtp_use_starttls: bool,
+    from_address: Option<String>,
+    from_name: Option<String>,
+    #[serde(default = "default_email_poll_interval_secs")]
+    poll_interval_secs: u64,
+    #[serde(default = "default_email_folders")]
+    folders: Vec<String>,
+    #[serde(default)]
+    allowed_senders: Vec<String>,
+    #[serde(default = "default_email_max_body_bytes")]
+    max_body_bytes: usize,
+    #[serde(default = "default_email_max_attachment_bytes")]
+    max_attachment_bytes: usize,
+    #[serde(default = "default_email_size_policy_reply")]
+    size_policy_reply: bool,
+    #[serde(default = "default_email_max_replies_per_hour")]
+    max_replies_per_hour: usize,
+    #[serde(default)]
+    spam_patterns: Vec<String>,
+    #[serde(default)]
+    spam_scorer_command: Option<String>,
+    #[serde(default)]
+    drop_failed_auth: bool,
+    #[serde(default)]
+    pgp_sign: bool,
+    #[serde(default)]
+    pgp_key_id: Option<String>,
+    #[serde(default)]
+    dkim_selector: Option<String>,
+    #[serde(default)]
+    dkim_key_path: Option<String>,
+    #[serde(default)]
+    custom_headers: HashMap<String, String>,
+    #[serde(default)]
+    reply_all: bool,
+    backend: Option<String>,
+    graph_tenant_id: Option<String>,
+    graph_client_id: Option<String>,
+    graph_client_secret: Option<String>,
+    graph_user: Option<String>,
+    jmap_session_url: Option<String>,
+    jmap_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlWebhookConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_webhook_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+    auth_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlTwitchConfig {
+    #[serde(default)]
+    enabled: bool,
+    username: Option<String>,
+    oauth_token: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    refresh_token: Option<String>,
+    #[serde(default)]
+    instances: Vec<TomlTwitchInstanceConfig>,
+    #[serde(default)]
+    channels: Vec<String>,
+    trigger_prefix: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlTwitchInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    username: Option<String>,
+    oauth_token: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    refresh_token: Option<String>,
+    #[serde(default)]
+    channels: Vec<String>,
+    trigger_prefix: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlMattermostConfig {
+    #[serde(default)]
+    enabled: bool,
+    url: Option<String>,
+    token: Option<String>,
+    #[serde(default)]
+    channels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlWhatsAppConfig {
+    #[serde(default)]
+    enabled: bool,
+    access_token: Option<String>,
+    phone_number_id: Option<String>,
+    verify_token: Option<String>,
+    app_secret: Option<String>,
+    #[serde(default = "default_whatsapp_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_whatsapp_port() -> u16 {
+    18791
+}
+
+#[derive(Deserialize)]
+struct TomlSmsConfig {
+    #[serde(default)]
+    enabled: bool,
+    account_sid: Option<String>,
+    auth_token: Option<String>,
+    #[serde(default)]
+    numbers: Vec<String>,
+    #[serde(default = "default_sms_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_sms_port() -> u16 {
+    18792
+}
+
+#[derive(Deserialize)]
+struct TomlRocketChatConfig {
+    #[serde(default)]
+    enabled: bool,
+    url: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    channels: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlNostrConfig {
+    #[serde(default)]
+    enabled: bool,
+    secret_key: Option<String>,
+    #[serde(default)]
+    relays: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlXmppConfig {
+    #[serde(default)]
+    enabled: bool,
+    jid: Option<String>,
+    password: Option<String>,
+    server: Option<String>,
+    #[serde(default = "default_xmpp_port")]
+    port: u16,
+    nick: Option<String>,
+    #[serde(default)]
+    rooms: Vec<String>,
+}
+
+fn default_xmpp_port() -> u16 {
+    5222
+}
+
+#[derive(Deserialize)]
+struct TomlLineConfig {
+    #[serde(default)]
+    enabled: bool,
+    channel_secret: Option<String>,
+    channel_access_token: Option<String>,
+    #[serde(default = "default_line_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_line_port() -> u16 {
+    18794
+}
+
+#[derive(Deserialize)]
+struct TomlRedditConfig {
+    #[serde(default)]
+    enabled: bool,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    subreddits: Vec<String>,
+    #[serde(default = "default_reddit_poll_interval")]
+    poll_interval_secs: u64,
+}
+
+fn default_reddit_poll_interval() -> u64 {
+    30
+}
+
+#[derive(Deserialize)]
+struct TomlGithubConfig {
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    webhook_secret: Option<String>,
+    #[serde(default = "default_github_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_github_port() -> u16 {
+    18795
+}
+
+#[derive(Deserialize)]
+struct TomlGitlabConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_gitlab_base_url")]
+    base_url: String,
+    token: Option<String>,
+    webhook_token: Option<String>,
+    #[serde(default = "default_gitlab_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_gitlab_base_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+fn default_gitlab_port() -> u16 {
+    18796
+}
+
+#[derive(Deserialize)]
+struct TomlJiraConfig {
+    #[serde(default)]
+    enabled: bool,
+    base_url: Option<String>,
+    email: Option<String>,
+    api_token: Option<String>,
+    webhook_token: Option<String>,
+    #[serde(default = "default_jira_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_jira_port() -> u16 {
+    18797
+}
+
+#[derive(Deserialize)]
+struct TomlLinearConfig {
+    #[serde(default)]
+    enabled: bool,
+    api_key: Option<String>,
+    webhook_secret: Option<String>,
+    #[serde(default = "default_linear_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+    #[serde(default)]
+    instances: Vec<TomlLinearInstanceConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlLinearInstanceConfig {
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    api_key: Option<String>,
+    webhook_secret: Option<String>,
+    port: Option<u16>,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_linear_port() -> u16 {
+    18798
+}
+
+#[derive(Deserialize)]
+struct TomlNextcloudConfig {
+    #[serde(default)]
+    enabled: bool,
+    base_url: Option<String>,
+    username: Option<String>,
+    app_password: Option<String>,
+    #[serde(default)]
+    rooms: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlMqttConfig {
+    #[serde(default)]
+    enabled: bool,
+    host: Option<String>,
+    #[serde(default = "default_mqtt_port")]
+    port: u16,
+    #[serde(default = "default_mqtt_client_id")]
+    client_id: String,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    response_topic: Option<String>,
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_client_id() -> String {
+    "spacebot".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlWebsocketConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_websocket_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+    auth_token: Option<String>,
+}
+
+fn default_websocket_port() -> u16 {
+    18799
+}
+
+#[derive(Deserialize)]
+struct TomlConsoleConfig {
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[derive(Deserialize)]
+struct TomlVoiceConfig {
+    #[serde(default)]
+    enabled: bool,
+    auth_token: Option<String>,
+    #[serde(default = "default_voice_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+    #[serde(default = "default_voice_greeting")]
+    greeting: String,
+    #[serde(default = "default_voice_voice")]
+    voice: String,
+    #[serde(default = "default_voice_language")]
+    language: String,
+}
+
+fn default_voice_port() -> u16 {
+    18800
+}
+
+fn default_voice_greeting() -> String {
+    "Hello! How can I help you?".to_string()
+}
+
+fn default_voice_voice() -> String {
+    "alice".to_string()
+}
+
+fn default_voice_language() -> String {
+    "en-US".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlSteamConfig {
+    #[serde(default)]
+    enabled: bool,
+    access_token: Option<String>,
+    #[serde(default)]
+    allowed_friends: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlViberConfig {
+    #[serde(default)]
+    enabled: bool,
+    auth_token: Option<String>,
+    #[serde(default = "default_viber_sender_name")]
+    sender_name: String,
+    #[serde(default = "default_viber_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_viber_sender_name() -> String {
+    "spacebot".to_string()
+}
+
+fn default_viber_port() -> u16 {
+    18801
+}
+
+#[derive(Deserialize)]
+struct TomlRssConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    feeds: Vec<TomlRssFeedConfig>,
+    #[serde(default = "default_rss_poll_interval_secs")]
+    poll_interval_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct TomlRssFeedConfig {
+    name: String,
+    url: String,
+}
+
+fn default_rss_poll_interval_secs() -> u64 {
+    300
+}
+
+#[derive(Deserialize)]
+struct TomlBlueskyConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    instances: Vec<TomlBlueskyInstanceConfig>,
+}
+
+#[derive(Deserialize)]
+struct TomlBlueskyInstanceConfig {
+    name: String,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    #[serde(default = "default_bluesky_service")]
+    service: String,
+    handle: Option<String>,
+    app_password: Option<String>,
+}
+
+fn default_bluesky_service() -> String {
+    "https://bsky.social".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlMastodonConfig {
+    #[serde(default)]
+    enabled: bool,
+    url: Option<String>,
+    access_token: Option<String>,
+    #[serde(default = "default_mastodon_reply_visibility")]
+    reply_visibility: String,
+}
+
+fn default_mastodon_reply_visibility() -> String {
+    "inherit".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlGoogleChatConfig {
+    #[serde(default)]
+    enabled: bool,
+    credentials_path: Option<String>,
+    audience: Option<String>,
+    #[serde(default = "default_googlechat_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+    #[serde(default)]
+    spaces: Vec<String>,
+    #[serde(default)]
+    users: Vec<String>,
+}
+
+fn default_googlechat_port() -> u16 {
+    18793
+}
+
+#[derive(Deserialize)]
+struct TomlNtfyConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_ntfy_server")]
+    server: String,
+    topic: Option<String>,
+    token: Option<String>,
+}
+
+fn default_ntfy_server() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlPushoverConfig {
+    #[serde(default)]
+    enabled: bool,
+    token: Option<String>,
+    user: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlGotifyConfig {
+    #[serde(default)]
+    enabled: bool,
+    server: Option<String>,
+    token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlZulipConfig {
+    #[serde(default)]
+    enabled: bool,
+    url: Option<String>,
+    email: Option<String>,
+    api_key: Option<String>,
+    #[serde(default)]
+    streams: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TomlSignalConfig {
+    #[serde(default)]
+    enabled: bool,
+    account: Option<String>,
+    #[serde(default = "default_signal_daemon_addr")]
+    daemon_addr: String,
+}
+
+fn default_signal_daemon_addr() -> String {
+    "127.0.0.1:7583".to_string()
+}
+
+#[derive(Deserialize)]
+struct TomlTeamsConfig {
+    #[serde(default)]
+    enabled: bool,
+    app_id: Option<String>,
+    app_password: Option<String>,
+    tenant_id: Option<String>,
+    #[serde(default = "default_teams_port")]
+    port: u16,
+    #[serde(default = "default_webhook_bind")]
+    bind: String,
+}
+
+fn default_teams_port() -> u16 {
+    18790
+}
+
+fn default_webhook_port() -> u16 {
+    18789
+}
+fn default_webhook_bind() -> String {
+    "127.0.0.1".into()
+}
+
+fn default_email_imap_port() -> u16 {
+    993
+}
+
+fn default_email_imap_use_tls() -> bool {
+    true
+}
+
+fn default_email_smtp_port() -> u16 {
+    587
+}
+
+fn default_email_smtp_use_starttls() -> bool {
+    true
+}
+
+fn default_email_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_email_folders() -> Vec<String> {
+    vec!["INBOX".to_string()]
+}
+
+fn default_email_max_body_bytes() -> usize {
+    256 * 1024
+}
+
+fn default_email_max_attachment_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_email_size_policy_reply() -> bool {
+    true
+}
+
+fn default_email_max_replies_per_hour() -> usize {
+    10
+}
+
+#[derive(Deserialize)]
+struct TomlBinding {
+    agent_id: String,
+    channel: String,
+    #[serde(default)]
+    adapter: Option<String>,
     guild_id: Option<String>,
     workspace_id: Option<String>,
     chat_id: Option<String>,
@@ -2969,6 +5195,34 @@ struct TomlBinding {
     require_mention: bool,
     #[serde(default)]
     dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    onboarding: Vec<String>,
+    #[serde(default)]
+    allowed_regions: Vec<String>,
+    #[serde(default)]
+    trigger_prefix: Option<String>,
+    #[serde(default)]
+    wake_words: Vec<String>,
+    #[serde(default = "default_bot_loop_protection")]
+    bot_loop_protection: bool,
+    #[serde(default = "default_link_safety")]
+    link_safety: bool,
+    #[serde(default)]
+    reconnect_greeting: bool,
+    #[serde(default = "default_reconnect_greeting_threshold_secs")]
+    reconnect_greeting_threshold_secs: u64,
+}
+
+fn default_bot_loop_protection() -> bool {
+    true
+}
+
+fn default_link_safety() -> bool {
+    true
+}
+
+fn default_reconnect_greeting_threshold_secs() -> u64 {
+    900
 }
 
 /// Resolve a value that might be an "env:VAR_NAME" reference.
@@ -3303,6 +5557,38 @@ impl Config {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("failed to read config from {}", path.display()))?;
 
+        // Upgrade older schema layouts before parsing, keeping a backup of
+        // the original alongside the modernized file.
+        let content = match crate::config_migrations::migrate_content(&content) {
+            Ok(Some((migrated, outcome))) => {
+                let backup_path = path.with_extension("toml.bak");
+                match std::fs::write(&backup_path, &content) {
+                    Ok(()) => {
+                        if let Err(error) = std::fs::write(path, &migrated) {
+                            tracing::warn!(%error, "failed to write migrated config; keeping upgraded form in memory only");
+                        } else {
+                            tracing::info!(
+                                from_version = outcome.from_version,
+                                to_version = outcome.to_version,
+                                applied = ?outcome.applied,
+                                backup = %backup_path.display(),
+                                "upgraded config schema"
+                            );
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "failed to back up config before schema migration; not writing back");
+                    }
+                }
+                migrated
+            }
+            Ok(None) => content,
+            Err(error) => {
+                tracing::warn!(%error, "config schema migration failed; loading file as-is");
+                content
+            }
+        };
+
         let toml_config: TomlConfig = toml::from_str(&content)
             .with_context(|| format!("failed to parse config from {}", path.display()))?;
 
@@ -3338,6 +5624,7 @@ impl Config {
             moonshot_key: std::env::var("MOONSHOT_API_KEY").ok(),
             zai_coding_plan_key: std::env::var("ZAI_CODING_PLAN_API_KEY").ok(),
             providers: HashMap::new(),
+            provider_regions: HashMap::new(),
         };
 
         // Populate providers from env vars (same as from_toml does)
@@ -3753,6 +6040,7 @@ impl Config {
                     .unwrap_or_else(|_| "spacebot".into()),
                 sample_rate: 1.0,
             },
+            network: NetworkConfig::default(),
         })
     }
 
@@ -3928,6 +6216,17 @@ impl Config {
                 .as_deref()
                 .and_then(resolve_env_value)
                 .or_else(|| std::env::var("ZAI_CODING_PLAN_API_KEY").ok()),
+            provider_regions: toml
+                .llm
+                .providers
+                .iter()
+                .filter_map(|(provider_id, config)| {
+                    config
+                        .region
+                        .as_ref()
+                        .map(|region| (provider_id.to_lowercase(), region.to_lowercase()))
+                })
+                .collect(),
             providers: toml
                 .llm
                 .providers
@@ -4362,6 +6661,14 @@ impl Config {
                 .as_deref()
                 .and_then(resolve_env_value),
             history_backfill_count: base_defaults.history_backfill_count,
+            cite_sources: toml
+                .defaults
+                .cite_sources
+                .unwrap_or(base_defaults.cite_sources),
+            time_context: toml
+                .defaults
+                .time_context
+                .unwrap_or(base_defaults.time_context),
             cron: Vec::new(),
             opencode: toml
                 .defaults
@@ -4394,6 +6701,108 @@ impl Config {
                     }
                 })
                 .unwrap_or_else(|| base_defaults.opencode.clone()),
+            ops: toml
+                .defaults
+                .ops
+                .map(|ops| {
+                    let base = &base_defaults.ops;
+                    OpsConfig {
+                        enabled: ops.enabled.unwrap_or(base.enabled),
+                        docker_enabled: ops.docker_enabled.unwrap_or(base.docker_enabled),
+                        systemd_enabled: ops.systemd_enabled.unwrap_or(base.systemd_enabled),
+                        allowed_containers: ops.allowed_containers,
+                        allowed_units: ops.allowed_units,
+                        allow_restart: ops.allow_restart.unwrap_or(base.allow_restart),
+                        log_tail_limit: ops.log_tail_limit.unwrap_or(base.log_tail_limit),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.ops.clone()),
+            kube: toml
+                .defaults
+                .kube
+                .map(|kube| {
+                    let base = &base_defaults.kube;
+                    KubeConfig {
+                        enabled: kube.enabled.unwrap_or(base.enabled),
+                        contexts: kube.contexts,
+                        namespaces: kube.namespaces,
+                        log_tail_limit: kube.log_tail_limit.unwrap_or(base.log_tail_limit),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.kube.clone()),
+            sql: toml
+                .defaults
+                .sql
+                .map(|sql| {
+                    let base = &base_defaults.sql;
+                    SqlConfig {
+                        enabled: sql.enabled.unwrap_or(base.enabled),
+                        databases: sql
+                            .databases
+                            .into_iter()
+                            .filter_map(|db| {
+                                let dsn = resolve_env_value(&db.dsn)?;
+                                Some(SqlDatabaseConfig { name: db.name, dsn })
+                            })
+                            .collect(),
+                        max_rows: sql.max_rows.unwrap_or(base.max_rows),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.sql.clone()),
+            ssh: toml
+                .defaults
+                .ssh
+                .map(|ssh| {
+                    let base = &base_defaults.ssh;
+                    SshConfig {
+                        enabled: ssh.enabled.unwrap_or(base.enabled),
+                        allow_destructive: ssh
+                            .allow_destructive
+                            .unwrap_or(base.allow_destructive),
+                        connect_timeout_secs: ssh
+                            .connect_timeout_secs
+                            .unwrap_or(base.connect_timeout_secs),
+                        hosts: ssh
+                            .hosts
+                            .into_iter()
+                            .map(|host| SshHostConfig {
+                                name: host.name,
+                                address: host.address,
+                                port: host.port.unwrap_or(22),
+                                identity_file: host.identity_file,
+                                allowed_commands: host.allowed_commands,
+                                destructive_commands: host.destructive_commands,
+                            })
+                            .collect(),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.ssh.clone()),
+            translation_memory: toml
+                .defaults
+                .translation_memory
+                .map(|tm| {
+                    let base = &base_defaults.translation_memory;
+                    TranslationMemoryConfig {
+                        enabled: tm.enabled.unwrap_or(base.enabled),
+                        ttl_secs: tm.ttl_secs.unwrap_or(base.ttl_secs),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.translation_memory.clone()),
+            memory_maintenance: toml
+                .defaults
+                .memory_maintenance
+                .map(|mm| {
+                    let base = &base_defaults.memory_maintenance;
+                    MemoryMaintenanceConfig {
+                        enabled: mm.enabled.unwrap_or(base.enabled),
+                        active_hours: (
+                            mm.start_hour.unwrap_or(base.active_hours.0).min(23),
+                            mm.end_hour.unwrap_or(base.active_hours.1).min(23),
+                        ),
+                        report_target: mm.report_target.or_else(|| base.report_target.clone()),
+                    }
+                })
+                .unwrap_or_else(|| base_defaults.memory_maintenance.clone()),
             worker_log_mode: toml
                 .defaults
                 .worker_log_mode
@@ -4420,6 +6829,7 @@ impl Config {
                         cron_expr: h.cron_expr,
                         interval_secs: h.interval_secs.unwrap_or(3600),
                         delivery_target: h.delivery_target,
+                        template: h.template,
                         active_hours: match (h.active_start_hour, h.active_end_hour) {
                             (Some(s), Some(e)) => Some((s, e)),
                             _ => None,
@@ -4552,44 +6962,174 @@ impl Config {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        if agents.is_empty() {
-            agents.push(AgentConfig {
-                id: "main".into(),
-                default: true,
-                display_name: None,
-                role: None,
-                workspace: None,
-                routing: None,
-                max_concurrent_branches: None,
-                max_concurrent_workers: None,
-                max_turns: None,
-                branch_max_turns: None,
-                context_window: None,
-                compaction: None,
-                memory_persistence: None,
-                coalesce: None,
-                ingestion: None,
-                cortex: None,
-                warmup: None,
-                browser: None,
-                mcp: None,
-                brave_search_key: None,
-                cron_timezone: None,
-                user_timezone: None,
-                sandbox: None,
-                cron: Vec::new(),
-            });
-        }
+        if agents.is_empty() {
+            agents.push(AgentConfig {
+                id: "main".into(),
+                default: true,
+                display_name: None,
+                role: None,
+                workspace: None,
+                routing: None,
+                max_concurrent_branches: None,
+                max_concurrent_workers: None,
+                max_turns: None,
+                branch_max_turns: None,
+                context_window: None,
+                compaction: None,
+                memory_persistence: None,
+                coalesce: None,
+                ingestion: None,
+                cortex: None,
+                warmup: None,
+                browser: None,
+                mcp: None,
+                brave_search_key: None,
+                cron_timezone: None,
+                user_timezone: None,
+                sandbox: None,
+                cron: Vec::new(),
+            });
+        }
+
+        if !agents.iter().any(|a| a.default)
+            && let Some(first) = agents.first_mut()
+        {
+            first.default = true;
+        }
+
+        let messaging = MessagingConfig {
+            discord: toml.messaging.discord.and_then(|d| {
+                let instances = d
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let token = instance.token.as_deref().and_then(resolve_env_value);
+                        if instance.enabled && token.is_none() {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "discord instance is enabled but token is missing/unresolvable — disabling"
+                            );
+                        }
+                        DiscordInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && token.is_some(),
+                            token: token.unwrap_or_default(),
+                            dm_allowed_users: instance.dm_allowed_users,
+                            allow_bot_messages: instance.allow_bot_messages,
+                            permission_sync_interval_secs: instance
+                                .permission_sync_interval_secs,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let token = std::env::var("DISCORD_BOT_TOKEN")
+                    .ok()
+                    .or_else(|| d.token.as_deref().and_then(resolve_env_value));
+
+                if token.is_none() && instances.is_empty() {
+                    return None;
+                }
+
+                let voice = d.voice.and_then(|v| {
+                    let guild_id = v.guild_id?;
+                    let channel_id = v.channel_id?;
+                    let api_key = std::env::var("OPENAI_API_KEY")
+                        .ok()
+                        .or_else(|| v.api_key.as_deref().and_then(resolve_env_value))
+                        .unwrap_or_default();
+                    if v.enabled && api_key.is_empty() {
+                        tracing::warn!(
+                            "discord voice is enabled but the speech api_key is missing/unresolvable — disabling"
+                        );
+                    }
+                    Some(DiscordVoiceConfig {
+                        enabled: v.enabled && !api_key.is_empty(),
+                        guild_id,
+                        channel_id,
+                        api_base: v.api_base,
+                        api_key,
+                        stt_model: v.stt_model,
+                        tts_model: v.tts_model,
+                        tts_voice: v.tts_voice,
+                    })
+                });
+
+                Some(DiscordConfig {
+                    enabled: d.enabled,
+                    token: token.unwrap_or_default(),
+                    instances,
+                    dm_allowed_users: d.dm_allowed_users,
+                    allow_bot_messages: d.allow_bot_messages,
+                    permission_sync_interval_secs: d.permission_sync_interval_secs,
+                    voice,
+                    proxy: d.proxy.as_deref().and_then(resolve_env_value),
+                })
+            }),
+            slack: toml.messaging.slack.and_then(|s| {
+                let instances = s
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let bot_token =
+                            instance.bot_token.as_deref().and_then(resolve_env_value);
+                        let app_token =
+                            instance.app_token.as_deref().and_then(resolve_env_value);
+                        if instance.enabled && (bot_token.is_none() || app_token.is_none()) {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "slack instance is enabled but tokens are missing/unresolvable — disabling"
+                            );
+                        }
+                        let has_credentials = bot_token.is_some() && app_token.is_some();
+                        SlackInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && has_credentials,
+                            bot_token: bot_token.unwrap_or_default(),
+                            app_token: app_token.unwrap_or_default(),
+                            dm_allowed_users: instance.dm_allowed_users,
+                            commands: instance
+                                .commands
+                                .into_iter()
+                                .map(|command| SlackCommandConfig {
+                                    command: command.command,
+                                    agent_id: command.agent_id,
+                                    description: command.description,
+                                })
+                                .collect(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let bot_token = std::env::var("SLACK_BOT_TOKEN")
+                    .ok()
+                    .or_else(|| s.bot_token.as_deref().and_then(resolve_env_value));
+                let app_token = std::env::var("SLACK_APP_TOKEN")
+                    .ok()
+                    .or_else(|| s.app_token.as_deref().and_then(resolve_env_value));
 
-        if !agents.iter().any(|a| a.default)
-            && let Some(first) = agents.first_mut()
-        {
-            first.default = true;
-        }
+                if (bot_token.is_none() || app_token.is_none()) && instances.is_empty() {
+                    return None;
+                }
 
-        let messaging = MessagingConfig {
-            discord: toml.messaging.discord.and_then(|d| {
-                let instances = d
+                Some(SlackConfig {
+                    enabled: s.enabled,
+                    bot_token: bot_token.unwrap_or_default(),
+                    app_token: app_token.unwrap_or_default(),
+                    instances,
+                    dm_allowed_users: s.dm_allowed_users,
+                    commands: s
+                        .commands
+                        .into_iter()
+                        .map(|c| SlackCommandConfig {
+                            command: c.command,
+                            agent_id: c.agent_id,
+                            description: c.description,
+                        })
+                        .collect(),
+                })
+            }),
+            telegram: toml.messaging.telegram.and_then(|t| {
+                let instances = t
                     .instances
                     .into_iter()
                     .map(|instance| {
@@ -4597,359 +7137,986 @@ impl Config {
                         if instance.enabled && token.is_none() {
                             tracing::warn!(
                                 adapter = %instance.name,
-                                "discord instance is enabled but token is missing/unresolvable — disabling"
+                                "telegram instance is enabled but token is missing/unresolvable — disabling"
                             );
                         }
-                        DiscordInstanceConfig {
+                        TelegramInstanceConfig {
                             name: instance.name,
                             enabled: instance.enabled && token.is_some(),
                             token: token.unwrap_or_default(),
                             dm_allowed_users: instance.dm_allowed_users,
-                            allow_bot_messages: instance.allow_bot_messages,
+                            trigger_prefix: instance.trigger_prefix,
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let token = std::env::var("DISCORD_BOT_TOKEN")
+                let token = std::env::var("TELEGRAM_BOT_TOKEN")
                     .ok()
-                    .or_else(|| d.token.as_deref().and_then(resolve_env_value));
+                    .or_else(|| t.token.as_deref().and_then(resolve_env_value));
 
                 if token.is_none() && instances.is_empty() {
                     return None;
                 }
 
-                Some(DiscordConfig {
-                    enabled: d.enabled,
+                Some(TelegramConfig {
+                    enabled: t.enabled,
                     token: token.unwrap_or_default(),
                     instances,
-                    dm_allowed_users: d.dm_allowed_users,
-                    allow_bot_messages: d.allow_bot_messages,
+                    dm_allowed_users: t.dm_allowed_users,
+                    trigger_prefix: t.trigger_prefix,
+                    proxy: t.proxy.as_deref().and_then(resolve_env_value),
+                    mode: TelegramMode::parse(t.mode.as_deref()),
+                    webhook_url: t
+                        .webhook_url
+                        .as_deref()
+                        .and_then(resolve_env_value)
+                        .unwrap_or_default(),
+                    webhook_secret: t
+                        .webhook_secret
+                        .as_deref()
+                        .and_then(resolve_env_value)
+                        .unwrap_or_default(),
+                    stt: t.stt.map(|stt| TelegramSttConfig {
+                        enabled: stt.enabled,
+                        api_base: stt
+                            .api_base
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
+                        api_key: stt
+                            .api_key
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default(),
+                        model: stt.model.unwrap_or_else(|| "whisper-1".to_string()),
+                    }),
+                    commands: t
+                        .commands
+                        .into_iter()
+                        .map(|command| TelegramCommandConfig {
+                            command: command.command,
+                            agent_id: command.agent_id,
+                            description: command.description,
+                        })
+                        .collect(),
+                    moderation: TelegramModerationConfig {
+                        allow_delete: t.moderation.allow_delete,
+                        allow_mute: t.moderation.allow_mute,
+                        allow_ban: t.moderation.allow_ban,
+                        allow_pin: t.moderation.allow_pin,
+                    },
+                })
+            }),
+            email: toml.messaging.email.and_then(|email| {
+                let instances = email
+                    .instances
+                    .into_iter()
+                    .map(|instance| {
+                        let imap_host =
+                            instance.imap_host.as_deref().and_then(resolve_env_value);
+                        let imap_username =
+                            instance.imap_username.as_deref().and_then(resolve_env_value);
+                        let imap_password =
+                            instance.imap_password.as_deref().and_then(resolve_env_value);
+                        let smtp_host =
+                            instance.smtp_host.as_deref().and_then(resolve_env_value);
+
+                        let backend = EmailBackend::parse(instance.backend.as_deref());
+                        let graph_tenant_id = instance
+                            .graph_tenant_id
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+                        let graph_client_id = instance
+                            .graph_client_id
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+                        let graph_client_secret = instance
+                            .graph_client_secret
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+                        let graph_user = instance
+                            .graph_user
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+                        let jmap_session_url = instance
+                            .jmap_session_url
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+                        let jmap_token = instance
+                            .jmap_token
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_default();
+
+                        let has_credentials = match backend {
+                            EmailBackend::Imap => {
+                                imap_host.is_some()
+                                    && imap_username.is_some()
+                                    && imap_password.is_some()
+                                    && smtp_host.is_some()
+                            }
+                            EmailBackend::Graph => {
+                                !graph_tenant_id.is_empty()
+                                    && !graph_client_id.is_empty()
+                                    && !graph_client_secret.is_empty()
+                            }
+                            EmailBackend::Jmap => {
+                                !jmap_session_url.is_empty() && !jmap_token.is_empty()
+                            }
+                        };
+
+                        if instance.enabled && !has_credentials {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "email instance is enabled but credentials are missing/unresolvable — disabling"
+                            );
+                        }
+
+                        let imap_username_val = imap_username.unwrap_or_default();
+                        let imap_password_val = imap_password.unwrap_or_default();
+                        let smtp_username = instance
+                            .smtp_username
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_else(|| imap_username_val.clone());
+                        let smtp_password = instance
+                            .smtp_password
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_else(|| imap_password_val.clone());
+                        let from_address = instance
+                            .from_address
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                            .unwrap_or_else(|| smtp_username.clone());
+                        let from_name =
+                            instance.from_name.as_deref().and_then(resolve_env_value);
+                        let from_address = if from_address.is_empty() {
+                            graph_user.clone()
+                        } else {
+                            from_address
+                        };
+
+                        EmailInstanceConfig {
+                            name: instance.name,
+                            enabled: instance.enabled && has_credentials,
+                            imap_host: imap_host.unwrap_or_default(),
+                            imap_port: instance.imap_port,
+                            imap_username: imap_username_val,
+                            imap_password: imap_password_val,
+                            imap_use_tls: instance.imap_use_tls,
+                            smtp_host: smtp_host.unwrap_or_default(),
+                            smtp_port: instance.smtp_port,
+                            smtp_username,
+                            smtp_password,
+                            smtp_use_starttls: instance.smtp_use_starttls,
+                            from_address,
+                            from_name,
+                            poll_interval_secs: instance.poll_interval_secs,
+                            folders: if instance.folders.is_empty() {
+                                vec!["INBOX".to_string()]
+                            } else {
+                                instance.folders
+                            },
+                            allowed_senders: instance.allowed_senders,
+                            max_body_bytes: instance.max_body_bytes,
+                            max_attachment_bytes: instance.max_attachment_bytes,
+                            size_policy_reply: instance.size_policy_reply,
+                            max_replies_per_hour: instance.max_replies_per_hour,
+                            spam_patterns: instance.spam_patterns.clone(),
+                            spam_scorer_command: instance
+                                .spam_scorer_command
+                                .clone()
+                                .unwrap_or_default(),
+                            drop_failed_auth: instance.drop_failed_auth,
+                            pgp_sign: instance.pgp_sign,
+                            pgp_key_id: instance.pgp_key_id.clone().unwrap_or_default(),
+                            dkim_selector: instance.dkim_selector.clone().unwrap_or_default(),
+                            dkim_key_path: instance.dkim_key_path.clone().unwrap_or_default(),
+                            custom_headers: instance.custom_headers.clone(),
+                            reply_all: instance.reply_all,
+                            backend,
+                            graph_tenant_id,
+                            graph_client_id,
+                            graph_client_secret,
+                            graph_user,
+                            jmap_session_url,
+                            jmap_token,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let imap_host = std::env::var("EMAIL_IMAP_HOST")
+                    .ok()
+                    .or_else(|| email.imap_host.as_deref().and_then(resolve_env_value));
+                let imap_username = std::env::var("EMAIL_IMAP_USERNAME")
+                    .ok()
+                    .or_else(|| email.imap_username.as_deref().and_then(resolve_env_value));
+                let imap_password = std::env::var("EMAIL_IMAP_PASSWORD")
+                    .ok()
+                    .or_else(|| email.imap_password.as_deref().and_then(resolve_env_value));
+                let smtp_host = std::env::var("EMAIL_SMTP_HOST")
+                    .ok()
+                    .or_else(|| email.smtp_host.as_deref().and_then(resolve_env_value));
+
+                let backend = EmailBackend::parse(
+                    std::env::var("EMAIL_BACKEND")
+                        .ok()
+                        .as_deref()
+                        .or(email.backend.as_deref()),
+                );
+                let graph_tenant_id = std::env::var("EMAIL_GRAPH_TENANT_ID")
+                    .ok()
+                    .or_else(|| email.graph_tenant_id.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_default();
+                let graph_client_id = std::env::var("EMAIL_GRAPH_CLIENT_ID")
+                    .ok()
+                    .or_else(|| email.graph_client_id.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_default();
+                let graph_client_secret = std::env::var("EMAIL_GRAPH_CLIENT_SECRET")
+                    .ok()
+                    .or_else(|| {
+                        email
+                            .graph_client_secret
+                            .as_deref()
+                            .and_then(resolve_env_value)
+                    })
+                    .unwrap_or_default();
+                let graph_user = std::env::var("EMAIL_GRAPH_USER")
+                    .ok()
+                    .or_else(|| email.graph_user.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_default();
+                let jmap_session_url = std::env::var("EMAIL_JMAP_SESSION_URL")
+                    .ok()
+                    .or_else(|| email.jmap_session_url.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_default();
+                let jmap_token = std::env::var("EMAIL_JMAP_TOKEN")
+                    .ok()
+                    .or_else(|| email.jmap_token.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_default();
+
+                let has_default = match backend {
+                    EmailBackend::Imap => {
+                        imap_host.is_some()
+                            && imap_username.is_some()
+                            && imap_password.is_some()
+                            && smtp_host.is_some()
+                    }
+                    EmailBackend::Graph => {
+                        !graph_tenant_id.is_empty()
+                            && !graph_client_id.is_empty()
+                            && !graph_client_secret.is_empty()
+                    }
+                    EmailBackend::Jmap => {
+                        !jmap_session_url.is_empty() && !jmap_token.is_empty()
+                    }
+                };
+
+                if !has_default && instances.is_empty() {
+                    return None;
+                }
+
+                let imap_host = imap_host.unwrap_or_default();
+                let imap_username = imap_username.unwrap_or_default();
+                let imap_password = imap_password.unwrap_or_default();
+                let smtp_host = smtp_host.unwrap_or_default();
+                let smtp_username = std::env::var("EMAIL_SMTP_USERNAME")
+                    .ok()
+                    .or_else(|| email.smtp_username.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_else(|| imap_username.clone());
+                let smtp_password = std::env::var("EMAIL_SMTP_PASSWORD")
+                    .ok()
+                    .or_else(|| email.smtp_password.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_else(|| imap_password.clone());
+
+                let from_address = std::env::var("EMAIL_FROM_ADDRESS")
+                    .ok()
+                    .or_else(|| email.from_address.as_deref().and_then(resolve_env_value))
+                    .unwrap_or_else(|| smtp_username.clone());
+                let from_name = std::env::var("EMAIL_FROM_NAME")
+                    .ok()
+                    .or_else(|| email.from_name.as_deref().and_then(resolve_env_value));
+                let from_address = if from_address.is_empty() {
+                    graph_user.clone()
+                } else {
+                    from_address
+                };
+
+                Some(EmailConfig {
+                    enabled: email.enabled,
+                    imap_host,
+                    imap_port: email.imap_port,
+                    imap_username,
+                    imap_password,
+                    imap_use_tls: email.imap_use_tls,
+                    smtp_host,
+                    smtp_port: email.smtp_port,
+                    smtp_username,
+                    smtp_password,
+                    smtp_use_starttls: email.smtp_use_starttls,
+                    from_address,
+                    from_name,
+                    poll_interval_secs: email.poll_interval_secs,
+                    folders: if email.folders.is_empty() {
+                        vec!["INBOX".to_string()]
+                    } else {
+                        email.folders
+                    },
+                    allowed_senders: email.allowed_senders,
+                    max_body_bytes: email.max_body_bytes,
+                    max_attachment_bytes: email.max_attachment_bytes,
+                    size_policy_reply: email.size_policy_reply,
+                    max_replies_per_hour: email.max_replies_per_hour,
+                    spam_patterns: email.spam_patterns.clone(),
+                    spam_scorer_command: email.spam_scorer_command.clone().unwrap_or_default(),
+                    drop_failed_auth: email.drop_failed_auth,
+                    pgp_sign: email.pgp_sign,
+                    pgp_key_id: email.pgp_key_id.clone().unwrap_or_default(),
+                    dkim_selector: email.dkim_selector.clone().unwrap_or_default(),
+                    dkim_key_path: email.dkim_key_path.clone().unwrap_or_default(),
+                    custom_headers: email.custom_headers.clone(),
+                    reply_all: email.reply_all,
+                    backend,
+                    graph_tenant_id,
+                    graph_client_id,
+                    graph_client_secret,
+                    graph_user,
+                    jmap_session_url,
+                    jmap_token,
+                    instances,
                 })
             }),
-            slack: toml.messaging.slack.and_then(|s| {
-                let instances = s
+            webhook: toml.messaging.webhook.map(|w| WebhookConfig {
+                enabled: w.enabled,
+                port: w.port,
+                bind: w.bind,
+                auth_token: w.auth_token.as_deref().and_then(resolve_env_value),
+            }),
+            twitch: toml.messaging.twitch.and_then(|t| {
+                let instances = t
                     .instances
                     .into_iter()
                     .map(|instance| {
-                        let bot_token =
-                            instance.bot_token.as_deref().and_then(resolve_env_value);
-                        let app_token =
-                            instance.app_token.as_deref().and_then(resolve_env_value);
-                        if instance.enabled && (bot_token.is_none() || app_token.is_none()) {
+                        let username = instance.username.as_deref().and_then(resolve_env_value);
+                        let oauth_token = instance
+                            .oauth_token
+                            .as_deref()
+                            .and_then(resolve_env_value);
+                        if instance.enabled && (username.is_none() || oauth_token.is_none()) {
                             tracing::warn!(
                                 adapter = %instance.name,
-                                "slack instance is enabled but tokens are missing/unresolvable — disabling"
+                                "twitch instance is enabled but credentials are missing/unresolvable — disabling"
                             );
                         }
-                        let has_credentials = bot_token.is_some() && app_token.is_some();
-                        SlackInstanceConfig {
+                        let has_credentials = username.is_some() && oauth_token.is_some();
+                        let client_id = instance.client_id.as_deref().and_then(resolve_env_value);
+                        let client_secret = instance
+                            .client_secret
+                            .as_deref()
+                            .and_then(resolve_env_value);
+                        let refresh_token = instance
+                            .refresh_token
+                            .as_deref()
+                            .and_then(resolve_env_value);
+                        TwitchInstanceConfig {
                             name: instance.name,
                             enabled: instance.enabled && has_credentials,
-                            bot_token: bot_token.unwrap_or_default(),
-                            app_token: app_token.unwrap_or_default(),
-                            dm_allowed_users: instance.dm_allowed_users,
-                            commands: instance
-                                .commands
-                                .into_iter()
-                                .map(|command| SlackCommandConfig {
-                                    command: command.command,
-                                    agent_id: command.agent_id,
-                                    description: command.description,
-                                })
-                                .collect(),
+                            username: username.unwrap_or_default(),
+                            oauth_token: oauth_token.unwrap_or_default(),
+                            client_id,
+                            client_secret,
+                            refresh_token,
+                            channels: instance.channels,
+                            trigger_prefix: instance.trigger_prefix,
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let bot_token = std::env::var("SLACK_BOT_TOKEN")
+                let username = std::env::var("TWITCH_BOT_USERNAME")
                     .ok()
-                    .or_else(|| s.bot_token.as_deref().and_then(resolve_env_value));
-                let app_token = std::env::var("SLACK_APP_TOKEN")
+                    .or_else(|| t.username.as_deref().and_then(resolve_env_value));
+                let oauth_token = std::env::var("TWITCH_OAUTH_TOKEN")
                     .ok()
-                    .or_else(|| s.app_token.as_deref().and_then(resolve_env_value));
+                    .or_else(|| t.oauth_token.as_deref().and_then(resolve_env_value));
 
-                if (bot_token.is_none() || app_token.is_none()) && instances.is_empty() {
+                if (username.is_none() || oauth_token.is_none()) && instances.is_empty() {
                     return None;
                 }
 
-                Some(SlackConfig {
-                    enabled: s.enabled,
-                    bot_token: bot_token.unwrap_or_default(),
-                    app_token: app_token.unwrap_or_default(),
+                let client_id = t
+                    .client_id
+                    .as_deref()
+                    .and_then(resolve_env_value)
+                    .or_else(|| std::env::var("TWITCH_CLIENT_ID").ok());
+                let client_secret = t
+                    .client_secret
+                    .as_deref()
+                    .and_then(resolve_env_value)
+                    .or_else(|| std::env::var("TWITCH_CLIENT_SECRET").ok());
+                let refresh_token = t
+                    .refresh_token
+                    .as_deref()
+                    .and_then(resolve_env_value)
+                    .or_else(|| std::env::var("TWITCH_REFRESH_TOKEN").ok());
+                Some(TwitchConfig {
+                    enabled: t.enabled,
+                    username: username.unwrap_or_default(),
+                    oauth_token: oauth_token.unwrap_or_default(),
+                    client_id,
+                    client_secret,
+                    refresh_token,
                     instances,
-                    dm_allowed_users: s.dm_allowed_users,
-                    commands: s
-                        .commands
-                        .into_iter()
-                        .map(|c| SlackCommandConfig {
-                            command: c.command,
-                            agent_id: c.agent_id,
-                            description: c.description,
-                        })
-                        .collect(),
+                    channels: t.channels,
+                    trigger_prefix: t.trigger_prefix,
                 })
             }),
-            telegram: toml.messaging.telegram.and_then(|t| {
-                let instances = t
-                    .instances
-                    .into_iter()
-                    .map(|instance| {
-                        let token = instance.token.as_deref().and_then(resolve_env_value);
-                        if instance.enabled && token.is_none() {
-                            tracing::warn!(
-                                adapter = %instance.name,
-                                "telegram instance is enabled but token is missing/unresolvable — disabling"
-                            );
-                        }
-                        TelegramInstanceConfig {
-                            name: instance.name,
-                            enabled: instance.enabled && token.is_some(),
-                            token: token.unwrap_or_default(),
-                            dm_allowed_users: instance.dm_allowed_users,
-                        }
-                    })
-                    .collect::<Vec<_>>();
+            mattermost: toml.messaging.mattermost.and_then(|m| {
+                let url = std::env::var("MATTERMOST_URL")
+                    .ok()
+                    .or_else(|| m.url.as_deref().and_then(resolve_env_value));
+                let token = std::env::var("MATTERMOST_TOKEN")
+                    .ok()
+                    .or_else(|| m.token.as_deref().and_then(resolve_env_value));
 
-                let token = std::env::var("TELEGRAM_BOT_TOKEN")
+                let (Some(url), Some(token)) = (url, token) else {
+                    return None;
+                };
+
+                Some(MattermostConfig {
+                    enabled: m.enabled,
+                    url,
+                    token,
+                    channels: m.channels,
+                })
+            }),
+            teams: toml.messaging.teams.and_then(|t| {
+                let app_id = std::env::var("TEAMS_APP_ID")
                     .ok()
-                    .or_else(|| t.token.as_deref().and_then(resolve_env_value));
+                    .or_else(|| t.app_id.as_deref().and_then(resolve_env_value));
+                let app_password = std::env::var("TEAMS_APP_PASSWORD")
+                    .ok()
+                    .or_else(|| t.app_password.as_deref().and_then(resolve_env_value));
+                let tenant_id = std::env::var("TEAMS_TENANT_ID")
+                    .ok()
+                    .or_else(|| t.tenant_id.as_deref().and_then(resolve_env_value));
 
-                if token.is_none() && instances.is_empty() {
+                let (Some(app_id), Some(app_password), Some(tenant_id)) =
+                    (app_id, app_password, tenant_id)
+                else {
                     return None;
-                }
+                };
 
-                Some(TelegramConfig {
+                Some(TeamsConfig {
                     enabled: t.enabled,
-                    token: token.unwrap_or_default(),
-                    instances,
-                    dm_allowed_users: t.dm_allowed_users,
+                    app_id,
+                    app_password,
+                    tenant_id,
+                    port: t.port,
+                    bind: t.bind,
                 })
             }),
-            email: toml.messaging.email.and_then(|email| {
-                let instances = email
+            signal: toml.messaging.signal.and_then(|s| {
+                let account = std::env::var("SIGNAL_ACCOUNT")
+                    .ok()
+                    .or_else(|| s.account.as_deref().and_then(resolve_env_value))?;
+                Some(SignalConfig {
+                    enabled: s.enabled,
+                    account,
+                    daemon_addr: s.daemon_addr,
+                })
+            }),
+            whatsapp: toml.messaging.whatsapp.and_then(|w| {
+                let access_token = std::env::var("WHATSAPP_ACCESS_TOKEN")
+                    .ok()
+                    .or_else(|| w.access_token.as_deref().and_then(resolve_env_value));
+                let phone_number_id = std::env::var("WHATSAPP_PHONE_NUMBER_ID")
+                    .ok()
+                    .or_else(|| w.phone_number_id.as_deref().and_then(resolve_env_value));
+                let verify_token = std::env::var("WHATSAPP_VERIFY_TOKEN")
+                    .ok()
+                    .or_else(|| w.verify_token.as_deref().and_then(resolve_env_value));
+                let app_secret = std::env::var("WHATSAPP_APP_SECRET")
+                    .ok()
+                    .or_else(|| w.app_secret.as_deref().and_then(resolve_env_value));
+
+                let (Some(access_token), Some(phone_number_id), Some(verify_token), Some(app_secret)) =
+                    (access_token, phone_number_id, verify_token, app_secret)
+                else {
+                    return None;
+                };
+
+                Some(WhatsAppConfig {
+                    enabled: w.enabled,
+                    access_token,
+                    phone_number_id,
+                    verify_token,
+                    app_secret,
+                    port: w.port,
+                    bind: w.bind,
+                })
+            }),
+            sms: toml.messaging.sms.and_then(|s| {
+                let account_sid = std::env::var("TWILIO_ACCOUNT_SID")
+                    .ok()
+                    .or_else(|| s.account_sid.as_deref().and_then(resolve_env_value));
+                let auth_token = std::env::var("TWILIO_AUTH_TOKEN")
+                    .ok()
+                    .or_else(|| s.auth_token.as_deref().and_then(resolve_env_value));
+
+                let (Some(account_sid), Some(auth_token)) = (account_sid, auth_token) else {
+                    return None;
+                };
+
+                Some(SmsConfig {
+                    enabled: s.enabled,
+                    account_sid,
+                    auth_token,
+                    numbers: s.numbers,
+                    port: s.port,
+                    bind: s.bind,
+                })
+            }),
+            zulip: toml.messaging.zulip.and_then(|z| {
+                let url = std::env::var("ZULIP_URL")
+                    .ok()
+                    .or_else(|| z.url.as_deref().and_then(resolve_env_value));
+                let email = std::env::var("ZULIP_EMAIL")
+                    .ok()
+                    .or_else(|| z.email.as_deref().and_then(resolve_env_value));
+                let api_key = std::env::var("ZULIP_API_KEY")
+                    .ok()
+                    .or_else(|| z.api_key.as_deref().and_then(resolve_env_value));
+
+                let (Some(url), Some(email), Some(api_key)) = (url, email, api_key) else {
+                    return None;
+                };
+
+                Some(ZulipConfig {
+                    enabled: z.enabled,
+                    url,
+                    email,
+                    api_key,
+                    streams: z.streams,
+                })
+            }),
+            rocketchat: toml.messaging.rocketchat.and_then(|r| {
+                let url = std::env::var("ROCKETCHAT_URL")
+                    .ok()
+                    .or_else(|| r.url.as_deref().and_then(resolve_env_value));
+                let username = std::env::var("ROCKETCHAT_USERNAME")
+                    .ok()
+                    .or_else(|| r.username.as_deref().and_then(resolve_env_value));
+                let password = std::env::var("ROCKETCHAT_PASSWORD")
+                    .ok()
+                    .or_else(|| r.password.as_deref().and_then(resolve_env_value));
+
+                let (Some(url), Some(username), Some(password)) = (url, username, password) else {
+                    return None;
+                };
+
+                Some(RocketChatConfig {
+                    enabled: r.enabled,
+                    url,
+                    username,
+                    password,
+                    channels: r.channels,
+                })
+            }),
+            nostr: toml.messaging.nostr.and_then(|n| {
+                let secret_key = std::env::var("NOSTR_SECRET_KEY")
+                    .ok()
+                    .or_else(|| n.secret_key.as_deref().and_then(resolve_env_value))?;
+                Some(NostrConfig {
+                    enabled: n.enabled,
+                    secret_key,
+                    relays: n.relays,
+                })
+            }),
+            xmpp: toml.messaging.xmpp.and_then(|x| {
+                let jid = std::env::var("XMPP_JID")
+                    .ok()
+                    .or_else(|| x.jid.as_deref().and_then(resolve_env_value))?;
+                let password = std::env::var("XMPP_PASSWORD")
+                    .ok()
+                    .or_else(|| x.password.as_deref().and_then(resolve_env_value))?;
+                Some(XmppConfig {
+                    enabled: x.enabled,
+                    jid,
+                    password,
+                    server: x.server,
+                    port: x.port,
+                    nick: x.nick,
+                    rooms: x.rooms,
+                })
+            }),
+            line: toml.messaging.line.and_then(|l| {
+                let channel_secret = std::env::var("LINE_CHANNEL_SECRET")
+                    .ok()
+                    .or_else(|| l.channel_secret.as_deref().and_then(resolve_env_value))?;
+                let channel_access_token = std::env::var("LINE_CHANNEL_ACCESS_TOKEN")
+                    .ok()
+                    .or_else(|| l.channel_access_token.as_deref().and_then(resolve_env_value))?;
+                Some(LineConfig {
+                    enabled: l.enabled,
+                    channel_secret,
+                    channel_access_token,
+                    port: l.port,
+                    bind: l.bind,
+                })
+            }),
+            reddit: toml.messaging.reddit.and_then(|r| {
+                let client_id = std::env::var("REDDIT_CLIENT_ID")
+                    .ok()
+                    .or_else(|| r.client_id.as_deref().and_then(resolve_env_value))?;
+                let client_secret = std::env::var("REDDIT_CLIENT_SECRET")
+                    .ok()
+                    .or_else(|| r.client_secret.as_deref().and_then(resolve_env_value))?;
+                let username = std::env::var("REDDIT_USERNAME")
+                    .ok()
+                    .or_else(|| r.username.as_deref().and_then(resolve_env_value))?;
+                let password = std::env::var("REDDIT_PASSWORD")
+                    .ok()
+                    .or_else(|| r.password.as_deref().and_then(resolve_env_value))?;
+                Some(RedditConfig {
+                    enabled: r.enabled,
+                    client_id,
+                    client_secret,
+                    username,
+                    password,
+                    subreddits: r.subreddits,
+                    poll_interval_secs: r.poll_interval_secs,
+                })
+            }),
+            github: toml.messaging.github.and_then(|g| {
+                let token = std::env::var("GITHUB_TOKEN")
+                    .ok()
+                    .or_else(|| g.token.as_deref().and_then(resolve_env_value))?;
+                let webhook_secret = std::env::var("GITHUB_WEBHOOK_SECRET")
+                    .ok()
+                    .or_else(|| g.webhook_secret.as_deref().and_then(resolve_env_value));
+                Some(GithubConfig {
+                    enabled: g.enabled,
+                    token,
+                    webhook_secret,
+                    port: g.port,
+                    bind: g.bind,
+                })
+            }),
+            gitlab: toml.messaging.gitlab.and_then(|g| {
+                let token = std::env::var("GITLAB_TOKEN")
+                    .ok()
+                    .or_else(|| g.token.as_deref().and_then(resolve_env_value))?;
+                let webhook_token = std::env::var("GITLAB_WEBHOOK_TOKEN")
+                    .ok()
+                    .or_else(|| g.webhook_token.as_deref().and_then(resolve_env_value));
+                Some(GitlabConfig {
+                    enabled: g.enabled,
+                    base_url: g.base_url,
+                    token,
+                    webhook_token,
+                    port: g.port,
+                    bind: g.bind,
+                })
+            }),
+            jira: toml.messaging.jira.and_then(|j| {
+                let base_url = j.base_url.as_deref().and_then(resolve_env_value)?;
+                let email = std::env::var("JIRA_EMAIL")
+                    .ok()
+                    .or_else(|| j.email.as_deref().and_then(resolve_env_value))?;
+                let api_token = std::env::var("JIRA_API_TOKEN")
+                    .ok()
+                    .or_else(|| j.api_token.as_deref().and_then(resolve_env_value))?;
+                let webhook_token = std::env::var("JIRA_WEBHOOK_TOKEN")
+                    .ok()
+                    .or_else(|| j.webhook_token.as_deref().and_then(resolve_env_value));
+                Some(JiraConfig {
+                    enabled: j.enabled,
+                    base_url,
+                    email,
+                    api_token,
+                    webhook_token,
+                    port: j.port,
+                    bind: j.bind,
+                })
+            }),
+            linear: toml.messaging.linear.and_then(|l| {
+                let instances = l
                     .instances
                     .into_iter()
                     .map(|instance| {
-                        let imap_host =
-                            instance.imap_host.as_deref().and_then(resolve_env_value);
-                        let imap_username =
-                            instance.imap_username.as_deref().and_then(resolve_env_value);
-                        let imap_password =
-                            instance.imap_password.as_deref().and_then(resolve_env_value);
-                        let smtp_host =
-                            instance.smtp_host.as_deref().and_then(resolve_env_value);
-
-                        let has_credentials = imap_host.is_some()
-                            && imap_username.is_some()
-                            && imap_password.is_some()
-                            && smtp_host.is_some();
-
-                        if instance.enabled && !has_credentials {
+                        let api_key = instance.api_key.as_deref().and_then(resolve_env_value);
+                        if instance.enabled && api_key.is_none() {
                             tracing::warn!(
                                 adapter = %instance.name,
-                                "email instance is enabled but credentials are missing/unresolvable — disabling"
+                                "linear instance is enabled but API key is missing/unresolvable — disabling"
                             );
                         }
-
-                        let imap_username_val = imap_username.unwrap_or_default();
-                        let imap_password_val = imap_password.unwrap_or_default();
-                        let smtp_username = instance
-                            .smtp_username
-                            .as_deref()
-                            .and_then(resolve_env_value)
-                            .unwrap_or_else(|| imap_username_val.clone());
-                        let smtp_password = instance
-                            .smtp_password
-                            .as_deref()
-                            .and_then(resolve_env_value)
-                            .unwrap_or_else(|| imap_password_val.clone());
-                        let from_address = instance
-                            .from_address
+                        if instance.enabled && instance.port.is_none() {
+                            tracing::warn!(
+                                adapter = %instance.name,
+                                "linear instance is enabled but has no webhook port — disabling"
+                            );
+                        }
+                        let webhook_secret = instance
+                            .webhook_secret
                             .as_deref()
-                            .and_then(resolve_env_value)
-                            .unwrap_or_else(|| smtp_username.clone());
-                        let from_name =
-                            instance.from_name.as_deref().and_then(resolve_env_value);
-
-                        EmailInstanceConfig {
+                            .and_then(resolve_env_value);
+                        LinearInstanceConfig {
                             name: instance.name,
-                            enabled: instance.enabled && has_credentials,
-                            imap_host: imap_host.unwrap_or_default(),
-                            imap_port: instance.imap_port,
-                            imap_username: imap_username_val,
-                            imap_password: imap_password_val,
-                            imap_use_tls: instance.imap_use_tls,
-                            smtp_host: smtp_host.unwrap_or_default(),
-                            smtp_port: instance.smtp_port,
-                            smtp_username,
-                            smtp_password,
-                            smtp_use_starttls: instance.smtp_use_starttls,
-                            from_address,
-                            from_name,
-                            poll_interval_secs: instance.poll_interval_secs,
-                            folders: if instance.folders.is_empty() {
-                                vec!["INBOX".to_string()]
-                            } else {
-                                instance.folders
-                            },
-                            allowed_senders: instance.allowed_senders,
-                            max_body_bytes: instance.max_body_bytes,
-                            max_attachment_bytes: instance.max_attachment_bytes,
+                            enabled: instance.enabled
+                                && api_key.is_some()
+                                && instance.port.is_some(),
+                            api_key: api_key.unwrap_or_default(),
+                            webhook_secret,
+                            port: instance.port.unwrap_or_default(),
+                            bind: instance.bind,
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let imap_host = std::env::var("EMAIL_IMAP_HOST")
-                    .ok()
-                    .or_else(|| email.imap_host.as_deref().and_then(resolve_env_value));
-                let imap_username = std::env::var("EMAIL_IMAP_USERNAME")
+                let api_key = std::env::var("LINEAR_API_KEY")
                     .ok()
-                    .or_else(|| email.imap_username.as_deref().and_then(resolve_env_value));
-                let imap_password = std::env::var("EMAIL_IMAP_PASSWORD")
-                    .ok()
-                    .or_else(|| email.imap_password.as_deref().and_then(resolve_env_value));
-                let smtp_host = std::env::var("EMAIL_SMTP_HOST")
+                    .or_else(|| l.api_key.as_deref().and_then(resolve_env_value));
+                let webhook_secret = std::env::var("LINEAR_WEBHOOK_SECRET")
                     .ok()
-                    .or_else(|| email.smtp_host.as_deref().and_then(resolve_env_value));
+                    .or_else(|| l.webhook_secret.as_deref().and_then(resolve_env_value));
 
-                let has_default = imap_host.is_some()
-                    && imap_username.is_some()
-                    && imap_password.is_some()
-                    && smtp_host.is_some();
-
-                if !has_default && instances.is_empty() {
+                if api_key.is_none() && instances.is_empty() {
                     return None;
                 }
 
-                let imap_host = imap_host.unwrap_or_default();
-                let imap_username = imap_username.unwrap_or_default();
-                let imap_password = imap_password.unwrap_or_default();
-                let smtp_host = smtp_host.unwrap_or_default();
-                let smtp_username = std::env::var("EMAIL_SMTP_USERNAME")
+                Some(LinearConfig {
+                    enabled: l.enabled,
+                    api_key: api_key.unwrap_or_default(),
+                    webhook_secret,
+                    port: l.port,
+                    bind: l.bind,
+                    instances,
+                })
+            }),
+            nextcloud: toml.messaging.nextcloud.and_then(|n| {
+                let base_url = n.base_url.as_deref().and_then(resolve_env_value)?;
+                let username = std::env::var("NEXTCLOUD_USERNAME")
                     .ok()
-                    .or_else(|| email.smtp_username.as_deref().and_then(resolve_env_value))
-                    .unwrap_or_else(|| imap_username.clone());
-                let smtp_password = std::env::var("EMAIL_SMTP_PASSWORD")
+                    .or_else(|| n.username.as_deref().and_then(resolve_env_value))?;
+                let app_password = std::env::var("NEXTCLOUD_APP_PASSWORD")
                     .ok()
-                    .or_else(|| email.smtp_password.as_deref().and_then(resolve_env_value))
-                    .unwrap_or_else(|| imap_password.clone());
-
-                let from_address = std::env::var("EMAIL_FROM_ADDRESS")
+                    .or_else(|| n.app_password.as_deref().and_then(resolve_env_value))?;
+                Some(NextcloudConfig {
+                    enabled: n.enabled,
+                    base_url,
+                    username,
+                    app_password,
+                    rooms: n.rooms,
+                })
+            }),
+            rss: toml.messaging.rss.map(|r| RssConfig {
+                enabled: r.enabled,
+                feeds: r
+                    .feeds
+                    .into_iter()
+                    .map(|feed| RssFeedConfig {
+                        name: feed.name,
+                        url: feed.url,
+                    })
+                    .collect(),
+                poll_interval_secs: r.poll_interval_secs,
+            }),
+            mqtt: toml.messaging.mqtt.and_then(|m| {
+                let host = m.host.as_deref().and_then(resolve_env_value)?;
+                let username = std::env::var("MQTT_USERNAME")
                     .ok()
-                    .or_else(|| email.from_address.as_deref().and_then(resolve_env_value))
-                    .unwrap_or_else(|| smtp_username.clone());
-                let from_name = std::env::var("EMAIL_FROM_NAME")
+                    .or_else(|| m.username.as_deref().and_then(resolve_env_value));
+                let password = std::env::var("MQTT_PASSWORD")
                     .ok()
-                    .or_else(|| email.from_name.as_deref().and_then(resolve_env_value));
-
-                Some(EmailConfig {
-                    enabled: email.enabled,
-                    imap_host,
-                    imap_port: email.imap_port,
-                    imap_username,
-                    imap_password,
-                    imap_use_tls: email.imap_use_tls,
-                    smtp_host,
-                    smtp_port: email.smtp_port,
-                    smtp_username,
-                    smtp_password,
-                    smtp_use_starttls: email.smtp_use_starttls,
-                    from_address,
-                    from_name,
-                    poll_interval_secs: email.poll_interval_secs,
-                    folders: if email.folders.is_empty() {
-                        vec!["INBOX".to_string()]
-                    } else {
-                        email.folders
-                    },
-                    allowed_senders: email.allowed_senders,
-                    max_body_bytes: email.max_body_bytes,
-                    max_attachment_bytes: email.max_attachment_bytes,
-                    instances,
+                    .or_else(|| m.password.as_deref().and_then(resolve_env_value));
+                Some(MqttConfig {
+                    enabled: m.enabled,
+                    host,
+                    port: m.port,
+                    client_id: m.client_id,
+                    username,
+                    password,
+                    topics: m.topics,
+                    response_topic: m.response_topic,
                 })
             }),
-            webhook: toml.messaging.webhook.map(|w| WebhookConfig {
+            websocket: toml.messaging.websocket.map(|w| WebsocketConfig {
                 enabled: w.enabled,
                 port: w.port,
                 bind: w.bind,
                 auth_token: w.auth_token.as_deref().and_then(resolve_env_value),
             }),
-            twitch: toml.messaging.twitch.and_then(|t| {
-                let instances = t
+            console: toml
+                .messaging
+                .console
+                .map(|c| ConsoleConfig { enabled: c.enabled }),
+            voice: toml.messaging.voice.and_then(|v| {
+                let auth_token = std::env::var("TWILIO_AUTH_TOKEN")
+                    .ok()
+                    .or_else(|| v.auth_token.as_deref().and_then(resolve_env_value));
+                let Some(auth_token) = auth_token else {
+                    if v.enabled {
+                        tracing::warn!(
+                            "voice is enabled but auth_token is missing/unresolvable — disabling"
+                        );
+                    }
+                    return None;
+                };
+                Some(VoiceConfig {
+                    enabled: v.enabled,
+                    auth_token,
+                    port: v.port,
+                    bind: v.bind,
+                    greeting: v.greeting,
+                    voice: v.voice,
+                    language: v.language,
+                })
+            }),
+            steam: toml.messaging.steam.and_then(|s| {
+                let access_token = s.access_token.as_deref().and_then(resolve_env_value);
+                if s.enabled && access_token.is_none() {
+                    tracing::warn!(
+                        "steam is enabled but access_token is missing/unresolvable — disabling"
+                    );
+                }
+                access_token.map(|access_token| SteamConfig {
+                    enabled: s.enabled,
+                    access_token,
+                    allowed_friends: s.allowed_friends,
+                })
+            }),
+            viber: toml.messaging.viber.and_then(|v| {
+                let auth_token = std::env::var("VIBER_AUTH_TOKEN")
+                    .ok()
+                    .or_else(|| v.auth_token.as_deref().and_then(resolve_env_value))?;
+                Some(ViberConfig {
+                    enabled: v.enabled,
+                    auth_token,
+                    sender_name: v.sender_name,
+                    port: v.port,
+                    bind: v.bind,
+                })
+            }),
+            bluesky: toml.messaging.bluesky.and_then(|b| {
+                let instances = b
                     .instances
                     .into_iter()
                     .map(|instance| {
-                        let username = instance.username.as_deref().and_then(resolve_env_value);
-                        let oauth_token = instance
-                            .oauth_token
-                            .as_deref()
-                            .and_then(resolve_env_value);
-                        if instance.enabled && (username.is_none() || oauth_token.is_none()) {
+                        let handle = instance.handle.as_deref().and_then(resolve_env_value);
+                        let app_password =
+                            instance.app_password.as_deref().and_then(resolve_env_value);
+                        if instance.enabled && (handle.is_none() || app_password.is_none()) {
                             tracing::warn!(
                                 adapter = %instance.name,
-                                "twitch instance is enabled but credentials are missing/unresolvable — disabling"
+                                "bluesky instance is enabled but handle/app_password is missing/unresolvable — disabling"
                             );
                         }
-                        let has_credentials = username.is_some() && oauth_token.is_some();
-                        let client_id = instance.client_id.as_deref().and_then(resolve_env_value);
-                        let client_secret = instance
-                            .client_secret
-                            .as_deref()
-                            .and_then(resolve_env_value);
-                        let refresh_token = instance
-                            .refresh_token
-                            .as_deref()
-                            .and_then(resolve_env_value);
-                        TwitchInstanceConfig {
+                        let resolved = handle.is_some() && app_password.is_some();
+                        BlueskyInstanceConfig {
                             name: instance.name,
-                            enabled: instance.enabled && has_credentials,
-                            username: username.unwrap_or_default(),
-                            oauth_token: oauth_token.unwrap_or_default(),
-                            client_id,
-                            client_secret,
-                            refresh_token,
-                            channels: instance.channels,
-                            trigger_prefix: instance.trigger_prefix,
+                            enabled: instance.enabled && resolved,
+                            service: instance.service,
+                            handle: handle.unwrap_or_default(),
+                            app_password: app_password.unwrap_or_default(),
                         }
                     })
                     .collect::<Vec<_>>();
 
-                let username = std::env::var("TWITCH_BOT_USERNAME")
+                if instances.is_empty() {
+                    return None;
+                }
+
+                Some(BlueskyConfig {
+                    enabled: b.enabled,
+                    instances,
+                })
+            }),
+            mastodon: toml.messaging.mastodon.and_then(|m| {
+                let url = std::env::var("MASTODON_URL")
                     .ok()
-                    .or_else(|| t.username.as_deref().and_then(resolve_env_value));
-                let oauth_token = std::env::var("TWITCH_OAUTH_TOKEN")
+                    .or_else(|| m.url.as_deref().and_then(resolve_env_value));
+                let access_token = std::env::var("MASTODON_ACCESS_TOKEN")
                     .ok()
-                    .or_else(|| t.oauth_token.as_deref().and_then(resolve_env_value));
+                    .or_else(|| m.access_token.as_deref().and_then(resolve_env_value));
 
-                if (username.is_none() || oauth_token.is_none()) && instances.is_empty() {
+                let (Some(url), Some(access_token)) = (url, access_token) else {
                     return None;
-                }
+                };
 
-                let client_id = t
-                    .client_id
-                    .as_deref()
-                    .and_then(resolve_env_value)
-                    .or_else(|| std::env::var("TWITCH_CLIENT_ID").ok());
-                let client_secret = t
-                    .client_secret
-                    .as_deref()
-                    .and_then(resolve_env_value)
-                    .or_else(|| std::env::var("TWITCH_CLIENT_SECRET").ok());
-                let refresh_token = t
-                    .refresh_token
-                    .as_deref()
-                    .and_then(resolve_env_value)
-                    .or_else(|| std::env::var("TWITCH_REFRESH_TOKEN").ok());
-                Some(TwitchConfig {
-                    enabled: t.enabled,
-                    username: username.unwrap_or_default(),
-                    oauth_token: oauth_token.unwrap_or_default(),
-                    client_id,
-                    client_secret,
-                    refresh_token,
-                    instances,
-                    channels: t.channels,
-                    trigger_prefix: t.trigger_prefix,
+                Some(MastodonConfig {
+                    enabled: m.enabled,
+                    url,
+                    access_token,
+                    reply_visibility: m.reply_visibility,
+                })
+            }),
+            googlechat: toml.messaging.googlechat.and_then(|g| {
+                let credentials_path = std::env::var("GOOGLE_APPLICATION_CREDENTIALS")
+                    .ok()
+                    .or_else(|| g.credentials_path.as_deref().and_then(resolve_env_value))?;
+                let audience = std::env::var("GOOGLE_CHAT_AUDIENCE")
+                    .ok()
+                    .or_else(|| g.audience.as_deref().and_then(resolve_env_value));
+                let Some(audience) = audience else {
+                    if g.enabled {
+                        tracing::warn!(
+                            "googlechat is enabled but audience (project number) is missing — disabling"
+                        );
+                    }
+                    return None;
+                };
+                Some(GoogleChatConfig {
+                    enabled: g.enabled,
+                    credentials_path,
+                    audience,
+                    port: g.port,
+                    bind: g.bind,
+                    spaces: g.spaces,
+                    users: g.users,
+                })
+            }),
+            ntfy: toml.messaging.ntfy.and_then(|n| {
+                let topic = std::env::var("NTFY_TOPIC")
+                    .ok()
+                    .or_else(|| n.topic.as_deref().and_then(resolve_env_value))?;
+                Some(NtfyConfig {
+                    enabled: n.enabled,
+                    server: n.server,
+                    topic,
+                    token: n.token.as_deref().and_then(resolve_env_value),
+                })
+            }),
+            pushover: toml.messaging.pushover.and_then(|p| {
+                let token = std::env::var("PUSHOVER_TOKEN")
+                    .ok()
+                    .or_else(|| p.token.as_deref().and_then(resolve_env_value));
+                let user = std::env::var("PUSHOVER_USER")
+                    .ok()
+                    .or_else(|| p.user.as_deref().and_then(resolve_env_value));
+                let (Some(token), Some(user)) = (token, user) else {
+                    return None;
+                };
+                Some(PushoverConfig {
+                    enabled: p.enabled,
+                    token,
+                    user,
+                })
+            }),
+            gotify: toml.messaging.gotify.and_then(|g| {
+                let server = g.server.as_deref().and_then(resolve_env_value)?;
+                let token = std::env::var("GOTIFY_TOKEN")
+                    .ok()
+                    .or_else(|| g.token.as_deref().and_then(resolve_env_value))?;
+                Some(GotifyConfig {
+                    enabled: g.enabled,
+                    server,
+                    token,
                 })
             }),
         };
@@ -4967,6 +8134,14 @@ impl Config {
                 channel_ids: b.channel_ids,
                 require_mention: b.require_mention,
                 dm_allowed_users: b.dm_allowed_users,
+                onboarding: b.onboarding,
+                allowed_regions: b.allowed_regions,
+                trigger_prefix: b.trigger_prefix,
+                wake_words: b.wake_words,
+                bot_loop_protection: b.bot_loop_protection,
+                link_safety: b.link_safety,
+                reconnect_greeting: b.reconnect_greeting,
+                reconnect_greeting_threshold_secs: b.reconnect_greeting_threshold_secs,
             })
             .collect();
 
@@ -4981,6 +8156,7 @@ impl Config {
 
         let metrics = MetricsConfig {
             enabled: toml.metrics.enabled,
+            perf_instrumentation: toml.metrics.perf_instrumentation,
             port: toml.metrics.port,
             bind: toml.metrics.bind,
         };
@@ -5071,6 +8247,21 @@ impl Config {
             api,
             metrics,
             telemetry,
+            network: NetworkConfig {
+                proxy: toml
+                    .network
+                    .proxy
+                    .as_deref()
+                    .and_then(resolve_env_value)
+                    .filter(|proxy| !proxy.trim().is_empty()),
+                ip_preference: IpPreference::from_toml_value(toml.network.prefer.as_deref()),
+                dns_servers: toml.network.dns_servers,
+                doh_provider: toml
+                    .network
+                    .doh_provider
+                    .map(|provider| provider.trim().to_ascii_lowercase())
+                    .filter(|provider| !provider.is_empty()),
+            },
         })
     }
 
@@ -5120,6 +8311,10 @@ pub struct RuntimeConfig {
     pub browser_config: ArcSwap<BrowserConfig>,
     pub mcp: ArcSwap<Vec<McpServerConfig>>,
     pub history_backfill_count: ArcSwap<usize>,
+    /// Append source footnotes to replies when retrieval tools were used.
+    pub cite_sources: ArcSwap<bool>,
+    /// Inject date/time and cron schedule context into channel prompts.
+    pub time_context: ArcSwap<bool>,
     pub brave_search_key: ArcSwap<Option<String>>,
     pub cron_timezone: ArcSwap<Option<String>>,
     pub user_timezone: ArcSwap<Option<String>>,
@@ -5136,6 +8331,15 @@ pub struct RuntimeConfig {
     pub identity: ArcSwap<crate::identity::Identity>,
     pub skills: ArcSwap<crate::skills::SkillSet>,
     pub opencode: ArcSwap<OpenCodeConfig>,
+    /// Host operations tool (Docker / systemd) configuration.
+    pub ops: ArcSwap<OpsConfig>,
+    /// Kubernetes observability tool configuration.
+    pub kube: ArcSwap<KubeConfig>,
+    /// SQL query tool configuration.
+    pub sql: ArcSwap<SqlConfig>,
+    pub ssh: ArcSwap<SshConfig>,
+    /// Translation memory configuration.
+    pub translation_memory: ArcSwap<TranslationMemoryConfig>,
     /// Shared pool of OpenCode server processes. Lazily initialized on first use.
     pub opencode_server_pool: Arc<crate::opencode::OpenCodeServerPool>,
     /// Cron store, set after agent initialization.
@@ -5181,6 +8385,8 @@ impl RuntimeConfig {
             browser_config: ArcSwap::from_pointee(agent_config.browser.clone()),
             mcp: ArcSwap::from_pointee(agent_config.mcp.clone()),
             history_backfill_count: ArcSwap::from_pointee(agent_config.history_backfill_count),
+            cite_sources: ArcSwap::from_pointee(agent_config.cite_sources),
+            time_context: ArcSwap::from_pointee(agent_config.time_context),
             brave_search_key: ArcSwap::from_pointee(agent_config.brave_search_key.clone()),
             cron_timezone: ArcSwap::from_pointee(agent_config.cron_timezone.clone()),
             user_timezone: ArcSwap::from_pointee(agent_config.user_timezone.clone()),
@@ -5193,6 +8399,11 @@ impl RuntimeConfig {
             identity: ArcSwap::from_pointee(identity),
             skills: ArcSwap::from_pointee(skills),
             opencode: ArcSwap::from_pointee(defaults.opencode.clone()),
+            ops: ArcSwap::from_pointee(defaults.ops.clone()),
+            kube: ArcSwap::from_pointee(defaults.kube.clone()),
+            sql: ArcSwap::from_pointee(defaults.sql.clone()),
+            ssh: ArcSwap::from_pointee(defaults.ssh.clone()),
+            translation_memory: ArcSwap::from_pointee(defaults.translation_memory.clone()),
             opencode_server_pool: Arc::new(server_pool),
             cron_store: ArcSwap::from_pointee(None),
             cron_scheduler: ArcSwap::from_pointee(None),
@@ -5268,6 +8479,8 @@ impl RuntimeConfig {
         self.mcp.store(Arc::new(new_mcp.clone()));
         self.history_backfill_count
             .store(Arc::new(resolved.history_backfill_count));
+        self.cite_sources.store(Arc::new(resolved.cite_sources));
+        self.time_context.store(Arc::new(resolved.time_context));
         self.brave_search_key
             .store(Arc::new(resolved.brave_search_key));
         self.cron_timezone.store(Arc::new(resolved.cron_timezone));
@@ -5535,9 +8748,16 @@ pub fn spawn_file_watcher(
                     let telegram_permissions = telegram_permissions.clone();
                     let twitch_permissions = twitch_permissions.clone();
                     let instance_dir = instance_dir.clone();
+                    let config_path = config_path.clone();
 
                     rt.spawn(async move {
                         // Discord: start default + named instances that are enabled and not already running.
+                        #[cfg(not(feature = "adapter-discord"))]
+                        if config.messaging.discord.as_ref().is_some_and(|c| c.enabled) {
+                            let _ = &discord_permissions;
+                            tracing::warn!("discord is configured but this binary was built without the adapter-discord feature");
+                        }
+                        #[cfg(feature = "adapter-discord")]
                         if let Some(discord_config) = &config.messaging.discord
                             && discord_config.enabled {
                                 if !discord_config.token.is_empty() && !manager.has_adapter("discord").await {
@@ -5552,6 +8772,7 @@ pub fn spawn_file_watcher(
                                         "discord",
                                         &discord_config.token,
                                         permissions,
+                                        discord_config.proxy.clone(),
                                     );
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, "failed to hot-start discord adapter from config change");
@@ -5580,6 +8801,7 @@ pub fn spawn_file_watcher(
                                         runtime_key,
                                         &instance.token,
                                         permissions,
+                                        discord_config.proxy.clone(),
                                     );
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, adapter = %instance.name, "failed to hot-start named discord adapter from config change");
@@ -5588,6 +8810,12 @@ pub fn spawn_file_watcher(
                             }
 
                         // Slack: start default + named instances that are enabled and not already running.
+                        #[cfg(not(feature = "adapter-slack"))]
+                        if config.messaging.slack.as_ref().is_some_and(|c| c.enabled) {
+                            let _ = &slack_permissions;
+                            tracing::warn!("slack is configured but this binary was built without the adapter-slack feature");
+                        }
+                        #[cfg(feature = "adapter-slack")]
                         if let Some(slack_config) = &config.messaging.slack
                             && slack_config.enabled {
                                 if !slack_config.bot_token.is_empty()
@@ -5652,6 +8880,12 @@ pub fn spawn_file_watcher(
                             }
 
                         // Telegram: start default + named instances that are enabled and not already running.
+                        #[cfg(not(feature = "adapter-telegram"))]
+                        if config.messaging.telegram.as_ref().is_some_and(|c| c.enabled) {
+                            let _ = (&telegram_permissions, &config_path);
+                            tracing::warn!("telegram is configured but this binary was built without the adapter-telegram feature");
+                        }
+                        #[cfg(feature = "adapter-telegram")]
                         if let Some(telegram_config) = &config.messaging.telegram
                             && telegram_config.enabled {
                                 if !telegram_config.token.is_empty()
@@ -5668,6 +8902,8 @@ pub fn spawn_file_watcher(
                                         "telegram",
                                         &telegram_config.token,
                                         permissions,
+                                        Some(config_path.clone()),
+                                        telegram_config.proxy.clone(),
                                     );
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, "failed to hot-start telegram adapter from config change");
@@ -5691,6 +8927,8 @@ pub fn spawn_file_watcher(
                                         runtime_key,
                                         &instance.token,
                                         permissions,
+                                        Some(config_path.clone()),
+                                        telegram_config.proxy.clone(),
                                     );
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, adapter = %instance.name, "failed to hot-start named telegram adapter from config change");
@@ -5699,6 +8937,11 @@ pub fn spawn_file_watcher(
                             }
 
                         // Email: start default + named instances that are enabled and not already running.
+                        #[cfg(not(feature = "adapter-email"))]
+                        if config.messaging.email.as_ref().is_some_and(|c| c.enabled) {
+                            tracing::warn!("email is configured but this binary was built without the adapter-email feature");
+                        }
+                        #[cfg(feature = "adapter-email")]
                         if let Some(email_config) = &config.messaging.email
                             && email_config.enabled {
                                 if !email_config.imap_host.is_empty() && !manager.has_adapter("email").await {
@@ -6296,6 +9539,31 @@ api_key = "test-key"
         assert_eq!(result5.unwrap().api_type, ApiType::Anthropic);
     }
 
+    #[test]
+    fn network_section_parses_resolver_options() {
+        let toml = r#"
+[network]
+proxy = "socks5://127.0.0.1:1080"
+prefer = "ipv6"
+dns_servers = ["9.9.9.9"]
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        assert_eq!(config.network.proxy.as_deref(), Some("socks5://127.0.0.1:1080"));
+        assert_eq!(config.network.ip_preference, IpPreference::Ipv6);
+        assert_eq!(config.network.dns_servers, vec!["9.9.9.9".to_string()]);
+        assert_eq!(config.network.doh_provider, None);
+    }
+
+    #[test]
+    fn unknown_ip_preference_falls_back_to_auto() {
+        assert_eq!(IpPreference::from_toml_value(Some("both")), IpPreference::Auto);
+        assert_eq!(IpPreference::from_toml_value(None), IpPreference::Auto);
+        assert_eq!(IpPreference::from_toml_value(Some("IPv4")), IpPreference::Ipv4);
+    }
+
     #[test]
     fn test_api_type_deserialization_invalid() {
         let toml = r#"api_type = "invalid_type""#;
@@ -6607,6 +9875,14 @@ bind = "127.0.0.1"
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users,
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         }
     }
 
@@ -7353,6 +10629,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         assert_eq!(binding.runtime_adapter_key(), "telegram:sales");
     }
@@ -7369,6 +10653,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         assert!(binding.uses_default_adapter());
     }
@@ -7388,6 +10680,76 @@ startup_delay_secs = 2
         }
     }
 
+    #[test]
+    fn handoff_commands_parse() {
+        assert_eq!(parse_handoff_command("!handoff support-agent"), Some("support-agent"));
+        assert_eq!(parse_handoff_command("  !handoff main "), Some("main"));
+        assert_eq!(parse_handoff_command("!handoff"), None);
+        assert_eq!(parse_handoff_command("!handoff two words"), None);
+        assert_eq!(parse_handoff_command("please !handoff main"), None);
+    }
+
+    #[test]
+    fn trigger_prefix_gates_group_messages() {
+        let binding = Binding {
+            agent_id: "main".into(),
+            channel: "telegram".into(),
+            adapter: None,
+            guild_id: None,
+            workspace_id: None,
+            chat_id: None,
+            channel_ids: vec![],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: Some("!ask".into()),
+            wake_words: vec!["spacebot".into()],
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
+        };
+
+        let mut group = test_inbound_message("telegram", None);
+        group.metadata.insert(
+            "telegram_chat_type".into(),
+            serde_json::Value::String("group".into()),
+        );
+        assert!(!binding.matches(&group), "unprefixed group message matched");
+
+        let mut prefixed = group.clone();
+        prefixed.content = crate::MessageContent::Text("!ask what's up".into());
+        assert!(binding.matches(&prefixed));
+
+        let mut woken = group.clone();
+        woken.content = crate::MessageContent::Text("hey SpaceBot, ping".into());
+        assert!(binding.matches(&woken), "wake word should match case-insensitively");
+
+        let mut embedded = group.clone();
+        embedded.content = crate::MessageContent::Text("the spacebots are coming".into());
+        assert!(
+            !binding.matches(&embedded),
+            "wake word should not fire inside longer words"
+        );
+
+        let mut mentioned = group.clone();
+        mentioned
+            .metadata
+            .insert("mentions_bot".into(), serde_json::Value::Bool(true));
+        assert!(
+            binding.matches(&mentioned),
+            "bot mentions should bypass the trigger on any platform"
+        );
+
+        let mut dm = test_inbound_message("telegram", None);
+        dm.metadata.insert(
+            "telegram_chat_type".into(),
+            serde_json::Value::String("private".into()),
+        );
+        assert!(binding.matches(&dm), "DMs should bypass the trigger");
+    }
+
     #[test]
     fn adapter_matches_default_binding_default_message() {
         let binding = Binding {
@@ -7400,6 +10762,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         let message = test_inbound_message("telegram", None);
         assert!(binding_adapter_matches(&binding, &message));
@@ -7417,6 +10787,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(binding_adapter_matches(&binding, &message));
@@ -7434,6 +10812,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         let message = test_inbound_message("telegram", None);
         assert!(!binding_adapter_matches(&binding, &message));
@@ -7451,6 +10837,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(!binding_adapter_matches(&binding, &message));
@@ -7468,6 +10862,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         };
         let message = test_inbound_message("telegram", Some("telegram:sales"));
         assert!(!binding_adapter_matches(&binding, &message));
@@ -7486,12 +10888,50 @@ startup_delay_secs = 2
                     enabled: true,
                     token: "tok2".into(),
                     dm_allowed_users: vec![],
+                    trigger_prefix: None,
                 }],
                 dm_allowed_users: vec![],
+                trigger_prefix: None,
+                proxy: None,
+                mode: TelegramMode::Polling,
+                webhook_url: String::new(),
+                webhook_secret: String::new(),
+                stt: None,
+                commands: Vec::new(),
+            moderation: TelegramModerationConfig::default(),
             }),
             email: None,
             webhook: None,
             twitch: None,
+            mattermost: None,
+            teams: None,
+            signal: None,
+            whatsapp: None,
+            sms: None,
+            zulip: None,
+            ntfy: None,
+            pushover: None,
+            gotify: None,
+            googlechat: None,
+            rocketchat: None,
+            mastodon: None,
+            bluesky: None,
+            nostr: None,
+            xmpp: None,
+            line: None,
+            reddit: None,
+            github: None,
+            gitlab: None,
+            jira: None,
+            linear: None,
+            nextcloud: None,
+            rss: None,
+            mqtt: None,
+            websocket: None,
+            console: None,
+            voice: None,
+            steam: None,
+            viber: None,
         };
         let bindings = vec![
             Binding {
@@ -7504,6 +10944,14 @@ startup_delay_secs = 2
                 channel_ids: vec![],
                 require_mention: false,
                 dm_allowed_users: vec![],
+                onboarding: Vec::new(),
+                allowed_regions: Vec::new(),
+                trigger_prefix: None,
+                wake_words: Vec::new(),
+                bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
             },
             Binding {
                 agent_id: "support-agent".into(),
@@ -7515,6 +10963,14 @@ startup_delay_secs = 2
                 channel_ids: vec![],
                 require_mention: false,
                 dm_allowed_users: vec![],
+                onboarding: Vec::new(),
+                allowed_regions: Vec::new(),
+                trigger_prefix: None,
+                wake_words: Vec::new(),
+                bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
             },
         ];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_ok());
@@ -7530,10 +10986,47 @@ startup_delay_secs = 2
                 token: "tok".into(),
                 instances: vec![],
                 dm_allowed_users: vec![],
+                trigger_prefix: None,
+                proxy: None,
+                mode: TelegramMode::Polling,
+                webhook_url: String::new(),
+                webhook_secret: String::new(),
+                stt: None,
+                commands: Vec::new(),
+            moderation: TelegramModerationConfig::default(),
             }),
             email: None,
             webhook: None,
             twitch: None,
+            mattermost: None,
+            teams: None,
+            signal: None,
+            whatsapp: None,
+            sms: None,
+            zulip: None,
+            ntfy: None,
+            pushover: None,
+            gotify: None,
+            googlechat: None,
+            rocketchat: None,
+            mastodon: None,
+            bluesky: None,
+            nostr: None,
+            xmpp: None,
+            line: None,
+            reddit: None,
+            github: None,
+            gitlab: None,
+            jira: None,
+            linear: None,
+            nextcloud: None,
+            rss: None,
+            mqtt: None,
+            websocket: None,
+            console: None,
+            voice: None,
+            steam: None,
+            viber: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -7545,6 +11038,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -7592,10 +11093,57 @@ startup_delay_secs = 2
                 allowed_senders: vec![],
                 max_body_bytes: 1_000_000,
                 max_attachment_bytes: 10_000_000,
+                size_policy_reply: true,
+                max_replies_per_hour: 10,
+                spam_patterns: Vec::new(),
+                spam_scorer_command: String::new(),
+                drop_failed_auth: false,
+                pgp_sign: false,
+                pgp_key_id: String::new(),
+                dkim_selector: String::new(),
+                dkim_key_path: String::new(),
+                custom_headers: HashMap::new(),
+                reply_all: false,
+                backend: EmailBackend::Imap,
+                graph_tenant_id: String::new(),
+                graph_client_id: String::new(),
+                graph_client_secret: String::new(),
+                graph_user: String::new(),
+                jmap_session_url: String::new(),
+                jmap_token: String::new(),
                 instances: vec![],
             }),
             webhook: None,
             twitch: None,
+            mattermost: None,
+            teams: None,
+            signal: None,
+            whatsapp: None,
+            sms: None,
+            zulip: None,
+            ntfy: None,
+            pushover: None,
+            gotify: None,
+            googlechat: None,
+            rocketchat: None,
+            mastodon: None,
+            bluesky: None,
+            nostr: None,
+            xmpp: None,
+            line: None,
+            reddit: None,
+            github: None,
+            gitlab: None,
+            jira: None,
+            linear: None,
+            nextcloud: None,
+            rss: None,
+            mqtt: None,
+            websocket: None,
+            console: None,
+            voice: None,
+            steam: None,
+            viber: None,
         };
         let bindings = vec![Binding {
             agent_id: "main".into(),
@@ -7607,6 +11155,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -7624,12 +11180,50 @@ startup_delay_secs = 2
                     enabled: true,
                     token: "tok".into(),
                     dm_allowed_users: vec![],
+                    trigger_prefix: None,
                 }],
                 dm_allowed_users: vec![],
+                trigger_prefix: None,
+                proxy: None,
+                mode: TelegramMode::Polling,
+                webhook_url: String::new(),
+                webhook_secret: String::new(),
+                stt: None,
+                commands: Vec::new(),
+            moderation: TelegramModerationConfig::default(),
             }),
             email: None,
             webhook: None,
             twitch: None,
+            mattermost: None,
+            teams: None,
+            signal: None,
+            whatsapp: None,
+            sms: None,
+            zulip: None,
+            ntfy: None,
+            pushover: None,
+            gotify: None,
+            googlechat: None,
+            rocketchat: None,
+            mastodon: None,
+            bluesky: None,
+            nostr: None,
+            xmpp: None,
+            line: None,
+            reddit: None,
+            github: None,
+            gitlab: None,
+            jira: None,
+            linear: None,
+            nextcloud: None,
+            rss: None,
+            mqtt: None,
+            websocket: None,
+            console: None,
+            voice: None,
+            steam: None,
+            viber: None,
         };
         // Binding targets default adapter, but no default credentials exist
         let bindings = vec![Binding {
@@ -7642,6 +11236,14 @@ startup_delay_secs = 2
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -7711,6 +11313,37 @@ chat_id = "-100111"
         assert_eq!(config.bindings[1].chat_id.as_deref(), Some("-100111"));
     }
 
+    #[test]
+    fn telegram_moderation_section_parses_and_defaults_off() {
+        let _guard = env_test_lock().lock().unwrap();
+        let guard = EnvGuard::new();
+
+        let toml_content = r#"
+[messaging.telegram]
+enabled = true
+token = "default-token"
+
+[messaging.telegram.moderation]
+allow_delete = true
+allow_pin = true
+"#;
+        let config_path = guard.test_dir.join("config.toml");
+        std::fs::write(&config_path, toml_content).unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        let telegram = config.messaging.telegram.as_ref().unwrap();
+        assert!(telegram.moderation.allow_delete);
+        assert!(telegram.moderation.allow_pin);
+        // Unlisted actions stay disabled.
+        assert!(!telegram.moderation.allow_mute);
+        assert!(!telegram.moderation.allow_ban);
+
+        // The permission filters carry the moderation section through.
+        let permissions = TelegramPermissions::from_config(telegram, &config.bindings);
+        assert!(permissions.moderation.allow_delete);
+        assert!(!permissions.moderation.allow_ban);
+    }
+
     #[test]
     fn toml_backward_compat_no_adapter_field() {
         let _guard = env_test_lock().lock().unwrap();
@@ -0,0 +1,299 @@
+//! Versioned config schema with automatic upgrades.
+//!
+//! Older `config.toml` layouts are modernized on load: migrations run against
+//! the raw `toml_edit` document (preserving comments and formatting), the
+//! original file is backed up next to itself, and the upgraded form plus a
+//! `schema_version` stamp are written back. Breaking schema changes then stop
+//! requiring manual edits — add a [`Migration`] here instead.
+
+use toml_edit::{DocumentMut, Item};
+
+/// Current config schema version. Files without a `schema_version` key are
+/// treated as version 1.
+pub const CONFIG_SCHEMA_VERSION: i64 = 4;
+
+/// What a migration pass did to a document.
+#[derive(Debug)]
+pub struct MigrationOutcome {
+    pub from_version: i64,
+    pub to_version: i64,
+    /// Descriptions of the migrations that actually changed the document.
+    pub applied: Vec<&'static str>,
+}
+
+struct Migration {
+    /// Schema version the document is at after this migration.
+    to_version: i64,
+    description: &'static str,
+    /// Returns true when the document was changed.
+    apply: fn(&mut DocumentMut) -> bool,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to_version: 2,
+        description: "move root-level platform tables under [messaging]",
+        apply: move_root_platform_tables,
+    },
+    Migration {
+        to_version: 3,
+        description: "move root-level provider credentials under [llm]",
+        apply: move_root_provider_keys,
+    },
+    Migration {
+        to_version: 4,
+        description: "rename legacy per-platform [[accounts]] arrays to [[instances]]",
+        apply: rename_legacy_account_arrays,
+    },
+];
+
+/// Upgrade a raw config file body to the current schema.
+///
+/// Returns the modernized TOML and the outcome when the file was behind,
+/// `None` when it is already current.
+pub fn migrate_content(content: &str) -> anyhow::Result<Option<(String, MigrationOutcome)>> {
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|error| anyhow::anyhow!("failed to parse config for schema migration: {error}"))?;
+
+    let Some(outcome) = migrate_document(&mut doc) else {
+        return Ok(None);
+    };
+
+    Ok(Some((doc.to_string(), outcome)))
+}
+
+/// Run all outstanding migrations against a parsed document and stamp the
+/// current schema version. Returns `None` when the document is already
+/// at [`CONFIG_SCHEMA_VERSION`].
+pub fn migrate_document(doc: &mut DocumentMut) -> Option<MigrationOutcome> {
+    let from_version = doc
+        .get("schema_version")
+        .and_then(Item::as_integer)
+        .unwrap_or(1);
+
+    if from_version >= CONFIG_SCHEMA_VERSION {
+        return None;
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        if migration.to_version > from_version && (migration.apply)(doc) {
+            applied.push(migration.description);
+        }
+    }
+
+    doc["schema_version"] = toml_edit::value(CONFIG_SCHEMA_VERSION);
+
+    Some(MigrationOutcome {
+        from_version,
+        to_version: CONFIG_SCHEMA_VERSION,
+        applied,
+    })
+}
+
+/// Early layouts put platform tables like `[discord]` at the document root;
+/// the schema has nested them under `[messaging]` ever since.
+fn move_root_platform_tables(doc: &mut DocumentMut) -> bool {
+    const PLATFORMS: &[&str] = &[
+        "discord",
+        "slack",
+        "telegram",
+        "email",
+        "webhook",
+        "twitch",
+        "mattermost",
+        "signal",
+        "whatsapp",
+    ];
+
+    let mut changed = false;
+    for key in PLATFORMS {
+        if !doc.get(key).is_some_and(Item::is_table_like) {
+            continue;
+        }
+
+        let messaging_has = doc
+            .get("messaging")
+            .and_then(|messaging| messaging.get(key))
+            .is_some();
+
+        let Some(item) = doc.remove(key) else {
+            continue;
+        };
+
+        // If both forms exist, the nested one wins and the stale root copy
+        // is dropped.
+        if !messaging_has {
+            doc["messaging"][key] = item;
+        }
+        changed = true;
+    }
+
+    changed
+}
+
+/// Provider credentials used to live at the document root (`anthropic_key =
+/// "..."`); they belong under `[llm]` now.
+fn move_root_provider_keys(doc: &mut DocumentMut) -> bool {
+    const KEYS: &[&str] = &[
+        "anthropic_key",
+        "openai_key",
+        "openrouter_key",
+        "gemini_key",
+        "ollama_base_url",
+    ];
+
+    let mut changed = false;
+    for key in KEYS {
+        if !doc.get(key).is_some_and(Item::is_value) {
+            continue;
+        }
+
+        let llm_has = doc.get("llm").and_then(|llm| llm.get(key)).is_some();
+
+        let Some(item) = doc.remove(key) else {
+            continue;
+        };
+
+        if !llm_has {
+            doc["llm"][key] = item;
+        }
+        changed = true;
+    }
+
+    changed
+}
+
+/// Multi-account support originally shipped as `[[messaging.X.accounts]]`;
+/// the arrays were renamed to `instances` when every platform gained them.
+fn rename_legacy_account_arrays(doc: &mut DocumentMut) -> bool {
+    let Some(messaging) = doc.get_mut("messaging").and_then(Item::as_table_like_mut) else {
+        return false;
+    };
+
+    let platforms = messaging
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+
+    let mut changed = false;
+    for key in platforms {
+        let Some(platform) = messaging.get_mut(&key).and_then(Item::as_table_like_mut) else {
+            continue;
+        };
+
+        if platform.get("instances").is_some() {
+            continue;
+        }
+
+        let Some(accounts) = platform.remove("accounts") else {
+            continue;
+        };
+
+        if accounts.is_array_of_tables() || accounts.is_array() {
+            platform.insert("instances", accounts);
+            changed = true;
+        } else {
+            // Not the legacy shape — put it back untouched.
+            platform.insert("accounts", accounts);
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_platform_tables_move_under_messaging() {
+        let content = r#"
+[discord]
+token = "abc"
+
+[messaging.telegram]
+token = "def"
+"#;
+
+        let (migrated, outcome) = migrate_content(content)
+            .expect("migration runs")
+            .expect("document was behind");
+
+        assert_eq!(outcome.from_version, 1);
+        assert_eq!(outcome.to_version, CONFIG_SCHEMA_VERSION);
+        assert!(
+            outcome
+                .applied
+                .contains(&"move root-level platform tables under [messaging]")
+        );
+
+        let doc: DocumentMut = migrated.parse().expect("migrated config parses");
+        assert!(doc.get("discord").is_none());
+        assert_eq!(
+            doc["messaging"]["discord"]["token"].as_str(),
+            Some("abc")
+        );
+        assert_eq!(doc["messaging"]["telegram"]["token"].as_str(), Some("def"));
+        assert_eq!(
+            doc["schema_version"].as_integer(),
+            Some(CONFIG_SCHEMA_VERSION)
+        );
+    }
+
+    #[test]
+    fn root_provider_keys_move_under_llm() {
+        let content = r#"
+anthropic_key = "sk-test"
+
+[llm]
+openai_key = "sk-other"
+"#;
+
+        let (migrated, _) = migrate_content(content)
+            .expect("migration runs")
+            .expect("document was behind");
+
+        let doc: DocumentMut = migrated.parse().expect("migrated config parses");
+        assert!(doc.get("anthropic_key").is_none());
+        assert_eq!(doc["llm"]["anthropic_key"].as_str(), Some("sk-test"));
+        assert_eq!(doc["llm"]["openai_key"].as_str(), Some("sk-other"));
+    }
+
+    #[test]
+    fn legacy_account_arrays_become_instances() {
+        let content = r#"
+[[messaging.telegram.accounts]]
+name = "support"
+token = "tok"
+"#;
+
+        let (migrated, outcome) = migrate_content(content)
+            .expect("migration runs")
+            .expect("document was behind");
+
+        assert!(
+            outcome
+                .applied
+                .contains(&"rename legacy per-platform [[accounts]] arrays to [[instances]]")
+        );
+
+        let doc: DocumentMut = migrated.parse().expect("migrated config parses");
+        assert!(doc["messaging"]["telegram"].get("accounts").is_none());
+        assert_eq!(
+            doc["messaging"]["telegram"]["instances"][0]["name"].as_str(),
+            Some("support")
+        );
+    }
+
+    #[test]
+    fn current_documents_are_left_alone() {
+        let content = format!("schema_version = {CONFIG_SCHEMA_VERSION}\n\n[messaging.discord]\ntoken = \"abc\"\n");
+        assert!(
+            migrate_content(&content)
+                .expect("migration runs")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Per-user privacy consent records.
+//!
+//! Each sender can control three things independently: whether their
+//! messages are retained in conversation history, whether their messages
+//! are processed by the LLM at all, and whether their messages may be
+//! used for analytics-style secondary processing (transcript exports,
+//! cross-channel recall). Absent rows mean full consent, so existing
+//! deployments behave exactly as before; preferences are changed through
+//! the `/consent` chat command or the API.
+
+use sqlx::{Row as _, SqlitePool};
+
+use crate::error::Result;
+
+use anyhow::Context as _;
+
+/// A sender's privacy preferences. Everything defaults to allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct ConsentRecord {
+    /// Messages are persisted to conversation history.
+    pub history_retention: bool,
+    /// Messages are processed by the LLM.
+    pub llm_processing: bool,
+    /// Messages may appear in transcript exports and cross-channel recall.
+    pub analytics: bool,
+}
+
+impl Default for ConsentRecord {
+    fn default() -> Self {
+        Self {
+            history_retention: true,
+            llm_processing: true,
+            analytics: true,
+        }
+    }
+}
+
+/// SQLite-backed store for per-sender consent records.
+#[derive(Debug, Clone)]
+pub struct ConsentStore {
+    pool: SqlitePool,
+}
+
+impl ConsentStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Look up a sender's preferences; senders with no record get defaults.
+    pub async fn get(&self, sender_id: &str) -> Result<ConsentRecord> {
+        let row = sqlx::query(
+            "SELECT history_retention, llm_processing, analytics \
+             FROM user_consent WHERE sender_id = ?",
+        )
+        .bind(sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to query consent record")?;
+
+        Ok(match row {
+            Some(row) => ConsentRecord {
+                history_retention: row.get::<i64, _>("history_retention") != 0,
+                llm_processing: row.get::<i64, _>("llm_processing") != 0,
+                analytics: row.get::<i64, _>("analytics") != 0,
+            },
+            None => ConsentRecord::default(),
+        })
+    }
+
+    /// Store a sender's full preference set, replacing any existing record.
+    pub async fn set(&self, sender_id: &str, record: ConsentRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO user_consent (sender_id, history_retention, llm_processing, analytics, updated_at) \
+             VALUES (?, ?, ?, ?, ?) \
+             ON CONFLICT (sender_id) DO UPDATE SET \
+                 history_retention = excluded.history_retention, \
+                 llm_processing = excluded.llm_processing, \
+                 analytics = excluded.analytics, \
+                 updated_at = excluded.updated_at",
+        )
+        .bind(sender_id)
+        .bind(record.history_retention as i64)
+        .bind(record.llm_processing as i64)
+        .bind(record.analytics as i64)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .context("failed to store consent record")?;
+        Ok(())
+    }
+
+    /// Remove a sender's record, restoring the full-consent defaults.
+    pub async fn reset(&self, sender_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM user_consent WHERE sender_id = ?")
+            .bind(sender_id)
+            .execute(&self.pool)
+            .await
+            .context("failed to reset consent record")?;
+        Ok(())
+    }
+}
+
+/// Parse an on/off style toggle from user input.
+pub fn parse_toggle(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "on" | "yes" | "true" | "allow" => Some(true),
+        "off" | "no" | "false" | "deny" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_store() -> ConsentStore {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        ConsentStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn unknown_senders_get_full_consent() {
+        let store = test_store().await;
+        assert_eq!(store.get("U1").await.unwrap(), ConsentRecord::default());
+    }
+
+    #[tokio::test]
+    async fn preferences_roundtrip_and_reset() {
+        let store = test_store().await;
+        let record = ConsentRecord {
+            history_retention: false,
+            llm_processing: true,
+            analytics: false,
+        };
+        store.set("U1", record).await.unwrap();
+        assert_eq!(store.get("U1").await.unwrap(), record);
+
+        store.reset("U1").await.unwrap();
+        assert_eq!(store.get("U1").await.unwrap(), ConsentRecord::default());
+    }
+
+    #[test]
+    fn toggles_parse_loosely() {
+        assert_eq!(parse_toggle("ON"), Some(true));
+        assert_eq!(parse_toggle("deny"), Some(false));
+        assert_eq!(parse_toggle("maybe"), None);
+    }
+}
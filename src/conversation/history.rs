@@ -146,6 +146,10 @@ impl ConversationLogger {
     }
 
     /// Load recent messages from any channel (not just the current one).
+    ///
+    /// Messages from senders who opted out of analytics are excluded — this
+    /// path feeds transcript exports and cross-channel recall, not the live
+    /// conversation.
     pub async fn load_channel_transcript(
         &self,
         channel_id: &str,
@@ -155,6 +159,10 @@ impl ConversationLogger {
             "SELECT id, channel_id, role, sender_name, sender_id, content, metadata, created_at \
              FROM conversation_messages \
              WHERE channel_id = ? \
+               AND NOT EXISTS ( \
+                   SELECT 1 FROM user_consent \
+                   WHERE user_consent.sender_id = conversation_messages.sender_id \
+                     AND user_consent.analytics = 0) \
              ORDER BY created_at DESC \
              LIMIT ?",
         )
@@ -71,12 +71,10 @@ fn convert_history(history: &[rig::message::Message]) -> Vec<TranscriptStep> {
                 let mut parts = Vec::new();
                 for item in content.iter() {
                     match item {
-                        rig::message::AssistantContent::Text(text) => {
-                            if !text.text.is_empty() {
-                                parts.push(ActionContent::Text {
-                                    text: text.text.clone(),
-                                });
-                            }
+                        rig::message::AssistantContent::Text(text) if !text.text.is_empty() => {
+                            parts.push(ActionContent::Text {
+                                text: text.text.clone(),
+                            });
                         }
                         rig::message::AssistantContent::ToolCall(tool_call) => {
                             let args_str = tool_call.function.arguments.to_string();
@@ -128,15 +126,15 @@ fn convert_history(history: &[rig::message::Message]) -> Vec<TranscriptStep> {
                                 text: truncated,
                             });
                         }
-                        rig::message::UserContent::Text(text) => {
-                            // Skip compaction markers and system-injected messages
-                            if !text.text.is_empty() && !text.text.starts_with("[System:") {
-                                steps.push(TranscriptStep::Action {
-                                    content: vec![ActionContent::Text {
-                                        text: text.text.clone(),
-                                    }],
-                                });
-                            }
+                        // Skip compaction markers and system-injected messages
+                        rig::message::UserContent::Text(text)
+                            if !text.text.is_empty() && !text.text.starts_with("[System:") =>
+                        {
+                            steps.push(TranscriptStep::Action {
+                                content: vec![ActionContent::Text {
+                                    text: text.text.clone(),
+                                }],
+                            });
                         }
                         _ => {}
                     }
@@ -3,5 +3,5 @@
 pub mod scheduler;
 pub mod store;
 
-pub use scheduler::{CronConfig, CronContext, Scheduler};
+pub use scheduler::{CronConfig, CronContext, Scheduler, UpcomingCronRun};
 pub use store::{CronExecutionEntry, CronExecutionStats, CronStore};
@@ -29,6 +29,8 @@ pub struct CronJob {
     pub cron_expr: Option<String>,
     pub interval_secs: u64,
     pub delivery_target: BroadcastTarget,
+    /// Optional outbound template name; the result renders through it before delivery.
+    pub template: Option<String>,
     pub active_hours: Option<(u8, u8)>,
     pub enabled: bool,
     pub run_once: bool,
@@ -49,6 +51,9 @@ pub struct CronConfig {
     pub interval_secs: u64,
     /// Delivery target in "adapter:target" format (e.g. "discord:123456789").
     pub delivery_target: String,
+    /// Optional outbound template name (see `crate::templates`).
+    #[serde(default)]
+    pub template: Option<String>,
     pub active_hours: Option<(u8, u8)>,
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -79,6 +84,17 @@ pub struct CronContext {
     pub logs_dir: std::path::PathBuf,
     pub messaging_manager: Arc<MessagingManager>,
     pub store: Arc<CronStore>,
+    pub templates: Arc<crate::templates::TemplateStore>,
+}
+
+/// One upcoming cron firing, as surfaced in channel time context.
+#[derive(Debug, Clone)]
+pub struct UpcomingCronRun {
+    pub id: String,
+    pub prompt: String,
+    /// Next wall-clock fire time; `None` for interval jobs.
+    pub next_fire_utc: Option<chrono::DateTime<chrono::Utc>>,
+    pub interval_secs: u64,
 }
 
 const MAX_CONSECUTIVE_FAILURES: u32 = 3;
@@ -147,6 +163,7 @@ impl Scheduler {
             cron_expr,
             interval_secs: config.interval_secs,
             delivery_target,
+            template: config.template,
             active_hours: normalize_active_hours(config.active_hours),
             enabled: config.enabled,
             run_once: config.run_once,
@@ -417,6 +434,34 @@ impl Scheduler {
         jobs.contains_key(job_id)
     }
 
+    /// Next fire times for enabled jobs, soonest first. Interval jobs carry
+    /// no wall-clock expression, so only their cadence is reported and they
+    /// sort after the dated entries.
+    pub async fn upcoming_runs(&self, limit: usize) -> Vec<UpcomingCronRun> {
+        let jobs = self.jobs.read().await;
+        let mut runs: Vec<UpcomingCronRun> = jobs
+            .values()
+            .filter(|job| job.enabled)
+            .map(|job| UpcomingCronRun {
+                id: job.id.clone(),
+                prompt: job.prompt.clone(),
+                next_fire_utc: job.cron_expr.as_deref().and_then(|cron_expr| {
+                    next_fire_duration(&self.context, &job.id, cron_expr)
+                        .map(|(_, next_fire_utc, _)| next_fire_utc)
+                }),
+                interval_secs: job.interval_secs,
+            })
+            .collect();
+        drop(jobs);
+
+        runs.sort_by_key(|run| {
+            run.next_fire_utc
+                .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC)
+        });
+        runs.truncate(limit);
+        runs
+    }
+
     /// Trigger a cron job immediately, outside the timer loop.
     pub async fn trigger_now(&self, job_id: &str) -> Result<()> {
         let job = {
@@ -491,6 +536,7 @@ impl Scheduler {
                         cron_expr: normalize_cron_expr(config.cron_expr)?,
                         interval_secs: config.interval_secs,
                         delivery_target,
+                        template: config.template,
                         active_hours: normalize_active_hours(config.active_hours),
                         enabled: true,
                         run_once: config.run_once,
@@ -578,7 +624,9 @@ fn current_hour_and_timezone(context: &CronContext, cron_id: &str) -> (u8, Strin
     }
 }
 
-fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
+/// Whether `current_hour` falls inside the window, handling wrap-around
+/// windows like 22-06. Equal start/end means always active.
+pub fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
     if start_hour == end_hour {
         return true;
     }
@@ -818,6 +866,35 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
     let result_text = collected_text.join("\n\n");
     let has_result = !result_text.trim().is_empty();
 
+    // Render through the job's outbound template, if it names one
+    let delivery_text = match &job.template {
+        Some(template) if has_result => {
+            let template_context = serde_json::json!({
+                "result": result_text,
+                "cron_id": job.id,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "platform": job.delivery_target.adapter,
+            });
+            match context.templates.render(
+                template,
+                Some(&job.delivery_target.adapter),
+                &template_context,
+            ) {
+                Ok(rendered) => rendered,
+                Err(error) => {
+                    tracing::warn!(
+                        cron_id = %job.id,
+                        template = %template,
+                        %error,
+                        "failed to render cron template, delivering raw result"
+                    );
+                    result_text.clone()
+                }
+            }
+        }
+        _ => result_text.clone(),
+    };
+
     // Deliver result to target (only if there's something to say)
     if has_result {
         if let Err(error) = context
@@ -825,7 +902,7 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
             .broadcast(
                 &job.delivery_target.adapter,
                 &job.delivery_target.target,
-                OutboundResponse::Text(result_text.clone()),
+                OutboundResponse::Text(delivery_text),
             )
             .await
         {
@@ -24,13 +24,14 @@ impl CronStore {
 
         sqlx::query(
             r#"
-            INSERT INTO cron_jobs (id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO cron_jobs (id, prompt, cron_expr, interval_secs, delivery_target, template, active_start_hour, active_end_hour, enabled, run_once, timeout_secs)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 prompt = excluded.prompt,
                 cron_expr = excluded.cron_expr,
                 interval_secs = excluded.interval_secs,
                 delivery_target = excluded.delivery_target,
+                template = excluded.template,
                 active_start_hour = excluded.active_start_hour,
                 active_end_hour = excluded.active_end_hour,
                 enabled = excluded.enabled,
@@ -43,6 +44,7 @@ impl CronStore {
         .bind(config.cron_expr.as_deref())
         .bind(config.interval_secs as i64)
         .bind(&config.delivery_target)
+        .bind(config.template.as_deref())
         .bind(active_start)
         .bind(active_end)
         .bind(config.enabled as i64)
@@ -59,7 +61,7 @@ impl CronStore {
     pub async fn load_all(&self) -> Result<Vec<CronConfig>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs
+            SELECT id, prompt, cron_expr, interval_secs, delivery_target, template, active_start_hour, active_end_hour, enabled, run_once, timeout_secs
             FROM cron_jobs
             WHERE enabled = 1
             ORDER BY created_at ASC
@@ -77,6 +79,7 @@ impl CronStore {
                 cron_expr: row.try_get::<Option<String>, _>("cron_expr").ok().flatten(),
                 interval_secs: row.try_get::<i64, _>("interval_secs").unwrap_or(3600) as u64,
                 delivery_target: row.try_get("delivery_target").unwrap_or_default(),
+                template: row.try_get::<Option<String>, _>("template").ok().flatten(),
                 active_hours: {
                     let start: Option<i64> = row.try_get("active_start_hour").ok();
                     let end: Option<i64> = row.try_get("active_end_hour").ok();
@@ -151,7 +154,7 @@ impl CronStore {
     pub async fn load_all_unfiltered(&self) -> Result<Vec<CronConfig>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs
+            SELECT id, prompt, cron_expr, interval_secs, delivery_target, template, active_start_hour, active_end_hour, enabled, run_once, timeout_secs
             FROM cron_jobs
             ORDER BY created_at ASC
             "#,
@@ -168,6 +171,7 @@ impl CronStore {
                 cron_expr: row.try_get::<Option<String>, _>("cron_expr").ok().flatten(),
                 interval_secs: row.try_get::<i64, _>("interval_secs").unwrap_or(3600) as u64,
                 delivery_target: row.try_get("delivery_target").unwrap_or_default(),
+                template: row.try_get::<Option<String>, _>("template").ok().flatten(),
                 active_hours: {
                     let start: Option<i64> = row.try_get("active_start_hour").ok();
                     let end: Option<i64> = row.try_get("active_end_hour").ok();
@@ -23,6 +23,12 @@ use std::time::Instant;
 pub enum IpcCommand {
     Shutdown,
     Status,
+    MaintenanceOn {
+        message: Option<String>,
+        until: Option<i64>,
+    },
+    MaintenanceOff,
+    MaintenanceStatus,
 }
 
 /// Responses from the daemon back to the CLI client.
@@ -31,6 +37,11 @@ pub enum IpcCommand {
 pub enum IpcResponse {
     Ok,
     Status { pid: u32, uptime_seconds: u64 },
+    Maintenance {
+        active: bool,
+        message: Option<String>,
+        until: Option<i64>,
+    },
     Error { message: String },
 }
 
@@ -318,6 +329,7 @@ fn build_otlp_provider(telemetry: &TelemetryConfig) -> Option<SdkTracerProvider>
 /// loop should select on.
 pub async fn start_ipc_server(
     paths: &DaemonPaths,
+    maintenance: crate::maintenance::MaintenanceStore,
 ) -> anyhow::Result<(watch::Receiver<bool>, tokio::task::JoinHandle<()>)> {
     // Ensure the instance directory exists (e.g. on first run)
     if let Some(parent) = paths.socket.parent() {
@@ -346,9 +358,10 @@ pub async fn start_ipc_server(
                 Ok((stream, _address)) => {
                     let shutdown_tx = shutdown_tx.clone();
                     let uptime = start_time.elapsed();
+                    let maintenance = maintenance.clone();
                     tokio::spawn(async move {
                         if let Err(error) =
-                            handle_ipc_connection(stream, &shutdown_tx, uptime).await
+                            handle_ipc_connection(stream, &shutdown_tx, uptime, &maintenance).await
                         {
                             tracing::warn!(%error, "IPC connection handler failed");
                         }
@@ -377,6 +390,7 @@ async fn handle_ipc_connection(
     stream: UnixStream,
     shutdown_tx: &watch::Sender<bool>,
     uptime: std::time::Duration,
+    maintenance: &crate::maintenance::MaintenanceStore,
 ) -> anyhow::Result<()> {
     let (reader, mut writer) = stream.into_split();
     let mut reader = tokio::io::BufReader::new(reader);
@@ -396,6 +410,24 @@ async fn handle_ipc_connection(
             pid: std::process::id(),
             uptime_seconds: uptime.as_secs(),
         },
+        IpcCommand::MaintenanceOn { message, until } => {
+            maintenance.enable(message, until).await;
+            tracing::info!("maintenance mode enabled via IPC");
+            IpcResponse::Ok
+        }
+        IpcCommand::MaintenanceOff => {
+            maintenance.disable().await;
+            tracing::info!("maintenance mode disabled via IPC");
+            IpcResponse::Ok
+        }
+        IpcCommand::MaintenanceStatus => {
+            let status = maintenance.status().await;
+            IpcResponse::Maintenance {
+                active: status.is_some(),
+                message: status.as_ref().and_then(|window| window.message.clone()),
+                until: status.as_ref().and_then(|window| window.until),
+            }
+        }
     };
 
     let mut response_bytes = serde_json::to_vec(&response)?;
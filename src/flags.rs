@@ -0,0 +1,218 @@
+//! Runtime feature flags.
+//!
+//! API-managed flags that gate experimental subsystems (Discord streaming,
+//! new middleware, consensus mode) per agent without config file churn. The
+//! flag set persists to `feature_flags.json` in the instance directory and is
+//! shared through an `Arc<ArcSwap<FeatureFlags>>` like bindings: consumers
+//! read a lock-free snapshot, the API swaps in updates at runtime.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// File name under the instance directory.
+pub const FEATURE_FLAGS_FILE: &str = "feature_flags.json";
+
+/// Streaming responses on Discord.
+pub const FLAG_DISCORD_STREAMING: &str = "discord_streaming";
+/// Multi-model consensus mode for channel replies.
+pub const FLAG_CONSENSUS_MODE: &str = "consensus_mode";
+
+/// A single feature flag with its rollout state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlag {
+    pub name: String,
+    /// Default state when no override or rollout rule decides.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Per-agent overrides (agent id -> state). Checked before everything else.
+    #[serde(default)]
+    pub agent_overrides: HashMap<String, bool>,
+    /// Percentage rollout 0-100, bucketed deterministically by conversation
+    /// ID so a conversation stays in or out for the flag's whole lifetime.
+    #[serde(default)]
+    pub rollout_percent: Option<u8>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// The full flag set. One snapshot of this lives behind the ArcSwap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeatureFlags {
+    #[serde(default)]
+    pub flags: Vec<FeatureFlag>,
+}
+
+impl FeatureFlags {
+    pub fn get(&self, name: &str) -> Option<&FeatureFlag> {
+        self.flags.iter().find(|flag| flag.name == name)
+    }
+
+    /// Evaluate a flag for an agent/conversation.
+    ///
+    /// Precedence: agent override, then percentage rollout (when a
+    /// conversation ID is available to bucket on), then the flag's default.
+    /// Unknown flags are off.
+    pub fn is_enabled(
+        &self,
+        name: &str,
+        agent_id: Option<&str>,
+        conversation_id: Option<&str>,
+    ) -> bool {
+        let Some(flag) = self.get(name) else {
+            return false;
+        };
+
+        if let Some(agent_id) = agent_id
+            && let Some(state) = flag.agent_overrides.get(agent_id)
+        {
+            return *state;
+        }
+
+        if let Some(percent) = flag.rollout_percent
+            && let Some(conversation_id) = conversation_id
+        {
+            return rollout_bucket(name, conversation_id) < percent.min(100);
+        }
+
+        flag.enabled
+    }
+
+    /// Insert or replace a flag by name.
+    pub fn upsert(&mut self, flag: FeatureFlag) {
+        if let Some(existing) = self
+            .flags
+            .iter_mut()
+            .find(|existing| existing.name == flag.name)
+        {
+            *existing = flag;
+        } else {
+            self.flags.push(flag);
+        }
+    }
+
+    /// Remove a flag by name. Returns whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.flags.len();
+        self.flags.retain(|flag| flag.name != name);
+        self.flags.len() != before
+    }
+
+    /// Load the flag set from the instance directory. A missing file is an
+    /// empty set; a corrupt file is logged and treated the same so a bad
+    /// write can't keep the instance from booting.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(FEATURE_FLAGS_FILE);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&content) {
+            Ok(flags) => flags,
+            Err(error) => {
+                tracing::warn!(%error, path = %path.display(), "failed to parse feature flags file; starting empty");
+                Self::default()
+            }
+        }
+    }
+
+    /// Persist the flag set to the instance directory.
+    pub fn save(&self, instance_dir: &Path) -> anyhow::Result<()> {
+        let path = instance_dir.join(FEATURE_FLAGS_FILE);
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+}
+
+/// Deterministic 0-99 bucket for percentage rollouts: the same
+/// flag/conversation pair always lands in the same bucket.
+fn rollout_bucket(flag_name: &str, conversation_id: &str) -> u8 {
+    let mut hasher = Sha256::new();
+    hasher.update(flag_name.as_bytes());
+    hasher.update(b":");
+    hasher.update(conversation_id.as_bytes());
+    let digest = hasher.finalize();
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(bytes) % 100) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag_set(flag: FeatureFlag) -> FeatureFlags {
+        FeatureFlags { flags: vec![flag] }
+    }
+
+    #[test]
+    fn agent_overrides_beat_defaults_and_rollout() {
+        let flags = flag_set(FeatureFlag {
+            name: FLAG_DISCORD_STREAMING.into(),
+            enabled: false,
+            agent_overrides: [("pilot".to_string(), true)].into(),
+            rollout_percent: Some(0),
+            description: None,
+        });
+
+        assert!(flags.is_enabled(FLAG_DISCORD_STREAMING, Some("pilot"), Some("discord:1")));
+        assert!(!flags.is_enabled(FLAG_DISCORD_STREAMING, Some("other"), None));
+        assert!(!flags.is_enabled("unknown_flag", Some("pilot"), None));
+    }
+
+    #[test]
+    fn rollout_buckets_are_deterministic_and_bounded() {
+        let first = rollout_bucket("consensus_mode", "discord:12345");
+        let second = rollout_bucket("consensus_mode", "discord:12345");
+        assert_eq!(first, second);
+        assert!(first < 100);
+
+        let flags = flag_set(FeatureFlag {
+            name: FLAG_CONSENSUS_MODE.into(),
+            enabled: false,
+            agent_overrides: HashMap::new(),
+            rollout_percent: Some(100),
+            description: None,
+        });
+        assert!(flags.is_enabled(FLAG_CONSENSUS_MODE, None, Some("discord:12345")));
+
+        let flags = flag_set(FeatureFlag {
+            name: FLAG_CONSENSUS_MODE.into(),
+            enabled: true,
+            agent_overrides: HashMap::new(),
+            rollout_percent: Some(0),
+            description: None,
+        });
+        // A 0% rollout turns the flag off for bucketed conversations, but the
+        // default still applies where there's nothing to bucket on.
+        assert!(!flags.is_enabled(FLAG_CONSENSUS_MODE, None, Some("discord:12345")));
+        assert!(flags.is_enabled(FLAG_CONSENSUS_MODE, None, None));
+    }
+
+    #[test]
+    fn upsert_replaces_by_name() {
+        let mut flags = FeatureFlags::default();
+        flags.upsert(FeatureFlag {
+            name: "x".into(),
+            enabled: false,
+            agent_overrides: HashMap::new(),
+            rollout_percent: None,
+            description: None,
+        });
+        flags.upsert(FeatureFlag {
+            name: "x".into(),
+            enabled: true,
+            agent_overrides: HashMap::new(),
+            rollout_percent: None,
+            description: None,
+        });
+
+        assert_eq!(flags.flags.len(), 1);
+        assert!(flags.is_enabled("x", None, None));
+        assert!(flags.remove("x"));
+        assert!(!flags.remove("x"));
+    }
+}
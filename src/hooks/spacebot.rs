@@ -3,8 +3,19 @@
 use crate::{AgentId, ChannelId, ProcessEvent, ProcessId, ProcessType};
 use rig::agent::{HookAction, PromptHook, ToolCallHookAction};
 use rig::completion::{CompletionModel, CompletionResponse, Message};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::broadcast;
 
+/// Cumulative generation counters behind progress heartbeats. Shared across
+/// hook clones so multi-segment runs keep one running total per process.
+#[derive(Default)]
+struct ProgressCounters {
+    turns: AtomicU64,
+    output_tokens: AtomicU64,
+    total_tokens: AtomicU64,
+}
+
 /// Hook for observing agent behavior and sending events.
 #[derive(Clone)]
 pub struct SpacebotHook {
@@ -13,6 +24,7 @@ pub struct SpacebotHook {
     process_type: ProcessType,
     channel_id: Option<ChannelId>,
     event_tx: broadcast::Sender<ProcessEvent>,
+    progress: Arc<ProgressCounters>,
 }
 
 impl SpacebotHook {
@@ -30,6 +42,21 @@ impl SpacebotHook {
             process_type,
             channel_id,
             event_tx,
+            progress: Arc::new(ProgressCounters::default()),
+        }
+    }
+
+    /// Whether this process gets generation heartbeats: workers and cron
+    /// channels run for minutes without any interactive stream, while normal
+    /// channels already stream their output to the platform.
+    fn emits_generation_progress(&self) -> bool {
+        match self.process_type {
+            ProcessType::Worker => true,
+            ProcessType::Channel => self
+                .channel_id
+                .as_deref()
+                .is_some_and(|channel_id| channel_id.starts_with("cron:")),
+            _ => false,
         }
     }
 
@@ -162,13 +189,38 @@ where
     async fn on_completion_response(
         &self,
         _prompt: &Message,
-        _response: &CompletionResponse<M::Response>,
+        response: &CompletionResponse<M::Response>,
     ) -> HookAction {
         tracing::debug!(
             process_id = %self.process_id,
             "completion response received"
         );
 
+        let usage = &response.usage;
+        let turns = self.progress.turns.fetch_add(1, Ordering::Relaxed) + 1;
+        let output_tokens = self
+            .progress
+            .output_tokens
+            .fetch_add(usage.output_tokens, Ordering::Relaxed)
+            + usage.output_tokens;
+        let total_tokens = self
+            .progress
+            .total_tokens
+            .fetch_add(usage.total_tokens, Ordering::Relaxed)
+            + usage.total_tokens;
+
+        if self.emits_generation_progress() {
+            let event = ProcessEvent::GenerationProgress {
+                agent_id: self.agent_id.clone(),
+                process_id: self.process_id.clone(),
+                channel_id: self.channel_id.clone(),
+                turns,
+                output_tokens,
+                total_tokens,
+            };
+            self.event_tx.send(event).ok();
+        }
+
         HookAction::Continue
     }
 
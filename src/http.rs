@@ -0,0 +1,247 @@
+//! Shared outbound HTTP client construction.
+//!
+//! Adapters and providers used to build their own `reqwest::Client` (a few
+//! call sites even built one per request), so nothing shared a connection
+//! pool and every client re-ran TLS setup. The helpers here hand out one
+//! tuned client — keep-alive, HTTP/2 pings, and the global egress proxy in
+//! one place — while [`builder`] lets call sites layer their own timeout or
+//! redirect policy on top of the same pooling configuration.
+
+use hickory_resolver::TokioAsyncResolver;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+/// How long idle pooled connections are kept around for reuse.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Cap on idle connections retained per host.
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+/// TCP keep-alive probe interval for pooled connections.
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+/// HTTP/2 ping interval; keeps multiplexed connections from being dropped
+/// by idle-timeout middleboxes between messages.
+const HTTP2_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+static SHARED: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    builder()
+        .build()
+        .expect("failed to build shared HTTP client")
+});
+
+/// Global egress proxy from `[network] proxy`, applied to every client built
+/// here unless a per-adapter proxy overrides it.
+static DEFAULT_PROXY: RwLock<Option<String>> = RwLock::new(None);
+
+/// Resolver built from `[network]` options; `None` means reqwest's default
+/// getaddrinfo resolution.
+static OUTBOUND_RESOLVER: RwLock<Option<Arc<OutboundResolver>>> = RwLock::new(None);
+
+/// Install the configured global egress proxy. Must run before the first
+/// [`client`] call — the shared client is built once and keeps whatever proxy
+/// was in effect at that point.
+pub fn set_default_proxy(proxy: Option<String>) {
+    *DEFAULT_PROXY.write().expect("default proxy lock poisoned") = proxy
+        .map(|proxy| proxy.trim().to_string())
+        .filter(|proxy| !proxy.is_empty());
+}
+
+/// Install resolver behaviour from `[network]`: address-family preference,
+/// custom DNS servers, or a DoH provider. Like [`set_default_proxy`], this
+/// must run before the first [`client`] call.
+pub fn set_resolver_options(network: &crate::config::NetworkConfig) {
+    use crate::config::IpPreference;
+
+    let custom = custom_resolver(network);
+    if custom.is_none()
+        && network.ip_preference == IpPreference::Auto
+    {
+        return;
+    }
+
+    let resolver = Arc::new(OutboundResolver {
+        prefer: network.ip_preference,
+        custom,
+    });
+    *OUTBOUND_RESOLVER
+        .write()
+        .expect("outbound resolver lock poisoned") = Some(resolver);
+}
+
+/// Build a hickory resolver when custom DNS servers or a DoH provider are
+/// configured; `None` falls through to system resolution.
+fn custom_resolver(network: &crate::config::NetworkConfig) -> Option<TokioAsyncResolver> {
+    use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+
+    if let Some(provider) = network.doh_provider.as_deref() {
+        let group = match provider {
+            "cloudflare" => NameServerConfigGroup::cloudflare_https(),
+            "google" => NameServerConfigGroup::google_https(),
+            "quad9" => NameServerConfigGroup::quad9_https(),
+            other => {
+                tracing::warn!(
+                    provider = other,
+                    "unknown [network] doh_provider (expected cloudflare, google, or quad9), using system DNS"
+                );
+                return None;
+            }
+        };
+        let config = ResolverConfig::from_parts(None, Vec::new(), group);
+        return Some(TokioAsyncResolver::tokio(config, ResolverOpts::default()));
+    }
+
+    if network.dns_servers.is_empty() {
+        return None;
+    }
+
+    let ips: Vec<std::net::IpAddr> = network
+        .dns_servers
+        .iter()
+        .filter_map(|server| match server.trim().parse() {
+            Ok(ip) => Some(ip),
+            Err(error) => {
+                tracing::warn!(%error, server, "invalid [network] dns_servers entry, skipping");
+                None
+            }
+        })
+        .collect();
+    if ips.is_empty() {
+        return None;
+    }
+
+    let group = NameServerConfigGroup::from_ips_clear(&ips, 53, true);
+    let config = ResolverConfig::from_parts(None, Vec::new(), group);
+    Some(TokioAsyncResolver::tokio(config, ResolverOpts::default()))
+}
+
+/// Resolver plugged into reqwest that applies the configured address-family
+/// preference and optional custom DNS/DoH backend.
+struct OutboundResolver {
+    prefer: crate::config::IpPreference,
+    custom: Option<TokioAsyncResolver>,
+}
+
+impl reqwest::dns::Resolve for OutboundResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let prefer = self.prefer;
+        let custom = self.custom.clone();
+        Box::pin(async move {
+            let mut addrs: Vec<std::net::SocketAddr> = match &custom {
+                Some(resolver) => resolver
+                    .lookup_ip(name.as_str())
+                    .await
+                    .map_err(box_resolve_error)?
+                    .iter()
+                    // Port is a placeholder; reqwest substitutes the real one.
+                    .map(|ip| std::net::SocketAddr::new(ip, 0))
+                    .collect(),
+                None => tokio::net::lookup_host((name.as_str(), 0))
+                    .await
+                    .map_err(box_resolve_error)?
+                    .collect(),
+            };
+
+            match prefer {
+                crate::config::IpPreference::Auto => {}
+                crate::config::IpPreference::Ipv4 => {
+                    addrs.sort_by_key(|addr| !addr.is_ipv4());
+                }
+                crate::config::IpPreference::Ipv6 => {
+                    addrs.sort_by_key(|addr| !addr.is_ipv6());
+                }
+            }
+
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+fn box_resolve_error(
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    Box::new(error)
+}
+
+fn default_proxy() -> Option<String> {
+    std::env::var("SPACEBOT_HTTP_PROXY")
+        .ok()
+        .map(|proxy| proxy.trim().to_string())
+        .filter(|proxy| !proxy.is_empty())
+        .or_else(|| {
+            DEFAULT_PROXY
+                .read()
+                .expect("default proxy lock poisoned")
+                .clone()
+        })
+}
+
+/// A pooled client routed through `proxy` (`socks5://` or `http://`) when one
+/// is set, or the shared client otherwise. Invalid proxy URLs log a warning
+/// and fall back to the shared client rather than failing adapter startup.
+pub fn client_with_proxy(proxy: Option<&str>) -> reqwest::Client {
+    let Some(proxy_url) = proxy.map(str::trim).filter(|proxy| !proxy.is_empty()) else {
+        return client();
+    };
+
+    let proxy = match reqwest::Proxy::all(proxy_url) {
+        Ok(proxy) => proxy,
+        Err(error) => {
+            tracing::warn!(%error, "invalid proxy URL, connecting directly");
+            return client();
+        }
+    };
+
+    match tuned().proxy(proxy).build() {
+        Ok(client) => client,
+        Err(error) => {
+            tracing::warn!(%error, "failed to build proxied HTTP client, connecting directly");
+            client()
+        }
+    }
+}
+
+/// The shared pooled client. `reqwest::Client` wraps an `Arc`, so the clone
+/// is cheap and every caller reuses the same connection pool.
+pub fn client() -> reqwest::Client {
+    SHARED.clone()
+}
+
+/// A pre-tuned builder for call sites that need their own timeout, redirect
+/// policy, or user agent. Applies the pooling, keep-alive, and proxy
+/// configuration so custom clients still behave like the shared one.
+///
+/// The global egress proxy (`SPACEBOT_HTTP_PROXY`, falling back to
+/// `[network] proxy`) applies to all traffic; the standard
+/// `HTTPS_PROXY`/`HTTP_PROXY` variables are honored by reqwest itself.
+pub fn builder() -> reqwest::ClientBuilder {
+    let mut builder = tuned();
+
+    if let Some(proxy_url) = default_proxy() {
+        match reqwest::Proxy::all(&proxy_url) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(error) => {
+                tracing::warn!(%error, "invalid global proxy URL, ignoring")
+            }
+        }
+    }
+
+    builder
+}
+
+/// The shared pool settings without any proxy applied.
+fn tuned() -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
+        .http2_keep_alive_while_idle(true)
+        .connect_timeout(Duration::from_secs(30));
+
+    let resolver = OUTBOUND_RESOLVER
+        .read()
+        .expect("outbound resolver lock poisoned")
+        .clone();
+    match resolver {
+        Some(resolver) => builder.dns_resolver(resolver),
+        None => builder,
+    }
+}
@@ -3,31 +3,48 @@
 pub mod agent;
 pub mod api;
 pub mod auth;
+pub mod binding_pins;
+pub mod bot_loop;
+pub mod citations;
 pub mod config;
+pub mod config_migrations;
+pub mod consent;
 pub mod conversation;
 pub mod cron;
 pub mod daemon;
 pub mod db;
 pub mod error;
+pub mod flags;
 pub mod hooks;
+pub mod http;
 pub mod identity;
+pub mod link_safety;
 pub mod links;
 pub mod llm;
+pub mod maintenance;
 pub mod mcp;
 pub mod memory;
+pub mod migrate;
 pub mod messaging;
+pub mod onboarding;
 pub mod openai_auth;
 pub mod opencode;
+pub mod perf;
 pub mod prompts;
+pub mod quarantine;
 pub mod sandbox;
 pub mod secrets;
+pub mod selftest;
+pub mod service;
 pub mod settings;
 pub mod skills;
 pub mod tasks;
 #[cfg(feature = "metrics")]
 pub mod telemetry;
+pub mod templates;
 pub mod tools;
 pub mod update;
+pub mod watch;
 
 pub use error::{Error, Result};
 
@@ -133,6 +150,17 @@ pub enum ProcessEvent {
         notify: bool,
         success: bool,
     },
+    /// Heartbeat for long-running worker and cron generations: cumulative
+    /// turns and token usage so dashboards can show live progress instead of
+    /// a spinner.
+    GenerationProgress {
+        agent_id: AgentId,
+        process_id: ProcessId,
+        channel_id: Option<ChannelId>,
+        turns: u64,
+        output_tokens: u64,
+        total_tokens: u64,
+    },
     ToolStarted {
         agent_id: AgentId,
         process_id: ProcessId,
@@ -372,11 +400,13 @@ pub enum OutboundResponse {
         thread_name: String,
         text: String,
     },
-    /// Send a file attachment to the user.
+    /// Send a file attachment to the user. The payload is a [`bytes::Bytes`]
+    /// so cloning the response (broadcasts, retries) shares one buffer
+    /// instead of duplicating a potentially large file.
     File {
         filename: String,
         #[serde(with = "base64_bytes")]
-        data: Vec<u8>,
+        data: bytes::Bytes,
         mime_type: String,
         caption: Option<String>,
     },
@@ -415,8 +445,9 @@ pub enum OutboundResponse {
         #[serde(default)]
         poll: Option<Poll>,
     },
-    /// Schedule a message to be posted at a future Unix timestamp (Slack only).
-    /// Other adapters send immediately as a regular `Text` message.
+    /// Schedule a message to be posted at a future Unix timestamp. Slack
+    /// schedules natively; email and Telegram queue the send internally;
+    /// other adapters send immediately as a regular `Text` message.
     ScheduledMessage {
         text: String,
         /// Unix epoch seconds when the message should be delivered.
@@ -519,10 +550,13 @@ mod base64_bytes {
         serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(data))
     }
 
-    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<bytes::Bytes, D::Error> {
         let s = String::deserialize(deserializer)?;
         base64::engine::general_purpose::STANDARD
             .decode(&s)
+            .map(bytes::Bytes::from)
             .map_err(serde::de::Error::custom)
     }
 }
@@ -0,0 +1,273 @@
+//! Link safety checks for URLs in inbound messages.
+//!
+//! URLs are looked up against URLhaus (keyless) and, when a
+//! `SAFE_BROWSING_API_KEY` is set, the Google Safe Browsing v4 API before
+//! agents browse or unfurl them. The inbound loop warns the conversation when
+//! a message carries a flagged link, and the browser tool refuses to navigate
+//! to one. Lookups fail open: an unreachable reputation service never blocks
+//! a message. Enforced per binding through `link_safety` (on by default).
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const URLHAUS_API_URL: &str = "https://urlhaus-api.abuse.ch/v1/url/";
+const SAFE_BROWSING_API_URL: &str =
+    "https://safebrowsing.googleapis.com/v4/threatMatches:find";
+
+/// Verdicts are remembered this long before a URL is looked up again.
+const VERDICT_TTL_SECS: u64 = 3600;
+
+/// URLs checked per message; the rest are ignored rather than stalling the
+/// inbound loop on a link dump.
+const MAX_URLS_PER_MESSAGE: usize = 5;
+
+static GLOBAL_CHECKER: OnceLock<LinkSafetyChecker> = OnceLock::new();
+
+/// Install the process-wide checker consulted by the browser tool.
+pub fn set_global(checker: LinkSafetyChecker) {
+    let _ = GLOBAL_CHECKER.set(checker);
+}
+
+/// The process-wide checker, when one has been installed.
+pub fn global() -> Option<&'static LinkSafetyChecker> {
+    GLOBAL_CHECKER.get()
+}
+
+/// A URL flagged by a reputation service.
+#[derive(Debug, Clone)]
+pub struct LinkVerdict {
+    pub url: String,
+    /// Which service flagged it ("urlhaus" or "safe-browsing").
+    pub source: &'static str,
+    /// The service's threat label (e.g. "malware_download", "SOCIAL_ENGINEERING").
+    pub threat: String,
+}
+
+struct CachedVerdict {
+    flagged: Option<LinkVerdict>,
+    checked_at: Instant,
+}
+
+/// Looks up URL reputations with a short-lived verdict cache, shared by the
+/// inbound loop and the browser tool.
+#[derive(Clone)]
+pub struct LinkSafetyChecker {
+    client: reqwest::Client,
+    safe_browsing_api_key: Option<String>,
+    cache: Arc<Mutex<HashMap<String, CachedVerdict>>>,
+}
+
+impl LinkSafetyChecker {
+    pub fn new() -> Self {
+        let client = crate::http::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            safe_browsing_api_key: std::env::var("SAFE_BROWSING_API_KEY")
+                .ok()
+                .filter(|key| !key.is_empty()),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Check one URL, returning the verdict when a service flags it. Lookup
+    /// failures are logged and treated as clean.
+    pub async fn check_url(&self, url: &str) -> Option<LinkVerdict> {
+        {
+            let cache = self.cache.lock().await;
+            if let Some(cached) = cache.get(url)
+                && cached.checked_at.elapsed() < Duration::from_secs(VERDICT_TTL_SECS)
+            {
+                return cached.flagged.clone();
+            }
+        }
+
+        let mut flagged = match self.check_urlhaus(url).await {
+            Ok(verdict) => verdict,
+            Err(error) => {
+                tracing::warn!(%error, url, "urlhaus lookup failed, treating link as clean");
+                None
+            }
+        };
+
+        if flagged.is_none() && self.safe_browsing_api_key.is_some() {
+            flagged = match self.check_safe_browsing(url).await {
+                Ok(verdict) => verdict,
+                Err(error) => {
+                    tracing::warn!(
+                        %error,
+                        url,
+                        "safe browsing lookup failed, treating link as clean"
+                    );
+                    None
+                }
+            };
+        }
+
+        self.cache.lock().await.insert(
+            url.to_string(),
+            CachedVerdict {
+                flagged: flagged.clone(),
+                checked_at: Instant::now(),
+            },
+        );
+
+        flagged
+    }
+
+    /// Check every URL in a message body, bounded by [`MAX_URLS_PER_MESSAGE`].
+    pub async fn check_message(&self, text: &str) -> Vec<LinkVerdict> {
+        let mut verdicts = Vec::new();
+        for url in extract_urls(text).into_iter().take(MAX_URLS_PER_MESSAGE) {
+            if let Some(verdict) = self.check_url(&url).await {
+                verdicts.push(verdict);
+            }
+        }
+        verdicts
+    }
+
+    async fn check_urlhaus(&self, url: &str) -> anyhow::Result<Option<LinkVerdict>> {
+        let response: serde_json::Value = self
+            .client
+            .post(URLHAUS_API_URL)
+            .form(&[("url", url)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if response["query_status"].as_str() != Some("ok") {
+            return Ok(None);
+        }
+
+        let threat = response["threat"]
+            .as_str()
+            .unwrap_or("malicious")
+            .to_string();
+        Ok(Some(LinkVerdict {
+            url: url.to_string(),
+            source: "urlhaus",
+            threat,
+        }))
+    }
+
+    async fn check_safe_browsing(&self, url: &str) -> anyhow::Result<Option<LinkVerdict>> {
+        let Some(api_key) = &self.safe_browsing_api_key else {
+            return Ok(None);
+        };
+
+        let body = serde_json::json!({
+            "client": { "clientId": "spacebot", "clientVersion": env!("CARGO_PKG_VERSION") },
+            "threatInfo": {
+                "threatTypes": [
+                    "MALWARE",
+                    "SOCIAL_ENGINEERING",
+                    "UNWANTED_SOFTWARE",
+                    "POTENTIALLY_HARMFUL_APPLICATION",
+                ],
+                "platformTypes": ["ANY_PLATFORM"],
+                "threatEntryTypes": ["URL"],
+                "threatEntries": [{ "url": url }],
+            },
+        });
+
+        let response: serde_json::Value = self
+            .client
+            .post(format!("{SAFE_BROWSING_API_URL}?key={api_key}"))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let threat = response["matches"][0]["threatType"]
+            .as_str()
+            .map(str::to_string);
+        Ok(threat.map(|threat| LinkVerdict {
+            url: url.to_string(),
+            source: "safe-browsing",
+            threat,
+        }))
+    }
+}
+
+impl Default for LinkSafetyChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pull http/https URLs out of a message body, trimming trailing punctuation
+/// and markdown delimiters and deduplicating.
+pub fn extract_urls(text: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(start) = word.find("http://").or_else(|| word.find("https://")) else {
+            continue;
+        };
+        let url = word[start..]
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '>', '"', '\'']);
+        if url.len() > "https://".len() && !urls.iter().any(|existing| existing == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// Format the warning posted to a conversation whose message carried flagged
+/// links.
+pub fn format_link_warning(verdicts: &[LinkVerdict]) -> String {
+    let mut lines = vec![
+        "⚠️ Link safety warning — the following links are flagged as unsafe and will not be opened:".to_string(),
+    ];
+    for verdict in verdicts {
+        lines.push(format!(
+            "- {} ({}: {})",
+            verdict.url, verdict.source, verdict.threat
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkVerdict, extract_urls, format_link_warning};
+
+    #[test]
+    fn urls_are_extracted_trimmed_and_deduped() {
+        let text = "see https://example.com/page, <https://other.example> and \
+                    (http://example.com/a). again: https://example.com/page";
+        let urls = extract_urls(text);
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/page",
+                "https://other.example",
+                "http://example.com/a",
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_text_yields_no_urls() {
+        assert!(extract_urls("nothing to see here, not even ftp://old.school").is_empty());
+    }
+
+    #[test]
+    fn warning_lists_each_flagged_link() {
+        let warning = format_link_warning(&[LinkVerdict {
+            url: "https://bad.example".into(),
+            source: "urlhaus",
+            threat: "malware_download".into(),
+        }]);
+        assert!(warning.contains("https://bad.example"));
+        assert!(warning.contains("urlhaus: malware_download"));
+    }
+}
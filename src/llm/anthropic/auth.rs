@@ -163,7 +163,7 @@ mod tests {
         thinking: bool,
         force_bearer: bool,
     ) -> (reqwest::Request, AnthropicAuthPath) {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         let builder = client.post("https://api.anthropic.com/v1/messages");
         let (builder, auth_path) = apply_auth_headers(builder, token, thinking, force_bearer);
         (builder.build().unwrap(), auth_path)
@@ -38,7 +38,7 @@ pub struct LlmManager {
 impl LlmManager {
     /// Create a new LLM manager with the given configuration.
     pub async fn new(config: LlmConfig) -> Result<Self> {
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::http::builder()
             .timeout(std::time::Duration::from_secs(120))
             .build()
             .with_context(|| "failed to build HTTP client")?;
@@ -69,7 +69,7 @@ impl LlmManager {
 
     /// Initialize with an instance directory (for use at construction time).
     pub async fn with_instance_dir(config: LlmConfig, instance_dir: PathBuf) -> Result<Self> {
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::http::builder()
             .timeout(std::time::Duration::from_secs(120))
             .build()
             .with_context(|| "failed to build HTTP client")?;
@@ -300,6 +300,41 @@ impl LlmManager {
         }
     }
 
+    /// Enforce a binding's data-residency restriction for a model.
+    ///
+    /// An empty allow-list permits everything. Providers declare their
+    /// region in config (`region = "eu"` on the provider section); a
+    /// provider without one is treated as non-compliant once a restriction
+    /// is active, so data never leaks through an unclassified endpoint.
+    pub fn check_region(&self, model_name: &str, allowed_regions: &[String]) -> Result<()> {
+        if allowed_regions.is_empty() {
+            return Ok(());
+        }
+        let (provider_id, _) = self.resolve_model(model_name)?;
+        let config = self.config.load();
+        match config.provider_regions.get(&provider_id) {
+            Some(region)
+                if allowed_regions
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(region)) =>
+            {
+                Ok(())
+            }
+            Some(region) => Err(anyhow::anyhow!(
+                "model '{model_name}' is served by provider '{provider_id}' in region \
+                 '{region}', but this binding only allows: {}",
+                allowed_regions.join(", ")
+            )
+            .into()),
+            None => Err(anyhow::anyhow!(
+                "provider '{provider_id}' declares no region, but this binding requires one \
+                 of: {}; set `region` on the provider or relax the binding's allowed_regions",
+                allowed_regions.join(", ")
+            )
+            .into()),
+        }
+    }
+
     /// Record that a model hit a rate limit.
     pub async fn record_rate_limit(&self, model_name: &str) {
         self.rate_limited
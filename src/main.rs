@@ -49,6 +49,50 @@ enum Command {
     /// Manage authentication
     #[command(subcommand)]
     Auth(AuthCommand),
+    /// Manage OS service registration (Windows service / macOS launchd)
+    #[command(subcommand)]
+    Service(ServiceCommand),
+    /// Toggle maintenance mode on the running daemon
+    #[command(subcommand)]
+    Maintenance(MaintenanceCommand),
+    /// Migrate this instance to another host over SSH
+    Migrate {
+        /// Target, e.g. ssh://user@host:port/dest/dir
+        #[arg(long)]
+        to: String,
+        /// Mark the source read-only after the transfer so it can't be
+        /// started again by accident during cutover
+        #[arg(long)]
+        leave_read_only: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ServiceCommand {
+    /// Register spacebot with the platform service manager
+    Install,
+    /// Remove the service registration
+    Uninstall,
+    /// Show whether the service is installed and running
+    Status,
+}
+
+#[derive(Subcommand)]
+enum MaintenanceCommand {
+    /// Pause inbound processing; conversations get an auto-reply
+    On {
+        /// Notice shown to users, e.g. "back at 14:00 UTC"
+        #[arg(short, long)]
+        message: Option<String>,
+        /// End of the window as RFC 3339 (e.g. 2026-08-30T14:00:00Z);
+        /// maintenance lifts automatically when it passes
+        #[arg(short, long)]
+        until: Option<String>,
+    },
+    /// Resume inbound processing
+    Off,
+    /// Show whether maintenance mode is active
+    Status,
 }
 
 #[derive(Subcommand)]
@@ -144,6 +188,16 @@ fn main() -> anyhow::Result<()> {
         Command::Status => cmd_status(),
         Command::Skill(skill_cmd) => cmd_skill(cli.config, skill_cmd),
         Command::Auth(auth_cmd) => cmd_auth(cli.config, auth_cmd),
+        Command::Migrate {
+            to,
+            leave_read_only,
+        } => spacebot::migrate::migrate(&to, leave_read_only),
+        Command::Maintenance(maintenance_cmd) => cmd_maintenance(maintenance_cmd),
+        Command::Service(service_cmd) => match service_cmd {
+            ServiceCommand::Install => spacebot::service::install(cli.config.as_deref()),
+            ServiceCommand::Uninstall => spacebot::service::uninstall(),
+            ServiceCommand::Status => spacebot::service::status(),
+        },
     }
 }
 
@@ -160,6 +214,19 @@ fn cmd_start(
         std::process::exit(1);
     }
 
+    // Refuse to start an instance that was migrated away with --leave-read-only.
+    let default_instance_dir = spacebot::config::Config::default_instance_dir();
+    if let Some(target) = spacebot::migrate::readonly_target(&default_instance_dir) {
+        eprintln!("this instance was migrated to {target} and is marked read-only");
+        eprintln!(
+            "remove {} to start it here anyway",
+            default_instance_dir
+                .join(spacebot::migrate::READONLY_MARKER)
+                .display()
+        );
+        std::process::exit(1);
+    }
+
     // Run onboarding interactively before daemonizing
     let resolved_config_path = if config_path.is_some() {
         config_path.clone()
@@ -312,6 +379,77 @@ fn cmd_status() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::main]
+async fn cmd_maintenance(command: MaintenanceCommand) -> anyhow::Result<()> {
+    let paths = spacebot::daemon::DaemonPaths::from_default();
+
+    if spacebot::daemon::is_running(&paths).is_none() {
+        eprintln!("spacebot is not running");
+        std::process::exit(1);
+    }
+
+    let ipc_command = match &command {
+        MaintenanceCommand::On { message, until } => {
+            let until = match until {
+                Some(value) => Some(
+                    chrono::DateTime::parse_from_rfc3339(value)
+                        .with_context(|| format!("invalid --until timestamp '{value}'"))?
+                        .timestamp(),
+                ),
+                None => None,
+            };
+            spacebot::daemon::IpcCommand::MaintenanceOn {
+                message: message.clone(),
+                until,
+            }
+        }
+        MaintenanceCommand::Off => spacebot::daemon::IpcCommand::MaintenanceOff,
+        MaintenanceCommand::Status => spacebot::daemon::IpcCommand::MaintenanceStatus,
+    };
+
+    match spacebot::daemon::send_command(&paths, ipc_command).await {
+        Ok(spacebot::daemon::IpcResponse::Ok) => {
+            match command {
+                MaintenanceCommand::On { .. } => eprintln!("maintenance mode enabled"),
+                _ => eprintln!("maintenance mode disabled"),
+            }
+        }
+        Ok(spacebot::daemon::IpcResponse::Maintenance {
+            active,
+            message,
+            until,
+        }) => {
+            if active {
+                eprintln!("maintenance mode is ON");
+                if let Some(message) = message {
+                    eprintln!("  notice: {message}");
+                }
+                if let Some(until) = until
+                    && let Some(until) = chrono::DateTime::from_timestamp(until, 0)
+                {
+                    eprintln!("  until:  {}", until.to_rfc3339());
+                }
+            } else {
+                eprintln!("maintenance mode is off");
+            }
+        }
+        Ok(spacebot::daemon::IpcResponse::Error { message }) => {
+            eprintln!("maintenance command failed: {message}");
+            std::process::exit(1);
+        }
+        Ok(_) => {
+            eprintln!("unexpected response from daemon");
+            std::process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("failed to reach daemon: {error}");
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
 fn cmd_auth(config_path: Option<std::path::PathBuf>, auth_cmd: AuthCommand) -> anyhow::Result<()> {
     // We need the instance_dir for credential storage. Try loading config,
     // but fall back to the default instance dir if config doesn't exist yet
@@ -606,10 +744,12 @@ async fn run(
     tracing::info!("starting spacebot");
     tracing::info!(instance_dir = %config.instance_dir.display(), "configuration loaded");
 
-    // Start the IPC server for stop/status commands
-    let (mut shutdown_rx, _ipc_handle) = spacebot::daemon::start_ipc_server(&paths)
-        .await
-        .context("failed to start IPC server")?;
+    // Start the IPC server for stop/status/maintenance commands
+    let maintenance = spacebot::maintenance::MaintenanceStore::load(&config.instance_dir);
+    let (mut shutdown_rx, _ipc_handle) =
+        spacebot::daemon::start_ipc_server(&paths, maintenance.clone())
+            .await
+            .context("failed to start IPC server")?;
 
     // Create the provider setup channel so API handlers can signal the main loop
     let (provider_tx, mut provider_rx) = mpsc::channel::<spacebot::ProviderSetupEvent>(1);
@@ -627,6 +767,10 @@ async fn run(
     // Start background update checker
     spacebot::update::spawn_update_checker(api_state.update_status.clone());
 
+    spacebot::perf::PerfRecorder::global().set_enabled(config.metrics.perf_instrumentation);
+    spacebot::http::set_default_proxy(config.network.proxy.clone());
+    spacebot::http::set_resolver_options(&config.network);
+
     // Start metrics server if enabled (requires `metrics` cargo feature)
     #[cfg(feature = "metrics")]
     let _metrics_handle = if config.metrics.enabled {
@@ -662,6 +806,42 @@ async fn run(
         None
     };
 
+    // Boot self-test. Non-critical failures are logged and reported through
+    // the diagnostics endpoint; critical ones put the instance into safe
+    // mode, keeping the management API up instead of crash-looping.
+    let self_test = spacebot::selftest::run_self_test(&config).await;
+    for failure in self_test.failures() {
+        tracing::warn!(
+            check = %failure.name,
+            critical = failure.critical,
+            detail = %failure.detail,
+            "self-test check failed"
+        );
+    }
+    let safe_mode = self_test.has_critical_failures();
+    api_state.set_self_test(self_test).await;
+
+    if safe_mode {
+        api_state
+            .safe_mode
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        tracing::error!(
+            "critical self-test failure — entering safe mode (management API only)"
+        );
+        if foreground {
+            eprintln!("Critical self-test failure. Running in safe mode.");
+            eprintln!(
+                "Diagnostics: http://{}:{}/api/selftest",
+                config.api.bind, config.api.port
+            );
+        }
+
+        // Serve only the management API until the operator stops the daemon
+        shutdown_rx.wait_for(|shutdown| *shutdown).await.ok();
+        spacebot::daemon::cleanup(&paths);
+        std::process::exit(1);
+    }
+
     // Check if we have provider configuration (API keys or OAuth credentials)
     let has_providers = has_provider_credentials(&config.llm, &config.instance_dir);
 
@@ -714,6 +894,17 @@ async fn run(
 
     // These hold the initialized subsystems. Empty until agents are initialized.
     let mut agents: HashMap<spacebot::AgentId, spacebot::Agent> = HashMap::new();
+    let keyword_watches = spacebot::watch::KeywordWatchStore::load(&config.instance_dir);
+    let binding_pins = spacebot::binding_pins::BindingPinStore::load(&config.instance_dir);
+    api_state.set_binding_pins(binding_pins.clone()).await;
+    api_state.set_maintenance(maintenance.clone()).await;
+    let quarantine = spacebot::quarantine::QuarantineStore::load(&config.instance_dir);
+    spacebot::quarantine::set_global(quarantine.clone());
+    api_state.set_quarantine(quarantine).await;
+    let bot_loop_guard = spacebot::bot_loop::BotLoopGuard::new();
+    let link_safety = spacebot::link_safety::LinkSafetyChecker::new();
+    spacebot::link_safety::set_global(link_safety.clone());
+
     let mut messaging_manager: Arc<spacebot::messaging::MessagingManager> =
         Arc::new(spacebot::messaging::MessagingManager::new());
     // Use an Option to represent "no inbound stream yet" (setup mode)
@@ -726,6 +917,10 @@ async fn run(
     let bindings: Arc<ArcSwap<Vec<spacebot::config::Binding>>> =
         Arc::new(ArcSwap::from_pointee(config.bindings.clone()));
     api_state.set_bindings(bindings.clone()).await;
+    let feature_flags: Arc<ArcSwap<spacebot::flags::FeatureFlags>> = Arc::new(
+        ArcSwap::from_pointee(spacebot::flags::FeatureFlags::load(&config.instance_dir)),
+    );
+    api_state.set_feature_flags(feature_flags.clone()).await;
     let default_agent_id = config.default_agent_id().to_string();
 
     // Set the config path on the API state for config.toml writes
@@ -818,6 +1013,11 @@ async fn run(
     // Active conversation channels: conversation_id -> ActiveChannel
     let mut active_channels: HashMap<String, ActiveChannel> = HashMap::new();
 
+    // Sticky agent per conversation so threads don't switch personas when
+    // bindings or selection order change mid-conversation.
+    const STICKY_AGENTS_CAPACITY: usize = 4096;
+    let mut conversation_agents: HashMap<String, spacebot::AgentId> = HashMap::new();
+
     // Main event loop: route inbound messages to agent channels
     loop {
         // Poll the inbound stream if it exists, otherwise yield a never-resolving future
@@ -829,8 +1029,46 @@ async fn run(
         };
         tokio::select! {
             Some(mut message) = inbound_next, if agents_initialized => {
+                let perf = spacebot::perf::PerfRecorder::global();
+                // Inbound lag: platform event timestamp to dispatch receipt,
+                // covering adapter parsing, transport, and queueing
+                if let Ok(lag) = (chrono::Utc::now() - message.timestamp).to_std() {
+                    perf.record(spacebot::perf::PerfStage::AdapterParse, lag);
+                }
+                let resolve_started = std::time::Instant::now();
+
+                // Explicit handoff re-pins the thread and retires the old channel
+                let handoff_target = spacebot::config::parse_handoff_command(
+                    &message.content.to_string(),
+                )
+                .map(str::to_string);
+                if let Some(target) = &handoff_target {
+                    let key: spacebot::AgentId = Arc::from(target.as_str());
+                    if agents.contains_key(&key) {
+                        conversation_agents.insert(message.conversation_id.clone(), key.clone());
+                        active_channels.remove(&message.conversation_id);
+                        message.agent_id = Some(key);
+                        tracing::info!(
+                            conversation_id = %message.conversation_id,
+                            target = %target,
+                            "conversation handed off to agent"
+                        );
+                    } else {
+                        tracing::warn!(
+                            conversation_id = %message.conversation_id,
+                            target = %target,
+                            "handoff to unknown agent ignored"
+                        );
+                    }
+                }
+
                 let agent_id = if let Some(existing) = message.agent_id.as_ref() {
                     existing.clone()
+                } else if let Some(pinned) = conversation_agents.get(&message.conversation_id) {
+                    // Keep the thread with the agent that first answered it
+                    let pinned = pinned.clone();
+                    message.agent_id = Some(pinned.clone());
+                    pinned
                 } else {
                     let current_bindings = bindings.load();
                     let resolved = spacebot::config::resolve_agent_for_message(
@@ -838,12 +1076,229 @@ async fn run(
                         &message,
                         &default_agent_id,
                     );
+                    if conversation_agents.len() >= STICKY_AGENTS_CAPACITY {
+                        // Under pressure, keep only pins with live channels
+                        conversation_agents.retain(|id, _| active_channels.contains_key(id));
+                    }
+                    conversation_agents
+                        .insert(message.conversation_id.clone(), resolved.clone());
                     message.agent_id = Some(resolved.clone());
                     resolved
                 };
 
+                perf.record(
+                    spacebot::perf::PerfStage::BindingResolution,
+                    resolve_started.elapsed(),
+                );
+
                 let conversation_id = message.conversation_id.clone();
 
+                // During a maintenance window every conversation is told
+                // once and the message is dropped; queues and channel state
+                // stay intact for a clean resume
+                if maintenance.active().await {
+                    if maintenance.should_notify(&conversation_id).await {
+                        let reply = maintenance.auto_reply().await;
+                        if let Err(error) = messaging_manager
+                            .respond(&message, spacebot::OutboundResponse::Text(reply))
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to send maintenance auto-reply");
+                        }
+                    }
+                    continue;
+                }
+
+                // Drop bot-authored messages that look like feedback loops
+                // before they reach the model
+                {
+                    let current_bindings = bindings.load();
+                    let protected =
+                        spacebot::config::matching_binding(&current_bindings, &message)
+                            .map(|binding| binding.bot_loop_protection)
+                            .unwrap_or(true);
+                    if protected && let Some(reason) = bot_loop_guard.check(&message) {
+                        tracing::info!(
+                            conversation_id = %conversation_id,
+                            sender_id = %message.sender_id,
+                            reason,
+                            "suppressing response to bot-authored message"
+                        );
+                        continue;
+                    }
+                }
+
+                // Warn the conversation when a message carries links flagged
+                // by a reputation service; the browser tool separately
+                // refuses to open them
+                {
+                    let current_bindings = bindings.load();
+                    let check_links =
+                        spacebot::config::matching_binding(&current_bindings, &message)
+                            .map(|binding| binding.link_safety)
+                            .unwrap_or(true);
+                    if check_links
+                        && let spacebot::MessageContent::Text(text) = &message.content
+                        && !spacebot::link_safety::extract_urls(text).is_empty()
+                    {
+                        let verdicts = link_safety.check_message(text).await;
+                        if !verdicts.is_empty() {
+                            tracing::warn!(
+                                conversation_id = %conversation_id,
+                                flagged = verdicts.len(),
+                                "inbound message carries flagged links"
+                            );
+                            let warning = spacebot::link_safety::format_link_warning(&verdicts);
+                            if let Err(error) = messaging_manager
+                                .respond(&message, spacebot::OutboundResponse::Text(warning))
+                                .await
+                            {
+                                tracing::warn!(%error, "failed to post link safety warning");
+                            }
+                        }
+                    }
+                }
+
+                // Keyword watch commands are answered directly without
+                // invoking the model
+                if let Some(reply) = keyword_watches.handle_command(&message).await {
+                    if let Err(error) = messaging_manager
+                        .respond(&message, spacebot::OutboundResponse::Text(reply))
+                        .await
+                    {
+                        tracing::warn!(%error, "failed to reply to watch command");
+                    }
+                    continue;
+                }
+
+                // Binding pin commands are answered directly without
+                // invoking the model
+                {
+                    let current_bindings = bindings.load();
+                    let binding_key = spacebot::config::matching_binding(&current_bindings, &message)
+                        .map(|binding| {
+                            format!("{}/{}", binding.runtime_adapter_key(), binding.agent_id)
+                        });
+                    if let Some(reply) = binding_pins
+                        .handle_command(&message, binding_key.as_deref())
+                        .await
+                    {
+                        if let Err(error) = messaging_manager
+                            .respond(&message, spacebot::OutboundResponse::Text(reply))
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to reply to pin command");
+                        }
+                        continue;
+                    }
+                }
+
+                // DM subscribers whose watched keywords this message mentions
+                let watch_hits = keyword_watches.matches(&message).await;
+                if !watch_hits.is_empty() {
+                    let messaging = messaging_manager.clone();
+                    let watched_message = message.clone();
+                    tokio::spawn(async move {
+                        for watch in watch_hits {
+                            let notice =
+                                spacebot::watch::format_watch_notice(&watch, &watched_message);
+                            if let Err(error) = messaging
+                                .broadcast(
+                                    &watch.adapter,
+                                    &watch.user_id,
+                                    spacebot::OutboundResponse::Text(notice),
+                                )
+                                .await
+                            {
+                                tracing::warn!(
+                                    %error,
+                                    adapter = %watch.adapter,
+                                    user = %watch.user_id,
+                                    "failed to deliver keyword watch notification"
+                                );
+                            }
+                        }
+                    });
+                }
+
+                // Duplicate-question check: questions that closely match a
+                // curated FAQ entry are answered from the store (with a link
+                // to the original thread) without invoking the model
+                if handoff_target.is_none()
+                    && let Some(agent) = agents.get(&agent_id)
+                {
+                    let text = message.content.to_string();
+                    let faq = spacebot::memory::FaqStore::new(agent.deps.sqlite_pool.clone());
+                    let has_entries = !faq.is_empty().await.unwrap_or(true);
+                    if has_entries && !text.trim().is_empty() {
+                        let matched = match spacebot::memory::embedding::embed_text(
+                            &embedding_model,
+                            &text,
+                        )
+                        .await
+                        {
+                            Ok(embedding) => faq.best_match(&embedding).await.unwrap_or_else(|error| {
+                                tracing::warn!(%error, "FAQ lookup failed");
+                                None
+                            }),
+                            Err(error) => {
+                                tracing::warn!(%error, "failed to embed message for FAQ lookup");
+                                None
+                            }
+                        };
+
+                        if let Some(matched) = matched {
+                            tracing::info!(
+                                conversation_id = %conversation_id,
+                                faq_id = %matched.entry.id,
+                                similarity = matched.similarity,
+                                "answering from FAQ without invoking the model"
+                            );
+                            faq.record_hit(&matched.entry.id).await.ok();
+                            let reply = spacebot::memory::faq::format_faq_answer(&matched.entry);
+                            if let Err(error) = messaging_manager
+                                .respond(&message, spacebot::OutboundResponse::Text(reply))
+                                .await
+                            {
+                                tracing::warn!(%error, "failed to send FAQ answer");
+                            }
+                            continue;
+                        }
+                    }
+                }
+
+                // First-contact onboarding for bindings that configure it
+                {
+                    let current_bindings = bindings.load();
+                    if let Some(binding) =
+                        spacebot::config::matching_binding(&current_bindings, &message)
+                        && !binding.onboarding.is_empty()
+                        && let Some(agent) = agents.get(&agent_id)
+                    {
+                        let store = spacebot::onboarding::OnboardingStore::new(
+                            agent.deps.sqlite_pool.clone(),
+                        );
+                        let messaging = messaging_manager.clone();
+                        let onboarding_message = message.clone();
+                        let binding_key =
+                            format!("{}/{}", binding.runtime_adapter_key(), binding.agent_id);
+                        let steps = binding.onboarding.clone();
+                        tokio::spawn(async move {
+                            if let Err(error) = spacebot::onboarding::maybe_onboard(
+                                &store,
+                                &messaging,
+                                &onboarding_message,
+                                &binding_key,
+                                &steps,
+                            )
+                            .await
+                            {
+                                tracing::warn!(%error, "onboarding flow failed");
+                            }
+                        });
+                    }
+                }
+
                 // Find or create a channel for this conversation
                 if !active_channels.contains_key(&conversation_id) {
                     let Some(agent) = agents.get(&agent_id) else {
@@ -884,6 +1339,27 @@ async fn run(
                         channel.state.clone(),
                     ).await;
 
+                    // Apply the binding's data-residency restriction and
+                    // attach its pinned knowledge, if any
+                    {
+                        let current_bindings = bindings.load();
+                        if let Some(binding) =
+                            spacebot::config::matching_binding(&current_bindings, &message)
+                        {
+                            if !binding.allowed_regions.is_empty() {
+                                *channel.state.allowed_regions.write().await =
+                                    binding.allowed_regions.clone();
+                            }
+                            let binding_key =
+                                format!("{}/{}", binding.runtime_adapter_key(), binding.agent_id);
+                            *channel.state.binding_pins.write().await =
+                                Some(spacebot::binding_pins::ChannelBindingPins::new(
+                                    binding_pins.clone(),
+                                    binding_key,
+                                ));
+                        }
+                    }
+
                     // Backfill recent message history from the platform
                     let backfill_count = agent.config.history_backfill_count();
                     if backfill_count > 0 {
@@ -991,12 +1467,17 @@ async fn run(
                                         conversation_id = %outbound_conversation_id,
                                         "routing outbound response to messaging adapter"
                                     );
+                                    let send_started = std::time::Instant::now();
                                     if let Err(error) = messaging_for_outbound
                                         .respond(&current_message, response)
                                         .await
                                     {
                                         tracing::error!(%error, "failed to send outbound response");
                                     }
+                                    spacebot::perf::PerfRecorder::global().record(
+                                        spacebot::perf::PerfStage::Send,
+                                        send_started.elapsed(),
+                                    );
                                 }
                             }
                         }
@@ -1517,18 +1998,42 @@ async fn initialize_agents(
         api_state.set_discord_permissions(perms.clone()).await;
     }
 
+    #[cfg(not(feature = "adapter-discord"))]
+    if config.messaging.discord.as_ref().is_some_and(|c| c.enabled) {
+        tracing::warn!("discord is configured but this binary was built without the adapter-discord feature");
+    }
+    #[cfg(feature = "adapter-discord")]
     if let Some(discord_config) = &config.messaging.discord
         && discord_config.enabled
     {
         if !discord_config.token.is_empty() {
+            let perms = discord_permissions.clone().ok_or_else(|| {
+                anyhow::anyhow!("discord permissions not initialized when discord is enabled")
+            })?;
             let adapter = spacebot::messaging::discord::DiscordAdapter::new(
                 "discord",
                 &discord_config.token,
-                discord_permissions.clone().ok_or_else(|| {
-                    anyhow::anyhow!("discord permissions not initialized when discord is enabled")
-                })?,
+                perms.clone(),
+                discord_config.proxy.clone(),
             );
+            #[cfg(feature = "discord-voice")]
+            let adapter = match &discord_config.voice {
+                Some(voice_config) if voice_config.enabled => adapter.with_voice(
+                    spacebot::messaging::discord_voice::DiscordVoicePresence::new(
+                        voice_config.clone(),
+                    ),
+                ),
+                _ => adapter,
+            };
             new_messaging_manager.register(adapter).await;
+            if let Some(interval_secs) = discord_config.permission_sync_interval_secs {
+                spacebot::messaging::discord::spawn_permission_sync(
+                    "discord",
+                    &discord_config.token,
+                    perms,
+                    interval_secs,
+                );
+            }
         }
 
         for instance in discord_config
@@ -1551,11 +2056,20 @@ async fn initialize_agents(
                 ),
             ));
             let adapter = spacebot::messaging::discord::DiscordAdapter::new(
-                runtime_key,
+                runtime_key.clone(),
                 &instance.token,
-                perms,
+                perms.clone(),
+                discord_config.proxy.clone(),
             );
             new_messaging_manager.register(adapter).await;
+            if let Some(interval_secs) = instance.permission_sync_interval_secs {
+                spacebot::messaging::discord::spawn_permission_sync(
+                    runtime_key,
+                    &instance.token,
+                    perms,
+                    interval_secs,
+                );
+            }
         }
     }
 
@@ -1568,6 +2082,11 @@ async fn initialize_agents(
         api_state.set_slack_permissions(perms.clone()).await;
     }
 
+    #[cfg(not(feature = "adapter-slack"))]
+    if config.messaging.slack.as_ref().is_some_and(|c| c.enabled) {
+        tracing::warn!("slack is configured but this binary was built without the adapter-slack feature");
+    }
+    #[cfg(feature = "adapter-slack")]
     if let Some(slack_config) = &config.messaging.slack
         && slack_config.enabled
     {
@@ -1633,9 +2152,22 @@ async fn initialize_agents(
         Arc::new(ArcSwap::from_pointee(perms))
     });
 
+    #[cfg(not(feature = "adapter-telegram"))]
+    if config.messaging.telegram.as_ref().is_some_and(|c| c.enabled) {
+        tracing::warn!("telegram is configured but this binary was built without the adapter-telegram feature");
+    }
+    #[cfg(feature = "adapter-telegram")]
     if let Some(telegram_config) = &config.messaging.telegram
         && telegram_config.enabled
     {
+        let webhook_mode = telegram_config.mode == spacebot::config::TelegramMode::Webhook
+            && !telegram_config.webhook_url.is_empty();
+        if telegram_config.mode == spacebot::config::TelegramMode::Webhook && !webhook_mode {
+            tracing::warn!(
+                "telegram webhook mode requires webhook_url, falling back to long polling"
+            );
+        }
+
         if !telegram_config.token.is_empty() {
             let adapter = spacebot::messaging::telegram::TelegramAdapter::new(
                 "telegram",
@@ -1643,8 +2175,26 @@ async fn initialize_agents(
                 telegram_permissions.clone().ok_or_else(|| {
                     anyhow::anyhow!("telegram permissions not initialized when telegram is enabled")
                 })?,
+                Some(config.instance_dir.join("config.toml")),
+                telegram_config.proxy.clone(),
             );
-            new_messaging_manager.register(adapter).await;
+            let adapter = match &telegram_config.stt {
+                Some(stt) if stt.enabled => adapter.with_stt(stt.clone()),
+                _ => adapter,
+            };
+            let adapter = adapter.with_commands(telegram_config.commands.clone());
+            if webhook_mode {
+                let adapter = Arc::new(adapter.with_webhook(
+                    telegram_config.webhook_url.clone(),
+                    telegram_config.webhook_secret.clone(),
+                ));
+                api_state
+                    .insert_telegram_webhook_adapter("telegram", adapter.clone())
+                    .await;
+                new_messaging_manager.register_shared(adapter).await;
+            } else {
+                new_messaging_manager.register(adapter).await;
+            }
         }
 
         for instance in telegram_config
@@ -1667,24 +2217,71 @@ async fn initialize_agents(
                 ),
             ));
             let adapter = spacebot::messaging::telegram::TelegramAdapter::new(
-                runtime_key,
+                runtime_key.clone(),
                 &instance.token,
                 perms,
+                Some(config.instance_dir.join("config.toml")),
+                telegram_config.proxy.clone(),
             );
-            new_messaging_manager.register(adapter).await;
+            let adapter = match &telegram_config.stt {
+                Some(stt) if stt.enabled => adapter.with_stt(stt.clone()),
+                _ => adapter,
+            };
+            let adapter = adapter.with_commands(telegram_config.commands.clone());
+            if webhook_mode {
+                let adapter = Arc::new(adapter.with_webhook(
+                    telegram_config.webhook_url.clone(),
+                    telegram_config.webhook_secret.clone(),
+                ));
+                api_state
+                    .insert_telegram_webhook_adapter(&runtime_key, adapter.clone())
+                    .await;
+                new_messaging_manager.register_shared(adapter).await;
+            } else {
+                new_messaging_manager.register(adapter).await;
+            }
         }
     }
 
+    #[cfg(not(feature = "adapter-email"))]
+    if config.messaging.email.as_ref().is_some_and(|c| c.enabled) {
+        tracing::warn!("email is configured but this binary was built without the adapter-email feature");
+    }
+    #[cfg(feature = "adapter-email")]
     if let Some(email_config) = &config.messaging.email
         && email_config.enabled
     {
-        if !email_config.imap_host.is_empty() {
-            match spacebot::messaging::email::EmailAdapter::from_config(email_config) {
-                Ok(adapter) => {
-                    new_messaging_manager.register(adapter).await;
+        match email_config.backend {
+            spacebot::config::EmailBackend::Graph => {
+                match spacebot::messaging::graphmail::GraphEmailAdapter::from_config(email_config) {
+                    Ok(adapter) => {
+                        new_messaging_manager.register(adapter).await;
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "failed to build graph email adapter");
+                    }
                 }
-                Err(error) => {
-                    tracing::error!(%error, "failed to build email adapter");
+            }
+            spacebot::config::EmailBackend::Jmap => {
+                match spacebot::messaging::jmap::JmapEmailAdapter::from_config(email_config) {
+                    Ok(adapter) => {
+                        new_messaging_manager.register(adapter).await;
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "failed to build jmap email adapter");
+                    }
+                }
+            }
+            spacebot::config::EmailBackend::Imap => {
+                if !email_config.imap_host.is_empty() {
+                    match spacebot::messaging::email::EmailAdapter::from_config(email_config) {
+                        Ok(adapter) => {
+                            new_messaging_manager.register(adapter).await;
+                        }
+                        Err(error) => {
+                            tracing::error!(%error, "failed to build email adapter");
+                        }
+                    }
                 }
             }
         }
@@ -1694,14 +2291,42 @@ async fn initialize_agents(
             .iter()
             .filter(|instance| instance.enabled)
         {
-            if instance.imap_host.is_empty() {
-                tracing::warn!(adapter = %instance.name, "skipping enabled email instance with empty credentials");
-                continue;
-            }
             let runtime_key = spacebot::config::binding_runtime_adapter_key(
                 "email",
                 Some(instance.name.as_str()),
             );
+            if instance.backend == spacebot::config::EmailBackend::Graph {
+                match spacebot::messaging::graphmail::GraphEmailAdapter::from_instance_config(
+                    runtime_key,
+                    instance,
+                ) {
+                    Ok(adapter) => {
+                        new_messaging_manager.register(adapter).await;
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, adapter = %instance.name, "failed to build named graph email adapter");
+                    }
+                }
+                continue;
+            }
+            if instance.backend == spacebot::config::EmailBackend::Jmap {
+                match spacebot::messaging::jmap::JmapEmailAdapter::from_instance_config(
+                    runtime_key,
+                    instance,
+                ) {
+                    Ok(adapter) => {
+                        new_messaging_manager.register(adapter).await;
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, adapter = %instance.name, "failed to build named jmap email adapter");
+                    }
+                }
+                continue;
+            }
+            if instance.imap_host.is_empty() {
+                tracing::warn!(adapter = %instance.name, "skipping enabled email instance with empty credentials");
+                continue;
+            }
             match spacebot::messaging::email::EmailAdapter::from_instance_config(
                 runtime_key,
                 instance,
@@ -1716,6 +2341,421 @@ async fn initialize_agents(
         }
     }
 
+    if let Some(bluesky_config) = &config.messaging.bluesky
+        && bluesky_config.enabled
+    {
+        for instance in bluesky_config
+            .instances
+            .iter()
+            .filter(|instance| instance.enabled)
+        {
+            let runtime_key = spacebot::config::binding_runtime_adapter_key(
+                "bluesky",
+                Some(instance.name.as_str()),
+            );
+            let adapter = spacebot::messaging::bluesky::BlueskyAdapter::new(
+                runtime_key,
+                &instance.service,
+                &instance.handle,
+                &instance.app_password,
+            );
+            new_messaging_manager.register(adapter).await;
+        }
+    }
+
+    if let Some(googlechat_config) = &config.messaging.googlechat
+        && googlechat_config.enabled
+        && !googlechat_config.credentials_path.is_empty()
+    {
+        match spacebot::messaging::googlechat::GoogleChatAdapter::from_key_file(
+            "googlechat",
+            &googlechat_config.credentials_path,
+            &googlechat_config.audience,
+            googlechat_config.port,
+            &googlechat_config.bind,
+            googlechat_config.spaces.clone(),
+            googlechat_config.users.clone(),
+        ) {
+            Ok(adapter) => new_messaging_manager.register(adapter).await,
+            Err(error) => {
+                tracing::error!(%error, "failed to load Google Chat credentials, adapter disabled");
+            }
+        }
+    }
+
+    if let Some(mastodon_config) = &config.messaging.mastodon
+        && mastodon_config.enabled
+        && !mastodon_config.url.is_empty()
+        && !mastodon_config.access_token.is_empty()
+    {
+        let adapter = spacebot::messaging::mastodon::MastodonAdapter::new(
+            "mastodon",
+            &mastodon_config.url,
+            &mastodon_config.access_token,
+            &mastodon_config.reply_visibility,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(mattermost_config) = &config.messaging.mattermost
+        && mattermost_config.enabled
+        && !mattermost_config.url.is_empty()
+        && !mattermost_config.token.is_empty()
+    {
+        let adapter = spacebot::messaging::mattermost::MattermostAdapter::new(
+            "mattermost",
+            &mattermost_config.url,
+            &mattermost_config.token,
+            mattermost_config.channels.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(nostr_config) = &config.messaging.nostr
+        && nostr_config.enabled
+        && !nostr_config.secret_key.is_empty()
+        && !nostr_config.relays.is_empty()
+    {
+        match spacebot::messaging::nostr::NostrAdapter::new(
+            "nostr",
+            &nostr_config.secret_key,
+            nostr_config.relays.clone(),
+        ) {
+            Ok(adapter) => {
+                tracing::info!(pubkey = %adapter.pubkey_hex(), "nostr identity loaded");
+                new_messaging_manager.register(adapter).await;
+            }
+            Err(error) => {
+                tracing::error!(%error, "failed to initialize nostr adapter — skipping");
+            }
+        }
+    }
+
+    if let Some(xmpp_config) = &config.messaging.xmpp
+        && xmpp_config.enabled
+        && !xmpp_config.jid.is_empty()
+    {
+        match spacebot::messaging::xmpp::XmppAdapter::new(
+            "xmpp",
+            &xmpp_config.jid,
+            &xmpp_config.password,
+            xmpp_config.server.clone(),
+            xmpp_config.port,
+            xmpp_config.nick.clone(),
+            xmpp_config.rooms.clone(),
+        ) {
+            Ok(adapter) => {
+                new_messaging_manager.register(adapter).await;
+            }
+            Err(error) => {
+                tracing::error!(%error, "failed to initialize XMPP adapter — skipping");
+            }
+        }
+    }
+
+    if let Some(line_config) = &config.messaging.line
+        && line_config.enabled
+        && !line_config.channel_secret.is_empty()
+        && !line_config.channel_access_token.is_empty()
+    {
+        let adapter = spacebot::messaging::line::LineAdapter::new(
+            "line",
+            &line_config.channel_secret,
+            &line_config.channel_access_token,
+            line_config.port,
+            &line_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(reddit_config) = &config.messaging.reddit
+        && reddit_config.enabled
+        && !reddit_config.client_id.is_empty()
+        && !reddit_config.username.is_empty()
+    {
+        let adapter = spacebot::messaging::reddit::RedditAdapter::new(
+            "reddit",
+            &reddit_config.client_id,
+            &reddit_config.client_secret,
+            &reddit_config.username,
+            &reddit_config.password,
+            reddit_config.subreddits.clone(),
+            reddit_config.poll_interval_secs,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(github_config) = &config.messaging.github
+        && github_config.enabled
+        && !github_config.token.is_empty()
+    {
+        let adapter = spacebot::messaging::github::GithubAdapter::new(
+            "github",
+            &github_config.token,
+            github_config.webhook_secret.clone(),
+            github_config.port,
+            &github_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(gitlab_config) = &config.messaging.gitlab
+        && gitlab_config.enabled
+        && !gitlab_config.token.is_empty()
+    {
+        let adapter = spacebot::messaging::gitlab::GitlabAdapter::new(
+            "gitlab",
+            &gitlab_config.base_url,
+            &gitlab_config.token,
+            gitlab_config.webhook_token.clone(),
+            gitlab_config.port,
+            &gitlab_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(jira_config) = &config.messaging.jira
+        && jira_config.enabled
+        && !jira_config.api_token.is_empty()
+    {
+        let adapter = spacebot::messaging::jira::JiraAdapter::new(
+            "jira",
+            &jira_config.base_url,
+            &jira_config.email,
+            &jira_config.api_token,
+            jira_config.webhook_token.clone(),
+            jira_config.port,
+            &jira_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(linear_config) = &config.messaging.linear
+        && linear_config.enabled
+    {
+        if !linear_config.api_key.is_empty() {
+            let adapter = spacebot::messaging::linear::LinearAdapter::new(
+                "linear",
+                &linear_config.api_key,
+                linear_config.webhook_secret.clone(),
+                linear_config.port,
+                &linear_config.bind,
+            );
+            new_messaging_manager.register(adapter).await;
+        }
+
+        for instance in linear_config
+            .instances
+            .iter()
+            .filter(|instance| instance.enabled)
+        {
+            if instance.api_key.is_empty() {
+                tracing::warn!(adapter = %instance.name, "skipping enabled linear instance with empty API key");
+                continue;
+            }
+            let runtime_key = spacebot::config::binding_runtime_adapter_key(
+                "linear",
+                Some(instance.name.as_str()),
+            );
+            let adapter = spacebot::messaging::linear::LinearAdapter::new(
+                runtime_key,
+                &instance.api_key,
+                instance.webhook_secret.clone(),
+                instance.port,
+                &instance.bind,
+            );
+            new_messaging_manager.register(adapter).await;
+        }
+    }
+
+    if let Some(nextcloud_config) = &config.messaging.nextcloud
+        && nextcloud_config.enabled
+        && !nextcloud_config.app_password.is_empty()
+    {
+        let adapter = spacebot::messaging::nextcloud::NextcloudAdapter::new(
+            "nextcloud",
+            &nextcloud_config.base_url,
+            &nextcloud_config.username,
+            &nextcloud_config.app_password,
+            nextcloud_config.rooms.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(rss_config) = &config.messaging.rss
+        && rss_config.enabled
+        && !rss_config.feeds.is_empty()
+    {
+        let feeds = rss_config
+            .feeds
+            .iter()
+            .map(|feed| spacebot::messaging::rss::RssFeed {
+                name: feed.name.clone(),
+                url: feed.url.clone(),
+            })
+            .collect();
+        let adapter = spacebot::messaging::rss::RssAdapter::new(
+            "rss",
+            feeds,
+            rss_config.poll_interval_secs,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(mqtt_config) = &config.messaging.mqtt
+        && mqtt_config.enabled
+        && !mqtt_config.topics.is_empty()
+    {
+        let adapter = spacebot::messaging::mqtt::MqttAdapter::new(
+            "mqtt",
+            &mqtt_config.host,
+            mqtt_config.port,
+            &mqtt_config.client_id,
+            mqtt_config.username.clone(),
+            mqtt_config.password.clone(),
+            mqtt_config.topics.clone(),
+            mqtt_config.response_topic.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(websocket_config) = &config.messaging.websocket
+        && websocket_config.enabled
+    {
+        let adapter = spacebot::messaging::websocket::WebsocketAdapter::new(
+            websocket_config.port,
+            &websocket_config.bind,
+            websocket_config.auth_token.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(console_config) = &config.messaging.console
+        && console_config.enabled
+    {
+        let adapter = spacebot::messaging::console::ConsoleAdapter::new();
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(voice_config) = &config.messaging.voice
+        && voice_config.enabled
+    {
+        let adapter = spacebot::messaging::voice::VoiceAdapter::new(
+            "voice",
+            &voice_config.auth_token,
+            voice_config.port,
+            &voice_config.bind,
+            &voice_config.greeting,
+            &voice_config.voice,
+            &voice_config.language,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(steam_config) = &config.messaging.steam
+        && steam_config.enabled
+        && !steam_config.access_token.is_empty()
+    {
+        let adapter = spacebot::messaging::steam::SteamAdapter::new(
+            steam_config.access_token.clone(),
+            steam_config.allowed_friends.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(viber_config) = &config.messaging.viber
+        && viber_config.enabled
+        && !viber_config.auth_token.is_empty()
+    {
+        let adapter = spacebot::messaging::viber::ViberAdapter::new(
+            "viber",
+            &viber_config.auth_token,
+            &viber_config.sender_name,
+            viber_config.port,
+            &viber_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(rocketchat_config) = &config.messaging.rocketchat
+        && rocketchat_config.enabled
+        && !rocketchat_config.url.is_empty()
+        && !rocketchat_config.username.is_empty()
+    {
+        let adapter = spacebot::messaging::rocketchat::RocketChatAdapter::new(
+            "rocketchat",
+            &rocketchat_config.url,
+            &rocketchat_config.username,
+            &rocketchat_config.password,
+            rocketchat_config.channels.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(signal_config) = &config.messaging.signal
+        && signal_config.enabled
+        && !signal_config.account.is_empty()
+    {
+        let adapter = spacebot::messaging::signal::SignalAdapter::new(
+            "signal",
+            &signal_config.account,
+            &signal_config.daemon_addr,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(sms_config) = &config.messaging.sms
+        && sms_config.enabled
+        && !sms_config.numbers.is_empty()
+    {
+        let provider = spacebot::messaging::sms::TwilioProvider::new(
+            &sms_config.account_sid,
+            &sms_config.auth_token,
+        );
+        let adapter = spacebot::messaging::sms::SmsAdapter::new(
+            "sms",
+            provider,
+            sms_config.numbers.clone(),
+            &sms_config.auth_token,
+            sms_config.port,
+            &sms_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(teams_config) = &config.messaging.teams
+        && teams_config.enabled
+        && !teams_config.app_id.is_empty()
+        && !teams_config.app_password.is_empty()
+    {
+        let adapter = spacebot::messaging::teams::TeamsAdapter::new(
+            "teams",
+            &teams_config.app_id,
+            &teams_config.app_password,
+            &teams_config.tenant_id,
+            teams_config.port,
+            &teams_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(whatsapp_config) = &config.messaging.whatsapp
+        && whatsapp_config.enabled
+        && !whatsapp_config.access_token.is_empty()
+        && !whatsapp_config.phone_number_id.is_empty()
+    {
+        let adapter = spacebot::messaging::whatsapp::WhatsAppAdapter::new(
+            "whatsapp",
+            &whatsapp_config.access_token,
+            &whatsapp_config.phone_number_id,
+            &whatsapp_config.verify_token,
+            &whatsapp_config.app_secret,
+            whatsapp_config.port,
+            &whatsapp_config.bind,
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
     if let Some(webhook_config) = &config.messaging.webhook
         && webhook_config.enabled
     {
@@ -1727,6 +2767,61 @@ async fn initialize_agents(
         new_messaging_manager.register(adapter).await;
     }
 
+    if let Some(zulip_config) = &config.messaging.zulip
+        && zulip_config.enabled
+        && !zulip_config.url.is_empty()
+        && !zulip_config.api_key.is_empty()
+    {
+        let adapter = spacebot::messaging::zulip::ZulipAdapter::new(
+            "zulip",
+            &zulip_config.url,
+            &zulip_config.email,
+            &zulip_config.api_key,
+            zulip_config.streams.clone(),
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(ntfy_config) = &config.messaging.ntfy
+        && ntfy_config.enabled
+    {
+        let adapter = spacebot::messaging::notify::NotifyAdapter::new(
+            "ntfy",
+            spacebot::messaging::notify::NotifyService::Ntfy {
+                server: ntfy_config.server.clone(),
+                topic: ntfy_config.topic.clone(),
+                token: ntfy_config.token.clone(),
+            },
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(pushover_config) = &config.messaging.pushover
+        && pushover_config.enabled
+    {
+        let adapter = spacebot::messaging::notify::NotifyAdapter::new(
+            "pushover",
+            spacebot::messaging::notify::NotifyService::Pushover {
+                token: pushover_config.token.clone(),
+                user: pushover_config.user.clone(),
+            },
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
+    if let Some(gotify_config) = &config.messaging.gotify
+        && gotify_config.enabled
+    {
+        let adapter = spacebot::messaging::notify::NotifyAdapter::new(
+            "gotify",
+            spacebot::messaging::notify::NotifyService::Gotify {
+                server: gotify_config.server.clone(),
+                token: gotify_config.token.clone(),
+            },
+        );
+        new_messaging_manager.register(adapter).await;
+    }
+
     // Shared Twitch permissions (hot-reloadable via file watcher)
     *twitch_permissions = config.messaging.twitch.as_ref().map(|twitch_config| {
         let perms =
@@ -1812,6 +2907,12 @@ async fn initialize_agents(
         .await;
     api_state.set_webchat_adapter(webchat_adapter);
 
+    let hooks_adapter = Arc::new(spacebot::messaging::hooks::HooksAdapter::new());
+    new_messaging_manager
+        .register_shared(hooks_adapter.clone())
+        .await;
+    api_state.set_hooks_adapter(hooks_adapter);
+
     *messaging_manager = Arc::new(new_messaging_manager);
     api_state
         .set_messaging_manager(messaging_manager.clone())
@@ -1826,7 +2927,168 @@ async fn initialize_agents(
 
     tracing::info!("messaging adapters started");
 
+    // Reconnect greetings: bindings can opt in to a short "back online"
+    // notice when the bot returns after downtime beyond their threshold.
+    // A heartbeat file records when the process was last alive; skip all
+    // of this on config reloads, which aren't downtime.
+    static HEARTBEAT_STARTED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+    if !HEARTBEAT_STARTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        let heartbeat_path = config.instance_dir.join("last_alive");
+        let downtime_secs = std::fs::read_to_string(&heartbeat_path)
+            .ok()
+            .and_then(|contents| contents.trim().parse::<i64>().ok())
+            .map(|last_alive| (chrono::Utc::now().timestamp() - last_alive).max(0) as u64);
+
+        let greetings: Vec<(String, String, String)> = config
+            .bindings
+            .iter()
+            .filter(|binding| binding.reconnect_greeting)
+            .filter_map(|binding| {
+                let downtime = downtime_secs?;
+                if downtime < binding.reconnect_greeting_threshold_secs {
+                    return None;
+                }
+                let target = binding
+                    .chat_id
+                    .clone()
+                    .or_else(|| binding.channel_ids.first().cloned())?;
+                Some((
+                    binding.runtime_adapter_key(),
+                    target,
+                    format_reconnect_greeting(downtime),
+                ))
+            })
+            .collect();
+        if !greetings.is_empty() {
+            let manager = messaging_manager.clone();
+            tokio::spawn(async move {
+                // Give gateway-based adapters a moment to finish connecting.
+                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                for (adapter, target, text) in greetings {
+                    if let Err(error) = manager
+                        .broadcast(&adapter, &target, spacebot::OutboundResponse::Text(text))
+                        .await
+                    {
+                        tracing::warn!(
+                            %error,
+                            adapter = %adapter,
+                            target = %target,
+                            "failed to send reconnect greeting"
+                        );
+                    }
+                }
+            });
+        }
+
+        // Keep the heartbeat fresh so the next startup can measure downtime.
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(error) =
+                    std::fs::write(&heartbeat_path, chrono::Utc::now().timestamp().to_string())
+                {
+                    tracing::debug!(%error, "failed to write heartbeat file");
+                }
+            }
+        });
+    }
+
+    // Nightly memory maintenance during the configured off-hours window
+    if config.defaults.memory_maintenance.enabled {
+        let maintenance = config.defaults.memory_maintenance.clone();
+        let timezone = config.defaults.cron_timezone.clone();
+        let manager = messaging_manager.clone();
+        let searches: Vec<(String, Arc<spacebot::memory::MemorySearch>)> = agents
+            .iter()
+            .map(|(agent_id, agent)| (agent_id.to_string(), agent.deps.memory_search.clone()))
+            .collect();
+
+        tokio::spawn(async move {
+            let mut last_run_date: Option<chrono::NaiveDate> = None;
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(600));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                let (hour, today) = match timezone
+                    .as_deref()
+                    .and_then(|name| name.parse::<chrono_tz::Tz>().ok())
+                {
+                    Some(tz) => {
+                        let now = chrono::Utc::now().with_timezone(&tz);
+                        (chrono::Timelike::hour(&now) as u8, now.date_naive())
+                    }
+                    None => {
+                        let now = chrono::Local::now();
+                        (chrono::Timelike::hour(&now) as u8, now.date_naive())
+                    }
+                };
+
+                let (start, end) = maintenance.active_hours;
+                if !spacebot::cron::scheduler::hour_in_active_window(hour, start, end)
+                    || last_run_date == Some(today)
+                {
+                    continue;
+                }
+                last_run_date = Some(today);
+
+                for (agent_id, search) in &searches {
+                    let report = match spacebot::memory::maintenance::run_maintenance(
+                        search.store(),
+                        &spacebot::memory::maintenance::MaintenanceConfig::default(),
+                    )
+                    .await
+                    {
+                        Ok(report) => report,
+                        Err(error) => {
+                            tracing::warn!(agent_id = %agent_id, %error, "memory maintenance failed");
+                            continue;
+                        }
+                    };
+
+                    // Rebuild vector and full-text indexes over the compacted store
+                    if let Err(error) = search.embedding_table().create_indexes().await {
+                        tracing::warn!(agent_id = %agent_id, %error, "embedding index rebuild failed");
+                    }
+                    if let Err(error) = search.embedding_table().ensure_fts_index().await {
+                        tracing::warn!(agent_id = %agent_id, %error, "FTS index rebuild failed");
+                    }
+
+                    tracing::info!(agent_id = %agent_id, ?report, "nightly memory maintenance complete");
+
+                    if report.is_noop() {
+                        continue;
+                    }
+                    if let Some(raw_target) = &maintenance.report_target
+                        && let Some(target) =
+                            spacebot::messaging::target::parse_delivery_target(raw_target)
+                    {
+                        let text = format!("[{agent_id}] {}", report.summary());
+                        if let Err(error) = manager
+                            .broadcast(
+                                &target.adapter,
+                                &target.target,
+                                spacebot::OutboundResponse::Text(text),
+                            )
+                            .await
+                        {
+                            tracing::warn!(%error, "failed to deliver maintenance report");
+                        }
+                    }
+                }
+            }
+        });
+    }
+
     // Initialize cron schedulers for each agent
+    let template_store = Arc::new(
+        spacebot::templates::TemplateStore::new(config.instance_dir.join("templates"))
+            .context("failed to open template store")?,
+    );
     let mut cron_stores_map = std::collections::HashMap::new();
     let mut cron_schedulers_map = std::collections::HashMap::new();
 
@@ -1842,6 +3104,7 @@ async fn initialize_agents(
                 cron_expr: cron_def.cron_expr.clone(),
                 interval_secs: cron_def.interval_secs,
                 delivery_target: cron_def.delivery_target.clone(),
+                template: cron_def.template.clone(),
                 active_hours: cron_def.active_hours,
                 enabled: cron_def.enabled,
                 run_once: cron_def.run_once,
@@ -1864,6 +3127,7 @@ async fn initialize_agents(
             logs_dir: agent.config.logs_dir(),
             messaging_manager: messaging_manager.clone(),
             store: store.clone(),
+            templates: template_store.clone(),
         };
 
         let scheduler = Arc::new(spacebot::cron::Scheduler::new(cron_context));
@@ -1979,6 +3243,28 @@ async fn initialize_agents(
     Ok(())
 }
 
+/// "Back online" notice for bindings that opted into reconnect greetings,
+/// mentioning how long the bot was away and that missed messages are being
+/// picked up.
+fn format_reconnect_greeting(downtime_secs: u64) -> String {
+    format!(
+        "Back online after about {} away. Catching up on anything sent in the meantime — \
+         mention me if something needs a fresh look.",
+        format_downtime(downtime_secs)
+    )
+}
+
+/// Render a downtime duration at minute granularity ("2h 13m", "3d 4h").
+fn format_downtime(secs: u64) -> String {
+    if secs >= 86_400 {
+        format!("{}d {}h", secs / 86_400, (secs % 86_400) / 3_600)
+    } else if secs >= 3_600 {
+        format!("{}h {}m", secs / 3_600, (secs % 3_600) / 60)
+    } else {
+        format!("{}m", (secs / 60).max(1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::wait_for_startup_warmup_tasks;
@@ -1986,6 +3272,13 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration;
 
+    #[test]
+    fn downtime_formats_at_minute_granularity() {
+        assert_eq!(super::format_downtime(30), "1m");
+        assert_eq!(super::format_downtime(8_000), "2h 13m");
+        assert_eq!(super::format_downtime(273_600), "3d 4h");
+    }
+
     #[tokio::test]
     async fn startup_warmup_wait_returns_false_when_tasks_finish_in_time() {
         let mut tasks = tokio::task::JoinSet::new();
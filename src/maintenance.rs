@@ -0,0 +1,214 @@
+//! System-wide maintenance mode.
+//!
+//! While maintenance is on, the inbound loop answers every conversation once
+//! with an auto-reply (optionally naming when the instance is back) and drops
+//! the message before it reaches an agent; channels, queues, and persisted
+//! state are untouched, so turning maintenance off resumes exactly where the
+//! operators left it. The toggle is reachable over the management API and the
+//! `spacebot maintenance` CLI, and the state survives restarts via
+//! `maintenance.json` in the instance directory.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const MAINTENANCE_FILE: &str = "maintenance.json";
+
+/// Active maintenance window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    /// Operator-supplied notice shown to users, e.g. "back at 14:00 UTC".
+    pub message: Option<String>,
+    /// Unix timestamp after which maintenance ends automatically.
+    pub until: Option<i64>,
+    /// Unix timestamp when maintenance was enabled.
+    pub enabled_at: i64,
+}
+
+/// Maintenance toggle shared by the inbound loop, API, and daemon IPC.
+#[derive(Clone)]
+pub struct MaintenanceStore {
+    path: PathBuf,
+    state: Arc<RwLock<Option<MaintenanceState>>>,
+    /// Conversations already shown the auto-reply this window.
+    notified: Arc<RwLock<HashSet<String>>>,
+}
+
+impl MaintenanceStore {
+    /// Load persisted maintenance state from the instance directory.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(MAINTENANCE_FILE);
+        let state: Option<MaintenanceState> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok());
+
+        Self {
+            path,
+            state: Arc::new(RwLock::new(state)),
+            notified: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Turn maintenance on.
+    pub async fn enable(&self, message: Option<String>, until: Option<i64>) {
+        let state = MaintenanceState {
+            message: message.filter(|value| !value.trim().is_empty()),
+            until,
+            enabled_at: Utc::now().timestamp(),
+        };
+        *self.state.write().await = Some(state.clone());
+        self.notified.write().await.clear();
+        self.save(Some(&state)).await;
+    }
+
+    /// Turn maintenance off. Returns false when it was not on.
+    pub async fn disable(&self) -> bool {
+        let was_active = self.state.write().await.take().is_some();
+        if was_active {
+            self.notified.write().await.clear();
+            self.save(None).await;
+        }
+        was_active
+    }
+
+    /// The current window, expiring it first when `until` has passed.
+    pub async fn status(&self) -> Option<MaintenanceState> {
+        let expired = {
+            let state = self.state.read().await;
+            match state.as_ref() {
+                Some(state) => state
+                    .until
+                    .is_some_and(|until| until <= Utc::now().timestamp()),
+                None => return None,
+            }
+        };
+
+        if expired {
+            self.disable().await;
+            return None;
+        }
+
+        self.state.read().await.clone()
+    }
+
+    /// Whether inbound processing is currently paused.
+    pub async fn active(&self) -> bool {
+        self.status().await.is_some()
+    }
+
+    /// Whether this conversation still needs the auto-reply, marking it
+    /// notified. Each conversation is warned once per window.
+    pub async fn should_notify(&self, conversation_id: &str) -> bool {
+        self.notified.write().await.insert(conversation_id.to_string())
+    }
+
+    /// The auto-reply posted to conversations during maintenance.
+    pub async fn auto_reply(&self) -> String {
+        let state = self.state.read().await;
+        format_auto_reply(state.as_ref())
+    }
+
+    async fn save(&self, state: Option<&MaintenanceState>) {
+        let result = match state {
+            Some(state) => serde_json::to_string_pretty(state)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| std::fs::write(&self.path, contents).map_err(Into::into)),
+            None => match std::fs::remove_file(&self.path) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+                _ => Ok(()),
+            },
+        };
+        if let Err(error) = result {
+            tracing::warn!(%error, path = %self.path.display(), "failed to persist maintenance state");
+        }
+    }
+}
+
+/// Render the user-facing maintenance notice.
+pub fn format_auto_reply(state: Option<&MaintenanceState>) -> String {
+    let Some(state) = state else {
+        return "🔧 This bot is temporarily down for maintenance.".to_string();
+    };
+
+    let mut reply = match &state.message {
+        Some(message) => format!("🔧 Down for maintenance: {message}"),
+        None => "🔧 This bot is temporarily down for maintenance.".to_string(),
+    };
+
+    if let Some(until) = state.until
+        && let Some(until) = chrono::DateTime::from_timestamp(until, 0)
+    {
+        reply.push_str(&format!(
+            " Expected back at {}.",
+            until.format("%H:%M UTC on %Y-%m-%d")
+        ));
+    }
+
+    reply
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaintenanceState, MaintenanceStore, format_auto_reply};
+
+    #[tokio::test]
+    async fn enable_disable_round_trip_persists() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = MaintenanceStore::load(dir.path());
+        assert!(!store.active().await);
+
+        store
+            .enable(Some("upgrading database".into()), None)
+            .await;
+        assert!(store.active().await);
+
+        // A fresh load sees the persisted window.
+        let reloaded = MaintenanceStore::load(dir.path());
+        let status = reloaded.status().await.expect("active window");
+        assert_eq!(status.message.as_deref(), Some("upgrading database"));
+
+        assert!(store.disable().await);
+        assert!(!store.active().await);
+        assert!(!MaintenanceStore::load(dir.path()).active().await);
+    }
+
+    #[tokio::test]
+    async fn window_expires_when_until_passes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = MaintenanceStore::load(dir.path());
+
+        store
+            .enable(None, Some(chrono::Utc::now().timestamp() - 10))
+            .await;
+        assert!(!store.active().await);
+    }
+
+    #[tokio::test]
+    async fn conversations_are_notified_once_per_window() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = MaintenanceStore::load(dir.path());
+        store.enable(None, None).await;
+
+        assert!(store.should_notify("discord:1").await);
+        assert!(!store.should_notify("discord:1").await);
+        assert!(store.should_notify("discord:2").await);
+
+        // A new window notifies everyone again.
+        store.enable(None, None).await;
+        assert!(store.should_notify("discord:1").await);
+    }
+
+    #[test]
+    fn auto_reply_includes_message_and_eta() {
+        let reply = format_auto_reply(Some(&MaintenanceState {
+            message: Some("kernel patching".into()),
+            until: Some(0),
+            enabled_at: 0,
+        }));
+        assert!(reply.contains("kernel patching"));
+        assert!(reply.contains("00:00 UTC on 1970-01-01"));
+    }
+}
@@ -1,14 +1,18 @@
 //! Memory storage and retrieval system.
 
 pub mod embedding;
+pub mod faq;
 pub mod lance;
 pub mod maintenance;
 pub mod search;
 pub mod store;
+pub mod translation;
 pub mod types;
 
 pub use embedding::EmbeddingModel;
+pub use faq::{FaqEntry, FaqMatch, FaqStore};
 pub use lance::EmbeddingTable;
 pub use search::{MemorySearch, SearchConfig, SearchMode, SearchSort, curate_results};
 pub use store::MemoryStore;
+pub use translation::{CachedTranslation, TranslationMemory};
 pub use types::{Association, Memory, MemoryType, RelationType};
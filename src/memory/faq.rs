@@ -0,0 +1,287 @@
+//! Curated FAQ store for duplicate-question detection.
+//!
+//! Support channels see the same questions over and over. Answers promoted
+//! into the FAQ are embedded once; inbound questions are compared against
+//! those embeddings and close matches are answered directly from the store —
+//! with a pointer to the thread where the question was first answered —
+//! before the full model is ever invoked.
+
+use anyhow::Context as _;
+use sqlx::{Row as _, SqlitePool};
+
+use crate::error::Result;
+
+/// Minimum cosine similarity for an inbound question to count as a duplicate
+/// of an FAQ entry. Embeddings of paraphrases typically land well above this;
+/// unrelated questions well below.
+pub const FAQ_SIMILARITY_THRESHOLD: f32 = 0.88;
+
+/// A curated question/answer pair.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FaqEntry {
+    pub id: String,
+    pub question: String,
+    pub answer: String,
+    /// Conversation where this answer was originally given, if promoted from
+    /// a live thread.
+    pub source_conversation_id: Option<String>,
+    /// How many times this entry has answered an inbound question.
+    pub hits: i64,
+    pub created_at: i64,
+}
+
+/// A matched entry with its similarity score.
+#[derive(Debug, Clone)]
+pub struct FaqMatch {
+    pub entry: FaqEntry,
+    pub similarity: f32,
+}
+
+/// SQLite-backed FAQ store. Embeddings are computed by the caller so the
+/// store itself stays independent of the embedding runtime.
+#[derive(Debug, Clone)]
+pub struct FaqStore {
+    pool: SqlitePool,
+}
+
+impl FaqStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Whether the store has any entries. Checked before embedding inbound
+    /// messages so empty stores cost nothing per message.
+    pub async fn is_empty(&self) -> Result<bool> {
+        let row = sqlx::query("SELECT EXISTS(SELECT 1 FROM faq_entries) AS present")
+            .fetch_one(&self.pool)
+            .await
+            .context("failed to check FAQ store")?;
+        let present: i64 = row.get("present");
+        Ok(present == 0)
+    }
+
+    /// Add an entry with the question's embedding. Returns the stored entry.
+    pub async fn add(
+        &self,
+        question: &str,
+        answer: &str,
+        source_conversation_id: Option<&str>,
+        embedding: &[f32],
+    ) -> Result<FaqEntry> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO faq_entries
+                (id, question, answer, source_conversation_id, embedding, created_at, last_used_at, hits)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 0)
+            "#,
+        )
+        .bind(&id)
+        .bind(question)
+        .bind(answer)
+        .bind(source_conversation_id)
+        .bind(encode_embedding(embedding))
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .context("failed to store FAQ entry")?;
+
+        Ok(FaqEntry {
+            id,
+            question: question.to_string(),
+            answer: answer.to_string(),
+            source_conversation_id: source_conversation_id.map(str::to_string),
+            hits: 0,
+            created_at: now,
+        })
+    }
+
+    /// Find the entry most similar to the query embedding, if any clears
+    /// [`FAQ_SIMILARITY_THRESHOLD`]. The curated store is small, so a full
+    /// scan is fine.
+    pub async fn best_match(&self, query_embedding: &[f32]) -> Result<Option<FaqMatch>> {
+        let rows = sqlx::query(
+            "SELECT id, question, answer, source_conversation_id, embedding, hits, created_at
+             FROM faq_entries",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to load FAQ entries")?;
+
+        let mut best: Option<FaqMatch> = None;
+        for row in rows {
+            let embedding = decode_embedding(row.get("embedding"));
+            let similarity = cosine_similarity(query_embedding, &embedding);
+            if similarity < FAQ_SIMILARITY_THRESHOLD {
+                continue;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|current| similarity > current.similarity)
+            {
+                best = Some(FaqMatch {
+                    entry: FaqEntry {
+                        id: row.get("id"),
+                        question: row.get("question"),
+                        answer: row.get("answer"),
+                        source_conversation_id: row.get("source_conversation_id"),
+                        hits: row.get("hits"),
+                        created_at: row.get("created_at"),
+                    },
+                    similarity,
+                });
+            }
+        }
+
+        Ok(best)
+    }
+
+    /// Record that an entry answered an inbound question.
+    pub async fn record_hit(&self, id: &str) -> Result<()> {
+        sqlx::query("UPDATE faq_entries SET hits = hits + 1, last_used_at = ? WHERE id = ?")
+            .bind(chrono::Utc::now().timestamp())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to record FAQ hit")?;
+        Ok(())
+    }
+
+    /// List all entries, most recently created first.
+    pub async fn list(&self) -> Result<Vec<FaqEntry>> {
+        let rows = sqlx::query(
+            "SELECT id, question, answer, source_conversation_id, hits, created_at
+             FROM faq_entries ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list FAQ entries")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FaqEntry {
+                id: row.get("id"),
+                question: row.get("question"),
+                answer: row.get("answer"),
+                source_conversation_id: row.get("source_conversation_id"),
+                hits: row.get("hits"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Remove an entry. Returns whether it existed.
+    pub async fn remove(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM faq_entries WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to delete FAQ entry")?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Formats the reply sent when an FAQ entry answers a question: the curated
+/// answer plus a pointer to the thread it was promoted from.
+pub fn format_faq_answer(entry: &FaqEntry) -> String {
+    match &entry.source_conversation_id {
+        Some(source) => format!("{}\n\n(previously answered in {source})", entry.answer),
+        None => entry.answer.clone(),
+    }
+}
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_embedding(blob: Vec<u8>) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn store() -> FaqStore {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        FaqStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn close_matches_are_found() {
+        let store = store().await;
+        assert!(store.is_empty().await.unwrap());
+
+        store
+            .add(
+                "How do I reset my password?",
+                "Use the reset link on the login page.",
+                Some("discord:123"),
+                &[1.0, 0.0, 0.0],
+            )
+            .await
+            .unwrap();
+        assert!(!store.is_empty().await.unwrap());
+
+        // Near-identical direction clears the threshold
+        let matched = store
+            .best_match(&[0.99, 0.05, 0.0])
+            .await
+            .unwrap()
+            .expect("duplicate detected");
+        assert!(matched.similarity > FAQ_SIMILARITY_THRESHOLD);
+        assert_eq!(
+            format_faq_answer(&matched.entry),
+            "Use the reset link on the login page.\n\n(previously answered in discord:123)"
+        );
+
+        // Orthogonal query is not a duplicate
+        assert!(store.best_match(&[0.0, 1.0, 0.0]).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn hits_accumulate_and_entries_remove() {
+        let store = store().await;
+        let entry = store
+            .add("What are your hours?", "We're online 24/7.", None, &[0.0, 1.0])
+            .await
+            .unwrap();
+
+        store.record_hit(&entry.id).await.unwrap();
+        store.record_hit(&entry.id).await.unwrap();
+        let listed = store.list().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].hits, 2);
+
+        assert!(store.remove(&entry.id).await.unwrap());
+        assert!(!store.remove(&entry.id).await.unwrap());
+    }
+
+    #[test]
+    fn cosine_handles_degenerate_inputs() {
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        let similar = cosine_similarity(&[1.0, 0.0], &[1.0, 0.1]);
+        assert!(similar > 0.99);
+    }
+}
@@ -1,8 +1,8 @@
-//! Memory maintenance: decay, prune, merge, reindex.
+//! Memory maintenance: decay, prune, consolidate, merge, reindex.
 
 use crate::error::Result;
 use crate::memory::MemoryStore;
-use crate::memory::types::MemoryType;
+use crate::memory::types::{Memory, MemoryType};
 
 /// Maintenance configuration.
 #[derive(Debug, Clone)]
@@ -15,6 +15,10 @@ pub struct MaintenanceConfig {
     pub min_age_days: i64,
     /// Similarity threshold for merging memories (0.0 - 1.0).
     pub merge_similarity_threshold: f32,
+    /// Minimum age in days before episodic memories are consolidated.
+    pub consolidate_min_age_days: i64,
+    /// Minimum number of old events (per channel) worth consolidating.
+    pub consolidate_batch_min: usize,
 }
 
 impl Default for MaintenanceConfig {
@@ -24,6 +28,8 @@ impl Default for MaintenanceConfig {
             decay_rate: 0.05,
             min_age_days: 30,
             merge_similarity_threshold: 0.95,
+            consolidate_min_age_days: 14,
+            consolidate_batch_min: 5,
         }
     }
 }
@@ -41,6 +47,7 @@ pub async fn run_maintenance(
     {
         report.decayed = apply_decay(memory_store, config.decay_rate).await?;
         report.pruned = prune_memories(memory_store, config).await?;
+        report.consolidated = consolidate_events(memory_store, config).await?;
         report.merged =
             merge_similar_memories(memory_store, config.merge_similarity_threshold).await?;
     }
@@ -122,6 +129,74 @@ async fn prune_memories(memory_store: &MemoryStore, config: &MaintenanceConfig)
     Ok(pruned_count)
 }
 
+/// Consolidate old episodic memories into long-term summaries.
+///
+/// Events past the age threshold are folded, per channel, into a single
+/// observation digest; the originals are soft-deleted. Returns how many
+/// events were consolidated.
+async fn consolidate_events(
+    memory_store: &MemoryStore,
+    config: &MaintenanceConfig,
+) -> Result<usize> {
+    /// Cap on digest lines so summaries stay a readable size.
+    const MAX_DIGEST_LINES: usize = 20;
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(config.consolidate_min_age_days);
+    let events = memory_store.get_by_type(MemoryType::Event, 1000).await?;
+
+    // Group stale events by channel so each summary stays coherent
+    let mut groups: std::collections::HashMap<Option<crate::ChannelId>, Vec<Memory>> =
+        std::collections::HashMap::new();
+    for event in events {
+        if event.created_at < cutoff {
+            groups.entry(event.channel_id.clone()).or_default().push(event);
+        }
+    }
+
+    let mut consolidated = 0;
+
+    for (channel_id, mut group) in groups {
+        if group.len() < config.consolidate_batch_min {
+            continue;
+        }
+        group.sort_by_key(|memory| memory.created_at);
+
+        let earliest = group.first().map(|m| m.created_at.date_naive());
+        let latest = group.last().map(|m| m.created_at.date_naive());
+        let mut digest = format!(
+            "Summary of {} earlier events ({} to {}):",
+            group.len(),
+            earliest.map(|d| d.to_string()).unwrap_or_default(),
+            latest.map(|d| d.to_string()).unwrap_or_default(),
+        );
+        for event in group.iter().take(MAX_DIGEST_LINES) {
+            let line: String = event.content.chars().take(160).collect();
+            digest.push_str("\n- ");
+            digest.push_str(&line);
+        }
+        if group.len() > MAX_DIGEST_LINES {
+            digest.push_str(&format!("\n- …and {} more", group.len() - MAX_DIGEST_LINES));
+        }
+
+        let mut summary = Memory::new(digest, MemoryType::Observation);
+        summary.source = Some("consolidation".to_string());
+        summary.channel_id = channel_id;
+        // Keep the summary at least as prominent as its strongest source
+        summary.importance = group
+            .iter()
+            .map(|m| m.importance)
+            .fold(summary.importance, f32::max);
+        memory_store.save(&summary).await?;
+
+        for event in &group {
+            memory_store.forget(&event.id).await?;
+        }
+        consolidated += group.len();
+    }
+
+    Ok(consolidated)
+}
+
 /// Merge near-duplicate memories.
 async fn merge_similar_memories(
     _memory_store: &MemoryStore,
@@ -141,5 +216,76 @@ async fn merge_similar_memories(
 pub struct MaintenanceReport {
     pub decayed: usize,
     pub pruned: usize,
+    pub consolidated: usize,
     pub merged: usize,
 }
+
+impl MaintenanceReport {
+    /// Whether the pass changed anything worth reporting.
+    pub fn is_noop(&self) -> bool {
+        self.decayed == 0 && self.pruned == 0 && self.consolidated == 0 && self.merged == 0
+    }
+
+    /// One-line summary for the admin channel report.
+    pub fn summary(&self) -> String {
+        format!(
+            "memory maintenance: consolidated {} events into summaries, pruned {}, decayed {}, merged {}",
+            self.consolidated, self.pruned, self.decayed, self.merged
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn old_events_consolidate_into_a_summary() {
+        let store = MemoryStore::connect_in_memory().await;
+        for i in 0..6 {
+            let mut event = Memory::new(format!("deploy {i} completed"), MemoryType::Event);
+            event.created_at = chrono::Utc::now() - chrono::Duration::days(30);
+            store.save(&event).await.unwrap();
+        }
+        // A recent event stays untouched
+        store
+            .save(&Memory::new("fresh event", MemoryType::Event))
+            .await
+            .unwrap();
+
+        let config = MaintenanceConfig {
+            consolidate_min_age_days: 14,
+            ..Default::default()
+        };
+        let consolidated = consolidate_events(&store, &config).await.unwrap();
+        assert_eq!(consolidated, 6);
+
+        let events = store.get_by_type(MemoryType::Event, 100).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].content, "fresh event");
+
+        let observations = store
+            .get_by_type(MemoryType::Observation, 100)
+            .await
+            .unwrap();
+        assert_eq!(observations.len(), 1);
+        assert!(observations[0].content.starts_with("Summary of 6 earlier events"));
+        assert_eq!(observations[0].source.as_deref(), Some("consolidation"));
+    }
+
+    #[tokio::test]
+    async fn small_batches_are_left_alone() {
+        let store = MemoryStore::connect_in_memory().await;
+        for i in 0..3 {
+            let mut event = Memory::new(format!("event {i}"), MemoryType::Event);
+            event.created_at = chrono::Utc::now() - chrono::Duration::days(30);
+            store.save(&event).await.unwrap();
+        }
+
+        let consolidated = consolidate_events(&store, &MaintenanceConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(consolidated, 0);
+        assert_eq!(store.get_by_type(MemoryType::Event, 100).await.unwrap().len(), 3);
+    }
+}
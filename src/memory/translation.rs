@@ -0,0 +1,224 @@
+//! Translation memory: a cache of prior translations keyed by content hash
+//! and language pair.
+//!
+//! Multilingual support channels answer the same questions constantly; once a
+//! phrase has been translated, the stored output is reused instead of
+//! re-generating it with the LLM. Entries expire after a configurable TTL so
+//! stale phrasings age out.
+
+use anyhow::Context as _;
+use sha2::{Digest as _, Sha256};
+use sqlx::{Row as _, SqlitePool};
+
+use crate::error::Result;
+
+/// A cached translation returned from a lookup.
+#[derive(Debug, Clone)]
+pub struct CachedTranslation {
+    pub translated_text: String,
+    /// How many times this entry has been reused.
+    pub hits: i64,
+}
+
+/// SQLite-backed translation cache.
+#[derive(Debug, Clone)]
+pub struct TranslationMemory {
+    pool: SqlitePool,
+}
+
+impl TranslationMemory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Hash key for a source text: whitespace-collapsed and case-folded so
+    /// trivial variations of the same phrase share a cache entry.
+    fn content_hash(text: &str) -> String {
+        let normalized = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let digest = Sha256::digest(normalized.as_bytes());
+        hex::encode(digest)
+    }
+
+    /// Language codes are compared case-insensitively.
+    fn normalize_lang(lang: &str) -> String {
+        lang.trim().to_lowercase()
+    }
+
+    /// Look up a cached translation for the language pair.
+    ///
+    /// Entries older than `ttl_secs` are treated as misses (and removed).
+    /// A hit bumps the usage counter and freshness timestamp.
+    pub async fn lookup(
+        &self,
+        source_lang: &str,
+        target_lang: &str,
+        text: &str,
+        ttl_secs: u64,
+    ) -> Result<Option<CachedTranslation>> {
+        let hash = Self::content_hash(text);
+        let source_lang = Self::normalize_lang(source_lang);
+        let target_lang = Self::normalize_lang(target_lang);
+        let now = chrono::Utc::now().timestamp();
+
+        let row = sqlx::query(
+            r#"
+            SELECT translated_text, created_at, hits
+            FROM translation_memory
+            WHERE content_hash = ? AND source_lang = ? AND target_lang = ?
+            "#,
+        )
+        .bind(&hash)
+        .bind(&source_lang)
+        .bind(&target_lang)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to query translation memory")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let created_at: i64 = row.get("created_at");
+        if now.saturating_sub(created_at) >= ttl_secs as i64 {
+            sqlx::query(
+                "DELETE FROM translation_memory
+                 WHERE content_hash = ? AND source_lang = ? AND target_lang = ?",
+            )
+            .bind(&hash)
+            .bind(&source_lang)
+            .bind(&target_lang)
+            .execute(&self.pool)
+            .await
+            .context("failed to evict expired translation")?;
+            return Ok(None);
+        }
+
+        let hits: i64 = row.get("hits");
+        sqlx::query(
+            "UPDATE translation_memory SET hits = hits + 1, last_used_at = ?
+             WHERE content_hash = ? AND source_lang = ? AND target_lang = ?",
+        )
+        .bind(now)
+        .bind(&hash)
+        .bind(&source_lang)
+        .bind(&target_lang)
+        .execute(&self.pool)
+        .await
+        .context("failed to record translation cache hit")?;
+
+        Ok(Some(CachedTranslation {
+            translated_text: row.get("translated_text"),
+            hits: hits + 1,
+        }))
+    }
+
+    /// Store (or refresh) a translation for the language pair.
+    pub async fn store(
+        &self,
+        source_lang: &str,
+        target_lang: &str,
+        source_text: &str,
+        translated_text: &str,
+    ) -> Result<()> {
+        let hash = Self::content_hash(source_text);
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO translation_memory
+                (content_hash, source_lang, target_lang, source_text, translated_text,
+                 created_at, last_used_at, hits)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 0)
+            ON CONFLICT (content_hash, source_lang, target_lang) DO UPDATE SET
+                translated_text = excluded.translated_text,
+                created_at = excluded.created_at,
+                last_used_at = excluded.last_used_at
+            "#,
+        )
+        .bind(&hash)
+        .bind(Self::normalize_lang(source_lang))
+        .bind(Self::normalize_lang(target_lang))
+        .bind(source_text)
+        .bind(translated_text)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .context("failed to store translation")?;
+
+        Ok(())
+    }
+
+    /// Remove all entries older than the TTL. Returns how many were evicted.
+    pub async fn prune_expired(&self, ttl_secs: u64) -> Result<u64> {
+        let cutoff = chrono::Utc::now().timestamp() - ttl_secs as i64;
+        let result = sqlx::query("DELETE FROM translation_memory WHERE created_at <= ?")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .context("failed to prune translation memory")?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn memory() -> TranslationMemory {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        TranslationMemory::new(pool)
+    }
+
+    #[tokio::test]
+    async fn store_and_lookup_roundtrip() {
+        let memory = memory().await;
+        memory
+            .store("en", "de", "How do I reset my password?", "Wie setze ich mein Passwort zurück?")
+            .await
+            .unwrap();
+
+        let hit = memory
+            .lookup("en", "de", "How do I reset my password?", 3600)
+            .await
+            .unwrap()
+            .expect("cache hit");
+        assert_eq!(hit.translated_text, "Wie setze ich mein Passwort zurück?");
+        assert_eq!(hit.hits, 1);
+    }
+
+    #[tokio::test]
+    async fn normalizes_whitespace_and_case() {
+        let memory = memory().await;
+        memory.store("en", "fr", "Hello there", "Bonjour").await.unwrap();
+
+        let hit = memory
+            .lookup("EN", "fr", "  hello   THERE ", 3600)
+            .await
+            .unwrap();
+        assert!(hit.is_some());
+    }
+
+    #[tokio::test]
+    async fn expired_entries_miss() {
+        let memory = memory().await;
+        memory.store("en", "es", "thanks", "gracias").await.unwrap();
+
+        assert!(memory.lookup("en", "es", "thanks", 0).await.unwrap().is_none());
+        // The expired row was evicted, so a fresh store works cleanly
+        assert_eq!(memory.prune_expired(0).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn distinct_language_pairs_do_not_collide() {
+        let memory = memory().await;
+        memory.store("en", "de", "thanks", "danke").await.unwrap();
+
+        assert!(memory.lookup("en", "ja", "thanks", 3600).await.unwrap().is_none());
+    }
+}
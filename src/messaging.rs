@@ -1,15 +1,55 @@
-//! Messaging adapters (Discord, Slack, Telegram, Twitch, Email, Webhook, WebChat).
+//! Messaging adapters (Discord, Slack, Telegram, Twitch, Email, Bluesky, Google Chat, Mastodon, Mattermost, Rocket.Chat, Teams, Signal, WhatsApp, SMS, Zulip, Webhook, WebChat).
 
+pub mod bluesky;
+pub mod console;
+#[cfg(feature = "adapter-discord")]
 pub mod discord;
+#[cfg(feature = "discord-voice")]
+pub mod discord_voice;
+#[cfg(feature = "adapter-email")]
 pub mod email;
+pub mod github;
+pub mod gitlab;
+pub mod googlechat;
+#[cfg(feature = "adapter-email")]
+pub mod graphmail;
+pub mod hooks;
+pub mod httpbot;
+pub mod jira;
+#[cfg(feature = "adapter-email")]
+pub mod jmap;
+pub mod line;
+pub mod linear;
 pub mod manager;
+pub mod mastodon;
+pub mod mattermost;
+pub mod mqtt;
+pub mod nextcloud;
+pub mod nostr;
+pub mod notify;
+pub mod reactions;
+pub mod reddit;
+pub mod rocketchat;
+pub mod rss;
+pub mod signal;
+#[cfg(feature = "adapter-slack")]
 pub mod slack;
+pub mod sms;
+pub mod steam;
 pub mod target;
+pub mod teams;
+#[cfg(feature = "adapter-telegram")]
 pub mod telegram;
 pub mod traits;
 pub mod twitch;
+pub mod viber;
+pub mod voice;
 pub mod webchat;
 pub mod webhook;
+pub mod websocket;
+pub mod whatsapp;
+pub mod xmpp;
+pub mod zulip;
 
 pub use manager::MessagingManager;
 pub use traits::Messaging;
@@ -0,0 +1,567 @@
+//! Bluesky (AT Protocol) messaging adapter.
+//!
+//! Authenticates against a PDS with a handle and app password, polls the
+//! notifications endpoint for mentions and replies, and posts threaded
+//! replies through `com.atproto.repo.createRecord`. Long responses are
+//! chained into a thread, each post replying to the previous one. Multiple
+//! accounts are configured as `[[messaging.bluesky.instances]]`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Bluesky's limit is 300 graphemes; stay under it byte-wise to be safe.
+const MAX_POST_LENGTH: usize = 290;
+
+/// How often to poll for new notifications.
+const POLL_INTERVAL_SECS: u64 = 15;
+
+/// An authenticated session against the PDS.
+#[derive(Clone)]
+struct Session {
+    access_jwt: String,
+    refresh_jwt: String,
+    did: String,
+}
+
+/// A post reference used for reply threading.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct PostRef {
+    uri: String,
+    cid: String,
+}
+
+/// Bluesky adapter state.
+pub struct BlueskyAdapter {
+    runtime_key: String,
+    /// PDS base URL, e.g. `https://bsky.social` (no trailing slash).
+    service: String,
+    handle: String,
+    app_password: String,
+    client: reqwest::Client,
+    session: Arc<RwLock<Option<Session>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl BlueskyAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        service: impl Into<String>,
+        handle: impl Into<String>,
+        app_password: impl Into<String>,
+    ) -> Self {
+        let service = service.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            service: service.trim_end_matches('/').to_string(),
+            handle: handle.into(),
+            app_password: app_password.into(),
+            client: crate::http::client(),
+            session: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn clone_for_task(&self) -> Self {
+        Self {
+            runtime_key: self.runtime_key.clone(),
+            service: self.service.clone(),
+            handle: self.handle.clone(),
+            app_password: self.app_password.clone(),
+            client: self.client.clone(),
+            session: self.session.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        }
+    }
+
+    fn xrpc_url(&self, method: &str) -> String {
+        format!("{}/xrpc/{method}", self.service)
+    }
+
+    /// Create a fresh session with the app password.
+    async fn login(&self) -> crate::Result<Session> {
+        let response = self
+            .client
+            .post(self.xrpc_url("com.atproto.server.createSession"))
+            .json(&json!({
+                "identifier": self.handle,
+                "password": self.app_password,
+            }))
+            .send()
+            .await
+            .context("failed to reach Bluesky PDS")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Bluesky login failed: HTTP {status}: {body}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("invalid Bluesky session response")?;
+        let session = Session {
+            access_jwt: body["accessJwt"]
+                .as_str()
+                .context("missing accessJwt")?
+                .to_string(),
+            refresh_jwt: body["refreshJwt"]
+                .as_str()
+                .context("missing refreshJwt")?
+                .to_string(),
+            did: body["did"].as_str().context("missing did")?.to_string(),
+        };
+        *self.session.write().await = Some(session.clone());
+        Ok(session)
+    }
+
+    /// Exchange the refresh token for a new access token; falls back to a
+    /// full login when the refresh token itself has expired.
+    async fn refresh(&self) -> crate::Result<Session> {
+        let refresh_jwt = self
+            .session
+            .read()
+            .await
+            .as_ref()
+            .map(|session| session.refresh_jwt.clone());
+
+        if let Some(refresh_jwt) = refresh_jwt {
+            let response = self
+                .client
+                .post(self.xrpc_url("com.atproto.server.refreshSession"))
+                .bearer_auth(&refresh_jwt)
+                .send()
+                .await
+                .context("failed to refresh Bluesky session")?;
+
+            if response.status().is_success() {
+                let body: serde_json::Value = response
+                    .json()
+                    .await
+                    .context("invalid Bluesky refresh response")?;
+                if let (Some(access), Some(refresh), Some(did)) = (
+                    body["accessJwt"].as_str(),
+                    body["refreshJwt"].as_str(),
+                    body["did"].as_str(),
+                ) {
+                    let session = Session {
+                        access_jwt: access.to_string(),
+                        refresh_jwt: refresh.to_string(),
+                        did: did.to_string(),
+                    };
+                    *self.session.write().await = Some(session.clone());
+                    return Ok(session);
+                }
+            }
+        }
+
+        self.login().await
+    }
+
+    /// Current session, logging in if there is none yet.
+    async fn session(&self) -> crate::Result<Session> {
+        if let Some(session) = self.session.read().await.as_ref() {
+            return Ok(session.clone());
+        }
+        self.login().await
+    }
+
+    /// Authenticated XRPC query with a single retry after token refresh.
+    async fn xrpc_get(
+        &self,
+        method: &str,
+        params: &[(&str, &str)],
+    ) -> crate::Result<serde_json::Value> {
+        let mut session = self.session().await?;
+        for attempt in 0..2 {
+            let response = self
+                .client
+                .get(self.xrpc_url(method))
+                .query(params)
+                .bearer_auth(&session.access_jwt)
+                .send()
+                .await
+                .with_context(|| format!("Bluesky {method} request failed"))?;
+
+            if response.status() == reqwest::StatusCode::BAD_REQUEST
+                || response.status() == reqwest::StatusCode::UNAUTHORIZED
+            {
+                let body = response.text().await.unwrap_or_default();
+                if body.contains("ExpiredToken") && attempt == 0 {
+                    session = self.refresh().await?;
+                    continue;
+                }
+                return Err(anyhow::anyhow!("Bluesky {method} failed: {body}").into());
+            }
+
+            if !response.status().is_success() {
+                let status = response.status();
+                return Err(anyhow::anyhow!("Bluesky {method} failed: HTTP {status}").into());
+            }
+
+            return response
+                .json()
+                .await
+                .with_context(|| format!("invalid Bluesky {method} response"))
+                .map_err(Into::into);
+        }
+        unreachable!("retry loop always returns")
+    }
+
+    /// Authenticated XRPC procedure with a single retry after token refresh.
+    async fn xrpc_post(
+        &self,
+        method: &str,
+        payload: &serde_json::Value,
+    ) -> crate::Result<serde_json::Value> {
+        let mut session = self.session().await?;
+        for attempt in 0..2 {
+            let response = self
+                .client
+                .post(self.xrpc_url(method))
+                .bearer_auth(&session.access_jwt)
+                .json(payload)
+                .send()
+                .await
+                .with_context(|| format!("Bluesky {method} request failed"))?;
+
+            if response.status() == reqwest::StatusCode::BAD_REQUEST
+                || response.status() == reqwest::StatusCode::UNAUTHORIZED
+            {
+                let body = response.text().await.unwrap_or_default();
+                if body.contains("ExpiredToken") && attempt == 0 {
+                    session = self.refresh().await?;
+                    continue;
+                }
+                return Err(anyhow::anyhow!("Bluesky {method} failed: {body}").into());
+            }
+
+            if !response.status().is_success() {
+                let status = response.status();
+                return Err(anyhow::anyhow!("Bluesky {method} failed: HTTP {status}").into());
+            }
+
+            return response
+                .json()
+                .await
+                .with_context(|| format!("invalid Bluesky {method} response"))
+                .map_err(Into::into);
+        }
+        unreachable!("retry loop always returns")
+    }
+
+    /// Create a post record, optionally threaded, returning its reference.
+    async fn create_post(
+        &self,
+        text: &str,
+        reply: Option<(&PostRef, &PostRef)>,
+    ) -> crate::Result<PostRef> {
+        let session = self.session().await?;
+        let mut record = json!({
+            "$type": "app.bsky.feed.post",
+            "text": text,
+            "createdAt": chrono::Utc::now().to_rfc3339(),
+        });
+        if let Some((root, parent)) = reply {
+            record["reply"] = json!({
+                "root": { "uri": root.uri, "cid": root.cid },
+                "parent": { "uri": parent.uri, "cid": parent.cid },
+            });
+        }
+
+        let body = self
+            .xrpc_post(
+                "com.atproto.repo.createRecord",
+                &json!({
+                    "repo": session.did,
+                    "collection": "app.bsky.feed.post",
+                    "record": record,
+                }),
+            )
+            .await?;
+
+        Ok(PostRef {
+            uri: body["uri"]
+                .as_str()
+                .context("missing uri in createRecord response")?
+                .to_string(),
+            cid: body["cid"]
+                .as_str()
+                .context("missing cid in createRecord response")?
+                .to_string(),
+        })
+    }
+
+    /// Post a (possibly chunked) reply threaded under the triggering post.
+    async fn reply_threaded(
+        &self,
+        root: &PostRef,
+        parent: &PostRef,
+        text: &str,
+    ) -> crate::Result<()> {
+        let mut parent = parent.clone();
+        for chunk in split_message(text, MAX_POST_LENGTH) {
+            parent = self.create_post(&chunk, Some((root, &parent))).await?;
+        }
+        Ok(())
+    }
+
+    /// Poll notifications once and forward new mentions/replies.
+    async fn poll_notifications(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+    ) -> crate::Result<()> {
+        let session = self.session().await?;
+        let body = self
+            .xrpc_get("app.bsky.notification.listNotifications", &[("limit", "50")])
+            .await?;
+
+        let Some(notifications) = body["notifications"].as_array() else {
+            return Ok(());
+        };
+
+        // Oldest first so conversation order is preserved
+        for notification in notifications.iter().rev() {
+            if notification["isRead"].as_bool() == Some(true) {
+                continue;
+            }
+            let reason = notification["reason"].as_str().unwrap_or_default();
+            if reason != "mention" && reason != "reply" {
+                continue;
+            }
+            let author_did = notification["author"]["did"].as_str().unwrap_or_default();
+            if author_did == session.did {
+                continue;
+            }
+            if let Some(inbound) = self.parse_notification(notification)
+                && inbound_tx.send(inbound).await.is_err()
+            {
+                return Ok(());
+            }
+        }
+
+        // Mark everything up to now as read so the next poll only sees new posts
+        self.xrpc_post(
+            "app.bsky.notification.updateSeen",
+            &json!({ "seenAt": chrono::Utc::now().to_rfc3339() }),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Convert a mention/reply notification into an inbound message.
+    fn parse_notification(&self, notification: &serde_json::Value) -> Option<InboundMessage> {
+        let uri = notification["uri"].as_str()?.to_string();
+        let cid = notification["cid"].as_str()?.to_string();
+        let record = notification.get("record")?;
+        let text = record["text"].as_str()?.to_string();
+        if text.is_empty() {
+            return None;
+        }
+
+        let author = &notification["author"];
+        let author_handle = author["handle"].as_str()?.to_string();
+        let display_name = author["displayName"]
+            .as_str()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&author_handle)
+            .to_string();
+
+        // The thread root anchors the conversation; a top-level mention is
+        // its own root.
+        let (root_uri, root_cid) = match record.pointer("/reply/root") {
+            Some(root) => (
+                root["uri"].as_str().unwrap_or(&uri).to_string(),
+                root["cid"].as_str().unwrap_or(&cid).to_string(),
+            ),
+            None => (uri.clone(), cid.clone()),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("bluesky_uri".into(), serde_json::Value::String(uri.clone()));
+        metadata.insert("bluesky_cid".into(), serde_json::Value::String(cid));
+        metadata.insert(
+            "bluesky_root_uri".into(),
+            serde_json::Value::String(root_uri.clone()),
+        );
+        metadata.insert(
+            "bluesky_root_cid".into(),
+            serde_json::Value::String(root_cid),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(display_name.clone()),
+        );
+
+        let timestamp = notification["indexedAt"]
+            .as_str()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| ts.with_timezone(&chrono::Utc))
+            .unwrap_or_else(chrono::Utc::now);
+
+        Some(InboundMessage {
+            id: uri.clone(),
+            source: "bluesky".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id: format!("bluesky:{root_uri}"),
+            sender_id: author_handle,
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp,
+            metadata,
+            formatted_author: Some(display_name),
+        })
+    }
+
+    /// Reply routing references from inbound metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(PostRef, PostRef)> {
+        let get = |key: &str| -> crate::Result<String> {
+            message
+                .metadata
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .with_context(|| format!("missing {key} in metadata"))
+                .map_err(Into::into)
+        };
+        let root = PostRef {
+            uri: get("bluesky_root_uri")?,
+            cid: get("bluesky_root_cid")?,
+        };
+        let parent = PostRef {
+            uri: get("bluesky_uri")?,
+            cid: get("bluesky_cid")?,
+        };
+        Ok((root, parent))
+    }
+}
+
+impl Messaging for BlueskyAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Validate credentials up front so misconfiguration fails loudly
+        self.login().await?;
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = self.clone_for_task();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(error) = adapter.poll_notifications(&inbound_tx).await {
+                            tracing::warn!(%error, adapter = %adapter.runtime_key, "Bluesky notification poll failed");
+                        }
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                let (root, parent) = Self::routing(message)?;
+                self.reply_threaded(&root, &parent, &text).await
+            }
+            OutboundResponse::Reaction(_emoji) => {
+                // Closest native equivalent is a like on the triggering post
+                let (_, parent) = Self::routing(message)?;
+                let session = self.session().await?;
+                self.xrpc_post(
+                    "com.atproto.repo.createRecord",
+                    &json!({
+                        "repo": session.did,
+                        "collection": "app.bsky.feed.like",
+                        "record": {
+                            "$type": "app.bsky.feed.like",
+                            "subject": { "uri": parent.uri, "cid": parent.cid },
+                            "createdAt": chrono::Utc::now().to_rfc3339(),
+                        },
+                    }),
+                )
+                .await?;
+                Ok(())
+            }
+            OutboundResponse::File { caption, .. } => {
+                // Blob upload is not wired up yet; fall back to the caption
+                if let Some(caption) = caption {
+                    let (root, parent) = Self::routing(message)?;
+                    self.reply_threaded(&root, &parent, &caption).await?;
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, _target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Standalone post; overflow becomes a self-threaded chain
+        let chunks = split_message(&text, MAX_POST_LENGTH);
+        let mut chunks = chunks.into_iter();
+        let Some(first) = chunks.next() else {
+            return Ok(());
+        };
+        let root = self.create_post(&first, None).await?;
+        let mut parent = root.clone();
+        for chunk in chunks {
+            parent = self.create_post(&chunk, Some((&root, &parent))).await?;
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let session = self.session().await?;
+        self.xrpc_get(
+            "app.bsky.actor.getProfile",
+            &[("actor", session.did.as_str())],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!(adapter = %self.runtime_key, "Bluesky adapter shut down");
+        Ok(())
+    }
+}
+
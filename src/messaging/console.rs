@@ -0,0 +1,177 @@
+//! Console messaging adapter: stdin in, stdout out.
+//!
+//! Reads lines from standard input and prints responses to standard output,
+//! honoring streaming chunks. The adapter registers through the normal
+//! manager, so bindings and permissions can be exercised locally without any
+//! platform credentials — useful for headless testing and development.
+
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::sync::Arc;
+
+use tokio::io::AsyncBufReadExt as _;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Console adapter state.
+pub struct ConsoleAdapter {
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl ConsoleAdapter {
+    pub fn new() -> Self {
+        Self {
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Default for ConsoleAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Messaging for ConsoleAdapter {
+    fn name(&self) -> &str {
+        "console"
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(64);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        tracing::info!("console adapter reading from stdin");
+
+        tokio::spawn(async move {
+            let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+            let mut lines = stdin.lines();
+
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    line = lines.next_line() => {
+                        let text = match line {
+                            Ok(Some(line)) => line,
+                            // EOF or a read error: stdin is gone, stop reading
+                            Ok(None) => break,
+                            Err(error) => {
+                                tracing::warn!(%error, "failed to read from stdin");
+                                break;
+                            }
+                        };
+                        if text.trim().is_empty() {
+                            continue;
+                        }
+
+                        let mut metadata = HashMap::new();
+                        metadata.insert(
+                            "display_name".into(),
+                            serde_json::Value::String("console".into()),
+                        );
+
+                        let inbound = InboundMessage {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            source: "console".into(),
+                            adapter: Some("console".into()),
+                            conversation_id: "console:stdin".into(),
+                            sender_id: "console".into(),
+                            agent_id: None,
+                            content: MessageContent::Text(text),
+                            timestamp: chrono::Utc::now(),
+                            metadata,
+                            formatted_author: Some("console".into()),
+                        };
+
+                        if inbound_tx.send(inbound).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        _message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let Some(rendered) = render_response(response) else {
+            return Ok(());
+        };
+
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(rendered.as_bytes()).ok();
+        stdout.flush().ok();
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("console adapter shut down");
+        Ok(())
+    }
+}
+
+/// Renders a response for stdout, or `None` for variants that don't print.
+/// Stream chunks are emitted without a trailing newline so the output builds
+/// up on one line until `StreamEnd` terminates it.
+fn render_response(response: OutboundResponse) -> Option<String> {
+    match response {
+        OutboundResponse::Text(text)
+        | OutboundResponse::RichMessage { text, .. }
+        | OutboundResponse::ThreadReply { text, .. }
+        | OutboundResponse::Ephemeral { text, .. }
+        | OutboundResponse::ScheduledMessage { text, .. } => Some(format!("{text}\n")),
+        OutboundResponse::File {
+            filename, caption, ..
+        } => match caption {
+            Some(caption) => Some(format!("[file: {filename}] {caption}\n")),
+            None => Some(format!("[file: {filename}]\n")),
+        },
+        OutboundResponse::StreamStart => None,
+        OutboundResponse::StreamChunk(text) => Some(text),
+        OutboundResponse::StreamEnd => Some("\n".into()),
+        OutboundResponse::Reaction(_)
+        | OutboundResponse::RemoveReaction(_)
+        | OutboundResponse::Status(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_render_for_stdout() {
+        assert_eq!(
+            render_response(OutboundResponse::Text("hello".into())).as_deref(),
+            Some("hello\n")
+        );
+        assert_eq!(
+            render_response(OutboundResponse::StreamChunk("par".into())).as_deref(),
+            Some("par")
+        );
+        assert_eq!(
+            render_response(OutboundResponse::StreamEnd).as_deref(),
+            Some("\n")
+        );
+        assert!(render_response(OutboundResponse::StreamStart).is_none());
+        assert!(render_response(OutboundResponse::Reaction("eyes".into())).is_none());
+    }
+}
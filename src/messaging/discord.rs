@@ -2,7 +2,7 @@
 
 use crate::config::DiscordPermissions;
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
-use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::messaging::traits::{HistoryFetchPolicy, HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
 use anyhow::Context as _;
@@ -12,8 +12,10 @@ use serenity::all::{
     ButtonStyle, ChannelId, ChannelType, Context, CreateActionRow, CreateAttachment, CreateButton,
     CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
     CreateMessage, CreatePoll, CreatePollAnswer, CreateSelectMenu, CreateSelectMenuKind,
-    CreateSelectMenuOption, CreateThread, EditMessage, EventHandler, GatewayIntents, GetMessages,
-    Http, Interaction, Message, MessageId, ReactionType, Ready, ShardManager, User, UserId,
+    CreateSelectMenuOption, CreateThread, EditMessage, EventHandler, ExecuteWebhook,
+    GatewayIntents, GetMessages, GuildId, Http, Interaction, Message, MessageId,
+    PermissionOverwrite, PermissionOverwriteType, Permissions, ReactionType, Ready, RoleId,
+    ShardManager, User, UserId, Webhook, WebhookId,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -24,6 +26,8 @@ pub struct DiscordAdapter {
     runtime_key: String,
     token: String,
     permissions: Arc<ArcSwap<DiscordPermissions>>,
+    /// Egress proxy for REST traffic; the gateway connects directly.
+    proxy: Option<String>,
     http: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id: Arc<RwLock<Option<UserId>>>,
     /// Maps InboundMessage.id to the Discord MessageId being edited during streaming.
@@ -31,6 +35,9 @@ pub struct DiscordAdapter {
     /// Typing handles per message. Typing stops when the handle is dropped.
     typing_tasks: Arc<RwLock<HashMap<String, serenity::http::Typing>>>,
     shard_manager: Arc<RwLock<Option<Arc<ShardManager>>>>,
+    /// Voice-channel presence, joined once the gateway is ready.
+    #[cfg(feature = "discord-voice")]
+    voice: Option<Arc<crate::messaging::discord_voice::DiscordVoicePresence>>,
 }
 
 impl DiscordAdapter {
@@ -38,19 +45,34 @@ impl DiscordAdapter {
         runtime_key: impl Into<String>,
         token: impl Into<String>,
         permissions: Arc<ArcSwap<DiscordPermissions>>,
+        proxy: Option<String>,
     ) -> Self {
         Self {
             runtime_key: runtime_key.into(),
             token: token.into(),
             permissions,
+            proxy,
             http: Arc::new(RwLock::new(None)),
             bot_user_id: Arc::new(RwLock::new(None)),
             active_messages: Arc::new(RwLock::new(HashMap::new())),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shard_manager: Arc::new(RwLock::new(None)),
+            #[cfg(feature = "discord-voice")]
+            voice: None,
         }
     }
 
+    /// Attach a voice-channel presence; it joins its configured channel once
+    /// the gateway is ready.
+    #[cfg(feature = "discord-voice")]
+    pub fn with_voice(
+        mut self,
+        presence: crate::messaging::discord_voice::DiscordVoicePresence,
+    ) -> Self {
+        self.voice = Some(Arc::new(presence));
+        self
+    }
+
     async fn get_http(&self) -> anyhow::Result<Arc<Http>> {
         self.http
             .read()
@@ -102,21 +124,55 @@ impl Messaging for DiscordAdapter {
     async fn start(&self) -> crate::Result<InboundStream> {
         let (inbound_tx, inbound_rx) = mpsc::channel(256);
 
+        #[cfg(feature = "discord-voice")]
+        if let Some(presence) = &self.voice {
+            presence.bind(&self.runtime_key, inbound_tx.clone()).await;
+        }
+
         let handler = Handler {
             inbound_tx,
             runtime_key: self.runtime_key.clone(),
             permissions: self.permissions.clone(),
             http_slot: self.http.clone(),
             bot_user_id_slot: self.bot_user_id.clone(),
+            #[cfg(feature = "discord-voice")]
+            voice: self.voice.clone(),
         };
 
         let intents = GatewayIntents::GUILD_MESSAGES
             | GatewayIntents::DIRECT_MESSAGES
             | GatewayIntents::MESSAGE_CONTENT
             | GatewayIntents::GUILDS;
+        #[cfg(feature = "discord-voice")]
+        let intents = if self.voice.is_some() {
+            intents | GatewayIntents::GUILD_VOICE_STATES
+        } else {
+            intents
+        };
 
-        let mut client = serenity::Client::builder(&self.token, intents)
-            .event_handler(handler)
+        // A configured proxy applies to REST calls (message sends, file
+        // uploads); the gateway websocket still connects directly.
+        let client_builder = match self
+            .proxy
+            .as_deref()
+            .map(str::trim)
+            .filter(|proxy| !proxy.is_empty())
+        {
+            Some(proxy) => {
+                let http = serenity::http::HttpBuilder::new(&self.token)
+                    .client(crate::http::client_with_proxy(Some(proxy)))
+                    .build();
+                serenity::client::ClientBuilder::new_with_http(http, intents)
+            }
+            None => serenity::Client::builder(&self.token, intents),
+        }
+        .event_handler(handler);
+        #[cfg(feature = "discord-voice")]
+        let client_builder = match &self.voice {
+            Some(presence) => client_builder.voice_manager_arc(presence.songbird()),
+            None => client_builder,
+        };
+        let mut client = client_builder
             .await
             .context("failed to build discord client")?;
 
@@ -138,6 +194,17 @@ impl Messaging for DiscordAdapter {
         message: &InboundMessage,
         response: OutboundResponse,
     ) -> crate::Result<()> {
+        // Conversations born from voice transcripts are answered out loud
+        #[cfg(feature = "discord-voice")]
+        if message.metadata.contains_key("discord_voice_channel_id")
+            && let Some(presence) = &self.voice
+        {
+            return match response {
+                OutboundResponse::Text(text) => presence.speak(&text).await,
+                _ => Ok(()),
+            };
+        }
+
         let http = self.get_http().await?;
         let channel_id = self.extract_channel_id(message)?;
 
@@ -272,7 +339,7 @@ impl Messaging for DiscordAdapter {
                 self.stop_typing(message).await;
                 let reply_to = Self::extract_reply_message_id(message);
 
-                let attachment = CreateAttachment::bytes(data, &filename);
+                let attachment = CreateAttachment::bytes(data.to_vec(), &filename);
                 let mut builder = CreateMessage::new().add_file(attachment);
                 if let Some(caption_text) = caption {
                     builder = builder.content(caption_text);
@@ -293,6 +360,11 @@ impl Messaging for DiscordAdapter {
                     .and_then(|v| v.as_u64())
                     .context("missing discord_message_id for reaction")?;
 
+                // Resolve intent names and shortcodes to unicode; Discord
+                // rejects anything that isn't a raw emoji.
+                let emoji = crate::messaging::reactions::to_unicode(&emoji)
+                    .map(str::to_string)
+                    .unwrap_or(emoji);
                 channel_id
                     .create_reaction(
                         &*http,
@@ -390,6 +462,34 @@ impl Messaging for DiscordAdapter {
     async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
         let http = self.get_http().await?;
 
+        // Support "webhook:{id}/{token}" targets from notification URLs
+        if let Some(webhook_ref) = target.strip_prefix("webhook:") {
+            let (webhook_id, token) = webhook_ref
+                .split_once('/')
+                .context("invalid discord webhook broadcast target")?;
+            let webhook = Webhook::from_id_with_token(
+                &*http,
+                WebhookId::new(
+                    webhook_id
+                        .parse::<u64>()
+                        .context("invalid discord webhook id")?,
+                ),
+                token,
+            )
+            .await
+            .context("failed to resolve discord webhook")?;
+
+            if let OutboundResponse::Text(text) = response {
+                for chunk in split_message(&text, 2000) {
+                    webhook
+                        .execute(&*http, false, ExecuteWebhook::new().content(&chunk))
+                        .await
+                        .context("failed to execute discord webhook")?;
+                }
+            }
+            return Ok(());
+        }
+
         // Support "dm:{user_id}" targets for opening DM channels
         let channel_id = if let Some(user_id_str) = target.strip_prefix("dm:") {
             let user_id = UserId::new(
@@ -489,12 +589,50 @@ impl Messaging for DiscordAdapter {
             .await
             .context("failed to fetch discord message history")?;
 
+        // Follow the referenced-message chain above the trigger so a reply
+        // to an old message carries its context even when the chain falls
+        // outside the recent window. Fail open: the window alone is still
+        // useful history.
+        let policy = HistoryFetchPolicy::from_limit(limit);
+        let fetched_ids: std::collections::HashSet<MessageId> =
+            messages.iter().map(|msg| msg.id).collect();
+        let mut chain: Vec<Message> = Vec::new();
+        let mut next_id = channel_id
+            .message(&*http, MessageId::new(message_id))
+            .await
+            .ok()
+            .and_then(|trigger| {
+                trigger
+                    .message_reference
+                    .and_then(|reference| reference.message_id)
+            });
+        while let Some(id) = next_id {
+            if chain.len() >= policy.chain_depth || fetched_ids.contains(&id) {
+                break;
+            }
+            match channel_id.message(&*http, id).await {
+                Ok(referenced) => {
+                    next_id = referenced
+                        .message_reference
+                        .as_ref()
+                        .and_then(|reference| reference.message_id);
+                    chain.push(referenced);
+                }
+                Err(error) => {
+                    tracing::debug!(%error, "failed to fetch referenced discord message");
+                    break;
+                }
+            }
+        }
+
         let bot_user_id = self.bot_user_id.read().await;
 
-        // Messages come back newest-first from Discord, reverse to chronological
-        let history: Vec<HistoryMessage> = messages
+        // Messages come back newest-first from Discord (and the reply chain
+        // was walked upwards), reverse to chronological with the chain first
+        let history: Vec<HistoryMessage> = chain
             .iter()
             .rev()
+            .chain(messages.iter().rev())
             .map(|message| {
                 let is_bot = bot_user_id
                     .map(|bot_id| message.author.id == bot_id)
@@ -563,6 +701,8 @@ impl Messaging for DiscordAdapter {
 // -- Serenity EventHandler --
 
 struct Handler {
+    #[cfg(feature = "discord-voice")]
+    voice: Option<Arc<crate::messaging::discord_voice::DiscordVoicePresence>>,
     inbound_tx: mpsc::Sender<InboundMessage>,
     runtime_key: String,
     permissions: Arc<ArcSwap<DiscordPermissions>>,
@@ -578,6 +718,11 @@ impl EventHandler for Handler {
         *self.http_slot.write().await = Some(ctx.http.clone());
         *self.bot_user_id_slot.write().await = Some(ready.user.id);
         tracing::info!(guild_count = ready.guilds.len(), "discord guilds available");
+
+        #[cfg(feature = "discord-voice")]
+        if let Some(presence) = &self.voice {
+            presence.join_configured_channel().await;
+        }
     }
 
     async fn message(&self, ctx: Context, message: Message) {
@@ -1088,6 +1233,120 @@ fn build_poll(
     p
 }
 
+/// Spawn a background task that periodically syncs Discord channel
+/// visibility into the adapter's permission filters.
+///
+/// Every tick, the task reads each guild's roles and channel overwrites and
+/// restricts `channel_filter` to channels the `@everyone` role can see —
+/// intersected with any channel list already configured on the bindings, so
+/// syncing only ever narrows access. A config reload rebuilds the filters from
+/// bindings alone; the next tick re-applies the visibility restriction.
+pub fn spawn_permission_sync(
+    runtime_key: impl Into<String>,
+    token: &str,
+    permissions: Arc<ArcSwap<DiscordPermissions>>,
+    interval_secs: u64,
+) {
+    let runtime_key = runtime_key.into();
+    let http = Arc::new(Http::new(token));
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(interval_secs)).await;
+
+            let current = permissions.load_full();
+
+            // Sync the guilds the bot is restricted to, or everything the
+            // bot can see when no guild filter is configured.
+            let guild_ids: Vec<GuildId> = match &current.guild_filter {
+                Some(ids) => ids.iter().map(|id| GuildId::new(*id)).collect(),
+                None => match http.get_guilds(None, None).await {
+                    Ok(guilds) => guilds.iter().map(|g| g.id).collect(),
+                    Err(error) => {
+                        tracing::warn!(adapter = %runtime_key, %error, "permission sync failed to list guilds");
+                        continue;
+                    }
+                },
+            };
+
+            let mut channel_filter = current.channel_filter.clone();
+
+            for guild_id in guild_ids {
+                let roles = match http.get_guild_roles(guild_id).await {
+                    Ok(roles) => roles,
+                    Err(error) => {
+                        tracing::warn!(adapter = %runtime_key, guild_id = %guild_id, %error, "permission sync failed to fetch roles");
+                        continue;
+                    }
+                };
+                let everyone_role = RoleId::new(guild_id.get());
+                let Some(base) = roles
+                    .iter()
+                    .find(|role| role.id == everyone_role)
+                    .map(|role| role.permissions)
+                else {
+                    continue;
+                };
+
+                let channels = match http.get_channels(guild_id).await {
+                    Ok(channels) => channels,
+                    Err(error) => {
+                        tracing::warn!(adapter = %runtime_key, guild_id = %guild_id, %error, "permission sync failed to fetch channels");
+                        continue;
+                    }
+                };
+
+                let visible: Vec<u64> = channels
+                    .iter()
+                    .filter(|channel| {
+                        everyone_can_view(base, &channel.permission_overwrites, everyone_role)
+                    })
+                    .map(|channel| channel.id.get())
+                    .collect();
+
+                match channel_filter.get_mut(&guild_id.get()) {
+                    Some(existing) => existing.retain(|id| visible.contains(id)),
+                    None => {
+                        channel_filter.insert(guild_id.get(), visible);
+                    }
+                }
+            }
+
+            if channel_filter != current.channel_filter {
+                tracing::info!(adapter = %runtime_key, "permission sync updated channel filters");
+                permissions.store(Arc::new(DiscordPermissions {
+                    guild_filter: current.guild_filter.clone(),
+                    channel_filter,
+                    dm_allowed_users: current.dm_allowed_users.clone(),
+                    allow_bot_messages: current.allow_bot_messages,
+                }));
+            }
+        }
+    });
+}
+
+/// Whether the `@everyone` role can view a channel, given the role's base
+/// guild permissions and the channel's overwrites.
+fn everyone_can_view(
+    base: Permissions,
+    overwrites: &[PermissionOverwrite],
+    everyone_role: RoleId,
+) -> bool {
+    if base.contains(Permissions::ADMINISTRATOR) {
+        return true;
+    }
+    let mut perms = base;
+    for overwrite in overwrites {
+        if let PermissionOverwriteType::Role(role_id) = overwrite.kind
+            && role_id == everyone_role
+        {
+            perms &= !overwrite.deny;
+            perms |= overwrite.allow;
+        }
+    }
+    perms.contains(Permissions::VIEW_CHANNEL)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1149,4 +1408,48 @@ mod tests {
         let _ = build_poll(&poll);
         // Again, can't easily inspect CreatePoll fields, but we verify it runs.
     }
+
+    #[test]
+    fn test_everyone_can_view_respects_overwrites() {
+        let everyone = RoleId::new(1);
+        let base = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+
+        // No overwrites: base permissions decide
+        assert!(everyone_can_view(base, &[], everyone));
+
+        // A deny overwrite on @everyone hides the channel
+        let deny = PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(everyone),
+        };
+        assert!(!everyone_can_view(base, std::slice::from_ref(&deny), everyone));
+
+        // Overwrites on other roles don't affect @everyone
+        let other_deny = PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId::new(2)),
+        };
+        assert!(everyone_can_view(base, &[other_deny], everyone));
+
+        // An allow overwrite reveals a channel hidden at the base level
+        let allow = PermissionOverwrite {
+            allow: Permissions::VIEW_CHANNEL,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(everyone),
+        };
+        assert!(everyone_can_view(
+            Permissions::SEND_MESSAGES,
+            &[allow],
+            everyone
+        ));
+
+        // Administrators see everything regardless of overwrites
+        assert!(everyone_can_view(
+            Permissions::ADMINISTRATOR,
+            std::slice::from_ref(&deny),
+            everyone
+        ));
+    }
 }
@@ -0,0 +1,402 @@
+//! Discord voice-channel presence (behind the `discord-voice` cargo feature).
+//!
+//! When configured, the Discord adapter joins a voice channel through
+//! songbird, buffers decoded per-speaker audio from voice ticks, and cuts an
+//! utterance once the speaker goes quiet. Utterances are transcribed through
+//! an OpenAI-compatible speech API and routed as inbound messages; text
+//! responses for those conversations are synthesized with the same API and
+//! played back into the channel.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use songbird::events::context_data::VoiceTick;
+use songbird::model::payload::Speaking;
+use songbird::{CoreEvent, Event, EventContext, Songbird};
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+use crate::config::DiscordVoiceConfig;
+use crate::{InboundMessage, MessageContent};
+
+/// Voice ticks arrive every 20ms; this many silent ticks end an utterance.
+const SILENCE_TICKS_TO_FINALIZE: u32 = 40;
+
+/// Decoded voice is 48kHz stereo; drop utterances shorter than ~300ms of
+/// audio since they are almost always breath noise or keyboard clatter.
+const MIN_UTTERANCE_SAMPLES: usize = 48_000 * 2 * 3 / 10;
+
+/// Cap utterance buffers at 60 seconds so a hot mic can't grow unbounded.
+const MAX_UTTERANCE_SAMPLES: usize = 48_000 * 2 * 60;
+
+/// Voice presence for one Discord adapter: owns the songbird manager, the
+/// speech API client, and the inbound sender for transcripts.
+pub struct DiscordVoicePresence {
+    config: DiscordVoiceConfig,
+    speech: Arc<SpeechApi>,
+    songbird: Arc<Songbird>,
+    inbound_tx: RwLock<Option<mpsc::Sender<InboundMessage>>>,
+    runtime_key: RwLock<String>,
+}
+
+impl DiscordVoicePresence {
+    pub fn new(config: DiscordVoiceConfig) -> Self {
+        let speech = Arc::new(SpeechApi::from_config(&config));
+        Self {
+            config,
+            speech,
+            songbird: Songbird::serenity(),
+            inbound_tx: RwLock::new(None),
+            runtime_key: RwLock::new(String::new()),
+        }
+    }
+
+    /// The songbird manager to register as the serenity voice manager.
+    pub fn songbird(&self) -> Arc<Songbird> {
+        self.songbird.clone()
+    }
+
+    /// Bind the inbound sender transcripts are routed through.
+    pub async fn bind(&self, runtime_key: &str, inbound_tx: mpsc::Sender<InboundMessage>) {
+        *self.runtime_key.write().await = runtime_key.to_string();
+        *self.inbound_tx.write().await = Some(inbound_tx);
+    }
+
+    /// Join the configured voice channel and install the receive handler.
+    /// Called from the serenity `ready` event once the gateway is up.
+    pub async fn join_configured_channel(&self) {
+        let (guild_id, channel_id) = match self.parse_ids() {
+            Ok(ids) => ids,
+            Err(error) => {
+                tracing::error!(%error, "invalid discord voice channel configuration");
+                return;
+            }
+        };
+
+        match self.songbird.join(guild_id, channel_id).await {
+            Ok(call) => {
+                let handler = ReceiveHandler {
+                    inner: Arc::new(ReceiveState {
+                        speech: self.speech.clone(),
+                        inbound_tx: self.inbound_tx.read().await.clone(),
+                        runtime_key: self.runtime_key.read().await.clone(),
+                        guild_id: self.config.guild_id.clone(),
+                        channel_id: self.config.channel_id.clone(),
+                        speakers: Mutex::new(HashMap::new()),
+                        ssrc_users: Mutex::new(HashMap::new()),
+                    }),
+                };
+                let mut call = call.lock().await;
+                call.add_global_event(CoreEvent::SpeakingStateUpdate.into(), handler.clone());
+                call.add_global_event(CoreEvent::VoiceTick.into(), handler);
+                tracing::info!(
+                    guild_id = %self.config.guild_id,
+                    channel_id = %self.config.channel_id,
+                    "joined discord voice channel"
+                );
+            }
+            Err(error) => {
+                tracing::error!(%error, "failed to join discord voice channel");
+            }
+        }
+    }
+
+    /// Synthesize `text` and play it into the joined channel.
+    pub async fn speak(&self, text: &str) -> crate::Result<()> {
+        let (guild_id, _) = self.parse_ids()?;
+        let call = self
+            .songbird
+            .get(guild_id)
+            .context("not connected to a voice channel")?;
+        let audio = self.speech.synthesize(text).await?;
+        call.lock().await.play_input(audio.into());
+        Ok(())
+    }
+
+    fn parse_ids(&self) -> crate::Result<(songbird::id::GuildId, songbird::id::ChannelId)> {
+        let guild: u64 = self
+            .config
+            .guild_id
+            .parse()
+            .context("discord voice guild_id is not a snowflake")?;
+        let channel: u64 = self
+            .config
+            .channel_id
+            .parse()
+            .context("discord voice channel_id is not a snowflake")?;
+        Ok((
+            songbird::id::GuildId::from(guild),
+            songbird::id::ChannelId::from(channel),
+        ))
+    }
+}
+
+/// Per-speaker utterance accumulation.
+struct UtteranceBuffer {
+    samples: Vec<i16>,
+    silent_ticks: u32,
+}
+
+struct ReceiveState {
+    speech: Arc<SpeechApi>,
+    inbound_tx: Option<mpsc::Sender<InboundMessage>>,
+    runtime_key: String,
+    guild_id: String,
+    channel_id: String,
+    /// SSRC -> in-flight utterance.
+    speakers: Mutex<HashMap<u32, UtteranceBuffer>>,
+    /// SSRC -> Discord user ID, learned from speaking-state updates.
+    ssrc_users: Mutex<HashMap<u32, u64>>,
+}
+
+#[derive(Clone)]
+struct ReceiveHandler {
+    inner: Arc<ReceiveState>,
+}
+
+#[async_trait]
+impl songbird::EventHandler for ReceiveHandler {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        match ctx {
+            EventContext::SpeakingStateUpdate(Speaking { ssrc, user_id, .. }) => {
+                if let Some(user_id) = user_id {
+                    self.inner.ssrc_users.lock().await.insert(*ssrc, user_id.0);
+                }
+            }
+            EventContext::VoiceTick(tick) => {
+                self.inner.handle_tick(tick).await;
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+impl ReceiveState {
+    async fn handle_tick(&self, tick: &VoiceTick) {
+        let mut speakers = self.speakers.lock().await;
+
+        for (ssrc, data) in &tick.speaking {
+            let Some(decoded) = &data.decoded_voice else {
+                continue;
+            };
+            let buffer = speakers.entry(*ssrc).or_insert_with(|| UtteranceBuffer {
+                samples: Vec::new(),
+                silent_ticks: 0,
+            });
+            buffer.silent_ticks = 0;
+            if buffer.samples.len() < MAX_UTTERANCE_SAMPLES {
+                buffer.samples.extend_from_slice(decoded);
+            }
+        }
+
+        let mut finished = Vec::new();
+        for (ssrc, buffer) in speakers.iter_mut() {
+            if tick.speaking.contains_key(ssrc) {
+                continue;
+            }
+            buffer.silent_ticks += 1;
+            if buffer.silent_ticks >= SILENCE_TICKS_TO_FINALIZE {
+                finished.push(*ssrc);
+            }
+        }
+
+        for ssrc in finished {
+            let Some(buffer) = speakers.remove(&ssrc) else {
+                continue;
+            };
+            if buffer.samples.len() < MIN_UTTERANCE_SAMPLES {
+                continue;
+            }
+            let user_id = self.ssrc_users.lock().await.get(&ssrc).copied();
+            self.finalize_utterance(buffer.samples, user_id).await;
+        }
+    }
+
+    async fn finalize_utterance(&self, samples: Vec<i16>, user_id: Option<u64>) {
+        let Some(inbound_tx) = self.inbound_tx.clone() else {
+            return;
+        };
+        let speech = self.speech.clone();
+        let runtime_key = self.runtime_key.clone();
+        let guild_id = self.guild_id.clone();
+        let channel_id = self.channel_id.clone();
+
+        // Transcription is network-bound; don't hold up the tick handler
+        tokio::spawn(async move {
+            let wav = pcm_to_wav(&samples, 48_000, 2);
+            let text = match speech.transcribe(wav).await {
+                Ok(text) => text,
+                Err(error) => {
+                    tracing::warn!(%error, "voice transcription failed");
+                    return;
+                }
+            };
+            let text = text.trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+
+            let sender_id = user_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            let mut metadata = HashMap::new();
+            if let Ok(channel) = channel_id.parse::<u64>() {
+                metadata.insert("discord_channel_id".into(), serde_json::json!(channel));
+                metadata.insert(
+                    "discord_voice_channel_id".into(),
+                    serde_json::json!(channel),
+                );
+            }
+            if let Ok(guild) = guild_id.parse::<u64>() {
+                metadata.insert("discord_guild_id".into(), serde_json::json!(guild));
+            }
+
+            let inbound = InboundMessage {
+                id: uuid::Uuid::new_v4().to_string(),
+                source: "discord".into(),
+                adapter: Some(runtime_key),
+                conversation_id: format!("discord:{channel_id}"),
+                sender_id,
+                agent_id: None,
+                content: MessageContent::Text(text),
+                timestamp: chrono::Utc::now(),
+                metadata,
+                formatted_author: None,
+            };
+            if inbound_tx.send(inbound).await.is_err() {
+                tracing::warn!("inbound channel closed; dropping voice transcript");
+            }
+        });
+    }
+}
+
+/// OpenAI-compatible speech endpoints used for STT and TTS.
+struct SpeechApi {
+    client: reqwest::Client,
+    api_base: String,
+    api_key: String,
+    stt_model: String,
+    tts_model: String,
+    tts_voice: String,
+}
+
+impl SpeechApi {
+    fn from_config(config: &DiscordVoiceConfig) -> Self {
+        Self {
+            client: crate::http::client(),
+            api_base: config.api_base.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            stt_model: config.stt_model.clone(),
+            tts_model: config.tts_model.clone(),
+            tts_voice: config.tts_voice.clone(),
+        }
+    }
+
+    async fn transcribe(&self, wav: Vec<u8>) -> crate::Result<String> {
+        let part = reqwest::multipart::Part::bytes(wav)
+            .file_name("utterance.wav")
+            .mime_str("audio/wav")
+            .context("failed to build transcription part")?;
+        let form = reqwest::multipart::Form::new()
+            .part("file", part)
+            .text("model", self.stt_model.clone());
+
+        let response = self
+            .client
+            .post(format!("{}/audio/transcriptions", self.api_base))
+            .bearer_auth(&self.api_key)
+            .multipart(form)
+            .send()
+            .await
+            .context("failed to call transcription endpoint")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("transcription failed: HTTP {status}: {body}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to parse transcription response")?;
+        Ok(body
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn synthesize(&self, text: &str) -> crate::Result<Vec<u8>> {
+        let response = self
+            .client
+            .post(format!("{}/audio/speech", self.api_base))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "model": self.tts_model,
+                "voice": self.tts_voice,
+                "input": text,
+                "response_format": "mp3",
+            }))
+            .send()
+            .await
+            .context("failed to call speech endpoint")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("speech synthesis failed: HTTP {status}: {body}").into());
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .context("failed to read synthesized audio")?;
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Wrap raw 16-bit PCM in a minimal RIFF/WAVE container.
+fn pcm_to_wav(samples: &[i16], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let byte_rate = sample_rate * u32::from(channels) * 2;
+    let block_align = channels * 2;
+
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_header_describes_the_payload() {
+        let samples = [0i16, 1, -1, 32_767];
+        let wav = pcm_to_wav(&samples, 48_000, 2);
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+        assert_eq!(
+            u32::from_le_bytes(wav[24..28].try_into().unwrap()),
+            48_000
+        );
+    }
+}
@@ -1,4 +1,10 @@
-//! Email messaging adapter using IMAP polling and SMTP delivery.
+//! Email messaging adapter using IMAP and SMTP delivery.
+//!
+//! Inbound mail is fetched by polling, but between polls the adapter holds an
+//! IMAP IDLE connection when the server advertises the capability, so new
+//! messages trigger an immediate poll instead of waiting out
+//! `poll_interval_secs`. Servers without IDLE fall back to plain
+//! interval polling.
 
 use crate::config::EmailConfig;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
@@ -16,15 +22,43 @@ use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::sync::{RwLock, mpsc, watch};
+use tokio::sync::{Mutex, RwLock, mpsc, watch};
 use tokio::task::JoinHandle;
 
 const EMAIL_MAX_RETRY_BACKOFF_SECS: u64 = 300;
 
+/// How long a single IDLE wait lasts before the connection is refreshed with
+/// a regular poll. RFC 2177 requires re-issuing IDLE at least every
+/// 29 minutes; staying well under that also survives aggressive NAT timeouts.
+const EMAIL_IDLE_REFRESH_SECS: u64 = 9 * 60;
+
+/// How long a `File` response is held back so that several generated files
+/// arriving in quick succession are coalesced into one email with multiple
+/// attachments instead of one email per file.
+const EMAIL_ATTACHMENT_BATCH_SECS: u64 = 2;
+
+/// Idle IMAP sessions retained per account before extra ones are logged out.
+const EMAIL_POOL_MAX_IDLE: usize = 2;
+
+/// Pooled sessions idle longer than this are discarded instead of reused;
+/// most providers drop unauthenticated-traffic connections well before their
+/// advertised 30-minute autologout.
+const EMAIL_POOL_IDLE_SECS: u64 = 60;
+
 type ImapSession = imap::Session<native_tls::TlsStream<std::net::TcpStream>>;
 
+/// File in the instance directory holding emails queued for delayed delivery.
+const SCHEDULED_EMAILS_FILE: &str = "scheduled_emails.json";
+
+/// Window over which per-sender reply throttling is enforced.
+const EMAIL_REPLY_THROTTLE_WINDOW_SECS: u64 = 3600;
+
+/// References chains deeper than this are treated as autoresponder loops and
+/// the message is dropped; legitimate human threads rarely get near it.
+const EMAIL_MAX_REFERENCES_DEPTH: usize = 25;
+
 #[derive(Clone)]
-struct EmailPollConfig {
+pub(crate) struct EmailPollConfig {
     imap_host: String,
     imap_port: u16,
     imap_username: String,
@@ -36,9 +70,38 @@ struct EmailPollConfig {
     poll_interval: Duration,
     allowed_senders: Vec<String>,
     max_body_bytes: usize,
+    max_attachment_bytes: usize,
+    size_policy_reply: bool,
+    spam_patterns: Vec<String>,
+    spam_scorer_command: String,
+    drop_failed_auth: bool,
     runtime_key: String,
 }
 
+/// Poll/parse settings derived from an [`EmailConfig`], shared by the poll
+/// loop, mailbox search/actions, and the JMAP transport's MIME parsing.
+pub(crate) fn poll_config_from(config: &EmailConfig, runtime_key: String) -> EmailPollConfig {
+    EmailPollConfig {
+        imap_host: config.imap_host.clone(),
+        imap_port: config.imap_port,
+        imap_username: config.imap_username.clone(),
+        imap_password: config.imap_password.clone(),
+        imap_use_tls: config.imap_use_tls,
+        from_address: config.from_address.clone(),
+        smtp_username: config.smtp_username.clone(),
+        folders: config.folders.clone(),
+        poll_interval: Duration::from_secs(config.poll_interval_secs.max(5)),
+        allowed_senders: config.allowed_senders.clone(),
+        max_body_bytes: config.max_body_bytes.max(1024),
+        max_attachment_bytes: config.max_attachment_bytes.max(1024),
+        size_policy_reply: config.size_policy_reply,
+        spam_patterns: config.spam_patterns.clone(),
+        spam_scorer_command: config.spam_scorer_command.clone(),
+        drop_failed_auth: config.drop_failed_auth,
+        runtime_key,
+    }
+}
+
 struct HistoryEntry {
     timestamp: chrono::DateTime<chrono::Utc>,
     message: HistoryMessage,
@@ -70,6 +133,7 @@ pub struct EmailSearchHit {
 }
 
 /// Email adapter state.
+#[derive(Clone)]
 pub struct EmailAdapter {
     runtime_key: String,
     imap_host: String,
@@ -88,9 +152,34 @@ pub struct EmailAdapter {
     allowed_senders: Vec<String>,
     max_body_bytes: usize,
     max_attachment_bytes: usize,
+    size_policy_reply: bool,
+    max_replies_per_hour: usize,
+    spam_patterns: Vec<String>,
+    spam_scorer_command: String,
+    drop_failed_auth: bool,
+    pgp_sign: bool,
+    pgp_key_id: String,
+    dkim_selector: String,
+    dkim_key_path: String,
+    custom_headers: HashMap<String, String>,
+    reply_all: bool,
     smtp_transport: AsyncSmtpTransport<Tokio1Executor>,
     shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
     poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+    pending_files: Arc<Mutex<HashMap<String, PendingFileEmail>>>,
+    /// Timestamps of recent replies per recipient, for the hourly throttle.
+    reply_times: Arc<Mutex<HashMap<String, Vec<std::time::Instant>>>>,
+}
+
+/// Attachments queued for one outbound email while the batch window is open.
+struct PendingFileEmail {
+    recipient: String,
+    subject: String,
+    in_reply_to: Option<String>,
+    references: Vec<String>,
+    cc: Vec<String>,
+    captions: Vec<String>,
+    attachments: Vec<(String, bytes::Bytes, String)>,
 }
 
 impl std::fmt::Debug for EmailAdapter {
@@ -112,6 +201,7 @@ impl std::fmt::Debug for EmailAdapter {
             .field("allowed_senders", &"[REDACTED]")
             .field("max_body_bytes", &self.max_body_bytes)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field("reply_all", &self.reply_all)
             .finish()
     }
 }
@@ -145,6 +235,24 @@ impl EmailAdapter {
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes,
             max_attachment_bytes: config.max_attachment_bytes,
+            size_policy_reply: config.size_policy_reply,
+            max_replies_per_hour: config.max_replies_per_hour,
+            spam_patterns: config.spam_patterns.clone(),
+            spam_scorer_command: config.spam_scorer_command.clone(),
+            drop_failed_auth: config.drop_failed_auth,
+            pgp_sign: config.pgp_sign,
+            pgp_key_id: config.pgp_key_id.clone(),
+            dkim_selector: config.dkim_selector.clone(),
+            dkim_key_path: config.dkim_key_path.clone(),
+            custom_headers: config.custom_headers.clone(),
+            reply_all: config.reply_all,
+            backend: config.backend,
+            graph_tenant_id: config.graph_tenant_id.clone(),
+            graph_client_id: config.graph_client_id.clone(),
+            graph_client_secret: config.graph_client_secret.clone(),
+            graph_user: config.graph_user.clone(),
+            jmap_session_url: config.jmap_session_url.clone(),
+            jmap_token: config.jmap_token.clone(),
             instances: Vec::new(),
         };
         Self::build(runtime_key.into(), &email_config)
@@ -184,9 +292,22 @@ impl EmailAdapter {
             allowed_senders: config.allowed_senders.clone(),
             max_body_bytes: config.max_body_bytes.max(1024),
             max_attachment_bytes: config.max_attachment_bytes.max(1024),
+            size_policy_reply: config.size_policy_reply,
+            max_replies_per_hour: config.max_replies_per_hour,
+            spam_patterns: config.spam_patterns.clone(),
+            spam_scorer_command: config.spam_scorer_command.clone(),
+            drop_failed_auth: config.drop_failed_auth,
+            pgp_sign: config.pgp_sign,
+            pgp_key_id: config.pgp_key_id.clone(),
+            dkim_selector: config.dkim_selector.clone(),
+            dkim_key_path: config.dkim_key_path.clone(),
+            custom_headers: config.custom_headers.clone(),
+            reply_all: config.reply_all,
             smtp_transport,
             shutdown_tx: Arc::new(RwLock::new(None)),
             poll_task: Arc::new(RwLock::new(None)),
+            pending_files: Arc::new(Mutex::new(HashMap::new())),
+            reply_times: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -203,6 +324,11 @@ impl EmailAdapter {
             poll_interval: self.poll_interval,
             allowed_senders: self.allowed_senders.clone(),
             max_body_bytes: self.max_body_bytes,
+            max_attachment_bytes: self.max_attachment_bytes,
+            size_policy_reply: self.size_policy_reply,
+            spam_patterns: self.spam_patterns.clone(),
+            spam_scorer_command: self.spam_scorer_command.clone(),
+            drop_failed_auth: self.drop_failed_auth,
             runtime_key: self.runtime_key.clone(),
         }
     }
@@ -215,6 +341,7 @@ impl EmailAdapter {
         Ok(Mailbox::new(self.from_name.clone(), from_address))
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn send_email(
         &self,
         recipient: &str,
@@ -222,7 +349,8 @@ impl EmailAdapter {
         body: String,
         in_reply_to: Option<String>,
         references: Vec<String>,
-        attachment: Option<(String, Vec<u8>, String)>,
+        cc: &[String],
+        attachments: Vec<(String, bytes::Bytes, String)>,
     ) -> crate::Result<()> {
         let recipient_mailbox = parse_mailbox(recipient)
             .with_context(|| format!("invalid recipient address '{recipient}'"))?;
@@ -246,35 +374,324 @@ impl EmailAdapter {
             }
         }
 
-        let message = if let Some((filename, data, mime_type)) = attachment {
-            if data.len() > self.max_attachment_bytes {
-                return Err(anyhow::anyhow!(
-                    "attachment '{filename}' exceeds max_attachment_bytes ({} > {})",
-                    data.len(),
-                    self.max_attachment_bytes
-                )
-                .into());
+        for cc_address in cc {
+            match parse_mailbox(cc_address) {
+                Ok(mailbox) => builder = builder.cc(mailbox),
+                Err(error) => {
+                    tracing::warn!(%error, cc = %cc_address, "skipping invalid CC address");
+                }
             }
+        }
+
+        // Clearsigned mail carries only the signed text/plain part: clients
+        // prefer an HTML alternative, and an unsigned one would be rendered
+        // in place of the signature. Unsigned mail keeps the raw markdown as
+        // text/plain plus an HTML rendering, so code blocks, links, and
+        // tables display properly while text-only readers lose nothing.
+        let signed = self
+            .pgp_sign
+            .then(|| clearsign_with_pgp(&body, &self.pgp_key_id))
+            .flatten();
+        let alternative = match signed {
+            Some(signed) => MultiPart::alternative().singlepart(SinglePart::plain(signed)),
+            None => {
+                let html = markdown_to_html(&body);
+                MultiPart::alternative_plain_html(body, html)
+            }
+        };
+
+        let message = if attachments.is_empty() {
+            builder
+                .multipart(alternative)
+                .context("failed to build email body")?
+        } else {
+            let mut multipart = MultiPart::mixed().multipart(alternative);
+            for (filename, data, mime_type) in attachments {
+                if data.len() > self.max_attachment_bytes {
+                    return Err(anyhow::anyhow!(
+                        "attachment '{filename}' exceeds max_attachment_bytes ({} > {})",
+                        data.len(),
+                        self.max_attachment_bytes
+                    )
+                    .into());
+                }
 
-            let content_type = ContentType::parse(&mime_type).unwrap_or(ContentType::TEXT_PLAIN);
-            let attachment = EmailAttachment::new(filename).body(data, content_type);
-            let multipart = MultiPart::mixed()
-                .singlepart(SinglePart::plain(body))
-                .singlepart(attachment);
+                let content_type =
+                    ContentType::parse(&mime_type).unwrap_or(ContentType::TEXT_PLAIN);
+                multipart =
+                    multipart.singlepart(EmailAttachment::new(filename).body(data.to_vec(), content_type));
+            }
             builder
                 .multipart(multipart)
                 .context("failed to build multipart email")?
-        } else {
-            builder.body(body).context("failed to build email body")?
         };
 
+        let dkim_enabled = !self.dkim_selector.is_empty() && !self.dkim_key_path.is_empty();
+        if self.custom_headers.is_empty() && !dkim_enabled {
+            self.smtp_transport
+                .send(message)
+                .await
+                .context("failed to send email")?;
+            return Ok(());
+        }
+
+        // Custom headers and DKIM both work on the serialized RFC 5322 bytes:
+        // lettre's builder has no raw-header escape hatch, and the signature
+        // must cover the exact bytes put on the wire.
+        let envelope = message.envelope().clone();
+        let mut raw = message.formatted();
+        for (name, value) in &self.custom_headers {
+            let mut line = format!("{name}: {value}\r\n").into_bytes();
+            line.extend_from_slice(&raw);
+            raw = line;
+        }
+        if dkim_enabled {
+            let domain = self
+                .from_address
+                .rsplit_once('@')
+                .map(|(_, domain)| domain)
+                .unwrap_or_default();
+            match dkim_signature_header(
+                &raw,
+                domain,
+                &self.dkim_selector,
+                std::path::Path::new(&self.dkim_key_path),
+            ) {
+                Ok(header) => {
+                    let mut line = header.into_bytes();
+                    line.extend_from_slice(&raw);
+                    raw = line;
+                }
+                // Deliverability beats signing: a missing key or openssl must
+                // not bounce the reply.
+                Err(error) => tracing::warn!(%error, "DKIM signing failed; sending unsigned"),
+            }
+        }
+
         self.smtp_transport
-            .send(message)
+            .send_raw(&envelope, &raw)
             .await
             .context("failed to send email")?;
 
         Ok(())
     }
+
+    /// Check the per-recipient reply budget, recording the reply when it is
+    /// allowed. Returns true when the reply should be suppressed.
+    async fn reply_throttled(&self, recipient: &str) -> bool {
+        if self.max_replies_per_hour == 0 {
+            return false;
+        }
+
+        let mut reply_times = self.reply_times.lock().await;
+        let recent = reply_times.entry(recipient.to_ascii_lowercase()).or_default();
+        let window = Duration::from_secs(EMAIL_REPLY_THROTTLE_WINDOW_SECS);
+        let now = std::time::Instant::now();
+        recent.retain(|instant| now.duration_since(*instant) < window);
+
+        if recent.len() >= self.max_replies_per_hour {
+            return true;
+        }
+
+        recent.push(now);
+        false
+    }
+
+    /// Send the parser's prepared size-policy notice back to the sender so
+    /// truncation is not silent. Failures only log; the truncated message
+    /// still reaches the agent.
+    async fn maybe_send_size_policy_notice(&self, message: &InboundMessage) {
+        let Some(notice) = message
+            .metadata
+            .get("email_size_policy_notice")
+            .and_then(serde_json::Value::as_str)
+        else {
+            return;
+        };
+
+        let context = match reply_context_from_message(message) {
+            Ok(context) => context,
+            Err(error) => {
+                tracing::warn!(%error, "cannot address email size policy notice");
+                return;
+            }
+        };
+
+        if self.reply_throttled(&context.recipient).await {
+            tracing::warn!(
+                recipient = %context.recipient,
+                limit = self.max_replies_per_hour,
+                "suppressing email size policy notice: per-sender hourly limit reached"
+            );
+            return;
+        }
+
+        if let Err(error) = self
+            .send_email(
+                &context.recipient,
+                &context.subject,
+                notice.to_string(),
+                context.in_reply_to,
+                context.references,
+                &[],
+                Vec::new(),
+            )
+            .await
+        {
+            tracing::warn!(
+                %error,
+                recipient = %context.recipient,
+                "failed to send email size policy notice"
+            );
+        }
+    }
+
+    /// Persist an email for delivery at `post_at` and arm its timer. The
+    /// entry survives restarts; `start` re-arms anything still pending.
+    #[allow(clippy::too_many_arguments)]
+    async fn schedule_email(
+        &self,
+        recipient: String,
+        subject: String,
+        body: String,
+        in_reply_to: Option<String>,
+        references: Vec<String>,
+        cc: Vec<String>,
+        post_at: i64,
+    ) {
+        let entry = ScheduledEmail {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            runtime_key: self.runtime_key.clone(),
+            recipient,
+            subject,
+            body,
+            in_reply_to,
+            references,
+            cc,
+            post_at,
+        };
+        persist_scheduled_email(&scheduled_emails_path(), &entry);
+        tracing::info!(
+            id = %entry.id,
+            recipient = %entry.recipient,
+            post_at = entry.post_at,
+            "queued email for scheduled delivery"
+        );
+        self.spawn_scheduled_delivery(entry);
+    }
+
+    /// Sleep until the entry's `post_at`, send it, and clear it from the
+    /// store. Failed sends keep the entry so the next restart retries it.
+    fn spawn_scheduled_delivery(&self, entry: ScheduledEmail) {
+        let adapter = self.clone();
+        tokio::spawn(async move {
+            let delay = entry.post_at - chrono::Utc::now().timestamp();
+            if delay > 0 {
+                tokio::time::sleep(Duration::from_secs(delay as u64)).await;
+            }
+            match adapter
+                .send_email(
+                    &entry.recipient,
+                    &entry.subject,
+                    entry.body.clone(),
+                    entry.in_reply_to.clone(),
+                    entry.references.clone(),
+                    &entry.cc,
+                    Vec::new(),
+                )
+                .await
+            {
+                Ok(()) => remove_scheduled_email(&scheduled_emails_path(), &entry.id),
+                Err(error) => tracing::warn!(
+                    %error,
+                    id = %entry.id,
+                    recipient = %entry.recipient,
+                    "scheduled email delivery failed; entry kept for retry on restart"
+                ),
+            }
+        });
+    }
+
+    /// Re-arm timers for scheduled emails persisted by a previous run.
+    fn resume_scheduled_emails(&self) {
+        for entry in load_scheduled_emails(&scheduled_emails_path()) {
+            if entry.runtime_key == self.runtime_key {
+                self.spawn_scheduled_delivery(entry);
+            }
+        }
+    }
+
+    /// Queue a `File` response for delivery, holding it for
+    /// [`EMAIL_ATTACHMENT_BATCH_SECS`] so that further files for the same
+    /// reply context join the same email as extra attachments.
+    #[allow(clippy::too_many_arguments)]
+    async fn queue_attachment(
+        &self,
+        recipient: String,
+        subject: String,
+        in_reply_to: Option<String>,
+        references: Vec<String>,
+        cc: Vec<String>,
+        caption: Option<String>,
+        attachment: (String, bytes::Bytes, String),
+    ) {
+        let caption = caption.filter(|c| !c.trim().is_empty());
+        let key = format!("{recipient}\u{1f}{}", in_reply_to.as_deref().unwrap_or(""));
+
+        let mut pending = self.pending_files.lock().await;
+        if let Some(entry) = pending.get_mut(&key) {
+            entry.captions.extend(caption);
+            entry.attachments.push(attachment);
+            return;
+        }
+        pending.insert(
+            key.clone(),
+            PendingFileEmail {
+                recipient,
+                subject,
+                in_reply_to,
+                references,
+                cc,
+                captions: caption.into_iter().collect(),
+                attachments: vec![attachment],
+            },
+        );
+        drop(pending);
+
+        let adapter = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(EMAIL_ATTACHMENT_BATCH_SECS)).await;
+            let Some(entry) = adapter.pending_files.lock().await.remove(&key) else {
+                return;
+            };
+            let body = if !entry.captions.is_empty() {
+                entry.captions.join("\n\n")
+            } else if let [(filename, _, _)] = entry.attachments.as_slice() {
+                format!("Attached file: {filename}")
+            } else {
+                let names: Vec<&str> = entry
+                    .attachments
+                    .iter()
+                    .map(|(filename, _, _)| filename.as_str())
+                    .collect();
+                format!("Attached files: {}", names.join(", "))
+            };
+            if let Err(error) = adapter
+                .send_email(
+                    &entry.recipient,
+                    &entry.subject,
+                    body,
+                    entry.in_reply_to,
+                    entry.references,
+                    &entry.cc,
+                    entry.attachments,
+                )
+                .await
+            {
+                tracing::error!(%error, recipient = %entry.recipient, "failed to send email with batched attachments");
+            }
+        });
+    }
 }
 
 impl Messaging for EmailAdapter {
@@ -292,7 +709,11 @@ impl Messaging for EmailAdapter {
 
         *self.shutdown_tx.write().await = Some(shutdown_tx);
 
+        // Re-arm delayed sends that were queued before the last shutdown.
+        self.resume_scheduled_emails();
+
         let poll_config = self.poll_config();
+        let adapter = self.clone();
 
         let poll_task = tokio::spawn(async move {
             let mut retry_backoff = Duration::from_secs(5);
@@ -312,6 +733,7 @@ impl Messaging for EmailAdapter {
                     Ok(Ok(messages)) => {
                         retry_backoff = Duration::from_secs(5);
                         for message in messages {
+                            adapter.maybe_send_size_policy_notice(&message).await;
                             if inbound_tx.send(message).await.is_err() {
                                 tracing::warn!(
                                     "email inbound channel closed, stopping adapter loop"
@@ -330,22 +752,62 @@ impl Messaging for EmailAdapter {
                     }
                 }
 
-                let sleep_duration = if had_error {
+                if had_error {
                     let current = retry_backoff;
                     retry_backoff =
                         (retry_backoff * 2).min(Duration::from_secs(EMAIL_MAX_RETRY_BACKOFF_SECS));
-                    current
-                } else {
-                    poll_config.poll_interval
-                };
+                    tokio::select! {
+                        _ = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                break;
+                            }
+                        }
+                        _ = tokio::time::sleep(current) => {}
+                    }
+                    continue;
+                }
+
+                // Between polls, hold an IDLE connection so new mail triggers
+                // an immediate poll. Servers without IDLE (and failed waits)
+                // fall back to the configured polling cadence.
+                let config = poll_config.clone();
+                let idle_wait =
+                    tokio::task::spawn_blocking(move || wait_for_inbox_activity(&config));
 
-                tokio::select! {
+                let idle_outcome = tokio::select! {
                     _ = shutdown_rx.changed() => {
                         if *shutdown_rx.borrow() {
                             break;
                         }
+                        continue;
+                    }
+                    outcome = idle_wait => outcome,
+                };
+
+                let fall_back_to_interval = match idle_outcome {
+                    // Activity was reported or the refresh window elapsed;
+                    // either way the next poll runs immediately.
+                    Ok(Ok(true)) => false,
+                    Ok(Ok(false)) => true,
+                    Ok(Err(error)) => {
+                        tracing::debug!(%error, "IMAP IDLE wait failed, falling back to polling");
+                        true
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "IMAP IDLE task panicked, falling back to polling");
+                        true
+                    }
+                };
+
+                if fall_back_to_interval {
+                    tokio::select! {
+                        _ = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                break;
+                            }
+                        }
+                        _ = tokio::time::sleep(poll_config.poll_interval) => {}
                     }
-                    _ = tokio::time::sleep(sleep_duration) => {}
                 }
             }
 
@@ -364,6 +826,33 @@ impl Messaging for EmailAdapter {
         response: OutboundResponse,
     ) -> crate::Result<()> {
         let mut context = reply_context_from_message(message)?;
+        let cc = if self.reply_all {
+            reply_all_recipients(
+                message,
+                &context.recipient,
+                &[&self.from_address, &self.imap_username, &self.smtp_username],
+            )
+        } else {
+            Vec::new()
+        };
+
+        let sends_mail = !matches!(
+            response,
+            OutboundResponse::Reaction(_)
+                | OutboundResponse::RemoveReaction(_)
+                | OutboundResponse::Status(_)
+                | OutboundResponse::StreamStart
+                | OutboundResponse::StreamChunk(_)
+                | OutboundResponse::StreamEnd
+        );
+        if sends_mail && self.reply_throttled(&context.recipient).await {
+            tracing::warn!(
+                recipient = %context.recipient,
+                limit = self.max_replies_per_hour,
+                "suppressing email reply: per-sender hourly limit reached"
+            );
+            return Ok(());
+        }
 
         match response {
             OutboundResponse::Text(text) => {
@@ -373,7 +862,8 @@ impl Messaging for EmailAdapter {
                     text,
                     context.in_reply_to,
                     context.references,
-                    None,
+                    &cc,
+                    Vec::new(),
                 )
                 .await?;
             }
@@ -384,7 +874,8 @@ impl Messaging for EmailAdapter {
                     text,
                     context.in_reply_to,
                     context.references,
-                    None,
+                    &cc,
+                    Vec::new(),
                 )
                 .await?;
             }
@@ -398,7 +889,8 @@ impl Messaging for EmailAdapter {
                     text,
                     context.in_reply_to,
                     context.references,
-                    None,
+                    &cc,
+                    Vec::new(),
                 )
                 .await?;
             }
@@ -408,20 +900,16 @@ impl Messaging for EmailAdapter {
                 mime_type,
                 caption,
             } => {
-                let mut body = caption.unwrap_or_else(|| format!("Attached file: {filename}"));
-                if body.trim().is_empty() {
-                    body = format!("Attached file: {filename}");
-                }
-
-                self.send_email(
-                    &context.recipient,
-                    &context.subject,
-                    body,
+                self.queue_attachment(
+                    context.recipient,
+                    context.subject,
                     context.in_reply_to,
                     context.references,
-                    Some((filename, data, mime_type)),
+                    cc,
+                    caption,
+                    (filename, data, mime_type),
                 )
-                .await?;
+                .await;
             }
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
@@ -433,26 +921,35 @@ impl Messaging for EmailAdapter {
                     text,
                     context.in_reply_to,
                     context.references,
-                    None,
+                    &cc,
+                    Vec::new(),
                 )
                 .await?;
             }
             OutboundResponse::ScheduledMessage { text, post_at } => {
-                tracing::warn!(
-                    post_at,
-                    recipient = %context.recipient,
-                    subject = %context.subject,
-                    "email adapter does not support scheduled delivery; sending immediately"
-                );
-                self.send_email(
-                    &context.recipient,
-                    &context.subject,
-                    text,
-                    context.in_reply_to,
-                    context.references,
-                    None,
-                )
-                .await?;
+                if post_at <= chrono::Utc::now().timestamp() {
+                    self.send_email(
+                        &context.recipient,
+                        &context.subject,
+                        text,
+                        context.in_reply_to,
+                        context.references,
+                        &cc,
+                        Vec::new(),
+                    )
+                    .await?;
+                } else {
+                    self.schedule_email(
+                        context.recipient,
+                        context.subject,
+                        text,
+                        context.in_reply_to,
+                        context.references,
+                        cc,
+                        post_at,
+                    )
+                    .await;
+                }
             }
             OutboundResponse::StreamStart
             | OutboundResponse::StreamChunk(_)
@@ -468,11 +965,11 @@ impl Messaging for EmailAdapter {
 
         match response {
             OutboundResponse::Text(text) => {
-                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), None)
+                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), &[], Vec::new())
                     .await?;
             }
             OutboundResponse::RichMessage { text, .. } => {
-                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), None)
+                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), &[], Vec::new())
                     .await?;
             }
             OutboundResponse::File {
@@ -488,23 +985,32 @@ impl Messaging for EmailAdapter {
                     body,
                     None,
                     Vec::new(),
-                    Some((filename, data, mime_type)),
+                    &[],
+                    vec![(filename, data, mime_type)],
                 )
                 .await?;
             }
             OutboundResponse::ThreadReply { text, .. }
             | OutboundResponse::Ephemeral { text, .. } => {
-                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), None)
+                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), &[], Vec::new())
                     .await?;
             }
             OutboundResponse::ScheduledMessage { text, post_at } => {
-                tracing::warn!(
-                    post_at,
-                    recipient = %recipient,
-                    "email adapter does not support scheduled delivery; sending immediately"
-                );
-                self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), None)
-                    .await?;
+                if post_at <= chrono::Utc::now().timestamp() {
+                    self.send_email(&recipient, "Spacebot message", text, None, Vec::new(), &[], Vec::new())
+                        .await?;
+                } else {
+                    self.schedule_email(
+                        recipient,
+                        "Spacebot message".to_string(),
+                        text,
+                        None,
+                        Vec::new(),
+                        Vec::new(),
+                        post_at,
+                    )
+                    .await;
+                }
             }
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
@@ -576,7 +1082,7 @@ impl Messaging for EmailAdapter {
     async fn health_check(&self) -> crate::Result<()> {
         let poll_config = self.poll_config();
         tokio::task::spawn_blocking(move || {
-            let mut session = open_imap_session(&poll_config)?;
+            let mut session = checkout_imap_session(&poll_config)?;
             let folder = poll_config
                 .folders
                 .first()
@@ -585,7 +1091,7 @@ impl Messaging for EmailAdapter {
             session
                 .select(&folder)
                 .with_context(|| format!("failed to select IMAP folder '{folder}'"))?;
-            session.logout().ok();
+            checkin_imap_session(&poll_config, session);
             anyhow::Ok(())
         })
         .await
@@ -621,7 +1127,7 @@ impl Messaging for EmailAdapter {
     }
 }
 
-fn build_smtp_transport(config: &EmailConfig) -> crate::Result<AsyncSmtpTransport<Tokio1Executor>> {
+pub(crate) fn build_smtp_transport(config: &EmailConfig) -> crate::Result<AsyncSmtpTransport<Tokio1Executor>> {
     let builder = if config.smtp_use_starttls {
         AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
             .with_context(|| format!("invalid SMTP host '{}'", config.smtp_host))?
@@ -639,8 +1145,42 @@ fn build_smtp_transport(config: &EmailConfig) -> crate::Result<AsyncSmtpTranspor
         .build())
 }
 
-fn poll_inbox_once(config: &EmailPollConfig) -> anyhow::Result<Vec<InboundMessage>> {
-    let mut session = open_imap_session(config)?;
+/// Block until the IMAP server reports mailbox activity or the refresh
+/// window elapses. Returns `Ok(false)` when the server doesn't advertise
+/// IDLE, so the caller can fall back to interval polling.
+fn wait_for_inbox_activity(config: &EmailPollConfig) -> anyhow::Result<bool> {
+    let mut session = checkout_imap_session(config)?;
+
+    let supports_idle = session
+        .capabilities()
+        .map(|capabilities| capabilities.has_str("IDLE"))
+        .unwrap_or(false);
+    if !supports_idle {
+        checkin_imap_session(config, session);
+        return Ok(false);
+    }
+
+    let folder = config
+        .folders
+        .first()
+        .map(String::as_str)
+        .unwrap_or("INBOX");
+    session
+        .select(folder)
+        .with_context(|| format!("failed to select IMAP folder '{folder}' for IDLE"))?;
+
+    let mut handle = session.idle().context("failed to enter IMAP IDLE")?;
+    handle.set_keepalive(Duration::from_secs(EMAIL_IDLE_REFRESH_SECS));
+    // Returns on new activity or when the keepalive window elapses; either
+    // way the caller follows up with a regular poll.
+    handle.wait_keepalive().ok();
+
+    checkin_imap_session(config, session);
+    Ok(true)
+}
+
+pub(crate) fn poll_inbox_once(config: &EmailPollConfig) -> anyhow::Result<Vec<InboundMessage>> {
+    let mut session = checkout_imap_session(config)?;
     let mut inbound_messages = Vec::new();
 
     for folder in &config.folders {
@@ -698,11 +1238,139 @@ fn poll_inbox_once(config: &EmailPollConfig) -> anyhow::Result<Vec<InboundMessag
         }
     }
 
-    session.logout().ok();
+    checkin_imap_session(config, session);
 
     Ok(inbound_messages)
 }
 
+/// An IMAP session parked between operations, tagged with when it was last used.
+/// One delayed outbound email awaiting its `post_at` delivery time.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct ScheduledEmail {
+    id: String,
+    /// Adapter runtime key that owns the entry, so multiple email instances
+    /// sharing the store only deliver their own mail.
+    runtime_key: String,
+    recipient: String,
+    subject: String,
+    body: String,
+    in_reply_to: Option<String>,
+    references: Vec<String>,
+    cc: Vec<String>,
+    /// Unix epoch seconds when the email should be sent.
+    post_at: i64,
+}
+
+fn scheduled_emails_path() -> std::path::PathBuf {
+    crate::config::Config::default_instance_dir().join(SCHEDULED_EMAILS_FILE)
+}
+
+/// Serializes read-modify-write cycles on the scheduled email store.
+fn scheduled_emails_lock() -> &'static std::sync::Mutex<()> {
+    static LOCK: std::sync::OnceLock<std::sync::Mutex<()>> = std::sync::OnceLock::new();
+    LOCK.get_or_init(|| std::sync::Mutex::new(()))
+}
+
+fn load_scheduled_emails(path: &std::path::Path) -> Vec<ScheduledEmail> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_scheduled_emails(path: &std::path::Path, entries: &[ScheduledEmail]) {
+    match serde_json::to_string_pretty(entries) {
+        Ok(contents) => {
+            if let Err(error) = std::fs::write(path, contents) {
+                tracing::warn!(%error, path = %path.display(), "failed to save scheduled emails");
+            }
+        }
+        Err(error) => tracing::warn!(%error, "failed to serialize scheduled emails"),
+    }
+}
+
+fn persist_scheduled_email(path: &std::path::Path, entry: &ScheduledEmail) {
+    let _guard = scheduled_emails_lock().lock().expect("scheduled email lock poisoned");
+    let mut entries = load_scheduled_emails(path);
+    entries.push(entry.clone());
+    save_scheduled_emails(path, &entries);
+}
+
+fn remove_scheduled_email(path: &std::path::Path, id: &str) {
+    let _guard = scheduled_emails_lock().lock().expect("scheduled email lock poisoned");
+    let mut entries = load_scheduled_emails(path);
+    let before = entries.len();
+    entries.retain(|entry| entry.id != id);
+    if entries.len() != before {
+        save_scheduled_emails(path, &entries);
+    }
+}
+
+struct PooledImapSession {
+    session: ImapSession,
+    checked_in: std::time::Instant,
+}
+
+/// Process-wide pool of logged-in IMAP sessions keyed by host/port/username,
+/// so polls, history fetches, searches, and health checks reuse connections
+/// instead of opening a fresh TCP+TLS+LOGIN every time. Providers that
+/// throttle repeated logins (iCloud, Fastmail) otherwise lock accounts out.
+fn imap_pool() -> &'static std::sync::Mutex<HashMap<String, Vec<PooledImapSession>>> {
+    static POOL: OnceLock<std::sync::Mutex<HashMap<String, Vec<PooledImapSession>>>> =
+        OnceLock::new();
+    POOL.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
+fn imap_pool_key(config: &EmailPollConfig) -> String {
+    format!(
+        "{}:{}:{}",
+        config.imap_host, config.imap_port, config.imap_username
+    )
+}
+
+/// Take a pooled session if one is still alive (verified with NOOP), logging
+/// in fresh only when none is usable. Callers hand healthy sessions back via
+/// [`checkin_imap_session`]; error paths just drop them.
+fn checkout_imap_session(config: &EmailPollConfig) -> anyhow::Result<ImapSession> {
+    let key = imap_pool_key(config);
+    loop {
+        let pooled = {
+            let mut pool = imap_pool().lock().expect("IMAP pool lock poisoned");
+            pool.get_mut(&key).and_then(Vec::pop)
+        };
+        let Some(mut pooled) = pooled else {
+            break;
+        };
+        if pooled.checked_in.elapsed() > Duration::from_secs(EMAIL_POOL_IDLE_SECS) {
+            pooled.session.logout().ok();
+            continue;
+        }
+        if pooled.session.noop().is_ok() {
+            return Ok(pooled.session);
+        }
+        // Connection went stale under us; drop it and try the next one.
+    }
+    open_imap_session(config)
+}
+
+/// Return a known-good session to the pool, logging out the surplus when the
+/// per-account cap is already met.
+fn checkin_imap_session(config: &EmailPollConfig, mut session: ImapSession) {
+    let key = imap_pool_key(config);
+    {
+        let mut pool = imap_pool().lock().expect("IMAP pool lock poisoned");
+        let sessions = pool.entry(key).or_default();
+        if sessions.len() < EMAIL_POOL_MAX_IDLE {
+            sessions.push(PooledImapSession {
+                session,
+                checked_in: std::time::Instant::now(),
+            });
+            return;
+        }
+    }
+    session.logout().ok();
+}
+
 fn open_imap_session(config: &EmailPollConfig) -> anyhow::Result<ImapSession> {
     let tls = native_tls::TlsConnector::builder()
         .build()
@@ -742,7 +1410,7 @@ fn open_imap_session(config: &EmailPollConfig) -> anyhow::Result<ImapSession> {
     Ok(session)
 }
 
-fn parse_inbound_email(
+pub(crate) fn parse_inbound_email(
     raw_email: &[u8],
     folder: &str,
     uid: u32,
@@ -793,6 +1461,15 @@ fn parse_inbound_email(
         .map(|value| extract_message_ids(&value))
         .unwrap_or_default();
 
+    if references_indicate_loop(&references) {
+        tracing::warn!(
+            sender = %sender_email,
+            depth = references.len(),
+            "dropping email whose References chain looks like a reply loop"
+        );
+        return Ok(None);
+    }
+
     let thread_key = derive_thread_key(
         &references,
         in_reply_to.as_deref(),
@@ -804,13 +1481,16 @@ fn parse_inbound_email(
     let account_key = sanitize_account_key(&config.from_address);
     let conversation_id = format!("email:{account_key}:{thread_key}");
 
-    let (mut body_text, attachment_names) =
+    let (mut body_text, attachment_names, truncated_body_bytes) =
         extract_text_and_attachments(&parsed, config.max_body_bytes);
     if !attachment_names.is_empty() {
         body_text.push_str("\n\nAttachments: ");
         body_text.push_str(&attachment_names.join(", "));
     }
 
+    let (attachments, oversize_attachments) =
+        save_attachment_files(&parsed, uid, config.max_attachment_bytes);
+
     let timestamp = headers
         .get_first_value("Date")
         .and_then(|value| mailparse::dateparse(&value).ok())
@@ -829,6 +1509,9 @@ fn parse_inbound_email(
     if let Some(to_header) = to_header {
         metadata.insert("email_to".into(), serde_json::Value::String(to_header));
     }
+    if let Some(cc_header) = headers.get_first_value("Cc") {
+        metadata.insert("email_cc".into(), serde_json::Value::String(cc_header));
+    }
     metadata.insert(
         "email_subject".into(),
         serde_json::Value::String(subject.clone()),
@@ -871,25 +1554,78 @@ fn parse_inbound_email(
         |name| format!("{name} <{sender_email}>"),
     );
 
-    Ok(Some(InboundMessage {
+    let auth = verify_email_auth(raw_email, headers);
+    metadata.insert(
+        "email_dkim".into(),
+        serde_json::Value::String(auth.dkim.clone()),
+    );
+    metadata.insert(
+        "email_spf".into(),
+        serde_json::Value::String(auth.spf.clone()),
+    );
+
+    if config.size_policy_reply
+        && (truncated_body_bytes.is_some() || !oversize_attachments.is_empty())
+    {
+        metadata.insert(
+            "email_size_policy_notice".into(),
+            serde_json::Value::String(size_policy_notice(
+                truncated_body_bytes,
+                &oversize_attachments,
+                config,
+            )),
+        );
+    }
+
+    let quarantine_reason = if config.drop_failed_auth && auth.failed() {
+        Some(format!("auth: dkim={} spf={}", auth.dkim, auth.spf))
+    } else {
+        spam_reason(raw_email, headers, &subject, &body_text, config)
+    };
+
+    let message = InboundMessage {
         id: message_id,
         source: "email".into(),
         adapter: Some(config.runtime_key.clone()),
         conversation_id,
         sender_id: sender_email,
         agent_id: None,
-        content: MessageContent::Text(body_text),
+        content: if attachments.is_empty() {
+            MessageContent::Text(body_text)
+        } else {
+            MessageContent::Media {
+                text: Some(body_text),
+                attachments,
+            }
+        },
         timestamp,
         metadata,
         formatted_author: Some(formatted_author),
-    }))
-}
+    };
 
-fn reply_context_from_message(message: &InboundMessage) -> anyhow::Result<EmailReplyContext> {
-    let recipient = message
-        .metadata
-        .get("email_reply_to")
-        .and_then(json_value_to_string)
+    // Suspicious mail goes to the quarantine log instead of the agent; the
+    // API can review and release it later.
+    if let Some(reason) = quarantine_reason {
+        tracing::warn!(
+            sender = %message.sender_id,
+            subject = %subject,
+            reason = %reason,
+            "quarantining suspicious inbound email"
+        );
+        if let Some(store) = crate::quarantine::global() {
+            store.add(reason, message);
+        }
+        return Ok(None);
+    }
+
+    Ok(Some(message))
+}
+
+pub(crate) fn reply_context_from_message(message: &InboundMessage) -> anyhow::Result<EmailReplyContext> {
+    let recipient = message
+        .metadata
+        .get("email_reply_to")
+        .and_then(json_value_to_string)
         .or_else(|| {
             message
                 .metadata
@@ -938,7 +1674,7 @@ fn fetch_history_from_imap(
     message_ids: Vec<String>,
     limit: usize,
 ) -> anyhow::Result<Vec<HistoryMessage>> {
-    let mut session = open_imap_session(config)?;
+    let mut session = checkout_imap_session(config)?;
     let mut seen_message_ids = HashSet::new();
     let mut entries = Vec::new();
 
@@ -1021,7 +1757,7 @@ fn fetch_history_from_imap(
                         || sender_email.eq_ignore_ascii_case(&config.smtp_username);
 
                     let author = sender_name.unwrap_or(sender_email);
-                    let (body, _) = extract_text_and_attachments(&parsed, config.max_body_bytes);
+                    let (body, _, _) = extract_text_and_attachments(&parsed, config.max_body_bytes);
 
                     let timestamp = headers
                         .get_first_value("Date")
@@ -1042,7 +1778,7 @@ fn fetch_history_from_imap(
         }
     }
 
-    session.logout().ok();
+    checkin_imap_session(config, session);
 
     entries.sort_by_key(|entry| entry.timestamp);
     entries.truncate(limit);
@@ -1057,20 +1793,8 @@ pub fn search_mailbox(
     config: &EmailConfig,
     query: EmailSearchQuery,
 ) -> crate::Result<Vec<EmailSearchHit>> {
-    let mut session = open_imap_session(&EmailPollConfig {
-        imap_host: config.imap_host.clone(),
-        imap_port: config.imap_port,
-        imap_username: config.imap_username.clone(),
-        imap_password: config.imap_password.clone(),
-        imap_use_tls: config.imap_use_tls,
-        from_address: config.from_address.clone(),
-        smtp_username: config.smtp_username.clone(),
-        folders: config.folders.clone(),
-        poll_interval: Duration::from_secs(config.poll_interval_secs.max(5)),
-        allowed_senders: config.allowed_senders.clone(),
-        max_body_bytes: config.max_body_bytes.max(1024),
-        runtime_key: "email".to_string(),
-    })?;
+    let poll_config = poll_config_from(config, "email".to_string());
+    let mut session = checkout_imap_session(&poll_config)?;
 
     let limit = query.limit.clamp(1, 50);
     let criterion = build_imap_search_criterion(&query);
@@ -1149,7 +1873,7 @@ pub fn search_mailbox(
                     .as_deref()
                     .and_then(|value| mailparse::dateparse(value).ok())
                     .unwrap_or(i64::MIN);
-                let (body, attachment_names) =
+                let (body, attachment_names, _) =
                     extract_text_and_attachments(&parsed, max_body_bytes);
 
                 ranked_results.push((
@@ -1171,13 +1895,133 @@ pub fn search_mailbox(
 
     let results = sort_and_limit_search_hits(ranked_results, limit);
 
-    if let Err(error) = session.logout() {
-        tracing::debug!(%error, "IMAP logout failed after mailbox search");
-    }
+    checkin_imap_session(&poll_config, session);
 
     Ok(results)
 }
 
+/// Mailbox mutation performed by the `email_action` tool and API endpoint.
+#[derive(Debug, Clone)]
+pub enum EmailMailboxAction {
+    MarkRead,
+    MarkUnread,
+    /// Move the message to another folder.
+    Move { folder: String },
+    /// Add an IMAP keyword flag (label).
+    Label { flag: String },
+    /// Move to the Archive folder.
+    Archive,
+    /// Flag as deleted and expunge.
+    Delete,
+}
+
+impl EmailMailboxAction {
+    /// Build an action from the string form used by the tool and API.
+    pub fn from_parts(
+        action: &str,
+        target_folder: Option<String>,
+        label: Option<String>,
+    ) -> anyhow::Result<Self> {
+        match action {
+            "mark_read" => Ok(Self::MarkRead),
+            "mark_unread" => Ok(Self::MarkUnread),
+            "archive" => Ok(Self::Archive),
+            "delete" => Ok(Self::Delete),
+            "move" => {
+                let folder = target_folder
+                    .filter(|folder| !folder.trim().is_empty())
+                    .context("'move' requires target_folder")?;
+                Ok(Self::Move { folder })
+            }
+            "label" => {
+                let flag = label
+                    .filter(|flag| !flag.trim().is_empty())
+                    .context("'label' requires label")?;
+                anyhow::ensure!(
+                    flag.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-')),
+                    "label '{flag}' may only contain letters, digits, '_' and '-'"
+                );
+                Ok(Self::Label { flag })
+            }
+            other => anyhow::bail!(
+                "unknown action '{other}' (expected mark_read, mark_unread, move, label, archive, or delete)"
+            ),
+        }
+    }
+}
+
+/// Apply a mailbox action to one message, identified by folder + UID as
+/// returned by [`search_mailbox`]. Blocking; call from `spawn_blocking`.
+pub fn apply_mailbox_action(
+    config: &EmailConfig,
+    folder: &str,
+    uid: u32,
+    action: &EmailMailboxAction,
+) -> anyhow::Result<String> {
+    let poll_config = poll_config_from(config, "email".to_string());
+    let mut session = checkout_imap_session(&poll_config)?;
+    session
+        .select(folder)
+        .with_context(|| format!("failed to select IMAP folder '{folder}'"))?;
+
+    let uid_set = uid.to_string();
+    let summary = match action {
+        EmailMailboxAction::MarkRead => {
+            session
+                .uid_store(&uid_set, "+FLAGS (\\Seen)")
+                .context("failed to set \\Seen flag")?;
+            format!("marked {folder}/{uid} as read")
+        }
+        EmailMailboxAction::MarkUnread => {
+            session
+                .uid_store(&uid_set, "-FLAGS (\\Seen)")
+                .context("failed to clear \\Seen flag")?;
+            format!("marked {folder}/{uid} as unread")
+        }
+        EmailMailboxAction::Label { flag } => {
+            session
+                .uid_store(&uid_set, format!("+FLAGS ({flag})"))
+                .with_context(|| format!("failed to add flag '{flag}'"))?;
+            format!("labeled {folder}/{uid} with '{flag}'")
+        }
+        EmailMailboxAction::Move { folder: dest } => {
+            move_message(&mut session, &uid_set, dest)?;
+            format!("moved {folder}/{uid} to '{dest}'")
+        }
+        EmailMailboxAction::Archive => {
+            move_message(&mut session, &uid_set, "Archive")?;
+            format!("archived {folder}/{uid}")
+        }
+        EmailMailboxAction::Delete => {
+            session
+                .uid_store(&uid_set, "+FLAGS (\\Deleted)")
+                .context("failed to set \\Deleted flag")?;
+            session.expunge().context("failed to expunge mailbox")?;
+            format!("deleted {folder}/{uid}")
+        }
+    };
+
+    checkin_imap_session(&poll_config, session);
+    Ok(summary)
+}
+
+/// UID MOVE with a COPY + \Deleted + EXPUNGE fallback for servers without
+/// the MOVE capability.
+fn move_message(session: &mut ImapSession, uid_set: &str, dest: &str) -> anyhow::Result<()> {
+    if session.uid_mv(uid_set, dest).is_ok() {
+        return Ok(());
+    }
+    session
+        .uid_copy(uid_set, dest)
+        .with_context(|| format!("failed to copy message to '{dest}'"))?;
+    session
+        .uid_store(uid_set, "+FLAGS (\\Deleted)")
+        .context("failed to flag source message as deleted")?;
+    session.expunge().context("failed to expunge source folder")?;
+    Ok(())
+}
+
 fn sort_and_limit_search_hits(
     mut ranked_results: Vec<(i64, EmailSearchHit)>,
     limit: usize,
@@ -1272,6 +2116,456 @@ fn quote_imap_search_value(value: &str) -> String {
     format!("\"{escaped}\"")
 }
 
+/// Reply loops show up as References chains that grow without bound or that
+/// revisit the same Message-ID; either marks the thread as an autoresponder
+/// storm the Auto-Submitted check missed.
+fn references_indicate_loop(references: &[String]) -> bool {
+    if references.len() > EMAIL_MAX_REFERENCES_DEPTH {
+        return true;
+    }
+
+    let mut seen = std::collections::HashSet::with_capacity(references.len());
+    references.iter().any(|id| !seen.insert(id.as_str()))
+}
+
+/// Verdicts from inbound mail authentication, attached to message metadata
+/// as `email_dkim` / `email_spf` ("pass", "fail", "softfail", "neutral",
+/// "none", ...).
+struct EmailAuthVerdict {
+    dkim: String,
+    spf: String,
+}
+
+impl EmailAuthVerdict {
+    /// True when a verdict is an outright failure. SPF softfail counts: the
+    /// domain said "probably not us".
+    fn failed(&self) -> bool {
+        self.dkim == "fail" || matches!(self.spf.as_str(), "fail" | "softfail")
+    }
+}
+
+/// Verify what can be verified locally and defer to the receiving MTA's
+/// `Authentication-Results` header for the rest.
+///
+/// The adapter polls mail the MX has already accepted, so it never sees the
+/// SMTP client address SPF is defined over and cannot redo that check; the
+/// MX's recorded verdict (or a `Received-SPF` header) is authoritative here.
+/// For DKIM the raw message is at hand, so the signature's declared body
+/// hash is recomputed — a mismatch means the body was altered after signing
+/// and overrides whatever the MX concluded.
+fn verify_email_auth(raw_email: &[u8], headers: &[mailparse::MailHeader<'_>]) -> EmailAuthVerdict {
+    let auth_results = headers
+        .get_first_value("Authentication-Results")
+        .unwrap_or_default();
+    let ar_dkim = auth_results_verdict(&auth_results, "dkim");
+    let ar_spf = auth_results_verdict(&auth_results, "spf");
+
+    let dkim = match headers.get_first_value("DKIM-Signature") {
+        None => ar_dkim.unwrap_or_else(|| "none".to_string()),
+        Some(signature) => match dkim_body_hash_matches(raw_email, &signature) {
+            Some(false) => "fail".to_string(),
+            // Body intact; the signature itself was checked (or not) by the
+            // MX, so its verdict stands when one was recorded.
+            Some(true) => ar_dkim.unwrap_or_else(|| "neutral".to_string()),
+            // Unsupported hash or canonicalization: defer entirely.
+            None => ar_dkim.unwrap_or_else(|| "neutral".to_string()),
+        },
+    };
+
+    let spf = ar_spf
+        .or_else(|| {
+            headers.get_first_value("Received-SPF").and_then(|value| {
+                value
+                    .split_whitespace()
+                    .next()
+                    .map(|token| token.trim_end_matches(';').to_ascii_lowercase())
+            })
+        })
+        .unwrap_or_else(|| "none".to_string());
+
+    EmailAuthVerdict { dkim, spf }
+}
+
+/// Pull a `method=verdict` token out of an `Authentication-Results` header.
+fn auth_results_verdict(auth_results: &str, method: &str) -> Option<String> {
+    let lowered = auth_results.to_ascii_lowercase();
+    let needle = format!("{method}=");
+    let start = lowered.find(&needle)? + needle.len();
+    let verdict: String = lowered[start..]
+        .chars()
+        .take_while(char::is_ascii_alphanumeric)
+        .collect();
+    if verdict.is_empty() { None } else { Some(verdict) }
+}
+
+/// Recompute the body hash a DKIM signature declares in its `bh=` tag.
+/// Returns `None` when the signature uses something this check does not
+/// implement (a hash other than sha256, an unknown canonicalization, or an
+/// `l=` body-length limit).
+fn dkim_body_hash_matches(raw_email: &[u8], signature: &str) -> Option<bool> {
+    use base64::Engine as _;
+    use sha2::Digest as _;
+
+    // Tag values may contain '=' (base64 padding), so only split on the
+    // first one. Folding whitespace inside values is ignored per RFC 6376.
+    let tags: HashMap<&str, String> = signature
+        .split(';')
+        .filter_map(|tag| {
+            let (name, value) = tag.split_once('=')?;
+            Some((
+                name.trim(),
+                value.chars().filter(|c| !c.is_whitespace()).collect(),
+            ))
+        })
+        .collect();
+
+    if !tags.get("a")?.ends_with("-sha256") || tags.contains_key("l") {
+        return None;
+    }
+    let declared = base64::engine::general_purpose::STANDARD
+        .decode(tags.get("bh")?)
+        .ok()?;
+
+    // c= is "header/body"; a missing body half means simple.
+    let relaxed = match tags.get("c") {
+        None => false,
+        Some(c) => match c.split_once('/').map_or("simple", |(_, body)| body) {
+            "simple" => false,
+            "relaxed" => true,
+            _ => return None,
+        },
+    };
+
+    let canonical = canonicalize_dkim_body(email_body_slice(raw_email), relaxed);
+    Some(sha2::Sha256::digest(&canonical).as_slice() == declared.as_slice())
+}
+
+/// The raw body, past the blank line ending the headers.
+fn email_body_slice(raw_email: &[u8]) -> &[u8] {
+    if let Some(position) = raw_email.windows(4).position(|window| window == b"\r\n\r\n") {
+        &raw_email[position + 4..]
+    } else if let Some(position) = raw_email.windows(2).position(|window| window == b"\n\n") {
+        &raw_email[position + 2..]
+    } else {
+        &[]
+    }
+}
+
+/// RFC 6376 body canonicalization (simple or relaxed), with bare-LF input
+/// normalized to CRLF first.
+fn canonicalize_dkim_body(body: &[u8], relaxed: bool) -> Vec<u8> {
+    let mut lines: Vec<Vec<u8>> = body
+        .split(|&byte| byte == b'\n')
+        .map(|line| {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if !relaxed {
+                return line.to_vec();
+            }
+            // Relaxed: reduce WSP runs to a single SP, drop trailing WSP.
+            let mut collapsed = Vec::with_capacity(line.len());
+            let mut pending_space = false;
+            for &byte in line {
+                if byte == b' ' || byte == b'\t' {
+                    pending_space = true;
+                } else {
+                    if pending_space {
+                        collapsed.push(b' ');
+                        pending_space = false;
+                    }
+                    collapsed.push(byte);
+                }
+            }
+            collapsed
+        })
+        .collect();
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        // An empty body canonicalizes to a lone CRLF under simple.
+        return if relaxed { Vec::new() } else { b"\r\n".to_vec() };
+    }
+
+    let mut canonical = Vec::new();
+    for line in lines {
+        canonical.extend_from_slice(&line);
+        canonical.extend_from_slice(b"\r\n");
+    }
+    canonical
+}
+
+/// Run the optional spam pre-filter over a parsed message: spam headers set
+/// by an upstream filter, the configured subject/body regexes, and the
+/// external scorer command. Returns the quarantine reason when the mail
+/// should not reach an agent.
+fn spam_reason(
+    raw_email: &[u8],
+    headers: &[mailparse::MailHeader<'_>],
+    subject: &str,
+    body: &str,
+    config: &EmailPollConfig,
+) -> Option<String> {
+    if headers
+        .get_first_value("X-Spam-Flag")
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("yes"))
+    {
+        return Some("header: X-Spam-Flag is YES".to_string());
+    }
+    if headers
+        .get_first_value("X-Spam-Status")
+        .is_some_and(|value| value.trim().to_ascii_lowercase().starts_with("yes"))
+    {
+        return Some("header: X-Spam-Status is Yes".to_string());
+    }
+
+    for pattern in &config.spam_patterns {
+        let regex = match regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(error) => {
+                tracing::warn!(%error, pattern, "skipping invalid spam pattern");
+                continue;
+            }
+        };
+        if regex.is_match(subject) || regex.is_match(body) {
+            return Some(format!("pattern: {pattern}"));
+        }
+    }
+
+    if !config.spam_scorer_command.is_empty() {
+        match run_spam_scorer(&config.spam_scorer_command, raw_email) {
+            Ok(Some(code)) => {
+                return Some(format!(
+                    "scorer: `{}` exited with status {code}",
+                    config.spam_scorer_command
+                ));
+            }
+            Ok(None) => {}
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    command = %config.spam_scorer_command,
+                    "spam scorer failed to run, treating mail as clean"
+                );
+            }
+        }
+    }
+
+    None
+}
+
+/// Run the external scorer with the raw message on stdin. A nonzero exit
+/// marks the mail as spam (SpamAssassin's `spamc -E` convention); failure to
+/// run at all is an error so a broken scorer never eats mail.
+fn run_spam_scorer(command: &str, raw_email: &[u8]) -> anyhow::Result<Option<i32>> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("failed to spawn spam scorer")?;
+
+    child
+        .stdin
+        .take()
+        .context("spam scorer has no stdin")?
+        .write_all(raw_email)
+        .context("failed to write message to spam scorer")?;
+
+    let status = child.wait().context("failed to wait for spam scorer")?;
+    if status.success() {
+        Ok(None)
+    } else {
+        Ok(Some(status.code().unwrap_or(-1)))
+    }
+}
+
+/// GnuPG home directory for outbound signing keys, alongside the other
+/// instance state. Only passed to gpg when it exists, so an unconfigured
+/// instance falls through to the user keyring.
+fn pgp_home_dir() -> std::path::PathBuf {
+    crate::config::Config::default_instance_dir().join("gnupg")
+}
+
+/// Compose a `DKIM-Signature` header (rsa-sha256, relaxed/relaxed) over the
+/// serialized message, shelling out to `openssl` for the RSA signature the
+/// same way PGP signing shells out to `gpg`.
+fn dkim_signature_header(
+    raw_message: &[u8],
+    domain: &str,
+    selector: &str,
+    key_path: &std::path::Path,
+) -> anyhow::Result<String> {
+    use base64::Engine as _;
+
+    if domain.is_empty() {
+        anyhow::bail!("cannot derive DKIM domain from from_address");
+    }
+
+    let (headers, _) = mailparse::parse_headers(raw_message)
+        .context("failed to parse outbound headers for DKIM")?;
+
+    let body_hash = base64::engine::general_purpose::STANDARD.encode(Sha256::digest(
+        canonicalize_dkim_body(email_body_slice(raw_message), true),
+    ));
+
+    // Sign whichever of the usual candidates the message carries; relaxed
+    // canonicalization lowercases names and collapses folding whitespace.
+    let candidates = [
+        "from",
+        "to",
+        "cc",
+        "subject",
+        "date",
+        "message-id",
+        "in-reply-to",
+        "references",
+        "mime-version",
+        "content-type",
+        "list-unsubscribe",
+    ];
+    let mut signed_names = Vec::new();
+    let mut canonical_headers = String::new();
+    for name in candidates {
+        let Some(value) = headers.get_first_value(name) else {
+            continue;
+        };
+        signed_names.push(name);
+        canonical_headers.push_str(&format!("{name}:{}\r\n", collapse_dkim_value(&value)));
+    }
+
+    let timestamp = chrono::Utc::now().timestamp();
+    let value = format!(
+        "v=1; a=rsa-sha256; c=relaxed/relaxed; d={domain}; s={selector}; t={timestamp};\r\n\th={}; bh={body_hash};\r\n\tb=",
+        signed_names.join(":")
+    );
+
+    // The DKIM-Signature header itself signs last, with an empty b= tag and
+    // no trailing CRLF.
+    let mut signing_input = canonical_headers.into_bytes();
+    signing_input
+        .extend_from_slice(format!("dkim-signature:{}", collapse_dkim_value(&value)).as_bytes());
+
+    let signature = openssl_rsa_sha256_sign(&signing_input, key_path)?;
+    Ok(format!(
+        "DKIM-Signature: {value}{}\r\n",
+        base64::engine::general_purpose::STANDARD.encode(signature)
+    ))
+}
+
+/// Relaxed header-value canonicalization: unfold, collapse WSP runs to one
+/// SP, and trim.
+fn collapse_dkim_value(value: &str) -> String {
+    let mut collapsed = String::with_capacity(value.len());
+    let mut pending_space = false;
+    for c in value.chars() {
+        if c == '\r' || c == '\n' || c == ' ' || c == '\t' {
+            pending_space = true;
+        } else {
+            if pending_space && !collapsed.is_empty() {
+                collapsed.push(' ');
+            }
+            pending_space = false;
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
+/// RSA-SHA256 signature over `input` with the PEM key at `key_path`, produced
+/// by `openssl dgst` so no key parsing happens in-process.
+fn openssl_rsa_sha256_sign(input: &[u8], key_path: &std::path::Path) -> anyhow::Result<Vec<u8>> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("openssl")
+        .arg("dgst")
+        .arg("-sha256")
+        .arg("-sign")
+        .arg(key_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to spawn openssl for DKIM signing")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .context("failed to write DKIM input to openssl")?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .context("failed to read openssl output")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "openssl DKIM signing failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Inline-clearsign an outbound body by shelling out to `gpg`, the same
+/// external-command pattern as the spam scorer. Returns `None` (after a
+/// warning) when gpg is missing or signing fails, so mail still goes out
+/// unsigned rather than not at all.
+pub(crate) fn clearsign_with_pgp(body: &str, key_id: &str) -> Option<String> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut command = Command::new("gpg");
+    command.args(["--batch", "--yes", "--armor", "--clearsign"]);
+    let home = pgp_home_dir();
+    if home.is_dir() {
+        command.arg("--homedir").arg(&home);
+    }
+    if !key_id.is_empty() {
+        command.args(["--local-user", key_id]);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .inspect_err(|error| tracing::warn!(%error, "failed to spawn gpg; sending unsigned"))
+        .ok()?;
+
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(error) = stdin.write_all(body.as_bytes())
+    {
+        tracing::warn!(%error, "failed to write body to gpg");
+    }
+
+    let output = child
+        .wait_with_output()
+        .inspect_err(|error| tracing::warn!(%error, "failed to read gpg output"))
+        .ok()?;
+    if !output.status.success() {
+        tracing::warn!(
+            status = %output.status,
+            stderr = %String::from_utf8_lossy(&output.stderr),
+            "gpg signing failed; sending unsigned"
+        );
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .filter(|signed| signed.contains("BEGIN PGP SIGNED MESSAGE"))
+}
+
 fn is_auto_generated_email(headers: &[mailparse::MailHeader<'_>]) -> bool {
     let auto_submitted = headers
         .get_first_value("Auto-Submitted")
@@ -1299,7 +2593,7 @@ fn is_own_sender(sender_email: &str, config: &EmailPollConfig) -> bool {
         || sender_email.eq_ignore_ascii_case(&config.smtp_username)
 }
 
-fn is_allowed_sender(sender_email: &str, allowed_senders: &[String]) -> bool {
+pub(crate) fn is_allowed_sender(sender_email: &str, allowed_senders: &[String]) -> bool {
     if allowed_senders.is_empty() {
         return true;
     }
@@ -1324,6 +2618,45 @@ fn is_allowed_sender(sender_email: &str, allowed_senders: &[String]) -> bool {
     })
 }
 
+/// Addresses from the inbound To/Cc headers that a reply-all should keep,
+/// excluding the primary recipient and the adapter's own identities.
+fn reply_all_recipients(
+    message: &InboundMessage,
+    primary: &str,
+    own_addresses: &[&str],
+) -> Vec<String> {
+    let mut recipients = Vec::new();
+    for key in ["email_to", "email_cc"] {
+        let Some(value) = message.metadata.get(key).and_then(json_value_to_string) else {
+            continue;
+        };
+        let Ok(parsed) = mailparse::addrparse(&value) else {
+            continue;
+        };
+        for address in parsed.into_inner() {
+            match address {
+                MailAddr::Single(single) => recipients.push(single.addr),
+                MailAddr::Group(group) => {
+                    recipients.extend(group.addrs.into_iter().map(|single| single.addr));
+                }
+            }
+        }
+    }
+
+    let primary = primary.trim().to_ascii_lowercase();
+    let mut seen = HashSet::new();
+    recipients.retain(|address| {
+        let lower = address.trim().to_ascii_lowercase();
+        !lower.is_empty()
+            && lower != primary
+            && !own_addresses
+                .iter()
+                .any(|own| own.eq_ignore_ascii_case(&lower))
+            && seen.insert(lower)
+    });
+    recipients
+}
+
 fn parse_primary_mailbox(value: &str) -> Option<(String, Option<String>)> {
     let addresses = mailparse::addrparse(value).ok()?.into_inner();
     for address in addresses {
@@ -1341,7 +2674,7 @@ fn parse_primary_mailbox(value: &str) -> Option<(String, Option<String>)> {
     None
 }
 
-fn parse_mailbox(value: &str) -> anyhow::Result<Mailbox> {
+pub(crate) fn parse_mailbox(value: &str) -> anyhow::Result<Mailbox> {
     if let Ok(mailbox) = value.parse::<Mailbox>() {
         return Ok(mailbox);
     }
@@ -1354,10 +2687,45 @@ fn parse_mailbox(value: &str) -> anyhow::Result<Mailbox> {
     Ok(Mailbox::new(display_name, address))
 }
 
+/// Compose the polite reply sent when an inbound email exceeded the size
+/// limits, listing each limit hit and what to resend.
+fn size_policy_notice(
+    truncated_body_bytes: Option<usize>,
+    oversize_attachments: &[(String, usize)],
+    config: &EmailPollConfig,
+) -> String {
+    let mut lines = vec![
+        "Hello,".to_string(),
+        String::new(),
+        "Your message arrived but could not be processed in full:".to_string(),
+        String::new(),
+    ];
+
+    if let Some(size) = truncated_body_bytes {
+        lines.push(format!(
+            "- The message body is {size} bytes, over the {} byte limit, so it was truncated.",
+            config.max_body_bytes
+        ));
+    }
+    for (filename, size) in oversize_attachments {
+        lines.push(format!(
+            "- The attachment \"{filename}\" is {size} bytes, over the {} byte limit, so it was not processed.",
+            config.max_attachment_bytes
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(
+        "Please resend a shorter message or split it across several emails; for large files, a download link works instead of an attachment."
+            .to_string(),
+    );
+    lines.join("\n")
+}
+
 fn extract_text_and_attachments(
     parsed: &mailparse::ParsedMail<'_>,
     max_body_bytes: usize,
-) -> (String, Vec<String>) {
+) -> (String, Vec<String>, Option<usize>) {
     let mut plain_text_parts = Vec::new();
     let mut html_parts = Vec::new();
     let mut attachment_names = Vec::new();
@@ -1382,7 +2750,9 @@ fn extract_text_and_attachments(
         body_text = "(No message body)".to_string();
     }
 
+    let mut truncated_body_bytes = None;
     if body_text.len() > max_body_bytes {
+        truncated_body_bytes = Some(body_text.len());
         body_text = format!(
             "{}\n\n[Message truncated due to size limit]",
             truncate_to_bytes(&body_text, max_body_bytes)
@@ -1392,7 +2762,7 @@ fn extract_text_and_attachments(
     attachment_names.sort();
     attachment_names.dedup();
 
-    (body_text, attachment_names)
+    (body_text, attachment_names, truncated_body_bytes)
 }
 
 fn collect_parts(
@@ -1440,7 +2810,117 @@ fn collect_parts(
     }
 }
 
-fn html_to_text(html: &str) -> String {
+/// Decode attachment bodies (bounded by `max_attachment_bytes`), write them to
+/// a per-process temp directory, and return [`crate::Attachment`] entries with
+/// `file://` URLs so agents can read the files, mirroring the Signal adapter.
+/// Oversize attachments are skipped and returned as `(filename, size)` pairs
+/// so the caller can tell the sender what was dropped.
+fn save_attachment_files(
+    parsed: &mailparse::ParsedMail<'_>,
+    uid: u32,
+    max_attachment_bytes: usize,
+) -> (Vec<crate::Attachment>, Vec<(String, usize)>) {
+    let mut parts = Vec::new();
+    collect_attachment_parts(parsed, &mut parts);
+    if parts.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let dir = std::env::temp_dir().join("spacebot-email-attachments");
+    if let Err(error) = std::fs::create_dir_all(&dir) {
+        tracing::warn!(%error, "failed to create email attachment directory");
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut attachments = Vec::new();
+    let mut oversize = Vec::new();
+    for (filename, mime_type, data) in parts {
+        if data.len() > max_attachment_bytes {
+            tracing::warn!(
+                filename = %filename,
+                size = data.len(),
+                max = max_attachment_bytes,
+                "skipping inbound attachment over max_attachment_bytes"
+            );
+            oversize.push((filename, data.len()));
+            continue;
+        }
+
+        let safe_name = sanitize_attachment_filename(&filename);
+        let path = dir.join(format!("{}-{}-{}", uid, uuid::Uuid::new_v4(), safe_name));
+        if let Err(error) = std::fs::write(&path, &data) {
+            tracing::warn!(%error, filename = %filename, "failed to write inbound email attachment");
+            continue;
+        }
+
+        attachments.push(crate::Attachment {
+            filename,
+            mime_type,
+            url: format!("file://{}", path.display()),
+            size_bytes: Some(data.len() as u64),
+            auth_header: None,
+        });
+    }
+
+    (attachments, oversize)
+}
+
+/// Walk the MIME tree collecting `(filename, mime_type, decoded body)` for
+/// every attachment part, using the same attachment detection as
+/// [`collect_parts`].
+fn collect_attachment_parts(
+    part: &mailparse::ParsedMail<'_>,
+    out: &mut Vec<(String, String, Vec<u8>)>,
+) {
+    if part.subparts.is_empty() {
+        let disposition = part.get_content_disposition();
+        let filename = disposition
+            .params
+            .get("filename")
+            .cloned()
+            .or_else(|| part.ctype.params.get("name").cloned());
+
+        if let Some(filename) = filename {
+            match part.get_body_raw() {
+                Ok(data) if !data.is_empty() => {
+                    out.push((filename, part.ctype.mimetype.clone(), data));
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    tracing::warn!(%error, filename = %filename, "failed to decode email attachment body");
+                }
+            }
+        }
+        return;
+    }
+
+    for subpart in &part.subparts {
+        collect_attachment_parts(subpart, out);
+    }
+}
+
+/// Keep only path-safe characters so an attachment name can't escape the
+/// temp directory or produce an unusable filename.
+fn sanitize_attachment_filename(filename: &str) -> String {
+    let cleaned: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let trimmed = cleaned.trim_matches(['.', '_']).to_string();
+    if trimmed.is_empty() {
+        "attachment".to_string()
+    } else {
+        trimmed
+    }
+}
+
+pub(crate) fn html_to_text(html: &str) -> String {
     let without_tags = html_tag_regex().replace_all(html, " ");
     let decoded = without_tags
         .replace("&nbsp;", " ")
@@ -1458,7 +2938,23 @@ fn html_tag_regex() -> &'static Regex {
     REGEX.get_or_init(|| Regex::new(r"(?is)<[^>]+>").expect("valid HTML tag regex"))
 }
 
-fn normalize_reply_subject(subject: &str) -> String {
+/// Render agent markdown as HTML for the `multipart/alternative` body.
+/// Tables and strikethrough are enabled because agents emit both.
+pub(crate) fn markdown_to_html(markdown: &str) -> String {
+    use pulldown_cmark::{Options, Parser, html};
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let parser = Parser::new_ext(markdown, options);
+    let mut html_body = String::with_capacity(markdown.len() * 2);
+    html::push_html(&mut html_body, parser);
+
+    format!("<html><head><meta charset=\"utf-8\"></head><body>{html_body}</body></html>")
+}
+
+pub(crate) fn normalize_reply_subject(subject: &str) -> String {
     let subject = subject.trim();
     if subject.is_empty() {
         return "Re: Spacebot reply".to_string();
@@ -1482,7 +2978,7 @@ fn extract_message_ids(value: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
-fn normalize_message_id(value: &str) -> String {
+pub(crate) fn normalize_message_id(value: &str) -> String {
     value
         .trim()
         .trim_start_matches('<')
@@ -1491,7 +2987,7 @@ fn normalize_message_id(value: &str) -> String {
         .to_string()
 }
 
-fn format_message_id_for_header(message_id: &str) -> String {
+pub(crate) fn format_message_id_for_header(message_id: &str) -> String {
     let message_id = normalize_message_id(message_id);
     if message_id.is_empty() {
         String::new()
@@ -1540,7 +3036,7 @@ fn derive_thread_key(
     hex::encode(digest)[..24].to_string()
 }
 
-fn sanitize_account_key(value: &str) -> String {
+pub(crate) fn sanitize_account_key(value: &str) -> String {
     let mut result = String::new();
     for character in value.trim().to_ascii_lowercase().chars() {
         if character.is_ascii_alphanumeric() {
@@ -1558,7 +3054,7 @@ fn sanitize_account_key(value: &str) -> String {
     }
 }
 
-fn normalize_email_target(value: &str) -> Option<String> {
+pub(crate) fn normalize_email_target(value: &str) -> Option<String> {
     let value = value.trim();
     if value.is_empty() {
         return None;
@@ -1576,7 +3072,7 @@ fn normalize_email_target(value: &str) -> Option<String> {
     }
 }
 
-fn truncate_to_bytes(value: &str, max_bytes: usize) -> String {
+pub(crate) fn truncate_to_bytes(value: &str, max_bytes: usize) -> String {
     if value.len() <= max_bytes {
         return value.to_string();
     }
@@ -1591,7 +3087,7 @@ fn truncate_to_bytes(value: &str, max_bytes: usize) -> String {
     truncated
 }
 
-fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
+pub(crate) fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
     if let Some(text) = value.as_str() {
         return Some(text.to_string());
     }
@@ -1604,21 +3100,41 @@ fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
     None
 }
 
-struct EmailReplyContext {
-    recipient: String,
-    subject: String,
-    in_reply_to: Option<String>,
-    references: Vec<String>,
+pub(crate) struct EmailReplyContext {
+    pub(crate) recipient: String,
+    pub(crate) subject: String,
+    pub(crate) in_reply_to: Option<String>,
+    pub(crate) references: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        EmailSearchHit, EmailSearchQuery, build_imap_search_criterion, derive_thread_key,
-        extract_message_ids, normalize_email_target, normalize_reply_subject,
-        normalize_search_folders, parse_primary_mailbox, sort_and_limit_search_hits,
+        EmailMailboxAction, EmailSearchHit, EmailSearchQuery, build_imap_search_criterion,
+        collect_attachment_parts,
+        derive_thread_key, extract_message_ids, markdown_to_html, normalize_email_target,
+        normalize_reply_subject, normalize_search_folders, parse_primary_mailbox,
+        EmailPollConfig, ScheduledEmail, clearsign_with_pgp, collapse_dkim_value,
+        dkim_signature_header, load_scheduled_emails,
+        parse_inbound_email,
+        persist_scheduled_email,
+        references_indicate_loop,
+        remove_scheduled_email, spam_reason,
+        reply_all_recipients,
+        sanitize_attachment_filename, sort_and_limit_search_hits,
     };
 
+    #[test]
+    fn markdown_renders_code_links_and_tables() {
+        let html = markdown_to_html(
+            "see [docs](https://example.org)\n\n```rust\nfn main() {}\n```\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        );
+        assert!(html.contains("<a href=\"https://example.org\">docs</a>"));
+        assert!(html.contains("<pre><code class=\"language-rust\">"));
+        assert!(html.contains("<table>"));
+        assert!(html.starts_with("<html>"));
+    }
+
     #[test]
     fn parse_primary_mailbox_parses_display_name() {
         let parsed = parse_primary_mailbox("Alice Example <alice@example.com>");
@@ -1637,6 +3153,46 @@ mod tests {
         assert_eq!(ids, vec!["root@example.com", "child@example.com"]);
     }
 
+    #[test]
+    fn sanitize_attachment_filename_strips_path_characters() {
+        assert_eq!(
+            sanitize_attachment_filename("../../etc/passwd"),
+            "etc_passwd"
+        );
+        assert_eq!(sanitize_attachment_filename("report (v2).pdf"), "report__v2_.pdf");
+        assert_eq!(sanitize_attachment_filename("???"), "attachment");
+    }
+
+    #[test]
+    fn collect_attachment_parts_decodes_base64_bodies() {
+        let raw = concat!(
+            "From: sender@example.com\r\n",
+            "Subject: files\r\n",
+            "MIME-Version: 1.0\r\n",
+            "Content-Type: multipart/mixed; boundary=\"b1\"\r\n",
+            "\r\n",
+            "--b1\r\n",
+            "Content-Type: text/plain\r\n",
+            "\r\n",
+            "see attached\r\n",
+            "--b1\r\n",
+            "Content-Type: application/pdf; name=\"doc.pdf\"\r\n",
+            "Content-Disposition: attachment; filename=\"doc.pdf\"\r\n",
+            "Content-Transfer-Encoding: base64\r\n",
+            "\r\n",
+            "aGVsbG8=\r\n",
+            "--b1--\r\n",
+        );
+        let parsed = mailparse::parse_mail(raw.as_bytes()).expect("valid MIME");
+        let mut parts = Vec::new();
+        collect_attachment_parts(&parsed, &mut parts);
+        assert_eq!(parts.len(), 1);
+        let (filename, mime_type, data) = &parts[0];
+        assert_eq!(filename, "doc.pdf");
+        assert_eq!(mime_type, "application/pdf");
+        assert_eq!(data.as_slice(), b"hello");
+    }
+
     #[test]
     fn normalize_email_target_accepts_prefixed_target() {
         assert_eq!(
@@ -1657,6 +3213,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reply_all_recipients_excludes_self_and_primary() {
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert(
+            "email_to".to_string(),
+            serde_json::Value::String("bot@test.com, Alice <alice@example.com>".to_string()),
+        );
+        metadata.insert(
+            "email_cc".to_string(),
+            serde_json::Value::String("bob@example.com, alice@example.com".to_string()),
+        );
+        let message = crate::InboundMessage {
+            id: "mid".into(),
+            source: "email".into(),
+            adapter: None,
+            conversation_id: "email:test:thread".into(),
+            sender_id: "alice@example.com".into(),
+            agent_id: None,
+            content: crate::MessageContent::Text("hi".into()),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: None,
+        };
+
+        let cc = reply_all_recipients(&message, "alice@example.com", &["bot@test.com"]);
+        assert_eq!(cc, vec!["bob@example.com".to_string()]);
+    }
+
     #[test]
     fn derive_thread_key_prefers_root_reference() {
         let from_references = derive_thread_key(
@@ -1680,6 +3264,21 @@ mod tests {
         assert_eq!(from_references, from_root_only);
     }
 
+    #[test]
+    fn mailbox_action_from_parts_validates_arguments() {
+        assert!(matches!(
+            EmailMailboxAction::from_parts("archive", None, None),
+            Ok(EmailMailboxAction::Archive)
+        ));
+        assert!(matches!(
+            EmailMailboxAction::from_parts("move", Some("Receipts".into()), None),
+            Ok(EmailMailboxAction::Move { .. })
+        ));
+        assert!(EmailMailboxAction::from_parts("move", None, None).is_err());
+        assert!(EmailMailboxAction::from_parts("label", None, Some("bad flag".into())).is_err());
+        assert!(EmailMailboxAction::from_parts("shred", None, None).is_err());
+    }
+
     #[test]
     fn build_imap_search_criterion_defaults_to_all() {
         let criterion = build_imap_search_criterion(&EmailSearchQuery::default());
@@ -1759,4 +3358,239 @@ mod tests {
         assert_eq!(results[0].subject, "newest");
         assert_eq!(results[1].subject, "middle");
     }
+
+    #[test]
+    fn scheduled_email_store_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "spacebot-scheduled-{}.json",
+            uuid::Uuid::new_v4()
+        ));
+
+        let entry = ScheduledEmail {
+            id: "abcd1234".to_string(),
+            runtime_key: "email".to_string(),
+            recipient: "user@example.com".to_string(),
+            subject: "Re: reminder".to_string(),
+            body: "Following up as promised.".to_string(),
+            in_reply_to: Some("<msg@example.com>".to_string()),
+            references: vec!["<msg@example.com>".to_string()],
+            cc: Vec::new(),
+            post_at: 4_102_444_800,
+        };
+        persist_scheduled_email(&path, &entry);
+
+        let loaded = load_scheduled_emails(&path);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].recipient, "user@example.com");
+        assert_eq!(loaded[0].post_at, 4_102_444_800);
+
+        remove_scheduled_email(&path, "other-id");
+        assert_eq!(load_scheduled_emails(&path).len(), 1);
+
+        remove_scheduled_email(&path, "abcd1234");
+        assert!(load_scheduled_emails(&path).is_empty());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn references_loop_detection_flags_depth_and_repeats() {
+        let normal: Vec<String> = (0..5).map(|i| format!("<m{i}@example.com>")).collect();
+        assert!(!references_indicate_loop(&normal));
+
+        let deep: Vec<String> = (0..30).map(|i| format!("<m{i}@example.com>")).collect();
+        assert!(references_indicate_loop(&deep));
+
+        let repeated = vec![
+            "<a@example.com>".to_string(),
+            "<b@example.com>".to_string(),
+            "<a@example.com>".to_string(),
+        ];
+        assert!(references_indicate_loop(&repeated));
+    }
+
+    #[test]
+    fn dkim_relaxed_value_collapses_whitespace() {
+        assert_eq!(
+            collapse_dkim_value("  hello\r\n\tfolded   world  "),
+            "hello folded world"
+        );
+        assert_eq!(collapse_dkim_value("plain"), "plain");
+    }
+
+    #[test]
+    fn dkim_signing_errors_without_a_usable_key() {
+        let raw = b"From: bot@example.com\r\nSubject: hi\r\n\r\nbody\r\n";
+        assert!(
+            dkim_signature_header(
+                raw,
+                "example.com",
+                "mail",
+                std::path::Path::new("/nonexistent/dkim.pem"),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn clearsign_fails_open_without_a_usable_key() {
+        // With or without gpg installed, a key that cannot exist must yield
+        // None so the mail goes out unsigned instead of not at all.
+        assert!(clearsign_with_pgp("hello", "no-such-key@example.invalid").is_none());
+    }
+
+    fn spam_test_config(patterns: Vec<String>) -> EmailPollConfig {
+        EmailPollConfig {
+            imap_host: String::new(),
+            imap_port: 993,
+            imap_username: String::new(),
+            imap_password: String::new(),
+            imap_use_tls: true,
+            from_address: "bot@example.com".into(),
+            smtp_username: String::new(),
+            folders: vec!["INBOX".into()],
+            poll_interval: std::time::Duration::from_secs(60),
+            allowed_senders: Vec::new(),
+            max_body_bytes: 64 * 1024,
+            max_attachment_bytes: 64 * 1024,
+            size_policy_reply: false,
+            spam_patterns: patterns,
+            spam_scorer_command: String::new(),
+            drop_failed_auth: false,
+            runtime_key: "email".into(),
+        }
+    }
+
+    #[test]
+    fn spam_patterns_match_subject_and_body() {
+        let config = spam_test_config(vec!["fr[e3]+ crypto".into(), "(".into()]);
+        let raw = b"Subject: hello\r\n\r\nbody";
+        let parsed = mailparse::parse_mail(raw).expect("parse");
+
+        assert!(
+            spam_reason(raw, &parsed.headers, "FREE CRYPTO inside", "hi", &config)
+                .is_some_and(|reason| reason.starts_with("pattern:"))
+        );
+        assert!(
+            spam_reason(raw, &parsed.headers, "hi", "claim your freee crypto", &config).is_some()
+        );
+        // The invalid pattern is skipped rather than quarantining everything.
+        assert!(spam_reason(raw, &parsed.headers, "weekly report", "numbers", &config).is_none());
+    }
+
+    #[test]
+    fn dkim_body_hash_catches_tampering() {
+        use base64::Engine as _;
+        use sha2::Digest as _;
+
+        let bh = base64::engine::general_purpose::STANDARD
+            .encode(sha2::Sha256::digest(b"hello world\r\n"));
+        let signed = format!(
+            "From: Alice <alice@example.com>\r\nSubject: hi\r\nMessage-ID: <m1@example.com>\r\nDKIM-Signature: v=1; a=rsa-sha256; c=simple/simple; d=example.com; s=sel; h=from:subject; bh={bh}; b=sig\r\n\r\nhello world\r\n"
+        );
+
+        let mut config = spam_test_config(Vec::new());
+        let message = parse_inbound_email(signed.as_bytes(), "INBOX", 1, &config)
+            .expect("parse")
+            .expect("message");
+        assert_eq!(
+            message.metadata.get("email_dkim").and_then(|v| v.as_str()),
+            Some("neutral")
+        );
+
+        let tampered = signed.replace("hello world", "send money instead");
+        let message = parse_inbound_email(tampered.as_bytes(), "INBOX", 2, &config)
+            .expect("parse")
+            .expect("message");
+        assert_eq!(
+            message.metadata.get("email_dkim").and_then(|v| v.as_str()),
+            Some("fail")
+        );
+
+        // With the drop flag set a failed verdict never reaches an agent.
+        config.drop_failed_auth = true;
+        assert!(
+            parse_inbound_email(tampered.as_bytes(), "INBOX", 3, &config)
+                .expect("parse")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn authentication_results_verdicts_attach_to_metadata() {
+        let raw = b"From: Bob <bob@example.com>\r\nSubject: hi\r\nMessage-ID: <m2@example.com>\r\nAuthentication-Results: mx.example.com; dkim=pass header.d=example.com; spf=softfail smtp.mailfrom=example.com\r\n\r\nbody\r\n";
+
+        let mut config = spam_test_config(Vec::new());
+        let message = parse_inbound_email(raw, "INBOX", 1, &config)
+            .expect("parse")
+            .expect("message");
+        assert_eq!(
+            message.metadata.get("email_dkim").and_then(|v| v.as_str()),
+            Some("pass")
+        );
+        assert_eq!(
+            message.metadata.get("email_spf").and_then(|v| v.as_str()),
+            Some("softfail")
+        );
+
+        config.drop_failed_auth = true;
+        assert!(
+            parse_inbound_email(raw, "INBOX", 2, &config)
+                .expect("parse")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn size_policy_notice_metadata_lists_violations() {
+        let mut config = spam_test_config(Vec::new());
+        config.max_body_bytes = 1024;
+        config.size_policy_reply = true;
+
+        let body = "x".repeat(5000);
+        let raw = format!(
+            "From: sender@example.com\r\nSubject: big report\r\nMessage-ID: <big@example.com>\r\n\r\n{body}"
+        );
+        let message = parse_inbound_email(raw.as_bytes(), "INBOX", 1, &config)
+            .expect("parse")
+            .expect("message");
+
+        let notice = message
+            .metadata
+            .get("email_size_policy_notice")
+            .and_then(|v| v.as_str())
+            .expect("notice metadata");
+        assert!(notice.contains("truncated"));
+        assert!(notice.contains("1024 byte limit"));
+        assert!(notice.contains("resend"));
+    }
+
+    #[test]
+    fn size_policy_notice_absent_when_disabled() {
+        let mut config = spam_test_config(Vec::new());
+        config.max_body_bytes = 1024;
+
+        let body = "x".repeat(5000);
+        let raw = format!(
+            "From: sender@example.com\r\nSubject: big report\r\nMessage-ID: <big@example.com>\r\n\r\n{body}"
+        );
+        let message = parse_inbound_email(raw.as_bytes(), "INBOX", 1, &config)
+            .expect("parse")
+            .expect("message");
+
+        // Truncation still happens; only the notice is suppressed.
+        assert!(!message.metadata.contains_key("email_size_policy_notice"));
+    }
+
+    #[test]
+    fn upstream_spam_headers_quarantine() {
+        let config = spam_test_config(Vec::new());
+        let raw = b"X-Spam-Flag: YES\r\nSubject: hi\r\n\r\nbody";
+        let parsed = mailparse::parse_mail(raw).expect("parse");
+
+        assert!(
+            spam_reason(raw, &parsed.headers, "hi", "body", &config)
+                .is_some_and(|reason| reason.starts_with("header:"))
+        );
+    }
 }
@@ -0,0 +1,529 @@
+//! GitHub messaging adapter.
+//!
+//! Treats issues and pull requests as conversations: `issue_comment` and
+//! `issues` webhooks become inbound messages keyed by `owner/repo#number`,
+//! and replies are posted back as issue comments through the REST API.
+//! Webhook deliveries are validated with the `x-hub-signature-256` HMAC
+//! when a secret is configured. Bindings can match on the metadata to
+//! route a repository to an agent.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde_json::json;
+use sha2::Digest as _;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// GitHub caps comment bodies at 65536 characters.
+const MAX_MESSAGE_LENGTH: usize = 60_000;
+
+/// GitHub adapter state.
+pub struct GithubAdapter {
+    runtime_key: String,
+    /// Personal access token or GitHub App installation token.
+    token: String,
+    /// Webhook secret; deliveries are accepted unsigned when absent.
+    webhook_secret: Option<String>,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// The authenticated login, used to drop the bot's own comments.
+    own_login: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    runtime_key: String,
+    webhook_secret: Option<String>,
+    own_login: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl GithubAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        token: impl Into<String>,
+        webhook_secret: Option<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            token: token.into(),
+            webhook_secret,
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            own_login: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header(reqwest::header::USER_AGENT, "spacebot")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+    }
+
+    /// Post a comment on an issue or pull request.
+    async fn post_comment(&self, repo: &str, number: i64, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let response = self
+                .request(
+                    reqwest::Method::POST,
+                    format!("{GITHUB_API_BASE}/repos/{repo}/issues/{number}/comments"),
+                )
+                .json(&json!({ "body": chunk }))
+                .send()
+                .await
+                .context("failed to post GitHub comment")?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow::anyhow!(
+                    "GitHub comment on {repo}#{number} failed: HTTP {status}: {body}"
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The `owner/repo` and issue number a reply should target.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, i64)> {
+        let repo = message
+            .metadata
+            .get("github_repo")
+            .and_then(|v| v.as_str())
+            .context("missing github_repo in metadata")?;
+        let number = message
+            .metadata
+            .get("github_issue_number")
+            .and_then(|v| v.as_i64())
+            .context("missing github_issue_number in metadata")?;
+        Ok((repo, number))
+    }
+}
+
+impl Messaging for GithubAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Learn our own login so webhook echoes of our comments are dropped
+        match self
+            .request(reqwest::Method::GET, format!("{GITHUB_API_BASE}/user"))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => {
+                if let Ok(user) = response.json::<serde_json::Value>().await
+                    && let Some(login) = user["login"].as_str()
+                {
+                    tracing::info!(login, "GitHub identity loaded");
+                    *self.own_login.write().await = Some(login.to_string());
+                }
+            }
+            Ok(response) => {
+                let status = response.status();
+                return Err(anyhow::anyhow!("GitHub token rejected: HTTP {status}").into());
+            }
+            Err(error) => {
+                return Err(anyhow::anyhow!("GitHub API unreachable: {error}").into());
+            }
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            runtime_key: self.runtime_key.clone(),
+            webhook_secret: self.webhook_secret.clone(),
+            own_login: self.own_login.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/github", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind GitHub webhook to {bind}"))?;
+        tracing::info!(%bind, "GitHub webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "GitHub webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (repo, number) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.post_comment(repo, number, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Comment attachments need a separate upload flow; describe it
+                let note = match caption {
+                    Some(caption) => format!("{caption}\n\n*(attachment omitted: {filename})*"),
+                    None => format!("*(attachment omitted: {filename})*"),
+                };
+                self.post_comment(repo, number, &note).await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                // Map onto the fixed set of GitHub comment reactions
+                let content = match emoji.as_str() {
+                    "👍" => "+1",
+                    "👎" => "-1",
+                    "😄" | "😀" => "laugh",
+                    "🎉" => "hooray",
+                    "😕" => "confused",
+                    "❤️" => "heart",
+                    "🚀" => "rocket",
+                    _ => "eyes",
+                };
+                let Some(comment_id) = message
+                    .metadata
+                    .get("github_comment_id")
+                    .and_then(|v| v.as_i64())
+                else {
+                    return Ok(());
+                };
+                let response = self
+                    .request(
+                        reqwest::Method::POST,
+                        format!(
+                            "{GITHUB_API_BASE}/repos/{repo}/issues/comments/{comment_id}/reactions"
+                        ),
+                    )
+                    .json(&json!({ "content": content }))
+                    .send()
+                    .await
+                    .context("failed to add GitHub reaction")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    tracing::warn!(%status, "GitHub reaction failed");
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets look like "owner/repo#123"
+        let (repo, number) = target
+            .split_once('#')
+            .context("GitHub broadcast target must be owner/repo#number")?;
+        let number: i64 = number
+            .parse()
+            .context("invalid issue number in GitHub broadcast target")?;
+        self.post_comment(repo, number, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .request(reqwest::Method::GET, format!("{GITHUB_API_BASE}/user"))
+            .send()
+            .await
+            .context("GitHub API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("GitHub health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("GitHub adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    if let Some(secret) = &state.webhook_secret {
+        let Some(signature) = headers
+            .get("x-hub-signature-256")
+            .and_then(|v| v.to_str().ok())
+        else {
+            return StatusCode::UNAUTHORIZED;
+        };
+        if !verify_signature(secret, body.as_bytes(), signature) {
+            tracing::warn!("rejected GitHub webhook with bad signature");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let event_type = headers
+        .get("x-github-event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let own_login = state.own_login.read().await.clone();
+    let Some(inbound) = parse_event(
+        event_type,
+        &payload,
+        &state.runtime_key,
+        own_login.as_deref(),
+    ) else {
+        return StatusCode::OK;
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert an `issue_comment` or `issues` webhook into an inbound message.
+fn parse_event(
+    event_type: &str,
+    payload: &serde_json::Value,
+    runtime_key: &str,
+    own_login: Option<&str>,
+) -> Option<InboundMessage> {
+    // Only fresh activity; edits and deletions are not conversation turns
+    let action = payload["action"].as_str()?;
+    let (text, author, item_id) = match event_type {
+        "issue_comment" if action == "created" => {
+            let comment = payload.get("comment")?;
+            (
+                comment["body"].as_str()?.trim().to_string(),
+                comment["user"]["login"].as_str()?.to_string(),
+                comment["id"].as_i64(),
+            )
+        }
+        "issues" if action == "opened" => {
+            let issue = payload.get("issue")?;
+            let title = issue["title"].as_str().unwrap_or_default();
+            let body = issue["body"].as_str().unwrap_or_default().trim();
+            let text = if body.is_empty() {
+                title.to_string()
+            } else {
+                format!("{title}\n\n{body}")
+            };
+            (text, issue["user"]["login"].as_str()?.to_string(), None)
+        }
+        _ => return None,
+    };
+    if text.is_empty() {
+        return None;
+    }
+    // Drop our own comments echoed back by the webhook
+    if own_login.is_some_and(|login| login.eq_ignore_ascii_case(&author)) {
+        return None;
+    }
+
+    let repo = payload["repository"]["full_name"].as_str()?.to_string();
+    let issue = payload.get("issue")?;
+    let number = issue["number"].as_i64()?;
+    let is_pull_request = issue.get("pull_request").is_some();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "github_repo".into(),
+        serde_json::Value::String(repo.clone()),
+    );
+    metadata.insert("github_issue_number".into(), serde_json::Value::from(number));
+    metadata.insert(
+        "github_is_pull_request".into(),
+        serde_json::Value::Bool(is_pull_request),
+    );
+    if let Some(comment_id) = item_id {
+        metadata.insert(
+            "github_comment_id".into(),
+            serde_json::Value::from(comment_id),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(author.clone()),
+    );
+
+    Some(InboundMessage {
+        id: item_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| format!("{repo}#{number}")),
+        source: "github".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("github:{repo}#{number}"),
+        sender_id: author.clone(),
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(author),
+    })
+}
+
+/// Validate the `x-hub-signature-256` header: `sha256=` plus hex HMAC-SHA256
+/// over the raw body, keyed with the webhook secret.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(received) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    let expected = hex::encode(hmac_sha256(secret.as_bytes(), body));
+    // Constant-time comparison; signatures are attacker-supplied
+    expected.len() == received.len()
+        && expected
+            .bytes()
+            .zip(received.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b.to_ascii_lowercase()))
+            == 0
+}
+
+/// HMAC-SHA256 per RFC 2104, built on the sha2 crate.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&sha2::Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = sha2::Sha256::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = sha2::Sha256::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issue_comments_become_conversation_turns() {
+        let payload = serde_json::json!({
+            "action": "created",
+            "comment": {
+                "id": 42,
+                "body": "can you triage this?",
+                "user": { "login": "alice" },
+            },
+            "issue": {
+                "number": 7,
+                "pull_request": { "url": "https://api.github.com/..." },
+            },
+            "repository": { "full_name": "acme/widgets" },
+        });
+        let inbound = parse_event("issue_comment", &payload, "github", Some("spacebot")).unwrap();
+        assert_eq!(inbound.conversation_id, "github:acme/widgets#7");
+        assert_eq!(inbound.sender_id, "alice");
+        assert_eq!(
+            inbound.metadata.get("github_is_pull_request"),
+            Some(&serde_json::Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn own_comments_and_edits_are_dropped() {
+        let payload = serde_json::json!({
+            "action": "created",
+            "comment": { "id": 1, "body": "done", "user": { "login": "SpaceBot" } },
+            "issue": { "number": 7 },
+            "repository": { "full_name": "acme/widgets" },
+        });
+        assert!(parse_event("issue_comment", &payload, "github", Some("spacebot")).is_none());
+
+        let edited = serde_json::json!({
+            "action": "edited",
+            "comment": { "id": 1, "body": "hm", "user": { "login": "alice" } },
+            "issue": { "number": 7 },
+            "repository": { "full_name": "acme/widgets" },
+        });
+        assert!(parse_event("issue_comment", &edited, "github", None).is_none());
+    }
+
+    #[test]
+    fn webhook_signatures_verify() {
+        let body = b"{\"action\":\"created\"}";
+        let signature = format!("sha256={}", hex::encode(hmac_sha256(b"s3cret", body)));
+        assert!(verify_signature("s3cret", body, &signature));
+        assert!(!verify_signature("wrong", body, &signature));
+        assert!(!verify_signature("s3cret", body, "sha256=deadbeef"));
+    }
+}
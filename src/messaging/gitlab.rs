@@ -0,0 +1,543 @@
+//! GitLab messaging adapter.
+//!
+//! Issues and merge requests act as conversations: project webhooks deliver
+//! note and issue events, and replies go out through the notes API. Notes
+//! on merge request discussions are answered inside the same discussion
+//! thread; everything else gets a plain note on the issue or MR. Webhook
+//! deliveries are validated against the `x-gitlab-token` header when a
+//! secret is configured, and self-managed instances work via `base_url`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// GitLab caps note bodies at 1MB; stay far below it.
+const MAX_MESSAGE_LENGTH: usize = 60_000;
+
+/// GitLab adapter state.
+pub struct GitlabAdapter {
+    runtime_key: String,
+    /// Instance base URL, e.g. `https://gitlab.com`.
+    base_url: String,
+    /// Personal or project access token with `api` scope.
+    token: String,
+    /// Webhook secret token; deliveries are accepted unsigned when absent.
+    webhook_token: Option<String>,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// The authenticated user ID, used to drop the bot's own notes.
+    own_user_id: Arc<RwLock<Option<i64>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    runtime_key: String,
+    webhook_token: Option<String>,
+    own_user_id: Arc<RwLock<Option<i64>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl GitlabAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+        webhook_token: Option<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            webhook_token,
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            own_user_id: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}/api/v4{path}", self.base_url))
+            .header("PRIVATE-TOKEN", &self.token)
+    }
+
+    /// Post a note, threading into an MR discussion when one is given.
+    async fn post_note(
+        &self,
+        project_id: i64,
+        noteable: &str,
+        iid: i64,
+        discussion_id: Option<&str>,
+        text: &str,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let path = match discussion_id {
+                // Only MR discussions accept threaded replies
+                Some(discussion) if noteable == "merge_requests" => format!(
+                    "/projects/{project_id}/merge_requests/{iid}/discussions/{discussion}/notes"
+                ),
+                _ => format!("/projects/{project_id}/{noteable}/{iid}/notes"),
+            };
+            let response = self
+                .request(reqwest::Method::POST, path)
+                .json(&json!({ "body": chunk }))
+                .send()
+                .await
+                .context("failed to post GitLab note")?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow::anyhow!(
+                    "GitLab note on project {project_id} {noteable} !{iid} failed: HTTP {status}: {body}"
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The project, noteable kind, iid, and discussion a reply should target.
+    fn routing(message: &InboundMessage) -> crate::Result<(i64, &str, i64, Option<&str>)> {
+        let project_id = message
+            .metadata
+            .get("gitlab_project_id")
+            .and_then(|v| v.as_i64())
+            .context("missing gitlab_project_id in metadata")?;
+        let noteable = message
+            .metadata
+            .get("gitlab_noteable")
+            .and_then(|v| v.as_str())
+            .context("missing gitlab_noteable in metadata")?;
+        let iid = message
+            .metadata
+            .get("gitlab_iid")
+            .and_then(|v| v.as_i64())
+            .context("missing gitlab_iid in metadata")?;
+        let discussion_id = message
+            .metadata
+            .get("gitlab_discussion_id")
+            .and_then(|v| v.as_str());
+        Ok((project_id, noteable, iid, discussion_id))
+    }
+}
+
+impl Messaging for GitlabAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Learn our own user ID so webhook echoes of our notes are dropped
+        let response = self
+            .request(reqwest::Method::GET, "/user".to_string())
+            .send()
+            .await
+            .context("GitLab API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("GitLab token rejected: HTTP {status}").into());
+        }
+        if let Ok(user) = response.json::<serde_json::Value>().await
+            && let Some(id) = user["id"].as_i64()
+        {
+            tracing::info!(user_id = id, "GitLab identity loaded");
+            *self.own_user_id.write().await = Some(id);
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            runtime_key: self.runtime_key.clone(),
+            webhook_token: self.webhook_token.clone(),
+            own_user_id: self.own_user_id.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/gitlab", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind GitLab webhook to {bind}"))?;
+        tracing::info!(%bind, "GitLab webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "GitLab webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (project_id, noteable, iid, discussion_id) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.post_note(project_id, noteable, iid, discussion_id, &text)
+                    .await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Uploads need the project uploads API; describe the file
+                let note = match caption {
+                    Some(caption) => format!("{caption}\n\n*(attachment omitted: {filename})*"),
+                    None => format!("*(attachment omitted: {filename})*"),
+                };
+                self.post_note(project_id, noteable, iid, discussion_id, &note)
+                    .await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                // Award emoji go on the noteable itself, named not rendered
+                let name = match emoji.as_str() {
+                    "👍" => "thumbsup",
+                    "👎" => "thumbsdown",
+                    "❤️" => "heart",
+                    "🎉" => "tada",
+                    "🚀" => "rocket",
+                    _ => "eyes",
+                };
+                let response = self
+                    .request(
+                        reqwest::Method::POST,
+                        format!("/projects/{project_id}/{noteable}/{iid}/award_emoji"),
+                    )
+                    .json(&json!({ "name": name }))
+                    .send()
+                    .await
+                    .context("failed to add GitLab award emoji")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    tracing::warn!(%status, "GitLab award emoji failed");
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets look like "42#7" (issue) or "42!7" (merge request)
+        let (project_id, noteable, iid) = if let Some((project, iid)) = target.split_once('!') {
+            (project, "merge_requests", iid)
+        } else if let Some((project, iid)) = target.split_once('#') {
+            (project, "issues", iid)
+        } else {
+            return Err(anyhow::anyhow!(
+                "GitLab broadcast target must be project_id#issue_iid or project_id!mr_iid"
+            )
+            .into());
+        };
+        let project_id: i64 = project_id
+            .parse()
+            .context("invalid project ID in GitLab broadcast target")?;
+        let iid: i64 = iid
+            .parse()
+            .context("invalid iid in GitLab broadcast target")?;
+        self.post_note(project_id, noteable, iid, None, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .request(reqwest::Method::GET, "/user".to_string())
+            .send()
+            .await
+            .context("GitLab API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("GitLab health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("GitLab adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    if let Some(expected) = &state.webhook_token {
+        let received = headers
+            .get("x-gitlab-token")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        // Constant-time comparison; tokens are attacker-supplied
+        let matches = expected.len() == received.len()
+            && expected
+                .bytes()
+                .zip(received.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+        if !matches {
+            tracing::warn!("rejected GitLab webhook with bad token");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let own_user_id = *state.own_user_id.read().await;
+    let Some(inbound) = parse_event(&payload, &state.runtime_key, own_user_id) else {
+        return StatusCode::OK;
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert a note or issue webhook payload into an inbound message.
+fn parse_event(
+    payload: &serde_json::Value,
+    runtime_key: &str,
+    own_user_id: Option<i64>,
+) -> Option<InboundMessage> {
+    let project_id = payload["project"]["id"].as_i64()?;
+    let author_id = payload["object_attributes"]["author_id"]
+        .as_i64()
+        .or_else(|| payload["user"]["id"].as_i64());
+    // Drop our own notes echoed back by the webhook
+    if own_user_id.is_some() && author_id == own_user_id {
+        return None;
+    }
+    let author = payload["user"]["username"].as_str()?.to_string();
+
+    let (text, noteable, iid, note_id, discussion_id) =
+        match payload["object_kind"].as_str()? {
+            "note" => {
+                let attributes = &payload["object_attributes"];
+                let text = attributes["note"].as_str()?.trim().to_string();
+                let (noteable, iid) = match attributes["noteable_type"].as_str()? {
+                    "Issue" => ("issues", payload["issue"]["iid"].as_i64()?),
+                    "MergeRequest" => {
+                        ("merge_requests", payload["merge_request"]["iid"].as_i64()?)
+                    }
+                    _ => return None,
+                };
+                (
+                    text,
+                    noteable,
+                    iid,
+                    attributes["id"].as_i64(),
+                    attributes["discussion_id"].as_str().map(str::to_string),
+                )
+            }
+            "issue" => {
+                let attributes = &payload["object_attributes"];
+                if attributes["action"].as_str() != Some("open") {
+                    return None;
+                }
+                let title = attributes["title"].as_str().unwrap_or_default();
+                let description = attributes["description"].as_str().unwrap_or_default().trim();
+                let text = if description.is_empty() {
+                    title.to_string()
+                } else {
+                    format!("{title}\n\n{description}")
+                };
+                (text, "issues", attributes["iid"].as_i64()?, None, None)
+            }
+            _ => return None,
+        };
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "gitlab_project_id".into(),
+        serde_json::Value::from(project_id),
+    );
+    metadata.insert(
+        "gitlab_noteable".into(),
+        serde_json::Value::String(noteable.to_string()),
+    );
+    metadata.insert("gitlab_iid".into(), serde_json::Value::from(iid));
+    if let Some(discussion) = &discussion_id {
+        metadata.insert(
+            "gitlab_discussion_id".into(),
+            serde_json::Value::String(discussion.clone()),
+        );
+    }
+    if let Some(path) = payload["project"]["path_with_namespace"].as_str() {
+        metadata.insert(
+            "gitlab_project_path".into(),
+            serde_json::Value::String(path.to_string()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(author.clone()),
+    );
+
+    Some(InboundMessage {
+        id: note_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| format!("{project_id}:{noteable}:{iid}")),
+        source: "gitlab".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("gitlab:{project_id}:{noteable}:{iid}"),
+        sender_id: author_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| author.clone()),
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(author),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mr_notes_keep_their_discussion_thread() {
+        let payload = serde_json::json!({
+            "object_kind": "note",
+            "user": { "id": 10, "username": "alice" },
+            "project": { "id": 42, "path_with_namespace": "acme/widgets" },
+            "object_attributes": {
+                "id": 901,
+                "note": "does this handle retries?",
+                "noteable_type": "MergeRequest",
+                "author_id": 10,
+                "discussion_id": "abc123",
+            },
+            "merge_request": { "iid": 7 },
+        });
+        let inbound = parse_event(&payload, "gitlab", Some(99)).unwrap();
+        assert_eq!(inbound.conversation_id, "gitlab:42:merge_requests:7");
+        assert_eq!(
+            inbound
+                .metadata
+                .get("gitlab_discussion_id")
+                .and_then(|v| v.as_str()),
+            Some("abc123")
+        );
+    }
+
+    #[test]
+    fn own_notes_and_system_events_are_dropped() {
+        let own = serde_json::json!({
+            "object_kind": "note",
+            "user": { "id": 99, "username": "spacebot" },
+            "project": { "id": 42 },
+            "object_attributes": {
+                "note": "done",
+                "noteable_type": "Issue",
+                "author_id": 99,
+            },
+            "issue": { "iid": 7 },
+        });
+        assert!(parse_event(&own, "gitlab", Some(99)).is_none());
+
+        let push = serde_json::json!({
+            "object_kind": "push",
+            "user": { "id": 10, "username": "alice" },
+            "project": { "id": 42 },
+        });
+        assert!(parse_event(&push, "gitlab", Some(99)).is_none());
+    }
+
+    #[test]
+    fn opened_issues_start_conversations() {
+        let payload = serde_json::json!({
+            "object_kind": "issue",
+            "user": { "id": 10, "username": "alice" },
+            "project": { "id": 42 },
+            "object_attributes": {
+                "iid": 3,
+                "title": "Crash on startup",
+                "description": "stack trace attached",
+                "action": "open",
+                "author_id": 10,
+            },
+        });
+        let inbound = parse_event(&payload, "gitlab", Some(99)).unwrap();
+        assert_eq!(inbound.conversation_id, "gitlab:42:issues:3");
+        assert!(matches!(
+            &inbound.content,
+            MessageContent::Text(text) if text.starts_with("Crash on startup")
+        ));
+    }
+}
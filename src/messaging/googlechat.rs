@@ -0,0 +1,546 @@
+//! Google Chat messaging adapter (service-account bot).
+//!
+//! Inbound events arrive on an HTTP endpoint that works both as a direct
+//! Chat app webhook and as a Pub/Sub push subscription target (the Pub/Sub
+//! envelope is detected and unwrapped). Outbound messages go through the
+//! Chat REST API with a service-account bearer token minted via the JWT
+//! grant. `RichMessage` cards render as `cardsV2`, and spaces/users can be
+//! allowlisted like the Discord and Slack permission structures.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::{Json, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use base64::Engine as _;
+use serde::Deserialize;
+use serde_json::json;
+
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::{JwksCache, split_message};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{Card, InboundMessage, MessageContent, OutboundResponse};
+
+const CHAT_API_BASE: &str = "https://chat.googleapis.com/v1";
+const CHAT_SCOPE: &str = "https://www.googleapis.com/auth/chat.bot";
+
+/// System account Google issues inbound event JWTs from.
+const CHAT_ISSUER: &str = "chat@system.gserviceaccount.com";
+
+/// Published signing keys for [`CHAT_ISSUER`] tokens.
+const CHAT_ISSUER_JWKS_URL: &str =
+    "https://www.googleapis.com/service_accounts/v1/jwk/chat@system.gserviceaccount.com";
+
+/// Chat rejects text messages above 4096 characters; stay under it.
+const MAX_MESSAGE_LENGTH: usize = 4_000;
+
+/// The fields we need from a service-account key file.
+#[derive(Debug, Clone, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+/// A cached bearer token with its expiry.
+struct CachedToken {
+    access_token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Google Chat adapter state.
+pub struct GoogleChatAdapter {
+    runtime_key: String,
+    key: ServiceAccountKey,
+    /// Audience (project number) expected in inbound event JWTs.
+    audience: String,
+    /// Google's signing keys for the Chat system account's bearer tokens.
+    jwks: Arc<JwksCache>,
+    port: u16,
+    bind: String,
+    /// Space names (e.g. `spaces/AAAA1234`) to accept events from. Empty means all.
+    allowed_spaces: Vec<String>,
+    /// Sender user names (e.g. `users/1234`) allowed to talk to the bot. Empty means all.
+    allowed_users: Vec<String>,
+    client: reqwest::Client,
+    token: Arc<RwLock<Option<CachedToken>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    allowed_spaces: Vec<String>,
+    allowed_users: Vec<String>,
+    audience: String,
+    jwks: Arc<JwksCache>,
+    runtime_key: String,
+}
+
+impl GoogleChatAdapter {
+    /// Create an adapter from a service-account key file on disk.
+    pub fn from_key_file(
+        runtime_key: impl Into<String>,
+        credentials_path: &str,
+        audience: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+        allowed_spaces: Vec<String>,
+        allowed_users: Vec<String>,
+    ) -> crate::Result<Self> {
+        let raw = std::fs::read_to_string(credentials_path)
+            .with_context(|| format!("failed to read Google credentials at {credentials_path}"))?;
+        let key: ServiceAccountKey =
+            serde_json::from_str(&raw).context("invalid service-account key file")?;
+
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            key,
+            audience: audience.into(),
+            jwks: Arc::new(JwksCache::new(CHAT_ISSUER_JWKS_URL)),
+            port,
+            bind: bind.into(),
+            allowed_spaces,
+            allowed_users,
+            client: crate::http::client(),
+            token: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Get a bearer token for the Chat API, refreshing via the JWT grant when
+    /// the cached one is near expiry.
+    async fn access_token(&self) -> crate::Result<String> {
+        {
+            let token = self.token.read().await;
+            if let Some(cached) = token.as_ref()
+                && cached.expires_at > chrono::Utc::now() + chrono::Duration::seconds(60)
+            {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let claims = json!({
+            "iss": self.key.client_email,
+            "scope": CHAT_SCOPE,
+            "aud": self.key.token_uri,
+            "iat": now,
+            "exp": now + 3600,
+        });
+        let encoding_key = jsonwebtoken::EncodingKey::from_rsa_pem(self.key.private_key.as_bytes())
+            .context("invalid service-account private key")?;
+        let assertion = jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+            &claims,
+            &encoding_key,
+        )
+        .context("failed to sign service-account JWT")?;
+
+        let response = self
+            .client
+            .post(&self.key.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &assertion),
+            ])
+            .send()
+            .await
+            .context("failed to request Google access token")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Google token request failed: HTTP {status}: {body}").into());
+        }
+
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            expires_in: i64,
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("invalid Google token response")?;
+
+        let access_token = token.access_token.clone();
+        *self.token.write().await = Some(CachedToken {
+            access_token: token.access_token,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(token.expires_in),
+        });
+
+        Ok(access_token)
+    }
+
+    /// Post a message payload into a space, optionally threading it.
+    async fn create_message(
+        &self,
+        space: &str,
+        thread: Option<&str>,
+        mut payload: serde_json::Value,
+    ) -> crate::Result<()> {
+        let token = self.access_token().await?;
+        let mut url = format!("{CHAT_API_BASE}/{space}/messages");
+        if let Some(thread) = thread {
+            payload["thread"] = json!({ "name": thread });
+            url.push_str("?messageReplyOption=REPLY_MESSAGE_FALLBACK_TO_NEW_THREAD");
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&token)
+            .json(&payload)
+            .send()
+            .await
+            .context("failed to create Google Chat message")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(
+                anyhow::anyhow!("Google Chat message create failed: HTTP {status}: {body}").into(),
+            );
+        }
+        Ok(())
+    }
+
+    async fn send_text(&self, space: &str, thread: Option<&str>, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.create_message(space, thread, json!({ "text": chunk }))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// The space and thread a message came from, stored in metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, Option<&str>)> {
+        let space = message
+            .metadata
+            .get("googlechat_space")
+            .and_then(|v| v.as_str())
+            .context("missing googlechat_space in metadata")?;
+        let thread = message
+            .metadata
+            .get("googlechat_thread")
+            .and_then(|v| v.as_str());
+        Ok((space, thread))
+    }
+}
+
+/// Render a [`Card`] as a Chat `cardsV2` entry.
+fn chat_card(card: &Card) -> serde_json::Value {
+    let mut widgets = Vec::new();
+    if let Some(description) = &card.description {
+        widgets.push(json!({ "textParagraph": { "text": description } }));
+    }
+    for field in &card.fields {
+        widgets.push(json!({
+            "decoratedText": {
+                "topLabel": field.name,
+                "text": field.value,
+            }
+        }));
+    }
+    if let Some(url) = &card.url {
+        widgets.push(json!({
+            "buttonList": {
+                "buttons": [{ "text": "Open", "onClick": { "openLink": { "url": url } } }]
+            }
+        }));
+    }
+    if let Some(footer) = &card.footer {
+        widgets.push(json!({ "textParagraph": { "text": footer } }));
+    }
+
+    let mut chat_card = json!({ "sections": [{ "widgets": widgets }] });
+    if let Some(title) = &card.title {
+        chat_card["header"] = json!({ "title": title });
+    }
+    json!({ "card": chat_card })
+}
+
+impl Messaging for GoogleChatAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            inbound_tx: self.inbound_tx.clone(),
+            allowed_spaces: self.allowed_spaces.clone(),
+            allowed_users: self.allowed_users.clone(),
+            audience: self.audience.clone(),
+            jwks: self.jwks.clone(),
+            runtime_key: self.runtime_key.clone(),
+        };
+
+        let app = Router::new()
+            .route("/googlechat", post(handle_event))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind Google Chat endpoint to {bind}"))?;
+        tracing::info!(%bind, "Google Chat event endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "Google Chat endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (space, thread) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(space, thread, &text).await,
+            OutboundResponse::RichMessage { text, cards, .. } => {
+                if cards.is_empty() {
+                    return self.send_text(space, thread, &text).await;
+                }
+                let rendered: Vec<serde_json::Value> = cards.iter().map(chat_card).collect();
+                let mut payload = json!({ "cardsV2": rendered });
+                if !text.is_empty() {
+                    payload["text"] = json!(text);
+                }
+                self.create_message(space, thread, payload).await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Media upload needs the chat.messages.create user scope; link
+                // text is all a service-account bot can do here.
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment omitted: {filename})"),
+                    None => format!("(attachment omitted: {filename})"),
+                };
+                self.send_text(space, thread, &note).await
+            }
+            // No reaction or stream framing API for Chat bots
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        let space = if target.starts_with("spaces/") {
+            target.to_string()
+        } else {
+            format!("spaces/{target}")
+        };
+        self.send_text(&space, None, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.access_token().await.map(|_| ())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Google Chat adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_event(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<serde_json::Value>,
+) -> StatusCode {
+    // Chat webhooks and Pub/Sub push both attach a bearer JWT issued by the
+    // Chat system account; verify it against Google's published keys with
+    // issuer and audience checks so only Google can inject events.
+    let Some(token) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::RS256);
+    validation.set_issuer(&[CHAT_ISSUER]);
+    validation.set_audience(&[state.audience.as_str()]);
+    if let Err(error) = state.jwks.verify(token, &validation).await {
+        tracing::warn!(%error, "rejected Google Chat event with invalid JWT");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    // Pub/Sub push wraps the Chat event in {"message": {"data": base64}}.
+    let event = if let Some(data) = body
+        .get("message")
+        .and_then(|m| m.get("data"))
+        .and_then(|d| d.as_str())
+    {
+        let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(data) else {
+            return StatusCode::BAD_REQUEST;
+        };
+        match serde_json::from_slice::<serde_json::Value>(&decoded) {
+            Ok(event) => event,
+            Err(_) => return StatusCode::BAD_REQUEST,
+        }
+    } else {
+        body
+    };
+
+    if event.get("type").and_then(|t| t.as_str()) != Some("MESSAGE") {
+        return StatusCode::OK;
+    }
+
+    let Some(message) = event.get("message") else {
+        return StatusCode::OK;
+    };
+
+    let sender = message.get("sender");
+    let sender_name = sender
+        .and_then(|s| s.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or_default()
+        .to_string();
+    // Ignore our own messages and other bots
+    if sender.and_then(|s| s.get("type")).and_then(|t| t.as_str()) == Some("BOT") {
+        return StatusCode::OK;
+    }
+
+    let Some(space) = message
+        .get("space")
+        .and_then(|s| s.get("name"))
+        .and_then(|n| n.as_str())
+    else {
+        return StatusCode::OK;
+    };
+
+    if !state.allowed_spaces.is_empty() && !state.allowed_spaces.iter().any(|s| s == space) {
+        tracing::debug!(%space, "ignoring Google Chat event from non-allowlisted space");
+        return StatusCode::OK;
+    }
+    if !state.allowed_users.is_empty() && !state.allowed_users.iter().any(|u| u == &sender_name) {
+        tracing::debug!(sender = %sender_name, "ignoring Google Chat event from non-allowlisted user");
+        return StatusCode::OK;
+    }
+
+    // argumentText has the leading @mention stripped; fall back to raw text
+    let text = message
+        .get("argumentText")
+        .or_else(|| message.get("text"))
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    if text.is_empty() {
+        return StatusCode::OK;
+    }
+
+    let display_name = sender
+        .and_then(|s| s.get("displayName"))
+        .and_then(|n| n.as_str())
+        .unwrap_or(&sender_name)
+        .to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "googlechat_space".into(),
+        serde_json::Value::String(space.to_string()),
+    );
+    if let Some(thread) = message
+        .get("thread")
+        .and_then(|t| t.get("name"))
+        .and_then(|n| n.as_str())
+    {
+        metadata.insert(
+            "googlechat_thread".into(),
+            serde_json::Value::String(thread.to_string()),
+        );
+    }
+    metadata.insert(
+        "googlechat_sender".into(),
+        serde_json::Value::String(sender_name.clone()),
+    );
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(display_name.clone()),
+    );
+
+    let space_id = space.strip_prefix("spaces/").unwrap_or(space);
+    let inbound = InboundMessage {
+        id: message
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "googlechat".into(),
+        adapter: Some(state.runtime_key.clone()),
+        conversation_id: format!("googlechat:{space_id}"),
+        sender_id: sender_name,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(display_name),
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
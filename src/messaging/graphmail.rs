@@ -0,0 +1,959 @@
+//! Microsoft Graph email backend for Exchange Online mailboxes.
+//!
+//! Alternative to the IMAP/SMTP path in [`super::email`], selected with
+//! `backend = "graph"` under `[messaging.email]`. Authenticates with an OAuth2
+//! client-credentials grant against an Azure app registration and keeps the
+//! same inbound/outbound semantics as the IMAP adapter: unread messages are
+//! polled and marked read, replies thread through the original message, and
+//! conversation IDs use the `email:{account}:{thread}` shape.
+
+use crate::config::EmailConfig;
+use crate::messaging::email::{
+    html_to_text, is_allowed_sender, json_value_to_string, normalize_email_target,
+    normalize_message_id, normalize_reply_subject, sanitize_account_key, truncate_to_bytes,
+};
+use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, watch};
+use tokio::task::JoinHandle;
+
+const GRAPH_BASE_URL: &str = "https://graph.microsoft.com/v1.0";
+const GRAPH_SCOPE: &str = "https://graph.microsoft.com/.default";
+const GRAPH_MAX_RETRY_BACKOFF_SECS: u64 = 300;
+/// Refresh the cached token this long before it actually expires.
+const TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+/// Messages fetched per unread poll.
+const POLL_PAGE_SIZE: usize = 25;
+
+/// Shared request context, cloned into the poll task.
+#[derive(Clone)]
+struct GraphContext {
+    tenant_id: String,
+    client_id: String,
+    client_secret: String,
+    /// Mailbox user principal name requests operate on.
+    user: String,
+    from_address: String,
+    allowed_senders: Vec<String>,
+    max_body_bytes: usize,
+    runtime_key: String,
+    client: reqwest::Client,
+    token: Arc<RwLock<Option<CachedToken>>>,
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+impl CachedToken {
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct GraphListResponse {
+    #[serde(default)]
+    value: Vec<GraphMessage>,
+}
+
+/// Subset of the Graph `message` resource the adapter consumes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphMessage {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) subject: Option<String>,
+    #[serde(default)]
+    pub(crate) body: Option<GraphItemBody>,
+    #[serde(default)]
+    pub(crate) from: Option<GraphRecipient>,
+    #[serde(default)]
+    pub(crate) reply_to: Vec<GraphRecipient>,
+    #[serde(default)]
+    pub(crate) to_recipients: Vec<GraphRecipient>,
+    #[serde(default)]
+    pub(crate) received_date_time: Option<String>,
+    #[serde(default)]
+    pub(crate) conversation_id: Option<String>,
+    #[serde(default)]
+    pub(crate) internet_message_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphItemBody {
+    #[serde(default)]
+    pub(crate) content_type: Option<String>,
+    #[serde(default)]
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphRecipient {
+    #[serde(default)]
+    pub(crate) email_address: Option<GraphEmailAddress>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphEmailAddress {
+    #[serde(default)]
+    pub(crate) address: String,
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+}
+
+/// Graph email adapter state.
+pub struct GraphEmailAdapter {
+    context: GraphContext,
+    from_name: Option<String>,
+    poll_interval: Duration,
+    max_attachment_bytes: usize,
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
+    poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+}
+
+impl std::fmt::Debug for GraphEmailAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphEmailAdapter")
+            .field("tenant_id", &self.context.tenant_id)
+            .field("client_id", &"[REDACTED]")
+            .field("client_secret", &"[REDACTED]")
+            .field("user", &"[REDACTED]")
+            .field("from_name", &self.from_name)
+            .field("poll_interval", &self.poll_interval)
+            .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .finish()
+    }
+}
+
+impl GraphEmailAdapter {
+    pub fn from_config(config: &EmailConfig) -> crate::Result<Self> {
+        Self::build(
+            "email".to_string(),
+            &config.graph_tenant_id,
+            &config.graph_client_id,
+            &config.graph_client_secret,
+            &config.graph_user,
+            &config.from_address,
+            config.from_name.clone(),
+            config.poll_interval_secs,
+            config.allowed_senders.clone(),
+            config.max_body_bytes,
+            config.max_attachment_bytes,
+        )
+    }
+
+    pub fn from_instance_config(
+        runtime_key: impl Into<String>,
+        config: &crate::config::EmailInstanceConfig,
+    ) -> crate::Result<Self> {
+        Self::build(
+            runtime_key.into(),
+            &config.graph_tenant_id,
+            &config.graph_client_id,
+            &config.graph_client_secret,
+            &config.graph_user,
+            &config.from_address,
+            config.from_name.clone(),
+            config.poll_interval_secs,
+            config.allowed_senders.clone(),
+            config.max_body_bytes,
+            config.max_attachment_bytes,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        runtime_key: String,
+        tenant_id: &str,
+        client_id: &str,
+        client_secret: &str,
+        user: &str,
+        from_address: &str,
+        from_name: Option<String>,
+        poll_interval_secs: u64,
+        allowed_senders: Vec<String>,
+        max_body_bytes: usize,
+        max_attachment_bytes: usize,
+    ) -> crate::Result<Self> {
+        if tenant_id.is_empty() || client_id.is_empty() || client_secret.is_empty() {
+            return Err(anyhow::anyhow!(
+                "graph email backend requires graph_tenant_id, graph_client_id, and graph_client_secret"
+            )
+            .into());
+        }
+
+        let user = if user.is_empty() { from_address } else { user };
+        if user.is_empty() {
+            return Err(anyhow::anyhow!(
+                "graph email backend requires graph_user or from_address"
+            )
+            .into());
+        }
+
+        let from_address = if from_address.is_empty() {
+            user.to_string()
+        } else {
+            from_address.to_string()
+        };
+
+        let client = crate::http::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("failed to build HTTP client for graph email")?;
+
+        Ok(Self {
+            context: GraphContext {
+                tenant_id: tenant_id.to_string(),
+                client_id: client_id.to_string(),
+                client_secret: client_secret.to_string(),
+                user: user.to_string(),
+                from_address,
+                allowed_senders,
+                max_body_bytes: max_body_bytes.max(1024),
+                runtime_key,
+                client,
+                token: Arc::new(RwLock::new(None)),
+            },
+            from_name,
+            poll_interval: Duration::from_secs(poll_interval_secs.max(5)),
+            max_attachment_bytes: max_attachment_bytes.max(1024),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            poll_task: Arc::new(RwLock::new(None)),
+        })
+    }
+}
+
+impl GraphContext {
+    /// Returns a valid access token, refreshing through the client-credentials
+    /// grant when the cached one is missing or near expiry.
+    async fn access_token(&self) -> anyhow::Result<String> {
+        if let Some(token) = self.token.read().await.as_ref()
+            && !token.is_expired()
+        {
+            return Ok(token.access_token.clone());
+        }
+
+        let url = format!(
+            "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+            self.tenant_id
+        );
+        let response = self
+            .client
+            .post(&url)
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("scope", GRAPH_SCOPE),
+                ("grant_type", "client_credentials"),
+            ])
+            .send()
+            .await
+            .context("graph token request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("graph token request returned {status}: {body}");
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("failed to parse graph token response")?;
+
+        let margin = token.expires_in.saturating_sub(TOKEN_EXPIRY_MARGIN_SECS);
+        let cached = CachedToken {
+            access_token: token.access_token.clone(),
+            expires_at: Instant::now() + Duration::from_secs(margin),
+        };
+        *self.token.write().await = Some(cached);
+
+        Ok(token.access_token)
+    }
+
+    async fn get_json(&self, url: &str) -> anyhow::Result<serde_json::Value> {
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .with_context(|| format!("graph GET {url} failed"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("graph GET {url} returned {status}: {body}");
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("failed to parse graph response from {url}"))
+    }
+
+    async fn post_json(&self, url: &str, body: &serde_json::Value) -> anyhow::Result<()> {
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .with_context(|| format!("graph POST {url} failed"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("graph POST {url} returned {status}: {body}");
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_unread(&self) -> anyhow::Result<Vec<GraphMessage>> {
+        let url = format!(
+            "{GRAPH_BASE_URL}/users/{}/mailFolders/inbox/messages?$filter=isRead eq false&$orderby=receivedDateTime asc&$top={POLL_PAGE_SIZE}",
+            self.user
+        );
+        let value = self.get_json(&url).await?;
+        let list: GraphListResponse =
+            serde_json::from_value(value).context("failed to parse graph message list")?;
+        Ok(list.value)
+    }
+
+    async fn mark_read(&self, message_id: &str) -> anyhow::Result<()> {
+        let token = self.access_token().await?;
+        let url = format!("{GRAPH_BASE_URL}/users/{}/messages/{message_id}", self.user);
+        let response = self
+            .client
+            .patch(&url)
+            .bearer_auth(token)
+            .json(&serde_json::json!({ "isRead": true }))
+            .send()
+            .await
+            .context("graph mark-read request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("graph mark-read returned {status}: {body}");
+        }
+
+        Ok(())
+    }
+
+    /// Replies within the thread of an existing message.
+    async fn reply(&self, graph_message_id: &str, text: &str) -> anyhow::Result<()> {
+        let url = format!(
+            "{GRAPH_BASE_URL}/users/{}/messages/{graph_message_id}/reply",
+            self.user
+        );
+        self.post_json(&url, &serde_json::json!({ "comment": text }))
+            .await
+    }
+
+    /// Sends a fresh message via `sendMail`, optionally with one attachment.
+    async fn send_mail(
+        &self,
+        recipient: &str,
+        subject: &str,
+        body: &str,
+        attachment: Option<(String, bytes::Bytes, String)>,
+    ) -> anyhow::Result<()> {
+        let mut message = serde_json::json!({
+            "subject": subject,
+            "body": { "contentType": "Text", "content": body },
+            "toRecipients": [
+                { "emailAddress": { "address": recipient } }
+            ],
+        });
+
+        if let Some((filename, data, mime_type)) = attachment {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+            message["attachments"] = serde_json::json!([{
+                "@odata.type": "#microsoft.graph.fileAttachment",
+                "name": filename,
+                "contentType": mime_type,
+                "contentBytes": encoded,
+            }]);
+        }
+
+        let url = format!("{GRAPH_BASE_URL}/users/{}/sendMail", self.user);
+        self.post_json(
+            &url,
+            &serde_json::json!({ "message": message, "saveToSentItems": true }),
+        )
+        .await
+    }
+
+    async fn poll_once(&self) -> anyhow::Result<Vec<InboundMessage>> {
+        let messages = self.fetch_unread().await?;
+        let mut inbound_messages = Vec::new();
+
+        for message in messages {
+            let graph_id = message.id.clone();
+            if let Some(inbound) = inbound_from_graph_message(message, self) {
+                inbound_messages.push(inbound);
+            }
+
+            if let Err(error) = self.mark_read(&graph_id).await {
+                tracing::warn!(%error, "failed to mark graph message as read");
+            }
+        }
+
+        Ok(inbound_messages)
+    }
+}
+
+impl Messaging for GraphEmailAdapter {
+    fn name(&self) -> &str {
+        &self.context.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        if self.poll_task.read().await.is_some() {
+            return Err(anyhow::anyhow!("graph email adapter already started").into());
+        }
+
+        let (inbound_tx, inbound_rx) = tokio::sync::mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let context = self.context.clone();
+        let poll_interval = self.poll_interval;
+
+        let poll_task = tokio::spawn(async move {
+            let mut retry_backoff = Duration::from_secs(5);
+
+            loop {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+
+                let mut had_error = false;
+
+                match context.poll_once().await {
+                    Ok(messages) => {
+                        retry_backoff = Duration::from_secs(5);
+                        for message in messages {
+                            if inbound_tx.send(message).await.is_err() {
+                                tracing::warn!(
+                                    "graph email inbound channel closed, stopping adapter loop"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        had_error = true;
+                        tracing::warn!(%error, "graph email poll cycle failed");
+                    }
+                }
+
+                let sleep_duration = if had_error {
+                    let current = retry_backoff;
+                    retry_backoff =
+                        (retry_backoff * 2).min(Duration::from_secs(GRAPH_MAX_RETRY_BACKOFF_SECS));
+                    current
+                } else {
+                    poll_interval
+                };
+
+                tokio::select! {
+                    _ = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                    _ = tokio::time::sleep(sleep_duration) => {}
+                }
+            }
+
+            tracing::info!("graph email adapter loop stopped");
+        });
+
+        *self.poll_task.write().await = Some(poll_task);
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let graph_message_id = message
+            .metadata
+            .get("graph_message_id")
+            .and_then(json_value_to_string);
+
+        let recipient = message
+            .metadata
+            .get("email_reply_to")
+            .and_then(json_value_to_string)
+            .or_else(|| {
+                message
+                    .metadata
+                    .get("email_from")
+                    .and_then(json_value_to_string)
+            });
+
+        let subject = message
+            .metadata
+            .get("email_subject")
+            .and_then(json_value_to_string)
+            .map(|value| normalize_reply_subject(&value))
+            .unwrap_or_else(|| "Re: Spacebot reply".to_string());
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ThreadReply { text, .. } => {
+                self.send_threaded(graph_message_id, recipient, &subject, &text, None)
+                    .await?;
+            }
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                tracing::warn!(
+                    post_at,
+                    subject = %subject,
+                    "graph email adapter does not support scheduled delivery; sending immediately"
+                );
+                self.send_threaded(graph_message_id, recipient, &subject, &text, None)
+                    .await?;
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                if data.len() > self.max_attachment_bytes {
+                    return Err(anyhow::anyhow!(
+                        "attachment '{filename}' exceeds max_attachment_bytes ({} > {})",
+                        data.len(),
+                        self.max_attachment_bytes
+                    )
+                    .into());
+                }
+
+                let mut body = caption.unwrap_or_else(|| format!("Attached file: {filename}"));
+                if body.trim().is_empty() {
+                    body = format!("Attached file: {filename}");
+                }
+
+                // The reply endpoint can't carry attachments, so files always
+                // go out as a fresh message on the same subject.
+                let recipient = recipient
+                    .context("missing recipient metadata for graph email attachment reply")?;
+                self.context
+                    .send_mail(&recipient, &subject, &body, Some((filename, data, mime_type)))
+                    .await?;
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let recipient = normalize_email_target(target)
+            .ok_or_else(|| anyhow::anyhow!("invalid email target '{target}'"))?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. } => {
+                self.context
+                    .send_mail(&recipient, "Spacebot message", &text, None)
+                    .await?;
+            }
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                tracing::warn!(
+                    post_at,
+                    recipient = %recipient,
+                    "graph email adapter does not support scheduled delivery; sending immediately"
+                );
+                self.context
+                    .send_mail(&recipient, "Spacebot message", &text, None)
+                    .await?;
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                let body = caption.unwrap_or_else(|| format!("Attached file: {filename}"));
+                self.context
+                    .send_mail(
+                        &recipient,
+                        "Spacebot message",
+                        &body,
+                        Some((filename, data, mime_type)),
+                    )
+                    .await?;
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let Some(conversation_id) = message
+            .metadata
+            .get("email_thread_key")
+            .and_then(json_value_to_string)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let url = format!(
+            "{GRAPH_BASE_URL}/users/{}/messages?$filter=conversationId eq '{}'&$top={limit}",
+            self.context.user,
+            escape_odata_string(&conversation_id)
+        );
+        let value = self.context.get_json(&url).await?;
+        let list: GraphListResponse =
+            serde_json::from_value(value).context("failed to parse graph history response")?;
+
+        let mut entries = list.value;
+        entries.sort_by(|a, b| a.received_date_time.cmp(&b.received_date_time));
+
+        let history = entries
+            .into_iter()
+            .filter(|entry| entry.id != message.id)
+            .map(|entry| {
+                let (sender_email, sender_name) = primary_address(entry.from.as_ref());
+                let is_bot = sender_email.eq_ignore_ascii_case(&self.context.from_address)
+                    || sender_email.eq_ignore_ascii_case(&self.context.user);
+                HistoryMessage {
+                    author: sender_name.unwrap_or(sender_email),
+                    content: body_text(entry.body.as_ref(), self.context.max_body_bytes),
+                    is_bot,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let skip = history.len().saturating_sub(limit);
+        Ok(history.into_iter().skip(skip).collect())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let url = format!(
+            "{GRAPH_BASE_URL}/users/{}/mailFolders/inbox?$select=id",
+            self.context.user
+        );
+        self.context.get_json(&url).await?;
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
+            shutdown_tx.send(true).ok();
+        }
+
+        if let Some(poll_task) = self.poll_task.write().await.take()
+            && let Err(error) = poll_task.await
+        {
+            tracing::warn!(%error, "graph email poll task join failed during shutdown");
+        }
+
+        tracing::info!("graph email adapter shut down");
+        Ok(())
+    }
+}
+
+impl GraphEmailAdapter {
+    /// Threads through the original Graph message when its ID is known,
+    /// otherwise falls back to a fresh `sendMail` to the reply address.
+    async fn send_threaded(
+        &self,
+        graph_message_id: Option<String>,
+        recipient: Option<String>,
+        subject: &str,
+        text: &str,
+        attachment: Option<(String, bytes::Bytes, String)>,
+    ) -> crate::Result<()> {
+        if let Some(graph_message_id) = graph_message_id
+            && attachment.is_none()
+        {
+            self.context.reply(&graph_message_id, text).await?;
+            return Ok(());
+        }
+
+        let recipient = recipient.context("missing recipient metadata for graph email reply")?;
+        self.context
+            .send_mail(&recipient, subject, text, attachment)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Maps a Graph message onto the adapter's inbound shape, or `None` when the
+/// sender is the bot itself or not in `allowed_senders`.
+fn inbound_from_graph_message(message: GraphMessage, context: &GraphContext) -> Option<InboundMessage> {
+    let (sender_email, sender_name) = primary_address(message.from.as_ref());
+    if sender_email.is_empty() {
+        return None;
+    }
+
+    if sender_email.eq_ignore_ascii_case(&context.from_address)
+        || sender_email.eq_ignore_ascii_case(&context.user)
+    {
+        return None;
+    }
+
+    if !is_allowed_sender(&sender_email, &context.allowed_senders) {
+        return None;
+    }
+
+    let reply_to_email = message
+        .reply_to
+        .first()
+        .and_then(|recipient| recipient.email_address.as_ref())
+        .map(|address| address.address.clone())
+        .filter(|address| !address.is_empty())
+        .unwrap_or_else(|| sender_email.clone());
+
+    let subject = message
+        .subject
+        .clone()
+        .filter(|subject| !subject.is_empty())
+        .unwrap_or_else(|| "(no subject)".to_string());
+
+    let message_id = message
+        .internet_message_id
+        .as_deref()
+        .map(normalize_message_id)
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| message.id.clone());
+
+    let thread_key = message
+        .conversation_id
+        .clone()
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| message_id.clone());
+
+    let account_key = sanitize_account_key(&context.from_address);
+    let conversation_id = format!("email:{account_key}:{thread_key}");
+
+    let body = body_text(message.body.as_ref(), context.max_body_bytes);
+
+    let timestamp = message
+        .received_date_time
+        .as_deref()
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| value.with_timezone(&chrono::Utc))
+        .unwrap_or_else(chrono::Utc::now);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "email_from".into(),
+        serde_json::Value::String(sender_email.clone()),
+    );
+    metadata.insert(
+        "email_reply_to".into(),
+        serde_json::Value::String(reply_to_email),
+    );
+    if let Some(to_address) = message
+        .to_recipients
+        .first()
+        .and_then(|recipient| recipient.email_address.as_ref())
+    {
+        metadata.insert(
+            "email_to".into(),
+            serde_json::Value::String(to_address.address.clone()),
+        );
+    }
+    metadata.insert(
+        "email_subject".into(),
+        serde_json::Value::String(subject.clone()),
+    );
+    metadata.insert(
+        "email_message_id".into(),
+        serde_json::Value::String(message_id.clone()),
+    );
+    metadata.insert(
+        "graph_message_id".into(),
+        serde_json::Value::String(message.id.clone()),
+    );
+    metadata.insert(
+        "email_thread_key".into(),
+        serde_json::Value::String(thread_key),
+    );
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(sender_name.clone().unwrap_or_else(|| sender_email.clone())),
+    );
+
+    let formatted_author = sender_name.map_or_else(
+        || sender_email.clone(),
+        |name| format!("{name} <{sender_email}>"),
+    );
+
+    Some(InboundMessage {
+        id: message_id,
+        source: "email".into(),
+        adapter: Some(context.runtime_key.clone()),
+        conversation_id,
+        sender_id: sender_email,
+        agent_id: None,
+        content: MessageContent::Text(body),
+        timestamp,
+        metadata,
+        formatted_author: Some(formatted_author),
+    })
+}
+
+fn primary_address(recipient: Option<&GraphRecipient>) -> (String, Option<String>) {
+    let Some(address) = recipient.and_then(|recipient| recipient.email_address.as_ref()) else {
+        return (String::new(), None);
+    };
+    (
+        address.address.clone(),
+        address.name.clone().filter(|name| !name.is_empty()),
+    )
+}
+
+/// Renders the Graph item body as plain text, converting HTML bodies and
+/// applying the configured byte cap.
+fn body_text(body: Option<&GraphItemBody>, max_bytes: usize) -> String {
+    let Some(body) = body else {
+        return String::new();
+    };
+
+    let is_html = body
+        .content_type
+        .as_deref()
+        .is_some_and(|value| value.eq_ignore_ascii_case("html"));
+
+    let text = if is_html {
+        html_to_text(&body.content)
+    } else {
+        body.content.trim().to_string()
+    };
+
+    truncate_to_bytes(&text, max_bytes)
+}
+
+/// OData string literals escape single quotes by doubling them.
+fn escape_odata_string(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_context() -> GraphContext {
+        GraphContext {
+            tenant_id: "tenant".into(),
+            client_id: "client".into(),
+            client_secret: "secret".into(),
+            user: "bot@contoso.com".into(),
+            from_address: "bot@contoso.com".into(),
+            allowed_senders: Vec::new(),
+            max_body_bytes: 64 * 1024,
+            runtime_key: "email".into(),
+            client: crate::http::client(),
+            token: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn test_message() -> GraphMessage {
+        serde_json::from_value(serde_json::json!({
+            "id": "AAMk123",
+            "subject": "Quarterly numbers",
+            "body": { "contentType": "html", "content": "<p>Hello <b>there</b></p>" },
+            "from": { "emailAddress": { "address": "alice@example.com", "name": "Alice" } },
+            "receivedDateTime": "2026-08-30T12:00:00Z",
+            "conversationId": "conv-42",
+            "internetMessageId": "<abc@example.com>",
+        }))
+        .expect("valid graph message")
+    }
+
+    #[test]
+    fn graph_message_maps_to_inbound() {
+        let context = test_context();
+        let inbound = inbound_from_graph_message(test_message(), &context).expect("inbound");
+
+        assert_eq!(inbound.conversation_id, "email:bot_contoso_com:conv-42");
+        assert_eq!(inbound.sender_id, "alice@example.com");
+        assert_eq!(inbound.content.to_string(), "Hello there");
+        assert_eq!(
+            inbound.metadata.get("graph_message_id"),
+            Some(&serde_json::Value::String("AAMk123".into()))
+        );
+        assert_eq!(
+            inbound.metadata.get("email_message_id"),
+            Some(&serde_json::Value::String("abc@example.com".into()))
+        );
+        assert_eq!(inbound.formatted_author.as_deref(), Some("Alice <alice@example.com>"));
+    }
+
+    #[test]
+    fn own_messages_are_skipped() {
+        let context = test_context();
+        let mut message = test_message();
+        message.from = Some(GraphRecipient {
+            email_address: Some(GraphEmailAddress {
+                address: "bot@contoso.com".into(),
+                name: None,
+            }),
+        });
+
+        assert!(inbound_from_graph_message(message, &context).is_none());
+    }
+
+    #[test]
+    fn odata_strings_escape_quotes() {
+        assert_eq!(escape_odata_string("conv'42"), "conv''42");
+        assert_eq!(escape_odata_string("plain"), "plain");
+    }
+}
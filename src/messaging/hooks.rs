@@ -0,0 +1,139 @@
+//! Per-agent webhook "persona endpoints".
+//!
+//! `POST /api/hooks/agents/{id}` accepts plain text or JSON and answers with
+//! the agent's reply in the HTTP response, so scripts can call one specific
+//! agent without binding setup. Like webchat, the adapter owns no server or
+//! inbound stream: the API handler injects messages through
+//! `MessagingManager::inject_message` and parks a waiter here that `respond`
+//! completes with the first textual response for the conversation.
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, OutboundResponse};
+
+use std::collections::HashMap;
+use tokio::sync::{Mutex, oneshot};
+
+/// Hooks adapter. Responses are routed to parked HTTP handlers by
+/// conversation ID; replies arriving after the caller gave up are dropped
+/// here but remain in the conversation log.
+#[derive(Default)]
+pub struct HooksAdapter {
+    pending: Mutex<HashMap<String, oneshot::Sender<String>>>,
+}
+
+impl HooksAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Park a waiter for the first textual reply in `conversation_id`.
+    /// A second waiter for the same conversation replaces the first.
+    pub async fn wait_for_reply(&self, conversation_id: &str) -> oneshot::Receiver<String> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .await
+            .insert(conversation_id.to_string(), reply_tx);
+        reply_rx
+    }
+
+    /// Drop a parked waiter (the caller timed out) so the map does not grow.
+    pub async fn abandon(&self, conversation_id: &str) {
+        self.pending.lock().await.remove(conversation_id);
+    }
+}
+
+impl Messaging for HooksAdapter {
+    fn name(&self) -> &str {
+        "hooks"
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Inbound messages bypass the stream via inject_message, so return
+        // a stream that stays open but never yields.
+        Ok(Box::pin(futures::stream::pending()))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let text = match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::RichMessage { text, .. } => text,
+            // Reactions, files, and stream chunks have no synchronous shape.
+            _ => return Ok(()),
+        };
+
+        if let Some(waiter) = self.pending.lock().await.remove(&message.conversation_id) {
+            let _ = waiter.send(text);
+        } else {
+            tracing::debug!(
+                conversation_id = %message.conversation_id,
+                "hook reply arrived after the caller stopped waiting"
+            );
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        self.pending.lock().await.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HooksAdapter;
+    use crate::messaging::traits::Messaging;
+    use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+    fn inbound(conversation_id: &str) -> InboundMessage {
+        InboundMessage {
+            id: "1".into(),
+            source: "hooks".into(),
+            adapter: Some("hooks".into()),
+            conversation_id: conversation_id.into(),
+            sender_id: "script".into(),
+            agent_id: Some("helper".into()),
+            content: MessageContent::Text("ping".into()),
+            timestamp: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            formatted_author: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn first_textual_reply_completes_the_parked_waiter() {
+        let adapter = HooksAdapter::new();
+        let message = inbound("hooks:helper:session");
+
+        let receiver = adapter.wait_for_reply(&message.conversation_id).await;
+
+        // Non-textual responses leave the waiter parked.
+        adapter
+            .respond(&message, OutboundResponse::Reaction("👍".into()))
+            .await
+            .expect("respond");
+        adapter
+            .respond(&message, OutboundResponse::Text("pong".into()))
+            .await
+            .expect("respond");
+
+        assert_eq!(receiver.await.expect("reply"), "pong");
+
+        // A second reply for the same conversation has nowhere to go and is
+        // dropped without error.
+        adapter
+            .respond(&message, OutboundResponse::Text("again".into()))
+            .await
+            .expect("respond");
+    }
+}
@@ -0,0 +1,373 @@
+//! Shared plumbing for webhook-in/REST-out bot platforms.
+//!
+//! LINE, Viber, KakaoTalk and friends all follow the same shape: an HMAC
+//! signature over the raw webhook body, single-shot reply tokens with a short
+//! server-side validity, and outbound APIs that throttle pushy bots. The
+//! helpers here keep each adapter down to platform-specific parsing and
+//! payload building.
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use sha2::Digest as _;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+
+/// HMAC-SHA256 per RFC 2104, built on the sha2 crate.
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&sha2::Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = sha2::Sha256::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = sha2::Sha256::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+/// Constant-time string comparison; signatures are attacker-supplied.
+pub(crate) fn constant_time_eq(expected: &str, supplied: &str) -> bool {
+    expected.len() == supplied.len()
+        && expected
+            .bytes()
+            .zip(supplied.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Validate a base64-encoded HMAC-SHA256 signature over the raw body
+/// (LINE's `x-line-signature` style).
+pub(crate) fn verify_hmac_base64(secret: &str, body: &[u8], signature: &str) -> bool {
+    let expected =
+        base64::engine::general_purpose::STANDARD.encode(hmac_sha256(secret.as_bytes(), body));
+    constant_time_eq(&expected, signature)
+}
+
+/// Validate a hex-encoded HMAC-SHA256 signature over the raw body
+/// (Viber's `X-Viber-Content-Signature` style).
+pub(crate) fn verify_hmac_hex(secret: &str, body: &[u8], signature: &str) -> bool {
+    let expected = hex::encode(hmac_sha256(secret.as_bytes(), body));
+    constant_time_eq(&expected, &signature.to_ascii_lowercase())
+}
+
+/// Don't refetch the JWKS document more often than this, so a flood of
+/// tokens with bogus `kid`s can't turn into a flood of outbound requests.
+const JWKS_REFRESH_MIN_SECS: u64 = 60;
+
+/// Cached RSA public keys (a JWKS document) used to verify RS256 bearer
+/// tokens on webhook endpoints (Bot Framework, Google Chat). Keys are
+/// fetched lazily and refreshed when a token arrives with an unknown `kid`.
+pub(crate) struct JwksCache {
+    client: reqwest::Client,
+    /// Direct JWKS document URL, resolved via discovery when `None`.
+    jwks_url: RwLock<Option<String>>,
+    /// OpenID configuration URL carrying the `jwks_uri` field.
+    discovery_url: Option<String>,
+    /// `kid` to RSA `(n, e)` components, both base64url as published.
+    keys: RwLock<HashMap<String, (String, String)>>,
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+impl JwksCache {
+    /// Cache keys from a fixed JWKS document URL.
+    pub(crate) fn new(jwks_url: impl Into<String>) -> Self {
+        Self {
+            client: crate::http::client(),
+            jwks_url: RwLock::new(Some(jwks_url.into())),
+            discovery_url: None,
+            keys: RwLock::new(HashMap::new()),
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// Cache keys from the `jwks_uri` advertised by an OpenID configuration
+    /// document.
+    pub(crate) fn from_openid_discovery(discovery_url: impl Into<String>) -> Self {
+        Self {
+            client: crate::http::client(),
+            jwks_url: RwLock::new(None),
+            discovery_url: Some(discovery_url.into()),
+            keys: RwLock::new(HashMap::new()),
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    /// Verify an RS256 token's signature and claims against the cached keys,
+    /// refreshing the key set when the token's `kid` is unknown.
+    pub(crate) async fn verify(
+        &self,
+        token: &str,
+        validation: &jsonwebtoken::Validation,
+    ) -> anyhow::Result<()> {
+        let header = jsonwebtoken::decode_header(token).context("malformed JWT header")?;
+        let kid = header.kid.context("JWT has no kid")?;
+
+        let components = match self.keys.read().await.get(&kid).cloned() {
+            Some(components) => components,
+            None => {
+                self.refresh().await?;
+                self.keys
+                    .read()
+                    .await
+                    .get(&kid)
+                    .cloned()
+                    .with_context(|| format!("no JWKS key for kid {kid}"))?
+            }
+        };
+
+        let decoding_key =
+            jsonwebtoken::DecodingKey::from_rsa_components(&components.0, &components.1)
+                .context("invalid RSA components in JWKS")?;
+        jsonwebtoken::decode::<serde_json::Value>(token, &decoding_key, validation)
+            .context("JWT validation failed")?;
+        Ok(())
+    }
+
+    /// Refetch the JWKS document, resolving it via discovery first if needed.
+    /// Throttled so unknown `kid`s can't trigger request floods.
+    async fn refresh(&self) -> anyhow::Result<()> {
+        let mut last_refresh = self.last_refresh.lock().await;
+        if let Some(at) = *last_refresh
+            && at.elapsed() < Duration::from_secs(JWKS_REFRESH_MIN_SECS)
+        {
+            return Ok(());
+        }
+        *last_refresh = Some(Instant::now());
+
+        let jwks_url = match self.jwks_url.read().await.clone() {
+            Some(url) => url,
+            None => {
+                let discovery_url = self
+                    .discovery_url
+                    .as_deref()
+                    .context("no JWKS or discovery URL configured")?;
+                let config: serde_json::Value = self
+                    .client
+                    .get(discovery_url)
+                    .send()
+                    .await
+                    .context("failed to fetch OpenID configuration")?
+                    .json()
+                    .await
+                    .context("malformed OpenID configuration")?;
+                let url = config
+                    .get("jwks_uri")
+                    .and_then(|u| u.as_str())
+                    .context("OpenID configuration has no jwks_uri")?
+                    .to_string();
+                *self.jwks_url.write().await = Some(url.clone());
+                url
+            }
+        };
+
+        let document: serde_json::Value = self
+            .client
+            .get(&jwks_url)
+            .send()
+            .await
+            .context("failed to fetch JWKS document")?
+            .json()
+            .await
+            .context("malformed JWKS document")?;
+
+        let mut keys = HashMap::new();
+        for key in document
+            .get("keys")
+            .and_then(|k| k.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if key.get("kty").and_then(|v| v.as_str()) != Some("RSA") {
+                continue;
+            }
+            let (Some(kid), Some(n), Some(e)) = (
+                key.get("kid").and_then(|v| v.as_str()),
+                key.get("n").and_then(|v| v.as_str()),
+                key.get("e").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            keys.insert(kid.to_string(), (n.to_string(), e.to_string()));
+        }
+        if keys.is_empty() {
+            anyhow::bail!("JWKS document at {jwks_url} contained no RSA keys");
+        }
+        *self.keys.write().await = keys;
+        Ok(())
+    }
+}
+
+/// Tracks single-shot reply tokens so only the first response to an inbound
+/// message uses the reply path; later responses fall back to push APIs.
+#[derive(Default)]
+pub(crate) struct UsedReplyTokens {
+    inner: RwLock<HashSet<String>>,
+}
+
+impl UsedReplyTokens {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true when the token was fresh (and is now marked consumed).
+    pub(crate) async fn try_consume(&self, token: &str) -> bool {
+        self.inner.write().await.insert(token.to_string())
+    }
+}
+
+/// Per-target outbound pacing: waits until `min_interval` has elapsed since
+/// the previous send to the same key, so bursty responses don't trip platform
+/// flood protection.
+pub(crate) struct OutboundLimiter {
+    min_interval: Duration,
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl OutboundLimiter {
+    pub(crate) fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Blocks until a send to `key` is allowed, then records the slot.
+    pub(crate) async fn acquire(&self, key: &str) {
+        loop {
+            let wait = {
+                let mut last_sent = self.last_sent.lock().await;
+                let now = Instant::now();
+                match last_sent.get(key) {
+                    Some(last) if now.duration_since(*last) < self.min_interval => {
+                        Some(self.min_interval - now.duration_since(*last))
+                    }
+                    _ => {
+                        last_sent.insert(key.to_string(), now);
+                        None
+                    }
+                }
+            };
+
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+}
+
+/// Split a message into chunks at line boundaries where possible.
+pub(crate) fn split_message(text: &str, max_length: usize) -> Vec<String> {
+    if text.len() <= max_length {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.split_inclusive('\n') {
+        if current.len() + line.len() > max_length && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if line.len() > max_length {
+            let mut remaining = line;
+            while remaining.len() > max_length {
+                let mut split_at = max_length;
+                while !remaining.is_char_boundary(split_at) {
+                    split_at -= 1;
+                }
+                let (head, tail) = remaining.split_at(split_at);
+                chunks.push(head.to_string());
+                remaining = tail;
+            }
+            current.push_str(remaining);
+        } else {
+            current.push_str(line);
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc_4231_test_case_2() {
+        // Key "Jefe", data "what do ya want for nothing?"
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn base64_signatures_round_trip() {
+        let secret = "channel-secret";
+        let body = br#"{"events":[]}"#;
+        let signature = base64::engine::general_purpose::STANDARD
+            .encode(hmac_sha256(secret.as_bytes(), body));
+
+        assert!(verify_hmac_base64(secret, body, &signature));
+        assert!(!verify_hmac_base64(secret, body, "AAAA"));
+        assert!(!verify_hmac_base64("other-secret", body, &signature));
+    }
+
+    #[test]
+    fn hex_signatures_verify_case_insensitively() {
+        let secret = "auth-token";
+        let body = br#"{"event":"message"}"#;
+        let signature = hex::encode(hmac_sha256(secret.as_bytes(), body));
+
+        assert!(verify_hmac_hex(secret, body, &signature));
+        assert!(verify_hmac_hex(secret, body, &signature.to_ascii_uppercase()));
+        assert!(!verify_hmac_hex(secret, body, "deadbeef"));
+        assert!(!verify_hmac_hex("other", body, &signature));
+    }
+
+    #[tokio::test]
+    async fn reply_tokens_are_single_use() {
+        let tokens = UsedReplyTokens::new();
+        assert!(tokens.try_consume("token-1").await);
+        assert!(!tokens.try_consume("token-1").await);
+        assert!(tokens.try_consume("token-2").await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limiter_paces_sends_per_target() {
+        let limiter = OutboundLimiter::new(Duration::from_millis(500));
+
+        let started = tokio::time::Instant::now();
+        limiter.acquire("user-1").await;
+        // A different target is not held up by the first.
+        limiter.acquire("user-2").await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        limiter.acquire("user-1").await;
+        assert!(started.elapsed() >= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn long_messages_split_on_line_boundaries() {
+        let text = "first line\nsecond line\nthird line\n";
+        let chunks = split_message(text, 12);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 12));
+        assert_eq!(chunks.concat(), text);
+    }
+}
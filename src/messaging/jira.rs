@@ -0,0 +1,438 @@
+//! Jira messaging adapter.
+//!
+//! Issue comments act as conversations: a Jira webhook delivers
+//! comment-created (and issue-created) events, and replies are posted back
+//! through the REST comment API using basic auth with an API token. Jira
+//! Cloud webhooks can't set custom headers, so deliveries are optionally
+//! authenticated with a `token` query parameter instead. The issue key and
+//! project key are surfaced in metadata for agent prompts and bindings.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Jira caps comment bodies at 32KB of rendered content; stay below it.
+const MAX_MESSAGE_LENGTH: usize = 30_000;
+
+/// Jira adapter state.
+pub struct JiraAdapter {
+    runtime_key: String,
+    /// Site base URL, e.g. `https://acme.atlassian.net`.
+    base_url: String,
+    /// Account email paired with the API token for basic auth.
+    email: String,
+    api_token: String,
+    /// Shared secret expected in the webhook's `token` query parameter;
+    /// deliveries are accepted unauthenticated when absent.
+    webhook_token: Option<String>,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// The authenticated account ID, used to drop the bot's own comments.
+    own_account_id: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    runtime_key: String,
+    webhook_token: Option<String>,
+    own_account_id: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl JiraAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        email: impl Into<String>,
+        api_token: impl Into<String>,
+        webhook_token: Option<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            email: email.into(),
+            api_token: api_token.into(),
+            webhook_token,
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            own_account_id: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: String) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+    }
+
+    /// Add a comment to an issue.
+    async fn post_comment(&self, issue_key: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let response = self
+                .request(
+                    reqwest::Method::POST,
+                    format!("/rest/api/2/issue/{issue_key}/comment"),
+                )
+                .json(&json!({ "body": chunk }))
+                .send()
+                .await
+                .context("failed to post Jira comment")?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow::anyhow!(
+                    "Jira comment on {issue_key} failed: HTTP {status}: {body}"
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The issue key a reply should target.
+    fn routing(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("jira_issue_key")
+            .and_then(|v| v.as_str())
+            .context("missing jira_issue_key in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for JiraAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Learn our own account ID so webhook echoes of our comments are dropped
+        let response = self
+            .request(reqwest::Method::GET, "/rest/api/2/myself".to_string())
+            .send()
+            .await
+            .context("Jira API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Jira credentials rejected: HTTP {status}").into());
+        }
+        if let Ok(myself) = response.json::<serde_json::Value>().await
+            && let Some(account_id) = myself["accountId"].as_str()
+        {
+            tracing::info!(account_id, "Jira identity loaded");
+            *self.own_account_id.write().await = Some(account_id.to_string());
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            runtime_key: self.runtime_key.clone(),
+            webhook_token: self.webhook_token.clone(),
+            own_account_id: self.own_account_id.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/jira", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind Jira webhook to {bind}"))?;
+        tracing::info!(%bind, "Jira webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "Jira webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let issue_key = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.post_comment(issue_key, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Attachments need the multipart attachments API; describe it
+                let note = match caption {
+                    Some(caption) => format!("{caption}\n\n_(attachment omitted: {filename})_"),
+                    None => format!("_(attachment omitted: {filename})_"),
+                };
+                self.post_comment(issue_key, &note).await
+            }
+            // Jira has no comment reactions or stream framing
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets are issue keys like "OPS-123"
+        self.post_comment(target, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .request(reqwest::Method::GET, "/rest/api/2/myself".to_string())
+            .send()
+            .await
+            .context("Jira API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Jira health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Jira adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+    body: String,
+) -> StatusCode {
+    if let Some(expected) = &state.webhook_token {
+        let received = params.get("token").map(String::as_str).unwrap_or_default();
+        // Constant-time comparison; tokens are attacker-supplied
+        let matches = expected.len() == received.len()
+            && expected
+                .bytes()
+                .zip(received.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+        if !matches {
+            tracing::warn!("rejected Jira webhook with bad token");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let own_account_id = state.own_account_id.read().await.clone();
+    let Some(inbound) = parse_event(&payload, &state.runtime_key, own_account_id.as_deref())
+    else {
+        return StatusCode::OK;
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert a comment-created or issue-created webhook into an inbound message.
+fn parse_event(
+    payload: &serde_json::Value,
+    runtime_key: &str,
+    own_account_id: Option<&str>,
+) -> Option<InboundMessage> {
+    let issue = payload.get("issue")?;
+    let issue_key = issue["key"].as_str()?.to_string();
+
+    let (text, author, comment_id) = match payload["webhookEvent"].as_str()? {
+        "comment_created" => {
+            let comment = payload.get("comment")?;
+            (
+                comment["body"].as_str()?.trim().to_string(),
+                &comment["author"],
+                comment["id"].as_str().map(str::to_string),
+            )
+        }
+        "jira:issue_created" => {
+            let fields = &issue["fields"];
+            let summary = fields["summary"].as_str().unwrap_or_default();
+            let description = fields["description"].as_str().unwrap_or_default().trim();
+            let text = if description.is_empty() {
+                summary.to_string()
+            } else {
+                format!("{summary}\n\n{description}")
+            };
+            (text, &fields["reporter"], None)
+        }
+        _ => return None,
+    };
+    if text.is_empty() {
+        return None;
+    }
+
+    let account_id = author["accountId"].as_str()?.to_string();
+    // Drop our own comments echoed back by the webhook
+    if own_account_id.is_some_and(|own| own == account_id) {
+        return None;
+    }
+    let display_name = author["displayName"]
+        .as_str()
+        .unwrap_or(&account_id)
+        .to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "jira_issue_key".into(),
+        serde_json::Value::String(issue_key.clone()),
+    );
+    if let Some(project_key) = issue["fields"]["project"]["key"].as_str() {
+        metadata.insert(
+            "jira_project_key".into(),
+            serde_json::Value::String(project_key.to_string()),
+        );
+    }
+    if let Some(summary) = issue["fields"]["summary"].as_str() {
+        metadata.insert(
+            "jira_issue_summary".into(),
+            serde_json::Value::String(summary.to_string()),
+        );
+    }
+    if let Some(comment_id) = &comment_id {
+        metadata.insert(
+            "jira_comment_id".into(),
+            serde_json::Value::String(comment_id.clone()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(display_name.clone()),
+    );
+
+    Some(InboundMessage {
+        id: comment_id.unwrap_or_else(|| issue_key.clone()),
+        source: "jira".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("jira:{issue_key}"),
+        sender_id: account_id,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(display_name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_key_conversations_by_issue() {
+        let payload = serde_json::json!({
+            "webhookEvent": "comment_created",
+            "issue": {
+                "key": "OPS-42",
+                "fields": {
+                    "summary": "Deploys failing",
+                    "project": { "key": "OPS" },
+                },
+            },
+            "comment": {
+                "id": "10001",
+                "body": "any update?",
+                "author": { "accountId": "acc-1", "displayName": "Alice" },
+            },
+        });
+        let inbound = parse_event(&payload, "jira", Some("acc-bot")).unwrap();
+        assert_eq!(inbound.conversation_id, "jira:OPS-42");
+        assert_eq!(
+            inbound.metadata.get("jira_project_key").and_then(|v| v.as_str()),
+            Some("OPS")
+        );
+        assert_eq!(inbound.formatted_author.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn own_comments_and_other_events_are_dropped() {
+        let own = serde_json::json!({
+            "webhookEvent": "comment_created",
+            "issue": { "key": "OPS-42", "fields": {} },
+            "comment": {
+                "id": "10002",
+                "body": "on it",
+                "author": { "accountId": "acc-bot" },
+            },
+        });
+        assert!(parse_event(&own, "jira", Some("acc-bot")).is_none());
+
+        let updated = serde_json::json!({
+            "webhookEvent": "jira:issue_updated",
+            "issue": { "key": "OPS-42", "fields": {} },
+        });
+        assert!(parse_event(&updated, "jira", None).is_none());
+    }
+}
@@ -0,0 +1,965 @@
+//! JMAP email backend (RFC 8620/8621) for providers like Fastmail.
+//!
+//! Alternative to the IMAP/SMTP path in [`super::email`], selected with
+//! `backend = "jmap"` under `[messaging.email]`. Speaks JSON over HTTP with a
+//! bearer token and keeps the same inbound/outbound semantics as the IMAP
+//! adapter: unseen inbox mail is polled and marked `$seen` after pickup, the
+//! raw RFC 5322 bytes are downloaded per message and run through the shared
+//! MIME parser so threading and conversation IDs come out identical, and
+//! replies are uploaded as blobs and handed to `EmailSubmission/set`.
+//!
+//! The transport surface is the small [`MailTransport`] trait, also
+//! implemented for the IMAP/SMTP pair so the two stacks stay interchangeable
+//! behind the same fetch/send contract.
+
+use crate::config::EmailConfig;
+use crate::messaging::email::{
+    self, EmailPollConfig, build_smtp_transport, clearsign_with_pgp, format_message_id_for_header,
+    markdown_to_html, normalize_email_target, parse_mailbox, poll_config_from,
+    reply_context_from_message,
+};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, OutboundResponse};
+
+use anyhow::Context as _;
+use lettre::message::{MultiPart, SinglePart};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::sync::{RwLock, watch};
+use tokio::task::JoinHandle;
+
+/// JMAP capability URN that names the mail account in the session resource.
+const JMAP_MAIL_CAPABILITY: &str = "urn:ietf:params:jmap:mail";
+
+/// Capabilities requested with every API call.
+const JMAP_USING: [&str; 3] = [
+    "urn:ietf:params:jmap:core",
+    "urn:ietf:params:jmap:mail",
+    "urn:ietf:params:jmap:submission",
+];
+
+/// Messages fetched per unseen poll.
+const JMAP_POLL_PAGE_SIZE: usize = 25;
+
+const JMAP_MAX_RETRY_BACKOFF_SECS: u64 = 300;
+
+/// One outbound email as the transports see it.
+pub(crate) struct OutgoingMail {
+    pub(crate) recipient: String,
+    pub(crate) subject: String,
+    pub(crate) body: String,
+    pub(crate) in_reply_to: Option<String>,
+    pub(crate) references: Vec<String>,
+}
+
+/// Minimal transport surface shared by the JMAP and IMAP/SMTP mail paths:
+/// pick up whatever arrived since the last call and hand outbound mail to the
+/// provider.
+pub(crate) trait MailTransport: Send + Sync {
+    /// Inbound messages that arrived since the previous call.
+    fn fetch_new(
+        &self,
+    ) -> impl std::future::Future<Output = anyhow::Result<Vec<InboundMessage>>> + Send;
+
+    /// Deliver one outbound message.
+    fn send(
+        &self,
+        mail: OutgoingMail,
+    ) -> impl std::future::Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// The IMAP/SMTP pair behind the transport surface, delegating to the poll
+/// loop and SMTP transport in [`super::email`].
+#[allow(dead_code)]
+pub(crate) struct ImapTransport {
+    poll_config: EmailPollConfig,
+    smtp: AsyncSmtpTransport<Tokio1Executor>,
+    from_address: String,
+    from_name: Option<String>,
+    pgp_sign: bool,
+    pgp_key_id: String,
+}
+
+#[allow(dead_code)]
+impl ImapTransport {
+    pub(crate) fn from_config(runtime_key: &str, config: &EmailConfig) -> crate::Result<Self> {
+        Ok(Self {
+            poll_config: poll_config_from(config, runtime_key.to_string()),
+            smtp: build_smtp_transport(config)?,
+            from_address: config.from_address.clone(),
+            from_name: config.from_name.clone(),
+            pgp_sign: config.pgp_sign,
+            pgp_key_id: config.pgp_key_id.clone(),
+        })
+    }
+}
+
+impl MailTransport for ImapTransport {
+    async fn fetch_new(&self) -> anyhow::Result<Vec<InboundMessage>> {
+        let poll_config = self.poll_config.clone();
+        tokio::task::spawn_blocking(move || email::poll_inbox_once(&poll_config))
+            .await
+            .context("IMAP poll task failed")?
+    }
+
+    async fn send(&self, mail: OutgoingMail) -> anyhow::Result<()> {
+        let message = build_rfc822(
+            &self.from_address,
+            self.from_name.as_deref(),
+            self.pgp_sign.then_some(self.pgp_key_id.as_str()),
+            &mail,
+        )?;
+        self.smtp
+            .send(message)
+            .await
+            .context("failed to send email via SMTP")?;
+        Ok(())
+    }
+}
+
+/// Endpoints and account discovered from the JMAP session resource.
+#[derive(Clone)]
+struct JmapSession {
+    api_url: String,
+    upload_url: String,
+    download_url: String,
+    account_id: String,
+}
+
+/// JMAP implementation of [`MailTransport`] over the provider's API endpoint.
+pub(crate) struct JmapTransport {
+    session_url: String,
+    token: String,
+    from_address: String,
+    /// Clearsign outbound bodies with this `gpg` key before upload.
+    pgp_sign: bool,
+    pgp_key_id: String,
+    client: reqwest::Client,
+    session: RwLock<Option<JmapSession>>,
+    inbox_id: RwLock<Option<String>>,
+    identity_id: RwLock<Option<String>>,
+    /// Parse settings shared with the IMAP path's MIME handling.
+    poll_config: EmailPollConfig,
+    /// Synthetic UID for parsed messages; JMAP has no IMAP UIDs.
+    next_uid: AtomicU32,
+}
+
+impl JmapTransport {
+    fn from_config(runtime_key: &str, config: &EmailConfig) -> crate::Result<Self> {
+        let client = crate::http::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("failed to build HTTP client for JMAP email")?;
+
+        Ok(Self {
+            session_url: config.jmap_session_url.clone(),
+            token: config.jmap_token.clone(),
+            from_address: config.from_address.clone(),
+            pgp_sign: config.pgp_sign,
+            pgp_key_id: config.pgp_key_id.clone(),
+            client,
+            session: RwLock::new(None),
+            inbox_id: RwLock::new(None),
+            identity_id: RwLock::new(None),
+            poll_config: poll_config_from(config, runtime_key.to_string()),
+            next_uid: AtomicU32::new(1),
+        })
+    }
+
+    /// Fetch (and cache) the session resource: API/upload/download endpoints
+    /// plus the primary mail account ID.
+    async fn session(&self) -> anyhow::Result<JmapSession> {
+        if let Some(session) = self.session.read().await.as_ref() {
+            return Ok(session.clone());
+        }
+
+        let response = self
+            .client
+            .get(&self.session_url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("JMAP session request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("JMAP session request returned {status}: {body}");
+        }
+
+        let value: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to parse JMAP session resource")?;
+
+        let account_id = value["primaryAccounts"][JMAP_MAIL_CAPABILITY]
+            .as_str()
+            .context("JMAP session has no primary mail account")?
+            .to_string();
+        let session = JmapSession {
+            api_url: value["apiUrl"]
+                .as_str()
+                .context("JMAP session has no apiUrl")?
+                .to_string(),
+            upload_url: value["uploadUrl"]
+                .as_str()
+                .context("JMAP session has no uploadUrl")?
+                .to_string(),
+            download_url: value["downloadUrl"]
+                .as_str()
+                .context("JMAP session has no downloadUrl")?
+                .to_string(),
+            account_id,
+        };
+
+        *self.session.write().await = Some(session.clone());
+        Ok(session)
+    }
+
+    /// POST a batch of method calls, returning the `methodResponses` array.
+    async fn api_call(
+        &self,
+        session: &JmapSession,
+        method_calls: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let response = self
+            .client
+            .post(&session.api_url)
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({
+                "using": JMAP_USING,
+                "methodCalls": method_calls,
+            }))
+            .send()
+            .await
+            .context("JMAP API request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("JMAP API request returned {status}: {body}");
+        }
+
+        let value: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to parse JMAP API response")?;
+        Ok(value["methodResponses"].clone())
+    }
+
+    /// Find a method response by its client call ID, surfacing server errors.
+    fn method_response(
+        responses: &serde_json::Value,
+        call_id: &str,
+    ) -> anyhow::Result<serde_json::Value> {
+        for entry in responses.as_array().into_iter().flatten() {
+            if entry[2].as_str() == Some(call_id) {
+                if entry[0].as_str() == Some("error") {
+                    anyhow::bail!("JMAP method error for '{call_id}': {}", entry[1]);
+                }
+                return Ok(entry[1].clone());
+            }
+        }
+        anyhow::bail!("JMAP response is missing call '{call_id}'")
+    }
+
+    /// The inbox mailbox ID, resolved by role and cached.
+    async fn inbox_id(&self, session: &JmapSession) -> anyhow::Result<String> {
+        if let Some(id) = self.inbox_id.read().await.as_ref() {
+            return Ok(id.clone());
+        }
+
+        let responses = self
+            .api_call(
+                session,
+                serde_json::json!([[
+                    "Mailbox/query",
+                    {
+                        "accountId": session.account_id,
+                        "filter": { "role": "inbox" },
+                    },
+                    "inbox"
+                ]]),
+            )
+            .await?;
+        let result = Self::method_response(&responses, "inbox")?;
+        let id = result["ids"][0]
+            .as_str()
+            .context("JMAP account has no inbox mailbox")?
+            .to_string();
+
+        *self.inbox_id.write().await = Some(id.clone());
+        Ok(id)
+    }
+
+    /// The sending identity ID, preferring one matching the configured from
+    /// address, and cached.
+    async fn identity_id(&self, session: &JmapSession) -> anyhow::Result<String> {
+        if let Some(id) = self.identity_id.read().await.as_ref() {
+            return Ok(id.clone());
+        }
+
+        let responses = self
+            .api_call(
+                session,
+                serde_json::json!([[
+                    "Identity/get",
+                    { "accountId": session.account_id },
+                    "identities"
+                ]]),
+            )
+            .await?;
+        let result = Self::method_response(&responses, "identities")?;
+        let identities = result["list"].as_array().cloned().unwrap_or_default();
+        let id = identities
+            .iter()
+            .find(|identity| {
+                identity["email"]
+                    .as_str()
+                    .is_some_and(|address| address.eq_ignore_ascii_case(&self.from_address))
+            })
+            .or_else(|| identities.first())
+            .and_then(|identity| identity["id"].as_str())
+            .context("JMAP account has no sending identity")?
+            .to_string();
+
+        *self.identity_id.write().await = Some(id.clone());
+        Ok(id)
+    }
+
+    /// Download a message's raw RFC 5322 bytes through the blob endpoint.
+    async fn download_blob(&self, session: &JmapSession, blob_id: &str) -> anyhow::Result<Vec<u8>> {
+        let url = session
+            .download_url
+            .replace("{accountId}", &session.account_id)
+            .replace("{blobId}", blob_id)
+            .replace("{name}", "message.eml")
+            .replace("{type}", "message/rfc822");
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("JMAP blob download failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("JMAP blob download returned {status}");
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .context("failed to read JMAP blob body")?;
+        Ok(bytes.to_vec())
+    }
+}
+
+impl MailTransport for JmapTransport {
+    async fn fetch_new(&self) -> anyhow::Result<Vec<InboundMessage>> {
+        let session = self.session().await?;
+        let inbox_id = self.inbox_id(&session).await?;
+
+        // Query unseen inbox mail and fetch blob IDs in one batch via a
+        // back-reference to the query result.
+        let responses = self
+            .api_call(
+                &session,
+                serde_json::json!([
+                    [
+                        "Email/query",
+                        {
+                            "accountId": session.account_id,
+                            "filter": {
+                                "inMailbox": inbox_id,
+                                "notKeyword": "$seen",
+                            },
+                            "sort": [{ "property": "receivedAt", "isAscending": true }],
+                            "limit": JMAP_POLL_PAGE_SIZE,
+                        },
+                        "query"
+                    ],
+                    [
+                        "Email/get",
+                        {
+                            "accountId": session.account_id,
+                            "#ids": {
+                                "resultOf": "query",
+                                "name": "Email/query",
+                                "path": "/ids",
+                            },
+                            "properties": ["blobId"],
+                        },
+                        "get"
+                    ]
+                ]),
+            )
+            .await?;
+
+        let fetched = Self::method_response(&responses, "get")?;
+        let entries = fetched["list"].as_array().cloned().unwrap_or_default();
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = Vec::new();
+        let mut seen_updates = serde_json::Map::new();
+        for entry in &entries {
+            let (Some(email_id), Some(blob_id)) = (entry["id"].as_str(), entry["blobId"].as_str())
+            else {
+                continue;
+            };
+            seen_updates.insert(
+                email_id.to_string(),
+                serde_json::json!({ "keywords/$seen": true }),
+            );
+
+            let raw = match self.download_blob(&session, blob_id).await {
+                Ok(raw) => raw,
+                Err(error) => {
+                    tracing::warn!(%error, email_id, "failed to download JMAP message blob");
+                    continue;
+                }
+            };
+
+            let uid = self.next_uid.fetch_add(1, Ordering::Relaxed);
+            match email::parse_inbound_email(&raw, "INBOX", uid, &self.poll_config) {
+                Ok(Some(message)) => messages.push(message),
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::warn!(%error, email_id, "failed to parse JMAP message");
+                }
+            }
+        }
+
+        // Mark everything picked up as seen so the next poll skips it.
+        let responses = self
+            .api_call(
+                &session,
+                serde_json::json!([[
+                    "Email/set",
+                    {
+                        "accountId": session.account_id,
+                        "update": seen_updates,
+                    },
+                    "seen"
+                ]]),
+            )
+            .await?;
+        Self::method_response(&responses, "seen")?;
+
+        Ok(messages)
+    }
+
+    async fn send(&self, mail: OutgoingMail) -> anyhow::Result<()> {
+        let session = self.session().await?;
+        let identity_id = self.identity_id(&session).await?;
+        let inbox_id = self.inbox_id(&session).await?;
+
+        // Build the RFC 5322 bytes locally (same multipart shape as the SMTP
+        // path), upload them as a blob, then import + submit in one batch.
+        let message = build_rfc822(
+            &self.from_address,
+            None,
+            self.pgp_sign.then_some(self.pgp_key_id.as_str()),
+            &mail,
+        )?;
+        let raw = message.formatted();
+
+        let upload_url = session.upload_url.replace("{accountId}", &session.account_id);
+        let response = self
+            .client
+            .post(&upload_url)
+            .bearer_auth(&self.token)
+            .header("Content-Type", "message/rfc822")
+            .body(raw)
+            .send()
+            .await
+            .context("JMAP blob upload failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("JMAP blob upload returned {status}: {body}");
+        }
+
+        let uploaded: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to parse JMAP upload response")?;
+        let blob_id = uploaded["blobId"]
+            .as_str()
+            .context("JMAP upload returned no blobId")?;
+
+        let mut mailbox_ids = serde_json::Map::new();
+        mailbox_ids.insert(inbox_id, serde_json::Value::Bool(true));
+
+        let responses = self
+            .api_call(
+                &session,
+                serde_json::json!([
+                    [
+                        "Email/import",
+                        {
+                            "accountId": session.account_id,
+                            "emails": {
+                                "outbound": {
+                                    "blobId": blob_id,
+                                    // Imported mail must land in a mailbox;
+                                    // the copy is marked seen so the poll
+                                    // loop never picks it back up.
+                                    "mailboxIds": mailbox_ids,
+                                    "keywords": { "$seen": true, "$draft": true },
+                                }
+                            },
+                        },
+                        "import"
+                    ],
+                    [
+                        "EmailSubmission/set",
+                        {
+                            "accountId": session.account_id,
+                            "create": {
+                                "submission": {
+                                    "emailId": "#outbound",
+                                    "identityId": identity_id,
+                                }
+                            },
+                        },
+                        "submit"
+                    ]
+                ]),
+            )
+            .await?;
+
+        let submit = Self::method_response(&responses, "submit")?;
+        if submit["created"]["submission"].is_null() {
+            anyhow::bail!("JMAP submission was not created: {}", submit["notCreated"]);
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the RFC 5322 message both transports deliver: multipart/alternative
+/// with the raw markdown as text/plain plus an HTML rendering, matching the
+/// SMTP path.
+fn build_rfc822(
+    from_address: &str,
+    from_name: Option<&str>,
+    pgp_key: Option<&str>,
+    mail: &OutgoingMail,
+) -> anyhow::Result<Message> {
+    let from = match from_name {
+        Some(name) => parse_mailbox(&format!("{name} <{from_address}>"))?,
+        None => parse_mailbox(from_address)?,
+    };
+    let to = parse_mailbox(&mail.recipient)
+        .with_context(|| format!("invalid recipient address '{}'", mail.recipient))?;
+
+    let mut builder = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(mail.subject.clone());
+
+    if let Some(in_reply_to) = &mail.in_reply_to {
+        let in_reply_to = format_message_id_for_header(in_reply_to);
+        if !in_reply_to.is_empty() {
+            builder = builder.in_reply_to(in_reply_to);
+        }
+    }
+
+    // One space-separated References header; repeated builder calls would
+    // each replace the previous value and keep only the last ID.
+    let references = mail
+        .references
+        .iter()
+        .map(|reference| format_message_id_for_header(reference))
+        .filter(|reference| !reference.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !references.is_empty() {
+        builder = builder.references(references);
+    }
+
+    // Same shape rule as the SMTP adapter: clearsigned mail is a single
+    // text/plain part, since clients would render an unsigned HTML
+    // alternative in place of the signature.
+    match pgp_key.and_then(|key_id| clearsign_with_pgp(&mail.body, key_id)) {
+        Some(signed) => builder
+            .singlepart(SinglePart::plain(signed))
+            .context("failed to build email body"),
+        None => {
+            let html = markdown_to_html(&mail.body);
+            builder
+                .multipart(MultiPart::alternative_plain_html(mail.body.clone(), html))
+                .context("failed to build email body")
+        }
+    }
+}
+
+/// JMAP email adapter state.
+pub struct JmapEmailAdapter {
+    runtime_key: String,
+    poll_interval: Duration,
+    transport: Arc<JmapTransport>,
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
+    poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+}
+
+impl std::fmt::Debug for JmapEmailAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JmapEmailAdapter")
+            .field("runtime_key", &self.runtime_key)
+            .field("session_url", &self.transport.session_url)
+            .field("poll_interval", &self.poll_interval)
+            .finish()
+    }
+}
+
+impl JmapEmailAdapter {
+    pub fn from_config(config: &EmailConfig) -> crate::Result<Self> {
+        Self::build("email".to_string(), config)
+    }
+
+    pub fn from_instance_config(
+        runtime_key: impl Into<String>,
+        config: &crate::config::EmailInstanceConfig,
+    ) -> crate::Result<Self> {
+        let email_config = EmailConfig {
+            enabled: config.enabled,
+            imap_host: config.imap_host.clone(),
+            imap_port: config.imap_port,
+            imap_username: config.imap_username.clone(),
+            imap_password: config.imap_password.clone(),
+            imap_use_tls: config.imap_use_tls,
+            smtp_host: config.smtp_host.clone(),
+            smtp_port: config.smtp_port,
+            smtp_username: config.smtp_username.clone(),
+            smtp_password: config.smtp_password.clone(),
+            smtp_use_starttls: config.smtp_use_starttls,
+            from_address: config.from_address.clone(),
+            from_name: config.from_name.clone(),
+            poll_interval_secs: config.poll_interval_secs,
+            folders: config.folders.clone(),
+            allowed_senders: config.allowed_senders.clone(),
+            max_body_bytes: config.max_body_bytes,
+            max_attachment_bytes: config.max_attachment_bytes,
+            size_policy_reply: config.size_policy_reply,
+            max_replies_per_hour: config.max_replies_per_hour,
+            spam_patterns: config.spam_patterns.clone(),
+            spam_scorer_command: config.spam_scorer_command.clone(),
+            drop_failed_auth: config.drop_failed_auth,
+            pgp_sign: config.pgp_sign,
+            pgp_key_id: config.pgp_key_id.clone(),
+            dkim_selector: config.dkim_selector.clone(),
+            dkim_key_path: config.dkim_key_path.clone(),
+            custom_headers: config.custom_headers.clone(),
+            reply_all: config.reply_all,
+            backend: config.backend,
+            graph_tenant_id: config.graph_tenant_id.clone(),
+            graph_client_id: config.graph_client_id.clone(),
+            graph_client_secret: config.graph_client_secret.clone(),
+            graph_user: config.graph_user.clone(),
+            jmap_session_url: config.jmap_session_url.clone(),
+            jmap_token: config.jmap_token.clone(),
+            instances: Vec::new(),
+        };
+        Self::build(runtime_key.into(), &email_config)
+    }
+
+    fn build(runtime_key: String, config: &EmailConfig) -> crate::Result<Self> {
+        if config.jmap_session_url.is_empty() || config.jmap_token.is_empty() {
+            return Err(anyhow::anyhow!(
+                "jmap email backend requires jmap_session_url and jmap_token"
+            )
+            .into());
+        }
+
+        Ok(Self {
+            transport: Arc::new(JmapTransport::from_config(&runtime_key, config)?),
+            runtime_key,
+            poll_interval: Duration::from_secs(config.poll_interval_secs.max(5)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            poll_task: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Replies within the thread of the inbound message.
+    async fn send_reply(&self, message: &InboundMessage, body: String) -> crate::Result<()> {
+        let context = reply_context_from_message(message)?;
+        self.transport
+            .send(OutgoingMail {
+                recipient: context.recipient,
+                subject: context.subject,
+                body,
+                in_reply_to: context.in_reply_to,
+                references: context.references,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// Send the parser's prepared size-policy notice back to the sender so
+/// truncation is not silent. Failures only log; the truncated message still
+/// reaches the agent.
+async fn send_size_policy_notice<T: MailTransport>(transport: &T, message: &InboundMessage) {
+    let Some(notice) = message
+        .metadata
+        .get("email_size_policy_notice")
+        .and_then(serde_json::Value::as_str)
+    else {
+        return;
+    };
+
+    let context = match reply_context_from_message(message) {
+        Ok(context) => context,
+        Err(error) => {
+            tracing::warn!(%error, "cannot address email size policy notice");
+            return;
+        }
+    };
+
+    if let Err(error) = transport
+        .send(OutgoingMail {
+            recipient: context.recipient.clone(),
+            subject: context.subject,
+            body: notice.to_string(),
+            in_reply_to: context.in_reply_to,
+            references: context.references,
+        })
+        .await
+    {
+        tracing::warn!(
+            %error,
+            recipient = %context.recipient,
+            "failed to send email size policy notice"
+        );
+    }
+}
+
+impl Messaging for JmapEmailAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        if self.poll_task.read().await.is_some() {
+            return Err(anyhow::anyhow!("JMAP email adapter already started").into());
+        }
+
+        let (inbound_tx, inbound_rx) = tokio::sync::mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let transport = self.transport.clone();
+        let poll_interval = self.poll_interval;
+
+        let poll_task = tokio::spawn(async move {
+            let mut retry_backoff = Duration::from_secs(5);
+
+            loop {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+
+                let mut had_error = false;
+
+                match transport.fetch_new().await {
+                    Ok(messages) => {
+                        retry_backoff = Duration::from_secs(5);
+                        for message in messages {
+                            send_size_policy_notice(transport.as_ref(), &message).await;
+                            if inbound_tx.send(message).await.is_err() {
+                                tracing::warn!(
+                                    "JMAP email inbound channel closed, stopping adapter loop"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        had_error = true;
+                        tracing::warn!(%error, "JMAP email poll cycle failed");
+                    }
+                }
+
+                let sleep_duration = if had_error {
+                    let current = retry_backoff;
+                    retry_backoff =
+                        (retry_backoff * 2).min(Duration::from_secs(JMAP_MAX_RETRY_BACKOFF_SECS));
+                    current
+                } else {
+                    poll_interval
+                };
+
+                tokio::select! {
+                    _ = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                    _ = tokio::time::sleep(sleep_duration) => {}
+                }
+            }
+
+            tracing::info!("JMAP email adapter loop stopped");
+        });
+
+        *self.poll_task.write().await = Some(poll_task);
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ThreadReply { text, .. } => {
+                self.send_reply(message, text).await?;
+            }
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                tracing::warn!(
+                    post_at,
+                    "JMAP email adapter does not support scheduled delivery; sending immediately"
+                );
+                self.send_reply(message, text).await?;
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Attachment upload via Email/set bodyStructure is not wired
+                // up; send the caption so the reply isn't silently dropped.
+                let body = caption
+                    .filter(|caption| !caption.trim().is_empty())
+                    .unwrap_or_else(|| format!("Attached file omitted: {filename}"));
+                self.send_reply(message, body).await?;
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let recipient = normalize_email_target(target)
+            .ok_or_else(|| anyhow::anyhow!("invalid email target '{target}'"))?;
+
+        let text = match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. } => text,
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                tracing::warn!(
+                    post_at,
+                    recipient = %recipient,
+                    "JMAP email adapter does not support scheduled delivery; sending immediately"
+                );
+                text
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => caption
+                .filter(|caption| !caption.trim().is_empty())
+                .unwrap_or_else(|| format!("Attached file omitted: {filename}")),
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => return Ok(()),
+        };
+
+        self.transport
+            .send(OutgoingMail {
+                recipient,
+                subject: "Spacebot message".to_string(),
+                body: text,
+                in_reply_to: None,
+                references: Vec::new(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let session = self.transport.session().await?;
+        self.transport.inbox_id(&session).await?;
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
+            shutdown_tx.send(true).ok();
+        }
+
+        if let Some(poll_task) = self.poll_task.write().await.take()
+            && let Err(error) = poll_task.await
+        {
+            tracing::warn!(%error, "JMAP email poll task join failed during shutdown");
+        }
+
+        tracing::info!("JMAP email adapter shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JmapTransport, OutgoingMail, build_rfc822};
+
+    #[test]
+    fn rfc822_carries_threading_headers() {
+        let message = build_rfc822(
+            "bot@example.com",
+            Some("Spacebot"),
+            None,
+            &OutgoingMail {
+                recipient: "alice@example.com".into(),
+                subject: "Re: hello".into(),
+                body: "hi".into(),
+                in_reply_to: Some("abc@example.com".into()),
+                references: vec!["root@example.com".into(), "abc@example.com".into()],
+            },
+        )
+        .expect("message builds");
+
+        let rendered = String::from_utf8(message.formatted()).expect("utf8");
+        assert!(rendered.contains("In-Reply-To: <abc@example.com>"));
+        assert!(rendered.contains("<root@example.com>"));
+        assert!(rendered.contains("Subject: Re: hello"));
+    }
+
+    #[test]
+    fn method_response_lookup_matches_call_id_and_surfaces_errors() {
+        let responses = serde_json::json!([
+            ["Email/query", { "ids": ["m1"] }, "query"],
+            ["error", { "type": "serverFail" }, "get"],
+        ]);
+
+        let query = JmapTransport::method_response(&responses, "query").expect("query result");
+        assert_eq!(query["ids"][0], "m1");
+
+        assert!(JmapTransport::method_response(&responses, "get").is_err());
+        assert!(JmapTransport::method_response(&responses, "missing").is_err());
+    }
+}
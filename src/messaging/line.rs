@@ -0,0 +1,409 @@
+//! LINE Messaging API adapter.
+//!
+//! Inbound events arrive on a webhook endpoint validated with the
+//! `x-line-signature` HMAC-SHA256 over the raw request body. Replies use the
+//! single-shot reply token when it is still fresh and fall back to the push
+//! API once the token has been consumed or has expired; `broadcast` always
+//! pushes to the given user/group/room ID.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::{UsedReplyTokens, split_message, verify_hmac_base64};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+const LINE_API_BASE: &str = "https://api.line.me/v2/bot";
+
+/// LINE rejects text messages above 5000 characters.
+const MAX_MESSAGE_LENGTH: usize = 4_800;
+
+/// LINE adapter state.
+pub struct LineAdapter {
+    runtime_key: String,
+    channel_secret: String,
+    channel_access_token: String,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// Reply tokens already consumed; later responses for the same inbound
+    /// message fall back to the push API.
+    used_reply_tokens: Arc<UsedReplyTokens>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    channel_secret: String,
+    runtime_key: String,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl LineAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        channel_secret: impl Into<String>,
+        channel_access_token: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            channel_secret: channel_secret.into(),
+            channel_access_token: channel_access_token.into(),
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            used_reply_tokens: Arc::new(UsedReplyTokens::new()),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    async fn api_post(&self, endpoint: &str, payload: serde_json::Value) -> crate::Result<()> {
+        let response = self
+            .client
+            .post(format!("{LINE_API_BASE}/{endpoint}"))
+            .bearer_auth(&self.channel_access_token)
+            .json(&payload)
+            .send()
+            .await
+            .with_context(|| format!("failed to call LINE {endpoint}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("LINE {endpoint} failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    /// Send text, preferring the reply token (one shot, ~1 minute validity)
+    /// and falling back to push messages keyed by the source ID.
+    async fn send_text(&self, message: &InboundMessage, text: &str) -> crate::Result<()> {
+        let reply_token = message
+            .metadata
+            .get("line_reply_token")
+            .and_then(|v| v.as_str());
+        let source_id = message
+            .metadata
+            .get("line_source_id")
+            .and_then(|v| v.as_str())
+            .context("missing line_source_id in metadata")?;
+
+        let chunks = split_message(text, MAX_MESSAGE_LENGTH);
+        // The reply endpoint accepts at most five messages per token
+        let messages: Vec<serde_json::Value> = chunks
+            .iter()
+            .take(5)
+            .map(|chunk| json!({ "type": "text", "text": chunk }))
+            .collect();
+        let overflow = chunks.len() > 5;
+
+        let mut replied = false;
+        if let Some(token) = reply_token {
+            let fresh = self.used_reply_tokens.try_consume(token).await;
+            // Tokens also age out server-side; treat reply failure as stale
+            if fresh
+                && self
+                    .api_post(
+                        "message/reply",
+                        json!({ "replyToken": token, "messages": messages.clone() }),
+                    )
+                    .await
+                    .is_ok()
+            {
+                replied = true;
+            }
+        }
+
+        if !replied {
+            self.push_text_messages(source_id, messages).await?;
+        }
+        if overflow {
+            for chunk in &chunks[5..] {
+                self.push_text_messages(
+                    source_id,
+                    vec![json!({ "type": "text", "text": chunk })],
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn push_text_messages(
+        &self,
+        to: &str,
+        messages: Vec<serde_json::Value>,
+    ) -> crate::Result<()> {
+        self.api_post("message/push", json!({ "to": to, "messages": messages }))
+            .await
+    }
+}
+
+impl Messaging for LineAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            channel_secret: self.channel_secret.clone(),
+            runtime_key: self.runtime_key.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/line", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind LINE webhook to {bind}"))?;
+        tracing::info!(%bind, "LINE webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "LINE webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(message, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Media messages need public HTTPS URLs; describe the file instead
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment omitted: {filename})"),
+                    None => format!("(attachment omitted: {filename})"),
+                };
+                self.send_text(message, &note).await
+            }
+            // No reaction or stream framing API for LINE bots
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+            self.push_text_messages(target, vec![json!({ "type": "text", "text": chunk })])
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .client
+            .get(format!("{LINE_API_BASE}/info"))
+            .bearer_auth(&self.channel_access_token)
+            .send()
+            .await
+            .context("LINE API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("LINE health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("LINE adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    let Some(signature) = headers.get("x-line-signature").and_then(|v| v.to_str().ok()) else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    if !verify_hmac_base64(&state.channel_secret, body.as_bytes(), signature) {
+        tracing::warn!("rejected LINE webhook with bad signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let Some(events) = payload.get("events").and_then(|e| e.as_array()) else {
+        return StatusCode::OK;
+    };
+
+    for event in events {
+        let Some(inbound) = parse_event(event, &state.runtime_key) else {
+            continue;
+        };
+        let tx = {
+            let guard = state.inbound_tx.read().await;
+            let Some(tx) = guard.as_ref() else {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            };
+            tx.clone()
+        };
+        if tx.send(inbound).await.is_err() {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert a LINE message event into an inbound message.
+fn parse_event(event: &serde_json::Value, runtime_key: &str) -> Option<InboundMessage> {
+    if event.get("type").and_then(|t| t.as_str()) != Some("message") {
+        return None;
+    }
+    let message = event.get("message")?;
+    if message.get("type").and_then(|t| t.as_str()) != Some("text") {
+        return None;
+    }
+    let text = message.get("text")?.as_str()?.trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+
+    // The source ID is the push target: group > room > user
+    let source = event.get("source")?;
+    let user_id = source.get("userId").and_then(|u| u.as_str());
+    let source_id = source
+        .get("groupId")
+        .or_else(|| source.get("roomId"))
+        .and_then(|v| v.as_str())
+        .or(user_id)?
+        .to_string();
+    let sender_id = user_id.unwrap_or(&source_id).to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "line_source_id".into(),
+        serde_json::Value::String(source_id.clone()),
+    );
+    if let Some(reply_token) = event.get("replyToken").and_then(|t| t.as_str()) {
+        metadata.insert(
+            "line_reply_token".into(),
+            serde_json::Value::String(reply_token.to_string()),
+        );
+    }
+    if let Some(source_type) = source.get("type").and_then(|t| t.as_str()) {
+        metadata.insert(
+            "line_source_type".into(),
+            serde_json::Value::String(source_type.to_string()),
+        );
+    }
+
+    let timestamp = event
+        .get("timestamp")
+        .and_then(|t| t.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .unwrap_or_else(chrono::Utc::now);
+
+    Some(InboundMessage {
+        id: message
+            .get("id")
+            .and_then(|i| i.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "line".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("line:{source_id}"),
+        sender_id,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp,
+        metadata,
+        formatted_author: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_group_message_with_reply_token() {
+        let event = serde_json::json!({
+            "type": "message",
+            "replyToken": "token-1",
+            "timestamp": 1_700_000_000_000i64,
+            "source": { "type": "group", "groupId": "G1", "userId": "U1" },
+            "message": { "id": "m1", "type": "text", "text": "hello" },
+        });
+        let inbound = parse_event(&event, "line").unwrap();
+        assert_eq!(inbound.conversation_id, "line:G1");
+        assert_eq!(inbound.sender_id, "U1");
+        assert_eq!(
+            inbound.metadata.get("line_reply_token").and_then(|v| v.as_str()),
+            Some("token-1")
+        );
+
+        // Sticker and other non-text messages are skipped
+        let sticker = serde_json::json!({
+            "type": "message",
+            "source": { "type": "user", "userId": "U1" },
+            "message": { "id": "m2", "type": "sticker" },
+        });
+        assert!(parse_event(&sticker, "line").is_none());
+    }
+}
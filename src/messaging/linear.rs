@@ -0,0 +1,520 @@
+//! Linear messaging adapter.
+//!
+//! Binds agents to Linear teams: `Comment` and `Issue` create webhooks
+//! become inbound messages keyed by the issue's UUID, and replies post back
+//! as issue comments through the GraphQL API. Each configured instance
+//! authenticates with its own API key, so one process can serve several
+//! Linear workspaces. Deliveries are validated with the `linear-signature`
+//! HMAC when a secret is configured.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde_json::json;
+use sha2::Digest as _;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+const LINEAR_GRAPHQL_URL: &str = "https://api.linear.app/graphql";
+
+/// Linear comment bodies are markdown; stay well under the server limit.
+const MAX_MESSAGE_LENGTH: usize = 60_000;
+
+/// Linear adapter state.
+pub struct LinearAdapter {
+    runtime_key: String,
+    /// Personal or workspace API key for the GraphQL endpoint.
+    api_key: String,
+    /// Webhook signing secret; deliveries are accepted unsigned when absent.
+    webhook_secret: Option<String>,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// The authenticated user's UUID, used to drop the bot's own comments.
+    own_user_id: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    runtime_key: String,
+    webhook_secret: Option<String>,
+    own_user_id: Arc<RwLock<Option<String>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl LinearAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        api_key: impl Into<String>,
+        webhook_secret: Option<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            api_key: api_key.into(),
+            webhook_secret,
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            own_user_id: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Execute a GraphQL request, surfacing transport and `errors` failures.
+    async fn graphql(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> crate::Result<serde_json::Value> {
+        let response = self
+            .client
+            .post(LINEAR_GRAPHQL_URL)
+            .header(reqwest::header::AUTHORIZATION, &self.api_key)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .context("Linear GraphQL API unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Linear GraphQL request failed: HTTP {status}: {body}").into());
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to decode Linear GraphQL response")?;
+        if let Some(errors) = body.get("errors").and_then(|e| e.as_array())
+            && !errors.is_empty()
+        {
+            return Err(anyhow::anyhow!("Linear GraphQL errors: {errors:?}").into());
+        }
+        Ok(body["data"].clone())
+    }
+
+    /// Post a comment on an issue, threading under a parent when given.
+    async fn post_comment(
+        &self,
+        issue_id: &str,
+        parent_id: Option<&str>,
+        text: &str,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let mut input = json!({ "issueId": issue_id, "body": chunk });
+            if let Some(parent_id) = parent_id {
+                input["parentId"] = json!(parent_id);
+            }
+            let data = self
+                .graphql(
+                    "mutation($input: CommentCreateInput!) { commentCreate(input: $input) { success } }",
+                    json!({ "input": input }),
+                )
+                .await?;
+            if !data["commentCreate"]["success"].as_bool().unwrap_or(false) {
+                return Err(
+                    anyhow::anyhow!("Linear comment on issue {issue_id} was not accepted").into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The issue UUID a reply should target.
+    fn routing(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("linear_issue_id")
+            .and_then(|v| v.as_str())
+            .context("missing linear_issue_id in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for LinearAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Learn our own user ID so webhook echoes of our comments are dropped
+        let viewer = self
+            .graphql("{ viewer { id name } }", json!({}))
+            .await
+            .context("Linear API key rejected")?;
+        if let Some(id) = viewer["viewer"]["id"].as_str() {
+            tracing::info!(
+                name = viewer["viewer"]["name"].as_str().unwrap_or_default(),
+                "Linear identity loaded"
+            );
+            *self.own_user_id.write().await = Some(id.to_string());
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            runtime_key: self.runtime_key.clone(),
+            webhook_secret: self.webhook_secret.clone(),
+            own_user_id: self.own_user_id.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/linear", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind Linear webhook to {bind}"))?;
+        tracing::info!(%bind, "Linear webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "Linear webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let issue_id = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.post_comment(issue_id, None, &text).await
+            }
+            OutboundResponse::ThreadReply { text, .. } => {
+                // Thread under the comment that triggered us when we have one
+                let parent_id = message
+                    .metadata
+                    .get("linear_comment_id")
+                    .and_then(|v| v.as_str());
+                self.post_comment(issue_id, parent_id, &text).await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // File uploads need Linear's separate asset flow; describe it
+                let note = match caption {
+                    Some(caption) => format!("{caption}\n\n*(attachment omitted: {filename})*"),
+                    None => format!("*(attachment omitted: {filename})*"),
+                };
+                self.post_comment(issue_id, None, &note).await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let Some(comment_id) = message
+                    .metadata
+                    .get("linear_comment_id")
+                    .and_then(|v| v.as_str())
+                else {
+                    return Ok(());
+                };
+                // Linear takes the emoji itself, not a name
+                if let Err(error) = self
+                    .graphql(
+                        "mutation($input: ReactionCreateInput!) { reactionCreate(input: $input) { success } }",
+                        json!({ "input": { "commentId": comment_id, "emoji": emoji } }),
+                    )
+                    .await
+                {
+                    tracing::warn!(%error, "Linear reaction failed");
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets are issue UUIDs, matching the conversation ID suffix
+        self.post_comment(target, None, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.graphql("{ viewer { id } }", json!({}))
+            .await
+            .map(|_| ())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Linear adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    if let Some(secret) = &state.webhook_secret {
+        let Some(signature) = headers
+            .get("linear-signature")
+            .and_then(|v| v.to_str().ok())
+        else {
+            return StatusCode::UNAUTHORIZED;
+        };
+        if !verify_signature(secret, body.as_bytes(), signature) {
+            tracing::warn!("rejected Linear webhook with bad signature");
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let own_user_id = state.own_user_id.read().await.clone();
+    let Some(inbound) = parse_event(&payload, &state.runtime_key, own_user_id.as_deref()) else {
+        return StatusCode::OK;
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert a `Comment` or `Issue` create webhook into an inbound message.
+fn parse_event(
+    payload: &serde_json::Value,
+    runtime_key: &str,
+    own_user_id: Option<&str>,
+) -> Option<InboundMessage> {
+    // Only fresh activity; updates and removals are not conversation turns
+    if payload["action"].as_str()? != "create" {
+        return None;
+    }
+    let data = payload.get("data")?;
+
+    let (text, item_id, issue_id) = match payload["type"].as_str()? {
+        "Comment" => (
+            data["body"].as_str()?.trim().to_string(),
+            data["id"].as_str().map(str::to_string),
+            data["issueId"].as_str()?.to_string(),
+        ),
+        "Issue" => {
+            let title = data["title"].as_str().unwrap_or_default();
+            let description = data["description"].as_str().unwrap_or_default().trim();
+            let text = if description.is_empty() {
+                title.to_string()
+            } else {
+                format!("{title}\n\n{description}")
+            };
+            (text, None, data["id"].as_str()?.to_string())
+        }
+        _ => return None,
+    };
+    if text.is_empty() {
+        return None;
+    }
+
+    let actor_id = payload["actor"]["id"]
+        .as_str()
+        .or_else(|| data["userId"].as_str())?
+        .to_string();
+    // Drop our own comments echoed back by the webhook
+    if own_user_id == Some(actor_id.as_str()) {
+        return None;
+    }
+    let actor_name = payload["actor"]["name"].as_str().map(str::to_string);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "linear_issue_id".into(),
+        serde_json::Value::String(issue_id.clone()),
+    );
+    if let Some(identifier) = data["issue"]["identifier"]
+        .as_str()
+        .or_else(|| data["identifier"].as_str())
+    {
+        metadata.insert(
+            "linear_issue_identifier".into(),
+            serde_json::Value::String(identifier.to_string()),
+        );
+    }
+    if let Some(title) = data["issue"]["title"]
+        .as_str()
+        .or_else(|| data["title"].as_str())
+    {
+        metadata.insert(
+            "linear_issue_title".into(),
+            serde_json::Value::String(title.to_string()),
+        );
+    }
+    if let Some(comment_id) = &item_id {
+        metadata.insert(
+            "linear_comment_id".into(),
+            serde_json::Value::String(comment_id.clone()),
+        );
+    }
+    if let Some(name) = &actor_name {
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(name.clone()),
+        );
+    }
+
+    Some(InboundMessage {
+        id: item_id.unwrap_or_else(|| issue_id.clone()),
+        source: "linear".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("linear:{issue_id}"),
+        sender_id: actor_id,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: actor_name,
+    })
+}
+
+/// Validate the `linear-signature` header: hex HMAC-SHA256 over the raw
+/// body, keyed with the webhook signing secret.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let expected = hex::encode(hmac_sha256(secret.as_bytes(), body));
+    // Constant-time comparison; signatures are attacker-supplied
+    expected.len() == signature.len()
+        && expected
+            .bytes()
+            .zip(signature.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b.to_ascii_lowercase()))
+            == 0
+}
+
+/// HMAC-SHA256 per RFC 2104, built on the sha2 crate.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&sha2::Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = sha2::Sha256::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = sha2::Sha256::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comment_creates_become_conversation_turns() {
+        let payload = serde_json::json!({
+            "action": "create",
+            "type": "Comment",
+            "actor": { "id": "user-1", "name": "Alice" },
+            "data": {
+                "id": "comment-1",
+                "body": "can you pick this up?",
+                "userId": "user-1",
+                "issueId": "issue-9",
+                "issue": { "identifier": "ENG-42", "title": "Fix the widget" },
+            },
+        });
+        let inbound = parse_event(&payload, "linear", Some("bot-id")).unwrap();
+        assert_eq!(inbound.conversation_id, "linear:issue-9");
+        assert_eq!(inbound.sender_id, "user-1");
+        assert_eq!(
+            inbound.metadata.get("linear_issue_identifier"),
+            Some(&serde_json::Value::String("ENG-42".into()))
+        );
+    }
+
+    #[test]
+    fn own_comments_and_updates_are_dropped() {
+        let own = serde_json::json!({
+            "action": "create",
+            "type": "Comment",
+            "actor": { "id": "bot-id", "name": "spacebot" },
+            "data": { "id": "c", "body": "done", "userId": "bot-id", "issueId": "issue-9" },
+        });
+        assert!(parse_event(&own, "linear", Some("bot-id")).is_none());
+
+        let update = serde_json::json!({
+            "action": "update",
+            "type": "Comment",
+            "actor": { "id": "user-1", "name": "Alice" },
+            "data": { "id": "c", "body": "edited", "userId": "user-1", "issueId": "issue-9" },
+        });
+        assert!(parse_event(&update, "linear", None).is_none());
+    }
+
+    #[test]
+    fn webhook_signatures_verify() {
+        let body = b"{\"action\":\"create\"}";
+        let signature = hex::encode(hmac_sha256(b"s3cret", body));
+        assert!(verify_signature("s3cret", body, &signature));
+        assert!(!verify_signature("wrong", body, &signature));
+        assert!(!verify_signature("s3cret", body, "deadbeef"));
+    }
+}
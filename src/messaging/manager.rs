@@ -57,6 +57,10 @@ impl MessagingManager {
     pub async fn start(&self) -> crate::Result<InboundStream> {
         let adapters = self.adapters.read().await;
         for (name, adapter) in adapters.iter() {
+            if adapter.outbound_only() {
+                tracing::info!(adapter = %name, "outbound-only adapter, no inbound stream");
+                continue;
+            }
             match adapter.start().await {
                 Ok(stream) => Self::spawn_forwarder(name.clone(), stream, self.fan_in_tx.clone()),
                 Err(error) => {
@@ -93,6 +97,13 @@ impl MessagingManager {
     /// channel, so the main loop's stream receives messages without any
     /// stream replacement or restart.
     pub async fn register_and_start(&self, adapter: impl Messaging) -> crate::Result<()> {
+        self.register_and_start_shared(Arc::new(adapter)).await
+    }
+
+    /// `register_and_start` for a pre-wrapped adapter the caller retains a
+    /// handle to (e.g. a Telegram adapter in webhook mode, which the API
+    /// webhook route feeds updates into).
+    pub async fn register_and_start_shared(&self, adapter: Arc<impl Messaging>) -> crate::Result<()> {
         let name = adapter.name().to_string();
 
         // Shut down existing adapter with the same name if present
@@ -106,13 +117,17 @@ impl MessagingManager {
             }
         }
 
-        let adapter: Arc<dyn MessagingDyn> = Arc::new(adapter);
+        let adapter: Arc<dyn MessagingDyn> = adapter;
 
-        let stream = adapter
-            .start()
-            .await
-            .with_context(|| format!("failed to start adapter '{name}'"))?;
-        Self::spawn_forwarder(name.clone(), stream, self.fan_in_tx.clone());
+        if adapter.outbound_only() {
+            tracing::info!(adapter = %name, "outbound-only adapter, no inbound stream");
+        } else {
+            let stream = adapter
+                .start()
+                .await
+                .with_context(|| format!("failed to start adapter '{name}'"))?;
+            Self::spawn_forwarder(name.clone(), stream, self.fan_in_tx.clone());
+        }
 
         self.adapters.write().await.insert(name.clone(), adapter);
 
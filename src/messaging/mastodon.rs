@@ -0,0 +1,530 @@
+//! Mastodon messaging adapter.
+//!
+//! Listens to the user streaming API (SSE) for mention notifications and
+//! direct messages, strips status HTML down to text, and surfaces content
+//! warnings inline. Replies thread onto the triggering status with the same
+//! visibility (public replies are demoted to unlisted unless configured
+//! otherwise), mirror the inbound content warning, and can attach media
+//! through the v2 media API.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use futures::StreamExt as _;
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Mastodon's default status limit is 500 characters; leave room for the
+/// reply mention.
+const MAX_MESSAGE_LENGTH: usize = 450;
+
+/// Mastodon adapter state.
+pub struct MastodonAdapter {
+    runtime_key: String,
+    /// Instance base URL, e.g. `https://mastodon.social` (no trailing slash).
+    base_url: String,
+    access_token: String,
+    /// Visibility for replies to public statuses: "inherit" keeps the
+    /// trigger's visibility, anything else is used as-is.
+    reply_visibility: String,
+    client: reqwest::Client,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl MastodonAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        access_token: impl Into<String>,
+        reply_visibility: impl Into<String>,
+    ) -> Self {
+        let base_url = base_url.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            access_token: access_token.into(),
+            reply_visibility: reply_visibility.into(),
+            client: crate::http::client(),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn api_url(&self, endpoint: &str) -> String {
+        format!("{}/api/v1/{endpoint}", self.base_url)
+    }
+
+    /// The visibility to use when replying to a status with the given one.
+    fn visibility_for_reply(&self, inbound_visibility: &str) -> String {
+        if self.reply_visibility != "inherit" {
+            return self.reply_visibility.clone();
+        }
+        // Don't shout into the public timeline just because we were mentioned there
+        if inbound_visibility == "public" {
+            "unlisted".to_string()
+        } else {
+            inbound_visibility.to_string()
+        }
+    }
+
+    /// Post a status, optionally as a threaded reply with media.
+    async fn post_status(
+        &self,
+        text: &str,
+        in_reply_to: Option<&str>,
+        visibility: &str,
+        spoiler_text: Option<&str>,
+        media_ids: Vec<String>,
+    ) -> crate::Result<()> {
+        let mut payload = json!({
+            "status": text,
+            "visibility": visibility,
+        });
+        if let Some(in_reply_to) = in_reply_to {
+            payload["in_reply_to_id"] = json!(in_reply_to);
+        }
+        if let Some(spoiler_text) = spoiler_text
+            && !spoiler_text.is_empty()
+        {
+            payload["spoiler_text"] = json!(spoiler_text);
+        }
+        if !media_ids.is_empty() {
+            payload["media_ids"] = json!(media_ids);
+        }
+
+        let response = self
+            .client
+            .post(self.api_url("statuses"))
+            .bearer_auth(&self.access_token)
+            .json(&payload)
+            .send()
+            .await
+            .context("failed to post Mastodon status")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Mastodon post failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    /// Upload a media attachment and return its ID.
+    async fn upload_media(
+        &self,
+        filename: &str,
+        data: bytes::Bytes,
+        mime_type: &str,
+        description: Option<&str>,
+    ) -> crate::Result<String> {
+        let length = data.len() as u64;
+        let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::from(data), length)
+            .file_name(filename.to_string())
+            .mime_str(mime_type)
+            .context("invalid mime type for Mastodon upload")?;
+        let mut form = reqwest::multipart::Form::new().part("file", part);
+        if let Some(description) = description {
+            form = form.text("description", description.to_string());
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/api/v2/media", self.base_url))
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await
+            .context("failed to upload Mastodon media")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Mastodon media upload failed: HTTP {status}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("invalid Mastodon media response")?;
+        body["id"]
+            .as_str()
+            .map(str::to_string)
+            .context("missing media id in Mastodon response")
+            .map_err(Into::into)
+    }
+
+    /// Reply routing details from inbound metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, &str, &str, Option<&str>)> {
+        let status_id = message
+            .metadata
+            .get("mastodon_status_id")
+            .and_then(|v| v.as_str())
+            .context("missing mastodon_status_id in metadata")?;
+        let acct = message
+            .metadata
+            .get("mastodon_acct")
+            .and_then(|v| v.as_str())
+            .context("missing mastodon_acct in metadata")?;
+        let visibility = message
+            .metadata
+            .get("mastodon_visibility")
+            .and_then(|v| v.as_str())
+            .unwrap_or("direct");
+        let spoiler = message
+            .metadata
+            .get("mastodon_spoiler")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty());
+        Ok((status_id, acct, visibility, spoiler))
+    }
+
+    async fn reply(&self, message: &InboundMessage, text: &str) -> crate::Result<()> {
+        let (status_id, acct, visibility, spoiler) = Self::routing(message)?;
+        let visibility = self.visibility_for_reply(visibility);
+
+        let mut in_reply_to = status_id.to_string();
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let status_text = format!("@{acct} {chunk}");
+            self.post_status(&status_text, Some(&in_reply_to), &visibility, spoiler, Vec::new())
+                .await?;
+            // Chain further chunks onto the reply we just posted is not
+            // possible without reading back the ID; thread onto the trigger.
+            in_reply_to = status_id.to_string();
+        }
+        Ok(())
+    }
+
+    /// Run the user notification stream once; returns on disconnect.
+    async fn run_stream(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+    ) -> crate::Result<()> {
+        let response = self
+            .client
+            .get(self.api_url("streaming/user"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .context("failed to connect to Mastodon streaming API")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Mastodon stream rejected: HTTP {status}").into());
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut current_event = String::new();
+
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = shutdown_rx.recv() => return Ok(()),
+            };
+            let Some(chunk) = chunk else {
+                return Err(anyhow::anyhow!("Mastodon stream closed").into());
+            };
+            let chunk = chunk.context("Mastodon stream error")?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline) = buffer.find('\n') {
+                let line = buffer[..newline].trim_end_matches('\r').to_string();
+                buffer.drain(..=newline);
+
+                if let Some(event) = line.strip_prefix("event: ") {
+                    current_event = event.trim().to_string();
+                } else if let Some(data) = line.strip_prefix("data: ") {
+                    if current_event == "notification"
+                        && let Ok(notification) = serde_json::from_str::<serde_json::Value>(data)
+                        && let Some(inbound) = self.parse_notification(&notification)
+                        && inbound_tx.send(inbound).await.is_err()
+                    {
+                        return Ok(());
+                    }
+                } else if line.is_empty() {
+                    current_event.clear();
+                }
+            }
+        }
+    }
+
+    /// Convert a mention notification into an inbound message.
+    fn parse_notification(&self, notification: &serde_json::Value) -> Option<InboundMessage> {
+        if notification["type"].as_str() != Some("mention") {
+            return None;
+        }
+        let status = notification.get("status")?;
+        let account = notification.get("account")?;
+
+        if account["bot"].as_bool() == Some(true) {
+            return None;
+        }
+
+        let acct = account["acct"].as_str()?.to_string();
+        let status_id = status["id"].as_str()?.to_string();
+        let visibility = status["visibility"].as_str().unwrap_or("public");
+        let spoiler = status["spoiler_text"].as_str().unwrap_or_default();
+
+        let mut text = strip_html(status["content"].as_str().unwrap_or_default());
+        if text.is_empty() {
+            return None;
+        }
+        // Surface the content warning so the agent sees the full context
+        if !spoiler.is_empty() {
+            text = format!("[CW: {spoiler}] {text}");
+        }
+
+        let display_name = account["display_name"]
+            .as_str()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&acct)
+            .to_string();
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "mastodon_status_id".into(),
+            serde_json::Value::String(status_id.clone()),
+        );
+        metadata.insert(
+            "mastodon_acct".into(),
+            serde_json::Value::String(acct.clone()),
+        );
+        metadata.insert(
+            "mastodon_visibility".into(),
+            serde_json::Value::String(visibility.to_string()),
+        );
+        if !spoiler.is_empty() {
+            metadata.insert(
+                "mastodon_spoiler".into(),
+                serde_json::Value::String(spoiler.to_string()),
+            );
+        }
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(display_name.clone()),
+        );
+
+        let timestamp = status["created_at"]
+            .as_str()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| ts.with_timezone(&chrono::Utc))
+            .unwrap_or_else(chrono::Utc::now);
+
+        // One conversation per account keeps back-and-forth threads together
+        Some(InboundMessage {
+            id: status_id,
+            source: "mastodon".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id: format!("mastodon:{acct}"),
+            sender_id: acct,
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp,
+            metadata,
+            formatted_author: Some(display_name),
+        })
+    }
+}
+
+impl Messaging for MastodonAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = MastodonAdapter {
+            runtime_key: self.runtime_key.clone(),
+            base_url: self.base_url.clone(),
+            access_token: self.access_token.clone(),
+            reply_visibility: self.reply_visibility.clone(),
+            client: self.client.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        };
+
+        tokio::spawn(async move {
+            loop {
+                match adapter.run_stream(&inbound_tx, &mut shutdown_rx).await {
+                    Ok(()) => {
+                        tracing::info!("Mastodon stream loop stopped");
+                        break;
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "Mastodon stream disconnected, reconnecting in 10s");
+                        tokio::select! {
+                            _ = tokio::time::sleep(std::time::Duration::from_secs(10)) => {}
+                            _ = shutdown_rx.recv() => break,
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.reply(message, &text).await,
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                let (status_id, acct, visibility, spoiler) = Self::routing(message)?;
+                let visibility = self.visibility_for_reply(visibility);
+                let media_id = self
+                    .upload_media(&filename, data, &mime_type, caption.as_deref())
+                    .await?;
+                let text = format!("@{acct} {}", caption.unwrap_or_default());
+                self.post_status(
+                    text.trim(),
+                    Some(status_id),
+                    &visibility,
+                    spoiler,
+                    vec![media_id],
+                )
+                .await
+            }
+            OutboundResponse::Reaction(_emoji) => {
+                // Closest native equivalent is favouriting the status
+                let (status_id, ..) = Self::routing(message)?;
+                let response = self
+                    .client
+                    .post(self.api_url(&format!("statuses/{status_id}/favourite")))
+                    .bearer_auth(&self.access_token)
+                    .send()
+                    .await
+                    .context("failed to favourite Mastodon status")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(
+                        anyhow::anyhow!("Mastodon favourite failed: HTTP {status}").into()
+                    );
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Target is an @acct to mention, or "public"/"unlisted" for a bare post
+        let (prefix, visibility) = match target {
+            "public" | "unlisted" => (String::new(), target.to_string()),
+            acct => (
+                format!("@{} ", acct.trim_start_matches('@')),
+                "direct".to_string(),
+            ),
+        };
+        for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+            self.post_status(
+                &format!("{prefix}{chunk}"),
+                None,
+                &visibility,
+                None,
+                Vec::new(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .client
+            .get(self.api_url("accounts/verify_credentials"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .context("Mastodon instance unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Mastodon credential check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Mastodon adapter shut down");
+        Ok(())
+    }
+}
+
+/// Reduce status HTML to readable text: paragraph/line breaks become
+/// newlines, every other tag is dropped, and entities are unescaped.
+fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut tag = String::new();
+
+    for character in html.chars() {
+        match character {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                let tag_name = tag.trim_start_matches('/').trim();
+                if tag_name.starts_with("br") || tag == "/p" {
+                    text.push('\n');
+                }
+            }
+            _ if in_tag => tag.push(character),
+            _ => text.push(character),
+        }
+    }
+
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_html;
+
+    #[test]
+    fn strips_tags_and_preserves_breaks() {
+        let html = "<p>Hello <span>@bot</span></p><p>How are you?<br/>Fine.</p>";
+        assert_eq!(strip_html(html), "Hello @bot\nHow are you?\nFine.");
+    }
+
+    #[test]
+    fn unescapes_entities() {
+        assert_eq!(strip_html("<p>a &amp; b &lt;c&gt;</p>"), "a & b <c>");
+    }
+}
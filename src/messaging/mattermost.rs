@@ -0,0 +1,658 @@
+//! Mattermost messaging adapter.
+//!
+//! Connects to the Mattermost WebSocket event API for inbound messages and
+//! uses the REST API (`/api/v4`) for outbound posting, thread replies, file
+//! uploads, and reactions.
+
+use crate::messaging::apply_runtime_adapter_to_conversation_id;
+use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use futures::{SinkExt as _, StreamExt as _};
+use serde::Deserialize;
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{RwLock, mpsc};
+
+/// Mattermost post bodies are limited to 16383 characters; stay well under it.
+const MAX_MESSAGE_LENGTH: usize = 16_000;
+
+/// Mattermost adapter state.
+pub struct MattermostAdapter {
+    runtime_key: String,
+    /// Base server URL, e.g. `https://mattermost.example.com` (no trailing slash).
+    base_url: String,
+    token: String,
+    /// Channel IDs to accept messages from. Empty means all channels the bot is in.
+    channel_filter: Vec<String>,
+    client: reqwest::Client,
+    /// The bot's own user ID, resolved at start so we can skip self-messages.
+    bot_user_id: Arc<RwLock<Option<String>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MattermostUser {
+    id: String,
+}
+
+/// Envelope for WebSocket events.
+#[derive(Debug, Deserialize)]
+struct WsEvent {
+    event: Option<String>,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// The `post` payload inside a `posted` event (arrives JSON-encoded as a string).
+#[derive(Debug, Deserialize)]
+struct MattermostPost {
+    id: String,
+    channel_id: String,
+    user_id: String,
+    message: String,
+    #[serde(default)]
+    root_id: String,
+    create_at: i64,
+    #[serde(default)]
+    file_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileInfo {
+    id: String,
+    name: String,
+    #[serde(default)]
+    mime_type: String,
+    size: Option<u64>,
+}
+
+impl MattermostAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+        channel_filter: Vec<String>,
+    ) -> Self {
+        let base_url = base_url.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.into(),
+            channel_filter,
+            client: crate::http::client(),
+            bot_user_id: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/api/v4{path}", self.base_url)
+    }
+
+    /// Create a post in a channel, optionally as a thread reply.
+    async fn create_post(
+        &self,
+        channel_id: &str,
+        text: &str,
+        root_id: Option<&str>,
+        file_ids: &[String],
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let mut body = json!({
+                "channel_id": channel_id,
+                "message": chunk,
+            });
+            if let Some(root_id) = root_id
+                && !root_id.is_empty()
+            {
+                body["root_id"] = json!(root_id);
+            }
+            if !file_ids.is_empty() {
+                body["file_ids"] = json!(file_ids);
+            }
+
+            let response = self
+                .client
+                .post(self.api_url("/posts"))
+                .bearer_auth(&self.token)
+                .json(&body)
+                .send()
+                .await
+                .context("failed to send mattermost post")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow::anyhow!("mattermost post failed: HTTP {status}: {body}").into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Upload a file to a channel and return its file ID for attachment to a post.
+    async fn upload_file(
+        &self,
+        channel_id: &str,
+        filename: &str,
+        data: bytes::Bytes,
+    ) -> crate::Result<String> {
+        #[derive(Deserialize)]
+        struct UploadResponse {
+            file_infos: Vec<FileInfo>,
+        }
+
+        let length = data.len() as u64;
+        let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::from(data), length)
+            .file_name(filename.to_string());
+        let form = reqwest::multipart::Form::new()
+            .text("channel_id", channel_id.to_string())
+            .part("files", part);
+
+        let response = self
+            .client
+            .post(self.api_url("/files"))
+            .bearer_auth(&self.token)
+            .multipart(form)
+            .send()
+            .await
+            .context("failed to upload mattermost file")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("mattermost upload failed: HTTP {status}: {body}").into());
+        }
+
+        let upload: UploadResponse = response
+            .json()
+            .await
+            .context("failed to parse mattermost upload response")?;
+        upload
+            .file_infos
+            .into_iter()
+            .next()
+            .map(|info| info.id)
+            .context("mattermost upload returned no file infos")
+            .map_err(Into::into)
+    }
+
+    /// Add an emoji reaction to a post.
+    async fn add_reaction(&self, post_id: &str, emoji_name: &str) -> crate::Result<()> {
+        let user_id = self
+            .bot_user_id
+            .read()
+            .await
+            .clone()
+            .context("mattermost bot user id not resolved")?;
+
+        let response = self
+            .client
+            .post(self.api_url("/reactions"))
+            .bearer_auth(&self.token)
+            .json(&json!({
+                "user_id": user_id,
+                "post_id": post_id,
+                "emoji_name": sanitize_emoji_name(emoji_name),
+            }))
+            .send()
+            .await
+            .context("failed to add mattermost reaction")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("mattermost reaction failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    /// Remove the bot's emoji reaction from a post.
+    async fn remove_reaction(&self, post_id: &str, emoji_name: &str) -> crate::Result<()> {
+        let user_id = self
+            .bot_user_id
+            .read()
+            .await
+            .clone()
+            .context("mattermost bot user id not resolved")?;
+
+        let emoji_name = sanitize_emoji_name(emoji_name);
+        let response = self
+            .client
+            .delete(self.api_url(&format!(
+                "/users/{user_id}/posts/{post_id}/reactions/{emoji_name}"
+            )))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("failed to remove mattermost reaction")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("mattermost reaction removal failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    /// Resolve a post's channel from inbound message metadata.
+    fn channel_id_from(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("mattermost_channel_id")
+            .and_then(|v| v.as_str())
+            .context("missing mattermost_channel_id in metadata")
+            .map_err(Into::into)
+    }
+
+    /// Thread root for replies: an in-thread message's root, else the message itself.
+    fn thread_root_from(message: &InboundMessage) -> Option<&str> {
+        message
+            .metadata
+            .get("mattermost_root_id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.is_empty())
+            .or_else(|| {
+                message
+                    .metadata
+                    .get("mattermost_post_id")
+                    .and_then(|v| v.as_str())
+            })
+    }
+}
+
+impl Messaging for MattermostAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Resolve our own user ID so the event loop can skip self-messages
+        let me: MattermostUser = self
+            .client
+            .get(self.api_url("/users/me"))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("failed to reach mattermost server")?
+            .error_for_status()
+            .context("mattermost token rejected")?
+            .json()
+            .await
+            .context("failed to parse mattermost user response")?;
+
+        *self.bot_user_id.write().await = Some(me.id.clone());
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let ws_url = format!(
+            "{}/api/v4/websocket",
+            self.base_url
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1)
+        );
+
+        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url)
+            .await
+            .with_context(|| format!("failed to connect mattermost websocket at {ws_url}"))?;
+        let (mut ws_tx, mut ws_rx) = ws_stream.split();
+
+        // Authenticate the WebSocket connection via the challenge message
+        let auth = json!({
+            "seq": 1,
+            "action": "authentication_challenge",
+            "data": { "token": self.token },
+        });
+        ws_tx
+            .send(WsMessage::Text(auth.to_string().into()))
+            .await
+            .context("failed to send mattermost websocket auth")?;
+
+        tracing::info!(url = %self.base_url, "mattermost connected");
+
+        let bot_user_id = me.id;
+        let channel_filter = self.channel_filter.clone();
+        let runtime_key = self.runtime_key.clone();
+        let base_url = self.base_url.clone();
+        let token = self.token.clone();
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("mattermost event loop shutting down");
+                        let _ = ws_tx.send(WsMessage::Close(None)).await;
+                        break;
+                    }
+                    frame = ws_rx.next() => {
+                        let Some(Ok(frame)) = frame else {
+                            tracing::warn!("mattermost websocket stream ended");
+                            break;
+                        };
+
+                        let WsMessage::Text(text) = frame else {
+                            continue;
+                        };
+
+                        let Ok(event) = serde_json::from_str::<WsEvent>(&text) else {
+                            continue;
+                        };
+                        if event.event.as_deref() != Some("posted") {
+                            continue;
+                        }
+
+                        // The post arrives as a JSON string inside the event data
+                        let Some(post) = event
+                            .data
+                            .get("post")
+                            .and_then(|v| v.as_str())
+                            .and_then(|raw| serde_json::from_str::<MattermostPost>(raw).ok())
+                        else {
+                            continue;
+                        };
+
+                        if post.user_id == bot_user_id {
+                            continue;
+                        }
+
+                        if !channel_filter.is_empty()
+                            && !channel_filter.contains(&post.channel_id)
+                        {
+                            continue;
+                        }
+
+                        let sender_name = event
+                            .data
+                            .get("sender_name")
+                            .and_then(|v| v.as_str())
+                            .map(|name| name.trim_start_matches('@').to_string())
+                            .unwrap_or_else(|| post.user_id.clone());
+
+                        let mut attachments = Vec::new();
+                        for file_id in &post.file_ids {
+                            let response = client
+                                .get(format!("{base_url}/api/v4/files/{file_id}/info"))
+                                .bearer_auth(&token)
+                                .send()
+                                .await
+                                .ok()
+                                .and_then(|r| r.error_for_status().ok());
+                            let info = match response {
+                                Some(response) => response.json::<FileInfo>().await.ok(),
+                                None => None,
+                            };
+                            let Some(info) = info else {
+                                continue;
+                            };
+                            attachments.push(crate::Attachment {
+                                filename: info.name,
+                                mime_type: info.mime_type,
+                                url: format!("{base_url}/api/v4/files/{}", info.id),
+                                size_bytes: info.size,
+                                auth_header: Some(format!("Bearer {token}")),
+                            });
+                        }
+
+                        let base_conversation_id = format!("mattermost:{}", post.channel_id);
+                        let conversation_id = apply_runtime_adapter_to_conversation_id(
+                            &runtime_key,
+                            base_conversation_id,
+                        );
+
+                        let mut metadata = HashMap::new();
+                        metadata.insert(
+                            "mattermost_channel_id".into(),
+                            serde_json::Value::String(post.channel_id.clone()),
+                        );
+                        metadata.insert(
+                            "mattermost_post_id".into(),
+                            serde_json::Value::String(post.id.clone()),
+                        );
+                        metadata.insert(
+                            "mattermost_root_id".into(),
+                            serde_json::Value::String(post.root_id.clone()),
+                        );
+                        metadata.insert(
+                            "mattermost_user_id".into(),
+                            serde_json::Value::String(post.user_id.clone()),
+                        );
+                        metadata.insert(
+                            "sender_display_name".into(),
+                            serde_json::Value::String(sender_name.clone()),
+                        );
+
+                        let content = if attachments.is_empty() {
+                            MessageContent::Text(post.message.clone())
+                        } else {
+                            MessageContent::Media {
+                                text: Some(post.message.clone()).filter(|t| !t.is_empty()),
+                                attachments,
+                            }
+                        };
+
+                        let timestamp = chrono::DateTime::from_timestamp_millis(post.create_at)
+                            .unwrap_or_else(chrono::Utc::now);
+
+                        let inbound = InboundMessage {
+                            id: post.id.clone(),
+                            source: "mattermost".into(),
+                            adapter: Some(runtime_key.clone()),
+                            conversation_id,
+                            sender_id: post.user_id.clone(),
+                            agent_id: None,
+                            content,
+                            timestamp,
+                            metadata,
+                            formatted_author: Some(sender_name),
+                        };
+
+                        if inbound_tx.send(inbound).await.is_err() {
+                            tracing::warn!("mattermost inbound receiver dropped");
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let channel_id = Self::channel_id_from(message)?;
+        // Stay in the thread when the triggering message is part of one
+        let existing_root = message
+            .metadata
+            .get("mattermost_root_id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.is_empty());
+
+        match response {
+            OutboundResponse::Text(text) => {
+                self.create_post(channel_id, &text, existing_root, &[])
+                    .await?;
+            }
+            OutboundResponse::RichMessage { text, .. } => {
+                self.create_post(channel_id, &text, existing_root, &[])
+                    .await?;
+            }
+            OutboundResponse::ThreadReply { text, .. } => {
+                let root = Self::thread_root_from(message);
+                self.create_post(channel_id, &text, root, &[]).await?;
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                caption,
+                ..
+            } => {
+                let file_id = self.upload_file(channel_id, &filename, data).await?;
+                self.create_post(
+                    channel_id,
+                    caption.as_deref().unwrap_or(""),
+                    existing_root,
+                    &[file_id],
+                )
+                .await?;
+            }
+            OutboundResponse::Reaction(emoji) => {
+                if let Some(post_id) = message
+                    .metadata
+                    .get("mattermost_post_id")
+                    .and_then(|v| v.as_str())
+                {
+                    self.add_reaction(post_id, &emoji).await?;
+                }
+            }
+            OutboundResponse::RemoveReaction(emoji) => {
+                if let Some(post_id) = message
+                    .metadata
+                    .get("mattermost_post_id")
+                    .and_then(|v| v.as_str())
+                {
+                    self.remove_reaction(post_id, &emoji).await?;
+                }
+            }
+            OutboundResponse::Ephemeral { text, .. } => {
+                // No bot-accessible ephemeral API — send as a regular message
+                self.create_post(channel_id, &text, existing_root, &[])
+                    .await?;
+            }
+            OutboundResponse::ScheduledMessage { text, .. } => {
+                // No scheduled posting for bots — send immediately
+                self.create_post(channel_id, &text, existing_root, &[])
+                    .await?;
+            }
+            // Streaming is buffered upstream; the final text arrives as Text
+            OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text) | OutboundResponse::RichMessage { text, .. } => {
+                self.create_post(target, &text, None, &[]).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        #[derive(Deserialize)]
+        struct PostList {
+            order: Vec<String>,
+            posts: HashMap<String, MattermostPost>,
+        }
+
+        let channel_id = Self::channel_id_from(message)?;
+        let bot_user_id = self.bot_user_id.read().await.clone().unwrap_or_default();
+
+        let response = self
+            .client
+            .get(self.api_url(&format!("/channels/{channel_id}/posts")))
+            .bearer_auth(&self.token)
+            .query(&[("per_page", limit.to_string())])
+            .send()
+            .await
+            .context("failed to fetch mattermost history")?
+            .error_for_status()
+            .context("mattermost history request rejected")?;
+
+        let list: PostList = response
+            .json()
+            .await
+            .context("failed to parse mattermost history")?;
+
+        // `order` is newest-first; reverse into chronological order and skip
+        // the triggering message itself
+        let mut history = Vec::new();
+        for post_id in list.order.iter().rev() {
+            if post_id == &message.id {
+                continue;
+            }
+            let Some(post) = list.posts.get(post_id) else {
+                continue;
+            };
+            history.push(HistoryMessage {
+                author: post.user_id.clone(),
+                content: post.message.clone(),
+                is_bot: post.user_id == bot_user_id,
+            });
+        }
+
+        Ok(history)
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        if self.bot_user_id.read().await.is_none() {
+            return Err(anyhow::anyhow!("mattermost client not connected").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        *self.bot_user_id.write().await = None;
+        tracing::info!("mattermost adapter shut down");
+        Ok(())
+    }
+}
+
+/// Map a unicode emoji or alias to a Mattermost emoji name (no colons).
+fn sanitize_emoji_name(emoji: &str) -> String {
+    let trimmed = emoji.trim().trim_matches(':');
+    if let Some(parsed) = emojis::get(trimmed)
+        && let Some(shortcode) = parsed.shortcode()
+    {
+        return shortcode.to_string();
+    }
+    trimmed.to_string()
+}
+
+/// Split a message into chunks that fit within Mattermost's post size limit.
+fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut remaining = text;
+
+    while !remaining.is_empty() {
+        if remaining.len() <= max_len {
+            chunks.push(remaining.to_string());
+            break;
+        }
+
+        let split_at = remaining[..max_len]
+            .rfind('\n')
+            .or_else(|| remaining[..max_len].rfind(' '))
+            .unwrap_or(max_len);
+
+        chunks.push(remaining[..split_at].to_string());
+        remaining = remaining[split_at..].trim_start();
+    }
+
+    chunks
+}
@@ -0,0 +1,518 @@
+//! MQTT messaging adapter for IoT-triggered conversations.
+//!
+//! Speaks MQTT 3.1.1 directly over a TCP socket: CONNECT with optional
+//! credentials, QoS 0 subscriptions to the configured topics, and a read
+//! loop that turns PUBLISH packets into inbound messages keyed by topic.
+//! Agent responses publish back to the configured response topic (or
+//! `<topic>/response` when none is set). Only the handful of packet types
+//! a QoS 0 client needs are implemented, in the same spirit as the XMPP
+//! adapter's purpose-built tokenizer.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Context as _;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Keepalive interval advertised in CONNECT; pings go out at half this.
+const KEEPALIVE_SECS: u16 = 60;
+
+/// Outbound (topic, payload) pairs queued for the writer half.
+type OutboundQueue = Arc<RwLock<Option<mpsc::Sender<(String, Vec<u8>)>>>>;
+
+/// MQTT adapter state.
+#[derive(Clone)]
+pub struct MqttAdapter {
+    runtime_key: String,
+    host: String,
+    port: u16,
+    client_id: String,
+    username: Option<String>,
+    password: Option<String>,
+    /// Topic filters to subscribe to, e.g. `home/+/motion`.
+    topics: Vec<String>,
+    /// Topic agent responses publish to; defaults to `<topic>/response`.
+    response_topic: Option<String>,
+    /// Sender for outbound (topic, payload) pairs while connected.
+    outbound_tx: OutboundQueue,
+    connected: Arc<AtomicBool>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl MqttAdapter {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        runtime_key: impl Into<String>,
+        host: impl Into<String>,
+        port: u16,
+        client_id: impl Into<String>,
+        username: Option<String>,
+        password: Option<String>,
+        topics: Vec<String>,
+        response_topic: Option<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            host: host.into(),
+            port,
+            client_id: client_id.into(),
+            username,
+            password,
+            topics,
+            response_topic,
+            outbound_tx: Arc::new(RwLock::new(None)),
+            connected: Arc::new(AtomicBool::new(false)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Queue a publish for the writer half of the live connection.
+    async fn publish(&self, topic: &str, payload: &str) -> crate::Result<()> {
+        let tx = self.outbound_tx.read().await;
+        let tx = tx.as_ref().context("MQTT connection not established")?;
+        tx.send((topic.to_string(), payload.as_bytes().to_vec()))
+            .await
+            .map_err(|_| anyhow::anyhow!("MQTT writer task is gone"))?;
+        Ok(())
+    }
+
+    /// The topic a reply to this message should publish to.
+    fn reply_topic(&self, message: &InboundMessage) -> String {
+        if let Some(topic) = &self.response_topic {
+            return topic.clone();
+        }
+        let source_topic = message
+            .metadata
+            .get("mqtt_topic")
+            .and_then(|v| v.as_str())
+            .unwrap_or("spacebot");
+        format!("{source_topic}/response")
+    }
+
+    /// One connection lifetime: connect, subscribe, pump packets.
+    async fn run_connection(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+    ) -> crate::Result<()> {
+        let mut stream = tokio::net::TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .with_context(|| format!("failed to connect to MQTT broker {}:{}", self.host, self.port))?;
+
+        stream
+            .write_all(&encode_connect(
+                &self.client_id,
+                self.username.as_deref(),
+                self.password.as_deref(),
+            ))
+            .await
+            .context("failed to send MQTT CONNECT")?;
+
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut connack_seen = false;
+        let (outbound_tx, mut outbound_rx) = mpsc::channel::<(String, Vec<u8>)>(64);
+        *self.outbound_tx.write().await = Some(outbound_tx);
+
+        let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(
+            u64::from(KEEPALIVE_SECS) / 2,
+        ));
+        ping_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let mut read_chunk = [0u8; 4096];
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => {
+                    let _ = stream.write_all(&[0xE0, 0x00]).await; // DISCONNECT
+                    return Ok(());
+                }
+                _ = ping_interval.tick() => {
+                    if connack_seen {
+                        stream
+                            .write_all(&[0xC0, 0x00]) // PINGREQ
+                            .await
+                            .context("failed to send MQTT ping")?;
+                    }
+                }
+                outbound = outbound_rx.recv() => {
+                    let Some((topic, payload)) = outbound else {
+                        return Ok(());
+                    };
+                    stream
+                        .write_all(&encode_publish(&topic, &payload))
+                        .await
+                        .context("failed to send MQTT PUBLISH")?;
+                }
+                read = stream.read(&mut read_chunk) => {
+                    let n = read.context("MQTT socket read failed")?;
+                    if n == 0 {
+                        return Err(anyhow::anyhow!("MQTT broker closed the connection").into());
+                    }
+                    buffer.extend_from_slice(&read_chunk[..n]);
+
+                    while let Some((packet, consumed)) = parse_packet(&buffer)? {
+                        buffer.drain(..consumed);
+                        match packet {
+                            Packet::ConnAck(code) => {
+                                if code != 0 {
+                                    return Err(anyhow::anyhow!(
+                                        "MQTT broker refused connection (code {code})"
+                                    )
+                                    .into());
+                                }
+                                connack_seen = true;
+                                self.connected.store(true, Ordering::SeqCst);
+                                for (index, topic) in self.topics.iter().enumerate() {
+                                    stream
+                                        .write_all(&encode_subscribe(index as u16 + 1, topic))
+                                        .await
+                                        .context("failed to send MQTT SUBSCRIBE")?;
+                                }
+                                tracing::info!(
+                                    broker = %self.host,
+                                    topics = ?self.topics,
+                                    "mqtt connected"
+                                );
+                            }
+                            Packet::Publish { topic, payload } => {
+                                if let Some(inbound) =
+                                    self.build_inbound(&topic, &payload)
+                                    && inbound_tx.send(inbound).await.is_err()
+                                {
+                                    return Ok(());
+                                }
+                            }
+                            Packet::SubAck | Packet::PingResp | Packet::Other(_) => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Turn a PUBLISH payload into an inbound message.
+    fn build_inbound(&self, topic: &str, payload: &[u8]) -> Option<InboundMessage> {
+        let text = String::from_utf8_lossy(payload).trim().to_string();
+        if text.is_empty() {
+            return None;
+        }
+        // Don't loop on our own response topics
+        if self.response_topic.as_deref() == Some(topic) || topic.ends_with("/response") {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "mqtt_topic".into(),
+            serde_json::Value::String(topic.to_string()),
+        );
+
+        Some(InboundMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            source: "mqtt".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id: format!("mqtt:{topic}"),
+            sender_id: topic.to_string(),
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(topic.to_string()),
+        })
+    }
+}
+
+impl Messaging for MqttAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let result = adapter.run_connection(&inbound_tx, &mut shutdown_rx).await;
+                adapter.connected.store(false, Ordering::SeqCst);
+                *adapter.outbound_tx.write().await = None;
+                match result {
+                    Ok(()) => {
+                        tracing::info!("mqtt connection loop stopping");
+                        return;
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "mqtt connection lost; reconnecting in 10s");
+                        tokio::select! {
+                            _ = tokio::time::sleep(std::time::Duration::from_secs(10)) => {}
+                            _ = shutdown_rx.recv() => return,
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let topic = self.reply_topic(message);
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.publish(&topic, &text).await,
+            OutboundResponse::File { caption, .. } => {
+                // Binary payloads don't fit chat-style topics; send the caption if any
+                if let Some(caption) = caption {
+                    self.publish(&topic, &caption).await?;
+                }
+                Ok(())
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets are topics, matching the conversation ID suffix
+        self.publish(target, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        if !self.connected.load(Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("MQTT broker not connected").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("mqtt adapter shut down");
+        Ok(())
+    }
+}
+
+/// The packet types a QoS 0 client needs to recognize.
+#[derive(Debug, PartialEq)]
+enum Packet {
+    ConnAck(u8),
+    SubAck,
+    Publish { topic: String, payload: Vec<u8> },
+    PingResp,
+    Other(u8),
+}
+
+/// An MQTT UTF-8 string: u16 big-endian length followed by the bytes.
+fn encode_string(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// The variable-length remaining-length field from the fixed header.
+fn encode_remaining_length(out: &mut Vec<u8>, mut length: usize) {
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+}
+
+/// A CONNECT packet with clean session and optional credentials.
+fn encode_connect(client_id: &str, username: Option<&str>, password: Option<&str>) -> Vec<u8> {
+    let mut body = Vec::new();
+    encode_string(&mut body, "MQTT");
+    body.push(4); // protocol level 3.1.1
+    let mut flags = 0x02; // clean session
+    if username.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    body.extend_from_slice(&KEEPALIVE_SECS.to_be_bytes());
+    encode_string(&mut body, client_id);
+    if let Some(username) = username {
+        encode_string(&mut body, username);
+    }
+    if let Some(password) = password {
+        encode_string(&mut body, password);
+    }
+
+    let mut packet = vec![0x10];
+    encode_remaining_length(&mut packet, body.len());
+    packet.extend_from_slice(&body);
+    packet
+}
+
+/// A SUBSCRIBE packet for one topic filter at QoS 0.
+fn encode_subscribe(packet_id: u16, topic: &str) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&packet_id.to_be_bytes());
+    encode_string(&mut body, topic);
+    body.push(0); // requested QoS
+
+    let mut packet = vec![0x82];
+    encode_remaining_length(&mut packet, body.len());
+    packet.extend_from_slice(&body);
+    packet
+}
+
+/// A QoS 0 PUBLISH packet.
+fn encode_publish(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    encode_string(&mut body, topic);
+    body.extend_from_slice(payload);
+
+    let mut packet = vec![0x30];
+    encode_remaining_length(&mut packet, body.len());
+    packet.extend_from_slice(&body);
+    packet
+}
+
+/// Parse one packet from the front of the buffer.
+///
+/// Returns `None` when more bytes are needed, or the packet and the number
+/// of bytes it consumed.
+fn parse_packet(buffer: &[u8]) -> anyhow::Result<Option<(Packet, usize)>> {
+    if buffer.len() < 2 {
+        return Ok(None);
+    }
+    let packet_type = buffer[0] >> 4;
+    let qos = (buffer[0] >> 1) & 0x03;
+
+    // Decode the remaining-length varint
+    let mut remaining: usize = 0;
+    let mut multiplier: usize = 1;
+    let mut header_len = 1;
+    loop {
+        let Some(&byte) = buffer.get(header_len) else {
+            return Ok(None);
+        };
+        header_len += 1;
+        remaining += (byte & 0x7F) as usize * multiplier;
+        multiplier *= 128;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if header_len > 4 {
+            return Err(anyhow::anyhow!("malformed MQTT remaining length"));
+        }
+    }
+
+    let total = header_len + remaining;
+    if buffer.len() < total {
+        return Ok(None);
+    }
+    let body = &buffer[header_len..total];
+
+    let packet = match packet_type {
+        2 => Packet::ConnAck(body.get(1).copied().unwrap_or(0xFF)),
+        9 => Packet::SubAck,
+        13 => Packet::PingResp,
+        3 => {
+            if body.len() < 2 {
+                return Err(anyhow::anyhow!("truncated MQTT PUBLISH"));
+            }
+            let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+            let mut offset = 2 + topic_len;
+            if body.len() < offset {
+                return Err(anyhow::anyhow!("truncated MQTT PUBLISH topic"));
+            }
+            let topic = String::from_utf8_lossy(&body[2..offset]).to_string();
+            // QoS 1/2 publishes carry a packet identifier we can skip; we
+            // never subscribe above QoS 0, so no ack is owed.
+            if qos > 0 {
+                offset += 2;
+            }
+            if body.len() < offset {
+                return Err(anyhow::anyhow!("truncated MQTT PUBLISH packet id"));
+            }
+            Packet::Publish {
+                topic,
+                payload: body[offset..].to_vec(),
+            }
+        }
+        other => Packet::Other(other),
+    };
+    Ok(Some((packet, total)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_packet_round_trips_flags() {
+        let packet = encode_connect("spacebot", Some("user"), Some("pass"));
+        assert_eq!(packet[0], 0x10);
+        // flags byte: clean session + username + password
+        let flags = packet[2 + 2 + 4 + 1];
+        assert_eq!(flags, 0x02 | 0x80 | 0x40);
+
+        let anonymous = encode_connect("spacebot", None, None);
+        let flags = anonymous[2 + 2 + 4 + 1];
+        assert_eq!(flags, 0x02);
+    }
+
+    #[test]
+    fn publish_packets_parse() {
+        let encoded = encode_publish("home/kitchen/motion", b"detected");
+        let (packet, consumed) = parse_packet(&encoded).unwrap().unwrap();
+        assert_eq!(consumed, encoded.len());
+        assert_eq!(
+            packet,
+            Packet::Publish {
+                topic: "home/kitchen/motion".into(),
+                payload: b"detected".to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn partial_packets_wait_for_more_bytes() {
+        let encoded = encode_publish("topic", b"payload");
+        assert!(parse_packet(&encoded[..3]).unwrap().is_none());
+
+        let mut two = encoded.clone();
+        two.extend_from_slice(&encode_publish("other", b"x"));
+        let (_, consumed) = parse_packet(&two).unwrap().unwrap();
+        let (second, _) = parse_packet(&two[consumed..]).unwrap().unwrap();
+        assert_eq!(
+            second,
+            Packet::Publish {
+                topic: "other".into(),
+                payload: b"x".to_vec(),
+            }
+        );
+    }
+}
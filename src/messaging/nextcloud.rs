@@ -0,0 +1,509 @@
+//! Nextcloud Talk messaging adapter.
+//!
+//! Long-polls room chats through the OCS API: each configured room token
+//! gets its own polling task, and new chat messages become inbound messages
+//! keyed by the room token. Replies post back as chat messages, files are
+//! uploaded over WebDAV and shared into the room, and reactions use the
+//! Talk reaction endpoint.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc, watch};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Talk caps chat messages at 32000 characters.
+const MAX_MESSAGE_LENGTH: usize = 30_000;
+
+/// How long each long-poll request waits for new messages.
+const LONG_POLL_TIMEOUT_SECS: u32 = 30;
+
+/// Nextcloud Talk adapter state.
+#[derive(Clone)]
+pub struct NextcloudAdapter {
+    runtime_key: String,
+    /// Server base URL, e.g. `https://cloud.example.org`.
+    base_url: String,
+    username: String,
+    /// App password paired with the username for basic auth.
+    app_password: String,
+    /// Room tokens to poll. Empty polls every room the account has joined.
+    rooms: Vec<String>,
+    client: reqwest::Client,
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
+}
+
+impl NextcloudAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        username: impl Into<String>,
+        app_password: impl Into<String>,
+        rooms: Vec<String>,
+    ) -> Self {
+        let base_url = base_url.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            username: username.into(),
+            app_password: app_password.into(),
+            rooms,
+            client: crate::http::client(),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.base_url))
+            .basic_auth(&self.username, Some(&self.app_password))
+            .header("OCS-APIRequest", "true")
+            .header(reqwest::header::ACCEPT, "application/json")
+    }
+
+    /// Room tokens the adapter should poll.
+    async fn resolve_rooms(&self) -> crate::Result<Vec<String>> {
+        if !self.rooms.is_empty() {
+            return Ok(self.rooms.clone());
+        }
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                "/ocs/v2.php/apps/spreed/api/v4/room",
+            )
+            .send()
+            .await
+            .context("failed to list Nextcloud Talk rooms")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Nextcloud room list failed: HTTP {status}").into());
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to decode Nextcloud room list")?;
+        Ok(body["ocs"]["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|room| room["token"].as_str().map(str::to_string))
+            .collect())
+    }
+
+    /// The newest message ID in a room, used to prime the long poll so
+    /// history isn't replayed on startup.
+    async fn latest_message_id(&self, room: &str) -> i64 {
+        let result = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/ocs/v2.php/apps/spreed/api/v1/chat/{room}"),
+            )
+            .query(&[("lookIntoFuture", "0"), ("limit", "1")])
+            .send()
+            .await;
+        match result {
+            Ok(response) => response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|body| {
+                    body["ocs"]["data"]
+                        .as_array()?
+                        .iter()
+                        .filter_map(|m| m["id"].as_i64())
+                        .max()
+                })
+                .unwrap_or(0),
+            Err(_) => 0,
+        }
+    }
+
+    /// One long-poll cycle; returns new messages and the updated watermark.
+    async fn poll_room(
+        &self,
+        room: &str,
+        last_known: i64,
+    ) -> crate::Result<(Vec<InboundMessage>, i64)> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/ocs/v2.php/apps/spreed/api/v1/chat/{room}"),
+            )
+            .query(&[
+                ("lookIntoFuture", "1".to_string()),
+                ("timeout", LONG_POLL_TIMEOUT_SECS.to_string()),
+                ("lastKnownMessageId", last_known.to_string()),
+            ])
+            .send()
+            .await
+            .context("Nextcloud Talk long poll failed")?;
+
+        // 304 means the poll timed out with nothing new
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok((Vec::new(), last_known));
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Nextcloud Talk poll failed: HTTP {status}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to decode Nextcloud Talk messages")?;
+        let mut watermark = last_known;
+        let mut messages = Vec::new();
+        for value in body["ocs"]["data"].as_array().into_iter().flatten() {
+            if let Some(id) = value["id"].as_i64() {
+                watermark = watermark.max(id);
+            }
+            if let Some(inbound) = parse_message(value, &self.runtime_key, &self.username) {
+                messages.push(inbound);
+            }
+        }
+        Ok((messages, watermark))
+    }
+
+    /// Post a chat message, optionally as a reply to another message.
+    async fn post_message(
+        &self,
+        room: &str,
+        text: &str,
+        reply_to: Option<i64>,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let mut payload = json!({ "message": chunk });
+            if let Some(reply_to) = reply_to {
+                payload["replyTo"] = json!(reply_to);
+            }
+            let response = self
+                .request(
+                    reqwest::Method::POST,
+                    &format!("/ocs/v2.php/apps/spreed/api/v1/chat/{room}"),
+                )
+                .json(&payload)
+                .send()
+                .await
+                .context("failed to post Nextcloud Talk message")?;
+            if !response.status().is_success() {
+                let status = response.status();
+                return Err(
+                    anyhow::anyhow!("Nextcloud Talk message to {room} failed: HTTP {status}")
+                        .into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Upload a file over WebDAV and share it into the room.
+    async fn share_file(
+        &self,
+        room: &str,
+        filename: &str,
+        data: bytes::Bytes,
+    ) -> crate::Result<()> {
+        let path = format!("Talk/{filename}");
+        let response = self
+            .client
+            .put(format!(
+                "{}/remote.php/dav/files/{}/{path}",
+                self.base_url, self.username
+            ))
+            .basic_auth(&self.username, Some(&self.app_password))
+            .body(data)
+            .send()
+            .await
+            .context("failed to upload file to Nextcloud")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Nextcloud file upload failed: HTTP {status}").into());
+        }
+
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                "/ocs/v2.php/apps/files_sharing/api/v1/shares",
+            )
+            .json(&json!({
+                "shareType": 10,
+                "shareWith": room,
+                "path": format!("/{path}"),
+            }))
+            .send()
+            .await
+            .context("failed to share file into Nextcloud Talk room")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Nextcloud file share failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    /// The room token a reply should target.
+    fn routing(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("nextcloud_room_token")
+            .and_then(|v| v.as_str())
+            .context("missing nextcloud_room_token in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for NextcloudAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let rooms = self.resolve_rooms().await?;
+        if rooms.is_empty() {
+            tracing::warn!("Nextcloud Talk adapter started with no rooms to poll");
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        for room in rooms {
+            let adapter = self.clone();
+            let inbound_tx = inbound_tx.clone();
+            let mut shutdown_rx = shutdown_rx.clone();
+            tokio::spawn(async move {
+                let mut last_known = adapter.latest_message_id(&room).await;
+                loop {
+                    tokio::select! {
+                        result = adapter.poll_room(&room, last_known) => {
+                            match result {
+                                Ok((messages, watermark)) => {
+                                    last_known = watermark;
+                                    for message in messages {
+                                        if inbound_tx.send(message).await.is_err() {
+                                            return;
+                                        }
+                                    }
+                                }
+                                Err(error) => {
+                                    tracing::warn!(%error, room = %room, "Nextcloud Talk poll error; backing off");
+                                    tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                                }
+                            }
+                        }
+                        _ = shutdown_rx.changed() => {
+                            tracing::debug!(room = %room, "Nextcloud Talk room poller stopping");
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let room = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.post_message(room, &text, None).await,
+            OutboundResponse::ThreadReply { text, .. } => {
+                let reply_to = message
+                    .metadata
+                    .get("nextcloud_message_id")
+                    .and_then(|v| v.as_i64());
+                self.post_message(room, &text, reply_to).await
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                caption,
+                ..
+            } => {
+                self.share_file(room, &filename, data).await?;
+                if let Some(caption) = caption {
+                    self.post_message(room, &caption, None).await?;
+                }
+                Ok(())
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let Some(message_id) = message
+                    .metadata
+                    .get("nextcloud_message_id")
+                    .and_then(|v| v.as_i64())
+                else {
+                    return Ok(());
+                };
+                let response = self
+                    .request(
+                        reqwest::Method::POST,
+                        &format!("/ocs/v2.php/apps/spreed/api/v1/reaction/{room}/{message_id}"),
+                    )
+                    .json(&json!({ "reaction": emoji }))
+                    .send()
+                    .await
+                    .context("failed to add Nextcloud Talk reaction")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    tracing::warn!(%status, "Nextcloud Talk reaction failed");
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Targets are room tokens, matching the conversation ID suffix
+        self.post_message(target, &text, None).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                "/ocs/v2.php/cloud/capabilities",
+            )
+            .send()
+            .await
+            .context("Nextcloud server unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Nextcloud health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(true).ok();
+        }
+        tracing::info!("Nextcloud Talk adapter shut down");
+        Ok(())
+    }
+}
+
+/// Convert an OCS chat message into an inbound message.
+fn parse_message(
+    value: &serde_json::Value,
+    runtime_key: &str,
+    own_username: &str,
+) -> Option<InboundMessage> {
+    // System messages (joins, renames, reactions) are not conversation turns
+    if value["systemMessage"].as_str().is_some_and(|s| !s.is_empty()) {
+        return None;
+    }
+    let actor_type = value["actorType"].as_str()?;
+    if actor_type != "users" && actor_type != "guests" {
+        return None;
+    }
+    let actor_id = value["actorId"].as_str()?;
+    if actor_id == own_username {
+        return None;
+    }
+    let text = value["message"].as_str()?.trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+
+    let room = value["token"].as_str()?.to_string();
+    let message_id = value["id"].as_i64()?;
+    let display_name = value["actorDisplayName"].as_str().map(str::to_string);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "nextcloud_room_token".into(),
+        serde_json::Value::String(room.clone()),
+    );
+    metadata.insert(
+        "nextcloud_message_id".into(),
+        serde_json::Value::from(message_id),
+    );
+    if let Some(name) = &display_name {
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(name.clone()),
+        );
+    }
+
+    Some(InboundMessage {
+        id: message_id.to_string(),
+        source: "nextcloud".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("nextcloud:{room}"),
+        sender_id: actor_id.to_string(),
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: display_name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_messages_become_conversation_turns() {
+        let value = serde_json::json!({
+            "id": 512,
+            "token": "abc123",
+            "actorType": "users",
+            "actorId": "alice",
+            "actorDisplayName": "Alice A",
+            "message": "hello bot",
+            "systemMessage": "",
+        });
+        let inbound = parse_message(&value, "nextcloud", "spacebot").unwrap();
+        assert_eq!(inbound.conversation_id, "nextcloud:abc123");
+        assert_eq!(inbound.sender_id, "alice");
+        assert_eq!(
+            inbound.metadata.get("nextcloud_message_id"),
+            Some(&serde_json::Value::from(512))
+        );
+    }
+
+    #[test]
+    fn own_and_system_messages_are_dropped() {
+        let own = serde_json::json!({
+            "id": 1,
+            "token": "abc123",
+            "actorType": "users",
+            "actorId": "spacebot",
+            "message": "done",
+            "systemMessage": "",
+        });
+        assert!(parse_message(&own, "nextcloud", "spacebot").is_none());
+
+        let system = serde_json::json!({
+            "id": 2,
+            "token": "abc123",
+            "actorType": "users",
+            "actorId": "alice",
+            "message": "alice joined the conversation",
+            "systemMessage": "user_added",
+        });
+        assert!(parse_message(&system, "nextcloud", "spacebot").is_none());
+    }
+}
@@ -0,0 +1,605 @@
+//! Nostr messaging adapter.
+//!
+//! Connects to a configurable list of relays over WebSocket, subscribes to
+//! events addressed to the bot's pubkey, and handles both encrypted DMs
+//! (NIP-04, kind 4) and public mentions (kind 1). Replies go back the same
+//! way: DMs are encrypted to the sender, mentions become threaded kind-1
+//! replies. NIP-17 gift-wrapped DMs are not yet supported; NIP-04 remains
+//! the interoperable baseline.
+//!
+//! The bot keypair lives in `[messaging.nostr]` next to the other platform
+//! credentials, as a hex-encoded secret key.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use aes::cipher::{BlockDecryptMut as _, BlockEncryptMut as _, KeyIvInit as _, block_padding::Pkcs7};
+use anyhow::Context as _;
+use base64::Engine as _;
+use futures::{SinkExt as _, StreamExt as _};
+use secp256k1::{Keypair, Message as SecpMessage, PublicKey, Secp256k1, SecretKey};
+use serde_json::json;
+use sha2::{Digest as _, Sha256};
+use tokio::sync::{Mutex, RwLock, mpsc};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+
+/// Kind-1 notes are best kept short; longer replies are split.
+const MAX_NOTE_LENGTH: usize = 2_000;
+
+/// Cap on the dedup set so it can't grow without bound across relays.
+const SEEN_EVENT_CAP: usize = 4_096;
+
+/// A signed Nostr event ready for publishing or received from a relay.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct NostrEvent {
+    id: String,
+    pubkey: String,
+    created_at: i64,
+    kind: u32,
+    tags: Vec<Vec<String>>,
+    content: String,
+    sig: String,
+}
+
+/// Nostr adapter state.
+pub struct NostrAdapter {
+    runtime_key: String,
+    relays: Vec<String>,
+    secp: Secp256k1<secp256k1::All>,
+    keypair: Keypair,
+    /// Hex-encoded x-only pubkey of the bot.
+    pubkey_hex: String,
+    /// Event IDs already forwarded, to dedup across relays.
+    seen_events: Arc<Mutex<HashSet<String>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl NostrAdapter {
+    /// Build the adapter from a hex-encoded secret key and relay list.
+    pub fn new(
+        runtime_key: impl Into<String>,
+        secret_key_hex: &str,
+        relays: Vec<String>,
+    ) -> crate::Result<Self> {
+        let secp = Secp256k1::new();
+        let bytes = hex::decode(secret_key_hex.trim())
+            .context("nostr secret key must be hex-encoded")?;
+        let secret_key =
+            SecretKey::from_slice(&bytes).context("invalid nostr secret key")?;
+        let keypair = Keypair::from_secret_key(&secp, &secret_key);
+        let (xonly, _parity) = keypair.x_only_public_key();
+        let pubkey_hex = hex::encode(xonly.serialize());
+
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            relays,
+            secp,
+            keypair,
+            pubkey_hex,
+            seen_events: Arc::new(Mutex::new(HashSet::new())),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    fn clone_for_task(&self) -> Self {
+        Self {
+            runtime_key: self.runtime_key.clone(),
+            relays: self.relays.clone(),
+            secp: self.secp.clone(),
+            keypair: self.keypair,
+            pubkey_hex: self.pubkey_hex.clone(),
+            seen_events: self.seen_events.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        }
+    }
+
+    /// The bot's hex pubkey (for status displays).
+    pub fn pubkey_hex(&self) -> &str {
+        &self.pubkey_hex
+    }
+
+    /// Build and sign an event per NIP-01.
+    fn sign_event(&self, kind: u32, tags: Vec<Vec<String>>, content: String) -> NostrEvent {
+        let created_at = chrono::Utc::now().timestamp();
+        let serialized = serde_json::to_string(&json!([
+            0,
+            self.pubkey_hex,
+            created_at,
+            kind,
+            tags,
+            content,
+        ]))
+        .unwrap_or_default();
+
+        let digest: [u8; 32] = Sha256::digest(serialized.as_bytes()).into();
+        let id = hex::encode(digest);
+        let message = SecpMessage::from_digest(digest);
+        let sig = self.secp.sign_schnorr_no_aux_rand(&message, &self.keypair);
+
+        NostrEvent {
+            id,
+            pubkey: self.pubkey_hex.clone(),
+            created_at,
+            kind,
+            tags,
+            content,
+            sig: hex::encode(sig.as_ref()),
+        }
+    }
+
+    /// NIP-04 shared key: the x coordinate of the ECDH point, unhashed.
+    fn shared_key(&self, peer_pubkey_hex: &str) -> crate::Result<[u8; 32]> {
+        let peer_bytes =
+            hex::decode(peer_pubkey_hex).context("invalid peer pubkey hex")?;
+        // x-only pubkeys get an even-parity prefix for point reconstruction
+        let mut full = Vec::with_capacity(33);
+        full.push(0x02);
+        full.extend_from_slice(&peer_bytes);
+        let peer = PublicKey::from_slice(&full).context("invalid peer pubkey")?;
+
+        let point = secp256k1::ecdh::shared_secret_point(&peer, &self.keypair.secret_key());
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&point[..32]);
+        Ok(key)
+    }
+
+    /// Encrypt a DM body per NIP-04: AES-256-CBC, `base64(ct)?iv=base64(iv)`.
+    fn encrypt_nip04(&self, peer_pubkey_hex: &str, plaintext: &str) -> crate::Result<String> {
+        let key = self.shared_key(peer_pubkey_hex)?;
+        let iv: [u8; 16] = rand::random();
+
+        let cipher = Aes256CbcEnc::new(&key.into(), &iv.into());
+        let ciphertext = cipher.encrypt_padded_vec_mut::<Pkcs7>(plaintext.as_bytes());
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        Ok(format!(
+            "{}?iv={}",
+            engine.encode(ciphertext),
+            engine.encode(iv)
+        ))
+    }
+
+    /// Decrypt a NIP-04 DM body.
+    fn decrypt_nip04(&self, peer_pubkey_hex: &str, content: &str) -> crate::Result<String> {
+        let (ciphertext_b64, iv_b64) = content
+            .split_once("?iv=")
+            .context("malformed NIP-04 content")?;
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let ciphertext = engine
+            .decode(ciphertext_b64)
+            .context("invalid NIP-04 ciphertext encoding")?;
+        let iv = engine.decode(iv_b64).context("invalid NIP-04 iv encoding")?;
+        if iv.len() != 16 {
+            return Err(anyhow::anyhow!("NIP-04 iv must be 16 bytes").into());
+        }
+
+        let key = self.shared_key(peer_pubkey_hex)?;
+        let mut iv_bytes = [0u8; 16];
+        iv_bytes.copy_from_slice(&iv);
+
+        let cipher = Aes256CbcDec::new(&key.into(), &iv_bytes.into());
+        let plaintext = cipher
+            .decrypt_padded_vec_mut::<Pkcs7>(&ciphertext)
+            .map_err(|_| anyhow::anyhow!("NIP-04 decryption failed"))?;
+        String::from_utf8(plaintext)
+            .context("NIP-04 plaintext is not valid UTF-8")
+            .map_err(Into::into)
+    }
+
+    /// Publish a signed event to every configured relay (best effort).
+    async fn publish(&self, event: &NostrEvent) -> crate::Result<()> {
+        let payload = serde_json::to_string(&json!(["EVENT", event]))
+            .context("failed to serialize nostr event")?;
+
+        let mut delivered = 0usize;
+        for relay in &self.relays {
+            match publish_to_relay(relay, &payload).await {
+                Ok(()) => delivered += 1,
+                Err(error) => {
+                    tracing::warn!(%error, relay = %relay, "failed to publish nostr event");
+                }
+            }
+        }
+
+        if delivered == 0 {
+            return Err(anyhow::anyhow!("event not accepted by any relay").into());
+        }
+        Ok(())
+    }
+
+    /// Send a DM (kind 4) to a peer.
+    async fn send_dm(&self, peer_pubkey_hex: &str, text: &str) -> crate::Result<()> {
+        let content = self.encrypt_nip04(peer_pubkey_hex, text)?;
+        let event = self.sign_event(4, vec![vec!["p".into(), peer_pubkey_hex.into()]], content);
+        self.publish(&event).await
+    }
+
+    /// Send a public reply (kind 1) threaded under an event.
+    async fn send_reply(
+        &self,
+        peer_pubkey_hex: &str,
+        reply_to_event_id: &str,
+        root_event_id: Option<&str>,
+        text: &str,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_NOTE_LENGTH) {
+            let mut tags = Vec::new();
+            if let Some(root) = root_event_id
+                && root != reply_to_event_id
+            {
+                tags.push(vec!["e".into(), root.into(), String::new(), "root".into()]);
+            }
+            tags.push(vec![
+                "e".into(),
+                reply_to_event_id.into(),
+                String::new(),
+                "reply".into(),
+            ]);
+            tags.push(vec!["p".into(), peer_pubkey_hex.into()]);
+
+            let event = self.sign_event(1, tags, chunk);
+            self.publish(&event).await?;
+        }
+        Ok(())
+    }
+
+    /// Run one relay subscription until the connection drops.
+    async fn run_relay(
+        &self,
+        relay: &str,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+    ) -> crate::Result<()> {
+        let (stream, _) = tokio_tungstenite::connect_async(relay)
+            .await
+            .with_context(|| format!("failed to connect to relay {relay}"))?;
+        let (mut write, mut read) = stream.split();
+
+        // DMs and mentions addressed to us, from now on
+        let since = chrono::Utc::now().timestamp();
+        let subscription = json!([
+            "REQ",
+            "spacebot",
+            { "kinds": [1, 4], "#p": [self.pubkey_hex], "since": since },
+        ]);
+        write
+            .send(WsMessage::Text(subscription.to_string().into()))
+            .await
+            .context("failed to subscribe to relay")?;
+
+        while let Some(frame) = read.next().await {
+            let frame = frame.context("relay stream error")?;
+            let text = match frame {
+                WsMessage::Text(text) => text,
+                WsMessage::Ping(data) => {
+                    write.send(WsMessage::Pong(data)).await.ok();
+                    continue;
+                }
+                WsMessage::Close(_) => break,
+                _ => continue,
+            };
+
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&text) else {
+                continue;
+            };
+            if value[0].as_str() != Some("EVENT") {
+                continue;
+            }
+            let Ok(event) = serde_json::from_value::<NostrEvent>(value[2].clone()) else {
+                continue;
+            };
+
+            if event.pubkey == self.pubkey_hex {
+                continue;
+            }
+
+            {
+                let mut seen = self.seen_events.lock().await;
+                if !seen.insert(event.id.clone()) {
+                    continue;
+                }
+                if seen.len() > SEEN_EVENT_CAP {
+                    seen.clear();
+                }
+            }
+
+            if let Some(inbound) = self.parse_event(event)
+                && inbound_tx.send(inbound).await.is_err()
+            {
+                return Ok(());
+            }
+        }
+
+        Err(anyhow::anyhow!("relay connection closed: {relay}").into())
+    }
+
+    /// Convert a DM or mention event into an inbound message.
+    fn parse_event(&self, event: NostrEvent) -> Option<InboundMessage> {
+        let is_dm = event.kind == 4;
+        let text = if is_dm {
+            match self.decrypt_nip04(&event.pubkey, &event.content) {
+                Ok(text) => text,
+                Err(error) => {
+                    tracing::warn!(%error, event_id = %event.id, "failed to decrypt nostr DM");
+                    return None;
+                }
+            }
+        } else {
+            event.content.clone()
+        };
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        // Thread root for mentions: the first "root"-marked e tag, else the
+        // first e tag, else this event itself.
+        let root_id = event
+            .tags
+            .iter()
+            .filter(|tag| tag.first().map(String::as_str) == Some("e"))
+            .find(|tag| tag.get(3).map(String::as_str) == Some("root"))
+            .or_else(|| {
+                event
+                    .tags
+                    .iter()
+                    .find(|tag| tag.first().map(String::as_str) == Some("e"))
+            })
+            .and_then(|tag| tag.get(1).cloned())
+            .unwrap_or_else(|| event.id.clone());
+
+        let conversation_id = if is_dm {
+            format!("nostr:dm:{}", event.pubkey)
+        } else {
+            format!("nostr:{root_id}")
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "nostr_event_id".into(),
+            serde_json::Value::String(event.id.clone()),
+        );
+        metadata.insert(
+            "nostr_pubkey".into(),
+            serde_json::Value::String(event.pubkey.clone()),
+        );
+        metadata.insert("nostr_kind".into(), serde_json::Value::from(event.kind));
+        metadata.insert(
+            "nostr_root_id".into(),
+            serde_json::Value::String(root_id),
+        );
+
+        let timestamp = chrono::DateTime::from_timestamp(event.created_at, 0)
+            .unwrap_or_else(chrono::Utc::now);
+
+        Some(InboundMessage {
+            id: event.id,
+            source: "nostr".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: event.pubkey,
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp,
+            metadata,
+            formatted_author: None,
+        })
+    }
+
+    /// Reply routing details from inbound metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, &str, Option<&str>, bool)> {
+        let pubkey = message
+            .metadata
+            .get("nostr_pubkey")
+            .and_then(|v| v.as_str())
+            .context("missing nostr_pubkey in metadata")?;
+        let event_id = message
+            .metadata
+            .get("nostr_event_id")
+            .and_then(|v| v.as_str())
+            .context("missing nostr_event_id in metadata")?;
+        let root_id = message
+            .metadata
+            .get("nostr_root_id")
+            .and_then(|v| v.as_str());
+        let is_dm = message
+            .metadata
+            .get("nostr_kind")
+            .and_then(|v| v.as_u64())
+            == Some(4);
+        Ok((pubkey, event_id, root_id, is_dm))
+    }
+
+    async fn reply(&self, message: &InboundMessage, text: &str) -> crate::Result<()> {
+        let (pubkey, event_id, root_id, is_dm) = Self::routing(message)?;
+        if is_dm {
+            self.send_dm(pubkey, text).await
+        } else {
+            self.send_reply(pubkey, event_id, root_id, text).await
+        }
+    }
+}
+
+impl Messaging for NostrAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        if self.relays.is_empty() {
+            return Err(anyhow::anyhow!("no nostr relays configured").into());
+        }
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, _) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx.clone());
+
+        for relay in self.relays.clone() {
+            let adapter = self.clone_for_task();
+            let inbound_tx = inbound_tx.clone();
+            let shutdown_tx = shutdown_tx.clone();
+            tokio::spawn(async move {
+                loop {
+                    if shutdown_tx.is_closed() {
+                        break;
+                    }
+                    match adapter.run_relay(&relay, &inbound_tx).await {
+                        Ok(()) => break,
+                        Err(error) => {
+                            tracing::warn!(%error, relay = %relay, "nostr relay disconnected, reconnecting in 10s");
+                            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                        }
+                    }
+                }
+            });
+        }
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.reply(message, &text).await,
+            OutboundResponse::File { caption, .. } => {
+                // No relay-native file transfer; send the caption if present
+                if let Some(caption) = caption {
+                    self.reply(message, &caption).await?;
+                }
+                Ok(())
+            }
+            OutboundResponse::Reaction(emoji) => {
+                // NIP-25 reaction to the triggering event
+                let (pubkey, event_id, _, _) = Self::routing(message)?;
+                let event = self.sign_event(
+                    7,
+                    vec![
+                        vec!["e".into(), event_id.into()],
+                        vec!["p".into(), pubkey.into()],
+                    ],
+                    emoji,
+                );
+                self.publish(&event).await
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Target is a recipient pubkey for a DM, or empty for a public note
+        if target.is_empty() {
+            for chunk in split_message(&text, MAX_NOTE_LENGTH) {
+                let event = self.sign_event(1, Vec::new(), chunk);
+                self.publish(&event).await?;
+            }
+            Ok(())
+        } else {
+            self.send_dm(target, &text).await
+        }
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        for relay in &self.relays {
+            if publish_probe(relay).await.is_ok() {
+                return Ok(());
+            }
+        }
+        Err(anyhow::anyhow!("no nostr relay reachable").into())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        // Dropping the sender signals relay loops via is_closed()
+        *self.shutdown_tx.write().await = None;
+        tracing::info!(adapter = %self.runtime_key, "nostr adapter shut down");
+        Ok(())
+    }
+}
+
+/// Open a relay connection, send one payload, and wait briefly for the ack.
+async fn publish_to_relay(relay: &str, payload: &str) -> crate::Result<()> {
+    let connect = tokio_tungstenite::connect_async(relay);
+    let (stream, _) = tokio::time::timeout(std::time::Duration::from_secs(10), connect)
+        .await
+        .context("relay connect timed out")?
+        .with_context(|| format!("failed to connect to relay {relay}"))?;
+    let (mut write, mut read) = stream.split();
+
+    write
+        .send(WsMessage::Text(payload.to_string().into()))
+        .await
+        .context("failed to send event to relay")?;
+
+    // Wait for OK (or anything) so the relay has a chance to ingest the event
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), read.next()).await;
+    write.send(WsMessage::Close(None)).await.ok();
+    Ok(())
+}
+
+/// Connectivity probe used by health checks.
+async fn publish_probe(relay: &str) -> crate::Result<()> {
+    let connect = tokio_tungstenite::connect_async(relay);
+    let (mut stream, _) = tokio::time::timeout(std::time::Duration::from_secs(5), connect)
+        .await
+        .context("relay connect timed out")?
+        .with_context(|| format!("failed to connect to relay {relay}"))?;
+    stream.close(None).await.ok();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Throwaway test key (do not use anywhere real)
+    const TEST_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+    const PEER_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+
+    #[test]
+    fn nip04_roundtrip() {
+        let bot = NostrAdapter::new("nostr", TEST_KEY, vec![]).unwrap();
+        let peer = NostrAdapter::new("nostr", PEER_KEY, vec![]).unwrap();
+
+        let encrypted = bot.encrypt_nip04(peer.pubkey_hex(), "hello nostr").unwrap();
+        assert!(encrypted.contains("?iv="));
+
+        let decrypted = peer.decrypt_nip04(bot.pubkey_hex(), &encrypted).unwrap();
+        assert_eq!(decrypted, "hello nostr");
+    }
+
+    #[test]
+    fn signed_events_verify_structurally() {
+        let bot = NostrAdapter::new("nostr", TEST_KEY, vec![]).unwrap();
+        let event = bot.sign_event(1, vec![], "test note".to_string());
+
+        assert_eq!(event.id.len(), 64);
+        assert_eq!(event.sig.len(), 128);
+        assert_eq!(event.pubkey, bot.pubkey_hex());
+    }
+
+    #[test]
+    fn rejects_invalid_secret_key() {
+        assert!(NostrAdapter::new("nostr", "not-hex", vec![]).is_err());
+        assert!(NostrAdapter::new("nostr", "abcd", vec![]).is_err());
+    }
+}
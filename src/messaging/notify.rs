@@ -0,0 +1,148 @@
+//! Outbound-only push notification adapters (ntfy, Pushover, Gotify).
+//!
+//! These register in the `MessagingManager` like any other adapter so cron
+//! jobs and broadcasts can target them by name, but they carry no inbound
+//! stream: `outbound_only()` returns true, the manager never starts them for
+//! inbound consumption, and they cannot appear in bindings. The broadcast
+//! target selects the destination where the service supports one (ntfy topic,
+//! Pushover user key); Gotify addresses a single app per token.
+
+use anyhow::Context as _;
+
+use crate::OutboundResponse;
+use crate::messaging::traits::{InboundStream, Messaging};
+
+/// Which push service an adapter instance delivers to.
+#[derive(Debug, Clone)]
+pub enum NotifyService {
+    /// ntfy.sh or a self-hosted ntfy server. The broadcast target overrides
+    /// the default topic when non-empty.
+    Ntfy {
+        server: String,
+        topic: String,
+        token: Option<String>,
+    },
+    /// Pushover. The broadcast target overrides the default user key when
+    /// non-empty.
+    Pushover { token: String, user: String },
+    /// Gotify. The token addresses one app; the broadcast target is used as
+    /// the message title when non-empty.
+    Gotify { server: String, token: String },
+}
+
+/// Outbound-only notification adapter.
+pub struct NotifyAdapter {
+    runtime_key: String,
+    service: NotifyService,
+    client: reqwest::Client,
+}
+
+impl NotifyAdapter {
+    pub fn new(runtime_key: impl Into<String>, service: NotifyService) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            service,
+            client: crate::http::client(),
+        }
+    }
+
+    /// Deliver a plain text notification to the service.
+    async fn push(&self, target: &str, text: &str) -> crate::Result<()> {
+        let target = target.trim();
+        match &self.service {
+            NotifyService::Ntfy {
+                server,
+                topic,
+                token,
+            } => {
+                let topic = if target.is_empty() { topic } else { target };
+                let url = format!("{}/{topic}", server.trim_end_matches('/'));
+                let mut request = self.client.post(&url).body(text.to_string());
+                if let Some(token) = token {
+                    request = request.bearer_auth(token);
+                }
+                let response = request.send().await.context("failed to send ntfy push")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(anyhow::anyhow!("ntfy push failed: HTTP {status}").into());
+                }
+            }
+            NotifyService::Pushover { token, user } => {
+                let user = if target.is_empty() { user } else { target };
+                let response = self
+                    .client
+                    .post("https://api.pushover.net/1/messages.json")
+                    .form(&[("token", token.as_str()), ("user", user), ("message", text)])
+                    .send()
+                    .await
+                    .context("failed to send Pushover push")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(anyhow::anyhow!("Pushover push failed: HTTP {status}").into());
+                }
+            }
+            NotifyService::Gotify { server, token } => {
+                let url = format!("{}/message", server.trim_end_matches('/'));
+                let mut body = serde_json::json!({ "message": text });
+                if !target.is_empty() {
+                    body["title"] = serde_json::Value::String(target.to_string());
+                }
+                let response = self
+                    .client
+                    .post(&url)
+                    .header("X-Gotify-Key", token)
+                    .json(&body)
+                    .send()
+                    .await
+                    .context("failed to send Gotify push")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(anyhow::anyhow!("Gotify push failed: HTTP {status}").into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Messaging for NotifyAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    fn outbound_only(&self) -> bool {
+        true
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Never called by the manager for outbound-only adapters; return an
+        // empty stream for completeness.
+        Ok(Box::pin(futures::stream::empty()))
+    }
+
+    async fn respond(
+        &self,
+        _message: &crate::InboundMessage,
+        _response: OutboundResponse,
+    ) -> crate::Result<()> {
+        Err(anyhow::anyhow!(
+            "'{}' is outbound-only and cannot respond to conversations",
+            self.runtime_key
+        )
+        .into())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. } => self.push(target, &text).await,
+            // File and interactive payloads have no push equivalent
+            _ => Ok(()),
+        }
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+}
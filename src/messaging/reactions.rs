@@ -0,0 +1,136 @@
+//! Shared reaction vocabulary translated to platform emoji sets.
+//!
+//! Agents emit one reaction intent — a plain name like "thumbs_up", a
+//! gemoji shortcode like ":tada:", or a raw unicode emoji — and each
+//! adapter renders the closest emoji its platform supports instead of
+//! silently failing on an unfamiliar form: Slack wants shortcodes,
+//! Discord wants unicode, and Telegram only accepts a fixed emoji set.
+
+/// Intent names accepted on top of the gemoji shortcode set, covering the
+/// names agents commonly reach for that the shortcodes don't use.
+const INTENT_ALIASES: &[(&str, &str)] = &[
+    ("thumbs_up", "👍"),
+    ("thumbsup", "👍"),
+    ("like", "👍"),
+    ("agree", "👍"),
+    ("thumbs_down", "👎"),
+    ("thumbsdown", "👎"),
+    ("dislike", "👎"),
+    ("love", "❤️"),
+    ("applause", "👏"),
+    ("laugh", "😂"),
+    ("celebrate", "🎉"),
+    ("party", "🎉"),
+    ("thanks", "🙏"),
+    ("looking", "👀"),
+    ("check", "✅"),
+    ("done", "✅"),
+    ("thinking", "🤔"),
+    ("sad", "😢"),
+    ("shocked", "😱"),
+    ("fire", "🔥"),
+];
+
+/// The fixed emoji set Telegram accepts as message reactions (Bot API
+/// `setMessageReaction`). Stored without variation selectors, which
+/// Telegram also omits.
+const TELEGRAM_REACTIONS: &[&str] = &[
+    "👍", "👎", "❤", "🔥", "🥰", "👏", "😁", "🤔", "🤯", "😱", "🤬", "😢", "🎉", "🤩", "🤮", "💩",
+    "🙏", "👌", "🕊", "🤡", "🥱", "🥴", "😍", "🐳", "🌚", "🌭", "💯", "🤣", "⚡", "🍌", "🏆", "💔",
+    "🤨", "😐", "🍓", "🍾", "💋", "🖕", "😈", "😴", "😭", "🤓", "👻", "👀", "🎃", "🙈", "😇", "😨",
+    "🤝", "🤗", "🫡", "🎅", "🎄", "💅", "🤪", "🗿", "🆒", "💘", "🙉", "🦄", "😘", "💊", "🙊", "😎",
+    "👾", "🤷", "😡",
+];
+
+/// Closest stand-ins for common reactions missing from Telegram's set.
+const TELEGRAM_FALLBACKS: &[(&str, &str)] = &[
+    ("✅", "👌"),
+    ("☑", "👌"),
+    ("🙂", "👍"),
+    ("😄", "😁"),
+    ("😊", "😁"),
+    ("😀", "😁"),
+    ("😂", "🤣"),
+    ("🥳", "🎉"),
+    ("🚀", "🔥"),
+    ("💪", "🔥"),
+    ("👋", "🤝"),
+    ("⭐", "🤩"),
+    ("🌟", "🤩"),
+    ("😮", "😱"),
+    ("😲", "😱"),
+    ("☹", "😢"),
+    ("😞", "😢"),
+];
+
+/// Resolve a reaction intent to a unicode emoji.
+///
+/// Accepts raw unicode, `:shortcode:` or bare gemoji shortcodes, and the
+/// intent aliases above. Returns `None` for names nothing recognizes.
+pub fn to_unicode(reaction: &str) -> Option<&'static str> {
+    let trimmed = reaction.trim();
+    if let Some(emoji) = emojis::get(trimmed) {
+        return Some(emoji.as_str());
+    }
+    let name = trimmed
+        .trim_start_matches(':')
+        .trim_end_matches(':')
+        .to_lowercase();
+    if let Some((_, unicode)) = INTENT_ALIASES.iter().find(|(alias, _)| *alias == name) {
+        return Some(unicode);
+    }
+    emojis::get_by_shortcode(&name).map(|emoji| emoji.as_str())
+}
+
+/// Resolve a reaction intent to a Slack shortcode, when one exists.
+pub fn to_slack_shortcode(reaction: &str) -> Option<&'static str> {
+    let unicode = to_unicode(reaction)?;
+    emojis::get(unicode).and_then(|emoji| emoji.shortcode())
+}
+
+/// Resolve a reaction intent to an emoji Telegram accepts: the exact emoji
+/// when it is in Telegram's reaction set, otherwise the closest supported
+/// stand-in. Returns `None` when the intent itself is unrecognized.
+pub fn to_telegram_emoji(reaction: &str) -> Option<String> {
+    let unicode = to_unicode(reaction)?;
+    // Telegram's set omits variation selectors (e.g. "❤" not "❤️").
+    let stripped: String = unicode.chars().filter(|c| *c != '\u{fe0f}').collect();
+    if TELEGRAM_REACTIONS.contains(&stripped.as_str()) {
+        return Some(stripped);
+    }
+    TELEGRAM_FALLBACKS
+        .iter()
+        .find(|(from, _)| *from == stripped)
+        .map(|(_, fallback)| (*fallback).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intent_aliases_resolve_to_unicode() {
+        assert_eq!(to_unicode("like"), Some("👍"));
+        assert_eq!(to_unicode("celebrate"), Some("🎉"));
+        assert_eq!(to_unicode(":tada:"), Some("🎉"));
+        assert_eq!(to_unicode("🔥"), Some("🔥"));
+        assert_eq!(to_unicode("not_a_reaction"), None);
+    }
+
+    #[test]
+    fn slack_shortcodes_come_from_gemoji() {
+        assert_eq!(to_slack_shortcode("like"), Some("+1"));
+        assert_eq!(to_slack_shortcode("celebrate"), Some("tada"));
+    }
+
+    #[test]
+    fn telegram_maps_to_supported_set() {
+        // Exact member of the set, with the variation selector stripped.
+        assert_eq!(to_telegram_emoji("❤️").as_deref(), Some("❤"));
+        assert_eq!(to_telegram_emoji("like").as_deref(), Some("👍"));
+        // Unsupported reactions fall back to the closest stand-in.
+        assert_eq!(to_telegram_emoji("check").as_deref(), Some("👌"));
+        assert_eq!(to_telegram_emoji("rocket").as_deref(), Some("🔥"));
+        assert_eq!(to_telegram_emoji("not_a_reaction"), None);
+    }
+}
@@ -0,0 +1,579 @@
+//! Reddit messaging adapter.
+//!
+//! Polls the bot account's inbox (comment replies, username mentions,
+//! private messages) and optionally the new-post feed of watched
+//! subreddits. Authentication uses the OAuth2 password grant for script
+//! apps, with the bearer token refreshed automatically before expiry.
+//! Conversations are keyed by thread: every comment under the same link
+//! lands in one conversation, and private message threads map to their
+//! first message.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+const REDDIT_OAUTH_BASE: &str = "https://oauth.reddit.com";
+const REDDIT_TOKEN_URL: &str = "https://www.reddit.com/api/v1/access_token";
+
+/// Reddit comments cap out at 10000 characters.
+const MAX_MESSAGE_LENGTH: usize = 9_500;
+
+/// A cached bearer token with its expiry.
+struct CachedToken {
+    access_token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Reddit adapter state.
+pub struct RedditAdapter {
+    runtime_key: String,
+    client_id: String,
+    client_secret: String,
+    username: String,
+    password: String,
+    /// Subreddit names (without `r/`) whose new posts are ingested.
+    subreddits: Vec<String>,
+    poll_interval_secs: u64,
+    user_agent: String,
+    client: reqwest::Client,
+    token: Arc<RwLock<Option<CachedToken>>>,
+    /// Fullnames already forwarded, so poll overlap doesn't duplicate.
+    seen: Arc<Mutex<HashSet<String>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl RedditAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+        subreddits: Vec<String>,
+        poll_interval_secs: u64,
+    ) -> Self {
+        let username = username.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            user_agent: format!("spacebot/{} (by /u/{username})", env!("CARGO_PKG_VERSION")),
+            username,
+            password: password.into(),
+            subreddits,
+            poll_interval_secs: poll_interval_secs.max(15),
+            client: crate::http::client(),
+            token: Arc::new(RwLock::new(None)),
+            seen: Arc::new(Mutex::new(HashSet::new())),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get a bearer token, refreshing via the password grant when the cached
+    /// one is near expiry.
+    async fn access_token(&self) -> crate::Result<String> {
+        {
+            let token = self.token.read().await;
+            if let Some(cached) = token.as_ref()
+                && cached.expires_at > chrono::Utc::now() + chrono::Duration::seconds(120)
+            {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .post(REDDIT_TOKEN_URL)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .form(&[
+                ("grant_type", "password"),
+                ("username", self.username.as_str()),
+                ("password", self.password.as_str()),
+            ])
+            .send()
+            .await
+            .context("failed to request Reddit access token")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Reddit token request failed: HTTP {status}: {body}").into());
+        }
+
+        #[derive(serde::Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            expires_in: i64,
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("invalid Reddit token response")?;
+
+        let access_token = token.access_token.clone();
+        *self.token.write().await = Some(CachedToken {
+            access_token: token.access_token,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(token.expires_in),
+        });
+        Ok(access_token)
+    }
+
+    async fn api_get(&self, path: &str) -> crate::Result<serde_json::Value> {
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .get(format!("{REDDIT_OAUTH_BASE}{path}"))
+            .bearer_auth(&token)
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .send()
+            .await
+            .with_context(|| format!("failed to call Reddit {path}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Reddit {path} failed: HTTP {status}").into());
+        }
+        response
+            .json()
+            .await
+            .map_err(|error| anyhow::anyhow!("invalid Reddit response from {path}: {error}").into())
+    }
+
+    async fn api_post(&self, path: &str, form: &[(&str, &str)]) -> crate::Result<()> {
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .post(format!("{REDDIT_OAUTH_BASE}{path}"))
+            .bearer_auth(&token)
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .form(form)
+            .send()
+            .await
+            .with_context(|| format!("failed to call Reddit {path}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Reddit {path} failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    /// Reply under a comment, post, or private message.
+    async fn reply_to(&self, parent_fullname: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.api_post(
+                "/api/comment",
+                &[("api_type", "json"), ("thing_id", parent_fullname), ("text", &chunk)],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// One inbox poll: fetch unread items, forward them, mark them read.
+    async fn poll_inbox(&self, inbound_tx: &mpsc::Sender<InboundMessage>) -> crate::Result<()> {
+        let body = self.api_get("/message/unread?limit=50").await?;
+        let Some(children) = body["data"]["children"].as_array() else {
+            return Ok(());
+        };
+
+        let mut read_ids = Vec::new();
+        for child in children {
+            let kind = child["kind"].as_str().unwrap_or_default();
+            let data = &child["data"];
+            let Some(fullname) = data["name"].as_str() else {
+                continue;
+            };
+            read_ids.push(fullname.to_string());
+
+            if !self.mark_seen(fullname).await {
+                continue;
+            }
+            if let Some(inbound) = self.parse_inbox_item(kind, data)
+                && inbound_tx.send(inbound).await.is_err()
+            {
+                return Ok(());
+            }
+        }
+
+        if !read_ids.is_empty() {
+            let id_list = read_ids.join(",");
+            self.api_post("/api/read_message", &[("id", &id_list)])
+                .await
+                .ok();
+        }
+        Ok(())
+    }
+
+    /// One subreddit poll: forward new posts from each watched subreddit.
+    async fn poll_subreddits(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+        first_pass: bool,
+    ) -> crate::Result<()> {
+        for subreddit in &self.subreddits {
+            let body = self
+                .api_get(&format!("/r/{subreddit}/new?limit=25"))
+                .await?;
+            let Some(children) = body["data"]["children"].as_array() else {
+                continue;
+            };
+            for child in children {
+                let data = &child["data"];
+                let Some(fullname) = data["name"].as_str() else {
+                    continue;
+                };
+                if !self.mark_seen(fullname).await {
+                    continue;
+                }
+                // The first pass only primes the dedup set; replying to the
+                // backlog of an active subreddit would be noise
+                if first_pass {
+                    continue;
+                }
+                if let Some(inbound) = self.parse_post(subreddit, data)
+                    && inbound_tx.send(inbound).await.is_err()
+                {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a fullname as seen; returns false if it already was.
+    async fn mark_seen(&self, fullname: &str) -> bool {
+        let mut seen = self.seen.lock().await;
+        if seen.len() > 8_192 {
+            seen.clear();
+        }
+        seen.insert(fullname.to_string())
+    }
+
+    /// Convert an unread inbox item (t1 comment, t4 private message) into an
+    /// inbound message.
+    fn parse_inbox_item(&self, kind: &str, data: &serde_json::Value) -> Option<InboundMessage> {
+        let author = data["author"].as_str()?.to_string();
+        if author.eq_ignore_ascii_case(&self.username) {
+            return None;
+        }
+        let body = data["body"].as_str()?.trim().to_string();
+        if body.is_empty() {
+            return None;
+        }
+        let fullname = data["name"].as_str()?.to_string();
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "reddit_fullname".into(),
+            serde_json::Value::String(fullname.clone()),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(format!("u/{author}")),
+        );
+
+        // Comments carry the link they belong to; private messages thread on
+        // their first message
+        let conversation_id = if kind == "t1" {
+            let link_id = data["link_id"].as_str().unwrap_or(&fullname).to_string();
+            metadata.insert(
+                "reddit_link_id".into(),
+                serde_json::Value::String(link_id.clone()),
+            );
+            if let Some(subreddit) = data["subreddit"].as_str() {
+                metadata.insert(
+                    "reddit_subreddit".into(),
+                    serde_json::Value::String(subreddit.to_string()),
+                );
+            }
+            format!("reddit:{link_id}")
+        } else {
+            let thread_id = data["first_message_name"]
+                .as_str()
+                .unwrap_or(&fullname)
+                .to_string();
+            metadata.insert(
+                "reddit_thread_id".into(),
+                serde_json::Value::String(thread_id.clone()),
+            );
+            format!("reddit:pm:{thread_id}")
+        };
+
+        let timestamp = data["created_utc"]
+            .as_f64()
+            .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+            .unwrap_or_else(chrono::Utc::now);
+
+        Some(InboundMessage {
+            id: fullname,
+            source: "reddit".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: author.clone(),
+            agent_id: None,
+            content: MessageContent::Text(body),
+            timestamp,
+            metadata,
+            formatted_author: Some(format!("u/{author}")),
+        })
+    }
+
+    /// Convert a new subreddit post into an inbound message.
+    fn parse_post(&self, subreddit: &str, data: &serde_json::Value) -> Option<InboundMessage> {
+        let author = data["author"].as_str()?.to_string();
+        if author.eq_ignore_ascii_case(&self.username) {
+            return None;
+        }
+        let fullname = data["name"].as_str()?.to_string();
+        let title = data["title"].as_str().unwrap_or_default();
+        let selftext = data["selftext"].as_str().unwrap_or_default().trim();
+        let text = if selftext.is_empty() {
+            let url = data["url"].as_str().unwrap_or_default();
+            format!("{title}\n{url}").trim().to_string()
+        } else {
+            format!("{title}\n\n{selftext}")
+        };
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "reddit_fullname".into(),
+            serde_json::Value::String(fullname.clone()),
+        );
+        metadata.insert(
+            "reddit_link_id".into(),
+            serde_json::Value::String(fullname.clone()),
+        );
+        metadata.insert(
+            "reddit_subreddit".into(),
+            serde_json::Value::String(subreddit.to_string()),
+        );
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(format!("u/{author}")),
+        );
+
+        let timestamp = data["created_utc"]
+            .as_f64()
+            .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+            .unwrap_or_else(chrono::Utc::now);
+
+        Some(InboundMessage {
+            id: fullname.clone(),
+            source: "reddit".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id: format!("reddit:{fullname}"),
+            sender_id: author.clone(),
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp,
+            metadata,
+            formatted_author: Some(format!("u/{author}")),
+        })
+    }
+
+    /// The fullname replies should attach to.
+    fn routing(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("reddit_fullname")
+            .and_then(|v| v.as_str())
+            .context("missing reddit_fullname in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for RedditAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Validate credentials up front so bad config surfaces at startup
+        self.access_token().await?;
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = RedditAdapter {
+            runtime_key: self.runtime_key.clone(),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+            subreddits: self.subreddits.clone(),
+            poll_interval_secs: self.poll_interval_secs,
+            user_agent: self.user_agent.clone(),
+            client: self.client.clone(),
+            token: self.token.clone(),
+            seen: self.seen.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        };
+
+        tokio::spawn(async move {
+            let mut first_pass = true;
+            loop {
+                if let Err(error) = adapter.poll_inbox(&inbound_tx).await {
+                    tracing::warn!(%error, "Reddit inbox poll failed");
+                }
+                if !adapter.subreddits.is_empty()
+                    && let Err(error) = adapter.poll_subreddits(&inbound_tx, first_pass).await
+                {
+                    tracing::warn!(%error, "Reddit subreddit poll failed");
+                }
+                first_pass = false;
+
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(adapter.poll_interval_secs)) => {}
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("Reddit poll loop stopped");
+                        break;
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let parent = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.reply_to(parent, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // No media upload over the comment API; describe the file
+                let note = match caption {
+                    Some(caption) => format!("{caption} *(attachment omitted: {filename})*"),
+                    None => format!("*(attachment omitted: {filename})*"),
+                };
+                self.reply_to(parent, &note).await
+            }
+            // Reddit has votes, not reactions; don't vote on behalf of users
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // A thing fullname gets a comment; a bare name is a subreddit post
+        if target.starts_with("t1_") || target.starts_with("t3_") || target.starts_with("t4_") {
+            return self.reply_to(target, &text).await;
+        }
+
+        let subreddit = target.trim_start_matches("r/");
+        let (title, body) = match text.split_once('\n') {
+            Some((title, body)) => (title.trim(), body.trim()),
+            None => (text.as_str(), ""),
+        };
+        self.api_post(
+            "/api/submit",
+            &[
+                ("api_type", "json"),
+                ("sr", subreddit),
+                ("kind", "self"),
+                ("title", title),
+                ("text", body),
+            ],
+        )
+        .await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.access_token().await.map(|_| ())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Reddit adapter shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter() -> RedditAdapter {
+        RedditAdapter::new(
+            "reddit",
+            "id",
+            "secret",
+            "spacebot",
+            "hunter2",
+            vec!["rust".to_string()],
+            30,
+        )
+    }
+
+    #[test]
+    fn comment_replies_thread_on_their_link() {
+        let data = serde_json::json!({
+            "name": "t1_abc",
+            "author": "alice",
+            "body": "nice bot",
+            "link_id": "t3_xyz",
+            "subreddit": "rust",
+            "created_utc": 1_700_000_000.0,
+        });
+        let inbound = adapter().parse_inbox_item("t1", &data).unwrap();
+        assert_eq!(inbound.conversation_id, "reddit:t3_xyz");
+        assert_eq!(
+            inbound.metadata.get("reddit_fullname").and_then(|v| v.as_str()),
+            Some("t1_abc")
+        );
+    }
+
+    #[test]
+    fn private_messages_thread_on_first_message() {
+        let data = serde_json::json!({
+            "name": "t4_def",
+            "author": "bob",
+            "body": "hello",
+            "first_message_name": "t4_aaa",
+        });
+        let inbound = adapter().parse_inbox_item("t4", &data).unwrap();
+        assert_eq!(inbound.conversation_id, "reddit:pm:t4_aaa");
+    }
+
+    #[test]
+    fn own_activity_is_ignored() {
+        let data = serde_json::json!({
+            "name": "t1_own",
+            "author": "Spacebot",
+            "body": "echo",
+            "link_id": "t3_xyz",
+        });
+        assert!(adapter().parse_inbox_item("t1", &data).is_none());
+    }
+}
@@ -0,0 +1,640 @@
+//! Rocket.Chat messaging adapter.
+//!
+//! Realtime receive runs over the DDP WebSocket API with a bot-user login
+//! (sha-256 password digest) and a `stream-room-messages` subscription on the
+//! `__my_messages__` firehose, filtered by the channel allowlist. Outbound
+//! traffic uses the REST API (`chat.postMessage`, `rooms.upload`,
+//! `chat.react`) with a separate REST login, which keeps sends working while
+//! the socket reconnects.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Context as _;
+use futures::{SinkExt as _, StreamExt as _};
+use serde_json::json;
+use sha2::Digest as _;
+use tokio::sync::{RwLock, mpsc};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Rocket.Chat truncates messages around 5000 characters by default.
+const MAX_MESSAGE_LENGTH: usize = 4_500;
+
+/// REST session obtained from `/api/v1/login`.
+struct RestAuth {
+    token: String,
+    user_id: String,
+}
+
+/// Rocket.Chat adapter state.
+pub struct RocketChatAdapter {
+    runtime_key: String,
+    /// Server base URL, e.g. `https://chat.example.com` (no trailing slash).
+    base_url: String,
+    username: String,
+    password: String,
+    /// Room names to accept messages from. Empty means all rooms the bot is in.
+    channels: Vec<String>,
+    client: reqwest::Client,
+    auth: Arc<RwLock<Option<RestAuth>>>,
+    /// DDP method call ID counter.
+    ddp_id: Arc<AtomicU64>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl RocketChatAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+        channels: Vec<String>,
+    ) -> Self {
+        let base_url = base_url.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            username: username.into(),
+            password: password.into(),
+            channels,
+            client: crate::http::client(),
+            auth: Arc::new(RwLock::new(None)),
+            ddp_id: Arc::new(AtomicU64::new(1)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn api_url(&self, endpoint: &str) -> String {
+        format!("{}/api/v1/{endpoint}", self.base_url)
+    }
+
+    fn websocket_url(&self) -> String {
+        let ws_base = self
+            .base_url
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1);
+        format!("{ws_base}/websocket")
+    }
+
+    /// Log in over REST, caching the auth token and user ID.
+    async fn rest_login(&self) -> crate::Result<()> {
+        let response = self
+            .client
+            .post(self.api_url("login"))
+            .json(&json!({ "user": self.username, "password": self.password }))
+            .send()
+            .await
+            .context("failed to reach Rocket.Chat login")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Rocket.Chat login failed: HTTP {status}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("invalid Rocket.Chat login response")?;
+        let token = body["data"]["authToken"]
+            .as_str()
+            .context("missing authToken in Rocket.Chat login response")?
+            .to_string();
+        let user_id = body["data"]["userId"]
+            .as_str()
+            .context("missing userId in Rocket.Chat login response")?
+            .to_string();
+
+        *self.auth.write().await = Some(RestAuth { token, user_id });
+        Ok(())
+    }
+
+    /// Build an authenticated REST request, logging in first if needed.
+    async fn authed_post(&self, endpoint: &str) -> crate::Result<reqwest::RequestBuilder> {
+        if self.auth.read().await.is_none() {
+            self.rest_login().await?;
+        }
+        let auth = self.auth.read().await;
+        let auth = auth.as_ref().context("Rocket.Chat REST login missing")?;
+        Ok(self
+            .client
+            .post(self.api_url(endpoint))
+            .header("X-Auth-Token", &auth.token)
+            .header("X-User-Id", &auth.user_id))
+    }
+
+    async fn post_message(
+        &self,
+        room_id: &str,
+        thread_id: Option<&str>,
+        text: &str,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let mut payload = json!({ "roomId": room_id, "text": chunk });
+            if let Some(thread_id) = thread_id {
+                payload["tmid"] = json!(thread_id);
+            }
+            let response = self
+                .authed_post("chat.postMessage")
+                .await?
+                .json(&payload)
+                .send()
+                .await
+                .context("failed to post Rocket.Chat message")?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(
+                    anyhow::anyhow!("Rocket.Chat post failed: HTTP {status}: {body}").into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn upload_file(
+        &self,
+        room_id: &str,
+        thread_id: Option<&str>,
+        filename: &str,
+        data: bytes::Bytes,
+        mime_type: &str,
+        caption: Option<String>,
+    ) -> crate::Result<()> {
+        let length = data.len() as u64;
+        let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::from(data), length)
+            .file_name(filename.to_string())
+            .mime_str(mime_type)
+            .context("invalid mime type for Rocket.Chat upload")?;
+        let mut form = reqwest::multipart::Form::new().part("file", part);
+        if let Some(caption) = caption {
+            form = form.text("msg", caption);
+        }
+        if let Some(thread_id) = thread_id {
+            form = form.text("tmid", thread_id.to_string());
+        }
+
+        let response = self
+            .authed_post(&format!("rooms.upload/{room_id}"))
+            .await?
+            .multipart(form)
+            .send()
+            .await
+            .context("failed to upload file to Rocket.Chat")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Rocket.Chat upload failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    async fn react(&self, message_id: &str, emoji: &str, should_react: bool) -> crate::Result<()> {
+        let emoji = format!(":{}:", emoji.trim_matches(':'));
+        let response = self
+            .authed_post("chat.react")
+            .await?
+            .json(&json!({ "messageId": message_id, "emoji": emoji, "shouldReact": should_react }))
+            .send()
+            .await
+            .context("failed to react to Rocket.Chat message")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Rocket.Chat react failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    /// Room and thread routing from inbound metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, Option<&str>)> {
+        let room_id = message
+            .metadata
+            .get("rocketchat_room_id")
+            .and_then(|v| v.as_str())
+            .context("missing rocketchat_room_id in metadata")?;
+        let thread_id = message
+            .metadata
+            .get("rocketchat_thread_id")
+            .and_then(|v| v.as_str());
+        Ok((room_id, thread_id))
+    }
+
+    /// Run the DDP websocket loop once; returns on disconnect.
+    async fn run_ddp(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+    ) -> crate::Result<()> {
+        let url = self.websocket_url();
+        let (socket, _) = tokio_tungstenite::connect_async(&url)
+            .await
+            .with_context(|| format!("failed to connect to Rocket.Chat websocket at {url}"))?;
+        let (mut write, mut read) = socket.split();
+
+        write
+            .send(WsMessage::text(
+                json!({ "msg": "connect", "version": "1", "support": ["1"] }).to_string(),
+            ))
+            .await
+            .context("failed to send DDP connect")?;
+
+        // Bot-user login with a sha-256 password digest
+        let digest = hex::encode(sha2::Sha256::digest(self.password.as_bytes()));
+        let login_id = self.ddp_id.fetch_add(1, Ordering::Relaxed).to_string();
+        write
+            .send(WsMessage::text(
+                json!({
+                    "msg": "method",
+                    "method": "login",
+                    "id": login_id,
+                    "params": [{
+                        "user": { "username": self.username },
+                        "password": { "digest": digest, "algorithm": "sha-256" },
+                    }],
+                })
+                .to_string(),
+            ))
+            .await
+            .context("failed to send DDP login")?;
+
+        let mut own_user_id: Option<String> = None;
+
+        loop {
+            let frame = tokio::select! {
+                frame = read.next() => frame,
+                _ = shutdown_rx.recv() => return Ok(()),
+            };
+            let Some(frame) = frame else {
+                return Err(anyhow::anyhow!("Rocket.Chat websocket closed").into());
+            };
+            let frame = frame.context("Rocket.Chat websocket error")?;
+            let WsMessage::Text(text) = frame else {
+                continue;
+            };
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(&text) else {
+                continue;
+            };
+
+            match event.get("msg").and_then(|m| m.as_str()) {
+                Some("ping") => {
+                    write
+                        .send(WsMessage::text(json!({ "msg": "pong" }).to_string()))
+                        .await
+                        .context("failed to send DDP pong")?;
+                }
+                Some("result") if event.get("id").and_then(|i| i.as_str()) == Some(&login_id) => {
+                    if let Some(error) = event.get("error") {
+                        return Err(
+                            anyhow::anyhow!("Rocket.Chat DDP login failed: {error}").into()
+                        );
+                    }
+                    own_user_id = event["result"]["id"].as_str().map(str::to_string);
+                    tracing::info!("Rocket.Chat DDP login succeeded");
+
+                    // Firehose subscription filtered client-side by allowlist
+                    let sub_id = self.ddp_id.fetch_add(1, Ordering::Relaxed).to_string();
+                    write
+                        .send(WsMessage::text(
+                            json!({
+                                "msg": "sub",
+                                "id": sub_id,
+                                "name": "stream-room-messages",
+                                "params": ["__my_messages__", false],
+                            })
+                            .to_string(),
+                        ))
+                        .await
+                        .context("failed to subscribe to room messages")?;
+                }
+                Some("changed")
+                    if event.get("collection").and_then(|c| c.as_str())
+                        == Some("stream-room-messages") =>
+                {
+                    if let Some(inbound) =
+                        self.parse_room_message(&event, own_user_id.as_deref())
+                        && inbound_tx.send(inbound).await.is_err()
+                    {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Convert a `stream-room-messages` event into an inbound message.
+    fn parse_room_message(
+        &self,
+        event: &serde_json::Value,
+        own_user_id: Option<&str>,
+    ) -> Option<InboundMessage> {
+        let args = event["fields"]["args"].as_array()?;
+        let message = args.first()?;
+        let room_meta = args.get(1);
+
+        // System messages (joins, topic changes) carry a type field
+        if message.get("t").is_some() {
+            return None;
+        }
+
+        let sender_id = message["u"]["_id"].as_str()?.to_string();
+        if Some(sender_id.as_str()) == own_user_id {
+            return None;
+        }
+
+        let room_id = message["rid"].as_str()?.to_string();
+        let room_name = room_meta
+            .and_then(|meta| meta["roomName"].as_str())
+            .map(str::to_string);
+
+        if !self.channels.is_empty() {
+            let Some(room_name) = room_name.as_deref() else {
+                // DMs have no room name; always accept them
+                return self.build_inbound(message, &sender_id, &room_id, None);
+            };
+            if !self.channels.iter().any(|c| c == room_name) {
+                tracing::debug!(room = %room_name, "ignoring Rocket.Chat message from non-allowlisted room");
+                return None;
+            }
+        }
+
+        self.build_inbound(message, &sender_id, &room_id, room_name.as_deref())
+    }
+
+    fn build_inbound(
+        &self,
+        message: &serde_json::Value,
+        sender_id: &str,
+        room_id: &str,
+        room_name: Option<&str>,
+    ) -> Option<InboundMessage> {
+        let text = message["msg"].as_str()?.trim().to_string();
+        if text.is_empty() {
+            return None;
+        }
+
+        let username = message["u"]["username"]
+            .as_str()
+            .unwrap_or(sender_id)
+            .to_string();
+        let display_name = message["u"]["name"]
+            .as_str()
+            .unwrap_or(&username)
+            .to_string();
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "rocketchat_room_id".into(),
+            serde_json::Value::String(room_id.to_string()),
+        );
+        if let Some(room_name) = room_name {
+            metadata.insert(
+                "rocketchat_room_name".into(),
+                serde_json::Value::String(room_name.to_string()),
+            );
+        }
+        if let Some(message_id) = message["_id"].as_str() {
+            metadata.insert(
+                "rocketchat_message_id".into(),
+                serde_json::Value::String(message_id.to_string()),
+            );
+        }
+        if let Some(thread_id) = message["tmid"].as_str() {
+            metadata.insert(
+                "rocketchat_thread_id".into(),
+                serde_json::Value::String(thread_id.to_string()),
+            );
+        }
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(display_name.clone()),
+        );
+
+        let timestamp = message["ts"]["$date"]
+            .as_i64()
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .unwrap_or_else(chrono::Utc::now);
+
+        Some(InboundMessage {
+            id: message["_id"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            source: "rocketchat".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id: format!("rocketchat:{room_id}"),
+            sender_id: sender_id.to_string(),
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp,
+            metadata,
+            formatted_author: Some(display_name),
+        })
+    }
+}
+
+impl Messaging for RocketChatAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        // Validate credentials up front so bad config surfaces at startup
+        self.rest_login().await?;
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = RocketChatAdapter {
+            runtime_key: self.runtime_key.clone(),
+            base_url: self.base_url.clone(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+            channels: self.channels.clone(),
+            client: self.client.clone(),
+            auth: self.auth.clone(),
+            ddp_id: self.ddp_id.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        };
+
+        tokio::spawn(async move {
+            loop {
+                match adapter.run_ddp(&inbound_tx, &mut shutdown_rx).await {
+                    Ok(()) => {
+                        tracing::info!("Rocket.Chat DDP loop stopped");
+                        break;
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "Rocket.Chat DDP disconnected, reconnecting in 5s");
+                        tokio::select! {
+                            _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {}
+                            _ = shutdown_rx.recv() => break,
+                        }
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (room_id, thread_id) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.post_message(room_id, thread_id, &text).await
+            }
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.upload_file(room_id, thread_id, &filename, data, &mime_type, caption)
+                    .await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let message_id = message
+                    .metadata
+                    .get("rocketchat_message_id")
+                    .and_then(|v| v.as_str())
+                    .context("missing rocketchat_message_id in metadata")?;
+                self.react(message_id, &emoji, true).await
+            }
+            OutboundResponse::RemoveReaction(emoji) => {
+                let message_id = message
+                    .metadata
+                    .get("rocketchat_message_id")
+                    .and_then(|v| v.as_str())
+                    .context("missing rocketchat_message_id in metadata")?;
+                self.react(message_id, &emoji, false).await
+            }
+            OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        // Accept a raw room ID or a #channel-name
+        if let Some(channel_name) = target.strip_prefix('#') {
+            for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+                let response = self
+                    .authed_post("chat.postMessage")
+                    .await?
+                    .json(&json!({ "channel": format!("#{channel_name}"), "text": chunk }))
+                    .send()
+                    .await
+                    .context("failed to broadcast Rocket.Chat message")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(
+                        anyhow::anyhow!("Rocket.Chat broadcast failed: HTTP {status}").into(),
+                    );
+                }
+            }
+            return Ok(());
+        }
+        self.post_message(target, None, &text).await
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        let (room_id, _) = Self::routing(message)?;
+        if self.auth.read().await.is_none() {
+            self.rest_login().await?;
+        }
+        let auth = self.auth.read().await;
+        let auth = auth.as_ref().context("Rocket.Chat REST login missing")?;
+
+        let response = self
+            .client
+            .get(self.api_url("channels.history"))
+            .header("X-Auth-Token", &auth.token)
+            .header("X-User-Id", &auth.user_id)
+            .query(&[("roomId", room_id), ("count", &limit.to_string())])
+            .send()
+            .await
+            .context("failed to fetch Rocket.Chat history")?;
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("invalid Rocket.Chat history response")?;
+        let own_user_id = &auth.user_id;
+
+        let mut history: Vec<HistoryMessage> = body["messages"]
+            .as_array()
+            .map(|messages| {
+                messages
+                    .iter()
+                    .filter(|m| m.get("t").is_none())
+                    .filter_map(|m| {
+                        let author = m["u"]["username"].as_str()?.to_string();
+                        let content = m["msg"].as_str()?.to_string();
+                        let is_bot = m["u"]["_id"].as_str() == Some(own_user_id)
+                            || m.get("bot").is_some();
+                        Some(HistoryMessage {
+                            author,
+                            content,
+                            is_bot,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // API returns newest first; callers expect chronological order
+        history.reverse();
+        Ok(history)
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let response = self
+            .client
+            .get(self.api_url("info"))
+            .send()
+            .await
+            .context("Rocket.Chat server unreachable")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Rocket.Chat health check failed: HTTP {status}").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Rocket.Chat adapter shut down");
+        Ok(())
+    }
+}
+
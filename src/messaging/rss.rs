@@ -0,0 +1,419 @@
+//! RSS/Atom feed ingestion adapter.
+//!
+//! A read-only pseudo-adapter: each configured feed is polled on an
+//! interval and new entries are emitted as inbound messages so bindings
+//! can summarize or react to them. The first fetch primes the seen-entry
+//! set, so existing backlog is not replayed on startup. Outbound
+//! responses have nowhere to go and are dropped.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use tokio::sync::{RwLock, mpsc, watch};
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Entry IDs remembered per feed before the oldest are forgotten.
+const SEEN_ENTRIES_CAPACITY: usize = 1000;
+
+/// A feed the adapter polls.
+#[derive(Debug, Clone)]
+pub struct RssFeed {
+    /// Short name used in conversation IDs, e.g. `hn`.
+    pub name: String,
+    pub url: String,
+}
+
+/// RSS/Atom adapter state.
+#[derive(Clone)]
+pub struct RssAdapter {
+    runtime_key: String,
+    feeds: Vec<RssFeed>,
+    /// Seconds between fetches of each feed.
+    poll_interval_secs: u64,
+    client: reqwest::Client,
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
+}
+
+impl RssAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        feeds: Vec<RssFeed>,
+        poll_interval_secs: u64,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            feeds,
+            poll_interval_secs: poll_interval_secs.max(30),
+            client: crate::http::client(),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Fetch a feed and parse its entries, newest state wins.
+    async fn fetch_entries(&self, feed: &RssFeed) -> crate::Result<Vec<FeedEntry>> {
+        let response = self
+            .client
+            .get(&feed.url)
+            .header(reqwest::header::USER_AGENT, "spacebot-rss")
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch feed {}", feed.name))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("feed {} returned HTTP {status}", feed.name).into());
+        }
+        let body = response
+            .text()
+            .await
+            .with_context(|| format!("failed to read feed {}", feed.name))?;
+        Ok(parse_feed(&body))
+    }
+}
+
+impl Messaging for RssAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        for feed in self.feeds.clone() {
+            let adapter = self.clone();
+            let inbound_tx = inbound_tx.clone();
+            let mut shutdown_rx = shutdown_rx.clone();
+            tokio::spawn(async move {
+                // Prime with the current backlog so only future entries emit.
+                let mut seen: HashSet<String> = HashSet::new();
+                let mut seen_order: Vec<String> = Vec::new();
+                match adapter.fetch_entries(&feed).await {
+                    Ok(entries) => {
+                        for entry in entries {
+                            if seen.insert(entry.id.clone()) {
+                                seen_order.push(entry.id);
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, feed = %feed.name, "initial feed fetch failed");
+                    }
+                }
+
+                let interval = std::time::Duration::from_secs(adapter.poll_interval_secs);
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep(interval) => {}
+                        _ = shutdown_rx.changed() => {
+                            tracing::debug!(feed = %feed.name, "rss feed poller stopping");
+                            return;
+                        }
+                    }
+
+                    let entries = match adapter.fetch_entries(&feed).await {
+                        Ok(entries) => entries,
+                        Err(error) => {
+                            tracing::warn!(%error, feed = %feed.name, "feed fetch failed; will retry");
+                            continue;
+                        }
+                    };
+
+                    for entry in entries {
+                        if !seen.insert(entry.id.clone()) {
+                            continue;
+                        }
+                        seen_order.push(entry.id.clone());
+                        while seen_order.len() > SEEN_ENTRIES_CAPACITY {
+                            seen.remove(&seen_order.remove(0));
+                        }
+
+                        let inbound = entry.into_inbound(&adapter.runtime_key, &feed.name);
+                        if inbound_tx.send(inbound).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        _message: &InboundMessage,
+        _response: OutboundResponse,
+    ) -> crate::Result<()> {
+        // Feeds are read-only; responses are routed elsewhere by bindings.
+        tracing::debug!("dropping outbound response on read-only rss adapter");
+        Ok(())
+    }
+
+    async fn broadcast(&self, _target: &str, _response: OutboundResponse) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        let Some(feed) = self.feeds.first() else {
+            return Ok(());
+        };
+        self.client
+            .head(&feed.url)
+            .header(reqwest::header::USER_AGENT, "spacebot-rss")
+            .send()
+            .await
+            .with_context(|| format!("feed {} unreachable", feed.name))?;
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(true).ok();
+        }
+        tracing::info!("rss adapter shut down");
+        Ok(())
+    }
+}
+
+/// One parsed feed entry, format-agnostic.
+#[derive(Debug, Clone, PartialEq)]
+struct FeedEntry {
+    id: String,
+    title: String,
+    link: Option<String>,
+    summary: Option<String>,
+    author: Option<String>,
+}
+
+impl FeedEntry {
+    fn into_inbound(self, runtime_key: &str, feed_name: &str) -> InboundMessage {
+        let mut text = self.title.clone();
+        if let Some(summary) = &self.summary {
+            text.push_str("\n\n");
+            text.push_str(summary);
+        }
+        if let Some(link) = &self.link {
+            text.push_str("\n\n");
+            text.push_str(link);
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "rss_feed".into(),
+            serde_json::Value::String(feed_name.to_string()),
+        );
+        metadata.insert(
+            "rss_entry_id".into(),
+            serde_json::Value::String(self.id.clone()),
+        );
+        if let Some(link) = &self.link {
+            metadata.insert("rss_link".into(), serde_json::Value::String(link.clone()));
+        }
+        if let Some(author) = &self.author {
+            metadata.insert(
+                "sender_display_name".into(),
+                serde_json::Value::String(author.clone()),
+            );
+        }
+
+        InboundMessage {
+            id: self.id,
+            source: "rss".into(),
+            adapter: Some(runtime_key.to_string()),
+            conversation_id: format!("rss:{feed_name}"),
+            sender_id: feed_name.to_string(),
+            agent_id: None,
+            content: MessageContent::Text(text),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: self.author,
+        }
+    }
+}
+
+/// Parse an RSS 2.0 or Atom document into entries, newest first as listed.
+fn parse_feed(xml: &str) -> Vec<FeedEntry> {
+    let mut entries = Vec::new();
+    for block in element_blocks(xml, "item").chain(element_blocks(xml, "entry")) {
+        let title = element_text(block, "title").unwrap_or_default();
+        // Atom links carry the URL in an href attribute; RSS in element text
+        let link = element_text(block, "link")
+            .filter(|link| !link.is_empty())
+            .or_else(|| link_href(block));
+        let summary = element_text(block, "description")
+            .or_else(|| element_text(block, "summary"))
+            .map(|s| strip_html(&s))
+            .filter(|s| !s.is_empty());
+        let author = element_text(block, "author")
+            .map(|author| {
+                // Atom wraps the author in a <name> element
+                element_text(&author, "name").unwrap_or(author)
+            })
+            .or_else(|| element_text(block, "dc:creator"))
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+        let id = element_text(block, "guid")
+            .or_else(|| element_text(block, "id"))
+            .or_else(|| link.clone())
+            .unwrap_or_else(|| title.clone());
+        if id.is_empty() && title.is_empty() {
+            continue;
+        }
+        entries.push(FeedEntry {
+            id,
+            title,
+            link,
+            summary,
+            author,
+        });
+    }
+    entries
+}
+
+/// Iterate the contents of every `<name>...</name>` block in a document.
+fn element_blocks<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut rest = xml;
+    std::iter::from_fn(move || {
+        loop {
+            let start = rest.find(&open)?;
+            let after_tag = rest[start..].find('>')? + start + 1;
+            // Not our element if the match is a prefix like <itemref>
+            let tag = &rest[start + 1..after_tag - 1];
+            let tag_name = tag.split([' ', '/', '\t', '\n']).next().unwrap_or(tag);
+            if tag_name != name {
+                rest = &rest[after_tag..];
+                continue;
+            }
+            let end = rest[after_tag..].find(&close)? + after_tag;
+            let block = &rest[after_tag..end];
+            rest = &rest[end + close.len()..];
+            return Some(block);
+        }
+    })
+}
+
+/// Text content of the first `<name>` element, with CDATA and entities resolved.
+fn element_text(xml: &str, name: &str) -> Option<String> {
+    let block = element_blocks(xml, name).next()?;
+    let block = block.trim();
+    let text = block
+        .strip_prefix("<![CDATA[")
+        .and_then(|b| b.strip_suffix("]]>"))
+        .unwrap_or(block);
+    Some(xml_unescape(text.trim()))
+}
+
+/// The href of the first `<link>` tag, as used by Atom.
+fn link_href(xml: &str) -> Option<String> {
+    let start = xml.find("<link")?;
+    let tag_end = xml[start..].find('>')? + start;
+    let tag = &xml[start..tag_end];
+    for quote in ['\'', '"'] {
+        let needle = format!("href={quote}");
+        if let Some(href_start) = tag.find(&needle) {
+            let rest = &tag[href_start + needle.len()..];
+            let end = rest.find(quote)?;
+            return Some(xml_unescape(&rest[..end]));
+        }
+    }
+    None
+}
+
+/// Drop HTML tags from a summary, collapsing runs of whitespace.
+fn strip_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rss_items_parse() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+<title>Example</title>
+<item>
+  <title>First post</title>
+  <link>https://example.org/1</link>
+  <guid>tag:example,1</guid>
+  <description><![CDATA[Some <b>bold</b> news]]></description>
+</item>
+<item>
+  <title>Second post</title>
+  <link>https://example.org/2</link>
+</item>
+</channel></rss>"#;
+        let entries = parse_feed(xml);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "tag:example,1");
+        assert_eq!(entries[0].title, "First post");
+        assert_eq!(entries[0].summary.as_deref(), Some("Some bold news"));
+        assert_eq!(entries[1].id, "https://example.org/2");
+    }
+
+    #[test]
+    fn atom_entries_parse() {
+        let xml = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+<title>Example</title>
+<entry>
+  <id>urn:uuid:1</id>
+  <title>Atom post</title>
+  <link rel="alternate" href="https://example.org/atom/1"/>
+  <summary>An entry</summary>
+  <author><name>Alice</name></author>
+</entry>
+</feed>"#;
+        let entries = parse_feed(xml);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "urn:uuid:1");
+        assert_eq!(entries[0].link.as_deref(), Some("https://example.org/atom/1"));
+        assert_eq!(entries[0].author.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn entries_become_inbound_messages() {
+        let entry = FeedEntry {
+            id: "tag:example,1".into(),
+            title: "First post".into(),
+            link: Some("https://example.org/1".into()),
+            summary: Some("News".into()),
+            author: Some("Alice".into()),
+        };
+        let inbound = entry.into_inbound("rss", "example");
+        assert_eq!(inbound.source, "rss");
+        assert_eq!(inbound.conversation_id, "rss:example");
+        let MessageContent::Text(text) = inbound.content else {
+            panic!("expected text content");
+        };
+        assert!(text.contains("First post"));
+        assert!(text.contains("https://example.org/1"));
+    }
+}
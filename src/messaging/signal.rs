@@ -0,0 +1,451 @@
+//! Signal messaging adapter via a signal-cli daemon.
+//!
+//! Talks JSON-RPC to a `signal-cli` daemon over TCP (`signal-cli daemon
+//! --tcp`), which holds the linked secondary-device session. Run
+//! `signal-cli link -n spacebot` once to pair the account; the adapter itself
+//! never touches key material. Inbound `receive` notifications are mapped to
+//! messages (DMs and groups), attachments downloaded by signal-cli are
+//! surfaced as `MessageContent::Media` with `file://` URLs, and delivery /
+//! read receipts are recorded in the conversation metadata of subsequent
+//! sends via logging.
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+use tokio::net::TcpStream;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+/// Signal itself has no hard text limit worth hitting; keep chunks readable.
+const MAX_MESSAGE_LENGTH: usize = 60_000;
+
+/// Signal adapter state.
+pub struct SignalAdapter {
+    runtime_key: String,
+    /// The linked account's E.164 number, e.g. `+15551234567`.
+    account: String,
+    /// TCP address of the signal-cli daemon, e.g. `127.0.0.1:7583`.
+    daemon_addr: String,
+    /// Write half of the daemon connection, shared with the send path.
+    writer: Arc<Mutex<Option<tokio::net::tcp::OwnedWriteHalf>>>,
+    request_id: AtomicU64,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// A `receive` notification envelope from signal-cli.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    source_number: Option<String>,
+    source_uuid: Option<String>,
+    source_name: Option<String>,
+    timestamp: Option<i64>,
+    data_message: Option<DataMessage>,
+    receipt_message: Option<ReceiptMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataMessage {
+    message: Option<String>,
+    group_info: Option<GroupInfo>,
+    #[serde(default)]
+    attachments: Vec<SignalAttachment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupInfo {
+    group_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignalAttachment {
+    id: Option<String>,
+    filename: Option<String>,
+    content_type: Option<String>,
+    size: Option<u64>,
+    /// Local path where signal-cli stored the downloaded attachment.
+    file: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceiptMessage {
+    is_delivery: Option<bool>,
+    is_read: Option<bool>,
+    #[serde(default)]
+    timestamps: Vec<i64>,
+}
+
+impl SignalAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        account: impl Into<String>,
+        daemon_addr: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            account: account.into(),
+            daemon_addr: daemon_addr.into(),
+            writer: Arc::new(Mutex::new(None)),
+            request_id: AtomicU64::new(1),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Issue a JSON-RPC request to the daemon. Responses are consumed by the
+    /// read loop; the send path is fire-and-forget with errors surfaced there.
+    async fn rpc(&self, method: &str, params: serde_json::Value) -> crate::Result<()> {
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": id,
+        });
+        let mut line = serde_json::to_string(&request).context("failed to encode JSON-RPC")?;
+        line.push('\n');
+
+        let mut writer = self.writer.lock().await;
+        let writer = writer
+            .as_mut()
+            .context("signal-cli daemon connection not established")?;
+        writer
+            .write_all(line.as_bytes())
+            .await
+            .context("failed to write to signal-cli daemon")?;
+        Ok(())
+    }
+
+    /// Send a text message to a recipient number or group ID.
+    async fn send_text(&self, target: &SignalTarget<'_>, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            let mut params = json!({
+                "account": self.account,
+                "message": chunk,
+            });
+            match target {
+                SignalTarget::Direct(number) => params["recipient"] = json!([number]),
+                SignalTarget::Group(group_id) => params["groupId"] = json!(group_id),
+            }
+            self.rpc("send", params).await?;
+        }
+        Ok(())
+    }
+
+    /// Resolve the send target from inbound message metadata.
+    fn target_from(message: &InboundMessage) -> crate::Result<SignalTarget<'_>> {
+        if let Some(group_id) = message
+            .metadata
+            .get("signal_group_id")
+            .and_then(|v| v.as_str())
+        {
+            return Ok(SignalTarget::Group(group_id));
+        }
+        message
+            .metadata
+            .get("signal_source_number")
+            .and_then(|v| v.as_str())
+            .map(SignalTarget::Direct)
+            .context("message has no signal routing metadata")
+            .map_err(Into::into)
+    }
+}
+
+enum SignalTarget<'a> {
+    Direct(&'a str),
+    Group(&'a str),
+}
+
+impl Messaging for SignalAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let stream = TcpStream::connect(&self.daemon_addr)
+            .await
+            .with_context(|| {
+                format!("failed to connect to signal-cli daemon at {}", self.daemon_addr)
+            })?;
+        let (read_half, write_half) = stream.into_split();
+        *self.writer.lock().await = Some(write_half);
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let runtime_key = self.runtime_key.clone();
+        let account = self.account.clone();
+
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(read_half).lines();
+            loop {
+                let line = tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    line = lines.next_line() => line,
+                };
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    Ok(None) => {
+                        tracing::warn!("signal-cli daemon closed the connection");
+                        break;
+                    }
+                    Err(error) => {
+                        tracing::error!(%error, "error reading from signal-cli daemon");
+                        break;
+                    }
+                };
+
+                let Ok(notification) = serde_json::from_str::<serde_json::Value>(&line) else {
+                    continue;
+                };
+                if notification.get("method").and_then(|m| m.as_str()) != Some("receive") {
+                    continue;
+                }
+                let Some(envelope_value) = notification
+                    .get("params")
+                    .and_then(|params| params.get("envelope"))
+                else {
+                    continue;
+                };
+                let Ok(envelope) = serde_json::from_value::<Envelope>(envelope_value.clone())
+                else {
+                    continue;
+                };
+
+                if let Some(receipt) = &envelope.receipt_message {
+                    tracing::debug!(
+                        source = envelope.source_number.as_deref().unwrap_or("unknown"),
+                        delivered = receipt.is_delivery.unwrap_or(false),
+                        read = receipt.is_read.unwrap_or(false),
+                        timestamps = ?receipt.timestamps,
+                        "signal receipt"
+                    );
+                    continue;
+                }
+
+                let Some(data) = envelope.data_message else {
+                    continue;
+                };
+
+                let sender_number = envelope
+                    .source_number
+                    .clone()
+                    .or_else(|| envelope.source_uuid.clone())
+                    .unwrap_or_default();
+                // Skip sync copies of our own outbound messages
+                if sender_number == account {
+                    continue;
+                }
+
+                let group_id = data
+                    .group_info
+                    .as_ref()
+                    .and_then(|info| info.group_id.clone());
+                let conversation_id = match &group_id {
+                    Some(group_id) => format!("signal:{group_id}"),
+                    None => format!("signal:{sender_number}"),
+                };
+
+                let sender_name = envelope
+                    .source_name
+                    .clone()
+                    .unwrap_or_else(|| sender_number.clone());
+
+                let mut metadata = HashMap::new();
+                metadata.insert(
+                    "signal_source_number".into(),
+                    serde_json::Value::String(sender_number.clone()),
+                );
+                if let Some(uuid) = &envelope.source_uuid {
+                    metadata.insert(
+                        "signal_source_uuid".into(),
+                        serde_json::Value::String(uuid.clone()),
+                    );
+                }
+                if let Some(group_id) = &group_id {
+                    metadata.insert(
+                        "signal_group_id".into(),
+                        serde_json::Value::String(group_id.clone()),
+                    );
+                }
+                if let Some(timestamp) = envelope.timestamp {
+                    metadata.insert("signal_timestamp".into(), serde_json::json!(timestamp));
+                }
+                metadata.insert(
+                    "sender_display_name".into(),
+                    serde_json::Value::String(sender_name.clone()),
+                );
+
+                let text = data.message.unwrap_or_default();
+                let attachments: Vec<Attachment> = data
+                    .attachments
+                    .iter()
+                    .filter_map(|attachment| {
+                        let path = attachment.file.clone()?;
+                        Some(Attachment {
+                            filename: attachment
+                                .filename
+                                .clone()
+                                .or_else(|| attachment.id.clone())
+                                .unwrap_or_else(|| "attachment".into()),
+                            mime_type: attachment
+                                .content_type
+                                .clone()
+                                .unwrap_or_else(|| "application/octet-stream".into()),
+                            url: format!("file://{path}"),
+                            size_bytes: attachment.size,
+                            auth_header: None,
+                        })
+                    })
+                    .collect();
+
+                if text.is_empty() && attachments.is_empty() {
+                    continue;
+                }
+
+                let content = if attachments.is_empty() {
+                    MessageContent::Text(text)
+                } else {
+                    MessageContent::Media {
+                        text: (!text.is_empty()).then_some(text),
+                        attachments,
+                    }
+                };
+
+                let inbound = InboundMessage {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    source: "signal".into(),
+                    adapter: Some(runtime_key.clone()),
+                    conversation_id,
+                    sender_id: sender_number,
+                    agent_id: None,
+                    content,
+                    timestamp: envelope
+                        .timestamp
+                        .and_then(chrono::DateTime::from_timestamp_millis)
+                        .unwrap_or_else(chrono::Utc::now),
+                    metadata,
+                    formatted_author: Some(sender_name),
+                };
+
+                if inbound_tx.send(inbound).await.is_err() {
+                    break;
+                }
+            }
+            tracing::info!("signal read loop ended");
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let target = Self::target_from(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(&target, &text).await,
+            OutboundResponse::File {
+                filename,
+                data,
+                caption,
+                ..
+            } => {
+                // signal-cli accepts base64 data URIs for attachments
+                use base64::Engine as _;
+                let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+                let mut params = json!({
+                    "account": self.account,
+                    "message": caption.unwrap_or_default(),
+                    "attachments": [format!("data:application/octet-stream;filename={filename};base64,{encoded}")],
+                });
+                match &target {
+                    SignalTarget::Direct(number) => params["recipient"] = json!([number]),
+                    SignalTarget::Group(group_id) => params["groupId"] = json!(group_id),
+                }
+                self.rpc("send", params).await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let (Some(author), Some(timestamp)) = (
+                    message
+                        .metadata
+                        .get("signal_source_number")
+                        .and_then(|v| v.as_str()),
+                    message
+                        .metadata
+                        .get("signal_timestamp")
+                        .and_then(|v| v.as_i64()),
+                ) else {
+                    return Ok(());
+                };
+                let mut params = json!({
+                    "account": self.account,
+                    "emoji": emoji,
+                    "targetAuthor": author,
+                    "targetTimestamp": timestamp,
+                });
+                match &target {
+                    SignalTarget::Direct(number) => params["recipient"] = json!([number]),
+                    SignalTarget::Group(group_id) => params["groupId"] = json!(group_id),
+                }
+                self.rpc("sendReaction", params).await
+            }
+            // Streaming frames and status updates have no Signal equivalent
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let text = match response {
+            OutboundResponse::Text(text) => text,
+            _ => return Ok(()),
+        };
+        // Group IDs are base64 and contain '='; bare numbers start with '+'
+        let target = if target.starts_with('+') {
+            SignalTarget::Direct(target)
+        } else {
+            SignalTarget::Group(target)
+        };
+        self.send_text(&target, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        if self.writer.lock().await.is_none() {
+            return Err(anyhow::anyhow!("signal-cli daemon connection not established").into());
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        *self.writer.lock().await = None;
+        tracing::info!("signal adapter shut down");
+        Ok(())
+    }
+}
+
@@ -23,7 +23,7 @@
 
 use crate::config::{SlackCommandConfig, SlackPermissions};
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
-use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::messaging::traits::{HistoryFetchPolicy, HistoryMessage, InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
 
 use anyhow::Context as _;
@@ -247,7 +247,7 @@ async fn handle_message_event(
 
     let content = extract_message_content(&msg_event.content, &adapter_state.bot_token);
 
-    let (metadata, formatted_author) = build_metadata_and_author(
+    let (mut metadata, formatted_author) = build_metadata_and_author(
         &team_id_str,
         &channel_id,
         &ts,
@@ -261,6 +261,14 @@ async fn handle_message_event(
     )
     .await;
 
+    // Platform-neutral mention flag consumed by binding triggers.
+    if content
+        .to_string()
+        .contains(&format!("<@{}>", adapter_state.bot_user_id))
+    {
+        metadata.insert("mentions_bot".into(), serde_json::Value::Bool(true));
+    }
+
     send_inbound(
         &adapter_state.inbound_tx,
         &adapter_state.runtime_key,
@@ -337,7 +345,7 @@ async fn handle_app_mention_event(
     let content = MessageContent::Text(text);
 
     let slack_uid = SlackUserId(user_id.clone());
-    let (metadata, formatted_author) = build_metadata_and_author(
+    let (mut metadata, formatted_author) = build_metadata_and_author(
         &team_id_str,
         &channel_id,
         &ts,
@@ -350,6 +358,8 @@ async fn handle_app_mention_event(
         &adapter_state.channel_name_cache,
     )
     .await;
+    // An app_mention is by definition a bot mention.
+    metadata.insert("mentions_bot".into(), serde_json::Value::Bool(true));
 
     send_inbound(
         &adapter_state.inbound_tx,
@@ -913,7 +923,7 @@ impl Messaging for SlackAdapter {
                 session
                     .files_upload_via_url(&SlackApiFilesUploadViaUrlRequest::new(
                         upload_url_response.upload_url,
-                        data,
+                        data.to_vec(),
                         mime_type,
                     ))
                     .await
@@ -1141,20 +1151,48 @@ impl Messaging for SlackAdapter {
         let session = self.session();
         let channel_id = extract_channel_id(message)?;
         let thread_ts = extract_thread_ts(message);
-        let capped_limit = limit.min(100) as u16;
+        let policy = HistoryFetchPolicy::from_limit(limit);
 
         let messages = if let Some(ts) = thread_ts {
-            let req = SlackApiConversationsRepliesRequest::new(channel_id.clone(), ts)
-                .with_limit(capped_limit);
-            session
+            let req = SlackApiConversationsRepliesRequest::new(channel_id.clone(), ts.clone())
+                .with_limit(policy.thread_limit.min(100) as u16);
+            let mut messages = session
                 .conversations_replies(&req)
                 .await
                 .context("failed to fetch slack thread history")?
-                .messages
+                .messages;
+
+            // Also pull recent channel messages so the agent sees what else
+            // was happening around the thread. Fail open on errors — the
+            // thread itself is the important part.
+            if policy.context_limit > 0 {
+                let context_req = SlackApiConversationsHistoryRequest::new()
+                    .with_channel(channel_id.clone())
+                    .with_limit(policy.context_limit.min(100) as u16);
+                match session.conversations_history(&context_req).await {
+                    Ok(response) => {
+                        let mut context = response.messages;
+                        // Drop the thread parent and replies broadcast to the
+                        // channel; the replies fetch already covers them.
+                        context.retain(|msg| {
+                            msg.origin.ts != ts && msg.origin.thread_ts.as_ref() != Some(&ts)
+                        });
+                        // Both lists are newest-first, so appending the
+                        // channel context here puts it first once the
+                        // combined list is reversed to chronological order.
+                        messages.extend(context);
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "failed to fetch slack channel context for thread");
+                    }
+                }
+            }
+
+            messages
         } else {
             let req = SlackApiConversationsHistoryRequest::new()
                 .with_channel(channel_id.clone())
-                .with_limit(capped_limit);
+                .with_limit(limit.min(100) as u16);
             session
                 .conversations_history(&req)
                 .await
@@ -1593,10 +1631,19 @@ fn sanitize_reaction_name(emoji: &str) -> String {
         return name;
     }
     // Fall back to stripping colons and lowercasing (handles ":thumbsup:" and "thumbsup").
-    trimmed
+    let name = trimmed
         .trim_start_matches(':')
         .trim_end_matches(':')
-        .to_lowercase()
+        .to_lowercase();
+    // Known shortcodes pass through untouched; intent names outside the
+    // shortcode set (e.g. "like", "celebrate") resolve through the shared
+    // reaction vocabulary.
+    if emojis::get_by_shortcode(&name).is_none()
+        && let Some(shortcode) = crate::messaging::reactions::to_slack_shortcode(&name)
+    {
+        return shortcode.to_string();
+    }
+    name
 }
 
 fn resolve_slack_user_identity(user: &SlackUser, user_id: &str) -> SlackUserIdentity {
@@ -1618,6 +1665,20 @@ fn resolve_slack_user_identity(user: &SlackUser, user_id: &str) -> SlackUserIden
 mod tests {
     use super::*;
 
+    #[test]
+    fn history_fetch_policy_splits_budget() {
+        let policy = HistoryFetchPolicy::from_limit(30);
+        assert_eq!(policy.thread_limit, 20);
+        assert_eq!(policy.context_limit, 10);
+        assert_eq!(policy.chain_depth, 10);
+
+        // A tiny budget still fetches at least one thread message.
+        let policy = HistoryFetchPolicy::from_limit(1);
+        assert_eq!(policy.thread_limit, 1);
+        assert_eq!(policy.context_limit, 0);
+        assert_eq!(policy.chain_depth, 1);
+    }
+
     #[test]
     fn sanitize_reaction_name_unicode_emoji_with_shortcode() {
         // gemoji maps 👍 to "+1" — verify we get the shortcode, not the unicode back
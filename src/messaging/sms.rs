@@ -0,0 +1,464 @@
+//! SMS messaging adapter backed by Twilio.
+//!
+//! Inbound messages arrive on Twilio's webhook callback (form-encoded POST,
+//! authenticated via the `X-Twilio-Signature` HMAC over the request URL and
+//! params); outbound messages go through the provider REST API. Sending is abstracted
+//! behind [`SmsProvider`] so another carrier API can slot in later without
+//! touching the adapter. Multiple numbers are supported: each configured
+//! number carries its own credentials, the webhook dispatches on the `To`
+//! field, and replies go out from the number the conversation came in on.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::routing::{get, post};
+use base64::Engine as _;
+use serde::Deserialize;
+use sha1::Digest as _;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::{constant_time_eq, split_message};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Carriers segment above 160 GSM-7 characters; Twilio re-assembles up to
+/// 1600, so split there.
+const MAX_MESSAGE_LENGTH: usize = 1_500;
+
+/// Outbound SMS delivery, abstracted so other carriers can be added.
+pub trait SmsProvider: Send + Sync + 'static {
+    /// Send a message body from one of our numbers to a recipient.
+    fn send(
+        &self,
+        from: &str,
+        to: &str,
+        body: &str,
+    ) -> impl std::future::Future<Output = crate::Result<()>> + Send;
+}
+
+/// Twilio REST API provider.
+pub struct TwilioProvider {
+    account_sid: String,
+    auth_token: String,
+    client: reqwest::Client,
+}
+
+impl TwilioProvider {
+    pub fn new(account_sid: impl Into<String>, auth_token: impl Into<String>) -> Self {
+        Self {
+            account_sid: account_sid.into(),
+            auth_token: auth_token.into(),
+            client: crate::http::client(),
+        }
+    }
+}
+
+impl SmsProvider for TwilioProvider {
+    async fn send(&self, from: &str, to: &str, body: &str) -> crate::Result<()> {
+        let url = format!(
+            "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+            self.account_sid
+        );
+        let response = self
+            .client
+            .post(&url)
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .form(&[("From", from), ("To", to), ("Body", body)])
+            .send()
+            .await
+            .context("failed to send Twilio SMS")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Twilio send failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+}
+
+/// SMS adapter state.
+pub struct SmsAdapter<P: SmsProvider = TwilioProvider> {
+    runtime_key: String,
+    provider: Arc<P>,
+    /// Our numbers, in E.164. The first is the default for broadcasts.
+    numbers: Vec<String>,
+    /// Twilio auth token, used to verify webhook signatures.
+    auth_token: String,
+    port: u16,
+    bind: String,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    numbers: Vec<String>,
+    /// Twilio auth token, used to verify webhook signatures.
+    auth_token: String,
+    runtime_key: String,
+}
+
+/// Twilio's form-encoded webhook payload, reduced to the fields we consume.
+#[derive(Debug, Deserialize)]
+struct TwilioWebhook {
+    #[serde(rename = "MessageSid")]
+    message_sid: Option<String>,
+    #[serde(rename = "From")]
+    from: Option<String>,
+    #[serde(rename = "To")]
+    to: Option<String>,
+    #[serde(rename = "Body")]
+    body: Option<String>,
+}
+
+impl<P: SmsProvider> SmsAdapter<P> {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        provider: P,
+        numbers: Vec<String>,
+        auth_token: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            provider: Arc::new(provider),
+            numbers,
+            auth_token: auth_token.into(),
+            port,
+            bind: bind.into(),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    async fn send_text(&self, from: &str, to: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.provider.send(from, to, &chunk).await?;
+        }
+        Ok(())
+    }
+
+    /// The peer number and our number for a conversation, from metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, &str)> {
+        let peer = message
+            .metadata
+            .get("sms_peer_number")
+            .and_then(|v| v.as_str())
+            .context("missing sms_peer_number in metadata")?;
+        let own = message
+            .metadata
+            .get("sms_own_number")
+            .and_then(|v| v.as_str())
+            .context("missing sms_own_number in metadata")?;
+        Ok((peer, own))
+    }
+}
+
+impl<P: SmsProvider> Messaging for SmsAdapter<P> {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            inbound_tx: self.inbound_tx.clone(),
+            numbers: self.numbers.clone(),
+            auth_token: self.auth_token.clone(),
+            runtime_key: self.runtime_key.clone(),
+        };
+
+        let app = Router::new()
+            .route("/sms", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind SMS webhook to {bind}"))?;
+        tracing::info!(%bind, "SMS webhook listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "SMS webhook exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (peer, own) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(own, peer, &text).await,
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Plain SMS can't carry attachments; send the caption with a
+                // note instead of silently dropping the file.
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment omitted: {filename})"),
+                    None => format!("(attachment omitted: {filename})"),
+                };
+                self.send_text(own, peer, &note).await
+            }
+            // Reactions and stream framing have no SMS equivalent
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        let Some(own) = self.numbers.first() else {
+            return Err(anyhow::anyhow!("no SMS numbers configured").into());
+        };
+        self.send_text(own, target, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("SMS adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    raw_body: String,
+) -> StatusCode {
+    // Twilio signs every callback with the auth token; anything unsigned
+    // or mis-signed is not from Twilio.
+    let Some(signature) = headers
+        .get("x-twilio-signature")
+        .and_then(|v| v.to_str().ok())
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    let url = twilio_request_url(&headers, &uri);
+    if !verify_twilio_signature(&state.auth_token, &url, &raw_body, signature) {
+        tracing::warn!("rejected SMS webhook with bad signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+    let Ok(webhook) = serde_urlencoded::from_str::<TwilioWebhook>(&raw_body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let (Some(from), Some(to), Some(body)) = (webhook.from, webhook.to, webhook.body) else {
+        return StatusCode::OK;
+    };
+    if body.trim().is_empty() {
+        return StatusCode::OK;
+    }
+    // Only accept traffic addressed to one of our configured numbers
+    if !state.numbers.iter().any(|number| number == &to) {
+        tracing::warn!(%to, "SMS webhook received message for unconfigured number");
+        return StatusCode::OK;
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "sms_peer_number".into(),
+        serde_json::Value::String(from.clone()),
+    );
+    metadata.insert(
+        "sms_own_number".into(),
+        serde_json::Value::String(to.clone()),
+    );
+    if let Some(sid) = &webhook.message_sid {
+        metadata.insert(
+            "sms_message_sid".into(),
+            serde_json::Value::String(sid.clone()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(from.clone()),
+    );
+
+    let inbound = InboundMessage {
+        id: webhook
+            .message_sid
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "sms".into(),
+        adapter: Some(state.runtime_key.clone()),
+        conversation_id: format!("sms:{from}"),
+        sender_id: from.clone(),
+        agent_id: None,
+        content: MessageContent::Text(body),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(from),
+    };
+
+    let tx = state.inbound_tx.read().await;
+    let Some(tx) = tx.as_ref() else {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Reconstruct the public URL Twilio signed, honoring reverse-proxy
+/// forwarding headers. Twilio only delivers over HTTPS, so that is the
+/// default scheme.
+pub(crate) fn twilio_request_url(headers: &HeaderMap, uri: &Uri) -> String {
+    let proto = headers
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("https");
+    let host = headers
+        .get("x-forwarded-host")
+        .or_else(|| headers.get("host"))
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let path = uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or_else(|| uri.path());
+    format!("{proto}://{host}{path}")
+}
+
+/// Validate an `X-Twilio-Signature` header: base64 HMAC-SHA1 over the full
+/// request URL followed by each POST parameter name and value, sorted by
+/// name, keyed with the account's auth token.
+pub(crate) fn verify_twilio_signature(
+    auth_token: &str,
+    url: &str,
+    form_body: &str,
+    signature: &str,
+) -> bool {
+    let Ok(mut params) = serde_urlencoded::from_str::<Vec<(String, String)>>(form_body) else {
+        return false;
+    };
+    params.sort();
+
+    let mut signed = String::from(url);
+    for (name, value) in &params {
+        signed.push_str(name);
+        signed.push_str(value);
+    }
+    let expected = base64::engine::general_purpose::STANDARD
+        .encode(hmac_sha1(auth_token.as_bytes(), signed.as_bytes()));
+    constant_time_eq(&expected, signature)
+}
+
+/// HMAC-SHA1 per RFC 2104, built on the sha1 crate.
+fn hmac_sha1(key: &[u8], data: &[u8]) -> [u8; 20] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..20].copy_from_slice(&sha1::Sha1::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = sha1::Sha1::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = sha1::Sha1::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twilio_signatures_verify() {
+        let url = "https://example.com/sms";
+        let body = "To=%2B15551230000&From=%2B15557654321&Body=hello";
+        // Params are concatenated sorted by name: Body, From, To.
+        let signed = format!("{url}BodyhelloFrom+15557654321To+15551230000");
+        let signature = base64::engine::general_purpose::STANDARD
+            .encode(hmac_sha1(b"auth-token", signed.as_bytes()));
+
+        assert!(verify_twilio_signature("auth-token", url, body, &signature));
+        assert!(!verify_twilio_signature("wrong-token", url, body, &signature));
+        assert!(!verify_twilio_signature(
+            "auth-token",
+            "https://example.com/other",
+            body,
+            &signature
+        ));
+        assert!(!verify_twilio_signature("auth-token", url, body, "bogus"));
+    }
+
+    #[test]
+    fn request_url_honors_forwarding_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("host", "bot.example.com".parse().unwrap());
+        let uri: Uri = "/sms".parse().unwrap();
+        assert_eq!(
+            twilio_request_url(&headers, &uri),
+            "https://bot.example.com/sms"
+        );
+
+        headers.insert("x-forwarded-proto", "http".parse().unwrap());
+        headers.insert("x-forwarded-host", "edge.example.com".parse().unwrap());
+        assert_eq!(
+            twilio_request_url(&headers, &uri),
+            "http://edge.example.com/sms"
+        );
+    }
+}
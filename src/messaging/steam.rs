@@ -0,0 +1,589 @@
+//! Steam friend-chat adapter using the Web User Presence OAuth API.
+//!
+//! Logs the account on through `ISteamWebUserPresenceOAuth/Logon`, long-polls
+//! `Poll` for incoming friend messages, and sends replies with `Message`.
+//! Friend allowlists mirror Telegram's `dm_allowed_users`: an empty list
+//! accepts every friend, a non-empty list only the SteamID64s it contains.
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{RwLock, mpsc, watch};
+use tokio::task::JoinHandle;
+
+const STEAM_API_BASE: &str = "https://api.steampowered.com/ISteamWebUserPresenceOAuth";
+/// Server-side long-poll timeout, in seconds.
+const POLL_TIMEOUT_SECS: u32 = 20;
+const STEAM_MAX_RETRY_BACKOFF_SECS: u64 = 300;
+
+/// Active web chat session returned by `Logon`.
+#[derive(Debug, Clone)]
+struct SteamSession {
+    umqid: String,
+    steamid: String,
+    /// Last message number seen, passed back to `Poll`.
+    last_message: u64,
+}
+
+#[derive(Deserialize)]
+struct LogonResponse {
+    #[serde(default)]
+    error: String,
+    #[serde(default)]
+    umqid: Option<String>,
+    #[serde(default)]
+    steamid: Option<String>,
+    #[serde(default)]
+    message: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct PollResponse {
+    #[serde(default)]
+    error: String,
+    #[serde(default)]
+    messages: Vec<PollMessage>,
+    #[serde(default)]
+    messagelast: Option<u64>,
+}
+
+/// One event from the `Poll` endpoint. Only `saytext` carries chat text.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PollMessage {
+    #[serde(rename = "type", default)]
+    pub(crate) message_type: String,
+    #[serde(default)]
+    pub(crate) steamid_from: String,
+    #[serde(default)]
+    pub(crate) text: Option<String>,
+    #[serde(default)]
+    pub(crate) utc_timestamp: Option<i64>,
+}
+
+/// Steam adapter state.
+pub struct SteamAdapter {
+    access_token: String,
+    allowed_friends: Vec<String>,
+    client: reqwest::Client,
+    session: Arc<RwLock<Option<SteamSession>>>,
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<bool>>>>,
+    poll_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+}
+
+impl std::fmt::Debug for SteamAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SteamAdapter")
+            .field("access_token", &"[REDACTED]")
+            .field("allowed_friends", &self.allowed_friends)
+            .finish()
+    }
+}
+
+impl SteamAdapter {
+    pub fn new(access_token: impl Into<String>, allowed_friends: Vec<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            allowed_friends,
+            client: crate::http::client(),
+            session: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+            poll_task: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    async fn logon(&self) -> anyhow::Result<SteamSession> {
+        let url = format!("{STEAM_API_BASE}/Logon/v0001/");
+        let response: LogonResponse = self
+            .client
+            .post(&url)
+            .form(&[("access_token", self.access_token.as_str())])
+            .send()
+            .await
+            .context("steam logon request failed")?
+            .json()
+            .await
+            .context("failed to parse steam logon response")?;
+
+        if response.error != "OK" {
+            anyhow::bail!("steam logon rejected: {}", response.error);
+        }
+
+        let session = SteamSession {
+            umqid: response.umqid.context("steam logon response missing umqid")?,
+            steamid: response
+                .steamid
+                .context("steam logon response missing steamid")?,
+            last_message: response.message.unwrap_or(0),
+        };
+
+        *self.session.write().await = Some(session.clone());
+        Ok(session)
+    }
+
+    async fn current_session(&self) -> anyhow::Result<SteamSession> {
+        if let Some(session) = self.session.read().await.as_ref() {
+            return Ok(session.clone());
+        }
+        self.logon().await
+    }
+
+    /// Sends a `saytext` (or `typing`) message to a friend.
+    async fn send_message(
+        &self,
+        steamid_dst: &str,
+        message_type: &str,
+        text: Option<&str>,
+    ) -> crate::Result<()> {
+        let session = self.current_session().await?;
+
+        let mut form = vec![
+            ("access_token", self.access_token.clone()),
+            ("umqid", session.umqid),
+            ("type", message_type.to_string()),
+            ("steamid_dst", steamid_dst.to_string()),
+        ];
+        if let Some(text) = text {
+            form.push(("text", text.to_string()));
+        }
+
+        let url = format!("{STEAM_API_BASE}/Message/v0001/");
+        let response = self
+            .client
+            .post(&url)
+            .form(&form)
+            .send()
+            .await
+            .context("steam message request failed")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("steam message returned {status}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("failed to parse steam message response")?;
+        let error = body.get("error").and_then(|value| value.as_str());
+        if error != Some("OK") {
+            // Session expired — drop it so the next call logs on again.
+            if error == Some("Not Logged On") {
+                *self.session.write().await = None;
+            }
+            return Err(
+                anyhow::anyhow!("steam message rejected: {}", error.unwrap_or("unknown")).into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Messaging for SteamAdapter {
+    fn name(&self) -> &str {
+        "steam"
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        if self.poll_task.read().await.is_some() {
+            return Err(anyhow::anyhow!("steam adapter already started").into());
+        }
+
+        let mut session = self.logon().await?;
+        tracing::info!(steamid = %session.steamid, "steam adapter logged on");
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let client = self.client.clone();
+        let access_token = self.access_token.clone();
+        let allowed_friends = self.allowed_friends.clone();
+        let shared_session = self.session.clone();
+
+        let poll_task = tokio::spawn(async move {
+            let mut retry_backoff = Duration::from_secs(5);
+
+            loop {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+
+                let poll_result = poll_once(&client, &access_token, &session).await;
+
+                let mut had_error = false;
+
+                match poll_result {
+                    Ok(PollOutcome::Messages {
+                        messages,
+                        last_message,
+                    }) => {
+                        retry_backoff = Duration::from_secs(5);
+                        session.last_message = last_message;
+                        *shared_session.write().await = Some(session.clone());
+
+                        for message in messages {
+                            let Some(inbound) =
+                                inbound_from_poll_message(&message, &session.steamid, &allowed_friends)
+                            else {
+                                continue;
+                            };
+                            if inbound_tx.send(inbound).await.is_err() {
+                                tracing::warn!("steam inbound channel closed, stopping adapter loop");
+                                return;
+                            }
+                        }
+                    }
+                    Ok(PollOutcome::SessionExpired) => {
+                        tracing::info!("steam session expired, logging on again");
+                        match logon_with(&client, &access_token).await {
+                            Ok(new_session) => {
+                                session = new_session;
+                                *shared_session.write().await = Some(session.clone());
+                            }
+                            Err(error) => {
+                                had_error = true;
+                                tracing::warn!(%error, "steam re-logon failed");
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        had_error = true;
+                        tracing::warn!(%error, "steam poll cycle failed");
+                    }
+                }
+
+                if had_error {
+                    let current = retry_backoff;
+                    retry_backoff =
+                        (retry_backoff * 2).min(Duration::from_secs(STEAM_MAX_RETRY_BACKOFF_SECS));
+                    tokio::select! {
+                        _ = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                break;
+                            }
+                        }
+                        _ = tokio::time::sleep(current) => {}
+                    }
+                }
+            }
+
+            tracing::info!("steam adapter loop stopped");
+        });
+
+        *self.poll_task.write().await = Some(poll_task);
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let Some(steamid) = steam_target_from_conversation(&message.conversation_id) else {
+            return Err(anyhow::anyhow!(
+                "invalid steam conversation id '{}'",
+                message.conversation_id
+            )
+            .into());
+        };
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. } => {
+                self.send_message(&steamid, "saytext", Some(&text)).await?;
+            }
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                tracing::warn!(
+                    post_at,
+                    "steam adapter does not support scheduled delivery; sending immediately"
+                );
+                self.send_message(&steamid, "saytext", Some(&text)).await?;
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Steam web chat can't carry files; describe the attachment.
+                let text = match caption {
+                    Some(caption) => format!("[file: {filename}] {caption}"),
+                    None => format!("[file: {filename}]"),
+                };
+                self.send_message(&steamid, "saytext", Some(&text)).await?;
+            }
+            OutboundResponse::Status(StatusUpdate::Thinking) => {
+                self.send_message(&steamid, "typing", None).await.ok();
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+        }
+
+        Ok(())
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let steamid = parse_steam_target(target)
+            .ok_or_else(|| anyhow::anyhow!("invalid steam target '{target}'"))?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.send_message(&steamid, "saytext", Some(&text)).await?;
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                let text = match caption {
+                    Some(caption) => format!("[file: {filename}] {caption}"),
+                    None => format!("[file: {filename}]"),
+                };
+                self.send_message(&steamid, "saytext", Some(&text)).await?;
+            }
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd => {}
+        }
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.current_session().await?;
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(shutdown_tx) = self.shutdown_tx.write().await.take() {
+            shutdown_tx.send(true).ok();
+        }
+
+        if let Some(poll_task) = self.poll_task.write().await.take()
+            && let Err(error) = poll_task.await
+        {
+            tracing::warn!(%error, "steam poll task join failed during shutdown");
+        }
+
+        tracing::info!("steam adapter shut down");
+        Ok(())
+    }
+}
+
+enum PollOutcome {
+    Messages {
+        messages: Vec<PollMessage>,
+        last_message: u64,
+    },
+    SessionExpired,
+}
+
+async fn logon_with(client: &reqwest::Client, access_token: &str) -> anyhow::Result<SteamSession> {
+    let url = format!("{STEAM_API_BASE}/Logon/v0001/");
+    let response: LogonResponse = client
+        .post(&url)
+        .form(&[("access_token", access_token)])
+        .send()
+        .await
+        .context("steam logon request failed")?
+        .json()
+        .await
+        .context("failed to parse steam logon response")?;
+
+    if response.error != "OK" {
+        anyhow::bail!("steam logon rejected: {}", response.error);
+    }
+
+    Ok(SteamSession {
+        umqid: response.umqid.context("steam logon response missing umqid")?,
+        steamid: response
+            .steamid
+            .context("steam logon response missing steamid")?,
+        last_message: response.message.unwrap_or(0),
+    })
+}
+
+/// One long-poll cycle. The server holds the request up to
+/// [`POLL_TIMEOUT_SECS`], so this is the pacing of the loop too.
+async fn poll_once(
+    client: &reqwest::Client,
+    access_token: &str,
+    session: &SteamSession,
+) -> anyhow::Result<PollOutcome> {
+    let url = format!("{STEAM_API_BASE}/Poll/v0001/");
+    let response: PollResponse = client
+        .post(&url)
+        .form(&[
+            ("access_token", access_token.to_string()),
+            ("umqid", session.umqid.clone()),
+            ("message", session.last_message.to_string()),
+            ("sectimeout", POLL_TIMEOUT_SECS.to_string()),
+        ])
+        .timeout(Duration::from_secs(u64::from(POLL_TIMEOUT_SECS) + 10))
+        .send()
+        .await
+        .context("steam poll request failed")?
+        .json()
+        .await
+        .context("failed to parse steam poll response")?;
+
+    match response.error.as_str() {
+        "OK" | "Timeout" => Ok(PollOutcome::Messages {
+            last_message: response.messagelast.unwrap_or(session.last_message),
+            messages: response.messages,
+        }),
+        "Not Logged On" => Ok(PollOutcome::SessionExpired),
+        other => anyhow::bail!("steam poll rejected: {other}"),
+    }
+}
+
+/// Maps a poll event onto the inbound shape, or `None` for non-chat events,
+/// the bot's own messages, and friends outside the allowlist.
+fn inbound_from_poll_message(
+    message: &PollMessage,
+    own_steamid: &str,
+    allowed_friends: &[String],
+) -> Option<InboundMessage> {
+    if message.message_type != "saytext" {
+        return None;
+    }
+
+    let text = message.text.as_deref()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let sender = message.steamid_from.trim();
+    if sender.is_empty() || sender == own_steamid {
+        return None;
+    }
+
+    if !is_allowed_friend(sender, allowed_friends) {
+        tracing::debug!(steamid = sender, "ignoring steam message from friend outside allowlist");
+        return None;
+    }
+
+    let timestamp = message
+        .utc_timestamp
+        .and_then(|value| chrono::DateTime::from_timestamp(value, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(sender.to_string()),
+    );
+
+    Some(InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: "steam".into(),
+        adapter: Some("steam".into()),
+        conversation_id: format!("steam:{sender}"),
+        sender_id: sender.to_string(),
+        agent_id: None,
+        content: MessageContent::Text(text.to_string()),
+        timestamp,
+        metadata,
+        formatted_author: Some(sender.to_string()),
+    })
+}
+
+/// An empty allowlist accepts every friend, matching Telegram's
+/// `dm_allowed_users` semantics.
+fn is_allowed_friend(steamid: &str, allowed_friends: &[String]) -> bool {
+    allowed_friends.is_empty()
+        || allowed_friends
+            .iter()
+            .any(|allowed| allowed.trim() == steamid)
+}
+
+fn steam_target_from_conversation(conversation_id: &str) -> Option<String> {
+    conversation_id
+        .strip_prefix("steam:")
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Accepts `steam:<steamid64>` or a bare SteamID64.
+fn parse_steam_target(target: &str) -> Option<String> {
+    let value = target.trim();
+    let value = value.strip_prefix("steam:").unwrap_or(value).trim();
+    if !value.is_empty() && value.chars().all(|character| character.is_ascii_digit()) {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saytext_messages_map_to_inbound() {
+        let message = PollMessage {
+            message_type: "saytext".into(),
+            steamid_from: "76561198000000001".into(),
+            text: Some("hello there".into()),
+            utc_timestamp: Some(1_756_000_000),
+        };
+
+        let inbound =
+            inbound_from_poll_message(&message, "76561198000000099", &[]).expect("inbound");
+        assert_eq!(inbound.conversation_id, "steam:76561198000000001");
+        assert_eq!(inbound.sender_id, "76561198000000001");
+        assert_eq!(inbound.content.to_string(), "hello there");
+
+        // Typing notifications and own messages are dropped.
+        let typing = PollMessage {
+            message_type: "typing".into(),
+            ..message.clone()
+        };
+        assert!(inbound_from_poll_message(&typing, "76561198000000099", &[]).is_none());
+        assert!(inbound_from_poll_message(&message, "76561198000000001", &[]).is_none());
+    }
+
+    #[test]
+    fn friend_allowlist_matches_telegram_semantics() {
+        assert!(is_allowed_friend("76561198000000001", &[]));
+        assert!(is_allowed_friend(
+            "76561198000000001",
+            &["76561198000000001".to_string()]
+        ));
+        assert!(!is_allowed_friend(
+            "76561198000000002",
+            &["76561198000000001".to_string()]
+        ));
+    }
+
+    #[test]
+    fn steam_targets_parse() {
+        assert_eq!(
+            parse_steam_target("steam:76561198000000001").as_deref(),
+            Some("76561198000000001")
+        );
+        assert_eq!(
+            parse_steam_target("76561198000000001").as_deref(),
+            Some("76561198000000001")
+        );
+        assert!(parse_steam_target("steam:not-an-id").is_none());
+    }
+}
@@ -16,7 +16,15 @@ impl std::fmt::Display for BroadcastTarget {
 }
 
 /// Parse and normalize a delivery target in `adapter:target` format.
+///
+/// Apprise-style notification URLs (`ntfy://topic`, `mailto://user@host`,
+/// `discord://webhook_id/token`, `pushover://user_key`) are accepted and
+/// mapped onto the corresponding adapter.
 pub fn parse_delivery_target(raw: &str) -> Option<BroadcastTarget> {
+    if raw.contains("://") {
+        return parse_notification_url(raw);
+    }
+
     let (adapter, raw_target) = raw.split_once(':')?;
     if adapter.is_empty() || raw_target.is_empty() {
         return None;
@@ -129,6 +137,45 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
     })
 }
 
+/// Map an Apprise-style `scheme://rest` notification URL onto an adapter.
+fn parse_notification_url(raw: &str) -> Option<BroadcastTarget> {
+    let (scheme, rest) = raw.split_once("://")?;
+    let rest = rest.trim_matches('/');
+    if rest.is_empty() {
+        return None;
+    }
+
+    let (adapter, target) = match scheme {
+        // ntfy://topic or ntfy://host/topic — the configured server wins,
+        // only the topic (last path segment) is used
+        "ntfy" => ("ntfy", rest.rsplit('/').next()?.to_string()),
+        "mailto" => ("email", normalize_email_target(rest)?),
+        "pushover" => ("pushover", rest.to_string()),
+        "gotify" => ("gotify", rest.to_string()),
+        // discord://webhook_id/token posts through the webhook directly
+        "discord" => {
+            let (webhook_id, token) = rest.split_once('/')?;
+            if webhook_id.is_empty()
+                || token.is_empty()
+                || !webhook_id.chars().all(|character| character.is_ascii_digit())
+            {
+                return None;
+            }
+            ("discord", format!("webhook:{webhook_id}/{token}"))
+        }
+        _ => return None,
+    };
+
+    if target.is_empty() {
+        return None;
+    }
+
+    Some(BroadcastTarget {
+        adapter: adapter.to_string(),
+        target,
+    })
+}
+
 fn normalize_target(adapter: &str, raw_target: &str) -> Option<String> {
     let trimmed = raw_target.trim();
     if trimmed.is_empty() {
@@ -148,6 +195,17 @@ fn normalize_target(adapter: &str, raw_target: &str) -> Option<String> {
 fn normalize_discord_target(raw_target: &str) -> Option<String> {
     let target = strip_repeated_prefix(raw_target, "discord");
 
+    if let Some(webhook_ref) = target.strip_prefix("webhook:") {
+        let (webhook_id, token) = webhook_ref.split_once('/')?;
+        if !webhook_id.is_empty()
+            && !token.is_empty()
+            && webhook_id.chars().all(|character| character.is_ascii_digit())
+        {
+            return Some(target.to_string());
+        }
+        return None;
+    }
+
     if let Some(user_id) = target.strip_prefix("dm:") {
         if !user_id.is_empty() && user_id.chars().all(|character| character.is_ascii_digit()) {
             return Some(format!("dm:{user_id}"));
@@ -322,6 +380,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_ntfy_notification_url() {
+        let parsed = parse_delivery_target("ntfy://alerts");
+        assert_eq!(
+            parsed,
+            Some(super::BroadcastTarget {
+                adapter: "ntfy".to_string(),
+                target: "alerts".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_mailto_notification_url() {
+        let parsed = parse_delivery_target("mailto://alice@example.com");
+        assert_eq!(
+            parsed,
+            Some(super::BroadcastTarget {
+                adapter: "email".to_string(),
+                target: "alice@example.com".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_discord_webhook_notification_url() {
+        let parsed = parse_delivery_target("discord://123456789/abcDEF-token");
+        assert_eq!(
+            parsed,
+            Some(super::BroadcastTarget {
+                adapter: "discord".to_string(),
+                target: "webhook:123456789/abcDEF-token".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn reject_unknown_notification_scheme() {
+        assert_eq!(parse_delivery_target("carrierpigeon://coop"), None);
+    }
+
     #[test]
     fn parse_email_target_with_prefix() {
         let parsed = parse_delivery_target("email:alice@example.com");
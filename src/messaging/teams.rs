@@ -0,0 +1,572 @@
+//! Microsoft Teams messaging adapter via the Bot Framework.
+//!
+//! Runs an HTTP endpoint that receives Bot Framework activities (the
+//! "messaging endpoint" configured on the Azure bot registration) and sends
+//! replies through the connector REST API on each activity's `serviceUrl`.
+//! Outbound calls authenticate with an OAuth client-credential token for the
+//! bot's app registration; the token is cached until shortly before expiry.
+//!
+//! Teams channel threads and personal/group chats both arrive with a
+//! `conversation.id`, which maps directly onto `teams:{conversation_id}`.
+//! `OutboundResponse::RichMessage` cards are rendered as Adaptive Cards.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::{Json, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde::Deserialize;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::messaging::httpbot::{JwksCache, split_message};
+use crate::{Card, InboundMessage, MessageContent, OutboundResponse};
+
+/// Maximum message length before splitting. Teams rejects payloads well above
+/// this, but very long walls of text render poorly in the client anyway.
+const MAX_MESSAGE_LENGTH: usize = 25_000;
+
+/// Refresh the cached token this many seconds before it actually expires.
+const TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// OpenID configuration advertising the Bot Framework's signing keys.
+const BOT_FRAMEWORK_OPENID_URL: &str =
+    "https://login.botframework.com/v1/.well-known/openidconfiguration";
+
+/// Issuer the channel service puts in activity JWTs.
+const BOT_FRAMEWORK_ISSUER: &str = "https://api.botframework.com";
+
+/// Microsoft Teams adapter state.
+pub struct TeamsAdapter {
+    runtime_key: String,
+    app_id: String,
+    app_password: String,
+    tenant_id: String,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    /// Bot Framework signing keys for inbound activity JWTs.
+    jwks: Arc<JwksCache>,
+    token: Arc<RwLock<Option<CachedToken>>>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: std::time::Instant,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    app_id: String,
+    jwks: Arc<JwksCache>,
+    runtime_key: String,
+}
+
+/// A Bot Framework activity, reduced to the fields we consume.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Activity {
+    #[serde(rename = "type")]
+    activity_type: String,
+    id: Option<String>,
+    text: Option<String>,
+    from: Option<ChannelAccount>,
+    recipient: Option<ChannelAccount>,
+    conversation: Option<ConversationAccount>,
+    service_url: Option<String>,
+    channel_data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChannelAccount {
+    id: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConversationAccount {
+    id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl TeamsAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        app_id: impl Into<String>,
+        app_password: impl Into<String>,
+        tenant_id: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            app_id: app_id.into(),
+            app_password: app_password.into(),
+            tenant_id: tenant_id.into(),
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            jwks: Arc::new(JwksCache::from_openid_discovery(BOT_FRAMEWORK_OPENID_URL)),
+            token: Arc::new(RwLock::new(None)),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get a Bot Framework access token via the client-credential flow,
+    /// reusing the cached one while it's still valid.
+    async fn access_token(&self) -> crate::Result<String> {
+        if let Some(cached) = self.token.read().await.as_ref()
+            && cached.expires_at > std::time::Instant::now()
+        {
+            return Ok(cached.access_token.clone());
+        }
+
+        let token_url = format!(
+            "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+            self.tenant_id
+        );
+        let response = self
+            .client
+            .post(&token_url)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.app_id.as_str()),
+                ("client_secret", self.app_password.as_str()),
+                ("scope", "https://api.botframework.com/.default"),
+            ])
+            .send()
+            .await
+            .context("failed to request Teams access token")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!(
+                "Teams token request failed with {status}: {body}"
+            )
+            .into());
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("failed to parse Teams token response")?;
+
+        let expires_at = std::time::Instant::now()
+            + std::time::Duration::from_secs(
+                token.expires_in.saturating_sub(TOKEN_EXPIRY_MARGIN_SECS),
+            );
+        *self.token.write().await = Some(CachedToken {
+            access_token: token.access_token.clone(),
+            expires_at,
+        });
+
+        Ok(token.access_token)
+    }
+
+    /// POST an activity to a conversation on the given connector service URL.
+    async fn send_activity(
+        &self,
+        service_url: &str,
+        conversation_id: &str,
+        activity: serde_json::Value,
+    ) -> crate::Result<()> {
+        let token = self.access_token().await?;
+        let url = format!(
+            "{}/v3/conversations/{}/activities",
+            service_url.trim_end_matches('/'),
+            urlencoding::encode(conversation_id)
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .json(&activity)
+            .send()
+            .await
+            .context("failed to send Teams activity")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Teams send failed with {status}: {body}").into());
+        }
+
+        Ok(())
+    }
+
+    async fn send_text(
+        &self,
+        service_url: &str,
+        conversation_id: &str,
+        text: &str,
+    ) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.send_activity(
+                service_url,
+                conversation_id,
+                serde_json::json!({
+                    "type": "message",
+                    "text": chunk,
+                    "textFormat": "markdown",
+                }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Pull the connector routing info stored on the inbound message.
+    fn routing(message: &InboundMessage) -> crate::Result<(String, String)> {
+        let service_url = message
+            .metadata
+            .get("teams_service_url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("message has no teams_service_url metadata"))?;
+        let conversation_id = message
+            .metadata
+            .get("teams_conversation_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("message has no teams_conversation_id metadata"))?;
+        Ok((service_url.to_string(), conversation_id.to_string()))
+    }
+}
+
+impl Messaging for TeamsAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            inbound_tx: self.inbound_tx.clone(),
+            app_id: self.app_id.clone(),
+            jwks: self.jwks.clone(),
+            runtime_key: self.runtime_key.clone(),
+        };
+
+        let app = Router::new()
+            .route("/api/messages", post(handle_activity))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind Teams endpoint to {bind}"))?;
+        tracing::info!(%bind, "Teams messaging endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "Teams endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (service_url, conversation_id) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text) => {
+                self.send_text(&service_url, &conversation_id, &text).await
+            }
+            OutboundResponse::ThreadReply { text, .. } => {
+                // Teams channel threads are addressed by the conversation ID
+                // itself (it embeds the root message), so a thread reply is a
+                // plain send to the same conversation.
+                self.send_text(&service_url, &conversation_id, &text).await
+            }
+            OutboundResponse::RichMessage { text, cards, .. } => {
+                if cards.is_empty() {
+                    return self.send_text(&service_url, &conversation_id, &text).await;
+                }
+                let attachments: Vec<serde_json::Value> =
+                    cards.iter().map(adaptive_card_attachment).collect();
+                self.send_activity(
+                    &service_url,
+                    &conversation_id,
+                    serde_json::json!({
+                        "type": "message",
+                        "text": text,
+                        "textFormat": "markdown",
+                        "attachments": attachments,
+                    }),
+                )
+                .await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // The connector API can't upload raw bytes without a Graph
+                // drive; surface the file reference as text instead.
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment: {filename})"),
+                    None => format!("(attachment: {filename})"),
+                };
+                self.send_text(&service_url, &conversation_id, &note).await
+            }
+            OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.send_text(&service_url, &conversation_id, &text).await
+            }
+            // Reactions and stream framing have no connector equivalent
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Teams adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_activity(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(activity): Json<Activity>,
+) -> StatusCode {
+    // The Bot Framework signs requests with an RS256 JWT; validate it
+    // against the connector JWKS with issuer and audience checks so only
+    // the channel service can inject activities.
+    let Some(token) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::RS256);
+    validation.set_issuer(&[BOT_FRAMEWORK_ISSUER]);
+    validation.set_audience(&[state.app_id.as_str()]);
+    if let Err(error) = state.jwks.verify(token, &validation).await {
+        tracing::warn!(%error, "rejected Teams activity with invalid JWT");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    if activity.activity_type != "message" {
+        return StatusCode::OK;
+    }
+
+    let Some(text) = activity.text.as_deref() else {
+        return StatusCode::OK;
+    };
+    let text = strip_mention_tags(text);
+    if text.is_empty() {
+        return StatusCode::OK;
+    }
+
+    let sender_id = activity
+        .from
+        .as_ref()
+        .and_then(|from| from.id.clone())
+        .unwrap_or_default();
+    let bot_id = activity
+        .recipient
+        .as_ref()
+        .and_then(|recipient| recipient.id.clone());
+    // Ignore echoes of the bot's own messages
+    if bot_id.is_some_and(|bot_id| bot_id == sender_id) {
+        return StatusCode::OK;
+    }
+
+    let Some(conversation_id) = activity
+        .conversation
+        .as_ref()
+        .and_then(|conversation| conversation.id.clone())
+    else {
+        return StatusCode::OK;
+    };
+    let Some(service_url) = activity.service_url.clone() else {
+        return StatusCode::OK;
+    };
+
+    let sender_name = activity
+        .from
+        .as_ref()
+        .and_then(|from| from.name.clone())
+        .unwrap_or_else(|| sender_id.clone());
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "teams_conversation_id".into(),
+        serde_json::Value::String(conversation_id.clone()),
+    );
+    metadata.insert(
+        "teams_service_url".into(),
+        serde_json::Value::String(service_url),
+    );
+    if let Some(id) = activity.id.clone() {
+        metadata.insert("teams_activity_id".into(), serde_json::Value::String(id));
+    }
+    if let Some(channel_id) = activity
+        .channel_data
+        .as_ref()
+        .and_then(|data| data.get("teamsChannelId"))
+        .and_then(|v| v.as_str())
+    {
+        metadata.insert(
+            "teams_channel_id".into(),
+            serde_json::Value::String(channel_id.to_string()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(sender_name.clone()),
+    );
+
+    let inbound = InboundMessage {
+        id: activity
+            .id
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "teams".into(),
+        adapter: Some(state.runtime_key.clone()),
+        conversation_id: format!("teams:{conversation_id}"),
+        sender_id,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(sender_name),
+    };
+
+    let tx = state.inbound_tx.read().await;
+    let Some(tx) = tx.as_ref() else {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Remove `<at>...</at>` mention spans Teams embeds in message text.
+fn strip_mention_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut remaining = text;
+    while let Some(start) = remaining.find("<at>") {
+        result.push_str(&remaining[..start]);
+        match remaining[start..].find("</at>") {
+            Some(end) => remaining = &remaining[start + end + "</at>".len()..],
+            None => {
+                remaining = "";
+            }
+        }
+    }
+    result.push_str(remaining);
+    result.trim().to_string()
+}
+
+/// Render a generic [`Card`] as an Adaptive Card attachment.
+fn adaptive_card_attachment(card: &Card) -> serde_json::Value {
+    let mut body = Vec::new();
+    if let Some(title) = &card.title {
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": title,
+            "weight": "Bolder",
+            "size": "Medium",
+            "wrap": true,
+        }));
+    }
+    if let Some(description) = &card.description {
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": description,
+            "wrap": true,
+        }));
+    }
+    if !card.fields.is_empty() {
+        let facts: Vec<serde_json::Value> = card
+            .fields
+            .iter()
+            .map(|field| serde_json::json!({"title": field.name, "value": field.value}))
+            .collect();
+        body.push(serde_json::json!({"type": "FactSet", "facts": facts}));
+    }
+    if let Some(footer) = &card.footer {
+        body.push(serde_json::json!({
+            "type": "TextBlock",
+            "text": footer,
+            "isSubtle": true,
+            "size": "Small",
+            "wrap": true,
+        }));
+    }
+
+    let mut content = serde_json::json!({
+        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+        "type": "AdaptiveCard",
+        "version": "1.4",
+        "body": body,
+    });
+    if let Some(url) = &card.url
+        && let Some(object) = content.as_object_mut()
+    {
+        object.insert(
+            "actions".into(),
+            serde_json::json!([{"type": "Action.OpenUrl", "title": "Open", "url": url}]),
+        );
+    }
+
+    serde_json::json!({
+        "contentType": "application/vnd.microsoft.card.adaptive",
+        "content": content,
+    })
+}
+
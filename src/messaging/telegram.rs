@@ -1,9 +1,12 @@
 //! Telegram messaging adapter using teloxide.
 
-use crate::config::TelegramPermissions;
+use crate::config::{TelegramCommandConfig, TelegramPermissions, TelegramSttConfig};
 use crate::messaging::apply_runtime_adapter_to_conversation_id;
 use crate::messaging::traits::{InboundStream, Messaging};
-use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse, StatusUpdate};
+use crate::{
+    Attachment, ButtonStyle, InboundMessage, InteractiveElements, MessageContent,
+    OutboundResponse, StatusUpdate,
+};
 
 use anyhow::Context as _;
 use arc_swap::ArcSwap;
@@ -11,12 +14,15 @@ use regex::Regex;
 use teloxide::payloads::setters::*;
 use teloxide::requests::{Request, Requester};
 use teloxide::types::{
-    ChatAction, ChatId, FileId, InputFile, InputPollOption, MediaKind, MessageId, MessageKind,
-    ParseMode, ReactionType, ReplyParameters, UpdateKind, UserId,
+    BotCommand, CallbackQuery, ChatAction, ChatId, FileId, InlineKeyboardButton,
+    InlineKeyboardMarkup,
+    InputFile, InputPollOption, MediaKind, MessageEntityKind, MessageId, MessageKind, ParseMode,
+    ReactionType, ReplyParameters, ThreadId, Update, UpdateKind, UserId,
 };
 use teloxide::{ApiError, Bot, RequestError};
 
 use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock};
 use std::time::Instant;
 use tokio::sync::{RwLock, mpsc};
@@ -25,6 +31,17 @@ use tokio::task::JoinHandle;
 /// Maximum number of rejected DM users to remember.
 const REJECTED_USERS_CAPACITY: usize = 50;
 
+/// File in the instance directory holding Telegram messages queued for
+/// delayed delivery.
+const SCHEDULED_TELEGRAM_FILE: &str = "scheduled_telegram.json";
+
+/// File in the instance directory recording recently sent bot messages so
+/// the `telegram_message` tool can edit or delete them later.
+const SENT_TELEGRAM_FILE: &str = "sent_telegram.json";
+
+/// Most recent sent messages remembered per conversation.
+const SENT_MESSAGES_PER_CONVERSATION: usize = 20;
+
 /// Telegram adapter state.
 pub struct TelegramAdapter {
     runtime_key: String,
@@ -38,6 +55,213 @@ pub struct TelegramAdapter {
     typing_tasks: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
     /// Shutdown signal for the polling loop.
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+    /// Path to config.toml so admin commands can persist their changes.
+    config_path: Option<PathBuf>,
+    /// Chats silenced by `/mute_bot`, with the mute expiry per chat.
+    muted_until: Arc<RwLock<HashMap<i64, chrono::DateTime<chrono::Utc>>>>,
+    /// Transcription settings for inbound voice notes.
+    stt: Option<TelegramSttConfig>,
+    /// Configured `/command` definitions, registered via `setMyCommands`.
+    commands: Vec<TelegramCommandConfig>,
+    /// Webhook delivery settings; `None` long-polls getUpdates.
+    webhook: Option<TelegramWebhook>,
+    /// Feeds webhook updates into the processing loop while it is running.
+    update_tx: Arc<RwLock<Option<mpsc::Sender<Update>>>>,
+}
+
+/// Webhook registration details when the adapter runs in webhook mode.
+#[derive(Clone)]
+struct TelegramWebhook {
+    /// Public base URL updates are delivered under.
+    url: String,
+    /// Secret Telegram echoes back in `X-Telegram-Bot-Api-Secret-Token`.
+    secret: String,
+}
+
+/// Random secret for webhook registration when none is configured. Telegram
+/// accepts 1-256 chars of `A-Za-z0-9_-`, which base64url stays inside.
+fn generate_webhook_secret() -> String {
+    use base64::Engine as _;
+    use rand::RngCore as _;
+
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// One delayed outbound Telegram message awaiting its `post_at` delivery time.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct ScheduledTelegramMessage {
+    id: String,
+    /// Adapter runtime key that owns the entry, so multiple Telegram
+    /// instances sharing the store only deliver their own messages.
+    runtime_key: String,
+    chat_id: i64,
+    /// Forum topic thread to deliver into, if any.
+    thread_id: Option<i32>,
+    text: String,
+    /// Unix epoch seconds when the message should be sent.
+    post_at: i64,
+}
+
+fn scheduled_messages_path() -> PathBuf {
+    crate::config::Config::default_instance_dir().join(SCHEDULED_TELEGRAM_FILE)
+}
+
+/// Serializes read-modify-write cycles on the scheduled message store.
+fn scheduled_messages_lock() -> &'static std::sync::Mutex<()> {
+    static LOCK: std::sync::OnceLock<std::sync::Mutex<()>> = std::sync::OnceLock::new();
+    LOCK.get_or_init(|| std::sync::Mutex::new(()))
+}
+
+fn load_scheduled_messages(path: &Path) -> Vec<ScheduledTelegramMessage> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_scheduled_messages(path: &Path, entries: &[ScheduledTelegramMessage]) {
+    match serde_json::to_string_pretty(entries) {
+        Ok(contents) => {
+            if let Err(error) = std::fs::write(path, contents) {
+                tracing::warn!(%error, path = %path.display(), "failed to save scheduled telegram messages");
+            }
+        }
+        Err(error) => tracing::warn!(%error, "failed to serialize scheduled telegram messages"),
+    }
+}
+
+fn persist_scheduled_message(path: &Path, entry: &ScheduledTelegramMessage) {
+    let _guard = scheduled_messages_lock()
+        .lock()
+        .expect("scheduled telegram lock poisoned");
+    let mut entries = load_scheduled_messages(path);
+    entries.push(entry.clone());
+    save_scheduled_messages(path, &entries);
+}
+
+fn remove_scheduled_message(path: &Path, id: &str) {
+    let _guard = scheduled_messages_lock()
+        .lock()
+        .expect("scheduled telegram lock poisoned");
+    let mut entries = load_scheduled_messages(path);
+    let before = entries.len();
+    entries.retain(|entry| entry.id != id);
+    if entries.len() != before {
+        save_scheduled_messages(path, &entries);
+    }
+}
+
+/// One bot-sent message remembered so agents can edit or delete it later.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SentTelegramMessage {
+    /// Conversation the message was sent into.
+    pub conversation_id: String,
+    /// Adapter runtime key that sent the message, so multiple Telegram
+    /// instances sharing the store can tell their messages apart.
+    pub runtime_key: String,
+    pub chat_id: i64,
+    pub message_id: i32,
+    /// First line of the sent text, truncated, for identification.
+    pub preview: String,
+    /// Unix epoch seconds when the message was sent.
+    pub sent_at: i64,
+}
+
+pub(crate) fn sent_messages_path() -> PathBuf {
+    crate::config::Config::default_instance_dir().join(SENT_TELEGRAM_FILE)
+}
+
+/// Serializes read-modify-write cycles on the sent message store.
+fn sent_messages_lock() -> &'static std::sync::Mutex<()> {
+    static LOCK: std::sync::OnceLock<std::sync::Mutex<()>> = std::sync::OnceLock::new();
+    LOCK.get_or_init(|| std::sync::Mutex::new(()))
+}
+
+pub(crate) fn load_sent_messages(path: &Path) -> Vec<SentTelegramMessage> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_sent_messages(path: &Path, entries: &[SentTelegramMessage]) {
+    match serde_json::to_string_pretty(entries) {
+        Ok(contents) => {
+            if let Err(error) = std::fs::write(path, contents) {
+                tracing::warn!(%error, path = %path.display(), "failed to save sent telegram messages");
+            }
+        }
+        Err(error) => tracing::warn!(%error, "failed to serialize sent telegram messages"),
+    }
+}
+
+/// Append a sent message, dropping the oldest entries for its conversation
+/// beyond [`SENT_MESSAGES_PER_CONVERSATION`].
+fn record_sent_message(path: &Path, entry: SentTelegramMessage) {
+    let _guard = sent_messages_lock()
+        .lock()
+        .expect("sent telegram lock poisoned");
+    let mut entries = load_sent_messages(path);
+    let conversation_id = entry.conversation_id.clone();
+    entries.push(entry);
+    let tracked = entries
+        .iter()
+        .filter(|entry| entry.conversation_id == conversation_id)
+        .count();
+    if tracked > SENT_MESSAGES_PER_CONVERSATION {
+        let mut excess = tracked - SENT_MESSAGES_PER_CONVERSATION;
+        entries.retain(|entry| {
+            if excess > 0 && entry.conversation_id == conversation_id {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+    save_sent_messages(path, &entries);
+}
+
+pub(crate) fn update_sent_message_preview(path: &Path, chat_id: i64, message_id: i32, text: &str) {
+    let _guard = sent_messages_lock()
+        .lock()
+        .expect("sent telegram lock poisoned");
+    let mut entries = load_sent_messages(path);
+    let mut changed = false;
+    for entry in entries
+        .iter_mut()
+        .filter(|entry| entry.chat_id == chat_id && entry.message_id == message_id)
+    {
+        entry.preview = message_preview(text);
+        changed = true;
+    }
+    if changed {
+        save_sent_messages(path, &entries);
+    }
+}
+
+pub(crate) fn remove_sent_message(path: &Path, chat_id: i64, message_id: i32) {
+    let _guard = sent_messages_lock()
+        .lock()
+        .expect("sent telegram lock poisoned");
+    let mut entries = load_sent_messages(path);
+    let before = entries.len();
+    entries.retain(|entry| !(entry.chat_id == chat_id && entry.message_id == message_id));
+    if entries.len() != before {
+        save_sent_messages(path, &entries);
+    }
+}
+
+/// First line of the sent text, truncated for the tracking store.
+fn message_preview(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.len() > 96 {
+        let end = line.floor_char_boundary(93);
+        format!("{}...", &line[..end])
+    } else {
+        line.to_string()
+    }
 }
 
 /// Tracks an in-progress streaming message edit.
@@ -56,15 +280,47 @@ const FORMATTED_SPLIT_LENGTH: usize = MAX_MESSAGE_LENGTH / 2;
 /// Minimum interval between streaming edits to avoid rate limits.
 const STREAM_EDIT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1000);
 
+/// How long `/mute_bot` silences a chat when no duration is given.
+const DEFAULT_MUTE_DURATION: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Telegram's byte limit for inline keyboard callback data.
+const CALLBACK_DATA_LIMIT: usize = 64;
+
+/// Build the Bot API client, routed through `proxy` when one is configured.
+/// Uses teloxide's default reqwest settings so timeouts match `Bot::new`.
+fn bot_for_proxy(token: &str, proxy: Option<&str>) -> Bot {
+    let Some(proxy_url) = proxy.map(str::trim).filter(|proxy| !proxy.is_empty()) else {
+        return Bot::new(token);
+    };
+
+    let proxy = match reqwest::Proxy::all(proxy_url) {
+        Ok(proxy) => proxy,
+        Err(error) => {
+            tracing::warn!(%error, "invalid telegram proxy URL, connecting directly");
+            return Bot::new(token);
+        }
+    };
+
+    match teloxide::net::default_reqwest_settings().proxy(proxy).build() {
+        Ok(client) => Bot::with_client(token, client),
+        Err(error) => {
+            tracing::warn!(%error, "failed to build proxied telegram client, connecting directly");
+            Bot::new(token)
+        }
+    }
+}
+
 impl TelegramAdapter {
     pub fn new(
         runtime_key: impl Into<String>,
         token: impl Into<String>,
         permissions: Arc<ArcSwap<TelegramPermissions>>,
+        config_path: Option<PathBuf>,
+        proxy: Option<String>,
     ) -> Self {
         let runtime_key = runtime_key.into();
         let token = token.into();
-        let bot = Bot::new(&token);
+        let bot = bot_for_proxy(&token, proxy.as_deref());
         Self {
             runtime_key,
             permissions,
@@ -74,9 +330,67 @@ impl TelegramAdapter {
             active_messages: Arc::new(RwLock::new(HashMap::new())),
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
+            config_path,
+            muted_until: Arc::new(RwLock::new(HashMap::new())),
+            stt: None,
+            commands: Vec::new(),
+            webhook: None,
+            update_tx: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Transcribe inbound voice notes through the configured STT endpoint,
+    /// injecting the transcript as the message text.
+    pub fn with_stt(mut self, stt: TelegramSttConfig) -> Self {
+        self.stt = Some(stt);
+        self
+    }
+
+    /// Register `/command` definitions: they become the bot's command menu
+    /// on `start`, and matching inbound messages carry their target agent in
+    /// metadata like the Slack adapter's slash commands.
+    pub fn with_commands(mut self, commands: Vec<TelegramCommandConfig>) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Switch the adapter to webhook delivery: `start` registers
+    /// `{url}/api/telegram/webhook/{runtime_key}` with Telegram instead of
+    /// long polling, and the API route feeds updates in via `inject_update`.
+    ///
+    /// Since we register the webhook ourselves, delivery auth is never
+    /// optional: an empty `secret` is replaced with a random one that is
+    /// sent along during registration.
+    pub fn with_webhook(mut self, url: impl Into<String>, secret: impl Into<String>) -> Self {
+        let mut secret = secret.into();
+        if secret.is_empty() {
+            secret = generate_webhook_secret();
+        }
+        self.webhook = Some(TelegramWebhook {
+            url: url.into(),
+            secret,
+        });
+        self
+    }
+
+    /// Secret webhook deliveries must carry in `X-Telegram-Bot-Api-Secret-Token`.
+    pub fn webhook_secret(&self) -> Option<&str> {
+        self.webhook.as_ref().map(|webhook| webhook.secret.as_str())
+    }
+
+    /// Feed one webhook update into the processing loop.
+    pub async fn inject_update(&self, update: Update) -> crate::Result<()> {
+        let sender = self.update_tx.read().await.clone();
+        let Some(sender) = sender else {
+            return Err(anyhow::anyhow!("telegram adapter is not accepting webhook updates").into());
+        };
+        sender
+            .send(update)
+            .await
+            .map_err(|_| anyhow::anyhow!("telegram update loop has stopped"))?;
+        Ok(())
+    }
+
     fn extract_chat_id(&self, message: &InboundMessage) -> anyhow::Result<ChatId> {
         let id = message
             .metadata
@@ -96,6 +410,95 @@ impl TelegramAdapter {
         Ok(MessageId(id))
     }
 
+    /// Forum topic thread the triggering message belongs to, if any.
+    fn extract_thread_id(&self, message: &InboundMessage) -> Option<ThreadId> {
+        message
+            .metadata
+            .get("telegram_thread_id")
+            .and_then(|v| v.as_i64())
+            .map(|v| ThreadId(MessageId(v as i32)))
+    }
+
+    /// Persist a message for delivery at `post_at` and arm its timer. The
+    /// entry survives restarts; `start` re-arms anything still pending.
+    fn schedule_message(
+        &self,
+        chat_id: ChatId,
+        thread_id: Option<ThreadId>,
+        text: String,
+        post_at: i64,
+    ) {
+        let entry = ScheduledTelegramMessage {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            runtime_key: self.runtime_key.clone(),
+            chat_id: chat_id.0,
+            thread_id: thread_id.map(|thread| thread.0.0),
+            text,
+            post_at,
+        };
+        persist_scheduled_message(&scheduled_messages_path(), &entry);
+        tracing::info!(
+            id = %entry.id,
+            chat_id = entry.chat_id,
+            post_at = entry.post_at,
+            "queued telegram message for scheduled delivery"
+        );
+        self.spawn_scheduled_delivery(entry);
+    }
+
+    /// Sleep until the entry's `post_at`, send it, and clear it from the
+    /// store. Failed sends keep the entry so the next restart retries it.
+    fn spawn_scheduled_delivery(&self, entry: ScheduledTelegramMessage) {
+        let bot = self.bot.clone();
+        tokio::spawn(async move {
+            let delay = entry.post_at - chrono::Utc::now().timestamp();
+            if delay > 0 {
+                tokio::time::sleep(std::time::Duration::from_secs(delay as u64)).await;
+            }
+            let thread_id = entry.thread_id.map(|thread| ThreadId(MessageId(thread)));
+            match send_formatted(&bot, ChatId(entry.chat_id), &entry.text, None, thread_id).await {
+                Ok(_) => remove_scheduled_message(&scheduled_messages_path(), &entry.id),
+                Err(error) => tracing::warn!(
+                    %error,
+                    id = %entry.id,
+                    chat_id = entry.chat_id,
+                    "scheduled telegram delivery failed; entry kept for retry on restart"
+                ),
+            }
+        });
+    }
+
+    /// Re-arm timers for scheduled messages persisted by a previous run.
+    fn resume_scheduled_messages(&self) {
+        for entry in load_scheduled_messages(&scheduled_messages_path()) {
+            if entry.runtime_key == self.runtime_key {
+                self.spawn_scheduled_delivery(entry);
+            }
+        }
+    }
+
+    /// Record a sent bot message so the `telegram_message` tool can edit or
+    /// delete it later.
+    fn record_outbound_message(
+        &self,
+        conversation_id: &str,
+        chat_id: ChatId,
+        message_id: MessageId,
+        text: &str,
+    ) {
+        record_sent_message(
+            &sent_messages_path(),
+            SentTelegramMessage {
+                conversation_id: conversation_id.to_string(),
+                runtime_key: self.runtime_key.clone(),
+                chat_id: chat_id.0,
+                message_id: message_id.0,
+                preview: message_preview(text),
+                sent_at: chrono::Utc::now().timestamp(),
+            },
+        );
+    }
+
     async fn stop_typing(&self, conversation_id: &str) {
         if let Some(handle) = self.typing_tasks.write().await.remove(conversation_id) {
             handle.abort();
@@ -114,6 +517,9 @@ impl Messaging for TelegramAdapter {
 
         *self.shutdown_tx.write().await = Some(shutdown_tx);
 
+        // Re-arm delayed sends that were queued before the last shutdown.
+        self.resume_scheduled_messages();
+
         // Resolve bot identity
         let me = self
             .bot
@@ -129,14 +535,99 @@ impl Messaging for TelegramAdapter {
             "telegram connected"
         );
 
+        // Publish the configured commands as the bot's command menu. A
+        // failure only costs autocomplete, so it does not abort startup.
+        if !self.commands.is_empty() {
+            let menu: Vec<BotCommand> = self
+                .commands
+                .iter()
+                .map(|command| {
+                    BotCommand::new(
+                        command.command.trim_start_matches('/').to_string(),
+                        command
+                            .description
+                            .clone()
+                            .unwrap_or_else(|| format!("Ask {}", command.agent_id)),
+                    )
+                })
+                .collect();
+            if let Err(error) = self.bot.set_my_commands(menu).send().await {
+                tracing::warn!(%error, "failed to register telegram command menu");
+            }
+        }
+
+        // Updates funnel through one channel regardless of how they arrive:
+        // the long-poll producer below, or `inject_update` from the API
+        // webhook route when webhook delivery is configured.
+        let (update_tx, mut update_rx) = mpsc::channel::<Update>(256);
+
+        if let Some(webhook) = &self.webhook {
+            let endpoint = format!(
+                "{}/api/telegram/webhook/{}",
+                webhook.url.trim_end_matches('/'),
+                self.runtime_key
+            );
+            let url: reqwest::Url = endpoint
+                .parse()
+                .with_context(|| format!("invalid telegram webhook URL '{endpoint}'"))?;
+            let request = self
+                .bot
+                .set_webhook(url)
+                .secret_token(webhook.secret.clone());
+            request
+                .send()
+                .await
+                .context("failed to register Telegram webhook")?;
+            tracing::info!(%endpoint, "telegram webhook registered");
+            *self.update_tx.write().await = Some(update_tx);
+        } else {
+            let bot = self.bot.clone();
+            tokio::spawn(async move {
+                // Clear any webhook left over from a previous run: Telegram
+                // rejects getUpdates while one is registered.
+                if let Err(error) = bot.delete_webhook().send().await {
+                    tracing::debug!(%error, "telegram deleteWebhook failed");
+                }
+
+                let mut offset = 0i32;
+                loop {
+                    match bot.get_updates().offset(offset).timeout(10).send().await {
+                        Ok(updates) => {
+                            for update in updates {
+                                offset = update.id.as_offset();
+                                if update_tx.send(update).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        Err(error) => {
+                            if update_tx.is_closed() {
+                                return;
+                            }
+                            tracing::error!(%error, "telegram getUpdates failed");
+                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        }
+                    }
+                }
+            });
+        }
+
         let bot = self.bot.clone();
         let runtime_key = self.runtime_key.clone();
         let permissions = self.permissions.clone();
         let bot_user_id = self.bot_user_id.clone();
         let bot_username = self.bot_username.clone();
+        let config_path = self.config_path.clone();
+        let muted_until = self.muted_until.clone();
+        let stt = self.stt.clone();
+        // Map configured /commands to their target agents for routing.
+        let commands: HashMap<String, String> = self
+            .commands
+            .iter()
+            .map(|command| (command.command.clone(), command.agent_id.clone()))
+            .collect();
 
         tokio::spawn(async move {
-            let mut offset = 0i32;
             // Track users whose DMs were rejected so we can nudge them when they're allowed.
             let mut rejected_users: VecDeque<(ChatId, i64)> = VecDeque::new();
             // Snapshot the current allow list so we can detect changes.
@@ -145,17 +636,12 @@ impl Messaging for TelegramAdapter {
             loop {
                 tokio::select! {
                     _ = shutdown_rx.recv() => {
-                        tracing::info!("telegram polling loop shutting down");
+                        tracing::info!("telegram update loop shutting down");
                         break;
                     }
-                    result = bot.get_updates().offset(offset).timeout(10).send() => {
-                        let updates = match result {
-                            Ok(updates) => updates,
-                            Err(error) => {
-                                tracing::error!(%error, "telegram getUpdates failed");
-                                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                                continue;
-                            }
+                    update = update_rx.recv() => {
+                        let Some(update) = update else {
+                            break;
                         };
 
                         // Check if the allow list changed and nudge newly-allowed users.
@@ -189,100 +675,23 @@ impl Messaging for TelegramAdapter {
                             last_allowed = current_permissions.dm_allowed_users.clone();
                         }
 
-                        for update in updates {
-                            offset = update.id.as_offset();
-
-                            let message = match &update.kind {
-                                UpdateKind::Message(message) => message,
-                                _ => continue,
-                            };
-
-                            let bot_id = *bot_user_id.read().await;
-
-                            // Skip our own messages
-                            if let Some(from) = &message.from
-                                && bot_id.is_some_and(|id| from.id == id) {
-                                    continue;
-                                }
-
-                            let permissions = permissions.load();
-
-                            let chat_id = message.chat.id.0;
-                            let is_private = message.chat.is_private();
-
-                            // DM filter: in private chats, check dm_allowed_users
-                            if is_private {
-                                if let Some(from) = &message.from
-                                    && !permissions.dm_allowed_users.is_empty()
-                                        && !permissions
-                                            .dm_allowed_users
-                                            .contains(&(from.id.0 as i64))
-                                    {
-                                        // Remember this user so we can nudge them if they're added later.
-                                        let entry = (message.chat.id, from.id.0 as i64);
-                                        if !rejected_users.iter().any(|(_, uid)| *uid == entry.1) {
-                                            if rejected_users.len() >= REJECTED_USERS_CAPACITY {
-                                                rejected_users.pop_front();
-                                            }
-                                            rejected_users.push_back(entry);
-                                        }
-                                        continue;
-                                    }
-                            } else if let Some(filter) = &permissions.chat_filter {
-                                // Chat filter: if configured, only allow listed group/channel chats
-                                if !filter.contains(&chat_id) {
-                                    tracing::debug!(
-                                        chat_id,
-                                        ?filter,
-                                        "telegram message rejected by chat filter"
-                                    );
-                                    continue;
-                                }
-                            }
-
-                            // Extract text content
-                            let text = extract_text(message);
-                            if text.is_none() && !has_attachments(message) {
-                                continue;
-                            }
-
-                            let content = build_content(&bot, message, &text).await;
-                            let base_conversation_id = format!("telegram:{chat_id}");
-                            let conversation_id = apply_runtime_adapter_to_conversation_id(
-                                &runtime_key,
-                                base_conversation_id,
-                            );
-                            let sender_id = message
-                                .from
-                                .as_ref()
-                                .map(|u| u.id.0.to_string())
-                                .unwrap_or_default();
-
-                            let (metadata, formatted_author) = build_metadata(
-                                message,
-                                &*bot_username.read().await,
-                            );
-
-                            let inbound = InboundMessage {
-                                id: message.id.0.to_string(),
-                                source: "telegram".into(),
-                                adapter: Some(runtime_key.clone()),
-                                conversation_id,
-                                sender_id,
-                                agent_id: None,
-                                content,
-                                timestamp: message.date,
-                                metadata,
-                                formatted_author,
-                            };
-
-                            if let Err(error) = inbound_tx.send(inbound).await {
-                                tracing::warn!(
-                                    %error,
-                                    "failed to send inbound message from Telegram (receiver dropped)"
-                                );
-                                return;
-                            }
+                        let keep_going = process_update(
+                            &bot,
+                            &update,
+                            &runtime_key,
+                            &permissions,
+                            &bot_user_id,
+                            &bot_username,
+                            config_path.as_deref(),
+                            &muted_until,
+                            stt.as_ref(),
+                            &commands,
+                            &mut rejected_users,
+                            &inbound_tx,
+                        )
+                        .await;
+                        if !keep_going {
+                            return;
                         }
                     }
                 }
@@ -299,18 +708,44 @@ impl Messaging for TelegramAdapter {
         response: OutboundResponse,
     ) -> crate::Result<()> {
         let chat_id = self.extract_chat_id(message)?;
+        let thread_id = self.extract_thread_id(message);
 
         match response {
             OutboundResponse::Text(text) => {
                 self.stop_typing(&message.conversation_id).await;
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                let sent = send_formatted(&self.bot, chat_id, &text, None, thread_id).await?;
+                if let Some(message_id) = sent {
+                    self.record_outbound_message(
+                        &message.conversation_id,
+                        chat_id,
+                        message_id,
+                        &text,
+                    );
+                }
             }
-            OutboundResponse::RichMessage { text, poll, .. } => {
+            OutboundResponse::RichMessage {
+                text,
+                interactive_elements,
+                poll,
+                ..
+            } => {
                 self.stop_typing(&message.conversation_id).await;
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                let keyboard = build_inline_keyboard(&interactive_elements);
+                let sent = send_formatted_with_keyboard(
+                    &self.bot, chat_id, &text, None, keyboard, thread_id,
+                )
+                .await?;
+                if let Some(message_id) = sent {
+                    self.record_outbound_message(
+                        &message.conversation_id,
+                        chat_id,
+                        message_id,
+                        &text,
+                    );
+                }
 
                 if let Some(poll_data) = poll {
-                    send_poll(&self.bot, chat_id, &poll_data).await?;
+                    send_poll(&self.bot, chat_id, &poll_data, thread_id).await?;
                 }
             }
             OutboundResponse::ThreadReply {
@@ -321,7 +756,15 @@ impl Messaging for TelegramAdapter {
 
                 // Telegram doesn't have named threads. Reply to the source message instead.
                 let reply_to = self.extract_message_id(message).ok();
-                send_formatted(&self.bot, chat_id, &text, reply_to).await?;
+                let sent = send_formatted(&self.bot, chat_id, &text, reply_to, thread_id).await?;
+                if let Some(message_id) = sent {
+                    self.record_outbound_message(
+                        &message.conversation_id,
+                        chat_id,
+                        message_id,
+                        &text,
+                    );
+                }
             }
             OutboundResponse::File {
                 filename,
@@ -335,16 +778,19 @@ impl Messaging for TelegramAdapter {
                 // Fall back to send_document for everything else.
                 if mime_type.starts_with("audio/") {
                     let input_file = InputFile::memory(data.clone()).file_name(filename.clone());
+                    let mut request = self.bot.send_audio(chat_id, input_file);
+                    if let Some(thread) = thread_id {
+                        request = request.message_thread_id(thread);
+                    }
                     let sent = if let Some(ref caption_text) = caption {
                         let html_caption = markdown_to_telegram_html(caption_text);
-                        self.bot
-                            .send_audio(chat_id, input_file)
+                        request
                             .caption(&html_caption)
                             .parse_mode(ParseMode::Html)
                             .send()
                             .await
                     } else {
-                        self.bot.send_audio(chat_id, input_file).send().await
+                        request.send().await
                     };
 
                     if let Err(error) = sent {
@@ -355,6 +801,9 @@ impl Messaging for TelegramAdapter {
                             );
                             let fallback_file = InputFile::memory(data).file_name(filename);
                             let mut request = self.bot.send_audio(chat_id, fallback_file);
+                            if let Some(thread) = thread_id {
+                                request = request.message_thread_id(thread);
+                            }
                             if let Some(caption_text) = caption {
                                 request = request.caption(caption_text);
                             }
@@ -369,16 +818,19 @@ impl Messaging for TelegramAdapter {
                     }
                 } else {
                     let input_file = InputFile::memory(data.clone()).file_name(filename.clone());
+                    let mut request = self.bot.send_document(chat_id, input_file);
+                    if let Some(thread) = thread_id {
+                        request = request.message_thread_id(thread);
+                    }
                     let sent = if let Some(ref caption_text) = caption {
                         let html_caption = markdown_to_telegram_html(caption_text);
-                        self.bot
-                            .send_document(chat_id, input_file)
+                        request
                             .caption(&html_caption)
                             .parse_mode(ParseMode::Html)
                             .send()
                             .await
                     } else {
-                        self.bot.send_document(chat_id, input_file).send().await
+                        request.send().await
                     };
 
                     if let Err(error) = sent {
@@ -389,6 +841,9 @@ impl Messaging for TelegramAdapter {
                             );
                             let fallback_file = InputFile::memory(data).file_name(filename);
                             let mut request = self.bot.send_document(chat_id, fallback_file);
+                            if let Some(thread) = thread_id {
+                                request = request.message_thread_id(thread);
+                            }
                             if let Some(caption_text) = caption {
                                 request = request.caption(caption_text);
                             }
@@ -406,6 +861,10 @@ impl Messaging for TelegramAdapter {
             OutboundResponse::Reaction(emoji) => {
                 let message_id = self.extract_message_id(message)?;
 
+                // Map the reaction intent onto Telegram's fixed emoji set;
+                // keep the raw input when the vocabulary doesn't know it.
+                let emoji = crate::messaging::reactions::to_telegram_emoji(&emoji)
+                    .unwrap_or(emoji);
                 let reaction = ReactionType::Emoji {
                     emoji: emoji.clone(),
                 };
@@ -428,9 +887,11 @@ impl Messaging for TelegramAdapter {
             OutboundResponse::StreamStart => {
                 self.stop_typing(&message.conversation_id).await;
 
-                let placeholder = self
-                    .bot
-                    .send_message(chat_id, "...")
+                let mut request = self.bot.send_message(chat_id, "...");
+                if let Some(thread) = thread_id {
+                    request = request.message_thread_id(thread);
+                }
+                let placeholder = request
                     .send()
                     .await
                     .context("failed to send stream placeholder")?;
@@ -480,10 +941,19 @@ impl Messaging for TelegramAdapter {
                 }
             }
             OutboundResponse::StreamEnd => {
-                self.active_messages
+                let finished = self
+                    .active_messages
                     .write()
                     .await
                     .remove(&message.conversation_id);
+                if let Some(stream) = finished {
+                    self.record_outbound_message(
+                        &message.conversation_id,
+                        stream.chat_id,
+                        stream.message_id,
+                        "(streamed reply)",
+                    );
+                }
             }
             OutboundResponse::Status(status) => {
                 self.send_status(message, status).await?;
@@ -492,11 +962,14 @@ impl Messaging for TelegramAdapter {
             OutboundResponse::RemoveReaction(_) => {} // no-op
             OutboundResponse::Ephemeral { text, .. } => {
                 // Telegram has no ephemeral messages — send as regular text
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                send_formatted(&self.bot, chat_id, &text, None, thread_id).await?;
             }
-            OutboundResponse::ScheduledMessage { text, .. } => {
-                // Telegram has no scheduled messages — send immediately
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+            OutboundResponse::ScheduledMessage { text, post_at } => {
+                if post_at <= chrono::Utc::now().timestamp() {
+                    send_formatted(&self.bot, chat_id, &text, None, thread_id).await?;
+                } else {
+                    self.schedule_message(chat_id, thread_id, text, post_at);
+                }
             }
         }
 
@@ -511,6 +984,7 @@ impl Messaging for TelegramAdapter {
         match status {
             StatusUpdate::Thinking => {
                 let chat_id = self.extract_chat_id(message)?;
+                let thread_id = self.extract_thread_id(message);
                 let bot = self.bot.clone();
                 let conversation_id = message.conversation_id.clone();
 
@@ -518,11 +992,11 @@ impl Messaging for TelegramAdapter {
                 // Send one immediately, then repeat every 4 seconds.
                 let handle = tokio::spawn(async move {
                     loop {
-                        if let Err(error) = bot
-                            .send_chat_action(chat_id, ChatAction::Typing)
-                            .send()
-                            .await
-                        {
+                        let mut request = bot.send_chat_action(chat_id, ChatAction::Typing);
+                        if let Some(thread) = thread_id {
+                            request = request.message_thread_id(thread);
+                        }
+                        if let Err(error) = request.send().await {
                             tracing::debug!(%error, "failed to send typing indicator");
                             break;
                         }
@@ -544,19 +1018,38 @@ impl Messaging for TelegramAdapter {
     }
 
     async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        // Targets are a chat ID, optionally suffixed `:{thread_id}` to reach
+        // a specific forum topic.
+        let (chat_part, thread_part) = match target.split_once(':') {
+            Some((chat, thread)) => (chat, Some(thread)),
+            None => (target, None),
+        };
         let chat_id = ChatId(
-            target
+            chat_part
                 .parse::<i64>()
                 .context("invalid telegram chat id for broadcast target")?,
         );
+        let thread_id = thread_part
+            .map(|thread| thread.parse::<i32>())
+            .transpose()
+            .context("invalid telegram thread id for broadcast target")?
+            .map(|thread| ThreadId(MessageId(thread)));
 
         if let OutboundResponse::Text(text) = response {
-            send_formatted(&self.bot, chat_id, &text, None).await?;
-        } else if let OutboundResponse::RichMessage { text, poll, .. } = response {
-            send_formatted(&self.bot, chat_id, &text, None).await?;
+            send_formatted(&self.bot, chat_id, &text, None, thread_id).await?;
+        } else if let OutboundResponse::RichMessage {
+            text,
+            interactive_elements,
+            poll,
+            ..
+        } = response
+        {
+            let keyboard = build_inline_keyboard(&interactive_elements);
+            send_formatted_with_keyboard(&self.bot, chat_id, &text, None, keyboard, thread_id)
+                .await?;
 
             if let Some(poll_data) = poll {
-                send_poll(&self.bot, chat_id, &poll_data).await?;
+                send_poll(&self.bot, chat_id, &poll_data, thread_id).await?;
             }
         }
 
@@ -579,47 +1072,564 @@ impl Messaging for TelegramAdapter {
             handle.abort();
         }
 
-        // Signal the polling loop to stop
+        // Signal the update loop to stop and close the webhook intake
         if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
             tx.send(()).await.ok();
         }
+        *self.update_tx.write().await = None;
+
+        tracing::info!("telegram adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Helper functions --
+
+/// Extract text content from a Telegram message.
+fn extract_text(message: &teloxide::types::Message) -> Option<String> {
+    match &message.kind {
+        MessageKind::Common(common) => match &common.media_kind {
+            MediaKind::Text(text) => Some(text.text.clone()),
+            MediaKind::Photo(photo) => photo.caption.clone(),
+            MediaKind::Document(doc) => doc.caption.clone(),
+            MediaKind::Video(video) => video.caption.clone(),
+            MediaKind::Voice(voice) => voice.caption.clone(),
+            MediaKind::Audio(audio) => audio.caption.clone(),
+            // Stickers and locations carry no text — describe them so agents
+            // can react instead of the message being dropped.
+            MediaKind::Sticker(sticker) => {
+                let mut description = String::from("[sticker");
+                if let Some(emoji) = &sticker.sticker.emoji {
+                    description.push(' ');
+                    description.push_str(emoji);
+                }
+                if let Some(set_name) = &sticker.sticker.set_name {
+                    description.push_str(" from set ");
+                    description.push_str(set_name);
+                }
+                description.push(']');
+                Some(description)
+            }
+            MediaKind::Location(location) => Some(format!(
+                "[location: {:.6}, {:.6}]",
+                location.location.latitude, location.location.longitude
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check if a message contains file attachments.
+fn has_attachments(message: &teloxide::types::Message) -> bool {
+    match &message.kind {
+        MessageKind::Common(common) => matches!(
+            &common.media_kind,
+            MediaKind::Photo(_)
+                | MediaKind::Document(_)
+                | MediaKind::Video(_)
+                | MediaKind::Voice(_)
+                | MediaKind::Audio(_)
+        ),
+        _ => false,
+    }
+}
+
+/// An admin command issued inside a Telegram group.
+#[derive(Debug, Clone, PartialEq)]
+enum AdminCommand {
+    /// Add a user to the DM allow list.
+    Allow(Option<i64>),
+    /// Silence the bot in this chat for the given duration.
+    Mute(std::time::Duration),
+    /// Lift an active mute in this chat.
+    Unmute,
+    /// Set or clear the group trigger prefix.
+    SetTrigger(Option<String>),
+}
+
+/// Parse a group admin command, tolerating the `/command@botname` form.
+fn parse_admin_command(text: &str, bot_username: Option<&str>) -> Option<AdminCommand> {
+    let mut parts = text.trim().splitn(2, char::is_whitespace);
+    let command = parts.next()?;
+    let argument = parts.next().map(str::trim).filter(|arg| !arg.is_empty());
+
+    let command = match command.split_once('@') {
+        Some((name, suffix)) => {
+            // A suffixed command addressed to another bot is not ours.
+            if bot_username.is_none_or(|own| !suffix.eq_ignore_ascii_case(own)) {
+                return None;
+            }
+            name
+        }
+        None => command,
+    };
+
+    match command {
+        "/allow" => Some(AdminCommand::Allow(
+            argument.and_then(|arg| arg.parse::<i64>().ok()),
+        )),
+        "/mute_bot" => {
+            let duration = argument
+                .map(parse_duration)
+                .unwrap_or(Some(DEFAULT_MUTE_DURATION))?;
+            Some(AdminCommand::Mute(duration))
+        }
+        "/unmute_bot" => Some(AdminCommand::Unmute),
+        "/set_trigger" => Some(AdminCommand::SetTrigger(
+            argument
+                .filter(|arg| !arg.eq_ignore_ascii_case("off"))
+                .map(str::to_string),
+        )),
+        _ => None,
+    }
+}
+
+/// Match a message against the configured `/command` definitions, tolerating
+/// the `@botname` suffix Telegram appends in group chats. Returns the matched
+/// command and its target agent.
+fn parse_bot_command(
+    text: &str,
+    commands: &HashMap<String, String>,
+    bot_username: Option<&str>,
+) -> Option<(String, String)> {
+    let first = text.split_whitespace().next()?;
+    if !first.starts_with('/') {
+        return None;
+    }
+    let command = match first.split_once('@') {
+        Some((name, target)) => {
+            if let Some(username) = bot_username
+                && !target.eq_ignore_ascii_case(username)
+            {
+                return None;
+            }
+            name
+        }
+        None => first,
+    };
+    commands
+        .get(command)
+        .map(|agent_id| (command.to_string(), agent_id.clone()))
+}
+
+/// Parse durations like `90s`, `30m`, `1h`, `2d`. Bare numbers are minutes.
+fn parse_duration(arg: &str) -> Option<std::time::Duration> {
+    let arg = arg.trim();
+    let (number, unit) = match arg.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((split, _)) => arg.split_at(split),
+        None => (arg, "m"),
+    };
+    let number: u64 = number.parse().ok()?;
+    let seconds = match unit {
+        "s" => number,
+        "m" => number * 60,
+        "h" => number * 3600,
+        "d" => number * 86400,
+        _ => return None,
+    };
+    Some(std::time::Duration::from_secs(seconds))
+}
+
+/// Resolve the `/allow` target from a reply or an inline text mention.
+fn allow_target_from_message(message: &teloxide::types::Message) -> Option<i64> {
+    if let Some(replied) = message.reply_to_message()
+        && let Some(user) = &replied.from
+    {
+        return Some(user.id.0 as i64);
+    }
+    message
+        .entities()
+        .into_iter()
+        .flatten()
+        .find_map(|entity| match &entity.kind {
+            MessageEntityKind::TextMention { user } => Some(user.id.0 as i64),
+            _ => None,
+        })
+}
+
+/// Apply a group admin command after verifying the sender administers the chat.
+/// Filter and convert one Telegram update into an inbound message: permission
+/// gates, admin commands, mute and trigger-prefix handling all live here so
+/// long polling and webhook delivery share the same pipeline. Returns `false`
+/// when the inbound receiver is gone and update processing should stop.
+#[allow(clippy::too_many_arguments)]
+async fn process_update(
+    bot: &Bot,
+    update: &Update,
+    runtime_key: &str,
+    permissions: &Arc<ArcSwap<TelegramPermissions>>,
+    bot_user_id: &Arc<RwLock<Option<UserId>>>,
+    bot_username: &Arc<RwLock<Option<String>>>,
+    config_path: Option<&Path>,
+    muted_until: &Arc<RwLock<HashMap<i64, chrono::DateTime<chrono::Utc>>>>,
+    stt: Option<&TelegramSttConfig>,
+    commands: &HashMap<String, String>,
+    rejected_users: &mut VecDeque<(ChatId, i64)>,
+    inbound_tx: &mpsc::Sender<InboundMessage>,
+) -> bool {
+    let message = match &update.kind {
+        UpdateKind::Message(message) => message,
+        UpdateKind::CallbackQuery(query) => {
+            return handle_callback_query(bot, query, runtime_key, permissions, inbound_tx).await;
+        }
+        _ => return true,
+    };
+
+    let bot_id = *bot_user_id.read().await;
+
+    // Skip our own messages
+    if let Some(from) = &message.from
+        && bot_id.is_some_and(|id| from.id == id)
+    {
+        return true;
+    }
+
+    let current = permissions.load();
+
+    let chat_id = message.chat.id.0;
+    let is_private = message.chat.is_private();
+
+    // DM filter: in private chats, check dm_allowed_users
+    if is_private {
+        if let Some(from) = &message.from
+            && !current.dm_allowed_users.is_empty()
+            && !current.dm_allowed_users.contains(&(from.id.0 as i64))
+        {
+            // Remember this user so we can nudge them if they're added later.
+            let entry = (message.chat.id, from.id.0 as i64);
+            if !rejected_users.iter().any(|(_, uid)| *uid == entry.1) {
+                if rejected_users.len() >= REJECTED_USERS_CAPACITY {
+                    rejected_users.pop_front();
+                }
+                rejected_users.push_back(entry);
+            }
+            return true;
+        }
+    } else if let Some(filter) = &current.chat_filter {
+        // Chat filter: if configured, only allow listed group/channel chats
+        if !filter.contains(&chat_id) {
+            tracing::debug!(chat_id, ?filter, "telegram message rejected by chat filter");
+            return true;
+        }
+    }
+
+    // Extract text content
+    let text = extract_text(message);
+    if text.is_none() && !has_attachments(message) {
+        return true;
+    }
+
+    // Group admin commands are handled before mute/trigger gating so admins
+    // can always reach the bot.
+    if !is_private && let Some(raw) = text.as_deref() {
+        let command = {
+            let username = bot_username.read().await;
+            parse_admin_command(raw, username.as_deref())
+        };
+        if let Some(command) = command {
+            handle_admin_command(
+                bot,
+                message,
+                command,
+                permissions,
+                muted_until,
+                config_path,
+                runtime_key,
+            )
+            .await;
+            return true;
+        }
+    }
+
+    // Skip chats silenced by /mute_bot until the mute expires.
+    if !is_private {
+        let mut muted = muted_until.write().await;
+        if let Some(until) = muted.get(&chat_id).copied() {
+            if until > chrono::Utc::now() {
+                return true;
+            }
+            muted.remove(&chat_id);
+        }
+    }
+
+    // Configured /commands are explicit invocations: they bypass the trigger
+    // prefix and carry their target agent in metadata.
+    let command_route = {
+        let username = bot_username.read().await;
+        text.as_deref()
+            .and_then(|raw| parse_bot_command(raw, commands, username.as_deref()))
+    };
+
+    // Trigger prefix: in groups, only respond to prefixed messages, stripping
+    // the prefix before processing.
+    let text = match current.trigger_prefix.as_deref() {
+        Some(prefix) if !is_private && command_route.is_none() => {
+            match text.as_deref().and_then(|t| t.strip_prefix(prefix)) {
+                Some(stripped) => Some(stripped.trim_start().to_string()),
+                None => return true,
+            }
+        }
+        _ => text,
+    };
+
+    // Voice notes: transcribe so agents can respond to what was said. Fails
+    // open — a dead STT endpoint leaves the note as a plain attachment.
+    let text = if let (Some(stt), Some(voice)) = (stt, extract_voice(message)) {
+        if stt.enabled {
+            match transcribe_voice(bot, stt, voice).await {
+                Ok(transcript) if !transcript.trim().is_empty() => Some(match text {
+                    Some(caption) => format!("{caption}\n\n{transcript}"),
+                    None => transcript,
+                }),
+                Ok(_) => text,
+                Err(error) => {
+                    tracing::warn!(%error, "telegram voice transcription failed");
+                    text
+                }
+            }
+        } else {
+            text
+        }
+    } else {
+        text
+    };
+
+    let content = build_content(bot, message, &text).await;
+    // Forum topics get their own conversation so per-topic context stays separate.
+    let base_conversation_id = match message.is_topic_message.then_some(message.thread_id).flatten()
+    {
+        Some(thread) => format!("telegram:{chat_id}:{}", thread.0.0),
+        None => format!("telegram:{chat_id}"),
+    };
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(runtime_key, base_conversation_id);
+    let sender_id = message
+        .from
+        .as_ref()
+        .map(|u| u.id.0.to_string())
+        .unwrap_or_default();
+
+    let (mut metadata, formatted_author) = build_metadata(message, &*bot_username.read().await);
+    if let Some((command, agent_id)) = command_route {
+        metadata.insert(
+            "telegram_command".into(),
+            serde_json::Value::String(command),
+        );
+        // Embed the agent_id hint so the router can honour command-specific
+        // routing without requiring a separate binding entry per command.
+        metadata.insert(
+            "telegram_command_agent_id".into(),
+            serde_json::Value::String(agent_id),
+        );
+    }
+
+    let inbound = InboundMessage {
+        id: message.id.0.to_string(),
+        source: "telegram".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id,
+        sender_id,
+        agent_id: None,
+        content,
+        timestamp: message.date,
+        metadata,
+        formatted_author,
+    };
+
+    if let Err(error) = inbound_tx.send(inbound).await {
+        tracing::warn!(
+            %error,
+            "failed to send inbound message from Telegram (receiver dropped)"
+        );
+        return false;
+    }
+
+    true
+}
+
+async fn handle_admin_command(
+    bot: &Bot,
+    message: &teloxide::types::Message,
+    command: AdminCommand,
+    permissions: &Arc<ArcSwap<TelegramPermissions>>,
+    muted_until: &Arc<RwLock<HashMap<i64, chrono::DateTime<chrono::Utc>>>>,
+    config_path: Option<&Path>,
+    runtime_key: &str,
+) {
+    let Some(from) = &message.from else {
+        return;
+    };
+
+    let admins = match bot.get_chat_administrators(message.chat.id).send().await {
+        Ok(admins) => admins,
+        Err(error) => {
+            tracing::warn!(%error, "failed to fetch telegram chat administrators");
+            return;
+        }
+    };
+    if !admins.iter().any(|member| member.user.id == from.id) {
+        reply_in_chat(
+            bot,
+            message,
+            "Only group administrators can use bot admin commands.",
+        )
+        .await;
+        return;
+    }
+
+    let instance = runtime_key.split_once(':').map(|(_, name)| name);
+
+    let reply = match command {
+        AdminCommand::Allow(explicit_id) => {
+            let target = explicit_id.or_else(|| allow_target_from_message(message));
+            match target {
+                Some(user_id) => {
+                    let current = permissions.load_full();
+                    if current.dm_allowed_users.contains(&user_id) {
+                        format!("User {user_id} is already on the DM allow list.")
+                    } else {
+                        let mut updated = (*current).clone();
+                        updated.dm_allowed_users.push(user_id);
+                        permissions.store(Arc::new(updated));
+                        match config_path.map(|path| persist_allowed_user(path, instance, user_id))
+                        {
+                            Some(Err(error)) => {
+                                tracing::warn!(
+                                    %error,
+                                    "failed to persist telegram allow list change"
+                                );
+                                format!(
+                                    "Added user {user_id} to the DM allow list (until restart — updating config.toml failed)."
+                                )
+                            }
+                            _ => format!("Added user {user_id} to the DM allow list."),
+                        }
+                    }
+                }
+                None => {
+                    "Usage: /allow <user id>, or reply to a message from the user.".to_string()
+                }
+            }
+        }
+        AdminCommand::Mute(duration) => {
+            let until = chrono::Utc::now()
+                + chrono::Duration::from_std(duration)
+                    .unwrap_or_else(|_| chrono::Duration::hours(1));
+            muted_until.write().await.insert(message.chat.id.0, until);
+            format!(
+                "Muted in this chat until {}.",
+                until.format("%Y-%m-%d %H:%M UTC")
+            )
+        }
+        AdminCommand::Unmute => {
+            muted_until.write().await.remove(&message.chat.id.0);
+            "Unmuted in this chat.".to_string()
+        }
+        AdminCommand::SetTrigger(prefix) => {
+            let mut updated = (*permissions.load_full()).clone();
+            updated.trigger_prefix = prefix.clone();
+            permissions.store(Arc::new(updated));
+            let persisted =
+                config_path.map(|path| persist_trigger_prefix(path, instance, prefix.as_deref()));
+            let suffix = match persisted {
+                Some(Err(error)) => {
+                    tracing::warn!(%error, "failed to persist telegram trigger prefix");
+                    " (until restart — updating config.toml failed)"
+                }
+                _ => "",
+            };
+            match prefix {
+                Some(prefix) => {
+                    format!("Responding only to messages starting with {prefix}{suffix}.")
+                }
+                None => format!("Trigger prefix cleared — responding to all messages{suffix}."),
+            }
+        }
+    };
 
-        tracing::info!("telegram adapter shut down");
-        Ok(())
+    reply_in_chat(bot, message, &reply).await;
+}
+
+/// Send a short reply to an admin command in the chat it came from.
+async fn reply_in_chat(bot: &Bot, message: &teloxide::types::Message, text: &str) {
+    let request = bot
+        .send_message(message.chat.id, text)
+        .reply_parameters(ReplyParameters::new(message.id));
+    if let Err(error) = request.send().await {
+        tracing::warn!(%error, "failed to send telegram admin command reply");
     }
 }
 
-// -- Helper functions --
+/// Find the `[messaging.telegram]` table (or a named instance's table) in a
+/// parsed config document.
+fn telegram_table_mut<'doc>(
+    doc: &'doc mut toml_edit::DocumentMut,
+    instance: Option<&str>,
+) -> Option<&'doc mut toml_edit::Table> {
+    let telegram = doc
+        .get_mut("messaging")?
+        .get_mut("telegram")?
+        .as_table_mut()?;
+    match instance {
+        Some(name) => telegram
+            .get_mut("instances")?
+            .as_array_of_tables_mut()?
+            .iter_mut()
+            .find(|table| table.get("name").and_then(|item| item.as_str()) == Some(name)),
+        None => Some(telegram),
+    }
+}
 
-/// Extract text content from a Telegram message.
-fn extract_text(message: &teloxide::types::Message) -> Option<String> {
-    match &message.kind {
-        MessageKind::Common(common) => match &common.media_kind {
-            MediaKind::Text(text) => Some(text.text.clone()),
-            MediaKind::Photo(photo) => photo.caption.clone(),
-            MediaKind::Document(doc) => doc.caption.clone(),
-            MediaKind::Video(video) => video.caption.clone(),
-            MediaKind::Voice(voice) => voice.caption.clone(),
-            MediaKind::Audio(audio) => audio.caption.clone(),
-            _ => None,
-        },
-        _ => None,
+/// Append a user to `dm_allowed_users` in config.toml, preserving formatting.
+fn persist_allowed_user(
+    config_path: &Path,
+    instance: Option<&str>,
+    user_id: i64,
+) -> anyhow::Result<()> {
+    let raw = std::fs::read_to_string(config_path).context("failed to read config.toml")?;
+    let mut doc = raw
+        .parse::<toml_edit::DocumentMut>()
+        .context("failed to parse config.toml")?;
+    let table = telegram_table_mut(&mut doc, instance)
+        .context("no matching [messaging.telegram] section in config.toml")?;
+    let users = table
+        .entry("dm_allowed_users")
+        .or_insert(toml_edit::Item::Value(toml_edit::Value::Array(
+            toml_edit::Array::new(),
+        )))
+        .as_array_mut()
+        .context("dm_allowed_users is not an array")?;
+    let id = user_id.to_string();
+    if !users.iter().any(|value| value.as_str() == Some(id.as_str())) {
+        users.push(id);
     }
+    std::fs::write(config_path, doc.to_string()).context("failed to write config.toml")?;
+    Ok(())
 }
 
-/// Check if a message contains file attachments.
-fn has_attachments(message: &teloxide::types::Message) -> bool {
-    match &message.kind {
-        MessageKind::Common(common) => matches!(
-            &common.media_kind,
-            MediaKind::Photo(_)
-                | MediaKind::Document(_)
-                | MediaKind::Video(_)
-                | MediaKind::Voice(_)
-                | MediaKind::Audio(_)
-        ),
-        _ => false,
+/// Set or remove `trigger_prefix` in config.toml, preserving formatting.
+fn persist_trigger_prefix(
+    config_path: &Path,
+    instance: Option<&str>,
+    prefix: Option<&str>,
+) -> anyhow::Result<()> {
+    let raw = std::fs::read_to_string(config_path).context("failed to read config.toml")?;
+    let mut doc = raw
+        .parse::<toml_edit::DocumentMut>()
+        .context("failed to parse config.toml")?;
+    let table = telegram_table_mut(&mut doc, instance)
+        .context("no matching [messaging.telegram] section in config.toml")?;
+    match prefix {
+        Some(prefix) => {
+            table["trigger_prefix"] = toml_edit::value(prefix);
+        }
+        None => {
+            table.remove("trigger_prefix");
+        }
     }
+    std::fs::write(config_path, doc.to_string()).context("failed to write config.toml")?;
+    Ok(())
 }
 
 /// Build `MessageContent` from a Telegram message.
@@ -662,6 +1672,73 @@ async fn build_content(
     }
 }
 
+/// The voice note attached to a message, if there is one.
+fn extract_voice(message: &teloxide::types::Message) -> Option<&teloxide::types::Voice> {
+    match &message.kind {
+        MessageKind::Common(common) => match &common.media_kind {
+            MediaKind::Voice(voice) => Some(&voice.voice),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Download a voice note's OGG and run it through the configured
+/// OpenAI-compatible transcription endpoint.
+async fn transcribe_voice(
+    bot: &Bot,
+    stt: &TelegramSttConfig,
+    voice: &teloxide::types::Voice,
+) -> anyhow::Result<String> {
+    let url = resolve_file_url(bot, &voice.file.id.0).await?;
+    let audio = reqwest::get(&url)
+        .await
+        .context("failed to download telegram voice file")?
+        .error_for_status()
+        .context("telegram voice file download failed")?
+        .bytes()
+        .await
+        .context("failed to read telegram voice file")?;
+
+    let part = reqwest::multipart::Part::bytes(audio.to_vec())
+        .file_name("voice.ogg")
+        .mime_str(voice.mime_type.as_ref().map_or("audio/ogg", |m| m.as_ref()))
+        .context("failed to build transcription part")?;
+    let form = reqwest::multipart::Form::new()
+        .part("file", part)
+        .text("model", stt.model.clone());
+
+    let mut request = reqwest::Client::new()
+        .post(format!(
+            "{}/audio/transcriptions",
+            stt.api_base.trim_end_matches('/')
+        ))
+        .multipart(form);
+    if !stt.api_key.is_empty() {
+        request = request.bearer_auth(&stt.api_key);
+    }
+
+    let response = request
+        .send()
+        .await
+        .context("failed to call transcription endpoint")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("transcription failed: HTTP {status}: {body}");
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .context("failed to parse transcription response")?;
+    Ok(body
+        .get("text")
+        .and_then(|t| t.as_str())
+        .unwrap_or_default()
+        .to_string())
+}
+
 /// Extract file attachment metadata from a Telegram message.
 fn extract_attachments(message: &teloxide::types::Message) -> Vec<Attachment> {
     let mut attachments = Vec::new();
@@ -782,6 +1859,184 @@ async fn resolve_file_url(bot: &Bot, file_id: &str) -> anyhow::Result<String> {
 }
 
 /// Build platform-specific metadata for a Telegram message.
+/// Route a pressed inline-keyboard button back to the agent as an interaction
+/// message, applying the same DM and chat gates as regular messages. Returns
+/// `false` when the inbound receiver is gone and update processing should stop.
+async fn handle_callback_query(
+    bot: &Bot,
+    query: &CallbackQuery,
+    runtime_key: &str,
+    permissions: &Arc<ArcSwap<TelegramPermissions>>,
+    inbound_tx: &mpsc::Sender<InboundMessage>,
+) -> bool {
+    // Dismiss the client-side loading state regardless of what happens next.
+    if let Err(error) = bot.answer_callback_query(query.id.clone()).send().await {
+        tracing::debug!(%error, "failed to answer telegram callback query");
+    }
+
+    // Buttons on messages too old for Telegram to resolve carry no chat
+    // context, so there is nothing to route the interaction to.
+    let Some(message) = &query.message else {
+        return true;
+    };
+    let chat_id = message.chat().id.0;
+    let is_private = message.chat().is_private();
+
+    let current = permissions.load();
+    if is_private {
+        if !current.dm_allowed_users.is_empty()
+            && !current.dm_allowed_users.contains(&(query.from.id.0 as i64))
+        {
+            return true;
+        }
+    } else if let Some(filter) = &current.chat_filter
+        && !filter.contains(&chat_id)
+    {
+        return true;
+    }
+
+    let Some(data) = query.data.clone() else {
+        return true;
+    };
+
+    let thread_id = message
+        .regular_message()
+        .filter(|message| message.is_topic_message)
+        .and_then(|message| message.thread_id);
+    let base_conversation_id = match thread_id {
+        Some(thread) => format!("telegram:{chat_id}:{}", thread.0.0),
+        None => format!("telegram:{chat_id}"),
+    };
+    let conversation_id = apply_runtime_adapter_to_conversation_id(runtime_key, base_conversation_id);
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "telegram_chat_id".into(),
+        serde_json::Value::Number(chat_id.into()),
+    );
+    metadata.insert(
+        "telegram_message_id".into(),
+        serde_json::Value::Number(message.id().0.into()),
+    );
+    metadata.insert(
+        "telegram_user_id".into(),
+        serde_json::Value::Number(query.from.id.0.into()),
+    );
+    metadata.insert("telegram_callback_action".into(), data.clone().into());
+    if let Some(thread) = thread_id {
+        metadata.insert(
+            "telegram_thread_id".into(),
+            serde_json::Value::Number(thread.0.0.into()),
+        );
+    }
+    let display_name = build_display_name(&query.from);
+    metadata.insert("display_name".into(), display_name.clone().into());
+    metadata.insert("sender_display_name".into(), display_name.clone().into());
+
+    let inbound = InboundMessage {
+        id: query.id.0.clone(),
+        source: "telegram".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id,
+        sender_id: query.from.id.0.to_string(),
+        agent_id: None,
+        content: MessageContent::Interaction {
+            action_id: data.clone(),
+            block_id: None,
+            values: vec![data],
+            label: None,
+            message_ts: Some(message.id().0.to_string()),
+        },
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(display_name),
+    };
+
+    if let Err(error) = inbound_tx.send(inbound).await {
+        tracing::warn!(
+            %error,
+            "failed to send telegram callback interaction (receiver dropped)"
+        );
+        return false;
+    }
+
+    true
+}
+
+/// Clip callback data to Telegram's 64-byte limit on a char boundary.
+fn clip_callback_data(data: &str) -> String {
+    if data.len() <= CALLBACK_DATA_LIMIT {
+        return data.to_string();
+    }
+    let mut end = CALLBACK_DATA_LIMIT;
+    while !data.is_char_boundary(end) {
+        end -= 1;
+    }
+    data[..end].to_string()
+}
+
+/// Map generic interactive elements onto a Telegram inline keyboard. Link
+/// buttons become URL buttons and everything else becomes a callback button
+/// carrying its `custom_id` (falling back to the label). Telegram has no
+/// select menus, so each option becomes its own callback button, two per row,
+/// with `custom_id:value` as the data.
+fn build_inline_keyboard(elements: &[InteractiveElements]) -> Option<InlineKeyboardMarkup> {
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = Vec::new();
+
+    for element in elements {
+        match element {
+            InteractiveElements::Buttons { buttons } => {
+                let mut row = Vec::new();
+                for button in buttons {
+                    if button.style == ButtonStyle::Link {
+                        let Some(url) = button.url.as_deref().and_then(|url| url.parse().ok())
+                        else {
+                            tracing::warn!(
+                                label = %button.label,
+                                "skipping telegram link button without a valid URL"
+                            );
+                            continue;
+                        };
+                        row.push(InlineKeyboardButton::url(button.label.clone(), url));
+                    } else {
+                        let data = button.custom_id.as_deref().unwrap_or(&button.label);
+                        row.push(InlineKeyboardButton::callback(
+                            button.label.clone(),
+                            clip_callback_data(data),
+                        ));
+                    }
+                }
+                if !row.is_empty() {
+                    rows.push(row);
+                }
+            }
+            InteractiveElements::Select { select } => {
+                for pair in select.options.chunks(2) {
+                    let row = pair
+                        .iter()
+                        .map(|option| {
+                            InlineKeyboardButton::callback(
+                                option.label.clone(),
+                                clip_callback_data(&format!(
+                                    "{}:{}",
+                                    select.custom_id, option.value
+                                )),
+                            )
+                        })
+                        .collect();
+                    rows.push(row);
+                }
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        None
+    } else {
+        Some(InlineKeyboardMarkup::new(rows))
+    }
+}
+
 fn build_metadata(
     message: &teloxide::types::Message,
     bot_username: &Option<String>,
@@ -797,6 +2052,13 @@ fn build_metadata(
         serde_json::Value::Number(message.id.0.into()),
     );
 
+    if message.is_topic_message && let Some(thread) = message.thread_id {
+        metadata.insert(
+            "telegram_thread_id".into(),
+            serde_json::Value::Number(thread.0.0.into()),
+        );
+    }
+
     let chat_type = if message.chat.is_private() {
         "private"
     } else if message.chat.is_group() {
@@ -841,6 +2103,56 @@ fn build_metadata(
 
     if let Some(bot_username) = bot_username {
         metadata.insert("telegram_bot_username".into(), bot_username.clone().into());
+
+        // Platform-neutral mention flag consumed by binding triggers.
+        let mention = format!("@{}", bot_username.to_lowercase());
+        let mentioned = extract_text(message).is_some_and(|text| {
+            let lowered = text.to_lowercase();
+            lowered.match_indices(&mention).any(|(at, matched)| {
+                lowered[at + matched.len()..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_')
+            })
+        });
+        let replied_to_bot = message
+            .reply_to_message()
+            .and_then(|reply| reply.from.as_ref())
+            .is_some_and(|from| {
+                from.is_bot
+                    && from
+                        .username
+                        .as_deref()
+                        .is_some_and(|username| username.eq_ignore_ascii_case(bot_username))
+            });
+        if mentioned || replied_to_bot {
+            metadata.insert("mentions_bot".into(), serde_json::Value::Bool(true));
+        }
+    }
+
+    // Structured sticker/location details alongside the descriptive text
+    if let MessageKind::Common(common) = &message.kind {
+        match &common.media_kind {
+            MediaKind::Sticker(sticker) => {
+                if let Some(emoji) = &sticker.sticker.emoji {
+                    metadata.insert("telegram_sticker_emoji".into(), emoji.clone().into());
+                }
+                if let Some(set_name) = &sticker.sticker.set_name {
+                    metadata.insert("telegram_sticker_set".into(), set_name.clone().into());
+                }
+            }
+            MediaKind::Location(location) => {
+                metadata.insert(
+                    "telegram_latitude".into(),
+                    serde_json::Value::from(location.location.latitude),
+                );
+                metadata.insert(
+                    "telegram_longitude".into(),
+                    serde_json::Value::from(location.location.longitude),
+                );
+            }
+            _ => {}
+        }
     }
 
     // Reply-to context for threading
@@ -880,7 +2192,12 @@ fn build_display_name(user: &teloxide::types::User) -> String {
 /// max 100 chars. `open_period` only supports 5–600 seconds so we only set it
 /// when `duration_hours` converts to ≤600s; otherwise the poll stays open
 /// indefinitely (until manually stopped via the Telegram client).
-async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Result<()> {
+async fn send_poll(
+    bot: &Bot,
+    chat_id: ChatId,
+    poll: &crate::Poll,
+    thread_id: Option<ThreadId>,
+) -> anyhow::Result<()> {
     let question = if poll.question.len() > 300 {
         format!(
             "{}…",
@@ -912,6 +2229,10 @@ async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Re
         .send_poll(chat_id, question, options)
         .is_anonymous(false);
 
+    if let Some(thread) = thread_id {
+        request = request.message_thread_id(thread);
+    }
+
     // Telegram's open_period only supports 5–600 seconds. Apply it when the
     // requested duration fits; otherwise leave unset so the poll stays open
     // indefinitely.
@@ -1004,7 +2325,7 @@ fn strip_html_tags(html: &str) -> String {
 ///
 /// Handles fenced code blocks, inline code, bold, italic, strikethrough,
 /// links, headers (rendered as bold), and blockquotes.
-fn markdown_to_telegram_html(markdown: &str) -> String {
+pub(crate) fn markdown_to_telegram_html(markdown: &str) -> String {
     let mut result = String::with_capacity(markdown.len());
     let mut in_code_block = false;
     let mut code_language = String::new();
@@ -1141,26 +2462,46 @@ async fn send_plain_text(
     chat_id: ChatId,
     text: &str,
     reply_to: Option<MessageId>,
-) -> anyhow::Result<()> {
+    thread_id: Option<ThreadId>,
+) -> anyhow::Result<MessageId> {
     let mut request = bot.send_message(chat_id, text);
     if let Some(reply_id) = reply_to {
         request = request.reply_parameters(ReplyParameters::new(reply_id));
     }
-    request
+    if let Some(thread) = thread_id {
+        request = request.message_thread_id(thread);
+    }
+    let sent = request
         .send()
         .await
         .context("failed to send telegram message")?;
-    Ok(())
+    Ok(sent.id)
 }
 
 /// Send a message with Telegram HTML formatting, splitting at the message
 /// length limit. Falls back to plain text if the API rejects the HTML.
+/// Returns the ID of the last message sent, if any, so callers can track it.
 async fn send_formatted(
     bot: &Bot,
     chat_id: ChatId,
     text: &str,
     reply_to: Option<MessageId>,
-) -> anyhow::Result<()> {
+    thread_id: Option<ThreadId>,
+) -> anyhow::Result<Option<MessageId>> {
+    send_formatted_with_keyboard(bot, chat_id, text, reply_to, None, thread_id).await
+}
+
+/// `send_formatted` with an optional inline keyboard attached to the final
+/// chunk, so buttons land under the end of a split message.
+async fn send_formatted_with_keyboard(
+    bot: &Bot,
+    chat_id: ChatId,
+    text: &str,
+    reply_to: Option<MessageId>,
+    mut keyboard: Option<InlineKeyboardMarkup>,
+    thread_id: Option<ThreadId>,
+) -> anyhow::Result<Option<MessageId>> {
+    let mut last_sent = None;
     let mut pending_chunks: VecDeque<String> =
         VecDeque::from(split_message(text, MAX_MESSAGE_LENGTH));
     while let Some(markdown_chunk) = pending_chunks.pop_front() {
@@ -1176,7 +2517,7 @@ async fn send_formatted(
             }
 
             let plain_chunk = strip_html_tags(&html_chunk);
-            send_plain_text(bot, chat_id, &plain_chunk, reply_to).await?;
+            last_sent = Some(send_plain_text(bot, chat_id, &plain_chunk, reply_to, thread_id).await?);
             continue;
         }
 
@@ -1186,19 +2527,112 @@ async fn send_formatted(
         if let Some(reply_id) = reply_to {
             request = request.reply_parameters(ReplyParameters::new(reply_id));
         }
-        if let Err(error) = request.send().await {
-            tracing::debug!(%error, "HTML send failed, retrying as plain text");
-            let plain_chunk = strip_html_tags(&html_chunk);
-            send_plain_text(bot, chat_id, &plain_chunk, reply_to).await?;
+        if let Some(thread) = thread_id {
+            request = request.message_thread_id(thread);
+        }
+        if pending_chunks.is_empty()
+            && let Some(markup) = keyboard.take()
+        {
+            request = request.reply_markup(teloxide::types::ReplyMarkup::InlineKeyboard(markup));
+        }
+        match request.send().await {
+            Ok(sent) => last_sent = Some(sent.id),
+            Err(error) => {
+                tracing::debug!(%error, "HTML send failed, retrying as plain text");
+                let plain_chunk = strip_html_tags(&html_chunk);
+                last_sent =
+                    Some(send_plain_text(bot, chat_id, &plain_chunk, reply_to, thread_id).await?);
+            }
         }
     }
-    Ok(())
+    Ok(last_sent)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn admin_commands_parse() {
+        assert_eq!(
+            parse_admin_command("/allow 12345", Some("spacebot")),
+            Some(AdminCommand::Allow(Some(12345)))
+        );
+        assert_eq!(
+            parse_admin_command("/mute_bot@spacebot 2h", Some("spacebot")),
+            Some(AdminCommand::Mute(std::time::Duration::from_secs(7200)))
+        );
+        assert_eq!(
+            parse_admin_command("/mute_bot", Some("spacebot")),
+            Some(AdminCommand::Mute(DEFAULT_MUTE_DURATION))
+        );
+        assert_eq!(
+            parse_admin_command("/set_trigger !ask", Some("spacebot")),
+            Some(AdminCommand::SetTrigger(Some("!ask".into())))
+        );
+        assert_eq!(
+            parse_admin_command("/set_trigger off", Some("spacebot")),
+            Some(AdminCommand::SetTrigger(None))
+        );
+        assert_eq!(
+            parse_admin_command("/allow@otherbot 1", Some("spacebot")),
+            None
+        );
+        assert_eq!(parse_admin_command("hello there", Some("spacebot")), None);
+    }
+
+    #[test]
+    fn durations_parse() {
+        assert_eq!(
+            parse_duration("90s"),
+            Some(std::time::Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_duration("15"),
+            Some(std::time::Duration::from_secs(900))
+        );
+        assert_eq!(
+            parse_duration("1h"),
+            Some(std::time::Duration::from_secs(3600))
+        );
+        assert_eq!(
+            parse_duration("2d"),
+            Some(std::time::Duration::from_secs(172_800))
+        );
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn admin_changes_persist_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[messaging.telegram]\nenabled = true\ntoken = \"tok\"\n\n[[messaging.telegram.instances]]\nname = \"support\"\ntoken = \"tok2\"\n",
+        )
+        .unwrap();
+
+        persist_allowed_user(&path, None, 42).unwrap();
+        persist_allowed_user(&path, None, 42).unwrap();
+        persist_allowed_user(&path, Some("support"), 7).unwrap();
+        persist_trigger_prefix(&path, None, Some("!ask")).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let doc: toml_edit::DocumentMut = written.parse().unwrap();
+        let telegram = &doc["messaging"]["telegram"];
+        assert_eq!(
+            telegram["dm_allowed_users"].as_array().unwrap().len(),
+            1,
+            "duplicate allow entries should be collapsed"
+        );
+        assert_eq!(telegram["trigger_prefix"].as_str(), Some("!ask"));
+        let instance = &telegram["instances"].as_array_of_tables().unwrap().get(0).unwrap();
+        assert_eq!(
+            instance["dm_allowed_users"].as_array().unwrap().iter().next().unwrap().as_str(),
+            Some("7")
+        );
+    }
+
     #[test]
     fn bold() {
         assert_eq!(
@@ -1366,4 +2800,235 @@ mod tests {
         assert!(should_retry_plain_caption(&parse_error));
         assert!(!should_retry_plain_caption(&non_parse_error));
     }
+
+    #[test]
+    fn inline_keyboard_maps_buttons_and_select_options() {
+        use crate::{Button, SelectMenu, SelectOption};
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        let elements = vec![
+            InteractiveElements::Buttons {
+                buttons: vec![
+                    Button {
+                        label: "Approve".into(),
+                        custom_id: Some("approve".into()),
+                        style: ButtonStyle::Primary,
+                        url: None,
+                    },
+                    Button {
+                        label: "Docs".into(),
+                        custom_id: None,
+                        style: ButtonStyle::Link,
+                        url: Some("https://example.com".into()),
+                    },
+                ],
+            },
+            InteractiveElements::Select {
+                select: SelectMenu {
+                    custom_id: "pick".into(),
+                    placeholder: None,
+                    options: vec![
+                        SelectOption {
+                            label: "One".into(),
+                            value: "1".into(),
+                            description: None,
+                            emoji: None,
+                        },
+                        SelectOption {
+                            label: "Two".into(),
+                            value: "2".into(),
+                            description: None,
+                            emoji: None,
+                        },
+                        SelectOption {
+                            label: "Three".into(),
+                            value: "3".into(),
+                            description: None,
+                            emoji: None,
+                        },
+                    ],
+                },
+            },
+        ];
+
+        let keyboard = build_inline_keyboard(&elements).expect("keyboard");
+        let rows = &keyboard.inline_keyboard;
+        assert_eq!(rows.len(), 3, "one button row + two select rows of two");
+        assert!(matches!(
+            &rows[0][0].kind,
+            InlineKeyboardButtonKind::CallbackData(data) if data == "approve"
+        ));
+        assert!(matches!(
+            &rows[0][1].kind,
+            InlineKeyboardButtonKind::Url(_)
+        ));
+        assert!(matches!(
+            &rows[1][0].kind,
+            InlineKeyboardButtonKind::CallbackData(data) if data == "pick:1"
+        ));
+        assert_eq!(rows[2].len(), 1);
+
+        assert!(build_inline_keyboard(&[]).is_none());
+    }
+
+    #[test]
+    fn callback_data_clips_to_telegram_limit() {
+        let long = "é".repeat(60);
+        let clipped = clip_callback_data(&long);
+        assert!(clipped.len() <= CALLBACK_DATA_LIMIT);
+        assert!(long.starts_with(&clipped));
+        assert_eq!(clip_callback_data("short"), "short");
+    }
+
+    #[test]
+    fn bot_commands_match_with_and_without_bot_suffix() {
+        let commands: HashMap<String, String> =
+            [("/ask".to_string(), "helper".to_string())].into();
+
+        assert_eq!(
+            parse_bot_command("/ask what is up", &commands, Some("spacebot")),
+            Some(("/ask".to_string(), "helper".to_string()))
+        );
+        assert_eq!(
+            parse_bot_command("/ask@spacebot hi", &commands, Some("spacebot")),
+            Some(("/ask".to_string(), "helper".to_string()))
+        );
+        // Addressed to a different bot in the same group.
+        assert_eq!(
+            parse_bot_command("/ask@otherbot hi", &commands, Some("spacebot")),
+            None
+        );
+        assert_eq!(parse_bot_command("/unknown", &commands, None), None);
+        assert_eq!(parse_bot_command("plain text", &commands, None), None);
+    }
+
+    #[test]
+    fn scheduled_message_store_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "spacebot-scheduled-telegram-{}.json",
+            uuid::Uuid::new_v4()
+        ));
+
+        let entry = ScheduledTelegramMessage {
+            id: "abcd1234".to_string(),
+            runtime_key: "telegram".to_string(),
+            chat_id: -1001234567890,
+            thread_id: Some(42),
+            text: "Following up as promised.".to_string(),
+            post_at: 4_102_444_800,
+        };
+        persist_scheduled_message(&path, &entry);
+
+        let loaded = load_scheduled_messages(&path);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].chat_id, -1001234567890);
+        assert_eq!(loaded[0].thread_id, Some(42));
+        assert_eq!(loaded[0].post_at, 4_102_444_800);
+
+        remove_scheduled_message(&path, "other-id");
+        assert_eq!(load_scheduled_messages(&path).len(), 1);
+
+        remove_scheduled_message(&path, "abcd1234");
+        assert!(load_scheduled_messages(&path).is_empty());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn location_messages_become_descriptive_text() {
+        let message: teloxide::types::Message = serde_json::from_value(serde_json::json!({
+            "message_id": 7,
+            "date": 1_700_000_000,
+            "chat": {"id": 99, "type": "private", "first_name": "Ada"},
+            "from": {"id": 5, "is_bot": false, "first_name": "Ada"},
+            "location": {"latitude": 48.858_4, "longitude": 2.294_5}
+        }))
+        .expect("location message should deserialize");
+
+        assert_eq!(
+            extract_text(&message).as_deref(),
+            Some("[location: 48.858400, 2.294500]")
+        );
+
+        let (metadata, _) = build_metadata(&message, &None);
+        assert_eq!(
+            metadata.get("telegram_latitude").and_then(|v| v.as_f64()),
+            Some(48.858_4)
+        );
+        assert_eq!(
+            metadata.get("telegram_longitude").and_then(|v| v.as_f64()),
+            Some(2.294_5)
+        );
+    }
+
+    #[test]
+    fn sticker_messages_become_descriptive_text() {
+        let message: teloxide::types::Message = serde_json::from_value(serde_json::json!({
+            "message_id": 8,
+            "date": 1_700_000_000,
+            "chat": {"id": 99, "type": "private", "first_name": "Ada"},
+            "from": {"id": 5, "is_bot": false, "first_name": "Ada"},
+            "sticker": {
+                "file_id": "sticker-file",
+                "file_unique_id": "sticker-unique",
+                "type": "regular",
+                "width": 512,
+                "height": 512,
+                "is_animated": false,
+                "is_video": false,
+                "emoji": "\u{1F44D}",
+                "set_name": "TestPack"
+            }
+        }))
+        .expect("sticker message should deserialize");
+
+        assert_eq!(
+            extract_text(&message).as_deref(),
+            Some("[sticker \u{1F44D} from set TestPack]")
+        );
+
+        let (metadata, _) = build_metadata(&message, &None);
+        assert_eq!(
+            metadata.get("telegram_sticker_set").and_then(|v| v.as_str()),
+            Some("TestPack")
+        );
+    }
+
+    #[test]
+    fn sent_message_store_prunes_per_conversation() {
+        let path = std::env::temp_dir().join(format!(
+            "spacebot-sent-telegram-{}.json",
+            uuid::Uuid::new_v4()
+        ));
+
+        for index in 0..(SENT_MESSAGES_PER_CONVERSATION + 2) {
+            record_sent_message(
+                &path,
+                SentTelegramMessage {
+                    conversation_id: "telegram_123".to_string(),
+                    runtime_key: "telegram".to_string(),
+                    chat_id: 123,
+                    message_id: index as i32,
+                    preview: format!("reply {index}"),
+                    sent_at: index as i64,
+                },
+            );
+        }
+
+        let entries = load_sent_messages(&path);
+        assert_eq!(entries.len(), SENT_MESSAGES_PER_CONVERSATION);
+        // The oldest entries for the conversation are dropped first.
+        assert_eq!(entries[0].message_id, 2);
+
+        update_sent_message_preview(&path, 123, 2, "corrected reply");
+        assert_eq!(load_sent_messages(&path)[0].preview, "corrected reply");
+
+        remove_sent_message(&path, 123, 2);
+        assert_eq!(
+            load_sent_messages(&path).len(),
+            SENT_MESSAGES_PER_CONVERSATION - 1
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
 }
@@ -16,12 +16,50 @@ pub struct HistoryMessage {
     pub is_bot: bool,
 }
 
+/// How a history fetch divides its message budget across sources.
+///
+/// Derived from the configured backfill limit so adapters apply the same
+/// split: threaded fetches spend most of the budget on the thread itself
+/// and the remainder on surrounding channel context, while reply chains
+/// are followed a bounded number of hops.
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryFetchPolicy {
+    /// Messages fetched from the thread (or the channel when unthreaded).
+    pub thread_limit: usize,
+    /// Recent channel messages fetched alongside a thread for context.
+    pub context_limit: usize,
+    /// Maximum referenced-message (reply) chain hops to follow.
+    pub chain_depth: usize,
+}
+
+impl HistoryFetchPolicy {
+    /// Split an overall message budget: two thirds for the thread, one
+    /// third for surrounding context, reply chains capped at ten hops.
+    pub fn from_limit(limit: usize) -> Self {
+        let context_limit = limit / 3;
+        Self {
+            thread_limit: limit.saturating_sub(context_limit).max(1),
+            context_limit,
+            chain_depth: limit.min(10),
+        }
+    }
+}
+
 /// Static trait for messaging adapters.
 /// Use this for type-safe implementations.
 pub trait Messaging: Send + Sync + 'static {
     /// Unique name for this adapter.
     fn name(&self) -> &str;
 
+    /// Whether this adapter can only deliver outbound messages.
+    ///
+    /// Outbound-only adapters (push notification targets) are registered for
+    /// broadcast and cron delivery but are never started for inbound
+    /// consumption and are excluded from inbound binding.
+    fn outbound_only(&self) -> bool {
+        false
+    }
+
     /// Start the adapter and return inbound message stream.
     fn start(&self) -> impl std::future::Future<Output = Result<InboundStream>> + Send;
 
@@ -76,6 +114,8 @@ pub trait Messaging: Send + Sync + 'static {
 pub trait MessagingDyn: Send + Sync + 'static {
     fn name(&self) -> &str;
 
+    fn outbound_only(&self) -> bool;
+
     fn start<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<InboundStream>> + Send + 'a>>;
@@ -118,6 +158,10 @@ impl<T: Messaging> MessagingDyn for T {
         Messaging::name(self)
     }
 
+    fn outbound_only(&self) -> bool {
+        Messaging::outbound_only(self)
+    }
+
     fn start<'a>(
         &'a self,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<InboundStream>> + Send + 'a>> {
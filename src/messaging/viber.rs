@@ -0,0 +1,377 @@
+//! Viber Bot API adapter, built on the shared [`httpbot`](super::httpbot)
+//! helpers.
+//!
+//! Inbound callbacks arrive on a webhook endpoint validated with the
+//! `X-Viber-Content-Signature` header: hex-encoded HMAC-SHA256 over the raw
+//! body, keyed with the bot auth token. Outbound messages go through
+//! `send_message` on the REST API; Viber flood-protects chatty bots, so sends
+//! are paced per receiver through an [`OutboundLimiter`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::{OutboundLimiter, split_message, verify_hmac_hex};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+const VIBER_API_BASE: &str = "https://chatapi.viber.com/pa";
+
+/// Viber rejects text messages above 7000 characters.
+const MAX_MESSAGE_LENGTH: usize = 7_000;
+
+/// Minimum spacing between sends to the same receiver; Viber rate-limits
+/// public accounts that burst messages.
+const MIN_SEND_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Viber adapter state.
+pub struct ViberAdapter {
+    runtime_key: String,
+    auth_token: String,
+    /// Display name shown as the message sender; Viber requires one on every
+    /// outbound message.
+    sender_name: String,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    limiter: OutboundLimiter,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    auth_token: String,
+    runtime_key: String,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+}
+
+impl ViberAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        auth_token: impl Into<String>,
+        sender_name: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            auth_token: auth_token.into(),
+            sender_name: sender_name.into(),
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            limiter: OutboundLimiter::new(MIN_SEND_INTERVAL),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Call a Viber REST endpoint. Viber reports failures as HTTP 200 with a
+    /// non-zero `status` field, so both layers are checked.
+    async fn api_post(&self, endpoint: &str, payload: serde_json::Value) -> crate::Result<()> {
+        let response = self
+            .client
+            .post(format!("{VIBER_API_BASE}/{endpoint}"))
+            .header("X-Viber-Auth-Token", &self.auth_token)
+            .json(&payload)
+            .send()
+            .await
+            .with_context(|| format!("failed to call Viber {endpoint}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("Viber {endpoint} failed: HTTP {status}").into());
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .with_context(|| format!("failed to parse Viber {endpoint} response"))?;
+        let status = body.get("status").and_then(|s| s.as_i64()).unwrap_or(0);
+        if status != 0 {
+            let message = body
+                .get("status_message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown error");
+            return Err(anyhow::anyhow!("Viber {endpoint} failed: {status} {message}").into());
+        }
+        Ok(())
+    }
+
+    async fn send_text(&self, receiver: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.limiter.acquire(receiver).await;
+            self.api_post(
+                "send_message",
+                json!({
+                    "receiver": receiver,
+                    "min_api_version": 1,
+                    "sender": { "name": self.sender_name },
+                    "type": "text",
+                    "text": chunk,
+                }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn receiver_for(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("viber_user_id")
+            .and_then(|v| v.as_str())
+            .context("missing viber_user_id in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for ViberAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            auth_token: self.auth_token.clone(),
+            runtime_key: self.runtime_key.clone(),
+            inbound_tx: self.inbound_tx.clone(),
+        };
+
+        let app = Router::new()
+            .route("/viber", post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind Viber webhook to {bind}"))?;
+        tracing::info!(%bind, "Viber webhook endpoint listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "Viber webhook endpoint exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.send_text(Self::receiver_for(message)?, &text).await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Media messages need public HTTPS URLs; describe the file instead
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment omitted: {filename})"),
+                    None => format!("(attachment omitted: {filename})"),
+                };
+                self.send_text(Self::receiver_for(message)?, &note).await
+            }
+            // No reaction or stream framing API for Viber bots
+            OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        let target = target.strip_prefix("viber:").unwrap_or(target);
+        self.send_text(target, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.api_post("get_account_info", json!({})).await
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("Viber adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    let Some(signature) = headers
+        .get("x-viber-content-signature")
+        .and_then(|v| v.to_str().ok())
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    if !verify_hmac_hex(&state.auth_token, body.as_bytes(), signature) {
+        tracing::warn!("rejected Viber webhook with bad signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Ok(payload) = serde_json::from_str::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    // Viber verifies the endpoint with a `webhook` callback on set_webhook;
+    // delivery/seen/subscribed events carry nothing to act on either.
+    let Some(inbound) = parse_event(&payload, &state.runtime_key) else {
+        return StatusCode::OK;
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+
+    StatusCode::OK
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Convert a Viber `message` callback into an inbound message.
+fn parse_event(payload: &serde_json::Value, runtime_key: &str) -> Option<InboundMessage> {
+    if payload.get("event").and_then(|e| e.as_str()) != Some("message") {
+        return None;
+    }
+    let message = payload.get("message")?;
+    if message.get("type").and_then(|t| t.as_str()) != Some("text") {
+        return None;
+    }
+    let text = message.get("text")?.as_str()?.trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+
+    let sender = payload.get("sender")?;
+    let user_id = sender.get("id")?.as_str()?.to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "viber_user_id".into(),
+        serde_json::Value::String(user_id.clone()),
+    );
+    if let Some(name) = sender.get("name").and_then(|n| n.as_str()) {
+        metadata.insert(
+            "viber_sender_name".into(),
+            serde_json::Value::String(name.to_string()),
+        );
+    }
+
+    let timestamp = payload
+        .get("timestamp")
+        .and_then(|t| t.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .unwrap_or_else(chrono::Utc::now);
+
+    Some(InboundMessage {
+        id: payload
+            .get("message_token")
+            .and_then(|t| t.as_i64())
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "viber".into(),
+        adapter: Some(runtime_key.to_string()),
+        conversation_id: format!("viber:{user_id}"),
+        sender_id: user_id,
+        agent_id: None,
+        content: MessageContent::Text(text),
+        timestamp,
+        metadata,
+        formatted_author: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_text_message_event() {
+        let payload = serde_json::json!({
+            "event": "message",
+            "timestamp": 1_700_000_000_000i64,
+            "message_token": 4_912_661_846_655_238_145i64,
+            "sender": { "id": "01234567890A=", "name": "Ada" },
+            "message": { "type": "text", "text": "hello" },
+        });
+        let inbound = parse_event(&payload, "viber").unwrap();
+        assert_eq!(inbound.conversation_id, "viber:01234567890A=");
+        assert_eq!(inbound.sender_id, "01234567890A=");
+        assert_eq!(
+            inbound.metadata.get("viber_sender_name").and_then(|v| v.as_str()),
+            Some("Ada")
+        );
+        assert_eq!(inbound.content.to_string(), "hello");
+    }
+
+    #[test]
+    fn skips_non_message_events_and_non_text_payloads() {
+        let webhook = serde_json::json!({ "event": "webhook", "timestamp": 1i64 });
+        assert!(parse_event(&webhook, "viber").is_none());
+
+        let seen = serde_json::json!({ "event": "seen", "user_id": "01234567890A=" });
+        assert!(parse_event(&seen, "viber").is_none());
+
+        let sticker = serde_json::json!({
+            "event": "message",
+            "sender": { "id": "01234567890A=" },
+            "message": { "type": "sticker", "sticker_id": 46_105 },
+        });
+        assert!(parse_event(&sticker, "viber").is_none());
+    }
+}
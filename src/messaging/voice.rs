@@ -0,0 +1,496 @@
+//! Phone call adapter backed by Twilio Voice.
+//!
+//! Twilio drives the call with TwiML webhooks, each authenticated via the
+//! `X-Twilio-Signature` HMAC over the request URL and params: inbound
+//! speech is transcribed by `<Gather input="speech">` and arrives as text,
+//! agent replies are
+//! synthesized back with `<Say>`. Each call gets its own conversation keyed
+//! by the Twilio `CallSid`, with caller metadata attached. While the agent is
+//! thinking, the call is held with short pauses and redirect loops until the
+//! reply lands in the per-call buffer.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use serde::Deserialize;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::sms::{twilio_request_url, verify_twilio_signature};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// How long `/voice/collect` waits for the agent before holding the call
+/// with a pause/redirect loop. Twilio gives webhooks ~15 seconds.
+const REPLY_WAIT_SECS: u64 = 10;
+
+/// Poll interval while waiting for a buffered reply.
+const REPLY_POLL_MILLIS: u64 = 250;
+
+/// Voice adapter state.
+pub struct VoiceAdapter {
+    runtime_key: String,
+    /// Twilio auth token, used to verify webhook signatures.
+    auth_token: String,
+    port: u16,
+    bind: String,
+    greeting: String,
+    /// TTS voice name passed to `<Say>`.
+    voice: String,
+    /// Speech recognition and TTS language, e.g. `en-US`.
+    language: String,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    /// Finished replies per conversation_id, spoken on the next poll.
+    response_buffers: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    /// Partially streamed replies, promoted to the buffer on `StreamEnd`.
+    stream_buffers: Arc<RwLock<HashMap<String, String>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    response_buffers: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    auth_token: String,
+    runtime_key: String,
+    greeting: String,
+    voice: String,
+    language: String,
+}
+
+/// Twilio's form-encoded voice webhook payload, reduced to what we consume.
+#[derive(Debug, Deserialize)]
+struct VoiceWebhook {
+    #[serde(rename = "CallSid")]
+    call_sid: Option<String>,
+    #[serde(rename = "From")]
+    from: Option<String>,
+    #[serde(rename = "To")]
+    to: Option<String>,
+    #[serde(rename = "SpeechResult")]
+    speech_result: Option<String>,
+}
+
+impl VoiceAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        auth_token: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+        greeting: impl Into<String>,
+        voice: impl Into<String>,
+        language: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            auth_token: auth_token.into(),
+            port,
+            bind: bind.into(),
+            greeting: greeting.into(),
+            voice: voice.into(),
+            language: language.into(),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            response_buffers: Arc::new(RwLock::new(HashMap::new())),
+            stream_buffers: Arc::new(RwLock::new(HashMap::new())),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Messaging for VoiceAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(64);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            inbound_tx: self.inbound_tx.clone(),
+            response_buffers: self.response_buffers.clone(),
+            auth_token: self.auth_token.clone(),
+            runtime_key: self.runtime_key.clone(),
+            greeting: self.greeting.clone(),
+            voice: self.voice.clone(),
+            language: self.language.clone(),
+        };
+
+        let app = Router::new()
+            .route("/voice", post(handle_answer))
+            .route("/voice/collect", post(handle_collect))
+            .route("/voice/poll", post(handle_poll))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind voice webhook to {bind}"))?;
+        tracing::info!(%bind, "voice webhook listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "voice webhook exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let conversation_id = &message.conversation_id;
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. } => {
+                self.response_buffers
+                    .write()
+                    .await
+                    .entry(conversation_id.clone())
+                    .or_default()
+                    .push(text);
+            }
+            // Chunks accumulate until the stream ends, then get spoken whole —
+            // a caller can't do anything with half a sentence of TTS
+            OutboundResponse::StreamChunk(text) => {
+                self.stream_buffers
+                    .write()
+                    .await
+                    .entry(conversation_id.clone())
+                    .or_default()
+                    .push_str(&text);
+            }
+            OutboundResponse::StreamEnd => {
+                let text = self.stream_buffers.write().await.remove(conversation_id);
+                if let Some(text) = text
+                    && !text.is_empty()
+                {
+                    self.response_buffers
+                        .write()
+                        .await
+                        .entry(conversation_id.clone())
+                        .or_default()
+                        .push(text);
+                }
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // Can't deliver files over a phone call; speak the caption
+                let note = match caption {
+                    Some(caption) => caption,
+                    None => format!("I prepared a file called {filename}, but can't send it over the phone."),
+                };
+                self.response_buffers
+                    .write()
+                    .await
+                    .entry(conversation_id.clone())
+                    .or_default()
+                    .push(note);
+            }
+            OutboundResponse::StreamStart
+            | OutboundResponse::Reaction(_)
+            | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Status(_) => {}
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("voice adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+/// Reject callbacks that don't carry a valid `X-Twilio-Signature`; Twilio
+/// signs every webhook with the account's auth token.
+fn check_signature(state: &AppState, headers: &HeaderMap, uri: &Uri, body: &str) -> bool {
+    let Some(signature) = headers
+        .get("x-twilio-signature")
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let url = twilio_request_url(headers, uri);
+    if !verify_twilio_signature(&state.auth_token, &url, body, signature) {
+        tracing::warn!("rejected voice webhook with bad signature");
+        return false;
+    }
+    true
+}
+
+/// Answers an incoming call: greet the caller and start speech gathering.
+async fn handle_answer(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    body: String,
+) -> axum::response::Response {
+    if !check_signature(&state, &headers, &uri, &body) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    twiml_response(say_and_gather(&state, &state.greeting)).into_response()
+}
+
+/// Receives a transcribed utterance, forwards it to the agent, and waits a
+/// short while for the reply before holding the call.
+async fn handle_collect(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    body: String,
+) -> axum::response::Response {
+    if !check_signature(&state, &headers, &uri, &body) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let Ok(webhook) = serde_urlencoded::from_str::<VoiceWebhook>(&body) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let Some(call_sid) = webhook.call_sid else {
+        return StatusCode::OK.into_response();
+    };
+    let conversation_id = format!("voice:{call_sid}");
+
+    let Some(speech) = webhook.speech_result.filter(|s| !s.trim().is_empty()) else {
+        // Nothing recognized: prompt again
+        return twiml_response(say_and_gather(&state, "Sorry, I didn't catch that."))
+            .into_response();
+    };
+
+    let caller = webhook.from.unwrap_or_else(|| "unknown".into());
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "voice_call_sid".into(),
+        serde_json::Value::String(call_sid.clone()),
+    );
+    metadata.insert(
+        "voice_caller".into(),
+        serde_json::Value::String(caller.clone()),
+    );
+    if let Some(called) = &webhook.to {
+        metadata.insert(
+            "voice_called".into(),
+            serde_json::Value::String(called.clone()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(caller.clone()),
+    );
+
+    let inbound = InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: "voice".into(),
+        adapter: Some(state.runtime_key.clone()),
+        conversation_id: conversation_id.clone(),
+        sender_id: caller.clone(),
+        agent_id: None,
+        content: MessageContent::Text(speech),
+        timestamp: chrono::Utc::now(),
+        metadata,
+        formatted_author: Some(caller),
+    };
+
+    {
+        let tx = state.inbound_tx.read().await;
+        let Some(tx) = tx.as_ref() else {
+            return StatusCode::SERVICE_UNAVAILABLE.into_response();
+        };
+        if tx.send(inbound).await.is_err() {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    match wait_for_reply(&state, &conversation_id).await {
+        Some(reply) => twiml_response(say_and_gather(&state, &reply)).into_response(),
+        None => twiml_response(hold_twiml(&state)).into_response(),
+    }
+}
+
+/// Redirect target while the agent is still thinking: speak the reply if it
+/// arrived, otherwise keep holding.
+async fn handle_poll(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    uri: Uri,
+    body: String,
+) -> axum::response::Response {
+    if !check_signature(&state, &headers, &uri, &body) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let Ok(webhook) = serde_urlencoded::from_str::<VoiceWebhook>(&body) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let Some(call_sid) = webhook.call_sid else {
+        return StatusCode::OK.into_response();
+    };
+    let conversation_id = format!("voice:{call_sid}");
+
+    match wait_for_reply(&state, &conversation_id).await {
+        Some(reply) => twiml_response(say_and_gather(&state, &reply)).into_response(),
+        None => twiml_response(hold_twiml(&state)).into_response(),
+    }
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Waits up to [`REPLY_WAIT_SECS`] for buffered replies, draining them into
+/// one utterance.
+async fn wait_for_reply(state: &AppState, conversation_id: &str) -> Option<String> {
+    let deadline =
+        tokio::time::Instant::now() + std::time::Duration::from_secs(REPLY_WAIT_SECS);
+    loop {
+        {
+            let mut buffers = state.response_buffers.write().await;
+            if let Some(replies) = buffers.remove(conversation_id)
+                && !replies.is_empty()
+            {
+                return Some(replies.join(" "));
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(REPLY_POLL_MILLIS)).await;
+    }
+}
+
+/// TwiML that speaks `text` and gathers the caller's next utterance.
+fn say_and_gather(state: &AppState, text: &str) -> String {
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "<Response>",
+            r#"<Say voice="{voice}" language="{language}">{text}</Say>"#,
+            r#"<Gather input="speech" action="/voice/collect" method="POST" language="{language}" speechTimeout="auto"/>"#,
+            r#"<Say voice="{voice}" language="{language}">Goodbye.</Say>"#,
+            "<Hangup/>",
+            "</Response>"
+        ),
+        voice = xml_escape(&state.voice),
+        language = xml_escape(&state.language),
+        text = xml_escape(text),
+    )
+}
+
+/// TwiML that holds the call briefly and polls again for the agent's reply.
+fn hold_twiml(_state: &AppState) -> String {
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "<Response>",
+        r#"<Pause length="3"/>"#,
+        r#"<Redirect method="POST">/voice/poll</Redirect>"#,
+        "</Response>"
+    )
+    .to_string()
+}
+
+fn twiml_response(twiml: String) -> impl IntoResponse {
+    ([("content-type", "text/xml")], twiml)
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_state() -> AppState {
+        AppState {
+            inbound_tx: Arc::new(RwLock::new(None)),
+            response_buffers: Arc::new(RwLock::new(HashMap::new())),
+            auth_token: "auth-token".into(),
+            runtime_key: "voice".into(),
+            greeting: "Hello!".into(),
+            voice: "alice".into(),
+            language: "en-US".into(),
+        }
+    }
+
+    #[test]
+    fn twiml_escapes_reply_text() {
+        let twiml = say_and_gather(&test_state(), "Use <b> & \"quotes\"");
+        assert!(twiml.contains("Use &lt;b&gt; &amp; &quot;quotes&quot;"));
+        assert!(twiml.contains(r#"<Gather input="speech" action="/voice/collect""#));
+        assert!(twiml.contains(r#"language="en-US""#));
+    }
+
+    #[tokio::test]
+    async fn stream_chunks_are_spoken_whole() {
+        let adapter = VoiceAdapter::new("voice", "auth-token", 0, "127.0.0.1", "Hi", "alice", "en-US");
+        let message = InboundMessage {
+            id: "1".into(),
+            source: "voice".into(),
+            adapter: Some("voice".into()),
+            conversation_id: "voice:CA123".into(),
+            sender_id: "+15550001".into(),
+            agent_id: None,
+            content: MessageContent::Text("hi".into()),
+            timestamp: chrono::Utc::now(),
+            metadata: HashMap::new(),
+            formatted_author: None,
+        };
+
+        adapter
+            .respond(&message, OutboundResponse::StreamChunk("Hello ".into()))
+            .await
+            .unwrap();
+        adapter
+            .respond(&message, OutboundResponse::StreamChunk("caller.".into()))
+            .await
+            .unwrap();
+        // Nothing speakable until the stream completes
+        assert!(adapter.response_buffers.read().await.is_empty());
+
+        adapter
+            .respond(&message, OutboundResponse::StreamEnd)
+            .await
+            .unwrap();
+        let buffers = adapter.response_buffers.read().await;
+        assert_eq!(buffers.get("voice:CA123").unwrap(), &vec!["Hello caller.".to_string()]);
+    }
+}
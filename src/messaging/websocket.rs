@@ -0,0 +1,435 @@
+//! WebSocket messaging adapter for custom frontends.
+//!
+//! Hosts a WebSocket server where clients exchange JSON frames: the client
+//! sends inbound messages and receives responses — including streaming
+//! chunks — pushed over the same socket. This complements the webhook
+//! adapter's request/poll model for integrations that want live delivery,
+//! e.g. custom chat frontends built without Discord or Slack.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use futures::{SinkExt as _, StreamExt as _};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{RwLock, mpsc};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request};
+
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Live connections keyed by conversation_id, for routing responses back to
+/// whichever socket last spoke in that conversation.
+type ConnectionMap = Arc<RwLock<HashMap<String, mpsc::Sender<ServerFrame>>>>;
+
+/// WebSocket adapter state.
+pub struct WebsocketAdapter {
+    port: u16,
+    bind: String,
+    auth_token: Option<String>,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    connections: ConnectionMap,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// A message frame sent by a connected client.
+#[derive(Debug, Deserialize)]
+struct ClientFrame {
+    /// Unique conversation identifier. Reuse the same ID to continue a conversation.
+    conversation_id: String,
+    /// Sender identifier (e.g. a username or service name).
+    #[serde(default = "default_sender")]
+    sender_id: String,
+    /// Message text content.
+    content: String,
+    /// Optional agent to route to (overrides binding resolution).
+    agent_id: Option<String>,
+}
+
+fn default_sender() -> String {
+    "websocket".into()
+}
+
+/// A response frame pushed to a connected client.
+#[derive(Debug, Clone, Serialize)]
+struct ServerFrame {
+    #[serde(rename = "type")]
+    frame_type: String,
+    content: Option<String>,
+    filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    caption: Option<String>,
+}
+
+impl WebsocketAdapter {
+    pub fn new(port: u16, bind: impl Into<String>, auth_token: Option<String>) -> Self {
+        Self {
+            port,
+            bind: bind.into(),
+            auth_token,
+            inbound_tx: Arc::new(RwLock::new(None)),
+            connections: Arc::new(RwLock::new(HashMap::new())),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Messaging for WebsocketAdapter {
+    fn name(&self) -> &str {
+        "websocket"
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx.clone());
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        if self.auth_token.is_none() {
+            tracing::warn!(
+                "websocket authentication is disabled because no auth token is configured"
+            );
+        }
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind websocket server to {bind}"))?;
+        tracing::info!(%bind, "websocket server listening");
+
+        let runtime_key = self.name().to_string();
+        let auth_token = self.auth_token.clone();
+        let connections = self.connections.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    accepted = listener.accept() => {
+                        let (stream, peer) = match accepted {
+                            Ok(accepted) => accepted,
+                            Err(error) => {
+                                tracing::warn!(%error, "websocket accept failed");
+                                continue;
+                            }
+                        };
+                        let inbound_tx = inbound_tx.clone();
+                        let auth_token = auth_token.clone();
+                        let connections = connections.clone();
+                        let runtime_key = runtime_key.clone();
+                        tokio::spawn(async move {
+                            if let Err(error) = handle_connection(
+                                stream,
+                                auth_token,
+                                inbound_tx,
+                                connections,
+                                runtime_key,
+                            )
+                            .await
+                            {
+                                tracing::debug!(%peer, %error, "websocket connection closed with error");
+                            }
+                        });
+                    }
+                }
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let Some(frame) = frame_for_response(response) else {
+            return Ok(());
+        };
+
+        let connections = self.connections.read().await;
+        let Some(tx) = connections.get(&message.conversation_id) else {
+            tracing::debug!(
+                conversation_id = %message.conversation_id,
+                "dropping response for disconnected websocket client"
+            );
+            return Ok(());
+        };
+
+        tx.send(frame).await.ok();
+        Ok(())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("websocket adapter shut down");
+        Ok(())
+    }
+}
+
+/// Runs the websocket handshake and per-connection read/write loop.
+async fn handle_connection(
+    stream: tokio::net::TcpStream,
+    auth_token: Option<String>,
+    inbound_tx: mpsc::Sender<InboundMessage>,
+    connections: ConnectionMap,
+    runtime_key: String,
+) -> anyhow::Result<()> {
+    // The error type is fixed by tungstenite's handshake callback signature.
+    #[allow(clippy::result_large_err)]
+    let socket = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response| {
+        if is_authorized(request, auth_token.as_deref()) {
+            Ok(response)
+        } else {
+            Err(ErrorResponse::new(Some("unauthorized".into())))
+        }
+    })
+    .await
+    .context("websocket handshake failed")?;
+
+    let (mut write, mut read) = socket.split();
+    let (frame_tx, mut frame_rx) = mpsc::channel::<ServerFrame>(64);
+    // Conversations this socket has spoken in, cleaned up on disconnect.
+    let mut registered: Vec<String> = Vec::new();
+
+    loop {
+        tokio::select! {
+            frame = frame_rx.recv() => {
+                let Some(frame) = frame else { break };
+                let json = serde_json::to_string(&frame)?;
+                write.send(WsMessage::Text(json.into())).await?;
+            }
+            message = read.next() => {
+                let Some(message) = message else { break };
+                match message? {
+                    WsMessage::Text(text) => {
+                        let frame: ClientFrame = match serde_json::from_str(&text) {
+                            Ok(frame) => frame,
+                            Err(error) => {
+                                let reply = ServerFrame {
+                                    frame_type: "error".into(),
+                                    content: Some(format!("invalid frame: {error}")),
+                                    filename: None,
+                                    caption: None,
+                                };
+                                write
+                                    .send(WsMessage::Text(serde_json::to_string(&reply)?.into()))
+                                    .await?;
+                                continue;
+                            }
+                        };
+
+                        let conversation_id = format!("websocket:{}", frame.conversation_id);
+                        if !registered.contains(&conversation_id) {
+                            registered.push(conversation_id.clone());
+                        }
+                        connections
+                            .write()
+                            .await
+                            .insert(conversation_id.clone(), frame_tx.clone());
+
+                        let mut metadata = HashMap::new();
+                        metadata.insert(
+                            "websocket_conversation_id".into(),
+                            serde_json::Value::String(frame.conversation_id.clone()),
+                        );
+                        metadata.insert(
+                            "display_name".into(),
+                            serde_json::Value::String(frame.sender_id.clone()),
+                        );
+
+                        let inbound = InboundMessage {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            source: "websocket".into(),
+                            adapter: Some(runtime_key.clone()),
+                            conversation_id,
+                            sender_id: frame.sender_id.clone(),
+                            agent_id: frame.agent_id.map(Into::into),
+                            content: MessageContent::Text(frame.content),
+                            timestamp: chrono::Utc::now(),
+                            metadata,
+                            formatted_author: Some(frame.sender_id),
+                        };
+
+                        if inbound_tx.send(inbound).await.is_err() {
+                            break;
+                        }
+                    }
+                    WsMessage::Ping(payload) => {
+                        write.send(WsMessage::Pong(payload)).await?;
+                    }
+                    WsMessage::Close(_) => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Drop routing entries that still point at this socket. A conversation
+    // may have been re-registered by a newer connection in the meantime.
+    let mut connections = connections.write().await;
+    for key in registered {
+        if connections
+            .get(&key)
+            .is_some_and(|tx| tx.same_channel(&frame_tx))
+        {
+            connections.remove(&key);
+        }
+    }
+
+    Ok(())
+}
+
+/// Maps an agent response to the frame pushed to the client, or `None` for
+/// variants that aren't meaningful over a raw socket.
+fn frame_for_response(response: OutboundResponse) -> Option<ServerFrame> {
+    let frame = match response {
+        OutboundResponse::Text(text)
+        | OutboundResponse::RichMessage { text, .. }
+        | OutboundResponse::ThreadReply { text, .. }
+        | OutboundResponse::Ephemeral { text, .. }
+        | OutboundResponse::ScheduledMessage { text, .. } => ServerFrame {
+            frame_type: "text".into(),
+            content: Some(text),
+            filename: None,
+            caption: None,
+        },
+        OutboundResponse::File {
+            filename, caption, ..
+        } => ServerFrame {
+            frame_type: "file".into(),
+            content: None,
+            filename: Some(filename),
+            caption,
+        },
+        OutboundResponse::StreamStart => ServerFrame {
+            frame_type: "stream_start".into(),
+            content: None,
+            filename: None,
+            caption: None,
+        },
+        OutboundResponse::StreamChunk(text) => ServerFrame {
+            frame_type: "stream_chunk".into(),
+            content: Some(text),
+            filename: None,
+            caption: None,
+        },
+        OutboundResponse::StreamEnd => ServerFrame {
+            frame_type: "stream_end".into(),
+            content: None,
+            filename: None,
+            caption: None,
+        },
+        OutboundResponse::Reaction(_)
+        | OutboundResponse::RemoveReaction(_)
+        | OutboundResponse::Status(_) => return None,
+    };
+    Some(frame)
+}
+
+/// Checks the handshake request against the configured token: `Authorization:
+/// Bearer`, an `x-websocket-token` header, or a `token` query parameter (for
+/// browser clients that can't set headers).
+fn is_authorized(request: &Request, expected_token: Option<&str>) -> bool {
+    let Some(expected_token) = expected_token else {
+        return true;
+    };
+
+    if request
+        .headers()
+        .get("x-websocket-token")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|token| token == expected_token)
+    {
+        return true;
+    }
+
+    if request
+        .headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| token == expected_token)
+    {
+        return true;
+    }
+
+    request
+        .uri()
+        .query()
+        .is_some_and(|query| query.split('&').any(|pair| {
+            pair.strip_prefix("token=")
+                .is_some_and(|token| token == expected_token)
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_frames_parse_with_defaults() {
+        let frame: ClientFrame =
+            serde_json::from_str(r#"{"conversation_id":"abc","content":"hello"}"#).unwrap();
+        assert_eq!(frame.conversation_id, "abc");
+        assert_eq!(frame.sender_id, "websocket");
+        assert_eq!(frame.content, "hello");
+        assert!(frame.agent_id.is_none());
+
+        let frame: ClientFrame = serde_json::from_str(
+            r#"{"conversation_id":"abc","sender_id":"alice","content":"hi","agent_id":"support"}"#,
+        )
+        .unwrap();
+        assert_eq!(frame.sender_id, "alice");
+        assert_eq!(frame.agent_id.as_deref(), Some("support"));
+    }
+
+    #[test]
+    fn responses_map_to_frames() {
+        let frame = frame_for_response(OutboundResponse::StreamChunk("partial".into())).unwrap();
+        assert_eq!(frame.frame_type, "stream_chunk");
+        assert_eq!(frame.content.as_deref(), Some("partial"));
+
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"stream_chunk","content":"partial","filename":null}"#
+        );
+
+        assert!(frame_for_response(OutboundResponse::Reaction("eyes".into())).is_none());
+    }
+
+    #[test]
+    fn handshake_tokens_are_checked() {
+        let request = Request::builder()
+            .uri("ws://127.0.0.1/ws?token=secret")
+            .body(())
+            .unwrap();
+        assert!(is_authorized(&request, Some("secret")));
+        assert!(!is_authorized(&request, Some("other")));
+
+        let request = Request::builder()
+            .uri("ws://127.0.0.1/ws")
+            .header("authorization", "Bearer secret")
+            .body(())
+            .unwrap();
+        assert!(is_authorized(&request, Some("secret")));
+
+        let request = Request::builder().uri("ws://127.0.0.1/ws").body(()).unwrap();
+        assert!(!is_authorized(&request, Some("secret")));
+        assert!(is_authorized(&request, None));
+    }
+}
@@ -0,0 +1,606 @@
+//! WhatsApp Business Cloud API messaging adapter.
+//!
+//! Receives inbound messages on a Meta webhook endpoint (with the standard
+//! `hub.challenge` verification handshake and `X-Hub-Signature-256` HMAC
+//! validation of every delivery) and sends outbound messages
+//! through the Graph API. Inbound media IDs are resolved to download URLs
+//! and mapped to `MessageContent::Media`; each handled message is marked
+//! read so senders see the blue ticks.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use axum::Router;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::get;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::{split_message, verify_hmac_hex};
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{Attachment, InboundMessage, MessageContent, OutboundResponse};
+
+/// WhatsApp rejects text bodies over 4096 characters.
+const MAX_MESSAGE_LENGTH: usize = 4_000;
+
+const GRAPH_BASE_URL: &str = "https://graph.facebook.com/v21.0";
+
+/// WhatsApp Cloud API adapter state.
+pub struct WhatsAppAdapter {
+    runtime_key: String,
+    access_token: String,
+    /// The business phone number ID messages are sent from.
+    phone_number_id: String,
+    /// Token Meta echoes back during webhook verification.
+    verify_token: String,
+    /// Meta app secret, used to verify delivery signatures.
+    app_secret: String,
+    port: u16,
+    bind: String,
+    client: reqwest::Client,
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+/// Shared state for axum handlers.
+#[derive(Clone)]
+struct AppState {
+    inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
+    client: reqwest::Client,
+    access_token: String,
+    phone_number_id: String,
+    verify_token: String,
+    app_secret: String,
+    runtime_key: String,
+}
+
+/// Webhook verification query parameters.
+#[derive(Debug, Deserialize)]
+struct VerifyParams {
+    #[serde(rename = "hub.mode")]
+    mode: Option<String>,
+    #[serde(rename = "hub.verify_token")]
+    verify_token: Option<String>,
+    #[serde(rename = "hub.challenge")]
+    challenge: Option<String>,
+}
+
+/// Inbound webhook payload, reduced to the fields we consume.
+#[derive(Debug, Deserialize)]
+struct WebhookPayload {
+    #[serde(default)]
+    entry: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    #[serde(default)]
+    changes: Vec<Change>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Change {
+    value: ChangeValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangeValue {
+    #[serde(default)]
+    messages: Vec<WaMessage>,
+    #[serde(default)]
+    contacts: Vec<WaContact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaContact {
+    wa_id: Option<String>,
+    profile: Option<WaProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaProfile {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaMessage {
+    id: Option<String>,
+    from: Option<String>,
+    timestamp: Option<String>,
+    #[serde(rename = "type")]
+    message_type: Option<String>,
+    text: Option<WaText>,
+    image: Option<WaMedia>,
+    document: Option<WaMedia>,
+    audio: Option<WaMedia>,
+    video: Option<WaMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaText {
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaMedia {
+    id: Option<String>,
+    mime_type: Option<String>,
+    filename: Option<String>,
+    caption: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MediaUrlResponse {
+    url: String,
+    #[serde(default)]
+    file_size: Option<u64>,
+}
+
+impl WhatsAppAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        access_token: impl Into<String>,
+        phone_number_id: impl Into<String>,
+        verify_token: impl Into<String>,
+        app_secret: impl Into<String>,
+        port: u16,
+        bind: impl Into<String>,
+    ) -> Self {
+        Self {
+            runtime_key: runtime_key.into(),
+            access_token: access_token.into(),
+            phone_number_id: phone_number_id.into(),
+            verify_token: verify_token.into(),
+            app_secret: app_secret.into(),
+            port,
+            bind: bind.into(),
+            client: crate::http::client(),
+            inbound_tx: Arc::new(RwLock::new(None)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// POST a message payload to the Graph API send endpoint.
+    async fn send_payload(&self, payload: serde_json::Value) -> crate::Result<()> {
+        let url = format!("{GRAPH_BASE_URL}/{}/messages", self.phone_number_id);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&payload)
+            .send()
+            .await
+            .context("failed to send WhatsApp message")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("WhatsApp send failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    async fn send_text(&self, to: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.send_payload(json!({
+                "messaging_product": "whatsapp",
+                "to": to,
+                "type": "text",
+                "text": { "body": chunk },
+            }))
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Upload media bytes and send them as a document message.
+    async fn send_file(
+        &self,
+        to: &str,
+        filename: &str,
+        data: bytes::Bytes,
+        mime_type: &str,
+        caption: Option<String>,
+    ) -> crate::Result<()> {
+        let url = format!("{GRAPH_BASE_URL}/{}/media", self.phone_number_id);
+        let length = data.len() as u64;
+        let part = reqwest::multipart::Part::stream_with_length(reqwest::Body::from(data), length)
+            .file_name(filename.to_string())
+            .mime_str(mime_type)
+            .context("invalid mime type for WhatsApp upload")?;
+        let form = reqwest::multipart::Form::new()
+            .text("messaging_product", "whatsapp")
+            .part("file", part);
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .multipart(form)
+            .send()
+            .await
+            .context("failed to upload WhatsApp media")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("WhatsApp upload failed: HTTP {status}: {body}").into());
+        }
+
+        #[derive(Deserialize)]
+        struct UploadResponse {
+            id: String,
+        }
+        let upload: UploadResponse = response
+            .json()
+            .await
+            .context("failed to parse WhatsApp upload response")?;
+
+        let mut document = json!({ "id": upload.id, "filename": filename });
+        if let Some(caption) = caption {
+            document["caption"] = json!(caption);
+        }
+        self.send_payload(json!({
+            "messaging_product": "whatsapp",
+            "to": to,
+            "type": "document",
+            "document": document,
+        }))
+        .await
+    }
+
+    /// Resolve the recipient's wa_id from inbound message metadata.
+    fn recipient_from(message: &InboundMessage) -> crate::Result<&str> {
+        message
+            .metadata
+            .get("whatsapp_wa_id")
+            .and_then(|v| v.as_str())
+            .context("missing whatsapp_wa_id in metadata")
+            .map_err(Into::into)
+    }
+}
+
+impl Messaging for WhatsAppAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        *self.inbound_tx.write().await = Some(inbound_tx);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let state = AppState {
+            inbound_tx: self.inbound_tx.clone(),
+            client: self.client.clone(),
+            access_token: self.access_token.clone(),
+            phone_number_id: self.phone_number_id.clone(),
+            verify_token: self.verify_token.clone(),
+            app_secret: self.app_secret.clone(),
+            runtime_key: self.runtime_key.clone(),
+        };
+
+        let app = Router::new()
+            .route("/webhook", get(handle_verify).post(handle_webhook))
+            .route("/health", get(handle_health))
+            .with_state(state);
+
+        let bind = if self.bind.contains(':') {
+            format!("[{}]:{}", self.bind, self.port)
+        } else {
+            format!("{}:{}", self.bind, self.port)
+        };
+        let listener = tokio::net::TcpListener::bind(&bind)
+            .await
+            .with_context(|| format!("failed to bind WhatsApp webhook to {bind}"))?;
+        tracing::info!(%bind, "WhatsApp webhook listening");
+
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_rx.recv().await;
+                })
+                .await
+            {
+                tracing::error!(%error, "WhatsApp webhook exited with error");
+            }
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let to = Self::recipient_from(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(to, &text).await,
+            OutboundResponse::File {
+                filename,
+                data,
+                mime_type,
+                caption,
+            } => {
+                self.send_file(to, &filename, data, &mime_type, caption)
+                    .await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let Some(message_id) = message
+                    .metadata
+                    .get("whatsapp_message_id")
+                    .and_then(|v| v.as_str())
+                else {
+                    return Ok(());
+                };
+                self.send_payload(json!({
+                    "messaging_product": "whatsapp",
+                    "to": to,
+                    "type": "reaction",
+                    "reaction": { "message_id": message_id, "emoji": emoji },
+                }))
+                .await
+            }
+            OutboundResponse::RemoveReaction(_) => {
+                let Some(message_id) = message
+                    .metadata
+                    .get("whatsapp_message_id")
+                    .and_then(|v| v.as_str())
+                else {
+                    return Ok(());
+                };
+                // An empty emoji clears the reaction
+                self.send_payload(json!({
+                    "messaging_product": "whatsapp",
+                    "to": to,
+                    "type": "reaction",
+                    "reaction": { "message_id": message_id, "emoji": "" },
+                }))
+                .await
+            }
+            OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("WhatsApp adapter shut down");
+        Ok(())
+    }
+}
+
+// -- Axum handlers --
+
+async fn handle_verify(
+    State(state): State<AppState>,
+    Query(params): Query<VerifyParams>,
+) -> Result<String, StatusCode> {
+    if params.mode.as_deref() == Some("subscribe")
+        && params.verify_token.as_deref() == Some(state.verify_token.as_str())
+    {
+        return Ok(params.challenge.unwrap_or_default());
+    }
+    Err(StatusCode::FORBIDDEN)
+}
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    // Meta signs every delivery with the app secret; unsigned or
+    // mis-signed POSTs are not from WhatsApp.
+    let Some(signature) = headers
+        .get("x-hub-signature-256")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("sha256="))
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+    if !verify_hmac_hex(&state.app_secret, body.as_bytes(), signature) {
+        tracing::warn!("rejected WhatsApp webhook with bad signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+    let Ok(payload) = serde_json::from_str::<WebhookPayload>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    for entry in payload.entry {
+        for change in entry.changes {
+            let contact_names: HashMap<String, String> = change
+                .value
+                .contacts
+                .iter()
+                .filter_map(|contact| {
+                    let wa_id = contact.wa_id.clone()?;
+                    let name = contact.profile.as_ref()?.name.clone()?;
+                    Some((wa_id, name))
+                })
+                .collect();
+
+            for wa_message in change.value.messages {
+                process_message(&state, wa_message, &contact_names).await;
+            }
+        }
+    }
+    StatusCode::OK
+}
+
+async fn process_message(
+    state: &AppState,
+    wa_message: WaMessage,
+    contact_names: &HashMap<String, String>,
+) {
+    let Some(from) = wa_message.from.clone() else {
+        return;
+    };
+    let message_id = wa_message.id.clone();
+
+    let media = wa_message
+        .image
+        .as_ref()
+        .or(wa_message.document.as_ref())
+        .or(wa_message.audio.as_ref())
+        .or(wa_message.video.as_ref());
+
+    let text = wa_message
+        .text
+        .as_ref()
+        .map(|t| t.body.clone())
+        .or_else(|| media.and_then(|m| m.caption.clone()))
+        .unwrap_or_default();
+
+    let mut attachments = Vec::new();
+    if let Some(media) = media
+        && let Some(media_id) = &media.id
+        && let Some((url, size)) = resolve_media_url(state, media_id).await
+    {
+        attachments.push(Attachment {
+            filename: media
+                .filename
+                .clone()
+                .unwrap_or_else(|| media_id.clone()),
+            mime_type: media
+                .mime_type
+                .clone()
+                .unwrap_or_else(|| "application/octet-stream".into()),
+            url,
+            size_bytes: size,
+            auth_header: Some(format!("Bearer {}", state.access_token)),
+        });
+    }
+
+    if text.is_empty() && attachments.is_empty() {
+        return;
+    }
+
+    let sender_name = contact_names
+        .get(&from)
+        .cloned()
+        .unwrap_or_else(|| from.clone());
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "whatsapp_wa_id".into(),
+        serde_json::Value::String(from.clone()),
+    );
+    if let Some(id) = &message_id {
+        metadata.insert(
+            "whatsapp_message_id".into(),
+            serde_json::Value::String(id.clone()),
+        );
+    }
+    if let Some(message_type) = &wa_message.message_type {
+        metadata.insert(
+            "whatsapp_message_type".into(),
+            serde_json::Value::String(message_type.clone()),
+        );
+    }
+    metadata.insert(
+        "sender_display_name".into(),
+        serde_json::Value::String(sender_name.clone()),
+    );
+
+    let content = if attachments.is_empty() {
+        MessageContent::Text(text)
+    } else {
+        MessageContent::Media {
+            text: (!text.is_empty()).then_some(text),
+            attachments,
+        }
+    };
+
+    let timestamp = wa_message
+        .timestamp
+        .as_deref()
+        .and_then(|t| t.parse::<i64>().ok())
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    let inbound = InboundMessage {
+        id: message_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        source: "whatsapp".into(),
+        adapter: Some(state.runtime_key.clone()),
+        conversation_id: format!("whatsapp:{from}"),
+        sender_id: from,
+        agent_id: None,
+        content,
+        timestamp,
+        metadata,
+        formatted_author: Some(sender_name),
+    };
+
+    let tx = {
+        let guard = state.inbound_tx.read().await;
+        let Some(tx) = guard.as_ref() else {
+            return;
+        };
+        tx.clone()
+    };
+    if tx.send(inbound).await.is_err() {
+        return;
+    }
+
+    // Mark the message read so the sender gets a read receipt
+    if let Some(message_id) = message_id {
+        let url = format!("{GRAPH_BASE_URL}/{}/messages", state.phone_number_id);
+        let result = state
+            .client
+            .post(&url)
+            .bearer_auth(&state.access_token)
+            .json(&json!({
+                "messaging_product": "whatsapp",
+                "status": "read",
+                "message_id": message_id,
+            }))
+            .send()
+            .await;
+        if let Err(error) = result {
+            tracing::debug!(%error, "failed to mark WhatsApp message read");
+        }
+    }
+}
+
+/// Resolve a media ID to a short-lived download URL via the Graph API.
+async fn resolve_media_url(state: &AppState, media_id: &str) -> Option<(String, Option<u64>)> {
+    let response = state
+        .client
+        .get(format!("{GRAPH_BASE_URL}/{media_id}"))
+        .bearer_auth(&state.access_token)
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let media: MediaUrlResponse = response.json().await.ok()?;
+    Some((media.url, media.file_size))
+}
+
+async fn handle_health() -> StatusCode {
+    StatusCode::OK
+}
+
@@ -0,0 +1,756 @@
+//! XMPP/Jabber messaging adapter.
+//!
+//! Speaks the core XMPP client protocol directly over a TLS socket: STARTTLS
+//! on the standard client port, SASL PLAIN auth, resource binding, then a
+//! long-lived stanza loop. Handles both single-user chat and MUC rooms
+//! (XEP-0045), answers server pings (XEP-0199), and maps
+//! `StatusUpdate::Thinking` to chat-state "composing" notifications
+//! (XEP-0085). Stanzas are parsed with a small purpose-built tokenizer rather
+//! than a full XML stack — XMPP streams are flat sequences of top-level
+//! elements, which is all the tokenizer assumes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::sync::{RwLock, mpsc};
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+/// Soft cap per message stanza; most servers reject stanzas around 64 KiB.
+const MAX_MESSAGE_LENGTH: usize = 10_000;
+
+/// XMPP adapter state.
+pub struct XmppAdapter {
+    runtime_key: String,
+    /// Bare JID the bot logs in as, e.g. `bot@example.org`.
+    jid: String,
+    /// Localpart of the JID, used as the SASL authcid.
+    username: String,
+    /// Domain part of the JID.
+    domain: String,
+    /// Host to connect to; defaults to the JID domain.
+    server: String,
+    port: u16,
+    password: String,
+    /// Nickname used in MUC rooms.
+    nick: String,
+    /// MUC room JIDs to join on connect, e.g. `dev@conference.example.org`.
+    rooms: Vec<String>,
+    /// Sender for raw outbound stanzas, set while a connection is live.
+    outbound_tx: Arc<RwLock<Option<mpsc::Sender<String>>>>,
+    connected: Arc<AtomicBool>,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+impl XmppAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        jid: impl Into<String>,
+        password: impl Into<String>,
+        server: Option<String>,
+        port: u16,
+        nick: Option<String>,
+        rooms: Vec<String>,
+    ) -> crate::Result<Self> {
+        let jid = jid.into();
+        let (username, domain) = jid
+            .split_once('@')
+            .with_context(|| format!("XMPP JID '{jid}' is not of the form user@domain"))?;
+        if username.is_empty() || domain.is_empty() {
+            return Err(anyhow::anyhow!("XMPP JID '{jid}' is not of the form user@domain").into());
+        }
+
+        let username = username.to_string();
+        let domain = domain.to_string();
+        Ok(Self {
+            runtime_key: runtime_key.into(),
+            server: server.unwrap_or_else(|| domain.clone()),
+            nick: nick.unwrap_or_else(|| username.clone()),
+            jid,
+            username,
+            domain,
+            port,
+            password: password.into(),
+            rooms,
+            outbound_tx: Arc::new(RwLock::new(None)),
+            connected: Arc::new(AtomicBool::new(false)),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    fn stream_header(&self) -> String {
+        format!(
+            "<?xml version='1.0'?><stream:stream to='{}' version='1.0' \
+             xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams'>",
+            xml_escape(&self.domain)
+        )
+    }
+
+    /// Queue a raw stanza for the writer half of the live connection.
+    async fn send_stanza(&self, stanza: String) -> crate::Result<()> {
+        let tx = self.outbound_tx.read().await;
+        let tx = tx.as_ref().context("XMPP connection not established")?;
+        tx.send(stanza)
+            .await
+            .map_err(|_| anyhow::anyhow!("XMPP writer task is gone").into())
+    }
+
+    /// Send a chat or groupchat message, splitting overlong text.
+    async fn send_message(&self, to: &str, message_type: &str, text: &str) -> crate::Result<()> {
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            self.send_stanza(format!(
+                "<message to='{}' type='{}'><body>{}</body>\
+                 <active xmlns='http://jabber.org/protocol/chatstates'/></message>",
+                xml_escape(to),
+                message_type,
+                xml_escape(&chunk)
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Send a chat-state notification (composing/active) without a body.
+    async fn send_chat_state(&self, to: &str, message_type: &str, state: &str) -> crate::Result<()> {
+        self.send_stanza(format!(
+            "<message to='{}' type='{}'>\
+             <{state} xmlns='http://jabber.org/protocol/chatstates'/></message>",
+            xml_escape(to),
+            message_type,
+        ))
+        .await
+    }
+
+    /// Delivery target and message type from inbound metadata.
+    fn routing(message: &InboundMessage) -> crate::Result<(&str, &str)> {
+        let target = message
+            .metadata
+            .get("xmpp_counterpart")
+            .and_then(|v| v.as_str())
+            .context("missing xmpp_counterpart in metadata")?;
+        let message_type = message
+            .metadata
+            .get("xmpp_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("chat");
+        Ok((target, message_type))
+    }
+
+    /// Run one connection: negotiate TLS and SASL, bind, then loop on stanzas.
+    /// Returns on disconnect or shutdown.
+    async fn run_stream(
+        &self,
+        inbound_tx: &mpsc::Sender<InboundMessage>,
+        shutdown_rx: &mut mpsc::Receiver<()>,
+        outbound_rx: &mut mpsc::Receiver<String>,
+    ) -> crate::Result<()> {
+        let mut tcp = tokio::net::TcpStream::connect((self.server.as_str(), self.port))
+            .await
+            .with_context(|| {
+                format!("failed to connect to XMPP server {}:{}", self.server, self.port)
+            })?;
+
+        // Plaintext phase: stream header, then STARTTLS
+        tcp.write_all(self.stream_header().as_bytes())
+            .await
+            .context("failed to open XMPP stream")?;
+        let features = read_until(&mut tcp, "</stream:features>").await?;
+        if !features.contains("urn:ietf:params:xml:ns:xmpp-tls") {
+            return Err(anyhow::anyhow!("XMPP server does not offer STARTTLS").into());
+        }
+        tcp.write_all(b"<starttls xmlns='urn:ietf:params:xml:ns:xmpp-tls'/>")
+            .await
+            .context("failed to request STARTTLS")?;
+        let proceed = read_until(&mut tcp, ">").await?;
+        if !proceed.contains("<proceed") {
+            return Err(anyhow::anyhow!("XMPP server refused STARTTLS: {proceed}").into());
+        }
+
+        let connector = tokio_native_tls::TlsConnector::from(
+            native_tls::TlsConnector::new().context("failed to build TLS connector for XMPP")?,
+        );
+        let mut tls = connector
+            .connect(&self.domain, tcp)
+            .await
+            .context("XMPP TLS handshake failed")?;
+
+        // Encrypted phase: fresh stream, SASL PLAIN
+        tls.write_all(self.stream_header().as_bytes())
+            .await
+            .context("failed to reopen XMPP stream over TLS")?;
+        let features = read_until(&mut tls, "</stream:features>").await?;
+        if !features.contains("PLAIN") {
+            return Err(anyhow::anyhow!("XMPP server does not offer SASL PLAIN").into());
+        }
+        let credentials = BASE64.encode(format!("\0{}\0{}", self.username, self.password));
+        tls.write_all(
+            format!(
+                "<auth xmlns='urn:ietf:params:xml:ns:xmpp-sasl' mechanism='PLAIN'>{credentials}</auth>"
+            )
+            .as_bytes(),
+        )
+        .await
+        .context("failed to send SASL auth")?;
+        let sasl_result = read_until(&mut tls, ">").await?;
+        if !sasl_result.contains("<success") {
+            return Err(anyhow::anyhow!("XMPP authentication failed: {sasl_result}").into());
+        }
+
+        // Authenticated phase: fresh stream, resource bind, presence
+        tls.write_all(self.stream_header().as_bytes())
+            .await
+            .context("failed to reopen XMPP stream after auth")?;
+        read_until(&mut tls, "</stream:features>").await?;
+        tls.write_all(
+            b"<iq type='set' id='bind-1'>\
+              <bind xmlns='urn:ietf:params:xml:ns:xmpp-bind'>\
+              <resource>spacebot</resource></bind></iq>",
+        )
+        .await
+        .context("failed to bind XMPP resource")?;
+        let bind_result = read_until(&mut tls, "</iq>").await?;
+        if bind_result.contains("type='error'") || bind_result.contains("type=\"error\"") {
+            return Err(anyhow::anyhow!("XMPP resource bind failed: {bind_result}").into());
+        }
+        // Legacy session establishment; modern servers treat this as a no-op
+        tls.write_all(
+            b"<iq type='set' id='sess-1'>\
+              <session xmlns='urn:ietf:params:xml:ns:xmpp-session'/></iq>",
+        )
+        .await
+        .context("failed to establish XMPP session")?;
+        read_until(&mut tls, "</iq>").await.ok();
+
+        tls.write_all(b"<presence/>")
+            .await
+            .context("failed to send initial presence")?;
+        for room in &self.rooms {
+            let join = format!(
+                "<presence to='{}/{}'>\
+                 <x xmlns='http://jabber.org/protocol/muc'/></presence>",
+                xml_escape(room),
+                xml_escape(&self.nick)
+            );
+            tls.write_all(join.as_bytes())
+                .await
+                .with_context(|| format!("failed to join XMPP room {room}"))?;
+        }
+
+        tracing::info!(jid = %self.jid, rooms = self.rooms.len(), "XMPP connected");
+        self.connected.store(true, Ordering::Relaxed);
+
+        let (mut reader, mut writer) = tokio::io::split(tls);
+        let mut buffer = String::new();
+        let mut chunk = [0u8; 8192];
+
+        let result = loop {
+            tokio::select! {
+                read = reader.read(&mut chunk) => {
+                    let n = match read {
+                        Ok(0) => break Err(anyhow::anyhow!("XMPP stream closed by server").into()),
+                        Ok(n) => n,
+                        Err(error) => break Err(anyhow::anyhow!("XMPP read error: {error}").into()),
+                    };
+                    buffer.push_str(&String::from_utf8_lossy(&chunk[..n]));
+                    while let Some(stanza) = extract_stanza(&mut buffer) {
+                        if stanza.starts_with("</stream:stream") {
+                            break;
+                        }
+                        if let Some(reply) = ping_reply(&stanza) {
+                            writer.write_all(reply.as_bytes()).await.ok();
+                            continue;
+                        }
+                        if let Some(inbound) = self.parse_message_stanza(&stanza)
+                            && inbound_tx.send(inbound).await.is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+                stanza = outbound_rx.recv() => {
+                    let Some(stanza) = stanza else {
+                        break Ok(());
+                    };
+                    if let Err(error) = writer.write_all(stanza.as_bytes()).await {
+                        break Err(anyhow::anyhow!("XMPP write error: {error}").into());
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    writer.write_all(b"</stream:stream>").await.ok();
+                    break Ok(());
+                }
+            }
+        };
+
+        self.connected.store(false, Ordering::Relaxed);
+        result
+    }
+
+    /// Convert a `<message>` stanza into an inbound message, filtering
+    /// bodiless stanzas, MUC history replays, and the bot's own reflections.
+    fn parse_message_stanza(&self, stanza: &str) -> Option<InboundMessage> {
+        if !stanza.starts_with("<message") {
+            return None;
+        }
+        let message_type = attr(stanza, "type").unwrap_or_else(|| "normal".to_string());
+        if message_type != "chat" && message_type != "groupchat" && message_type != "normal" {
+            return None;
+        }
+        // Delayed delivery marks MUC history replays and offline messages
+        if stanza.contains("urn:xmpp:delay") || stanza.contains("jabber:x:delay") {
+            return None;
+        }
+
+        let body = element_text(stanza, "body")?;
+        let body = body.trim().to_string();
+        if body.is_empty() {
+            return None;
+        }
+
+        let from = attr(stanza, "from")?;
+        let (bare, resource) = match from.split_once('/') {
+            Some((bare, resource)) => (bare.to_string(), Some(resource.to_string())),
+            None => (from.clone(), None),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("xmpp_from".into(), serde_json::Value::String(from.clone()));
+        if let Some(id) = attr(stanza, "id") {
+            metadata.insert("xmpp_message_id".into(), serde_json::Value::String(id));
+        }
+
+        let (conversation_id, sender_id, display_name) = if message_type == "groupchat" {
+            let nick = resource?;
+            if nick == self.nick {
+                return None;
+            }
+            metadata.insert("xmpp_room".into(), serde_json::Value::String(bare.clone()));
+            metadata.insert("xmpp_nick".into(), serde_json::Value::String(nick.clone()));
+            metadata.insert(
+                "xmpp_counterpart".into(),
+                serde_json::Value::String(bare.clone()),
+            );
+            metadata.insert(
+                "xmpp_type".into(),
+                serde_json::Value::String("groupchat".into()),
+            );
+            (format!("xmpp:muc:{bare}"), from.clone(), nick)
+        } else {
+            if bare == self.jid {
+                return None;
+            }
+            let localpart = bare.split('@').next().unwrap_or(&bare).to_string();
+            metadata.insert(
+                "xmpp_counterpart".into(),
+                serde_json::Value::String(bare.clone()),
+            );
+            metadata.insert("xmpp_type".into(), serde_json::Value::String("chat".into()));
+            (format!("xmpp:{bare}"), bare.clone(), localpart)
+        };
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(display_name.clone()),
+        );
+
+        Some(InboundMessage {
+            id: attr(stanza, "id").unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            source: "xmpp".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id,
+            agent_id: None,
+            content: MessageContent::Text(body),
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(display_name),
+        })
+    }
+}
+
+impl Messaging for XmppAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let adapter = XmppAdapter {
+            runtime_key: self.runtime_key.clone(),
+            jid: self.jid.clone(),
+            username: self.username.clone(),
+            domain: self.domain.clone(),
+            server: self.server.clone(),
+            port: self.port,
+            password: self.password.clone(),
+            nick: self.nick.clone(),
+            rooms: self.rooms.clone(),
+            outbound_tx: self.outbound_tx.clone(),
+            connected: self.connected.clone(),
+            shutdown_tx: self.shutdown_tx.clone(),
+        };
+
+        tokio::spawn(async move {
+            loop {
+                let (outbound_tx, mut outbound_rx) = mpsc::channel::<String>(64);
+                *adapter.outbound_tx.write().await = Some(outbound_tx);
+
+                match adapter
+                    .run_stream(&inbound_tx, &mut shutdown_rx, &mut outbound_rx)
+                    .await
+                {
+                    Ok(()) => {
+                        tracing::info!("XMPP stream loop stopped");
+                        break;
+                    }
+                    Err(error) => {
+                        tracing::warn!(%error, "XMPP disconnected, reconnecting in 5s");
+                        tokio::select! {
+                            _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => {}
+                            _ = shutdown_rx.recv() => break,
+                        }
+                    }
+                }
+            }
+            adapter.outbound_tx.write().await.take();
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let (target, message_type) = Self::routing(message)?;
+
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::ThreadReply { text, .. }
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => {
+                self.send_message(target, message_type, &text).await
+            }
+            OutboundResponse::File { filename, caption, .. } => {
+                // No XEP-0363 HTTP upload support; describe the file instead
+                let note = caption.unwrap_or_else(|| format!("[attachment: {filename}]"));
+                self.send_message(target, message_type, &note).await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                // XEP-0444 reactions, best-effort: need the original message ID
+                if let Some(id) = message.metadata.get("xmpp_message_id").and_then(|v| v.as_str()) {
+                    self.send_stanza(format!(
+                        "<message to='{}' type='{}'>\
+                         <reactions id='{}' xmlns='urn:xmpp:reactions:0'>\
+                         <reaction>{}</reaction></reactions></message>",
+                        xml_escape(target),
+                        message_type,
+                        xml_escape(id),
+                        xml_escape(&emoji)
+                    ))
+                    .await?;
+                }
+                Ok(())
+            }
+            OutboundResponse::Status(crate::StatusUpdate::Thinking) => {
+                self.send_chat_state(target, message_type, "composing").await
+            }
+            OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        let OutboundResponse::Text(text) = response else {
+            return Ok(());
+        };
+        let message_type = if self.rooms.iter().any(|room| room == target) {
+            "groupchat"
+        } else {
+            "chat"
+        };
+        self.send_message(target, message_type, &text).await
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        if self.connected.load(Ordering::Relaxed) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("XMPP stream is not connected").into())
+        }
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("XMPP adapter shut down");
+        Ok(())
+    }
+}
+
+/// Read from the stream until `needle` appears, for the line-oriented
+/// negotiation phases before the stanza loop takes over.
+async fn read_until<S>(stream: &mut S, needle: &str) -> crate::Result<String>
+where
+    S: tokio::io::AsyncRead + Unpin,
+{
+    let mut buffer = String::new();
+    let mut chunk = [0u8; 4096];
+    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(30);
+    loop {
+        if buffer.contains(needle) {
+            return Ok(buffer);
+        }
+        let n = tokio::time::timeout_at(deadline, stream.read(&mut chunk))
+            .await
+            .context("timed out during XMPP negotiation")?
+            .context("XMPP negotiation read failed")?;
+        if n == 0 {
+            return Err(anyhow::anyhow!("XMPP stream closed during negotiation").into());
+        }
+        buffer.push_str(&String::from_utf8_lossy(&chunk[..n]));
+    }
+}
+
+/// Build the reply for an XEP-0199 ping iq, if the stanza is one.
+fn ping_reply(stanza: &str) -> Option<String> {
+    if !stanza.starts_with("<iq") || !stanza.contains("urn:xmpp:ping") {
+        return None;
+    }
+    let id = attr(stanza, "id")?;
+    let from = attr(stanza, "from").unwrap_or_default();
+    let to = if from.is_empty() {
+        String::new()
+    } else {
+        format!(" to='{}'", xml_escape(&from))
+    };
+    Some(format!("<iq type='result' id='{}'{to}/>", xml_escape(&id)))
+}
+
+/// Pull one complete top-level stanza off the front of the buffer.
+///
+/// Skips stream headers and XML declarations, and understands self-closing
+/// tags and quoted attribute values. Returns `None` until a full stanza has
+/// arrived.
+fn extract_stanza(buffer: &mut String) -> Option<String> {
+    loop {
+        let trimmed_start = buffer.len() - buffer.trim_start().len();
+        if trimmed_start > 0 {
+            buffer.drain(..trimmed_start);
+        }
+        if buffer.is_empty() || !buffer.starts_with('<') {
+            return None;
+        }
+        // Strip XML declarations and stream headers; they are not stanzas
+        if buffer.starts_with("<?xml") || buffer.starts_with("<stream:stream") {
+            let end = find_tag_end(buffer, 0)?;
+            buffer.drain(..=end);
+            continue;
+        }
+        if buffer.starts_with("</stream:stream") {
+            let end = find_tag_end(buffer, 0)?;
+            let stanza: String = buffer.drain(..=end).collect();
+            return Some(stanza);
+        }
+
+        let mut depth = 0usize;
+        let mut pos = 0usize;
+        loop {
+            let open = buffer[pos..].find('<').map(|i| pos + i)?;
+            let close = find_tag_end(buffer, open)?;
+            let tag = &buffer[open..=close];
+            if tag.starts_with("</") {
+                depth = depth.checked_sub(1)?;
+            } else if !tag.ends_with("/>") && !tag.starts_with("<?") && !tag.starts_with("<!") {
+                depth += 1;
+            }
+            if depth == 0 {
+                let stanza: String = buffer.drain(..=close).collect();
+                return Some(stanza);
+            }
+            pos = close + 1;
+        }
+    }
+}
+
+/// Position of the `>` closing the tag that starts at `start`, respecting
+/// quoted attribute values.
+fn find_tag_end(s: &str, start: usize) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut quote: Option<u8> = None;
+    for (offset, &byte) in bytes[start..].iter().enumerate() {
+        match quote {
+            Some(q) if byte == q => quote = None,
+            Some(_) => {}
+            None => match byte {
+                b'\'' | b'"' => quote = Some(byte),
+                b'>' => return Some(start + offset),
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+/// Extract an attribute value from the opening tag of a stanza.
+fn attr(stanza: &str, name: &str) -> Option<String> {
+    let tag_end = find_tag_end(stanza, 0)?;
+    let tag = &stanza[..tag_end];
+    for quote in ['\'', '"'] {
+        let needle = format!("{name}={quote}");
+        if let Some(start) = tag.find(&needle) {
+            let rest = &tag[start + needle.len()..];
+            let end = rest.find(quote)?;
+            return Some(xml_unescape(&rest[..end]));
+        }
+    }
+    None
+}
+
+/// Text content of the first `<name>` child element, unescaped.
+fn element_text(stanza: &str, name: &str) -> Option<String> {
+    let open = format!("<{name}");
+    let start = stanza.find(&open)?;
+    let content_start = find_tag_end(stanza, start)? + 1;
+    if stanza[..content_start].ends_with("/>") {
+        return None;
+    }
+    let close = format!("</{name}>");
+    let end = stanza[content_start..].find(&close)? + content_start;
+    Some(xml_unescape(&stanza[content_start..end]))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&apos;")
+        .replace('"', "&quot;")
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter() -> XmppAdapter {
+        XmppAdapter::new(
+            "xmpp",
+            "bot@example.org",
+            "secret",
+            None,
+            5222,
+            Some("spacebot".to_string()),
+            vec!["dev@conference.example.org".to_string()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn extracts_stanzas_across_partial_reads() {
+        let mut buffer = String::from(
+            "<?xml version='1.0'?><stream:stream id='x' from='example.org'>\
+             <message from='a@example.org' type='chat'><body>hi</body></message><mes",
+        );
+        let stanza = extract_stanza(&mut buffer).unwrap();
+        assert!(stanza.starts_with("<message"));
+        assert!(stanza.ends_with("</message>"));
+        // The trailing fragment stays buffered until the rest arrives
+        assert_eq!(buffer, "<mes");
+        assert!(extract_stanza(&mut buffer).is_none());
+
+        buffer.push_str("sage type='chat'><body>two</body></message>");
+        let stanza = extract_stanza(&mut buffer).unwrap();
+        assert!(stanza.contains("two"));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn tag_scanning_respects_quoted_attributes() {
+        let mut buffer =
+            String::from("<presence from='room@muc/no>de' type='unavailable'><x/></presence>");
+        let stanza = extract_stanza(&mut buffer).unwrap();
+        assert!(stanza.ends_with("</presence>"));
+        assert_eq!(attr(&stanza, "from").as_deref(), Some("room@muc/no>de"));
+    }
+
+    #[test]
+    fn parses_chat_and_filters_own_groupchat_echo() {
+        let adapter = adapter();
+
+        let chat = adapter
+            .parse_message_stanza(
+                "<message from='alice@example.org/phone' type='chat' id='m1'>\
+                 <body>hello &amp; welcome</body></message>",
+            )
+            .unwrap();
+        assert_eq!(chat.conversation_id, "xmpp:alice@example.org");
+        let MessageContent::Text(text) = chat.content else {
+            panic!("expected text content");
+        };
+        assert_eq!(text, "hello & welcome");
+
+        // Our own MUC reflection comes back under our nick and must be dropped
+        let echo = adapter.parse_message_stanza(
+            "<message from='dev@conference.example.org/spacebot' type='groupchat'>\
+             <body>reply</body></message>",
+        );
+        assert!(echo.is_none());
+
+        let muc = adapter
+            .parse_message_stanza(
+                "<message from='dev@conference.example.org/bob' type='groupchat'>\
+                 <body>ship it</body></message>",
+            )
+            .unwrap();
+        assert_eq!(muc.conversation_id, "xmpp:muc:dev@conference.example.org");
+        assert_eq!(
+            muc.metadata.get("xmpp_type").and_then(|v| v.as_str()),
+            Some("groupchat")
+        );
+    }
+
+    #[test]
+    fn ignores_delayed_and_bodiless_stanzas() {
+        let adapter = adapter();
+        assert!(
+            adapter
+                .parse_message_stanza(
+                    "<message from='dev@conference.example.org/bob' type='groupchat'>\
+                     <body>old</body><delay xmlns='urn:xmpp:delay' stamp='x'/></message>",
+                )
+                .is_none()
+        );
+        assert!(
+            adapter
+                .parse_message_stanza(
+                    "<message from='alice@example.org/phone' type='chat'>\
+                     <composing xmlns='http://jabber.org/protocol/chatstates'/></message>",
+                )
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//! Zulip messaging adapter.
+//!
+//! Long-polls the Zulip events API (register + `/events`) for inbound
+//! messages and posts through the REST API. Zulip's stream+topic model maps
+//! onto threading: each stream/topic pair is its own conversation, the topic
+//! name is exposed in metadata, and `OutboundResponse::ThreadReply` posts to
+//! the named topic so agents can fork discussions.
+
+use crate::messaging::httpbot::split_message;
+use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging};
+use crate::{InboundMessage, MessageContent, OutboundResponse};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{RwLock, mpsc};
+
+/// Zulip caps message bodies at 10000 characters.
+const MAX_MESSAGE_LENGTH: usize = 9_500;
+
+/// Zulip adapter state.
+pub struct ZulipAdapter {
+    runtime_key: String,
+    /// Base server URL, e.g. `https://chat.example.com` (no trailing slash).
+    base_url: String,
+    /// Bot email address used for basic auth.
+    email: String,
+    api_key: String,
+    /// Stream names to accept messages from. Empty means all subscribed streams.
+    stream_filter: Vec<String>,
+    client: reqwest::Client,
+    shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterResponse {
+    queue_id: String,
+    last_event_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsResponse {
+    #[serde(default)]
+    events: Vec<ZulipEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZulipEvent {
+    id: i64,
+    #[serde(rename = "type")]
+    event_type: Option<String>,
+    message: Option<ZulipMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZulipMessage {
+    id: i64,
+    sender_id: i64,
+    sender_email: Option<String>,
+    sender_full_name: Option<String>,
+    content: String,
+    #[serde(rename = "type")]
+    message_type: String,
+    stream_id: Option<i64>,
+    display_recipient: Option<serde_json::Value>,
+    subject: Option<String>,
+    timestamp: Option<i64>,
+    #[serde(default)]
+    is_me_message: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnUserResponse {
+    user_id: i64,
+}
+
+impl ZulipAdapter {
+    pub fn new(
+        runtime_key: impl Into<String>,
+        base_url: impl Into<String>,
+        email: impl Into<String>,
+        api_key: impl Into<String>,
+        stream_filter: Vec<String>,
+    ) -> Self {
+        let base_url = base_url.into();
+        Self {
+            runtime_key: runtime_key.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            email: email.into(),
+            api_key: api_key.into(),
+            stream_filter,
+            client: crate::http::client(),
+            shutdown_tx: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/api/v1{path}", self.base_url)
+    }
+
+    /// Send a message to a stream topic or a private conversation.
+    async fn send_message(&self, form: &[(&str, &str)]) -> crate::Result<()> {
+        let response = self
+            .client
+            .post(self.api_url("/messages"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(form)
+            .send()
+            .await
+            .context("failed to send zulip message")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("zulip send failed: HTTP {status}: {body}").into());
+        }
+        Ok(())
+    }
+
+    async fn send_text(
+        &self,
+        message: &InboundMessage,
+        text: &str,
+        topic_override: Option<&str>,
+    ) -> crate::Result<()> {
+        let is_private = message
+            .metadata
+            .get("zulip_message_type")
+            .and_then(|v| v.as_str())
+            == Some("private");
+
+        for chunk in split_message(text, MAX_MESSAGE_LENGTH) {
+            if is_private {
+                let sender_email = message
+                    .metadata
+                    .get("zulip_sender_email")
+                    .and_then(|v| v.as_str())
+                    .context("missing zulip_sender_email in metadata")?;
+                self.send_message(&[
+                    ("type", "private"),
+                    ("to", &format!("[\"{sender_email}\"]")),
+                    ("content", &chunk),
+                ])
+                .await?;
+            } else {
+                let stream = message
+                    .metadata
+                    .get("zulip_stream")
+                    .and_then(|v| v.as_str())
+                    .context("missing zulip_stream in metadata")?;
+                let topic = topic_override
+                    .or_else(|| {
+                        message
+                            .metadata
+                            .get("zulip_topic")
+                            .and_then(|v| v.as_str())
+                    })
+                    .unwrap_or("general");
+                self.send_message(&[
+                    ("type", "stream"),
+                    ("to", stream),
+                    ("topic", topic),
+                    ("content", &chunk),
+                ])
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Register an event queue for message events.
+    async fn register_queue(&self) -> crate::Result<RegisterResponse> {
+        let response = self
+            .client
+            .post(self.api_url("/register"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .form(&[("event_types", "[\"message\"]")])
+            .send()
+            .await
+            .context("failed to register zulip event queue")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("zulip register failed: HTTP {status}: {body}").into());
+        }
+
+        response
+            .json()
+            .await
+            .context("failed to parse zulip register response")
+            .map_err(Into::into)
+    }
+
+    /// Resolve our own user ID so the event loop can skip self-messages.
+    async fn own_user_id(&self) -> crate::Result<i64> {
+        let response = self
+            .client
+            .get(self.api_url("/users/me"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .send()
+            .await
+            .context("failed to fetch zulip bot identity")?;
+        let me: OwnUserResponse = response
+            .json()
+            .await
+            .context("failed to parse zulip bot identity")?;
+        Ok(me.user_id)
+    }
+}
+
+impl Messaging for ZulipAdapter {
+    fn name(&self) -> &str {
+        &self.runtime_key
+    }
+
+    async fn start(&self) -> crate::Result<InboundStream> {
+        let own_user_id = self.own_user_id().await?;
+        let registration = self.register_queue().await?;
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(256);
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+        *self.shutdown_tx.write().await = Some(shutdown_tx);
+
+        let client = self.client.clone();
+        let base_url = self.base_url.clone();
+        let email = self.email.clone();
+        let api_key = self.api_key.clone();
+        let stream_filter = self.stream_filter.clone();
+        let runtime_key = self.runtime_key.clone();
+
+        tokio::spawn(async move {
+            let mut queue_id = registration.queue_id;
+            let mut last_event_id = registration.last_event_id;
+
+            loop {
+                let request = client
+                    .get(format!("{base_url}/api/v1/events"))
+                    .basic_auth(&email, Some(&api_key))
+                    .query(&[
+                        ("queue_id", queue_id.as_str()),
+                        ("last_event_id", &last_event_id.to_string()),
+                    ])
+                    .timeout(std::time::Duration::from_secs(90))
+                    .send();
+
+                let response = tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    response = request => response,
+                };
+
+                let events = match response {
+                    Ok(response) if response.status().is_success() => {
+                        match response.json::<EventsResponse>().await {
+                            Ok(events) => events.events,
+                            Err(error) => {
+                                tracing::warn!(%error, "failed to parse zulip events");
+                                continue;
+                            }
+                        }
+                    }
+                    Ok(response) if response.status() == reqwest::StatusCode::BAD_REQUEST => {
+                        // Queue expired; re-register and continue
+                        tracing::info!("zulip event queue expired, re-registering");
+                        let register = client
+                            .post(format!("{base_url}/api/v1/register"))
+                            .basic_auth(&email, Some(&api_key))
+                            .form(&[("event_types", "[\"message\"]")])
+                            .send()
+                            .await;
+                        match register {
+                            Ok(response) => match response.json::<RegisterResponse>().await {
+                                Ok(registration) => {
+                                    queue_id = registration.queue_id;
+                                    last_event_id = registration.last_event_id;
+                                }
+                                Err(error) => {
+                                    tracing::error!(%error, "failed to re-register zulip queue");
+                                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                                }
+                            },
+                            Err(error) => {
+                                tracing::error!(%error, "failed to re-register zulip queue");
+                                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                            }
+                        }
+                        continue;
+                    }
+                    Ok(response) => {
+                        tracing::warn!(status = %response.status(), "zulip events poll failed");
+                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        continue;
+                    }
+                    Err(error) if error.is_timeout() => continue,
+                    Err(error) => {
+                        tracing::warn!(%error, "zulip events poll error");
+                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                for event in events {
+                    last_event_id = last_event_id.max(event.id);
+                    if event.event_type.as_deref() != Some("message") {
+                        continue;
+                    }
+                    let Some(message) = event.message else {
+                        continue;
+                    };
+                    if message.sender_id == own_user_id || message.is_me_message {
+                        continue;
+                    }
+
+                    let (conversation_id, stream_name) = match message.message_type.as_str() {
+                        "stream" => {
+                            let stream_name = match &message.display_recipient {
+                                Some(serde_json::Value::String(name)) => name.clone(),
+                                _ => continue,
+                            };
+                            if !stream_filter.is_empty()
+                                && !stream_filter.contains(&stream_name)
+                            {
+                                continue;
+                            }
+                            let topic = message.subject.clone().unwrap_or_default();
+                            let stream_id = message.stream_id.unwrap_or_default();
+                            (format!("zulip:{stream_id}:{topic}"), Some(stream_name))
+                        }
+                        "private" => (format!("zulip:dm:{}", message.sender_id), None),
+                        _ => continue,
+                    };
+
+                    let sender_name = message
+                        .sender_full_name
+                        .clone()
+                        .unwrap_or_else(|| message.sender_id.to_string());
+
+                    let mut metadata = HashMap::new();
+                    metadata.insert(
+                        "zulip_message_id".into(),
+                        serde_json::json!(message.id),
+                    );
+                    metadata.insert(
+                        "zulip_message_type".into(),
+                        serde_json::Value::String(message.message_type.clone()),
+                    );
+                    if let Some(stream_name) = &stream_name {
+                        metadata.insert(
+                            "zulip_stream".into(),
+                            serde_json::Value::String(stream_name.clone()),
+                        );
+                    }
+                    if let Some(topic) = &message.subject {
+                        metadata.insert(
+                            "zulip_topic".into(),
+                            serde_json::Value::String(topic.clone()),
+                        );
+                    }
+                    if let Some(sender_email) = &message.sender_email {
+                        metadata.insert(
+                            "zulip_sender_email".into(),
+                            serde_json::Value::String(sender_email.clone()),
+                        );
+                    }
+                    metadata.insert(
+                        "sender_display_name".into(),
+                        serde_json::Value::String(sender_name.clone()),
+                    );
+
+                    let inbound = InboundMessage {
+                        id: message.id.to_string(),
+                        source: "zulip".into(),
+                        adapter: Some(runtime_key.clone()),
+                        conversation_id,
+                        sender_id: message.sender_id.to_string(),
+                        agent_id: None,
+                        content: MessageContent::Text(message.content),
+                        timestamp: message
+                            .timestamp
+                            .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+                            .unwrap_or_else(chrono::Utc::now),
+                        metadata,
+                        formatted_author: Some(sender_name),
+                    };
+
+                    if inbound_tx.send(inbound).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            tracing::info!("zulip event loop ended");
+        });
+
+        let stream = tokio_stream::wrappers::ReceiverStream::new(inbound_rx);
+        Ok(Box::pin(stream))
+    }
+
+    async fn respond(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        match response {
+            OutboundResponse::Text(text)
+            | OutboundResponse::RichMessage { text, .. }
+            | OutboundResponse::Ephemeral { text, .. }
+            | OutboundResponse::ScheduledMessage { text, .. }
+            | OutboundResponse::StreamChunk(text) => self.send_text(message, &text, None).await,
+            OutboundResponse::ThreadReply { thread_name, text } => {
+                self.send_text(message, &text, Some(&thread_name)).await
+            }
+            OutboundResponse::File {
+                filename, caption, ..
+            } => {
+                // File uploads need the separate upload endpoint; reference
+                // the attachment in text until that's wired up.
+                let note = match caption {
+                    Some(caption) => format!("{caption} (attachment: {filename})"),
+                    None => format!("(attachment: {filename})"),
+                };
+                self.send_text(message, &note, None).await
+            }
+            OutboundResponse::Reaction(emoji) => {
+                let Some(message_id) = message
+                    .metadata
+                    .get("zulip_message_id")
+                    .and_then(|v| v.as_i64())
+                else {
+                    return Ok(());
+                };
+                let response = self
+                    .client
+                    .post(self.api_url(&format!("/messages/{message_id}/reactions")))
+                    .basic_auth(&self.email, Some(&self.api_key))
+                    .form(&[("emoji_name", sanitize_emoji_name(&emoji))])
+                    .send()
+                    .await
+                    .context("failed to add zulip reaction")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(anyhow::anyhow!("zulip reaction failed: HTTP {status}").into());
+                }
+                Ok(())
+            }
+            OutboundResponse::RemoveReaction(emoji) => {
+                let Some(message_id) = message
+                    .metadata
+                    .get("zulip_message_id")
+                    .and_then(|v| v.as_i64())
+                else {
+                    return Ok(());
+                };
+                let response = self
+                    .client
+                    .delete(self.api_url(&format!("/messages/{message_id}/reactions")))
+                    .basic_auth(&self.email, Some(&self.api_key))
+                    .form(&[("emoji_name", sanitize_emoji_name(&emoji))])
+                    .send()
+                    .await
+                    .context("failed to remove zulip reaction")?;
+                if !response.status().is_success() {
+                    let status = response.status();
+                    return Err(
+                        anyhow::anyhow!("zulip reaction removal failed: HTTP {status}").into(),
+                    );
+                }
+                Ok(())
+            }
+            OutboundResponse::StreamStart
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::Status(_) => Ok(()),
+        }
+    }
+
+    async fn fetch_history(
+        &self,
+        message: &InboundMessage,
+        limit: usize,
+    ) -> crate::Result<Vec<HistoryMessage>> {
+        let narrow = if message
+            .metadata
+            .get("zulip_message_type")
+            .and_then(|v| v.as_str())
+            == Some("private")
+        {
+            let sender_email = message
+                .metadata
+                .get("zulip_sender_email")
+                .and_then(|v| v.as_str())
+                .context("missing zulip_sender_email in metadata")?;
+            format!("[{{\"operator\": \"dm\", \"operand\": \"{sender_email}\"}}]")
+        } else {
+            let stream = message
+                .metadata
+                .get("zulip_stream")
+                .and_then(|v| v.as_str())
+                .context("missing zulip_stream in metadata")?;
+            let topic = message
+                .metadata
+                .get("zulip_topic")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            format!(
+                "[{{\"operator\": \"channel\", \"operand\": \"{stream}\"}}, {{\"operator\": \"topic\", \"operand\": \"{topic}\"}}]"
+            )
+        };
+
+        let anchor = message
+            .metadata
+            .get("zulip_message_id")
+            .and_then(|v| v.as_i64())
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "newest".to_string());
+
+        let response = self
+            .client
+            .get(self.api_url("/messages"))
+            .basic_auth(&self.email, Some(&self.api_key))
+            .query(&[
+                ("anchor", anchor.as_str()),
+                ("num_before", &limit.to_string()),
+                ("num_after", "0"),
+                ("narrow", &narrow),
+            ])
+            .send()
+            .await
+            .context("failed to fetch zulip history")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(anyhow::anyhow!("zulip history fetch failed: HTTP {status}").into());
+        }
+
+        #[derive(Deserialize)]
+        struct HistoryResponse {
+            #[serde(default)]
+            messages: Vec<HistoryEntry>,
+        }
+        #[derive(Deserialize)]
+        struct HistoryEntry {
+            sender_full_name: Option<String>,
+            content: String,
+            #[serde(default)]
+            sender_email: Option<String>,
+        }
+
+        let history: HistoryResponse = response
+            .json()
+            .await
+            .context("failed to parse zulip history")?;
+
+        let own_email = self.email.as_str();
+        Ok(history
+            .messages
+            .into_iter()
+            .map(|entry| HistoryMessage {
+                author: entry.sender_full_name.unwrap_or_else(|| "unknown".into()),
+                content: entry.content,
+                is_bot: entry.sender_email.as_deref() == Some(own_email),
+            })
+            .collect())
+    }
+
+    async fn health_check(&self) -> crate::Result<()> {
+        self.own_user_id().await.map(|_| ())
+    }
+
+    async fn shutdown(&self) -> crate::Result<()> {
+        if let Some(tx) = self.shutdown_tx.read().await.as_ref() {
+            tx.send(()).await.ok();
+        }
+        tracing::info!("zulip adapter shut down");
+        Ok(())
+    }
+}
+
+/// Zulip emoji reactions use colon-free shortcode names.
+fn sanitize_emoji_name(emoji: &str) -> String {
+    let trimmed = emoji.trim().trim_matches(':');
+    if trimmed.is_ascii() {
+        return trimmed.to_string();
+    }
+    emojis::get(trimmed)
+        .and_then(|e| e.shortcode())
+        .unwrap_or("thumbsup")
+        .to_string()
+}
+
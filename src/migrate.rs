@@ -0,0 +1,207 @@
+//! Instance migration between hosts over SSH.
+//!
+//! `spacebot migrate --to ssh://host` packages the whole instance directory —
+//! config, SQLite databases, token files, and attachments — into a tarball,
+//! copies it with `scp`, and unpacks it on the destination after verifying a
+//! SHA-256 checksum end to end. With `--leave-read-only` the source instance
+//! is additionally marked so that `spacebot start` refuses to run, pointing
+//! operators at the new host during cutover.
+
+use crate::config::Config;
+use crate::daemon::DaemonPaths;
+
+use anyhow::Context as _;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::process::Command;
+
+/// Marker file left in the instance directory by `--leave-read-only`; its
+/// contents name the migration target.
+pub const READONLY_MARKER: &str = "migrated-to";
+
+/// Parsed form of an `ssh://[user@]host[:port][/path]` migration target.
+#[derive(Debug, PartialEq, Eq)]
+struct SshTarget {
+    user_host: String,
+    port: Option<u16>,
+    /// Destination instance directory; defaults to `~/.spacebot` on the remote.
+    dest_dir: String,
+}
+
+/// Transfer this instance to a remote host and verify the copy.
+pub fn migrate(target: &str, leave_read_only: bool) -> anyhow::Result<()> {
+    let target = parse_ssh_target(target)?;
+    let instance_dir = Config::default_instance_dir();
+    anyhow::ensure!(
+        instance_dir.is_dir(),
+        "instance directory {} does not exist",
+        instance_dir.display()
+    );
+
+    let paths = DaemonPaths::new(&instance_dir);
+    if let Some(pid) = crate::daemon::is_running(&paths) {
+        anyhow::bail!(
+            "spacebot is running (pid {pid}); stop it first so databases are quiescent"
+        );
+    }
+
+    let archive = std::env::temp_dir().join(format!("spacebot-migrate-{}.tar.gz", uuid::Uuid::new_v4()));
+    println!("Packing {} ...", instance_dir.display());
+    run_checked(
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(&instance_dir)
+            // Skip transient runtime state; everything else moves as-is.
+            .args(["--exclude=./spacebot.pid", "--exclude=./spacebot.sock", "."]),
+        "tar",
+    )?;
+
+    let checksum = sha256_file(&archive)?;
+    println!("Archive checksum: {checksum}");
+
+    let remote_archive = format!("/tmp/{}", archive.file_name().unwrap().to_string_lossy());
+    println!("Copying to {} ...", target.user_host);
+    let mut scp = Command::new("scp");
+    if let Some(port) = target.port {
+        scp.arg("-P").arg(port.to_string());
+    }
+    run_checked(
+        scp.arg(&archive)
+            .arg(format!("{}:{}", target.user_host, remote_archive)),
+        "scp",
+    )?;
+
+    // Verify the checksum on the remote before unpacking, then extract into
+    // the destination instance directory.
+    println!("Verifying and unpacking on {} ...", target.user_host);
+    let remote_script = format!(
+        "set -e; \
+         echo '{checksum}  {remote_archive}' | sha256sum -c -; \
+         mkdir -p {dest}; \
+         tar -xzf {remote_archive} -C {dest}; \
+         rm -f {remote_archive}",
+        dest = target.dest_dir,
+    );
+    let mut ssh = Command::new("ssh");
+    if let Some(port) = target.port {
+        ssh.arg("-p").arg(port.to_string());
+    }
+    run_checked(ssh.arg(&target.user_host).arg(remote_script), "ssh")?;
+
+    std::fs::remove_file(&archive).ok();
+
+    if leave_read_only {
+        let marker = instance_dir.join(READONLY_MARKER);
+        std::fs::write(&marker, format!("{}:{}\n", target.user_host, target.dest_dir))
+            .with_context(|| format!("failed to write {}", marker.display()))?;
+        println!(
+            "Source marked read-only; `spacebot start` here will refuse until {} is removed",
+            marker.display()
+        );
+    }
+
+    println!(
+        "Migration complete. Start the instance on {} with the copied data in {}",
+        target.user_host, target.dest_dir
+    );
+    Ok(())
+}
+
+/// If a migration marker is present, return the recorded target so `start`
+/// can refuse to run a migrated-away instance.
+pub fn readonly_target(instance_dir: &Path) -> Option<String> {
+    let marker = instance_dir.join(READONLY_MARKER);
+    std::fs::read_to_string(marker)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+fn parse_ssh_target(target: &str) -> anyhow::Result<SshTarget> {
+    let rest = target
+        .strip_prefix("ssh://")
+        .with_context(|| format!("migration target '{target}' must start with ssh://"))?;
+    anyhow::ensure!(!rest.is_empty(), "migration target '{target}' is missing a host");
+
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, ""),
+    };
+
+    // Split a trailing :port off the authority; the user@ prefix (if any)
+    // passes through to ssh/scp untouched.
+    let (user_host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() => {
+            let port: u16 = port
+                .parse()
+                .with_context(|| format!("invalid port in migration target '{target}'"))?;
+            (host.to_string(), Some(port))
+        }
+        _ => (authority.to_string(), None),
+    };
+    anyhow::ensure!(!user_host.is_empty(), "migration target '{target}' is missing a host");
+
+    let dest_dir = if path.is_empty() || path == "/" {
+        "~/.spacebot".to_string()
+    } else {
+        path.to_string()
+    };
+
+    Ok(SshTarget {
+        user_host,
+        port,
+        dest_dir,
+    })
+}
+
+fn run_checked(command: &mut Command, name: &str) -> anyhow::Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("failed to run {name}"))?;
+    anyhow::ensure!(status.success(), "{name} exited with {status}");
+    Ok(())
+}
+
+fn sha256_file(path: &Path) -> anyhow::Result<String> {
+    let data =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&data);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SshTarget, parse_ssh_target};
+
+    #[test]
+    fn parse_ssh_target_defaults_destination() {
+        assert_eq!(
+            parse_ssh_target("ssh://user@example.com").unwrap(),
+            SshTarget {
+                user_host: "user@example.com".to_string(),
+                port: None,
+                dest_dir: "~/.spacebot".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ssh_target_reads_port_and_path() {
+        assert_eq!(
+            parse_ssh_target("ssh://example.com:2222/srv/spacebot").unwrap(),
+            SshTarget {
+                user_host: "example.com".to_string(),
+                port: Some(2222),
+                dest_dir: "/srv/spacebot".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ssh_target_rejects_other_schemes() {
+        assert!(parse_ssh_target("rsync://example.com").is_err());
+        assert!(parse_ssh_target("ssh://").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! First-contact onboarding flows for messaging bindings.
+//!
+//! A binding can configure a sequence of onboarding messages (welcome,
+//! capability overview, data-retention notice) sent the first time a new
+//! `sender_id` talks through it. Completion is tracked per binding and
+//! sender so nobody is re-onboarded, and senders who opt out are never
+//! messaged again.
+
+use sqlx::{Row as _, SqlitePool};
+
+use crate::error::Result;
+use crate::messaging::manager::MessagingManager;
+use crate::{InboundMessage, OutboundResponse};
+
+use anyhow::Context as _;
+use std::sync::Arc;
+
+/// Phrases that opt a sender out of onboarding messages.
+const OPT_OUT_PHRASES: &[&str] = &["stop", "unsubscribe", "opt out", "opt-out"];
+
+/// Onboarding state for one sender on one binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStatus {
+    /// Never interacted through this binding before.
+    New,
+    /// Already received the onboarding flow.
+    Onboarded,
+    /// Asked not to receive onboarding messages.
+    OptedOut,
+}
+
+/// SQLite-backed record of who has been onboarded on which binding.
+#[derive(Debug, Clone)]
+pub struct OnboardingStore {
+    pool: SqlitePool,
+}
+
+impl OnboardingStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Look up a sender's onboarding status for a binding.
+    pub async fn status(&self, binding_key: &str, sender_id: &str) -> Result<OnboardingStatus> {
+        let row = sqlx::query(
+            "SELECT opted_out FROM onboarded_users WHERE binding_key = ? AND sender_id = ?",
+        )
+        .bind(binding_key)
+        .bind(sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to query onboarding status")?;
+
+        Ok(match row {
+            None => OnboardingStatus::New,
+            Some(row) if row.get::<i64, _>("opted_out") != 0 => OnboardingStatus::OptedOut,
+            Some(_) => OnboardingStatus::Onboarded,
+        })
+    }
+
+    /// Record that a sender has received the onboarding flow.
+    pub async fn mark_onboarded(&self, binding_key: &str, sender_id: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO onboarded_users (binding_key, sender_id, completed_at, opted_out) \
+             VALUES (?, ?, ?, 0) \
+             ON CONFLICT (binding_key, sender_id) DO NOTHING",
+        )
+        .bind(binding_key)
+        .bind(sender_id)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .context("failed to record onboarding completion")?;
+        Ok(())
+    }
+
+    /// Record that a sender opted out of onboarding messages.
+    pub async fn mark_opted_out(&self, binding_key: &str, sender_id: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO onboarded_users (binding_key, sender_id, completed_at, opted_out) \
+             VALUES (?, ?, ?, 1) \
+             ON CONFLICT (binding_key, sender_id) DO UPDATE SET opted_out = 1",
+        )
+        .bind(binding_key)
+        .bind(sender_id)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .context("failed to record onboarding opt-out")?;
+        Ok(())
+    }
+}
+
+/// Whether a message text is an onboarding opt-out request.
+fn is_opt_out(text: &str) -> bool {
+    let normalized = text.trim().trim_end_matches(['.', '!']).to_lowercase();
+    OPT_OUT_PHRASES.contains(&normalized.as_str())
+}
+
+/// Run the onboarding flow for an inbound message if the sender is new.
+///
+/// Sends each configured step in order through the message's adapter, then
+/// marks the sender as onboarded. Opt-out replies are honored permanently.
+pub async fn maybe_onboard(
+    store: &OnboardingStore,
+    messaging: &Arc<MessagingManager>,
+    message: &InboundMessage,
+    binding_key: &str,
+    steps: &[String],
+) -> Result<()> {
+    if steps.is_empty() || message.source == "system" {
+        return Ok(());
+    }
+
+    let status = store.status(binding_key, &message.sender_id).await?;
+
+    if let crate::MessageContent::Text(text) = &message.content
+        && is_opt_out(text)
+        && status != OnboardingStatus::OptedOut
+    {
+        store
+            .mark_opted_out(binding_key, &message.sender_id)
+            .await?;
+        tracing::info!(
+            binding_key,
+            sender_id = %message.sender_id,
+            "sender opted out of onboarding"
+        );
+        return Ok(());
+    }
+
+    if status != OnboardingStatus::New {
+        return Ok(());
+    }
+
+    // Mark first so a crash mid-flow can't spam the sender on retry
+    store
+        .mark_onboarded(binding_key, &message.sender_id)
+        .await?;
+
+    for step in steps {
+        if let Err(error) = messaging
+            .respond(message, OutboundResponse::Text(step.clone()))
+            .await
+        {
+            tracing::warn!(%error, binding_key, "failed to deliver onboarding step");
+            break;
+        }
+        // Keep multi-step flows readable on platforms that reorder fast sends
+        tokio::time::sleep(std::time::Duration::from_millis(750)).await;
+    }
+
+    tracing::info!(
+        binding_key,
+        sender_id = %message.sender_id,
+        steps = steps.len(),
+        "onboarding flow delivered"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_store() -> OnboardingStore {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        OnboardingStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn new_sender_is_onboarded_once() {
+        let store = test_store().await;
+        assert_eq!(
+            store.status("discord", "U1").await.unwrap(),
+            OnboardingStatus::New
+        );
+
+        store.mark_onboarded("discord", "U1").await.unwrap();
+        assert_eq!(
+            store.status("discord", "U1").await.unwrap(),
+            OnboardingStatus::Onboarded
+        );
+
+        // Tracking is per binding
+        assert_eq!(
+            store.status("telegram", "U1").await.unwrap(),
+            OnboardingStatus::New
+        );
+    }
+
+    #[tokio::test]
+    async fn opt_out_is_permanent() {
+        let store = test_store().await;
+        store.mark_onboarded("discord", "U1").await.unwrap();
+        store.mark_opted_out("discord", "U1").await.unwrap();
+        assert_eq!(
+            store.status("discord", "U1").await.unwrap(),
+            OnboardingStatus::OptedOut
+        );
+    }
+
+    #[test]
+    fn opt_out_phrases_are_recognized() {
+        assert!(is_opt_out("stop"));
+        assert!(is_opt_out("  Opt Out. "));
+        assert!(is_opt_out("UNSUBSCRIBE!"));
+        assert!(!is_opt_out("please don't stop"));
+    }
+}
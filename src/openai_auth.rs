@@ -34,7 +34,7 @@ impl OAuthCredentials {
 
     /// Refresh the access token and return updated credentials.
     pub async fn refresh(&self) -> Result<Self> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         let response = client
             .post(OAUTH_TOKEN_URL)
             .header("Content-Type", "application/x-www-form-urlencoded")
@@ -199,7 +199,7 @@ pub enum DeviceTokenPollResult {
 
 /// Step 1: Request a device code and user code from OpenAI.
 pub async fn request_device_code() -> Result<DeviceCodeResponse> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let body = serde_json::json!({ "client_id": CLIENT_ID });
 
     let response = client
@@ -239,7 +239,7 @@ pub async fn poll_device_token(
     device_auth_id: &str,
     user_code: &str,
 ) -> Result<DeviceTokenPollResult> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let body = serde_json::json!({
         "device_auth_id": device_auth_id,
         "user_code": user_code,
@@ -318,7 +318,7 @@ pub async fn exchange_device_code(
     authorization_code: &str,
     code_verifier: &str,
 ) -> Result<OAuthCredentials> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .post(OAUTH_TOKEN_URL)
         .header("Content-Type", "application/x-www-form-urlencoded")
@@ -0,0 +1,296 @@
+//! Per-stage latency instrumentation for the inbound message hot path.
+//!
+//! When enabled (`[metrics] perf_instrumentation = true`), each stage of the
+//! pipeline — adapter parse, binding resolution, prompt build, the LLM turn,
+//! result formatting, outbound send — records its duration into a fixed-bucket
+//! histogram. The aggregate is served by `/api/perf` to pinpoint where
+//! multi-second latencies come from. Recording is lock-free and a no-op while
+//! disabled, so the breadcrumbs cost nothing in the default configuration.
+
+use serde::Serialize;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Global recorder instance. Initialized once, accessed from any call site.
+static RECORDER: LazyLock<PerfRecorder> = LazyLock::new(PerfRecorder::new);
+
+/// Histogram bucket upper bounds in milliseconds; an implicit final bucket
+/// catches everything slower than the last bound.
+const BUCKET_BOUNDS_MS: [u64; 13] = [
+    1, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000,
+];
+
+/// Pipeline stages instrumented on the hot path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfStage {
+    /// Platform event timestamp to dispatch-loop receipt: adapter parsing
+    /// plus transport and queueing.
+    AdapterParse,
+    /// Sticky-agent lookup and binding resolution.
+    BindingResolution,
+    /// System prompt assembly.
+    PromptBuild,
+    /// The agentic LLM turn, tool calls included.
+    Llm,
+    /// Turning the agent result into outbound responses.
+    Format,
+    /// Adapter send of one outbound response.
+    Send,
+}
+
+impl PerfStage {
+    const ALL: [PerfStage; 6] = [
+        PerfStage::AdapterParse,
+        PerfStage::BindingResolution,
+        PerfStage::PromptBuild,
+        PerfStage::Llm,
+        PerfStage::Format,
+        PerfStage::Send,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AdapterParse => "adapter_parse",
+            Self::BindingResolution => "binding_resolution",
+            Self::PromptBuild => "prompt_build",
+            Self::Llm => "llm",
+            Self::Format => "format",
+            Self::Send => "send",
+        }
+    }
+}
+
+/// Fixed-bucket histogram updated with atomics only.
+struct StageHistogram {
+    /// One counter per bound plus the overflow bucket.
+    buckets: [AtomicU64; BUCKET_BOUNDS_MS.len() + 1],
+    count: AtomicU64,
+    sum_ms: AtomicU64,
+    max_ms: AtomicU64,
+}
+
+impl StageHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            sum_ms: AtomicU64::new(0),
+            max_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, ms: u64) {
+        let index = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(ms, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.sum_ms.store(0, Ordering::Relaxed);
+        self.max_ms.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, stage: PerfStage) -> StageReport {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let count = self.count.load(Ordering::Relaxed);
+        let sum_ms = self.sum_ms.load(Ordering::Relaxed);
+        let max_ms = self.max_ms.load(Ordering::Relaxed);
+
+        // A quantile resolves to the upper bound of the bucket that crosses
+        // the target rank; the overflow bucket resolves to the observed max.
+        let quantile = |q: f64| -> u64 {
+            if count == 0 {
+                return 0;
+            }
+            let target = (q * count as f64).ceil() as u64;
+            let mut cumulative = 0u64;
+            for (index, bucket_count) in counts.iter().enumerate() {
+                cumulative += bucket_count;
+                if cumulative >= target {
+                    return BUCKET_BOUNDS_MS.get(index).copied().unwrap_or(max_ms);
+                }
+            }
+            max_ms
+        };
+
+        StageReport {
+            stage: stage.as_str(),
+            count,
+            mean_ms: if count == 0 {
+                0.0
+            } else {
+                sum_ms as f64 / count as f64
+            },
+            p50_ms: quantile(0.50),
+            p90_ms: quantile(0.90),
+            p99_ms: quantile(0.99),
+            max_ms,
+            buckets: BUCKET_BOUNDS_MS
+                .iter()
+                .zip(&counts)
+                .map(|(bound, bucket_count)| BucketCount {
+                    le_ms: *bound,
+                    count: *bucket_count,
+                })
+                .collect(),
+            overflow: counts[BUCKET_BOUNDS_MS.len()],
+        }
+    }
+}
+
+/// Aggregated per-stage timing breadcrumbs for the message pipeline.
+pub struct PerfRecorder {
+    enabled: AtomicBool,
+    stages: [StageHistogram; PerfStage::ALL.len()],
+}
+
+impl PerfRecorder {
+    fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            stages: std::array::from_fn(|_| StageHistogram::new()),
+        }
+    }
+
+    /// Access the global recorder instance.
+    pub fn global() -> &'static Self {
+        &RECORDER
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Record a stage duration. No-op while instrumentation is disabled.
+    pub fn record(&self, stage: PerfStage, duration: Duration) {
+        if !self.is_enabled() {
+            return;
+        }
+        let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        self.stages[stage as usize].record(ms);
+    }
+
+    /// Clear all recorded samples without changing the enabled state.
+    pub fn reset(&self) {
+        for stage in &self.stages {
+            stage.reset();
+        }
+    }
+
+    /// Snapshot every stage histogram for the `/api/perf` report.
+    pub fn report(&self) -> PerfReport {
+        PerfReport {
+            enabled: self.is_enabled(),
+            stages: PerfStage::ALL
+                .iter()
+                .map(|stage| self.stages[*stage as usize].snapshot(*stage))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PerfReport {
+    pub enabled: bool,
+    pub stages: Vec<StageReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StageReport {
+    pub stage: &'static str,
+    pub count: u64,
+    pub mean_ms: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+    pub buckets: Vec<BucketCount>,
+    /// Samples slower than the last bucket bound.
+    pub overflow: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BucketCount {
+    pub le_ms: u64,
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_recorder_drops_samples() {
+        let recorder = PerfRecorder::new();
+        recorder.record(PerfStage::Llm, Duration::from_millis(100));
+        let report = recorder.report();
+        assert!(!report.enabled);
+        assert!(report.stages.iter().all(|stage| stage.count == 0));
+    }
+
+    #[test]
+    fn quantiles_resolve_to_bucket_bounds() {
+        let recorder = PerfRecorder::new();
+        recorder.set_enabled(true);
+        for ms in [2, 3, 4, 8, 9, 40, 45, 90, 700, 4_000] {
+            recorder.record(PerfStage::Llm, Duration::from_millis(ms));
+        }
+
+        let report = recorder.report();
+        let llm = report
+            .stages
+            .iter()
+            .find(|stage| stage.stage == "llm")
+            .unwrap();
+        assert_eq!(llm.count, 10);
+        assert_eq!(llm.p50_ms, 10);
+        assert_eq!(llm.p90_ms, 1_000);
+        assert_eq!(llm.p99_ms, 5_000);
+        assert_eq!(llm.max_ms, 4_000);
+        assert!((llm.mean_ms - 490.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn overflow_samples_report_observed_max() {
+        let recorder = PerfRecorder::new();
+        recorder.set_enabled(true);
+        recorder.record(PerfStage::Send, Duration::from_millis(45_000));
+
+        let report = recorder.report();
+        let send = report
+            .stages
+            .iter()
+            .find(|stage| stage.stage == "send")
+            .unwrap();
+        assert_eq!(send.overflow, 1);
+        assert_eq!(send.p50_ms, 45_000);
+
+        recorder.reset();
+        let report = recorder.report();
+        let send = report
+            .stages
+            .iter()
+            .find(|stage| stage.stage == "send")
+            .unwrap();
+        assert_eq!(send.count, 0);
+        assert_eq!(send.max_ms, 0);
+    }
+}
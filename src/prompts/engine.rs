@@ -450,6 +450,7 @@ impl PromptEngine {
             available_channels,
             None,
             None,
+            None,
         )
     }
 
@@ -515,6 +516,7 @@ impl PromptEngine {
         available_channels: Option<String>,
         org_context: Option<String>,
         adapter_prompt: Option<String>,
+        pinned_context: Option<String>,
     ) -> Result<String> {
         self.render(
             "channel",
@@ -529,6 +531,7 @@ impl PromptEngine {
                 available_channels => available_channels,
                 org_context => org_context,
                 adapter_prompt => adapter_prompt,
+                pinned_context => pinned_context,
             },
         )
     }
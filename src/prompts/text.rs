@@ -146,6 +146,19 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/file") => include_str!("../../prompts/en/tools/file_description.md.j2"),
         ("en", "tools/exec") => include_str!("../../prompts/en/tools/exec_description.md.j2"),
         ("en", "tools/browser") => include_str!("../../prompts/en/tools/browser_description.md.j2"),
+        ("en", "tools/kube") => include_str!("../../prompts/en/tools/kube_description.md.j2"),
+        ("en", "tools/ops") => include_str!("../../prompts/en/tools/ops_description.md.j2"),
+        ("en", "tools/sql") => include_str!("../../prompts/en/tools/sql_description.md.j2"),
+        ("en", "tools/ssh") => include_str!("../../prompts/en/tools/ssh_description.md.j2"),
+        ("en", "tools/pin_context") => {
+            include_str!("../../prompts/en/tools/pin_context_description.md.j2")
+        }
+        ("en", "tools/translation_lookup") => {
+            include_str!("../../prompts/en/tools/translation_lookup_description.md.j2")
+        }
+        ("en", "tools/translation_save") => {
+            include_str!("../../prompts/en/tools/translation_save_description.md.j2")
+        }
         ("en", "tools/web_search") => {
             include_str!("../../prompts/en/tools/web_search_description.md.j2")
         }
@@ -164,6 +177,18 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/email_search") => {
             include_str!("../../prompts/en/tools/email_search_description.md.j2")
         }
+        ("en", "tools/email_action") => {
+            include_str!("../../prompts/en/tools/email_action_description.md.j2")
+        }
+        ("en", "tools/slack_context") => {
+            include_str!("../../prompts/en/tools/slack_context_description.md.j2")
+        }
+        ("en", "tools/telegram_message") => {
+            include_str!("../../prompts/en/tools/telegram_message_description.md.j2")
+        }
+        ("en", "tools/telegram_moderation") => {
+            include_str!("../../prompts/en/tools/telegram_moderation_description.md.j2")
+        }
         ("en", "tools/worker_inspect") => {
             include_str!("../../prompts/en/tools/worker_inspect_description.md.j2")
         }
@@ -0,0 +1,167 @@
+//! Quarantine log for suspicious inbound email.
+//!
+//! The email adapters run an optional pre-filter over parsed mail (configured
+//! regexes, spam headers, and a pluggable external scorer command). Messages
+//! that trip it are written here instead of reaching an agent, persisted to
+//! `quarantine.json` in the instance directory, and can be reviewed over the
+//! management API: releasing an entry re-injects the original message into
+//! inbound processing, discarding drops it for good.
+
+use crate::InboundMessage;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+const QUARANTINE_FILE: &str = "quarantine.json";
+
+/// Entries kept before the oldest are dropped.
+const MAX_QUARANTINE_ENTRIES: usize = 500;
+
+static GLOBAL_STORE: OnceLock<QuarantineStore> = OnceLock::new();
+
+/// Install the process-wide store the email poll paths write to.
+pub fn set_global(store: QuarantineStore) {
+    let _ = GLOBAL_STORE.set(store);
+}
+
+/// The process-wide store, when one has been installed.
+pub fn global() -> Option<&'static QuarantineStore> {
+    GLOBAL_STORE.get()
+}
+
+/// One quarantined message, kept whole so release loses nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedEmail {
+    pub id: String,
+    /// Which filter stage flagged it (e.g. "pattern: ...", "header: ...").
+    pub reason: String,
+    pub quarantined_at: i64,
+    pub message: InboundMessage,
+}
+
+/// Persisted quarantine log, shared by the email poll paths (sync, from
+/// blocking IMAP threads) and the API handlers.
+#[derive(Clone)]
+pub struct QuarantineStore {
+    path: PathBuf,
+    entries: Arc<Mutex<Vec<QuarantinedEmail>>>,
+}
+
+impl QuarantineStore {
+    /// Load persisted entries from the instance directory.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(QUARANTINE_FILE);
+        let entries: Vec<QuarantinedEmail> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    /// Quarantine a message, returning the entry ID.
+    pub fn add(&self, reason: String, message: InboundMessage) -> String {
+        let id = uuid::Uuid::new_v4().to_string()[..8].to_string();
+        let mut entries = self.entries.lock().expect("quarantine lock poisoned");
+        entries.push(QuarantinedEmail {
+            id: id.clone(),
+            reason,
+            quarantined_at: chrono::Utc::now().timestamp(),
+            message,
+        });
+        if entries.len() > MAX_QUARANTINE_ENTRIES {
+            let excess = entries.len() - MAX_QUARANTINE_ENTRIES;
+            entries.drain(..excess);
+        }
+        self.save(&entries);
+        id
+    }
+
+    /// All quarantined messages, oldest first.
+    pub fn list(&self) -> Vec<QuarantinedEmail> {
+        self.entries
+            .lock()
+            .expect("quarantine lock poisoned")
+            .clone()
+    }
+
+    /// Remove an entry and hand back its message for re-injection.
+    pub fn release(&self, id: &str) -> Option<InboundMessage> {
+        let mut entries = self.entries.lock().expect("quarantine lock poisoned");
+        let index = entries.iter().position(|entry| entry.id == id)?;
+        let entry = entries.remove(index);
+        self.save(&entries);
+        Some(entry.message)
+    }
+
+    /// Drop an entry without processing it. Returns false when unknown.
+    pub fn discard(&self, id: &str) -> bool {
+        let mut entries = self.entries.lock().expect("quarantine lock poisoned");
+        let before = entries.len();
+        entries.retain(|entry| entry.id != id);
+        let removed = entries.len() < before;
+        if removed {
+            self.save(&entries);
+        }
+        removed
+    }
+
+    fn save(&self, entries: &[QuarantinedEmail]) {
+        match serde_json::to_string_pretty(entries) {
+            Ok(contents) => {
+                if let Err(error) = std::fs::write(&self.path, contents) {
+                    tracing::warn!(%error, path = %self.path.display(), "failed to persist quarantine log");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(%error, "failed to serialize quarantine log");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuarantineStore;
+    use crate::{InboundMessage, MessageContent};
+
+    fn message(id: &str) -> InboundMessage {
+        InboundMessage {
+            id: id.into(),
+            source: "email".into(),
+            adapter: None,
+            conversation_id: "email:acct:thread".into(),
+            sender_id: "spammer@example.com".into(),
+            agent_id: None,
+            content: MessageContent::Text("buy now".into()),
+            timestamp: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            formatted_author: None,
+        }
+    }
+
+    #[test]
+    fn add_release_discard_round_trip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = QuarantineStore::load(dir.path());
+
+        let id_a = store.add("pattern: buy now".into(), message("a"));
+        let id_b = store.add("header: X-Spam-Flag".into(), message("b"));
+        assert_eq!(store.list().len(), 2);
+
+        // Entries survive a reload.
+        let reloaded = QuarantineStore::load(dir.path());
+        assert_eq!(reloaded.list().len(), 2);
+
+        let released = store.release(&id_a).expect("entry exists");
+        assert_eq!(released.id, "a");
+        assert!(store.release(&id_a).is_none());
+
+        assert!(store.discard(&id_b));
+        assert!(!store.discard(&id_b));
+        assert!(store.list().is_empty());
+    }
+}
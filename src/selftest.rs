@@ -0,0 +1,316 @@
+//! Startup self-test.
+//!
+//! Runs a battery of checks on boot — config validity, database migrations,
+//! provider reachability, adapter credentials. Critical failures put the
+//! process into safe mode: the management API stays up with the diagnostics
+//! report instead of crash-looping, so the operator can see what's wrong and
+//! fix it through the UI.
+
+use std::collections::HashSet;
+
+use crate::config::Config;
+
+/// Timeout for provider reachability probes.
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    /// Critical failures put the instance into safe mode; non-critical ones
+    /// are reported but don't block startup.
+    pub critical: bool,
+    pub detail: String,
+}
+
+/// Aggregated self-test results.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SelfTestReport {
+    pub checks: Vec<CheckResult>,
+    pub ran_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SelfTestReport {
+    pub fn has_critical_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.critical && !check.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+}
+
+/// Run all startup checks. Never panics; every problem lands in the report.
+pub async fn run_self_test(config: &Config) -> SelfTestReport {
+    let mut checks = Vec::new();
+
+    checks.push(check_config(config));
+    checks.push(check_migrations().await);
+    checks.push(check_provider_reachability(config).await);
+    checks.push(check_adapter_credentials(config));
+
+    SelfTestReport {
+        checks,
+        ran_at: chrono::Utc::now(),
+    }
+}
+
+/// Semantic config validation beyond what parsing enforces: agents exist,
+/// IDs are unique, and bindings reference known agents.
+fn check_config(config: &Config) -> CheckResult {
+    let agents = config.resolve_agents();
+    let mut problems = Vec::new();
+
+    if agents.is_empty() {
+        problems.push("no agents defined".to_string());
+    }
+
+    let mut agent_ids = HashSet::new();
+    for agent in &agents {
+        if !agent_ids.insert(agent.id.as_str()) {
+            problems.push(format!("duplicate agent id '{}'", agent.id));
+        }
+    }
+
+    problems.extend(binding_problems(&agent_ids, &config.bindings));
+
+    CheckResult {
+        name: "config".into(),
+        passed: problems.is_empty(),
+        critical: true,
+        detail: if problems.is_empty() {
+            format!("{} agents, {} bindings", agents.len(), config.bindings.len())
+        } else {
+            problems.join("; ")
+        },
+    }
+}
+
+/// Bindings that route to agents that don't exist.
+fn binding_problems(agent_ids: &HashSet<&str>, bindings: &[crate::config::Binding]) -> Vec<String> {
+    bindings
+        .iter()
+        .filter(|binding| !agent_ids.contains(binding.agent_id.as_str()))
+        .map(|binding| {
+            format!(
+                "binding for platform '{}' routes to unknown agent '{}'",
+                binding.channel, binding.agent_id
+            )
+        })
+        .collect()
+}
+
+/// Apply the bundled migrations to a scratch in-memory database. Catches a
+/// broken or mis-ordered migration before it can corrupt an agent store.
+async fn check_migrations() -> CheckResult {
+    let result = async {
+        let options = sqlx::sqlite::SqliteConnectOptions::new()
+            .in_memory(true)
+            .create_if_missing(true);
+        let pool = sqlx::pool::PoolOptions::<sqlx::Sqlite>::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+
+    CheckResult {
+        name: "database migrations".into(),
+        passed: result.is_ok(),
+        critical: true,
+        detail: match result {
+            Ok(()) => "migrations apply cleanly".into(),
+            Err(error) => error.to_string(),
+        },
+    }
+}
+
+/// Probe the API endpoints of configured LLM providers. Any HTTP response
+/// (including 401) counts as reachable; only connect failures don't.
+async fn check_provider_reachability(config: &Config) -> CheckResult {
+    let mut endpoints: Vec<(String, String)> = Vec::new();
+    if config.llm.anthropic_key.is_some() {
+        endpoints.push(("anthropic".into(), "https://api.anthropic.com".into()));
+    }
+    if config.llm.openai_key.is_some() {
+        endpoints.push(("openai".into(), "https://api.openai.com".into()));
+    }
+    if config.llm.openrouter_key.is_some() {
+        endpoints.push(("openrouter".into(), "https://openrouter.ai".into()));
+    }
+    if config.llm.gemini_key.is_some() {
+        endpoints.push((
+            "gemini".into(),
+            "https://generativelanguage.googleapis.com".into(),
+        ));
+    }
+    if let Some(base_url) = &config.llm.ollama_base_url {
+        endpoints.push(("ollama".into(), base_url.clone()));
+    }
+    for (id, provider) in &config.llm.providers {
+        if !provider.base_url.is_empty() {
+            endpoints.push((id.clone(), provider.base_url.clone()));
+        }
+    }
+
+    if endpoints.is_empty() {
+        return CheckResult {
+            name: "provider reachability".into(),
+            passed: true,
+            critical: false,
+            detail: "no providers configured".into(),
+        };
+    }
+
+    let client = crate::http::builder()
+        .timeout(std::time::Duration::from_secs(PROBE_TIMEOUT_SECS))
+        .build();
+    let Ok(client) = client else {
+        return CheckResult {
+            name: "provider reachability".into(),
+            passed: false,
+            critical: false,
+            detail: "failed to build HTTP client".into(),
+        };
+    };
+
+    let mut unreachable = Vec::new();
+    for (name, url) in &endpoints {
+        if let Err(error) = client.head(url).send().await {
+            unreachable.push(format!("{name} ({url}): {error}"));
+        }
+    }
+
+    CheckResult {
+        name: "provider reachability".into(),
+        passed: unreachable.is_empty(),
+        critical: false,
+        detail: if unreachable.is_empty() {
+            format!("{} providers reachable", endpoints.len())
+        } else {
+            unreachable.join("; ")
+        },
+    }
+}
+
+/// Enabled adapters with obviously missing credentials. Static validation
+/// only — live auth failures still surface through adapter health checks.
+fn check_adapter_credentials(config: &Config) -> CheckResult {
+    let messaging = &config.messaging;
+    let mut problems = Vec::new();
+
+    if let Some(discord) = &messaging.discord
+        && discord.enabled
+        && discord.token.is_empty()
+        && discord.instances.iter().all(|i| !i.enabled)
+    {
+        problems.push("discord is enabled without a token".to_string());
+    }
+    if let Some(telegram) = &messaging.telegram
+        && telegram.enabled
+        && telegram.token.is_empty()
+        && telegram.instances.iter().all(|i| !i.enabled)
+    {
+        problems.push("telegram is enabled without a token".to_string());
+    }
+    if let Some(mattermost) = &messaging.mattermost
+        && mattermost.enabled
+        && (mattermost.url.is_empty() || mattermost.token.is_empty())
+    {
+        problems.push("mattermost is enabled without url/token".to_string());
+    }
+    if let Some(rocketchat) = &messaging.rocketchat
+        && rocketchat.enabled
+        && (rocketchat.url.is_empty() || rocketchat.username.is_empty())
+    {
+        problems.push("rocketchat is enabled without url/username".to_string());
+    }
+    if let Some(whatsapp) = &messaging.whatsapp
+        && whatsapp.enabled
+        && (whatsapp.access_token.is_empty() || whatsapp.phone_number_id.is_empty())
+    {
+        problems.push("whatsapp is enabled without access_token/phone_number_id".to_string());
+    }
+    if let Some(sms) = &messaging.sms
+        && sms.enabled
+        && (sms.account_sid.is_empty() || sms.auth_token.is_empty() || sms.numbers.is_empty())
+    {
+        problems.push("sms is enabled without account_sid/auth_token/numbers".to_string());
+    }
+
+    CheckResult {
+        name: "adapter credentials".into(),
+        passed: problems.is_empty(),
+        critical: false,
+        detail: if problems.is_empty() {
+            "enabled adapters have credentials".into()
+        } else {
+            problems.join("; ")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(agent_id: &str) -> crate::config::Binding {
+        crate::config::Binding {
+            agent_id: agent_id.to_string(),
+            channel: "discord".into(),
+            adapter: None,
+            guild_id: None,
+            workspace_id: None,
+            chat_id: None,
+            channel_ids: vec![],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            onboarding: Vec::new(),
+            allowed_regions: Vec::new(),
+            trigger_prefix: None,
+            wake_words: Vec::new(),
+            bot_loop_protection: true,
+            link_safety: true,
+            reconnect_greeting: false,
+            reconnect_greeting_threshold_secs: 900,
+        }
+    }
+
+    #[test]
+    fn bindings_to_unknown_agents_are_flagged() {
+        let agent_ids: HashSet<&str> = ["assistant"].into();
+        let bindings = vec![binding("assistant"), binding("ghost")];
+
+        let problems = binding_problems(&agent_ids, &bindings);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("ghost"));
+    }
+
+    #[test]
+    fn only_critical_failures_trigger_safe_mode() {
+        let mut report = SelfTestReport {
+            checks: vec![CheckResult {
+                name: "provider reachability".into(),
+                passed: false,
+                critical: false,
+                detail: "offline".into(),
+            }],
+            ran_at: chrono::Utc::now(),
+        };
+        assert!(!report.has_critical_failures());
+        assert_eq!(report.failures().count(), 1);
+
+        report.checks.push(CheckResult {
+            name: "config".into(),
+            passed: false,
+            critical: true,
+            detail: "no agents defined".into(),
+        });
+        assert!(report.has_critical_failures());
+    }
+}
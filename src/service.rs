@@ -0,0 +1,232 @@
+//! OS service integration for Windows services and macOS launchd.
+//!
+//! `spacebot service install` registers the daemon with the platform service
+//! manager so it starts automatically, `uninstall` removes the registration,
+//! and `status` reports whether it is installed and running. On Windows the
+//! registration goes through `sc.exe`; on macOS a launchd property list is
+//! written to `~/Library/LaunchAgents` and loaded with `launchctl`. Both
+//! variants run the binary with `start --foreground` so logs flow to the
+//! service manager instead of the self-daemonizing path.
+
+use crate::config::Config;
+use crate::daemon::DaemonPaths;
+
+use anyhow::Context as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Service name registered with the Windows service control manager.
+const WINDOWS_SERVICE_NAME: &str = "spacebot";
+
+/// Reverse-DNS label used for the launchd job.
+const LAUNCHD_LABEL: &str = "app.spacedrive.spacebot";
+
+/// Register the daemon with the platform service manager.
+pub fn install(config_path: Option<&Path>) -> anyhow::Result<()> {
+    let exe = std::env::current_exe().context("failed to resolve current executable path")?;
+    let instance_dir = Config::default_instance_dir();
+    let paths = DaemonPaths::new(&instance_dir);
+    std::fs::create_dir_all(&paths.log_dir)
+        .with_context(|| format!("failed to create log directory {}", paths.log_dir.display()))?;
+
+    if cfg!(target_os = "macos") {
+        install_launchd(&exe, &instance_dir, &paths, config_path)
+    } else if cfg!(target_os = "windows") {
+        install_windows(&exe, config_path)
+    } else {
+        anyhow::bail!(
+            "service installation is only supported on Windows and macOS; \
+             on Linux run spacebot under systemd (see the deployment docs)"
+        );
+    }
+}
+
+/// Remove the service registration.
+pub fn uninstall() -> anyhow::Result<()> {
+    if cfg!(target_os = "macos") {
+        let plist = launchd_plist_path()?;
+        // Unload first so launchd stops tracking the job; ignore failures in
+        // case the job was never loaded in this login session.
+        let _ = Command::new("launchctl")
+            .args(["unload", "-w"])
+            .arg(&plist)
+            .status();
+        if plist.exists() {
+            std::fs::remove_file(&plist)
+                .with_context(|| format!("failed to remove {}", plist.display()))?;
+        }
+        println!("Removed launchd job {LAUNCHD_LABEL}");
+        Ok(())
+    } else if cfg!(target_os = "windows") {
+        let _ = Command::new("sc.exe")
+            .args(["stop", WINDOWS_SERVICE_NAME])
+            .status();
+        let status = Command::new("sc.exe")
+            .args(["delete", WINDOWS_SERVICE_NAME])
+            .status()
+            .context("failed to run sc.exe delete")?;
+        anyhow::ensure!(status.success(), "sc.exe delete exited with {status}");
+        println!("Removed Windows service {WINDOWS_SERVICE_NAME}");
+        Ok(())
+    } else {
+        anyhow::bail!("service management is only supported on Windows and macOS");
+    }
+}
+
+/// Report whether the service is installed and running.
+pub fn status() -> anyhow::Result<()> {
+    if cfg!(target_os = "macos") {
+        let plist = launchd_plist_path()?;
+        if !plist.exists() {
+            println!("Not installed (no plist at {})", plist.display());
+            return Ok(());
+        }
+        let loaded = Command::new("launchctl")
+            .args(["list", LAUNCHD_LABEL])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        println!("Installed: {}", plist.display());
+        println!("Loaded: {}", if loaded { "yes" } else { "no" });
+        Ok(())
+    } else if cfg!(target_os = "windows") {
+        let output = Command::new("sc.exe")
+            .args(["query", WINDOWS_SERVICE_NAME])
+            .output()
+            .context("failed to run sc.exe query")?;
+        if output.status.success() {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+        } else {
+            println!("Not installed (service {WINDOWS_SERVICE_NAME} not found)");
+        }
+        Ok(())
+    } else {
+        anyhow::bail!("service management is only supported on Windows and macOS");
+    }
+}
+
+fn install_launchd(
+    exe: &Path,
+    instance_dir: &Path,
+    paths: &DaemonPaths,
+    config_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let plist = launchd_plist_path()?;
+    if let Some(parent) = plist.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+
+    let mut arguments = vec![exe.display().to_string()];
+    if let Some(config_path) = config_path {
+        arguments.push("--config".to_string());
+        arguments.push(config_path.display().to_string());
+    }
+    arguments.push("start".to_string());
+    arguments.push("--foreground".to_string());
+    let argument_xml: String = arguments
+        .iter()
+        .map(|argument| format!("        <string>{}</string>\n", xml_escape(argument)))
+        .collect();
+
+    let stdout_log = paths.log_dir.join("launchd.out.log");
+    let stderr_log = paths.log_dir.join("launchd.err.log");
+    let contents = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+{argument_xml}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>EnvironmentVariables</key>
+    <dict>
+        <key>SPACEBOT_DIR</key>
+        <string>{instance_dir}</string>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{stdout_log}</string>
+    <key>StandardErrorPath</key>
+    <string>{stderr_log}</string>
+</dict>
+</plist>
+"#,
+        label = LAUNCHD_LABEL,
+        instance_dir = xml_escape(&instance_dir.display().to_string()),
+        stdout_log = xml_escape(&stdout_log.display().to_string()),
+        stderr_log = xml_escape(&stderr_log.display().to_string()),
+    );
+
+    std::fs::write(&plist, contents)
+        .with_context(|| format!("failed to write {}", plist.display()))?;
+
+    // Reload in case an older copy of the job is already registered.
+    let _ = Command::new("launchctl")
+        .args(["unload", "-w"])
+        .arg(&plist)
+        .status();
+    let status = Command::new("launchctl")
+        .args(["load", "-w"])
+        .arg(&plist)
+        .status()
+        .context("failed to run launchctl load")?;
+    anyhow::ensure!(status.success(), "launchctl load exited with {status}");
+
+    println!("Installed launchd job {LAUNCHD_LABEL}");
+    println!("Logs: {}", paths.log_dir.display());
+    Ok(())
+}
+
+fn install_windows(exe: &Path, config_path: Option<&Path>) -> anyhow::Result<()> {
+    // The binary runs as a console process under the service control manager;
+    // `start --foreground` keeps logging on stdout where the SCM captures it.
+    let mut bin_path = format!("\"{}\" start --foreground", exe.display());
+    if let Some(config_path) = config_path {
+        bin_path.push_str(&format!(" --config \"{}\"", config_path.display()));
+    }
+
+    let status = Command::new("sc.exe")
+        .args(["create", WINDOWS_SERVICE_NAME])
+        .arg(format!("binPath={bin_path}"))
+        .arg("start=auto")
+        .arg("DisplayName=Spacebot")
+        .status()
+        .context("failed to run sc.exe create")?;
+    anyhow::ensure!(status.success(), "sc.exe create exited with {status}");
+
+    println!("Installed Windows service {WINDOWS_SERVICE_NAME}");
+    println!("Start it with: sc.exe start {WINDOWS_SERVICE_NAME}");
+    Ok(())
+}
+
+fn launchd_plist_path() -> anyhow::Result<PathBuf> {
+    let home = dirs::home_dir().context("failed to resolve home directory")?;
+    Ok(home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{LAUNCHD_LABEL}.plist")))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xml_escape;
+
+    #[test]
+    fn xml_escape_handles_special_characters() {
+        assert_eq!(xml_escape("a & b <c>"), "a &amp; b &lt;c&gt;");
+        assert_eq!(xml_escape("/plain/path"), "/plain/path");
+    }
+}
@@ -33,7 +33,7 @@ pub async fn install_from_github(spec: &str, target_dir: &Path) -> Result<Vec<St
         "downloading skill from GitHub"
     );
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .get(&download_url)
         .send()
@@ -0,0 +1,249 @@
+//! Outbound message templates for recurring formats (digests, alerts, cron reports).
+//!
+//! Templates are MiniJinja sources stored as files under the instance
+//! directory (`templates/`), editable at runtime through the API and
+//! referenced by name from cron jobs and workflows. A template can have
+//! per-platform variants: `daily_digest.discord.j2` is preferred over
+//! `daily_digest.j2` when rendering for the `discord` adapter.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+
+use crate::error::Result;
+
+/// A stored template and its available platform variants.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TemplateInfo {
+    pub name: String,
+    /// Platforms with a dedicated variant (the base variant is implied).
+    pub platforms: Vec<String>,
+}
+
+/// File-backed store for outbound message templates.
+///
+/// Unlike the bundled prompt templates, these are user content: created and
+/// edited at runtime, so they live on disk rather than in the binary.
+#[derive(Debug, Clone)]
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    /// Open (and create if needed) the template directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create template directory {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Template names may only contain `[a-zA-Z0-9_-]` so they can't escape
+    /// the store directory.
+    fn check_name(value: &str) -> Result<()> {
+        if value.is_empty() {
+            return Err(anyhow::anyhow!("template name cannot be empty").into());
+        }
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow::anyhow!(
+                "invalid template name '{value}': only letters, digits, '-' and '_' are allowed"
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn path_for(&self, name: &str, platform: Option<&str>) -> Result<PathBuf> {
+        Self::check_name(name)?;
+        let filename = match platform {
+            Some(platform) => {
+                Self::check_name(platform)?;
+                format!("{name}.{platform}.j2")
+            }
+            None => format!("{name}.j2"),
+        };
+        Ok(self.dir.join(filename))
+    }
+
+    /// List all stored templates with their platform variants.
+    pub fn list(&self) -> Result<Vec<TemplateInfo>> {
+        let mut templates: std::collections::BTreeMap<String, Vec<String>> =
+            std::collections::BTreeMap::new();
+
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("failed to read template directory {}", self.dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(stem) = template_stem(&path) else {
+                continue;
+            };
+            match stem.split_once('.') {
+                Some((name, platform)) => templates
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(platform.to_string()),
+                None => {
+                    templates.entry(stem.to_string()).or_default();
+                }
+            }
+        }
+
+        Ok(templates
+            .into_iter()
+            .map(|(name, mut platforms)| {
+                platforms.sort();
+                TemplateInfo { name, platforms }
+            })
+            .collect())
+    }
+
+    /// Fetch a template source. Returns `None` when no such variant exists.
+    pub fn get(&self, name: &str, platform: Option<&str>) -> Result<Option<String>> {
+        let path = self.path_for(name, platform)?;
+        match std::fs::read_to_string(&path) {
+            Ok(source) => Ok(Some(source)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(anyhow::anyhow!(
+                "failed to read template {}: {error}",
+                path.display()
+            )
+            .into()),
+        }
+    }
+
+    /// Create or update a template variant, validating the syntax first.
+    pub fn put(&self, name: &str, platform: Option<&str>, source: &str) -> Result<()> {
+        let mut env = minijinja::Environment::new();
+        env.add_template("candidate", source)
+            .map_err(|error| anyhow::anyhow!("invalid template syntax: {error}"))?;
+
+        let path = self.path_for(name, platform)?;
+        std::fs::write(&path, source)
+            .with_context(|| format!("failed to write template {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Delete a template variant. Returns whether anything was removed.
+    pub fn delete(&self, name: &str, platform: Option<&str>) -> Result<bool> {
+        let path = self.path_for(name, platform)?;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(anyhow::anyhow!(
+                "failed to delete template {}: {error}",
+                path.display()
+            )
+            .into()),
+        }
+    }
+
+    /// Render a template by name with the platform variant preferred over the
+    /// base one.
+    pub fn render(
+        &self,
+        name: &str,
+        platform: Option<&str>,
+        context: &serde_json::Value,
+    ) -> Result<String> {
+        let source = match platform {
+            Some(platform) => match self.get(name, Some(platform))? {
+                Some(source) => source,
+                None => self
+                    .get(name, None)?
+                    .with_context(|| format!("no template named '{name}'"))?,
+            },
+            None => self
+                .get(name, None)?
+                .with_context(|| format!("no template named '{name}'"))?,
+        };
+
+        let mut env = minijinja::Environment::new();
+        env.add_template(name, &source)
+            .map_err(|error| anyhow::anyhow!("invalid template '{name}': {error}"))?;
+        let template = env
+            .get_template(name)
+            .map_err(|error| anyhow::anyhow!("template '{name}' missing after add: {error}"))?;
+        template
+            .render(context)
+            .map_err(|error| anyhow::anyhow!("failed to render template '{name}': {error}").into())
+    }
+}
+
+/// The file stem of a `.j2` template file, or `None` for other files.
+fn template_stem(path: &Path) -> Option<&str> {
+    let filename = path.file_name()?.to_str()?;
+    filename.strip_suffix(".j2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, TemplateStore) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = TemplateStore::new(dir.path().join("templates")).expect("store");
+        (dir, store)
+    }
+
+    #[test]
+    fn put_render_roundtrip() {
+        let (_dir, store) = store();
+        store
+            .put("digest", None, "Report for {{ cron_id }}: {{ result }}")
+            .unwrap();
+
+        let rendered = store
+            .render(
+                "digest",
+                None,
+                &serde_json::json!({ "cron_id": "daily", "result": "all green" }),
+            )
+            .unwrap();
+        assert_eq!(rendered, "Report for daily: all green");
+    }
+
+    #[test]
+    fn platform_variant_preferred_with_fallback() {
+        let (_dir, store) = store();
+        store.put("alert", None, "base: {{ result }}").unwrap();
+        store
+            .put("alert", Some("discord"), "**{{ result }}**")
+            .unwrap();
+
+        let context = serde_json::json!({ "result": "disk full" });
+        assert_eq!(
+            store.render("alert", Some("discord"), &context).unwrap(),
+            "**disk full**"
+        );
+        // No telegram variant: falls back to the base template
+        assert_eq!(
+            store.render("alert", Some("telegram"), &context).unwrap(),
+            "base: disk full"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_names_and_syntax() {
+        let (_dir, store) = store();
+        assert!(store.put("../escape", None, "x").is_err());
+        assert!(store.put("bad", None, "{{ unclosed").is_err());
+    }
+
+    #[test]
+    fn list_groups_platform_variants() {
+        let (_dir, store) = store();
+        store.put("digest", None, "a").unwrap();
+        store.put("digest", Some("slack"), "b").unwrap();
+        store.put("report", Some("discord"), "c").unwrap();
+
+        let listed = store.list().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].name, "digest");
+        assert_eq!(listed[0].platforms, vec!["slack".to_string()]);
+        assert_eq!(listed[1].name, "report");
+        assert_eq!(listed[1].platforms, vec!["discord".to_string()]);
+    }
+}
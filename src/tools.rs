@@ -29,6 +29,9 @@ pub mod browser;
 pub mod cancel;
 pub mod channel_recall;
 pub mod cron;
+#[cfg(feature = "adapter-email")]
+pub mod email_action;
+#[cfg(feature = "adapter-email")]
 pub mod email_search;
 pub mod exec;
 pub mod file;
@@ -36,6 +39,9 @@ pub mod mcp;
 pub mod memory_delete;
 pub mod memory_recall;
 pub mod memory_save;
+pub mod kube;
+pub mod ops;
+pub mod pin_context;
 pub mod react;
 pub mod read_skill;
 pub mod reply;
@@ -46,10 +52,18 @@ pub mod send_message_to_another_channel;
 pub mod set_status;
 pub mod shell;
 pub mod skip;
+pub mod slack_context;
 pub mod spawn_worker;
+pub mod sql;
+pub mod ssh;
 pub mod task_create;
 pub mod task_list;
 pub mod task_update;
+#[cfg(feature = "adapter-telegram")]
+pub mod telegram_message;
+#[cfg(feature = "adapter-telegram")]
+pub mod telegram_moderation;
+pub mod translate;
 pub mod web_search;
 pub mod worker_inspect;
 
@@ -63,7 +77,11 @@ pub use channel_recall::{
     ChannelRecallArgs, ChannelRecallError, ChannelRecallOutput, ChannelRecallTool,
 };
 pub use cron::{CronArgs, CronError, CronOutput, CronTool};
+#[cfg(feature = "adapter-email")]
+pub use email_action::{EmailActionArgs, EmailActionError, EmailActionOutput, EmailActionTool};
+#[cfg(feature = "adapter-email")]
 pub use email_search::{EmailSearchArgs, EmailSearchError, EmailSearchOutput, EmailSearchTool};
+pub use slack_context::{SlackContextArgs, SlackContextError, SlackContextOutput, SlackContextTool};
 pub use exec::{EnvVar, ExecArgs, ExecError, ExecOutput, ExecResult, ExecTool};
 pub use file::{FileArgs, FileEntry, FileEntryOutput, FileError, FileOutput, FileTool, FileType};
 pub use mcp::{McpToolAdapter, McpToolError, McpToolOutput};
@@ -76,6 +94,15 @@ pub use memory_recall::{
 pub use memory_save::{
     AssociationInput, MemorySaveArgs, MemorySaveError, MemorySaveOutput, MemorySaveTool,
 };
+pub use kube::{KubeAction, KubeArgs, KubeError, KubeOutput, KubeTool};
+pub use ops::{OpsAction, OpsArgs, OpsError, OpsOutput, OpsTool};
+pub use sql::{SqlAction, SqlArgs, SqlError, SqlOutput, SqlTool};
+pub use pin_context::{PinContextAction, PinContextArgs, PinContextError, PinContextOutput, PinContextTool};
+pub use ssh::{SshArgs, SshError, SshOutput, SshTool};
+pub use translate::{
+    TranslationLookupArgs, TranslationLookupOutput, TranslationLookupTool, TranslationMemoryError,
+    TranslationSaveArgs, TranslationSaveOutput, TranslationSaveTool,
+};
 pub use react::{ReactArgs, ReactError, ReactOutput, ReactTool};
 pub use read_skill::{ReadSkillArgs, ReadSkillError, ReadSkillOutput, ReadSkillTool};
 pub use reply::{RepliedFlag, ReplyArgs, ReplyError, ReplyOutput, ReplyTool, new_replied_flag};
@@ -94,6 +121,15 @@ pub use spawn_worker::{SpawnWorkerArgs, SpawnWorkerError, SpawnWorkerOutput, Spa
 pub use task_create::{TaskCreateArgs, TaskCreateError, TaskCreateOutput, TaskCreateTool};
 pub use task_list::{TaskListArgs, TaskListError, TaskListOutput, TaskListTool};
 pub use task_update::{TaskUpdateArgs, TaskUpdateError, TaskUpdateOutput, TaskUpdateTool};
+#[cfg(feature = "adapter-telegram")]
+pub use telegram_message::{
+    TelegramMessageArgs, TelegramMessageError, TelegramMessageOutput, TelegramMessageTool,
+};
+#[cfg(feature = "adapter-telegram")]
+pub use telegram_moderation::{
+    TelegramModerationArgs, TelegramModerationError, TelegramModerationOutput,
+    TelegramModerationTool,
+};
 pub use web_search::{SearchResult, WebSearchArgs, WebSearchError, WebSearchOutput, WebSearchTool};
 pub use worker_inspect::{
     WorkerInspectArgs, WorkerInspectError, WorkerInspectOutput, WorkerInspectTool,
@@ -251,6 +287,7 @@ pub async fn add_channel_tools(
             .get(state.deps.agent_id.as_ref())
             .cloned()
             .unwrap_or_else(|| state.deps.agent_id.to_string());
+        let cite_sources = **state.deps.runtime_config.cite_sources.load();
         handle
             .add_tool(ReplyTool::new(
                 response_tx.clone(),
@@ -259,6 +296,7 @@ pub async fn add_channel_tools(
                 state.channel_id.clone(),
                 replied_flag.clone(),
                 agent_display_name,
+                cite_sources,
             ))
             .await?;
     }
@@ -287,6 +325,9 @@ pub async fn add_channel_tools(
             state.deps.runtime_config.workspace_dir.clone(),
         ))
         .await?;
+    handle
+        .add_tool(PinContextTool::new(state.pinned_context.clone()))
+        .await?;
     handle.add_tool(CancelTool::new(state)).await?;
     handle
         .add_tool(SkipTool::new(skip_flag.clone(), response_tx.clone()))
@@ -331,6 +372,7 @@ pub async fn remove_channel_tools(
     handle.remove_tool(SkipTool::NAME).await?;
     handle.remove_tool(SendFileTool::NAME).await?;
     handle.remove_tool(ReactTool::NAME).await?;
+    handle.remove_tool(PinContextTool::NAME).await?;
     // Cron, send_message, and send_agent_message removal is best-effort since not all channels have them
     let _ = handle.remove_tool(CronTool::NAME).await;
     let _ = handle.remove_tool(SendMessageTool::NAME).await;
@@ -353,13 +395,15 @@ pub fn create_branch_tool_server(
     conversation_logger: crate::conversation::history::ConversationLogger,
     channel_store: crate::conversation::ChannelStore,
     run_logger: crate::conversation::history::ProcessRunLogger,
+    sqlite_pool: sqlx::SqlitePool,
 ) -> ToolServerHandle {
+    let citation_channel = state.as_ref().map(|state| state.channel_id.clone());
     let mut server = ToolServer::new()
         .tool(MemorySaveTool::new(memory_search.clone()))
-        .tool(MemoryRecallTool::new(memory_search.clone()))
+        .tool(MemoryRecallTool::new(memory_search.clone()).with_channel_id(citation_channel))
         .tool(MemoryDeleteTool::new(memory_search))
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
-        .tool(EmailSearchTool::new(runtime_config))
+        .tool(SlackContextTool::new(runtime_config.clone()))
         .tool(WorkerInspectTool::new(run_logger, agent_id.to_string()))
         .tool(TaskCreateTool::new(
             task_store.clone(),
@@ -369,6 +413,31 @@ pub fn create_branch_tool_server(
         .tool(TaskListTool::new(task_store.clone(), agent_id.to_string()))
         .tool(TaskUpdateTool::for_branch(task_store, agent_id.clone()));
 
+    #[cfg(feature = "adapter-email")]
+    {
+        server = server
+            .tool(EmailSearchTool::new(runtime_config.clone()))
+            .tool(EmailActionTool::new(runtime_config.clone()));
+    }
+
+    #[cfg(feature = "adapter-telegram")]
+    {
+        server = server
+            .tool(TelegramMessageTool::new(runtime_config.clone()))
+            .tool(TelegramModerationTool::new(runtime_config.clone()));
+    }
+
+    let translation_config = runtime_config.translation_memory.load();
+    if translation_config.enabled {
+        let translation_memory = crate::memory::TranslationMemory::new(sqlite_pool);
+        server = server
+            .tool(TranslationLookupTool::new(
+                translation_memory.clone(),
+                translation_config.ttl_secs,
+            ))
+            .tool(TranslationSaveTool::new(translation_memory));
+    }
+
     if let Some(state) = state {
         server = server.tool(SpawnWorkerTool::new(state));
     }
@@ -399,6 +468,7 @@ pub fn create_worker_tool_server(
     mcp_tools: Vec<McpToolAdapter>,
     runtime_config: Arc<RuntimeConfig>,
 ) -> ToolServerHandle {
+    let citation_channel = channel_id.clone();
     let mut server = ToolServer::new()
         .tool(ShellTool::new(workspace.clone(), sandbox.clone()))
         .tool(FileTool::new(workspace.clone()))
@@ -411,14 +481,34 @@ pub fn create_worker_tool_server(
         .tool(SetStatusTool::new(
             agent_id, worker_id, channel_id, event_tx,
         ))
-        .tool(ReadSkillTool::new(runtime_config));
+        .tool(ReadSkillTool::new(runtime_config.clone()));
 
     if browser_config.enabled {
         server = server.tool(BrowserTool::new(browser_config, screenshot_dir));
     }
 
+    let ops_config = runtime_config.ops.load();
+    if ops_config.enabled {
+        server = server.tool(OpsTool::new(ops_config.as_ref().clone()));
+    }
+
+    let kube_config = runtime_config.kube.load();
+    if kube_config.enabled {
+        server = server.tool(KubeTool::new(kube_config.as_ref().clone()));
+    }
+
+    let sql_config = runtime_config.sql.load();
+    if sql_config.enabled {
+        server = server.tool(SqlTool::new(sql_config.as_ref().clone()));
+    }
+
+    let ssh_config = runtime_config.ssh.load();
+    if ssh_config.enabled {
+        server = server.tool(SshTool::new(ssh_config.as_ref().clone()));
+    }
+
     if let Some(key) = brave_search_key {
-        server = server.tool(WebSearchTool::new(key));
+        server = server.tool(WebSearchTool::new(key).with_channel_id(citation_channel));
     }
 
     for mcp_tool in mcp_tools {
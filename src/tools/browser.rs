@@ -79,6 +79,20 @@ fn validate_url(url: &str) -> Result<(), BrowserError> {
     Ok(())
 }
 
+/// Refuse navigation to URLs flagged by the link safety checker, when one is
+/// installed.
+async fn check_link_safety(url: &str) -> Result<(), BrowserError> {
+    if let Some(checker) = crate::link_safety::global()
+        && let Some(verdict) = checker.check_url(url).await
+    {
+        return Err(BrowserError::new(format!(
+            "refusing to open flagged link {url} ({}: {})",
+            verdict.source, verdict.threat
+        )));
+    }
+    Ok(())
+}
+
 /// Returns true if the IP address belongs to a private, loopback, or
 /// link-local range that should not be reachable from the browser tool.
 fn is_blocked_ip(ip: IpAddr) -> bool {
@@ -511,6 +525,7 @@ impl BrowserTool {
         };
 
         validate_url(&url)?;
+        check_link_safety(&url).await?;
 
         let mut state = self.state.lock().await;
         let page = self.get_or_create_page(&mut state, Some(&url)).await?;
@@ -543,6 +558,7 @@ impl BrowserTool {
 
         if target_url != "about:blank" {
             validate_url(target_url)?;
+            check_link_safety(target_url).await?;
         }
 
         let page = browser
@@ -1,6 +1,6 @@
 //! Cron job management tool for creating, listing, and deleting scheduled tasks.
 
-use crate::cron::scheduler::{CronConfig, Scheduler};
+use crate::cron::scheduler::{CronConfig, Scheduler, UpcomingCronRun};
 use crate::cron::store::CronStore;
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -32,6 +32,23 @@ impl CronTool {
         }
     }
 
+    /// Upcoming runs and recent execution summaries for the channel time
+    /// context bulletin. Store failures degrade to an empty history rather
+    /// than blocking prompt assembly.
+    pub async fn time_context(
+        &self,
+        upcoming_limit: usize,
+        recent_limit: i64,
+    ) -> (Vec<UpcomingCronRun>, Vec<crate::cron::CronExecutionEntry>) {
+        let upcoming = self.scheduler.upcoming_runs(upcoming_limit).await;
+        let recent = self
+            .store
+            .load_all_executions(recent_limit)
+            .await
+            .unwrap_or_default();
+        (upcoming, recent)
+    }
+
     pub fn with_default_delivery_target(mut self, default_delivery_target: Option<String>) -> Self {
         self.default_delivery_target = default_delivery_target;
         self
@@ -62,6 +79,9 @@ pub struct CronArgs {
     /// Optional for "create": where to deliver results, in "adapter:target" format (e.g. "discord:123456789"). If omitted, defaults to the current conversation when available.
     #[serde(default)]
     pub delivery_target: Option<String>,
+    /// Optional for "create": name of an outbound template to render the result through (see the templates API).
+    #[serde(default)]
+    pub template: Option<String>,
     /// Optional for "create": hour (0-23) when the job becomes active.
     #[serde(default)]
     pub active_start_hour: Option<u8>,
@@ -272,6 +292,7 @@ impl CronTool {
             cron_expr: cron_expr.clone(),
             interval_secs,
             delivery_target: delivery_target.clone(),
+            template: args.template.clone(),
             active_hours,
             enabled: true,
             run_once,
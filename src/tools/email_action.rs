@@ -0,0 +1,140 @@
+//! Mailbox triage actions (archive, move, label, mark read/unread, delete).
+
+use crate::config::{Config, EmailConfig, RuntimeConfig};
+use crate::messaging::email::EmailMailboxAction;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Tool for mutating mailbox state through IMAP.
+#[derive(Debug, Clone)]
+pub struct EmailActionTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl EmailActionTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for email_action tool.
+#[derive(Debug, thiserror::Error)]
+#[error("email_action failed: {0}")]
+pub struct EmailActionError(String);
+
+/// Arguments for email_action.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EmailActionArgs {
+    /// One of: mark_read, mark_unread, move, label, archive, delete.
+    pub action: String,
+    /// Folder containing the message (from email_search results).
+    pub folder: String,
+    /// IMAP UID of the message (from email_search results).
+    pub uid: u32,
+    /// Destination folder for the "move" action.
+    #[serde(default)]
+    pub target_folder: Option<String>,
+    /// Keyword flag for the "label" action.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Output for email_action.
+#[derive(Debug, Serialize)]
+pub struct EmailActionOutput {
+    pub action: String,
+    pub folder: String,
+    pub uid: u32,
+    pub summary: String,
+}
+
+impl Tool for EmailActionTool {
+    const NAME: &'static str = "email_action";
+
+    type Error = EmailActionError;
+    type Args = EmailActionArgs;
+    type Output = EmailActionOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/email_action").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "description": "Action to perform: mark_read, mark_unread, move, label, archive, or delete."
+                    },
+                    "folder": {
+                        "type": "string",
+                        "description": "Folder containing the message (from email_search results)."
+                    },
+                    "uid": {
+                        "type": "integer",
+                        "description": "IMAP UID of the message (from email_search results)."
+                    },
+                    "target_folder": {
+                        "type": "string",
+                        "description": "Destination folder, required for the move action."
+                    },
+                    "label": {
+                        "type": "string",
+                        "description": "Keyword flag to add, required for the label action."
+                    }
+                },
+                "required": ["action", "folder", "uid"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let action =
+            EmailMailboxAction::from_parts(&args.action, args.target_folder, args.label)
+                .map_err(|error| EmailActionError(error.to_string()))?;
+
+        let instance_dir = self.runtime_config.instance_dir.clone();
+        let folder = args.folder.clone();
+        let uid = args.uid;
+        let summary = tokio::task::spawn_blocking(move || {
+            let email_config = load_email_config(&instance_dir)?;
+            crate::messaging::email::apply_mailbox_action(&email_config, &folder, uid, &action)
+                .map_err(|error| EmailActionError(error.to_string()))
+        })
+        .await
+        .map_err(|error| EmailActionError(format!("email action task failed: {error}")))??;
+
+        Ok(EmailActionOutput {
+            action: args.action,
+            folder: args.folder,
+            uid: args.uid,
+            summary,
+        })
+    }
+}
+
+fn load_email_config(instance_dir: &Path) -> Result<EmailConfig, EmailActionError> {
+    let config = Config::load_for_instance(instance_dir).map_err(|error| {
+        EmailActionError(format!(
+            "failed to resolve config for {}: {error}",
+            instance_dir.display()
+        ))
+    })?;
+
+    let email = config
+        .messaging
+        .email
+        .ok_or_else(|| EmailActionError("email adapter is not configured".to_string()))?;
+
+    if email.imap_host.trim().is_empty() {
+        return Err(EmailActionError(
+            "email adapter has no IMAP host configured".to_string(),
+        ));
+    }
+
+    Ok(email)
+}
@@ -0,0 +1,298 @@
+//! Kubernetes read-only observability tool (task workers only).
+//!
+//! Shells out to `kubectl` for triage-style queries: pods, deployments,
+//! recent events, and capped log tails. Contexts and namespaces must appear
+//! on the configured allowlists, and nothing here can mutate the cluster —
+//! only `get`, `logs`, and `events` subcommands are ever issued.
+
+use crate::config::KubeConfig;
+use crate::tools::{MAX_TOOL_OUTPUT_BYTES, truncate_output};
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool for read-only Kubernetes observability via kubectl.
+#[derive(Debug, Clone)]
+pub struct KubeTool {
+    config: KubeConfig,
+}
+
+impl KubeTool {
+    pub fn new(config: KubeConfig) -> Self {
+        Self { config }
+    }
+
+    fn check_context_allowed(&self, context: Option<&str>) -> Result<Option<String>, KubeError> {
+        match context {
+            Some(context) => {
+                if !self.config.contexts.iter().any(|c| c == context) {
+                    return Err(KubeError::NotAllowed(format!("context '{context}'")));
+                }
+                check_identifier(context)?;
+                Ok(Some(context.to_string()))
+            }
+            // No context requested: use the single configured one, or kubectl's
+            // current context when the allowlist is empty.
+            None => Ok(self.config.contexts.first().cloned()),
+        }
+    }
+
+    fn check_namespace_allowed(&self, namespace: Option<&str>) -> Result<String, KubeError> {
+        let namespace = namespace
+            .or(self.config.namespaces.first().map(String::as_str))
+            .unwrap_or("default");
+        if !self.config.namespaces.iter().any(|n| n == namespace) {
+            return Err(KubeError::NotAllowed(format!("namespace '{namespace}'")));
+        }
+        check_identifier(namespace)?;
+        Ok(namespace.to_string())
+    }
+
+    async fn run_kubectl(
+        &self,
+        context: Option<&str>,
+        namespace: &str,
+        args: &[&str],
+    ) -> Result<String, KubeError> {
+        let mut command = tokio::process::Command::new("kubectl");
+        if let Some(context) = context {
+            command.arg("--context").arg(context);
+        }
+        command.arg("-n").arg(namespace);
+        command.args(args);
+
+        let output = command
+            .output()
+            .await
+            .map_err(|error| KubeError::Kubectl(format!("failed to run kubectl: {error}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(KubeError::Kubectl(format!(
+                "kubectl exited with {}: {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// Error type for the kube tool.
+#[derive(Debug, thiserror::Error)]
+pub enum KubeError {
+    #[error("Kubernetes tooling is not enabled in this instance's config")]
+    Disabled,
+
+    #[error("{0} is not on the kube allowlist")]
+    NotAllowed(String),
+
+    #[error("'{0}' is not a valid Kubernetes identifier")]
+    InvalidName(String),
+
+    #[error("kubectl error: {0}")]
+    Kubectl(String),
+}
+
+/// The query to run.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KubeAction {
+    /// List pods in a namespace with status and restart counts.
+    ListPods,
+    /// List deployments with replica readiness.
+    ListDeployments,
+    /// Show recent events, most recent last.
+    Events,
+    /// Tail logs from a pod (optionally a specific container).
+    PodLogs,
+}
+
+/// Arguments for the kube tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct KubeArgs {
+    /// The query to run.
+    pub action: KubeAction,
+    /// Cluster context. Defaults to the first configured context.
+    pub context: Option<String>,
+    /// Namespace. Defaults to the first configured namespace.
+    pub namespace: Option<String>,
+    /// Pod name. Required for pod_logs.
+    pub pod: Option<String>,
+    /// Container name within the pod, for multi-container pods.
+    pub container: Option<String>,
+    /// Number of log lines to tail (default 100, capped by config).
+    pub lines: Option<usize>,
+}
+
+/// Output from the kube tool.
+#[derive(Debug, Serialize)]
+pub struct KubeOutput {
+    /// Raw kubectl output.
+    pub result: String,
+}
+
+impl Tool for KubeTool {
+    const NAME: &'static str = "kube";
+
+    type Error = KubeError;
+    type Args = KubeArgs;
+    type Output = KubeOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/kube").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list_pods", "list_deployments", "events", "pod_logs"],
+                        "description": "The query to run"
+                    },
+                    "context": {
+                        "type": "string",
+                        "description": "Cluster context (defaults to the first configured context)"
+                    },
+                    "namespace": {
+                        "type": "string",
+                        "description": "Namespace (defaults to the first configured namespace)"
+                    },
+                    "pod": {
+                        "type": "string",
+                        "description": "Pod name, required for pod_logs"
+                    },
+                    "container": {
+                        "type": "string",
+                        "description": "Container name for multi-container pods"
+                    },
+                    "lines": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of log lines to tail (default 100)"
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        if !self.config.enabled {
+            return Err(KubeError::Disabled);
+        }
+
+        let context = self.check_context_allowed(args.context.as_deref())?;
+        let namespace = self.check_namespace_allowed(args.namespace.as_deref())?;
+        let lines = args
+            .lines
+            .unwrap_or(100)
+            .min(self.config.log_tail_limit.max(1));
+
+        let result = match args.action {
+            KubeAction::ListPods => {
+                self.run_kubectl(context.as_deref(), &namespace, &["get", "pods", "-o", "wide"])
+                    .await?
+            }
+            KubeAction::ListDeployments => {
+                self.run_kubectl(context.as_deref(), &namespace, &["get", "deployments"])
+                    .await?
+            }
+            KubeAction::Events => {
+                self.run_kubectl(
+                    context.as_deref(),
+                    &namespace,
+                    &["get", "events", "--sort-by=.lastTimestamp"],
+                )
+                .await?
+            }
+            KubeAction::PodLogs => {
+                let pod = args
+                    .pod
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| KubeError::NotAllowed("missing pod name".into()))?;
+                check_identifier(pod)?;
+
+                let lines_arg = format!("--tail={lines}");
+                let mut kubectl_args = vec!["logs", pod, lines_arg.as_str(), "--timestamps"];
+                if let Some(container) = args.container.as_deref() {
+                    check_identifier(container)?;
+                    kubectl_args.push("-c");
+                    kubectl_args.push(container);
+                }
+                self.run_kubectl(context.as_deref(), &namespace, &kubectl_args)
+                    .await?
+            }
+        };
+
+        let result = if result.trim().is_empty() {
+            "(no output)".to_string()
+        } else {
+            result
+        };
+
+        Ok(KubeOutput {
+            result: truncate_output(&result, MAX_TOOL_OUTPUT_BYTES),
+        })
+    }
+}
+
+/// Names feed a command line — reject anything that isn't a plain
+/// DNS-label-style identifier.
+fn check_identifier(name: &str) -> Result<(), KubeError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(KubeError::InvalidName(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_allowlists() -> KubeConfig {
+        KubeConfig {
+            enabled: true,
+            contexts: vec!["prod".into()],
+            namespaces: vec!["default".into(), "monitoring".into()],
+            log_tail_limit: 200,
+        }
+    }
+
+    #[test]
+    fn namespace_allowlist_enforced() {
+        let tool = KubeTool::new(config_with_allowlists());
+        assert!(tool.check_namespace_allowed(Some("monitoring")).is_ok());
+        assert!(tool.check_namespace_allowed(Some("kube-system")).is_err());
+    }
+
+    #[test]
+    fn default_namespace_is_first_configured() {
+        let tool = KubeTool::new(config_with_allowlists());
+        assert_eq!(tool.check_namespace_allowed(None).unwrap(), "default");
+    }
+
+    #[test]
+    fn context_allowlist_enforced() {
+        let tool = KubeTool::new(config_with_allowlists());
+        assert!(tool.check_context_allowed(Some("prod")).is_ok());
+        assert!(tool.check_context_allowed(Some("staging")).is_err());
+    }
+
+    #[test]
+    fn identifiers_with_shell_metacharacters_rejected() {
+        assert!(check_identifier("web-7f9c4d").is_ok());
+        assert!(check_identifier("web; rm -rf /").is_err());
+        assert!(check_identifier("").is_err());
+    }
+}
@@ -16,12 +16,24 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct MemoryRecallTool {
     memory_search: Arc<MemorySearch>,
+    /// Channel to record recalled memories against for reply citations.
+    channel_id: Option<crate::ChannelId>,
 }
 
 impl MemoryRecallTool {
     /// Create a new memory recall tool.
     pub fn new(memory_search: Arc<MemorySearch>) -> Self {
-        Self { memory_search }
+        Self {
+            memory_search,
+            channel_id: None,
+        }
+    }
+
+    /// Attribute recalled memories to a channel so the reply tool can cite
+    /// them as sources.
+    pub fn with_channel_id(mut self, channel_id: Option<crate::ChannelId>) -> Self {
+        self.channel_id = channel_id;
+        self
     }
 }
 
@@ -251,6 +263,12 @@ impl Tool for MemoryRecallTool {
             });
         }
 
+        if let Some(channel_id) = &self.channel_id {
+            for memory in &memories {
+                crate::citations::record(channel_id, "memory", citation_title(&memory.content), None);
+            }
+        }
+
         let total_found = search_results.len();
         let summary = format_memories(&memories);
 
@@ -265,6 +283,16 @@ impl Tool for MemoryRecallTool {
     }
 }
 
+/// First line of a memory, capped so citation footnotes stay one line.
+fn citation_title(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= 80 {
+        return first_line.to_string();
+    }
+    let truncated: String = first_line.chars().take(79).collect();
+    format!("{truncated}…")
+}
+
 /// Format memories for display to an agent.
 pub fn format_memories(memories: &[MemoryOutput]) -> String {
     if memories.is_empty() {
@@ -0,0 +1,382 @@
+//! Host operations tool for Docker containers and systemd units (task workers only).
+//!
+//! Opt-in chatops control plane: lists, restarts, and tails logs for Docker
+//! containers (via the local socket) and systemd units (via systemctl /
+//! journalctl). Every target must appear on the configured allowlist, and
+//! restarts are additionally gated by `allow_restart`.
+
+use crate::config::OpsConfig;
+use crate::tools::{MAX_TOOL_OUTPUT_BYTES, truncate_output};
+
+use bollard::container::{ListContainersOptions, LogsOptions, RestartContainerOptions};
+use futures::StreamExt as _;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool for inspecting and restarting allowed Docker containers and systemd units.
+#[derive(Debug, Clone)]
+pub struct OpsTool {
+    config: OpsConfig,
+}
+
+impl OpsTool {
+    pub fn new(config: OpsConfig) -> Self {
+        Self { config }
+    }
+
+    fn check_container_allowed(&self, name: &str) -> Result<(), OpsError> {
+        if !self.config.docker_enabled {
+            return Err(OpsError::Disabled("docker".into()));
+        }
+        if !self
+            .config
+            .allowed_containers
+            .iter()
+            .any(|allowed| allowed == name)
+        {
+            return Err(OpsError::NotAllowed(format!("container '{name}'")));
+        }
+        Ok(())
+    }
+
+    fn check_unit_allowed(&self, name: &str) -> Result<(), OpsError> {
+        if !self.config.systemd_enabled {
+            return Err(OpsError::Disabled("systemd".into()));
+        }
+        // Accept both "foo" and "foo.service" against an allowlist of either form
+        let bare = name.trim_end_matches(".service");
+        if !self
+            .config
+            .allowed_units
+            .iter()
+            .any(|allowed| allowed == name || allowed.trim_end_matches(".service") == bare)
+        {
+            return Err(OpsError::NotAllowed(format!("unit '{name}'")));
+        }
+        // Unit names feed a command line — reject anything that isn't a plain name
+        if !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | '@'))
+        {
+            return Err(OpsError::NotAllowed(format!("unit '{name}'")));
+        }
+        Ok(())
+    }
+
+    async fn docker(&self) -> Result<bollard::Docker, OpsError> {
+        bollard::Docker::connect_with_local_defaults()
+            .map_err(|error| OpsError::Docker(error.to_string()))
+    }
+
+    async fn run_systemctl(&self, args: &[&str]) -> Result<String, OpsError> {
+        let output = tokio::process::Command::new(args[0])
+            .args(&args[1..])
+            .output()
+            .await
+            .map_err(|error| OpsError::Systemd(format!("failed to run {}: {error}", args[0])))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(OpsError::Systemd(format!(
+                "{} exited with {}: {}",
+                args[0],
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(stdout)
+    }
+}
+
+/// Error type for the ops tool.
+#[derive(Debug, thiserror::Error)]
+pub enum OpsError {
+    #[error("Ops backend '{0}' is not enabled in this instance's config")]
+    Disabled(String),
+
+    #[error("{0} is not on the ops allowlist")]
+    NotAllowed(String),
+
+    #[error("Restarts are not permitted: set defaults.ops.allow_restart = true to enable")]
+    RestartNotAllowed,
+
+    #[error("Docker error: {0}")]
+    Docker(String),
+
+    #[error("systemd error: {0}")]
+    Systemd(String),
+}
+
+/// The operation to perform.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpsAction {
+    /// List allowed Docker containers with their status.
+    ListContainers,
+    /// List allowed systemd units with their status.
+    ListUnits,
+    /// Restart a Docker container (requires allow_restart).
+    RestartContainer,
+    /// Restart a systemd unit (requires allow_restart).
+    RestartUnit,
+    /// Tail recent logs from a Docker container.
+    ContainerLogs,
+    /// Tail recent logs from a systemd unit's journal.
+    UnitLogs,
+}
+
+/// Arguments for the ops tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct OpsArgs {
+    /// The operation to perform.
+    pub action: OpsAction,
+    /// Target container or unit name. Required for restart and log actions.
+    pub target: Option<String>,
+    /// Number of log lines to tail (default 100, capped by config).
+    pub lines: Option<usize>,
+}
+
+/// Output from the ops tool.
+#[derive(Debug, Serialize)]
+pub struct OpsOutput {
+    /// Human-readable result of the operation.
+    pub result: String,
+}
+
+impl Tool for OpsTool {
+    const NAME: &'static str = "ops";
+
+    type Error = OpsError;
+    type Args = OpsArgs;
+    type Output = OpsOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/ops").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": [
+                            "list_containers",
+                            "list_units",
+                            "restart_container",
+                            "restart_unit",
+                            "container_logs",
+                            "unit_logs"
+                        ],
+                        "description": "The operation to perform"
+                    },
+                    "target": {
+                        "type": "string",
+                        "description": "Container or unit name. Required for restart and log actions."
+                    },
+                    "lines": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of log lines to tail (default 100)"
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let lines = args
+            .lines
+            .unwrap_or(100)
+            .min(self.config.log_tail_limit.max(1));
+
+        let result = match args.action {
+            OpsAction::ListContainers => {
+                if !self.config.docker_enabled {
+                    return Err(OpsError::Disabled("docker".into()));
+                }
+                let docker = self.docker().await?;
+                let containers = docker
+                    .list_containers(Some(ListContainersOptions::<String> {
+                        all: true,
+                        ..Default::default()
+                    }))
+                    .await
+                    .map_err(|error| OpsError::Docker(error.to_string()))?;
+
+                let mut rows = Vec::new();
+                for container in containers {
+                    let names: Vec<String> = container
+                        .names
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|n| n.trim_start_matches('/').to_string())
+                        .collect();
+                    if !names
+                        .iter()
+                        .any(|name| self.config.allowed_containers.contains(name))
+                    {
+                        continue;
+                    }
+                    rows.push(format!(
+                        "{}: {} ({})",
+                        names.join(", "),
+                        container.state.map(|s| s.to_string()).unwrap_or_default(),
+                        container.status.unwrap_or_default()
+                    ));
+                }
+                if rows.is_empty() {
+                    "No allowed containers found".to_string()
+                } else {
+                    rows.join("\n")
+                }
+            }
+            OpsAction::ListUnits => {
+                if !self.config.systemd_enabled {
+                    return Err(OpsError::Disabled("systemd".into()));
+                }
+                let mut rows = Vec::new();
+                for unit in &self.config.allowed_units {
+                    let status = self
+                        .run_systemctl(&["systemctl", "is-active", unit])
+                        .await
+                        .unwrap_or_else(|_| "inactive".into());
+                    rows.push(format!("{unit}: {}", status.trim()));
+                }
+                if rows.is_empty() {
+                    "No units on the allowlist".to_string()
+                } else {
+                    rows.join("\n")
+                }
+            }
+            OpsAction::RestartContainer => {
+                let target = require_target(&args.target)?;
+                self.check_container_allowed(target)?;
+                if !self.config.allow_restart {
+                    return Err(OpsError::RestartNotAllowed);
+                }
+                let docker = self.docker().await?;
+                docker
+                    .restart_container(target, None::<RestartContainerOptions>)
+                    .await
+                    .map_err(|error| OpsError::Docker(error.to_string()))?;
+                tracing::info!(container = %target, "ops tool restarted container");
+                format!("Container '{target}' restarted")
+            }
+            OpsAction::RestartUnit => {
+                let target = require_target(&args.target)?;
+                self.check_unit_allowed(target)?;
+                if !self.config.allow_restart {
+                    return Err(OpsError::RestartNotAllowed);
+                }
+                self.run_systemctl(&["systemctl", "restart", target])
+                    .await?;
+                tracing::info!(unit = %target, "ops tool restarted unit");
+                format!("Unit '{target}' restarted")
+            }
+            OpsAction::ContainerLogs => {
+                let target = require_target(&args.target)?;
+                self.check_container_allowed(target)?;
+                let docker = self.docker().await?;
+                let mut stream = docker.logs(
+                    target,
+                    Some(LogsOptions::<String> {
+                        stdout: true,
+                        stderr: true,
+                        tail: lines.to_string(),
+                        ..Default::default()
+                    }),
+                );
+                let mut collected = String::new();
+                while let Some(entry) = stream.next().await {
+                    match entry {
+                        Ok(log) => collected.push_str(&log.to_string()),
+                        Err(error) => return Err(OpsError::Docker(error.to_string())),
+                    }
+                }
+                if collected.is_empty() {
+                    format!("No logs for container '{target}'")
+                } else {
+                    collected
+                }
+            }
+            OpsAction::UnitLogs => {
+                let target = require_target(&args.target)?;
+                self.check_unit_allowed(target)?;
+                let lines_arg = lines.to_string();
+                let output = self
+                    .run_systemctl(&[
+                        "journalctl",
+                        "-u",
+                        target,
+                        "-n",
+                        &lines_arg,
+                        "--no-pager",
+                        "-o",
+                        "short-iso",
+                    ])
+                    .await?;
+                if output.trim().is_empty() {
+                    format!("No journal entries for unit '{target}'")
+                } else {
+                    output
+                }
+            }
+        };
+
+        Ok(OpsOutput {
+            result: truncate_output(&result, MAX_TOOL_OUTPUT_BYTES),
+        })
+    }
+}
+
+fn require_target(target: &Option<String>) -> Result<&str, OpsError> {
+    target
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| OpsError::NotAllowed("missing target".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_allowlists() -> OpsConfig {
+        OpsConfig {
+            enabled: true,
+            docker_enabled: true,
+            systemd_enabled: true,
+            allowed_containers: vec!["web".into()],
+            allowed_units: vec!["nginx.service".into()],
+            allow_restart: false,
+            log_tail_limit: 200,
+        }
+    }
+
+    #[test]
+    fn container_allowlist_enforced() {
+        let tool = OpsTool::new(config_with_allowlists());
+        assert!(tool.check_container_allowed("web").is_ok());
+        assert!(tool.check_container_allowed("db").is_err());
+    }
+
+    #[test]
+    fn unit_allowlist_accepts_bare_and_suffixed_names() {
+        let tool = OpsTool::new(config_with_allowlists());
+        assert!(tool.check_unit_allowed("nginx").is_ok());
+        assert!(tool.check_unit_allowed("nginx.service").is_ok());
+        assert!(tool.check_unit_allowed("sshd").is_err());
+    }
+
+    #[test]
+    fn unit_names_with_shell_metacharacters_rejected() {
+        let mut config = config_with_allowlists();
+        config.allowed_units.push("bad; rm -rf /".into());
+        let tool = OpsTool::new(config);
+        assert!(tool.check_unit_allowed("bad; rm -rf /").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Context pinning tool for channels.
+//!
+//! Lets the agent pin facts into its own system prompt so they survive
+//! history trimming and compaction, and unpin them when they stop being
+//! relevant. Operators manage the same list through the channels API.
+
+use crate::agent::channel::PinnedContextItem;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Maximum pinned item length. Pins are injected into every prompt, so they
+/// must stay short.
+const MAX_PIN_CONTENT_BYTES: usize = 2_000;
+
+/// Maximum number of pinned items per channel.
+const MAX_PINNED_ITEMS: usize = 20;
+
+/// Tool for pinning and unpinning context items on the current channel.
+#[derive(Debug, Clone)]
+pub struct PinContextTool {
+    pinned: Arc<RwLock<Vec<PinnedContextItem>>>,
+}
+
+impl PinContextTool {
+    pub fn new(pinned: Arc<RwLock<Vec<PinnedContextItem>>>) -> Self {
+        Self { pinned }
+    }
+}
+
+/// Error type for the pin context tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Pin context failed: {0}")]
+pub struct PinContextError(String);
+
+/// Action to perform on the pinned context list.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PinContextAction {
+    Pin,
+    Unpin,
+    List,
+}
+
+/// Arguments for the pin context tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PinContextArgs {
+    /// The action: "pin", "unpin", or "list".
+    pub action: PinContextAction,
+    /// The content to pin (required for "pin").
+    pub content: Option<String>,
+    /// The item ID to unpin (required for "unpin").
+    pub id: Option<String>,
+}
+
+/// Output from the pin context tool.
+#[derive(Debug, Serialize)]
+pub struct PinContextOutput {
+    pub success: bool,
+    pub message: String,
+    /// Current pinned items after the action.
+    pub pinned: Vec<PinnedContextItem>,
+}
+
+impl Tool for PinContextTool {
+    const NAME: &'static str = "pin_context";
+
+    type Error = PinContextError;
+    type Args = PinContextArgs;
+    type Output = PinContextOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/pin_context").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["pin", "unpin", "list"],
+                        "description": "Whether to pin new content, unpin an item by ID, or list current pins"
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "The fact to pin (for action=pin). Keep it short — pins are injected into every prompt."
+                    },
+                    "id": {
+                        "type": "string",
+                        "description": "The pinned item ID to remove (for action=unpin)"
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        match args.action {
+            PinContextAction::Pin => {
+                let content = args
+                    .content
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|content| !content.is_empty())
+                    .ok_or_else(|| PinContextError("content is required for pin".into()))?;
+                if content.len() > MAX_PIN_CONTENT_BYTES {
+                    return Err(PinContextError(format!(
+                        "content exceeds maximum length of {MAX_PIN_CONTENT_BYTES} bytes (got {})",
+                        content.len()
+                    )));
+                }
+
+                let mut pinned = self.pinned.write().await;
+                if pinned.len() >= MAX_PINNED_ITEMS {
+                    return Err(PinContextError(format!(
+                        "pin limit of {MAX_PINNED_ITEMS} items reached — unpin something first"
+                    )));
+                }
+                let item = PinnedContextItem::new(content, "agent");
+                let id = item.id.clone();
+                pinned.push(item);
+                Ok(PinContextOutput {
+                    success: true,
+                    message: format!("Pinned as {id}"),
+                    pinned: pinned.clone(),
+                })
+            }
+            PinContextAction::Unpin => {
+                let id = args
+                    .id
+                    .as_deref()
+                    .filter(|id| !id.is_empty())
+                    .ok_or_else(|| PinContextError("id is required for unpin".into()))?;
+
+                let mut pinned = self.pinned.write().await;
+                let before = pinned.len();
+                pinned.retain(|item| item.id != id);
+                if pinned.len() == before {
+                    return Err(PinContextError(format!("no pinned item with id {id}")));
+                }
+                Ok(PinContextOutput {
+                    success: true,
+                    message: format!("Unpinned {id}"),
+                    pinned: pinned.clone(),
+                })
+            }
+            PinContextAction::List => {
+                let pinned = self.pinned.read().await;
+                Ok(PinContextOutput {
+                    success: true,
+                    message: format!("{} item(s) pinned", pinned.len()),
+                    pinned: pinned.clone(),
+                })
+            }
+        }
+    }
+}
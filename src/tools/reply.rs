@@ -46,10 +46,13 @@ pub struct ReplyTool {
     channel_id: ChannelId,
     replied_flag: RepliedFlag,
     agent_display_name: String,
+    /// Append footnotes for sources recorded by retrieval tools.
+    cite_sources: bool,
 }
 
 impl ReplyTool {
     /// Create a new reply tool bound to a conversation's response channel.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         response_tx: mpsc::Sender<OutboundResponse>,
         conversation_id: impl Into<String>,
@@ -57,6 +60,7 @@ impl ReplyTool {
         channel_id: ChannelId,
         replied_flag: RepliedFlag,
         agent_display_name: impl Into<String>,
+        cite_sources: bool,
     ) -> Self {
         Self {
             response_tx,
@@ -65,6 +69,7 @@ impl ReplyTool {
             channel_id,
             replied_flag,
             agent_display_name: agent_display_name.into(),
+            cite_sources,
         }
     }
 }
@@ -354,7 +359,7 @@ impl Tool for ReplyTool {
         let source = self.conversation_id.split(':').next().unwrap_or("unknown");
 
         // Auto-convert @mentions to platform-specific syntax
-        let converted_content = convert_mentions(
+        let mut converted_content = convert_mentions(
             &args.content,
             &self.channel_id,
             &self.conversation_logger,
@@ -362,6 +367,11 @@ impl Tool for ReplyTool {
         )
         .await;
 
+        if self.cite_sources {
+            let sources = crate::citations::drain(&self.channel_id);
+            converted_content.push_str(&crate::citations::format_citations(&sources, source));
+        }
+
         if crate::tools::should_block_user_visible_text(&converted_content) {
             tracing::warn!(
                 conversation_id = %self.conversation_id,
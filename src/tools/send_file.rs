@@ -130,9 +130,12 @@ impl Tool for SendFileTool {
             )));
         }
 
-        let data = tokio::fs::read(&path).await.map_err(|error| {
-            SendFileError(format!("failed to read '{}': {error}", path.display()))
-        })?;
+        let data = tokio::fs::read(&path)
+            .await
+            .map(bytes::Bytes::from)
+            .map_err(|error| {
+                SendFileError(format!("failed to read '{}': {error}", path.display()))
+            })?;
 
         let filename = path
             .file_name()
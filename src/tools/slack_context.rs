@@ -0,0 +1,307 @@
+//! Look up Slack usergroup membership and channel rosters.
+
+use crate::config::{Config, RuntimeConfig};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const SLACK_API_BASE: &str = "https://slack.com/api";
+
+/// Rosters change slowly; cache lookups so repeated questions in one
+/// conversation don't hammer the Slack API.
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Tool for answering "who is in @oncall" and "who is in this channel".
+#[derive(Debug, Clone)]
+pub struct SlackContextTool {
+    runtime_config: Arc<RuntimeConfig>,
+    cache: Arc<Mutex<HashMap<String, (Instant, SlackContextOutput)>>>,
+}
+
+impl SlackContextTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self {
+            runtime_config,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// Error type for slack_context tool.
+#[derive(Debug, thiserror::Error)]
+#[error("slack_context failed: {0}")]
+pub struct SlackContextError(String);
+
+/// Arguments for slack_context.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SlackContextArgs {
+    /// What to look up: "usergroup" or "channel".
+    pub kind: String,
+    /// Usergroup handle (e.g. "oncall") or channel ID (e.g. "C0123456").
+    pub target: String,
+    /// Maximum members to resolve to display names (1..100). Defaults to 25.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A resolved member of a usergroup or channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlackMember {
+    pub id: String,
+    pub name: String,
+}
+
+/// Output for slack_context.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlackContextOutput {
+    pub kind: String,
+    pub target: String,
+    /// Total membership, which can exceed the resolved list.
+    pub member_count: usize,
+    pub members: Vec<SlackMember>,
+    /// True when served from the roster cache.
+    pub cached: bool,
+}
+
+impl Tool for SlackContextTool {
+    const NAME: &'static str = "slack_context";
+
+    type Error = SlackContextError;
+    type Args = SlackContextArgs;
+    type Output = SlackContextOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/slack_context").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "kind": {
+                        "type": "string",
+                        "enum": ["usergroup", "channel"],
+                        "description": "Whether target names a usergroup or a channel."
+                    },
+                    "target": {
+                        "type": "string",
+                        "description": "Usergroup handle (e.g. oncall) or channel ID (e.g. C0123456)."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum members to resolve to names (1-100, default 25)."
+                    }
+                },
+                "required": ["kind", "target"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let kind = args.kind.trim().to_lowercase();
+        if kind != "usergroup" && kind != "channel" {
+            return Err(SlackContextError(format!(
+                "unknown kind '{kind}'; expected usergroup or channel"
+            )));
+        }
+        let target = normalize_target(&args.target);
+        let limit = args.limit.unwrap_or(25).clamp(1, 100);
+
+        let cache_key = format!("{kind}:{target}:{limit}");
+        if let Some(hit) = {
+            let cache = self.cache.lock().expect("slack_context cache poisoned");
+            cache
+                .get(&cache_key)
+                .filter(|(at, _)| at.elapsed() < CACHE_TTL)
+                .map(|(_, output)| output.clone())
+        } {
+            let mut output = hit;
+            output.cached = true;
+            return Ok(output);
+        }
+
+        let bot_token = load_slack_token(&self.runtime_config.instance_dir)?;
+        let client = SlackWebClient::new(bot_token);
+
+        let member_ids = match kind.as_str() {
+            "usergroup" => client.usergroup_members(&target).await?,
+            _ => client.channel_members(&target).await?,
+        };
+
+        let mut members = Vec::new();
+        for id in member_ids.iter().take(limit) {
+            let name = client.display_name(id).await.unwrap_or_else(|_| id.clone());
+            members.push(SlackMember {
+                id: id.clone(),
+                name,
+            });
+        }
+
+        let output = SlackContextOutput {
+            kind,
+            target,
+            member_count: member_ids.len(),
+            members,
+            cached: false,
+        };
+        self.cache
+            .lock()
+            .expect("slack_context cache poisoned")
+            .insert(cache_key, (Instant::now(), output.clone()));
+        Ok(output)
+    }
+}
+
+/// Minimal Slack Web API client for roster lookups.
+struct SlackWebClient {
+    token: String,
+    client: reqwest::Client,
+}
+
+impl SlackWebClient {
+    fn new(token: String) -> Self {
+        Self {
+            token,
+            client: crate::http::client(),
+        }
+    }
+
+    async fn get(
+        &self,
+        method: &str,
+        params: &[(&str, &str)],
+    ) -> Result<serde_json::Value, SlackContextError> {
+        let response = self
+            .client
+            .get(format!("{SLACK_API_BASE}/{method}"))
+            .bearer_auth(&self.token)
+            .query(params)
+            .send()
+            .await
+            .map_err(|error| SlackContextError(format!("Slack API unreachable: {error}")))?;
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|error| SlackContextError(format!("bad Slack API response: {error}")))?;
+        if !body["ok"].as_bool().unwrap_or(false) {
+            let error = body["error"].as_str().unwrap_or("unknown error");
+            return Err(SlackContextError(format!("Slack {method} failed: {error}")));
+        }
+        Ok(body)
+    }
+
+    /// Member IDs of a usergroup, resolved from its handle.
+    async fn usergroup_members(&self, handle: &str) -> Result<Vec<String>, SlackContextError> {
+        let groups = self.get("usergroups.list", &[]).await?;
+        let group_id = groups["usergroups"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|group| {
+                group["handle"]
+                    .as_str()
+                    .is_some_and(|h| h.eq_ignore_ascii_case(handle))
+            })
+            .and_then(|group| group["id"].as_str())
+            .ok_or_else(|| SlackContextError(format!("no usergroup with handle '{handle}'")))?
+            .to_string();
+
+        let users = self
+            .get("usergroups.users.list", &[("usergroup", group_id.as_str())])
+            .await?;
+        Ok(users["users"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| id.as_str().map(str::to_string))
+            .collect())
+    }
+
+    /// Member IDs of a channel.
+    async fn channel_members(&self, channel: &str) -> Result<Vec<String>, SlackContextError> {
+        let mut members = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let response = self
+                .get(
+                    "conversations.members",
+                    &[("channel", channel), ("limit", "200"), ("cursor", &cursor)],
+                )
+                .await?;
+            members.extend(
+                response["members"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| id.as_str().map(str::to_string)),
+            );
+            match response["response_metadata"]["next_cursor"].as_str() {
+                Some(next) if !next.is_empty() => cursor = next.to_string(),
+                _ => break,
+            }
+        }
+        Ok(members)
+    }
+
+    /// A user's display name, falling back to their real name.
+    async fn display_name(&self, user_id: &str) -> Result<String, SlackContextError> {
+        let response = self.get("users.info", &[("user", user_id)]).await?;
+        let profile = &response["user"]["profile"];
+        let name = profile["display_name"]
+            .as_str()
+            .filter(|name| !name.is_empty())
+            .or_else(|| profile["real_name"].as_str())
+            .unwrap_or(user_id);
+        Ok(name.to_string())
+    }
+}
+
+/// Strip the decorations people paste in: `@oncall`, `#C0123456`, `<#C01|general>`.
+fn normalize_target(target: &str) -> String {
+    let target = target.trim().trim_start_matches(['@', '#']);
+    if let Some(inner) = target
+        .strip_prefix("<")
+        .and_then(|t| t.strip_suffix(">"))
+    {
+        let inner = inner.trim_start_matches(['@', '#']);
+        return inner.split('|').next().unwrap_or(inner).to_string();
+    }
+    target.to_string()
+}
+
+fn load_slack_token(instance_dir: &Path) -> Result<String, SlackContextError> {
+    let config = Config::load_for_instance(instance_dir).map_err(|error| {
+        SlackContextError(format!(
+            "failed to resolve config for {}: {error}",
+            instance_dir.display()
+        ))
+    })?;
+
+    let slack = config
+        .messaging
+        .slack
+        .ok_or_else(|| SlackContextError("slack adapter is not configured".to_string()))?;
+    if slack.bot_token.is_empty() {
+        return Err(SlackContextError(
+            "slack adapter has no bot token".to_string(),
+        ));
+    }
+    Ok(slack.bot_token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_are_normalized() {
+        assert_eq!(normalize_target("@oncall"), "oncall");
+        assert_eq!(normalize_target("#C0123456"), "C0123456");
+        assert_eq!(normalize_target("<#C0123456|general>"), "C0123456");
+        assert_eq!(normalize_target("  oncall "), "oncall");
+    }
+}
@@ -0,0 +1,332 @@
+//! Read-only SQL query tool (task workers only).
+//!
+//! Runs queries against named databases configured per agent (Postgres,
+//! MySQL, or SQLite DSNs via sqlx's Any driver). Only single read
+//! statements are accepted — SELECT / WITH / SHOW / EXPLAIN / DESCRIBE /
+//! PRAGMA reads — and results
+//! are capped by a configurable row limit on top of the usual output
+//! truncation. A schema action introspects tables and columns so agents can
+//! orient themselves before querying.
+
+use crate::config::SqlConfig;
+use crate::tools::{MAX_TOOL_OUTPUT_BYTES, truncate_output};
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::{Column as _, Row as _};
+
+/// Tool for read-only SQL queries against configured databases.
+#[derive(Debug, Clone)]
+pub struct SqlTool {
+    config: SqlConfig,
+}
+
+impl SqlTool {
+    pub fn new(config: SqlConfig) -> Self {
+        sqlx::any::install_default_drivers();
+        Self { config }
+    }
+
+    fn dsn_for(&self, database: Option<&str>) -> Result<&str, SqlError> {
+        let database = match database {
+            Some(database) => self
+                .config
+                .databases
+                .iter()
+                .find(|db| db.name == database)
+                .ok_or_else(|| SqlError::UnknownDatabase(database.to_string()))?,
+            None => self
+                .config
+                .databases
+                .first()
+                .ok_or(SqlError::NoDatabases)?,
+        };
+        Ok(&database.dsn)
+    }
+
+    async fn connect(&self, dsn: &str) -> Result<sqlx::AnyPool, SqlError> {
+        sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect(dsn)
+            .await
+            .map_err(|error| SqlError::Connection(error.to_string()))
+    }
+
+    async fn run_query(&self, dsn: &str, query: &str) -> Result<String, SqlError> {
+        check_read_only(query)?;
+        let pool = self.connect(dsn).await?;
+
+        let rows = sqlx::query(query)
+            .fetch_all(&pool)
+            .await
+            .map_err(|error| SqlError::Query(error.to_string()))?;
+        pool.close().await;
+
+        let max_rows = self.config.max_rows.max(1);
+        let truncated = rows.len() > max_rows;
+
+        let mut output = String::new();
+        if let Some(first) = rows.first() {
+            let header: Vec<&str> = first.columns().iter().map(|c| c.name()).collect();
+            output.push_str(&header.join(" | "));
+            output.push('\n');
+        }
+        for row in rows.iter().take(max_rows) {
+            let values: Vec<String> = (0..row.columns().len())
+                .map(|index| value_to_string(row, index))
+                .collect();
+            output.push_str(&values.join(" | "));
+            output.push('\n');
+        }
+
+        if rows.is_empty() {
+            output.push_str("(0 rows)");
+        } else if truncated {
+            output.push_str(&format!(
+                "({} rows, showing first {max_rows})",
+                rows.len()
+            ));
+        } else {
+            output.push_str(&format!("({} rows)", rows.len()));
+        }
+        Ok(output)
+    }
+
+    async fn introspect_schema(&self, dsn: &str) -> Result<String, SqlError> {
+        let query = if dsn.starts_with("sqlite:") {
+            "SELECT name AS table_name, sql AS definition FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+        } else {
+            "SELECT table_name, column_name || ' ' || data_type AS definition \
+             FROM information_schema.columns \
+             WHERE table_schema NOT IN ('pg_catalog', 'information_schema', 'mysql', \
+             'performance_schema', 'sys') \
+             ORDER BY table_name, ordinal_position"
+        };
+        self.run_query(dsn, query).await
+    }
+}
+
+/// Error type for the SQL tool.
+#[derive(Debug, thiserror::Error)]
+pub enum SqlError {
+    #[error("No databases are configured for this agent")]
+    NoDatabases,
+
+    #[error("Unknown database '{0}'")]
+    UnknownDatabase(String),
+
+    #[error("Only single read-only statements are allowed: {0}")]
+    NotReadOnly(String),
+
+    #[error("Connection failed: {0}")]
+    Connection(String),
+
+    #[error("Query failed: {0}")]
+    Query(String),
+}
+
+/// The operation to perform.
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlAction {
+    /// Run a read-only query and return rows.
+    Query,
+    /// List configured database names.
+    ListDatabases,
+    /// Introspect tables and columns in a database.
+    Schema,
+}
+
+/// Arguments for the SQL tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SqlArgs {
+    /// The operation to perform.
+    pub action: SqlAction,
+    /// Configured database name. Defaults to the first configured database.
+    pub database: Option<String>,
+    /// SQL statement for the query action.
+    pub query: Option<String>,
+}
+
+/// Output from the SQL tool.
+#[derive(Debug, Serialize)]
+pub struct SqlOutput {
+    /// Pipe-delimited rows, or schema/database listing.
+    pub result: String,
+}
+
+impl Tool for SqlTool {
+    const NAME: &'static str = "sql";
+
+    type Error = SqlError;
+    type Args = SqlArgs;
+    type Output = SqlOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/sql").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["query", "list_databases", "schema"],
+                        "description": "The operation to perform"
+                    },
+                    "database": {
+                        "type": "string",
+                        "description": "Configured database name (defaults to the first one)"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "SQL statement, required for the query action. Read-only."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let result = match args.action {
+            SqlAction::ListDatabases => {
+                if self.config.databases.is_empty() {
+                    return Err(SqlError::NoDatabases);
+                }
+                self.config
+                    .databases
+                    .iter()
+                    .map(|db| db.name.clone())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            SqlAction::Schema => {
+                let dsn = self.dsn_for(args.database.as_deref())?.to_string();
+                self.introspect_schema(&dsn).await?
+            }
+            SqlAction::Query => {
+                let query = args
+                    .query
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|q| !q.is_empty())
+                    .ok_or_else(|| SqlError::NotReadOnly("empty query".into()))?;
+                let dsn = self.dsn_for(args.database.as_deref())?.to_string();
+                self.run_query(&dsn, query).await?
+            }
+        };
+
+        Ok(SqlOutput {
+            result: truncate_output(&result, MAX_TOOL_OUTPUT_BYTES),
+        })
+    }
+}
+
+/// Reject anything that isn't a single read statement. This is a guard
+/// against accidental writes, not a sandbox — point the DSN at a read-only
+/// role for real isolation.
+fn check_read_only(query: &str) -> Result<(), SqlError> {
+    let trimmed = query.trim().trim_end_matches(';');
+    if trimmed.contains(';') {
+        return Err(SqlError::NotReadOnly(
+            "multiple statements are not allowed".into(),
+        ));
+    }
+    let first_word = trimmed
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    match first_word.as_str() {
+        "SELECT" | "SHOW" | "EXPLAIN" | "DESCRIBE" => Ok(()),
+        // `PRAGMA name` reads a setting; `PRAGMA name = value` mutates
+        // SQLite state (journal_mode, user_version, ...).
+        "PRAGMA" if trimmed.contains('=') => Err(SqlError::NotReadOnly(
+            "PRAGMA assignments mutate database state".into(),
+        )),
+        "PRAGMA" => Ok(()),
+        // Postgres allows writable CTEs (`WITH d AS (DELETE ... RETURNING *)
+        // SELECT ...`), so the body is scanned for write keywords too.
+        "WITH" => {
+            const WRITE_KEYWORDS: &[&str] =
+                &["INSERT", "UPDATE", "DELETE", "MERGE", "REPLACE", "TRUNCATE"];
+            let writes = trimmed
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| WRITE_KEYWORDS.iter().any(|kw| word.eq_ignore_ascii_case(kw)));
+            if writes {
+                Err(SqlError::NotReadOnly(
+                    "CTE bodies may not contain write statements".into(),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        other => Err(SqlError::NotReadOnly(format!(
+            "statement must start with SELECT/WITH/SHOW/EXPLAIN/DESCRIBE/PRAGMA, got '{other}'"
+        ))),
+    }
+}
+
+/// Render a column value as text, trying the types the Any driver supports.
+fn value_to_string(row: &sqlx::any::AnyRow, index: usize) -> String {
+    if let Ok(value) = row.try_get::<Option<String>, _>(index) {
+        return value.unwrap_or_else(|| "NULL".into());
+    }
+    if let Ok(value) = row.try_get::<Option<i64>, _>(index) {
+        return value.map_or_else(|| "NULL".into(), |v| v.to_string());
+    }
+    if let Ok(value) = row.try_get::<Option<f64>, _>(index) {
+        return value.map_or_else(|| "NULL".into(), |v| v.to_string());
+    }
+    if let Ok(value) = row.try_get::<Option<bool>, _>(index) {
+        return value.map_or_else(|| "NULL".into(), |v| v.to_string());
+    }
+    if let Ok(value) = row.try_get::<Option<Vec<u8>>, _>(index) {
+        return value.map_or_else(|| "NULL".into(), |v| format!("<{} bytes>", v.len()));
+    }
+    "?".into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_statements_allowed() {
+        assert!(check_read_only("SELECT 1").is_ok());
+        assert!(check_read_only("  with t as (select 1) select * from t;").is_ok());
+        assert!(check_read_only("EXPLAIN SELECT * FROM users").is_ok());
+    }
+
+    #[test]
+    fn write_statements_rejected() {
+        assert!(check_read_only("DELETE FROM users").is_err());
+        assert!(check_read_only("INSERT INTO t VALUES (1)").is_err());
+        assert!(check_read_only("UPDATE t SET a = 1").is_err());
+        assert!(check_read_only("DROP TABLE t").is_err());
+    }
+
+    #[test]
+    fn multiple_statements_rejected() {
+        assert!(check_read_only("SELECT 1; DELETE FROM users").is_err());
+    }
+
+    #[test]
+    fn pragma_reads_allowed_but_assignments_rejected() {
+        assert!(check_read_only("PRAGMA table_info(users)").is_ok());
+        assert!(check_read_only("PRAGMA journal_mode = DELETE").is_err());
+    }
+
+    #[test]
+    fn writable_ctes_rejected() {
+        assert!(
+            check_read_only("WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d").is_err()
+        );
+        assert!(check_read_only("WITH u AS (UPDATE t SET a = 1 RETURNING *) SELECT 1").is_err());
+        assert!(check_read_only("WITH t AS (SELECT 1 AS n) SELECT n FROM t").is_ok());
+    }
+}
@@ -0,0 +1,305 @@
+//! SSH remote-command tool with host and command allowlists (task workers only).
+//!
+//! Runs diagnostics on remote hosts through the system `ssh` client in batch
+//! mode (key-based auth only, no password prompts). Hosts must be configured
+//! by name, every command must match one of the host's allowed prefixes, and
+//! prefixes flagged destructive are additionally gated by `allow_destructive`.
+//! Every execution is audit-logged with host, command, and exit status.
+
+use crate::config::{SshConfig, SshHostConfig};
+use crate::tools::{MAX_TOOL_OUTPUT_BYTES, truncate_output};
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Hard ceiling on remote command runtime.
+const COMMAND_TIMEOUT_SECS: u64 = 60;
+
+/// Characters the remote login shell would interpret as chaining,
+/// redirection, or substitution. The command string is re-parsed by a shell
+/// on the far side, so a matched prefix followed by any of these could
+/// smuggle arbitrary commands past the allowlist.
+const SHELL_METACHARACTERS: &[char] = &[';', '|', '&', '$', '`', '<', '>', '(', ')', '\n', '\r'];
+
+/// Tool for running allowlisted commands on configured SSH hosts.
+#[derive(Debug, Clone)]
+pub struct SshTool {
+    config: SshConfig,
+}
+
+impl SshTool {
+    pub fn new(config: SshConfig) -> Self {
+        Self { config }
+    }
+
+    fn host_for(&self, name: &str) -> Result<&SshHostConfig, SshError> {
+        self.config
+            .hosts
+            .iter()
+            .find(|host| host.name == name)
+            .ok_or_else(|| SshError::UnknownHost(name.to_string()))
+    }
+
+    /// A command is allowed if it contains no shell metacharacters and
+    /// starts with one of the host's allowed prefixes at a word boundary.
+    fn check_command_allowed(&self, host: &SshHostConfig, command: &str) -> Result<(), SshError> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(SshError::NotAllowed("empty command".into()));
+        }
+        if let Some(ch) = command.chars().find(|ch| SHELL_METACHARACTERS.contains(ch)) {
+            return Err(SshError::Metacharacter(ch.escape_default().to_string()));
+        }
+
+        let matches_prefix = |prefix: &str| {
+            command == prefix
+                || command
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with(' '))
+        };
+
+        if host
+            .destructive_commands
+            .iter()
+            .any(|prefix| matches_prefix(prefix))
+        {
+            if !self.config.allow_destructive {
+                return Err(SshError::DestructiveNotAllowed(command.to_string()));
+            }
+            return Ok(());
+        }
+
+        if host
+            .allowed_commands
+            .iter()
+            .any(|prefix| matches_prefix(prefix))
+        {
+            return Ok(());
+        }
+
+        Err(SshError::NotAllowed(format!("command '{command}'")))
+    }
+}
+
+/// Error type for the SSH tool.
+#[derive(Debug, thiserror::Error)]
+pub enum SshError {
+    #[error("Unknown SSH host '{0}'")]
+    UnknownHost(String),
+
+    #[error("{0} is not on the SSH command allowlist for this host")]
+    NotAllowed(String),
+
+    #[error(
+        "Command contains shell metacharacter '{0}': chaining, redirection, and substitution are not allowed"
+    )]
+    Metacharacter(String),
+
+    #[error(
+        "Command '{0}' is flagged destructive: set defaults.ssh.allow_destructive = true to enable"
+    )]
+    DestructiveNotAllowed(String),
+
+    #[error("SSH error: {0}")]
+    Ssh(String),
+}
+
+/// Arguments for the SSH tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SshArgs {
+    /// Configured host name to run on.
+    pub host: String,
+    /// Command line to execute. Must match one of the host's allowed prefixes.
+    pub command: String,
+}
+
+/// Output from the SSH tool.
+#[derive(Debug, Serialize)]
+pub struct SshOutput {
+    /// Combined stdout/stderr from the remote command.
+    pub result: String,
+    /// Remote exit code, if the command ran.
+    pub exit_code: Option<i32>,
+}
+
+impl Tool for SshTool {
+    const NAME: &'static str = "ssh";
+
+    type Error = SshError;
+    type Args = SshArgs;
+    type Output = SshOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let hosts: Vec<&str> = self
+            .config
+            .hosts
+            .iter()
+            .map(|host| host.name.as_str())
+            .collect();
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/ssh").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "host": {
+                        "type": "string",
+                        "enum": hosts,
+                        "description": "Configured host name to run on"
+                    },
+                    "command": {
+                        "type": "string",
+                        "description": "Command line to execute (must match an allowed prefix)"
+                    }
+                },
+                "required": ["host", "command"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let host = self.host_for(&args.host)?;
+        self.check_command_allowed(host, &args.command)?;
+
+        let mut command = tokio::process::Command::new("ssh");
+        command
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg("-o")
+            .arg("StrictHostKeyChecking=accept-new")
+            .arg("-o")
+            .arg(format!("ConnectTimeout={}", self.config.connect_timeout_secs));
+        if let Some(identity_file) = &host.identity_file {
+            command.arg("-i").arg(identity_file);
+        }
+        if host.port != 22 {
+            command.arg("-p").arg(host.port.to_string());
+        }
+        command.arg(&host.address).arg("--").arg(&args.command);
+
+        let started = std::time::Instant::now();
+        let output = tokio::time::timeout(
+            std::time::Duration::from_secs(COMMAND_TIMEOUT_SECS),
+            command.output(),
+        )
+        .await
+        .map_err(|_| SshError::Ssh(format!("command timed out after {COMMAND_TIMEOUT_SECS}s")))?
+        .map_err(|error| SshError::Ssh(format!("failed to run ssh: {error}")))?;
+
+        let exit_code = output.status.code();
+        tracing::info!(
+            host = %host.name,
+            address = %host.address,
+            command = %args.command,
+            exit_code = ?exit_code,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "ssh tool executed remote command"
+        );
+
+        let mut result = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.trim().is_empty() {
+            if !result.is_empty() {
+                result.push('\n');
+            }
+            result.push_str("stderr:\n");
+            result.push_str(stderr.trim_end());
+        }
+        if result.trim().is_empty() {
+            result = "(no output)".to_string();
+        }
+
+        Ok(SshOutput {
+            result: truncate_output(&result, MAX_TOOL_OUTPUT_BYTES),
+            exit_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_host() -> SshConfig {
+        SshConfig {
+            enabled: true,
+            allow_destructive: false,
+            connect_timeout_secs: 10,
+            hosts: vec![SshHostConfig {
+                name: "web1".into(),
+                address: "ops@web1.example.com".into(),
+                port: 22,
+                identity_file: None,
+                allowed_commands: vec!["uptime".into(), "df".into(), "journalctl".into()],
+                destructive_commands: vec!["systemctl restart".into()],
+            }],
+        }
+    }
+
+    #[test]
+    fn allowed_prefixes_match_at_word_boundaries() {
+        let tool = SshTool::new(config_with_host());
+        let host = tool.host_for("web1").unwrap();
+        assert!(tool.check_command_allowed(host, "uptime").is_ok());
+        assert!(tool.check_command_allowed(host, "df -h /var").is_ok());
+        // "dfx" must not match the "df" prefix
+        assert!(tool.check_command_allowed(host, "dfx run").is_err());
+        assert!(tool.check_command_allowed(host, "rm -rf /").is_err());
+    }
+
+    #[test]
+    fn shell_metacharacters_rejected() {
+        let tool = SshTool::new(config_with_host());
+        let host = tool.host_for("web1").unwrap();
+        // The remote shell re-parses the command, so an allowed prefix must
+        // not be able to smuggle chained or substituted commands through.
+        assert!(tool.check_command_allowed(host, "uptime && rm -rf /").is_err());
+        assert!(tool.check_command_allowed(host, "df -h; rm -rf /").is_err());
+        assert!(tool.check_command_allowed(host, "df -h | sh").is_err());
+        assert!(
+            tool.check_command_allowed(host, "journalctl $(cat /etc/shadow)")
+                .is_err()
+        );
+        assert!(tool.check_command_allowed(host, "uptime `id`").is_err());
+        assert!(
+            tool.check_command_allowed(host, "df -h > /etc/passwd")
+                .is_err()
+        );
+        assert!(
+            tool.check_command_allowed(host, "journalctl -u nginx -n 50")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn destructive_commands_gated() {
+        let mut config = config_with_host();
+        let tool = SshTool::new(config.clone());
+        let host = tool.host_for("web1").unwrap();
+        assert!(
+            tool.check_command_allowed(host, "systemctl restart nginx")
+                .is_err()
+        );
+
+        config.allow_destructive = true;
+        let tool = SshTool::new(config);
+        let host = tool.host_for("web1").unwrap();
+        assert!(
+            tool.check_command_allowed(host, "systemctl restart nginx")
+                .is_ok()
+        );
+        // The metacharacter check applies to destructive prefixes too.
+        assert!(
+            tool.check_command_allowed(host, "systemctl restart nginx; rm -rf /")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_host_rejected() {
+        let tool = SshTool::new(config_with_host());
+        assert!(tool.host_for("db9").is_err());
+    }
+}
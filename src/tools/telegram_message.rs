@@ -0,0 +1,196 @@
+//! Edit or delete Telegram messages the bot sent earlier.
+
+use crate::config::{Config, RuntimeConfig, TelegramConfig};
+use crate::messaging::telegram::{
+    SentTelegramMessage, load_sent_messages, markdown_to_telegram_html, remove_sent_message,
+    sent_messages_path, update_sent_message_preview,
+};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::payloads::setters::*;
+use teloxide::requests::{Request, Requester};
+use teloxide::types::{ChatId, MessageId, ParseMode};
+
+/// Tool for correcting the bot's own Telegram messages in place.
+#[derive(Debug, Clone)]
+pub struct TelegramMessageTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl TelegramMessageTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for telegram_message tool.
+#[derive(Debug, thiserror::Error)]
+#[error("telegram_message failed: {0}")]
+pub struct TelegramMessageError(String);
+
+/// Arguments for telegram_message.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TelegramMessageArgs {
+    /// One of: list, edit, delete.
+    pub action: String,
+    /// Chat containing the message (from "list" results).
+    #[serde(default)]
+    pub chat_id: Option<i64>,
+    /// Message to act on (from "list" results).
+    #[serde(default)]
+    pub message_id: Option<i32>,
+    /// Replacement text for the "edit" action.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Output for telegram_message.
+#[derive(Debug, Serialize)]
+pub struct TelegramMessageOutput {
+    pub action: String,
+    pub summary: String,
+    /// Tracked bot messages, returned by the "list" action.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<SentTelegramMessage>,
+}
+
+impl Tool for TelegramMessageTool {
+    const NAME: &'static str = "telegram_message";
+
+    type Error = TelegramMessageError;
+    type Args = TelegramMessageArgs;
+    type Output = TelegramMessageOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/telegram_message").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list", "edit", "delete"],
+                        "description": "List tracked bot messages, edit one in place, or delete one."
+                    },
+                    "chat_id": {
+                        "type": "integer",
+                        "description": "Chat containing the message, from list results. Required for edit and delete."
+                    },
+                    "message_id": {
+                        "type": "integer",
+                        "description": "Message to act on, from list results. Required for edit and delete."
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "Replacement text (markdown), required for the edit action."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let action = args.action.trim().to_lowercase();
+
+        if action == "list" {
+            let messages = load_sent_messages(&sent_messages_path());
+            let summary = format!("{} tracked bot messages", messages.len());
+            return Ok(TelegramMessageOutput {
+                action,
+                summary,
+                messages,
+            });
+        }
+
+        let chat_id = args
+            .chat_id
+            .ok_or_else(|| TelegramMessageError("chat_id is required".to_string()))?;
+        let message_id = args
+            .message_id
+            .ok_or_else(|| TelegramMessageError("message_id is required".to_string()))?;
+
+        let telegram = load_telegram_config(&self.runtime_config.instance_dir)?;
+        let bot = Bot::new(telegram.token);
+
+        let summary = match action.as_str() {
+            "edit" => {
+                let text = args
+                    .text
+                    .filter(|text| !text.trim().is_empty())
+                    .ok_or_else(|| {
+                        TelegramMessageError("text is required for the edit action".to_string())
+                    })?;
+
+                let html = markdown_to_telegram_html(&text);
+                let html_edit = bot
+                    .edit_message_text(ChatId(chat_id), MessageId(message_id), &html)
+                    .parse_mode(ParseMode::Html)
+                    .send()
+                    .await;
+                if let Err(html_error) = html_edit {
+                    tracing::debug!(%html_error, "HTML edit failed, retrying as plain text");
+                    bot.edit_message_text(ChatId(chat_id), MessageId(message_id), &text)
+                        .send()
+                        .await
+                        .map_err(|error| {
+                            TelegramMessageError(format!("failed to edit message: {error}"))
+                        })?;
+                }
+
+                update_sent_message_preview(&sent_messages_path(), chat_id, message_id, &text);
+                format!("edited message {message_id} in chat {chat_id}")
+            }
+            "delete" => {
+                bot.delete_message(ChatId(chat_id), MessageId(message_id))
+                    .send()
+                    .await
+                    .map_err(|error| {
+                        TelegramMessageError(format!("failed to delete message: {error}"))
+                    })?;
+
+                remove_sent_message(&sent_messages_path(), chat_id, message_id);
+                format!("deleted message {message_id} in chat {chat_id}")
+            }
+            other => {
+                return Err(TelegramMessageError(format!(
+                    "unknown action '{other}'; expected list, edit, or delete"
+                )));
+            }
+        };
+
+        Ok(TelegramMessageOutput {
+            action,
+            summary,
+            messages: Vec::new(),
+        })
+    }
+}
+
+fn load_telegram_config(instance_dir: &Path) -> Result<TelegramConfig, TelegramMessageError> {
+    let config = Config::load_for_instance(instance_dir).map_err(|error| {
+        TelegramMessageError(format!(
+            "failed to resolve config for {}: {error}",
+            instance_dir.display()
+        ))
+    })?;
+
+    let telegram = config
+        .messaging
+        .telegram
+        .ok_or_else(|| TelegramMessageError("telegram adapter is not configured".to_string()))?;
+
+    if telegram.token.trim().is_empty() {
+        return Err(TelegramMessageError(
+            "telegram adapter has no bot token configured".to_string(),
+        ));
+    }
+
+    Ok(telegram)
+}
@@ -0,0 +1,225 @@
+//! Group moderation actions for chats where the bot is an admin.
+
+use crate::config::{Config, RuntimeConfig, TelegramConfig, TelegramPermissions};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::payloads::setters::*;
+use teloxide::requests::{Request, Requester};
+use teloxide::types::{ChatId, ChatPermissions, MessageId, UserId};
+
+/// Tool for moderating Telegram groups: delete, mute, ban, pin.
+#[derive(Debug, Clone)]
+pub struct TelegramModerationTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl TelegramModerationTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Error type for telegram_moderation tool.
+#[derive(Debug, thiserror::Error)]
+#[error("telegram_moderation failed: {0}")]
+pub struct TelegramModerationError(String);
+
+/// Arguments for telegram_moderation.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TelegramModerationArgs {
+    /// One of: delete, mute, ban, pin.
+    pub action: String,
+    /// Group chat to moderate.
+    pub chat_id: i64,
+    /// Message to act on, required for "delete" and "pin".
+    #[serde(default)]
+    pub message_id: Option<i32>,
+    /// User to act on, required for "mute" and "ban".
+    #[serde(default)]
+    pub user_id: Option<u64>,
+    /// How long a mute or ban lasts, in seconds. Omitted means indefinite.
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+/// Output for telegram_moderation.
+#[derive(Debug, Serialize)]
+pub struct TelegramModerationOutput {
+    pub action: String,
+    pub chat_id: i64,
+    pub summary: String,
+}
+
+impl Tool for TelegramModerationTool {
+    const NAME: &'static str = "telegram_moderation";
+
+    type Error = TelegramModerationError;
+    type Args = TelegramModerationArgs;
+    type Output = TelegramModerationOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/telegram_moderation").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["delete", "mute", "ban", "pin"],
+                        "description": "Delete a message, mute a user, ban a user, or pin a message."
+                    },
+                    "chat_id": {
+                        "type": "integer",
+                        "description": "Group chat to moderate (from message metadata)."
+                    },
+                    "message_id": {
+                        "type": "integer",
+                        "description": "Message to act on, required for delete and pin."
+                    },
+                    "user_id": {
+                        "type": "integer",
+                        "description": "User to act on, required for mute and ban."
+                    },
+                    "duration_secs": {
+                        "type": "integer",
+                        "description": "Mute/ban duration in seconds. Omit for indefinite."
+                    }
+                },
+                "required": ["action", "chat_id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let action = args.action.trim().to_lowercase();
+        let chat_id = args.chat_id;
+
+        let (telegram, permissions) = load_permissions(&self.runtime_config.instance_dir)?;
+
+        // Moderation stays inside the chats the permission filters allow.
+        if let Some(chat_filter) = &permissions.chat_filter
+            && !chat_filter.contains(&chat_id)
+        {
+            return Err(TelegramModerationError(format!(
+                "chat {chat_id} is outside the configured chat filter"
+            )));
+        }
+
+        let allowed = match action.as_str() {
+            "delete" => permissions.moderation.allow_delete,
+            "mute" => permissions.moderation.allow_mute,
+            "ban" => permissions.moderation.allow_ban,
+            "pin" => permissions.moderation.allow_pin,
+            other => {
+                return Err(TelegramModerationError(format!(
+                    "unknown action '{other}'; expected delete, mute, ban, or pin"
+                )));
+            }
+        };
+        if !allowed {
+            return Err(TelegramModerationError(format!(
+                "the '{action}' action is disabled in [messaging.telegram.moderation]"
+            )));
+        }
+
+        let bot = Bot::new(telegram.token);
+        let until_date = args
+            .duration_secs
+            .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64));
+
+        let summary = match action.as_str() {
+            "delete" => {
+                let message_id = require_message_id(&args)?;
+                bot.delete_message(ChatId(chat_id), MessageId(message_id))
+                    .send()
+                    .await
+                    .map_err(|error| {
+                        TelegramModerationError(format!("failed to delete message: {error}"))
+                    })?;
+                format!("deleted message {message_id}")
+            }
+            "mute" => {
+                let user_id = require_user_id(&args)?;
+                let mut request =
+                    bot.restrict_chat_member(ChatId(chat_id), UserId(user_id), ChatPermissions::empty());
+                if let Some(until) = until_date {
+                    request = request.until_date(until);
+                }
+                request.send().await.map_err(|error| {
+                    TelegramModerationError(format!("failed to mute user: {error}"))
+                })?;
+                format!("muted user {user_id}")
+            }
+            "ban" => {
+                let user_id = require_user_id(&args)?;
+                let mut request = bot.ban_chat_member(ChatId(chat_id), UserId(user_id));
+                if let Some(until) = until_date {
+                    request = request.until_date(until);
+                }
+                request.send().await.map_err(|error| {
+                    TelegramModerationError(format!("failed to ban user: {error}"))
+                })?;
+                format!("banned user {user_id}")
+            }
+            "pin" => {
+                let message_id = require_message_id(&args)?;
+                bot.pin_chat_message(ChatId(chat_id), MessageId(message_id))
+                    .send()
+                    .await
+                    .map_err(|error| {
+                        TelegramModerationError(format!("failed to pin message: {error}"))
+                    })?;
+                format!("pinned message {message_id}")
+            }
+            _ => unreachable!("action validated above"),
+        };
+
+        Ok(TelegramModerationOutput {
+            action,
+            chat_id,
+            summary,
+        })
+    }
+}
+
+fn require_message_id(args: &TelegramModerationArgs) -> Result<i32, TelegramModerationError> {
+    args.message_id
+        .ok_or_else(|| TelegramModerationError("message_id is required".to_string()))
+}
+
+fn require_user_id(args: &TelegramModerationArgs) -> Result<u64, TelegramModerationError> {
+    args.user_id
+        .ok_or_else(|| TelegramModerationError("user_id is required".to_string()))
+}
+
+fn load_permissions(
+    instance_dir: &Path,
+) -> Result<(TelegramConfig, TelegramPermissions), TelegramModerationError> {
+    let config = Config::load_for_instance(instance_dir).map_err(|error| {
+        TelegramModerationError(format!(
+            "failed to resolve config for {}: {error}",
+            instance_dir.display()
+        ))
+    })?;
+
+    let telegram = config
+        .messaging
+        .telegram
+        .clone()
+        .ok_or_else(|| TelegramModerationError("telegram adapter is not configured".to_string()))?;
+
+    if telegram.token.trim().is_empty() {
+        return Err(TelegramModerationError(
+            "telegram adapter has no bot token configured".to_string(),
+        ));
+    }
+
+    let permissions = TelegramPermissions::from_config(&telegram, &config.bindings);
+    Ok((telegram, permissions))
+}
@@ -0,0 +1,219 @@
+//! Translation memory tools for branches.
+//!
+//! `translation_lookup` checks the cache before the agent spends a turn
+//! re-translating a phrase it has already handled; `translation_save` records
+//! new translations so future lookups hit. Entries expire after the TTL
+//! configured in `[defaults.translation_memory]`.
+
+use crate::memory::TranslationMemory;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum source text length accepted into the cache. Long passages are
+/// unlikely to recur verbatim and would bloat the table.
+const MAX_SOURCE_TEXT_BYTES: usize = 10_000;
+
+/// Error type for the translation memory tools.
+#[derive(Debug, thiserror::Error)]
+#[error("Translation memory failed: {0}")]
+pub struct TranslationMemoryError(String);
+
+impl From<crate::error::Error> for TranslationMemoryError {
+    fn from(e: crate::error::Error) -> Self {
+        TranslationMemoryError(format!("{e}"))
+    }
+}
+
+/// Tool for checking the translation cache before translating.
+#[derive(Debug, Clone)]
+pub struct TranslationLookupTool {
+    memory: TranslationMemory,
+    ttl_secs: u64,
+}
+
+impl TranslationLookupTool {
+    pub fn new(memory: TranslationMemory, ttl_secs: u64) -> Self {
+        Self { memory, ttl_secs }
+    }
+}
+
+/// Arguments for translation lookup.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslationLookupArgs {
+    /// ISO language code of the source text (e.g. "en").
+    pub source_lang: String,
+    /// ISO language code to translate into (e.g. "de").
+    pub target_lang: String,
+    /// The text to look up.
+    pub text: String,
+}
+
+/// Output from translation lookup.
+#[derive(Debug, Serialize)]
+pub struct TranslationLookupOutput {
+    /// Whether a cached translation was found.
+    pub hit: bool,
+    /// The cached translation, when present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translated_text: Option<String>,
+    /// How many times this entry has been reused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits: Option<i64>,
+}
+
+impl Tool for TranslationLookupTool {
+    const NAME: &'static str = "translation_lookup";
+
+    type Error = TranslationMemoryError;
+    type Args = TranslationLookupArgs;
+    type Output = TranslationLookupOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/translation_lookup").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "source_lang": {
+                        "type": "string",
+                        "description": "ISO language code of the source text, e.g. 'en'"
+                    },
+                    "target_lang": {
+                        "type": "string",
+                        "description": "ISO language code to translate into, e.g. 'de'"
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "The text to look up in the translation cache"
+                    }
+                },
+                "required": ["source_lang", "target_lang", "text"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        if args.text.trim().is_empty() {
+            return Err(TranslationMemoryError("text must not be empty".into()));
+        }
+
+        let cached = self
+            .memory
+            .lookup(&args.source_lang, &args.target_lang, &args.text, self.ttl_secs)
+            .await?;
+
+        Ok(match cached {
+            Some(cached) => TranslationLookupOutput {
+                hit: true,
+                translated_text: Some(cached.translated_text),
+                hits: Some(cached.hits),
+            },
+            None => TranslationLookupOutput {
+                hit: false,
+                translated_text: None,
+                hits: None,
+            },
+        })
+    }
+}
+
+/// Tool for saving a completed translation into the cache.
+#[derive(Debug, Clone)]
+pub struct TranslationSaveTool {
+    memory: TranslationMemory,
+}
+
+impl TranslationSaveTool {
+    pub fn new(memory: TranslationMemory) -> Self {
+        Self { memory }
+    }
+}
+
+/// Arguments for translation save.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslationSaveArgs {
+    /// ISO language code of the source text (e.g. "en").
+    pub source_lang: String,
+    /// ISO language code of the translation (e.g. "de").
+    pub target_lang: String,
+    /// The original text.
+    pub source_text: String,
+    /// The translated text to cache.
+    pub translated_text: String,
+}
+
+/// Output from translation save.
+#[derive(Debug, Serialize)]
+pub struct TranslationSaveOutput {
+    pub success: bool,
+    pub message: String,
+}
+
+impl Tool for TranslationSaveTool {
+    const NAME: &'static str = "translation_save";
+
+    type Error = TranslationMemoryError;
+    type Args = TranslationSaveArgs;
+    type Output = TranslationSaveOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/translation_save").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "source_lang": {
+                        "type": "string",
+                        "description": "ISO language code of the source text, e.g. 'en'"
+                    },
+                    "target_lang": {
+                        "type": "string",
+                        "description": "ISO language code of the translation, e.g. 'de'"
+                    },
+                    "source_text": {
+                        "type": "string",
+                        "description": "The original text"
+                    },
+                    "translated_text": {
+                        "type": "string",
+                        "description": "The translated text to cache for reuse"
+                    }
+                },
+                "required": ["source_lang", "target_lang", "source_text", "translated_text"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        if args.source_text.trim().is_empty() || args.translated_text.trim().is_empty() {
+            return Err(TranslationMemoryError(
+                "source_text and translated_text must not be empty".into(),
+            ));
+        }
+
+        if args.source_text.len() > MAX_SOURCE_TEXT_BYTES {
+            return Err(TranslationMemoryError(format!(
+                "source_text exceeds maximum length of {MAX_SOURCE_TEXT_BYTES} bytes (got {})",
+                args.source_text.len()
+            )));
+        }
+
+        self.memory
+            .store(
+                &args.source_lang,
+                &args.target_lang,
+                &args.source_text,
+                &args.translated_text,
+            )
+            .await?;
+
+        Ok(TranslationSaveOutput {
+            success: true,
+            message: "Translation cached".to_string(),
+        })
+    }
+}
@@ -12,11 +12,13 @@ const BRAVE_WEB_SEARCH_URL: &str = "https://api.search.brave.com/res/v1/web/sear
 pub struct WebSearchTool {
     client: reqwest::Client,
     api_key: String,
+    /// Channel to record result URLs against for reply citations.
+    channel_id: Option<crate::ChannelId>,
 }
 
 impl WebSearchTool {
     pub fn new(api_key: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
+        let client = crate::http::builder()
             .gzip(true)
             .build()
             .expect("hardcoded reqwest client config");
@@ -24,8 +26,16 @@ impl WebSearchTool {
         Self {
             client,
             api_key: api_key.into(),
+            channel_id: None,
         }
     }
+
+    /// Attribute search results to a channel so the reply tool can cite
+    /// them as sources.
+    pub fn with_channel_id(mut self, channel_id: Option<crate::ChannelId>) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
 }
 
 /// Error type for web search tool.
@@ -217,6 +227,17 @@ impl Tool for WebSearchTool {
 
         let result_count = results.len();
 
+        if let Some(channel_id) = &self.channel_id {
+            for result in &results {
+                crate::citations::record(
+                    channel_id,
+                    "web",
+                    result.title.clone(),
+                    Some(result.url.clone()),
+                );
+            }
+        }
+
         Ok(WebSearchOutput {
             results,
             query: args.query,
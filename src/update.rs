@@ -207,7 +207,7 @@ async fn fetch_latest_release() -> anyhow::Result<GitHubRelease> {
         GITHUB_REPO
     );
 
-    let client = reqwest::Client::builder()
+    let client = crate::http::builder()
         .user_agent(format!("spacebot/{}", CURRENT_VERSION))
         .timeout(Duration::from_secs(15))
         .build()?;
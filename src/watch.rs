@@ -0,0 +1,264 @@
+//! Keyword watch subscriptions across messaging adapters.
+//!
+//! Users subscribe with chat commands — `!watch add release 2.0`,
+//! `!watch remove release 2.0`, `!watch list` — and the inbound loop sends
+//! them a direct message (through their adapter's broadcast target) whenever
+//! someone else mentions a watched phrase in that channel. Subscriptions are
+//! stored per user in `keyword_watches.json` in the instance directory.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// File in the instance directory holding all subscriptions.
+const WATCH_FILE: &str = "keyword_watches.json";
+
+/// One keyword subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeywordWatch {
+    /// Platform sender id of the subscriber; doubles as the DM broadcast target.
+    pub user_id: String,
+    /// Adapter runtime key the subscription was created from.
+    pub adapter: String,
+    /// Conversation the keyword applies to.
+    pub conversation_id: String,
+    /// Phrase matched case-insensitively against inbound message text.
+    pub keyword: String,
+}
+
+/// Keyword subscriptions shared between the inbound loop and command handling.
+#[derive(Clone)]
+pub struct KeywordWatchStore {
+    path: PathBuf,
+    watches: Arc<RwLock<Vec<KeywordWatch>>>,
+}
+
+impl KeywordWatchStore {
+    /// Load subscriptions from the instance directory, starting empty when the
+    /// file doesn't exist yet.
+    pub fn load(instance_dir: &Path) -> Self {
+        let path = instance_dir.join(WATCH_FILE);
+        let watches = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            watches: Arc::new(RwLock::new(watches)),
+        }
+    }
+
+    /// Handle a `!watch` command, returning the reply text when the message
+    /// was a command (the caller responds and skips agent routing).
+    pub async fn handle_command(&self, message: &crate::InboundMessage) -> Option<String> {
+        let text = message.content.to_string();
+        let rest = text.trim().strip_prefix("!watch")?.trim().to_string();
+
+        let user_id = message.sender_id.clone();
+        let conversation_id = message.conversation_id.clone();
+
+        if rest == "list" {
+            let watches = self.watches.read().await;
+            let mine: Vec<&KeywordWatch> = watches
+                .iter()
+                .filter(|watch| watch.user_id == user_id)
+                .collect();
+            if mine.is_empty() {
+                return Some("You have no keyword watches.".to_string());
+            }
+            let lines: Vec<String> = mine
+                .iter()
+                .map(|watch| {
+                    let scope = if watch.conversation_id == conversation_id {
+                        "this channel".to_string()
+                    } else {
+                        watch.conversation_id.clone()
+                    };
+                    format!("- \"{}\" in {}", watch.keyword, scope)
+                })
+                .collect();
+            return Some(format!("Your keyword watches:\n{}", lines.join("\n")));
+        }
+
+        if let Some(keyword) = rest.strip_prefix("add ") {
+            let keyword = keyword.trim().trim_matches('"').trim().to_string();
+            if keyword.is_empty() {
+                return Some("Usage: !watch add <keyword>".to_string());
+            }
+            let watch = KeywordWatch {
+                user_id,
+                adapter: message.adapter_key().to_string(),
+                conversation_id,
+                keyword: keyword.clone(),
+            };
+            let mut watches = self.watches.write().await;
+            if watches.contains(&watch) {
+                return Some(format!("You're already watching \"{keyword}\" here."));
+            }
+            watches.push(watch);
+            self.save(&watches);
+            return Some(format!(
+                "Watching \"{keyword}\" in this channel; you'll get a DM when it's mentioned."
+            ));
+        }
+
+        if let Some(keyword) = rest.strip_prefix("remove ") {
+            let keyword = keyword.trim().trim_matches('"').trim().to_ascii_lowercase();
+            let mut watches = self.watches.write().await;
+            let before = watches.len();
+            watches.retain(|watch| {
+                !(watch.user_id == user_id
+                    && watch.conversation_id == conversation_id
+                    && watch.keyword.to_ascii_lowercase() == keyword)
+            });
+            if watches.len() == before {
+                return Some(format!("No watch for \"{keyword}\" in this channel."));
+            }
+            self.save(&watches);
+            return Some(format!("Stopped watching \"{keyword}\" in this channel."));
+        }
+
+        Some("Usage: !watch add <keyword> | !watch remove <keyword> | !watch list".to_string())
+    }
+
+    /// Subscriptions triggered by this message: same channel, keyword present,
+    /// and not the subscriber's own message.
+    pub async fn matches(&self, message: &crate::InboundMessage) -> Vec<KeywordWatch> {
+        let text = message.content.to_string().to_ascii_lowercase();
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let watches = self.watches.read().await;
+        watches
+            .iter()
+            .filter(|watch| {
+                watch.conversation_id == message.conversation_id
+                    && watch.user_id != message.sender_id
+                    && text.contains(&watch.keyword.to_ascii_lowercase())
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn save(&self, watches: &[KeywordWatch]) {
+        match serde_json::to_string_pretty(watches) {
+            Ok(contents) => {
+                if let Err(error) = std::fs::write(&self.path, contents) {
+                    tracing::warn!(%error, path = %self.path.display(), "failed to save keyword watches");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(%error, "failed to serialize keyword watches");
+            }
+        }
+    }
+}
+
+/// Notification text sent to a subscriber when their keyword is mentioned.
+pub fn format_watch_notice(watch: &KeywordWatch, message: &crate::InboundMessage) -> String {
+    let author = message
+        .formatted_author
+        .clone()
+        .unwrap_or_else(|| message.sender_id.clone());
+    let text = message.content.to_string();
+    let excerpt: String = text.chars().take(200).collect();
+    let ellipsis = if text.chars().count() > 200 { "…" } else { "" };
+    format!(
+        "🔔 \"{}\" was mentioned in {} by {}:\n> {excerpt}{ellipsis}",
+        watch.keyword, message.conversation_id, author
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeywordWatchStore, format_watch_notice};
+    use crate::{InboundMessage, MessageContent};
+
+    fn message(sender: &str, conversation: &str, text: &str) -> InboundMessage {
+        InboundMessage {
+            id: "mid".into(),
+            source: "telegram".into(),
+            adapter: None,
+            conversation_id: conversation.into(),
+            sender_id: sender.into(),
+            agent_id: None,
+            content: MessageContent::Text(text.into()),
+            timestamp: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            formatted_author: None,
+        }
+    }
+
+    fn temp_store() -> KeywordWatchStore {
+        let dir = std::env::temp_dir().join(format!("spacebot-watch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        KeywordWatchStore::load(&dir)
+    }
+
+    #[tokio::test]
+    async fn add_then_match_notifies_other_users_only() {
+        let store = temp_store();
+        let reply = store
+            .handle_command(&message("alice", "telegram:1:2", "!watch add release 2.0"))
+            .await
+            .expect("command handled");
+        assert!(reply.contains("release 2.0"));
+
+        // Alice's own mention doesn't trigger her watch.
+        let own = store
+            .matches(&message("alice", "telegram:1:2", "shipping Release 2.0 soon"))
+            .await;
+        assert!(own.is_empty());
+
+        let hits = store
+            .matches(&message("bob", "telegram:1:2", "shipping Release 2.0 soon"))
+            .await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].user_id, "alice");
+
+        // Different channel doesn't match.
+        let elsewhere = store
+            .matches(&message("bob", "telegram:9:9", "release 2.0"))
+            .await;
+        assert!(elsewhere.is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_and_list_round_trip() {
+        let store = temp_store();
+        store
+            .handle_command(&message("alice", "c1", "!watch add deploy"))
+            .await
+            .unwrap();
+        let listing = store
+            .handle_command(&message("alice", "c1", "!watch list"))
+            .await
+            .unwrap();
+        assert!(listing.contains("deploy"));
+
+        store
+            .handle_command(&message("alice", "c1", "!watch remove deploy"))
+            .await
+            .unwrap();
+        let listing = store
+            .handle_command(&message("alice", "c1", "!watch list"))
+            .await
+            .unwrap();
+        assert!(listing.contains("no keyword watches"));
+    }
+
+    #[test]
+    fn notice_includes_keyword_and_excerpt() {
+        let store_message = message("bob", "c1", "the release 2.0 branch is cut");
+        let watch = super::KeywordWatch {
+            user_id: "alice".into(),
+            adapter: "telegram".into(),
+            conversation_id: "c1".into(),
+            keyword: "release 2.0".into(),
+        };
+        let notice = format_watch_notice(&watch, &store_message);
+        assert!(notice.contains("release 2.0"));
+        assert!(notice.contains("branch is cut"));
+    }
+}
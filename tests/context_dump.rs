@@ -207,6 +207,11 @@ async fn dump_channel_context() {
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        pinned_context: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+        binding_pins: Arc::new(tokio::sync::RwLock::new(None)),
+        last_system_prompt: Arc::new(tokio::sync::RwLock::new(String::new())),
+        model_override: Arc::new(tokio::sync::RwLock::new(None)),
+        allowed_regions: Arc::new(tokio::sync::RwLock::new(Vec::new())),
     };
 
     let tool_server = rig::tool::server::ToolServer::new().run();
@@ -283,6 +288,7 @@ async fn dump_branch_context() {
         conversation_logger,
         channel_store,
         run_logger,
+        deps.sqlite_pool.clone(),
     );
 
     let tool_defs = branch_tool_server
@@ -428,6 +434,11 @@ async fn dump_all_contexts() {
         screenshot_dir: std::path::PathBuf::from("/tmp/screenshots"),
         logs_dir: std::path::PathBuf::from("/tmp/logs"),
         reply_target_message_id: Arc::new(tokio::sync::RwLock::new(None)),
+        pinned_context: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+        binding_pins: Arc::new(tokio::sync::RwLock::new(None)),
+        last_system_prompt: Arc::new(tokio::sync::RwLock::new(String::new())),
+        model_override: Arc::new(tokio::sync::RwLock::new(None)),
+        allowed_regions: Arc::new(tokio::sync::RwLock::new(Vec::new())),
     };
     let channel_tool_server = rig::tool::server::ToolServer::new().run();
     let skip_flag = spacebot::tools::new_skip_flag();
@@ -472,6 +483,7 @@ async fn dump_all_contexts() {
         conversation_logger,
         channel_store,
         run_logger,
+        deps.sqlite_pool.clone(),
     );
     let branch_tool_defs = branch_tool_server.get_tool_defs(None).await.unwrap();
     let branch_tools_text = format_tool_defs(&branch_tool_defs);